@@ -0,0 +1,135 @@
+//! Regenerate Bao code from a cargo build script.
+//!
+//! Projects that keep generated code out of git call this crate from
+//! `build.rs`, and cargo regenerates it whenever `bao.toml` changes:
+//!
+//! ```no_run
+//! // build.rs
+//! fn main() -> eyre::Result<()> {
+//!     baobao_build::regenerate_if_stale("bao.toml")?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Relative manifest paths are resolved against the package directory, the
+//! working directory of build scripts.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use baobao::{GenerateOptions, GenerationReport, generate, is_up_to_date};
+use baobao_manifest::BaoToml;
+use eyre::{Result, eyre};
+
+/// Subdirectory of `OUT_DIR` that [`generate_in_out_dir`] generates into.
+pub const OUT_SUBDIR: &str = "bao";
+
+/// Regenerate the targets of the manifest next to it, unless they are up to
+/// date. Returns whether code was generated.
+///
+/// Generated files are compared with what the manifest produces, so a fresh
+/// checkout without them is regenerated too. Handler stubs are only created,
+/// never overwritten.
+pub fn regenerate_if_stale(manifest_path: impl AsRef<Path>) -> Result<bool> {
+    let manifest_path = manifest_path.as_ref();
+    rerun_if_changed(manifest_path)?;
+
+    let options = GenerateOptions::default();
+    if is_up_to_date(manifest_path, &options)? {
+        return Ok(false);
+    }
+    let report = generate(manifest_path, &options)?;
+    emit_warnings(&report);
+    Ok(true)
+}
+
+/// Generate the targets of the manifest into `$OUT_DIR/bao`, and return that
+/// directory.
+///
+/// The source tree is left untouched. The directory is also exported to the
+/// crate being built as the `BAO_OUT_DIR` environment variable.
+pub fn generate_in_out_dir(manifest_path: impl AsRef<Path>) -> Result<PathBuf> {
+    let manifest_path = manifest_path.as_ref();
+    let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+        eyre!("OUT_DIR is not set; generate_in_out_dir must run in a build script")
+    })?;
+    let output_dir = PathBuf::from(out_dir).join(OUT_SUBDIR);
+    rerun_if_changed(manifest_path)?;
+
+    let report = generate(
+        manifest_path,
+        &GenerateOptions {
+            output_dir: Some(output_dir.clone()),
+            ..Default::default()
+        },
+    )?;
+    emit_warnings(&report);
+    println!("cargo:rustc-env=BAO_OUT_DIR={}", output_dir.display());
+    Ok(output_dir)
+}
+
+/// Tell cargo to rerun the build script when the manifest or a file it reads changes.
+fn rerun_if_changed(manifest_path: &Path) -> Result<()> {
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+
+    let bao_toml = BaoToml::open(manifest_path)?;
+    let manifest = bao_toml.schema();
+    let dir = manifest_path.parent().unwrap_or(Path::new(""));
+    if let Some(extends) = &manifest.extends {
+        let base = dir.join(extends.trim_start_matches("file://"));
+        println!("cargo:rerun-if-changed={}", base.display());
+    }
+    if let Some(header_file) = &manifest.generation.header_file {
+        println!("cargo:rerun-if-changed={}", dir.join(header_file).display());
+    }
+    Ok(())
+}
+
+/// Surface generation warnings in cargo's output.
+fn emit_warnings(report: &GenerationReport) {
+    for target in &report.targets {
+        for warning in &target.warnings {
+            println!("cargo:warning={}", warning);
+        }
+        for path in &target.overwritten_edits {
+            println!(
+                "cargo:warning={} was edited by hand and has been regenerated",
+                path
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_regenerate_if_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("bao.toml");
+        fs::write(
+            &manifest,
+            r#"
+            [cli]
+            name = "app"
+            language = "go"
+
+            [commands.hello]
+            description = "Say hello"
+            "#,
+        )
+        .unwrap();
+
+        assert!(regenerate_if_stale(&manifest).unwrap());
+        assert!(dir.path().join("cmd/root.go").exists());
+        assert!(!regenerate_if_stale(&manifest).unwrap());
+
+        fs::remove_dir_all(dir.path().join("cmd")).unwrap();
+        assert!(regenerate_if_stale(&manifest).unwrap());
+    }
+}
@@ -0,0 +1,89 @@
+//! internal/generated/args.go generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, to_pascal_case};
+use baobao_ir::CommandOp;
+
+use super::command_go::{field_type, resolved_inputs};
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile, align_fields},
+    naming::command_ident,
+};
+
+/// Typed input structs passed from cobra commands to handlers, one per leaf command.
+pub struct ArgsGo {
+    pub name: String,
+    /// Top-level commands; leaves are collected recursively.
+    pub commands: Vec<CommandOp>,
+}
+
+impl ArgsGo {
+    pub fn new(name: impl Into<String>, commands: Vec<CommandOp>) -> Self {
+        Self {
+            name: name.into(),
+            commands,
+        }
+    }
+
+    fn collect(&self, cmd: &CommandOp, ancestors: &mut Vec<CommandOp>, blocks: &mut Vec<String>) {
+        if cmd.has_subcommands() {
+            ancestors.push(cmd.clone());
+            for child in &cmd.children {
+                self.collect(child, ancestors, blocks);
+            }
+            ancestors.pop();
+            return;
+        }
+
+        let ident = command_ident(&cmd.path);
+        let fields: Vec<(String, String)> = resolved_inputs(cmd, ancestors)
+            .iter()
+            .map(|r| (to_pascal_case(&r.input.name), field_type(r.input)))
+            .collect();
+
+        let doc = format!(
+            "// {}Args holds the inputs of `{} {}`.",
+            ident,
+            self.name,
+            cmd.path.join(" ")
+        );
+        if fields.is_empty() {
+            blocks.push(format!("{}\ntype {}Args struct{{}}", doc, ident));
+        } else {
+            blocks.push(format!(
+                "{}\ntype {}Args struct {{\n{}\n}}",
+                doc,
+                ident,
+                align_fields(&fields, "\t").join("\n")
+            ));
+        }
+    }
+}
+
+impl GeneratedFile for ArgsGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("internal").join("generated").join("args.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut blocks = Vec::new();
+        for cmd in &self.commands {
+            self.collect(cmd, &mut Vec::new(), &mut blocks);
+        }
+
+        blocks
+            .into_iter()
+            .fold(
+                GoFile::new("generated")
+                    .generated()
+                    .doc("Package generated holds the typed inputs passed to command handlers."),
+                |file, block| file.add(block),
+            )
+            .render()
+    }
+}
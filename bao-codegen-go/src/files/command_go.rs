@@ -0,0 +1,917 @@
+//! cmd/<command>.go generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::{adapters::input_type_to_arg_type, language::TypeMapper};
+use baobao_core::{FileRules, GeneratedFile, to_env_var, to_kebab_case, to_pascal_case};
+use baobao_ir::{CommandOp, DefaultValue, Input, InputKind, InputType, NO_INPUT_FLAG};
+
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile, align_entries},
+    naming::{command_ident, command_var, file_stem, flag_name, go_string, viper_key},
+    type_mapper::{GO_TYPES, GoTypeMapper},
+};
+
+/// `RunE` of a command with subcommands that runs the one at `path` when none
+/// is given. Its flags are never parsed, so viper reads their defaults.
+pub(crate) fn default_run_e(path: &[String]) -> Vec<String> {
+    let var = command_var(path);
+    vec![
+        "\tRunE: func(cmd *cobra.Command, args []string) error {".to_string(),
+        format!("\t\treturn {}.RunE({}, args)", var, var),
+        "\t},".to_string(),
+    ]
+}
+
+/// An input as seen by a leaf command, together with the path of the command declaring it.
+///
+/// Flags declared on parent commands are persistent and reach every descendant.
+pub(crate) struct ResolvedInput<'a> {
+    pub declared_by: &'a [String],
+    pub input: &'a Input,
+}
+
+/// Inputs of a leaf command: inherited parent flags (outermost first), then its own
+/// inputs. A flag redeclared closer to the leaf shadows the inherited one.
+pub(crate) fn resolved_inputs<'a>(
+    command: &'a CommandOp,
+    ancestors: &'a [CommandOp],
+) -> Vec<ResolvedInput<'a>> {
+    let mut resolved: Vec<ResolvedInput<'a>> = Vec::new();
+    for cmd in ancestors.iter().chain(std::iter::once(command)) {
+        let is_leaf = std::ptr::eq(cmd, command);
+        for input in &cmd.inputs {
+            if !is_leaf && !matches!(input.kind, InputKind::Flag { .. }) {
+                continue;
+            }
+            resolved.retain(|r| r.input.name != input.name);
+            resolved.push(ResolvedInput {
+                declared_by: &cmd.path,
+                input,
+            });
+        }
+    }
+    resolved
+}
+
+/// Go type of an input field, a slice when it takes multiple values.
+pub(crate) fn field_type(input: &Input) -> String {
+    let ty = GoTypeMapper.map_arg_type(input_type_to_arg_type(input.ty));
+    if input.multiple {
+        format!("[]{}", ty)
+    } else {
+        ty.to_string()
+    }
+}
+
+/// A cobra command definition for a single manifest command.
+pub struct CommandGo {
+    /// Go module path of the generated project.
+    pub module: String,
+    pub command: CommandOp,
+    /// Ancestor commands, outermost first.
+    pub ancestors: Vec<CommandOp>,
+    /// Whether global options are read into the context.
+    pub globals: bool,
+    /// CLI name, prefixing the environment variables of secrets.
+    pub cli: String,
+}
+
+impl CommandGo {
+    pub fn new(module: impl Into<String>, command: CommandOp, ancestors: Vec<CommandOp>) -> Self {
+        Self {
+            module: module.into(),
+            command,
+            ancestors,
+            globals: false,
+            cli: String::new(),
+        }
+    }
+
+    /// Read global options into `appCtx.Globals` before calling the handler.
+    pub fn with_globals(mut self, globals: bool) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    /// Set the CLI name prefixing the environment variables of secrets.
+    pub fn with_cli(mut self, cli: impl Into<String>) -> Self {
+        self.cli = cli.into();
+        self
+    }
+
+    fn positionals(&self) -> Vec<&Input> {
+        self.command
+            .inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Positional))
+            .collect()
+    }
+
+    fn flags(&self) -> Vec<&Input> {
+        self.command
+            .inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Flag { .. }))
+            .collect()
+    }
+
+    fn parent_var(&self) -> String {
+        self.ancestors
+            .last()
+            .map(|parent| command_var(&parent.path))
+            .unwrap_or_else(|| "rootCmd".to_string())
+    }
+
+    /// The `Use:` line, listing positionals as `<required>` or `[optional]`,
+    /// followed by `...` when they take multiple values.
+    fn usage(&self) -> String {
+        let mut usage = self.command.name.clone();
+        if !self.command.has_subcommands() {
+            for input in self.positionals() {
+                let repeat = if input.multiple { "..." } else { "" };
+                if input.required {
+                    usage.push_str(&format!(" <{}>{}", input.name, repeat));
+                } else {
+                    usage.push_str(&format!(" [{}]{}", input.name, repeat));
+                }
+            }
+        }
+        usage
+    }
+
+    /// The `Args:` validator, accepting up to the last required positional,
+    /// or any number past it when the last positional takes multiple values.
+    fn args_validator(&self) -> String {
+        let positionals = self.positionals();
+        let min = positionals
+            .iter()
+            .rposition(|i| i.required)
+            .map_or(0, |idx| idx + 1);
+        let variadic = positionals.last().is_some_and(|i| i.multiple);
+        match (min, positionals.len()) {
+            (_, 0) => "cobra.NoArgs".to_string(),
+            (0, _) if variadic => "cobra.ArbitraryArgs".to_string(),
+            (min, _) if variadic => format!("cobra.MinimumNArgs({})", min),
+            (min, max) if min == max => format!("cobra.ExactArgs({})", max),
+            (min, max) => format!("cobra.RangeArgs({}, {})", min, max),
+        }
+    }
+
+    fn declaration(&self) -> String {
+        let cmd = &self.command;
+        let mut entries = vec![("Use".to_string(), go_string(&self.usage()))];
+        if !cmd.aliases.is_empty() {
+            let aliases: Vec<String> = cmd.aliases.iter().map(|a| go_string(a)).collect();
+            entries.push((
+                "Aliases".to_string(),
+                format!("[]string{{{}}}", aliases.join(", ")),
+            ));
+        }
+        if !cmd.description.is_empty() {
+            entries.push(("Short".to_string(), go_string(&cmd.description)));
+        }
+        // cobra prints `Long` instead of `Short`, so it repeats the summary
+        if let Some(long) = cmd
+            .docs
+            .long_description
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+        {
+            let text = if cmd.description.is_empty() {
+                long.to_string()
+            } else {
+                format!("{}\n\n{}", cmd.description, long)
+            };
+            entries.push(("Long".to_string(), go_string(&text)));
+        }
+        if !cmd.docs.examples.is_empty() {
+            let examples: Vec<String> = cmd
+                .docs
+                .examples
+                .iter()
+                .map(|e| format!("  {}", e.command))
+                .collect();
+            entries.push(("Example".to_string(), go_string(&examples.join("\n"))));
+        }
+
+        let mut lines = vec![format!("var {} = &cobra.Command{{", command_var(&cmd.path))];
+        if let Some(name) = &cmd.default_command {
+            entries.push(("Args".to_string(), "cobra.NoArgs".to_string()));
+            lines.extend(align_entries(&entries, "\t"));
+            let mut path = cmd.path.clone();
+            path.push(name.clone());
+            lines.extend(default_run_e(&path));
+        } else if cmd.has_subcommands() {
+            lines.extend(align_entries(&entries, "\t"));
+        } else {
+            entries.push(("Args".to_string(), self.args_validator()));
+            lines.extend(align_entries(&entries, "\t"));
+            lines.push("\tRunE: func(cmd *cobra.Command, args []string) error {".to_string());
+            lines.extend(self.run_body().into_iter().map(|line| {
+                if line.is_empty() {
+                    line
+                } else {
+                    format!("\t\t{}", line)
+                }
+            }));
+            lines.push("\t},".to_string());
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Body of `RunE`: collect inputs, validate choices, build the context and call the handler.
+    fn run_body(&self) -> Vec<String> {
+        let ident = command_ident(&self.command.path);
+        let resolved = resolved_inputs(&self.command, &self.ancestors);
+        let mut lines = self.deprecation_lines();
+
+        // Flags are read through viper so environment variables apply
+        let mut entries = Vec::new();
+        let mut lists = Vec::new();
+        for r in &resolved {
+            let field = to_pascal_case(&r.input.name);
+            match r.input.kind {
+                InputKind::Flag { .. } => {
+                    let value = format!(
+                        "viper.{}({})",
+                        viper_getter(r.input),
+                        go_string(&viper_key(r.declared_by, &r.input.name))
+                    );
+                    if r.input.multiple && needs_parse(r.input.ty) {
+                        lists.extend(parse_list(r.input, &format!("input.{}", field), &value));
+                    } else {
+                        entries.push((field, value));
+                    }
+                }
+                InputKind::Positional => {
+                    if let Some(default) = &r.input.default {
+                        let value = if r.input.multiple {
+                            slice_default(r.input.ty, default)
+                        } else {
+                            default_literal(r.input.ty, Some(default))
+                        };
+                        entries.push((field, value));
+                    }
+                }
+            }
+        }
+        if entries.is_empty() {
+            lines.push(format!("input := generated.{}Args{{}}", ident));
+        } else {
+            lines.push(format!("input := generated.{}Args{{", ident));
+            lines.extend(align_entries(&entries, "\t"));
+            lines.push("}".to_string());
+        }
+        lines.extend(lists);
+
+        // Positionals are parsed from cobra's args slice
+        let positionals = self.positionals();
+        if positionals.iter().any(|i| !i.multiple && needs_parse(i.ty)) {
+            lines.push("var err error".to_string());
+        }
+        for (idx, input) in positionals.iter().enumerate() {
+            let field = to_pascal_case(&input.name);
+            let source = format!("{}, args[{}]", go_string(&format!("<{}>", input.name)), idx);
+            let assign = match GO_TYPES.parse_expr(input.ty.into(), &source) {
+                _ if input.multiple => parse_list(
+                    input,
+                    &format!("input.{}", field),
+                    &match idx {
+                        0 => "args".to_string(),
+                        idx => format!("args[{}:]", idx),
+                    },
+                ),
+                None => vec![format!("input.{} = args[{}]", field, idx)],
+                Some(parse) => vec![
+                    format!("if input.{}, err = {}; err != nil {{", field, parse),
+                    "\treturn err".to_string(),
+                    "}".to_string(),
+                ],
+            };
+            if input.required {
+                lines.extend(assign);
+            } else {
+                lines.push(format!("if len(args) > {} {{", idx));
+                lines.extend(assign.into_iter().map(|line| format!("\t{}", line)));
+                lines.push("}".to_string());
+            }
+        }
+
+        // Missing arguments are asked for, then secrets not given are read
+        // from the environment or prompted for
+        for input in positionals.iter().filter(|i| i.prompt_message().is_some()) {
+            lines.extend(ask_lines(input));
+        }
+        for r in resolved.iter().filter(|r| r.input.ty == InputType::Secret) {
+            lines.extend(self.secret_lines(r.input));
+        }
+
+        // Choices
+        for r in &resolved {
+            lines.extend(choice_check(r.input, "input"));
+        }
+
+        // Groups, counting the members given on the command line or through
+        // the environment
+        for group in &self.command.groups {
+            let members: Vec<&Input> = group
+                .members
+                .iter()
+                .filter_map(|m| self.command.inputs.iter().find(|i| &i.name == m))
+                .collect();
+            let names: Vec<String> = members
+                .iter()
+                .map(|input| go_string(&display_name(input)))
+                .collect();
+            let given: Vec<String> = members
+                .iter()
+                .map(|input| self.given(input, &positionals))
+                .collect();
+            let checks = [
+                ("exclusive", group.exclusive),
+                ("oneRequired", group.required),
+            ];
+            for (helper, _) in checks.iter().filter(|(_, on)| *on) {
+                lines.push(format!(
+                    "if err := {}([]string{{{}}}, {}); err != nil {{",
+                    helper,
+                    names.join(", "),
+                    given.join(", ")
+                ));
+                lines.push("\treturn err".to_string());
+                lines.push("}".to_string());
+            }
+        }
+
+        // Requirements between the command's own inputs
+        for input in &self.command.inputs {
+            let find = |name: &str| self.command.inputs.iter().find(|i| i.name == name);
+            for other in input.requires.iter().filter_map(|name| find(name)) {
+                lines.push(format!(
+                    "if {} && {} {{",
+                    self.given(input, &positionals),
+                    self.missing(other, &positionals)
+                ));
+                lines.push(format!(
+                    "\treturn errors.New({})",
+                    go_string(&format!(
+                        "{} requires {}",
+                        display_name(input),
+                        display_name(other)
+                    ))
+                ));
+                lines.push("}".to_string());
+            }
+            if let Some(required_if) = &input.required_if
+                && let Some(flag) = find(&required_if.flag)
+            {
+                lines.push(format!(
+                    "if input.{} == {} && {} {{",
+                    to_pascal_case(&flag.name),
+                    go_string(&required_if.value),
+                    self.missing(input, &positionals)
+                ));
+                lines.push(format!(
+                    "\treturn errors.New({})",
+                    go_string(&format!(
+                        "{} is required when {} is {}",
+                        display_name(input),
+                        display_name(flag),
+                        required_if.value
+                    ))
+                ));
+                lines.push("}".to_string());
+            }
+        }
+
+        // Constraints, checked only for values given on the command line
+        for r in &resolved {
+            let guard = match r.input.kind {
+                InputKind::Flag { .. } => Some(format!(
+                    "viper.IsSet({})",
+                    go_string(&viper_key(r.declared_by, &r.input.name))
+                )),
+                InputKind::Positional if !r.input.required => {
+                    Some(format!("len(args) > {}", index_of(r.input, &positionals)))
+                }
+                InputKind::Positional => None,
+            };
+            lines.extend(constraint_check(r.input, "input", guard.as_deref()));
+        }
+
+        lines.push(String::new());
+        lines.push("appCtx, err := app.New(cmd.Context())".to_string());
+        lines.push("if err != nil {".to_string());
+        lines.push("\treturn err".to_string());
+        lines.push("}".to_string());
+        lines.push("defer appCtx.Close()".to_string());
+        if self.globals {
+            lines.push("if err := readGlobals(&appCtx.Globals); err != nil {".to_string());
+            lines.push("\treturn err".to_string());
+            lines.push("}".to_string());
+        }
+        lines.push(String::new());
+        lines.push(format!("return handlers.{}(appCtx, input)", ident));
+        lines
+    }
+
+    /// Lines reading an empty secret without echoing it. Flags already read
+    /// their environment variable through viper; positionals read it here.
+    fn secret_lines(&self, input: &Input) -> Vec<String> {
+        let field = format!("input.{}", to_pascal_case(&input.name));
+        let label = input
+            .description
+            .clone()
+            .unwrap_or_else(|| to_kebab_case(&input.name));
+        let mut lines = Vec::new();
+        if matches!(input.kind, InputKind::Positional) {
+            lines.extend([
+                format!("if {} == \"\" {{", field),
+                format!(
+                    "\t{} = os.Getenv({})",
+                    field,
+                    go_string(&to_env_var(&self.cli, &self.command.path, &input.name))
+                ),
+                "}".to_string(),
+            ]);
+        }
+        lines.extend([
+            format!("if {} == \"\" {{", field),
+            format!(
+                "\tfmt.Fprint(os.Stderr, {})",
+                go_string(&format!("{}: ", label))
+            ),
+            "\tsecret, err := term.ReadPassword(int(os.Stdin.Fd()))".to_string(),
+            "\tfmt.Fprintln(os.Stderr)".to_string(),
+            "\tif err != nil {".to_string(),
+            "\t\treturn err".to_string(),
+            "\t}".to_string(),
+            format!("\t{} = string(secret)", field),
+            "}".to_string(),
+        ]);
+        lines
+    }
+
+    /// Lines that print a warning to stderr for each deprecated command on
+    /// the path to this one and each deprecated flag that is given.
+    fn deprecation_lines(&self) -> Vec<String> {
+        let path = self.ancestors.iter().chain([&self.command]);
+        let mut lines: Vec<String> = path
+            .filter_map(|cmd| {
+                let message = cmd.deprecated.as_ref()?;
+                Some(format!(
+                    "fmt.Fprintln(os.Stderr, {})",
+                    go_string(&format!(
+                        "warning: command '{}' is deprecated: {}",
+                        cmd.path.join(" "),
+                        message
+                    ))
+                ))
+            })
+            .collect();
+        for r in resolved_inputs(&self.command, &self.ancestors) {
+            let Some(message) = &r.input.deprecated else {
+                continue;
+            };
+            lines.push(format!(
+                "if viper.IsSet({}) {{",
+                go_string(&viper_key(r.declared_by, &r.input.name))
+            ));
+            lines.push(format!(
+                "\tfmt.Fprintln(os.Stderr, {})",
+                go_string(&format!(
+                    "warning: flag '{}' is deprecated: {}",
+                    display_name(r.input),
+                    message
+                ))
+            ));
+            lines.push("}".to_string());
+        }
+        lines
+    }
+
+    /// Expression that is true when one of the command's own inputs is given
+    /// on the command line or, for flags, through the environment.
+    fn given(&self, input: &Input, positionals: &[&Input]) -> String {
+        match input.kind {
+            InputKind::Flag { .. } => format!(
+                "viper.IsSet({})",
+                go_string(&viper_key(&self.command.path, &input.name))
+            ),
+            InputKind::Positional => format!("len(args) > {}", index_of(input, positionals)),
+        }
+    }
+
+    /// The negation of [`Self::given`].
+    fn missing(&self, input: &Input, positionals: &[&Input]) -> String {
+        match input.kind {
+            InputKind::Flag { .. } => format!("!{}", self.given(input, positionals)),
+            InputKind::Positional => format!("len(args) <= {}", index_of(input, positionals)),
+        }
+    }
+
+    /// The `init()` function registering flags and attaching the command to its parent.
+    fn init_fn(&self) -> String {
+        let cmd = &self.command;
+        let var = command_var(&cmd.path);
+        let flags = self.flags();
+        let mut lines = vec!["func init() {".to_string()];
+
+        if !flags.is_empty() {
+            // Parent flags are persistent so subcommands inherit them
+            let flag_set = if cmd.has_subcommands() {
+                "PersistentFlags"
+            } else {
+                "Flags"
+            };
+            lines.push(format!("\tflags := {}.{}()", var, flag_set));
+            for input in &flags {
+                lines.extend(register_flag(&cmd.path, input));
+            }
+            lines.push(String::new());
+        }
+
+        lines.push(format!("\t{}.AddCommand({})", self.parent_var(), var));
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GeneratedFile for CommandGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("cmd")
+            .join(format!("{}.go", file_stem(&self.command.path)))
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = GoFile::new("cmd")
+            .generated()
+            .import("github.com/spf13/cobra");
+
+        if !self.flags().is_empty() {
+            file = file.import("github.com/spf13/viper");
+        }
+
+        if !self.command.has_subcommands() {
+            let resolved = resolved_inputs(&self.command, &self.ancestors);
+            if resolved
+                .iter()
+                .any(|r| matches!(r.input.kind, InputKind::Flag { .. }))
+            {
+                file = file.import("github.com/spf13/viper");
+            }
+            if resolved
+                .iter()
+                .any(|r| r.input.choices.is_some() && !is_go_string(r.input.ty))
+            {
+                file = file.import("fmt");
+            }
+            if self
+                .command
+                .inputs
+                .iter()
+                .any(|i| !i.requires.is_empty() || i.required_if.is_some())
+            {
+                file = file.import("errors");
+            }
+            if !self.deprecation_lines().is_empty() {
+                file = file.import("fmt").import("os");
+            }
+            if self
+                .command
+                .inputs
+                .iter()
+                .any(|i| i.prompt_message().is_some())
+            {
+                file = file
+                    .import("bufio")
+                    .import("errors")
+                    .import("fmt")
+                    .import("os")
+                    .import("strings")
+                    .import("github.com/spf13/viper");
+            }
+            if resolved.iter().any(|r| r.input.ty == InputType::Secret) {
+                file = file.import("fmt").import("os").import("golang.org/x/term");
+            }
+            file = file
+                .local_import(format!("{}/handlers", self.module))
+                .local_import(format!("{}/internal/app", self.module))
+                .local_import(format!("{}/internal/generated", self.module));
+        }
+
+        file.add(self.declaration()).add(self.init_fn()).render()
+    }
+}
+
+/// Index of a positional in cobra's args slice.
+fn index_of(input: &Input, positionals: &[&Input]) -> usize {
+    positionals
+        .iter()
+        .position(|p| std::ptr::eq(*p, input))
+        .unwrap_or_default()
+}
+
+/// How an input is spelled in error messages: `--name` or `<name>`.
+fn display_name(input: &Input) -> String {
+    match input.kind {
+        InputKind::Flag { .. } => format!("--{}", flag_name(&input.name)),
+        InputKind::Positional => format!("<{}>", input.name),
+    }
+}
+
+/// Whether values of a type are parsed from strings.
+fn needs_parse(ty: InputType) -> bool {
+    GO_TYPES.parse_expr(ty.into(), "").is_some()
+}
+
+/// Lines setting `target` from the string slice `source`, parsing each item
+/// when the input's type needs it.
+pub(crate) fn parse_list(input: &Input, target: &str, source: &str) -> Vec<String> {
+    let item = format!("{}, item", go_string(&display_name(input)));
+    let Some(parse) = GO_TYPES.parse_expr(input.ty.into(), &item) else {
+        return vec![format!("{} = {}", target, source)];
+    };
+    vec![
+        format!("for _, item := range {} {{", source),
+        format!("\tvalue, err := {}", parse),
+        "\tif err != nil {".to_string(),
+        "\t\treturn err".to_string(),
+        "\t}".to_string(),
+        format!("\t{} = append({}, value)", target, target),
+        "}".to_string(),
+    ]
+}
+
+/// Lines returning an error unless the input's value on the struct `var` is
+/// one of its choices, checking each value of an input taking multiple
+/// values. Inputs without choices produce no lines.
+pub(crate) fn choice_check(input: &Input, var: &str) -> Vec<String> {
+    let Some(choices) = &input.choices else {
+        return Vec::new();
+    };
+    let display = display_name(input);
+    let field = format!("{}.{}", var, to_pascal_case(&input.name));
+    let item = if input.multiple {
+        "value".to_string()
+    } else {
+        field.clone()
+    };
+    let value = if is_go_string(input.ty) {
+        item
+    } else {
+        format!("fmt.Sprint({})", item)
+    };
+    let choices = choices
+        .iter()
+        .map(|c| go_string(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let check = vec![
+        format!(
+            "if err := oneOf({}, {}, {}); err != nil {{",
+            go_string(&display),
+            value,
+            choices
+        ),
+        "\treturn err".to_string(),
+        "}".to_string(),
+    ];
+    if !input.multiple {
+        return check;
+    }
+    let mut lines = vec![format!("for _, value := range {} {{", field)];
+    lines.extend(check.into_iter().map(|line| format!("\t{}", line)));
+    lines.push("}".to_string());
+    lines
+}
+
+/// Whether values of the type are Go strings.
+fn is_go_string(ty: InputType) -> bool {
+    GO_TYPES.type_name(ty.into()) == "string"
+}
+
+/// Helper in `cmd/root.go` checking the format of a string-backed type.
+pub(crate) fn format_helper(ty: InputType) -> Option<&'static str> {
+    match ty {
+        InputType::Url => Some("isURL"),
+        InputType::Duration => Some("isDuration"),
+        InputType::DateTime => Some("isDateTime"),
+        InputType::Uuid => Some("isUUID"),
+        _ => None,
+    }
+}
+
+/// Lines returning an error unless the input's value on the struct `var`
+/// satisfies its constraints, checking each value of an input taking
+/// multiple values. A single value is only checked when `guard` holds.
+pub(crate) fn constraint_check(input: &Input, var: &str, guard: Option<&str>) -> Vec<String> {
+    let constraints = &input.constraints;
+    let display = go_string(&display_name(input));
+    let field = format!("{}.{}", var, to_pascal_case(&input.name));
+    let value = if input.multiple {
+        "value".to_string()
+    } else {
+        field.clone()
+    };
+    let mut calls = Vec::new();
+    if let Some(helper) = format_helper(input.ty) {
+        calls.push(format!("{}({}, {})", helper, display, value));
+    }
+    let number = format!("float64({})", value);
+    if let Some(min) = constraints.min {
+        calls.push(format!("atLeast({}, {}, {})", display, number, min));
+    }
+    if let Some(max) = constraints.max {
+        calls.push(format!("atMost({}, {}, {})", display, number, max));
+    }
+    if let Some(min) = constraints.min_length {
+        calls.push(format!("minLength({}, {}, {})", display, value, min));
+    }
+    if let Some(max) = constraints.max_length {
+        calls.push(format!("maxLength({}, {}, {})", display, value, max));
+    }
+    if let Some(pattern) = &constraints.pattern {
+        calls.push(format!(
+            "matches({}, {}, {})",
+            display,
+            value,
+            go_string(pattern)
+        ));
+    }
+    if constraints.path_exists {
+        calls.push(format!("pathExists({}, {})", display, value));
+    }
+    let checks: Vec<String> = calls
+        .into_iter()
+        .flat_map(|call| {
+            [
+                format!("if err := {}; err != nil {{", call),
+                "\treturn err".to_string(),
+                "}".to_string(),
+            ]
+        })
+        .collect();
+    if checks.is_empty() {
+        return checks;
+    }
+    let head = match guard {
+        _ if input.multiple => format!("for _, value := range {} {{", field),
+        Some(guard) => format!("if {} {{", guard),
+        None => return checks,
+    };
+    let mut lines = vec![head];
+    lines.extend(checks.into_iter().map(|line| format!("\t{}", line)));
+    lines.push("}".to_string());
+    lines
+}
+
+/// Lines registering a flag on the `flags` set and binding it to viper under
+/// the key scoped by `path`. Positional inputs produce no lines.
+pub(crate) fn register_flag(path: &[String], input: &Input) -> Vec<String> {
+    let InputKind::Flag { short } = input.kind else {
+        return Vec::new();
+    };
+    let name = go_string(&flag_name(&input.name));
+    // Count flags start at zero, so pflag takes no default for them
+    let (kind, default) = if input.multiple {
+        (
+            "StringSlice".to_string(),
+            Some(string_slice_default(input.default.as_ref())),
+        )
+    } else if input.ty == InputType::Count {
+        ("Count".to_string(), None)
+    } else {
+        (
+            flag_kind(input.ty),
+            Some(default_literal(input.ty, input.default.as_ref())),
+        )
+    };
+    let usage = go_string(input.description.as_deref().unwrap_or_default());
+    let mut args = vec![name.clone()];
+    if let Some(short) = short {
+        args.push(go_string(&short.to_string()));
+    }
+    args.extend(default);
+    args.push(usage);
+    let suffix = if short.is_some() { "P" } else { "" };
+    let register = format!("\tflags.{}{}({})", kind, suffix, args.join(", "));
+    vec![
+        register,
+        format!(
+            "\t_ = viper.BindPFlag({}, flags.Lookup({}))",
+            go_string(&viper_key(path, &input.name)),
+            name
+        ),
+    ]
+}
+
+/// pflag registration method for a type (`String`, `Int64`, ...), named after
+/// the Go type.
+fn flag_kind(ty: InputType) -> String {
+    to_pascal_case(GO_TYPES.type_name(ty.into()))
+}
+
+/// Lines asking for an argument with a prompt message when it is empty,
+/// unless `--no-input` is given, and failing when it is still empty.
+fn ask_lines(input: &Input) -> Vec<String> {
+    let field = format!("input.{}", to_pascal_case(&input.name));
+    let message = input.prompt_message().unwrap_or_default();
+    vec![
+        format!(
+            "if {} == \"\" && !viper.GetBool({}) {{",
+            field,
+            go_string(&viper_key(&[], NO_INPUT_FLAG))
+        ),
+        format!(
+            "\tfmt.Fprint(os.Stderr, {})",
+            go_string(&format!("{} ", message))
+        ),
+        "\tline, _ := bufio.NewReader(os.Stdin).ReadString('\\n')".to_string(),
+        format!("\t{} = strings.TrimSpace(line)", field),
+        "}".to_string(),
+        format!("if {} == \"\" {{", field),
+        format!(
+            "\treturn errors.New({})",
+            go_string(&format!("missing argument {}", display_name(input)))
+        ),
+        "}".to_string(),
+    ]
+}
+
+/// viper getter for a flag (`GetString`, `GetInt64`, ...).
+///
+/// Flags taking multiple values are registered as string slices and parsed
+/// by [`parse_list`], so environment variables can set them too.
+pub(crate) fn viper_getter(input: &Input) -> String {
+    if input.multiple {
+        "GetStringSlice".to_string()
+    } else {
+        format!("Get{}", flag_kind(input.ty))
+    }
+}
+
+/// `[]string` literal for the default of a flag taking multiple values.
+fn string_slice_default(default: Option<&DefaultValue>) -> String {
+    let items = match default {
+        None => return "nil".to_string(),
+        Some(DefaultValue::List(items)) => items.iter().collect(),
+        Some(value) => vec![value],
+    };
+    let items: Vec<_> = items
+        .iter()
+        .map(|item| go_string(&item.to_code_string()))
+        .collect();
+    format!("[]string{{{}}}", items.join(", "))
+}
+
+/// Slice literal for the default of a positional taking multiple values.
+fn slice_default(ty: InputType, default: &DefaultValue) -> String {
+    match default {
+        DefaultValue::List(_) => default_literal(ty, Some(default)),
+        value => format!(
+            "[]{}{{{}}}",
+            GO_TYPES.type_name(ty.into()),
+            default_literal(ty, Some(value))
+        ),
+    }
+}
+
+/// Go literal for an input default, falling back to the type's zero value.
+///
+/// Array and table defaults become slice and `map[string]` literals of the
+/// input's element type.
+fn default_literal(ty: InputType, default: Option<&DefaultValue>) -> String {
+    let go_type = GO_TYPES.type_name(ty.into());
+    match (ty, default) {
+        (_, Some(DefaultValue::List(items))) => {
+            let items: Vec<_> = items
+                .iter()
+                .map(|item| default_literal(ty, Some(item)))
+                .collect();
+            format!("[]{}{{{}}}", go_type, items.join(", "))
+        }
+        (_, Some(DefaultValue::Table(entries))) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", go_string(k), default_literal(ty, Some(v))))
+                .collect();
+            format!("map[string]{}{{{}}}", go_type, entries.join(", "))
+        }
+        (InputType::Int, Some(DefaultValue::Int(i))) => i.to_string(),
+        (InputType::Float, Some(DefaultValue::Float(f))) => format!("{:?}", f),
+        (InputType::Float, Some(DefaultValue::Int(i))) => i.to_string(),
+        (InputType::Bool, Some(DefaultValue::Bool(b))) => b.to_string(),
+        (InputType::Bool, _) => "false".to_string(),
+        (InputType::Int | InputType::Float | InputType::Count, _) => "0".to_string(),
+        (_, Some(value)) => go_string(&value.to_code_string()),
+        (_, None) => "\"\"".to_string(),
+    }
+}
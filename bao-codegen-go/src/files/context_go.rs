@@ -0,0 +1,1765 @@
+//! internal/app/context.go generator for Go projects.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use baobao_codegen::{adapters::RenderedResources, language::TypeMapper, schema::ContextFieldInfo};
+use baobao_core::{
+    ContextFieldType, DatabaseType, FileRules, GeneratedFile, QueueType, to_pascal_case,
+};
+use baobao_ir::{
+    CacheMode, CacheOptions, ConfigFieldType, ConfigFormat, ConfigOptions, EmailOptions,
+    GrpcOptions, HttpAuth, HttpOptions, Input, LlmOptions, LlmProvider, LogFormat, LogLevel,
+    PoolConfig, SecretsBackend, SecretsOptions, SmtpTls, SqliteOptions, WorkdirOptions,
+};
+
+use super::command_go::field_type;
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile, align_entries, align_fields},
+    naming::go_string,
+    type_mapper::GoTypeMapper,
+};
+
+/// Target pgx version for PostgreSQL pools.
+pub const PGX_VERSION: &str = "v5.6.0";
+
+/// Target sqlx version for MySQL, SQLite and DuckDB connections.
+pub const SQLX_VERSION: &str = "v1.4.0";
+
+/// Target MySQL driver version.
+pub const MYSQL_DRIVER_VERSION: &str = "v1.8.1";
+
+/// Target pure-Go SQLite driver version.
+pub const SQLITE_DRIVER_VERSION: &str = "v1.33.1";
+
+/// Target go-duckdb driver version.
+pub const DUCKDB_DRIVER_VERSION: &str = "v1.8.5";
+
+/// Target Go CDK version for S3 buckets.
+pub const GOCLOUD_VERSION: &str = "v0.40.0";
+
+/// Target grpc-go version for gRPC channels.
+pub const GRPC_VERSION: &str = "v1.67.1";
+
+/// Target nats.go version for NATS connections.
+pub const NATS_VERSION: &str = "v1.37.0";
+
+/// Target kafka-go version for Kafka writers.
+pub const KAFKA_GO_VERSION: &str = "v0.4.47";
+
+/// Target go-mail version for SMTP clients.
+pub const GO_MAIL_VERSION: &str = "v0.5.2";
+
+/// Target BurntSushi/toml version for TOML config files.
+pub const TOML_VERSION: &str = "v1.4.0";
+
+/// Target yaml.v3 version for YAML config files.
+pub const YAML_VERSION: &str = "v3.0.1";
+
+/// Target golang-lru version for caches.
+pub const GOLANG_LRU_VERSION: &str = "v2.0.7";
+
+/// Target go-keyring version for keyring secrets.
+pub const GO_KEYRING_VERSION: &str = "v0.2.5";
+
+/// The shared application context handed to every handler.
+///
+/// PostgreSQL uses a `pgxpool.Pool`; MySQL and SQLite use `sqlx.DB` on top of
+/// `database/sql`. Neither exposes an acquire timeout, so `acquire_timeout` is
+/// not applied. S3 storage is a Go CDK `blob.Bucket` and gRPC a `grpc.ClientConn`. Queues
+/// are a `nats.Conn` or a kafka-go `kafka.Writer`, email a go-mail `mail.Client`, the
+/// config file a generated `Config` struct, secrets a generated `Secrets` accessor
+/// backed by go-keyring, the environment or a file, and the LLM client a generated
+/// `LlmClient` over `net/http`. Caches are a golang-lru `expirable.LRU`, wrapped in a
+/// generated `Cache` when disk-backed, and the working directories a generated
+/// `Workdir` whose scratch directory `Close` removes. HTTP clients with a base URL or default
+/// headers apply them through a generated `http.RoundTripper`.
+pub struct ContextGo {
+    pub fields: Vec<ContextFieldInfo>,
+    pub log_level: LogLevel,
+    pub log_format: LogFormat,
+    pub resources: RenderedResources,
+    /// Global options, read into `Globals` by the root command.
+    pub globals: Vec<Input>,
+}
+
+impl ContextGo {
+    pub fn new(fields: Vec<ContextFieldInfo>) -> Self {
+        Self {
+            fields,
+            log_level: LogLevel::default(),
+            log_format: LogFormat::default(),
+            resources: RenderedResources::default(),
+            globals: Vec::new(),
+        }
+    }
+
+    /// Add a `Globals` field holding the application's global options.
+    pub fn with_globals(mut self, globals: Vec<Input>) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    /// Configure the default level and format of the `[context.logging]` logger.
+    pub fn with_logger(mut self, level: LogLevel, format: LogFormat) -> Self {
+        self.log_level = level;
+        self.log_format = format;
+        self
+    }
+
+    /// Render custom resource fields with their adapter's code.
+    ///
+    /// Custom fields without rendered code are left out.
+    pub fn with_resources(mut self, resources: RenderedResources) -> Self {
+        self.fields.retain(|f| {
+            f.field_type != ContextFieldType::Custom || resources.get(&f.name).is_some()
+        });
+        self.resources = resources;
+        self
+    }
+
+    /// Go modules required by the configured resources, as (module path, version).
+    pub fn requires(&self) -> Vec<(String, String)> {
+        let mut requires = Vec::new();
+        for field in &self.fields {
+            match field.field_type {
+                ContextFieldType::Database(DatabaseType::Postgres) => {
+                    requires.push(("github.com/jackc/pgx/v5", PGX_VERSION));
+                }
+                ContextFieldType::Database(DatabaseType::Mysql) => {
+                    requires.push(("github.com/jmoiron/sqlx", SQLX_VERSION));
+                    requires.push(("github.com/go-sql-driver/mysql", MYSQL_DRIVER_VERSION));
+                }
+                ContextFieldType::Database(DatabaseType::Sqlite) => {
+                    requires.push(("github.com/jmoiron/sqlx", SQLX_VERSION));
+                    requires.push(("modernc.org/sqlite", SQLITE_DRIVER_VERSION));
+                }
+                ContextFieldType::Database(DatabaseType::Duckdb) => {
+                    requires.push(("github.com/jmoiron/sqlx", SQLX_VERSION));
+                    requires.push(("github.com/marcboeker/go-duckdb", DUCKDB_DRIVER_VERSION));
+                }
+                ContextFieldType::Storage => requires.push(("gocloud.dev", GOCLOUD_VERSION)),
+                ContextFieldType::Grpc => requires.push(("google.golang.org/grpc", GRPC_VERSION)),
+                ContextFieldType::Email => {
+                    requires.push(("github.com/wneessen/go-mail", GO_MAIL_VERSION));
+                }
+                ContextFieldType::Queue(QueueType::Nats) => {
+                    requires.push(("github.com/nats-io/nats.go", NATS_VERSION));
+                }
+                ContextFieldType::Queue(QueueType::Kafka) => {
+                    requires.push(("github.com/segmentio/kafka-go", KAFKA_GO_VERSION));
+                }
+                ContextFieldType::Config => match field.config.as_ref().map(|c| c.format) {
+                    Some(ConfigFormat::Toml) => {
+                        requires.push(("github.com/BurntSushi/toml", TOML_VERSION));
+                    }
+                    Some(ConfigFormat::Yaml) => requires.push(("gopkg.in/yaml.v3", YAML_VERSION)),
+                    Some(ConfigFormat::Json) | None => {}
+                },
+                ContextFieldType::Secrets
+                    if Self::secrets_backend(field) == SecretsBackend::Keyring =>
+                {
+                    requires.push(("github.com/zalando/go-keyring", GO_KEYRING_VERSION));
+                }
+                ContextFieldType::Cache => {
+                    requires.push(("github.com/hashicorp/golang-lru/v2", GOLANG_LRU_VERSION));
+                }
+                ContextFieldType::Secrets | ContextFieldType::Llm | ContextFieldType::Workdir => {}
+                ContextFieldType::Http | ContextFieldType::Logger | ContextFieldType::Custom => {}
+            }
+        }
+        let mut requires: Vec<_> = requires
+            .into_iter()
+            .map(|(path, version)| (path.to_string(), version.to_string()))
+            .collect();
+        for dep in self.resources.dependencies() {
+            requires.push((dep.name.clone(), dep.version.clone()));
+        }
+        requires
+    }
+
+    fn secrets_backend(field: &ContextFieldInfo) -> SecretsBackend {
+        field
+            .secrets
+            .as_ref()
+            .map(|secrets| secrets.backend)
+            .unwrap_or_default()
+    }
+
+    /// Disk caches are wrapped in the generated `Cache`.
+    fn is_disk_cache(field: &ContextFieldInfo) -> bool {
+        field
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.mode == CacheMode::Disk)
+    }
+
+    fn is_sqlx(field: &ContextFieldInfo) -> bool {
+        matches!(
+            field.field_type,
+            ContextFieldType::Database(
+                DatabaseType::Mysql | DatabaseType::Sqlite | DatabaseType::Duckdb
+            )
+        )
+    }
+
+    fn http_options(field: &ContextFieldInfo) -> Option<&HttpOptions> {
+        field.http.as_ref().filter(|http| http.has_config())
+    }
+
+    /// Whether an HTTP client needs `httpTransport` for its base URL, headers,
+    /// retries or auth.
+    fn needs_transport(http: &HttpOptions) -> bool {
+        http.base_url.is_some()
+            || !http.headers.is_empty()
+            || http.user_agent.is_some()
+            || http.has_retries()
+            || http.auth.is_some()
+    }
+
+    /// Whether closing the context collects errors from several resources.
+    fn collects_close_errors(&self) -> bool {
+        self.fields.iter().any(|f| {
+            Self::is_sqlx(f)
+                || matches!(
+                    f.field_type,
+                    ContextFieldType::Storage
+                        | ContextFieldType::Grpc
+                        | ContextFieldType::Queue(QueueType::Kafka)
+                )
+                || Self::has_temp_dir(f)
+        })
+    }
+
+    /// A working directory with a scratch directory, which `Close` removes.
+    fn has_temp_dir(field: &ContextFieldInfo) -> bool {
+        field.workdir.as_ref().is_some_and(|workdir| workdir.temp)
+    }
+
+    fn struct_decl(&self) -> String {
+        let doc = "// Context holds the resources shared by all command handlers.";
+        if self.fields.is_empty() && self.globals.is_empty() {
+            return format!("{}\ntype Context struct{{}}", doc);
+        }
+        let mut fields: Vec<(String, String)> = self
+            .fields
+            .iter()
+            .map(|f| {
+                let ty = match self.resources.get(&f.name) {
+                    Some(code) => code.type_name.clone(),
+                    None if Self::is_disk_cache(f) => "*Cache".to_string(),
+                    None => GoTypeMapper.map_context_type(&f.field_type).to_string(),
+                };
+                (to_pascal_case(&f.name), ty)
+            })
+            .collect();
+        if !self.globals.is_empty() {
+            fields.push(("Globals".to_string(), "Globals".to_string()));
+        }
+        format!(
+            "{}\ntype Context struct {{\n{}\n}}",
+            doc,
+            align_fields(&fields, "\t").join("\n")
+        )
+    }
+
+    fn globals_decl(&self) -> String {
+        let fields: Vec<(String, String)> = self
+            .globals
+            .iter()
+            .map(|input| (to_pascal_case(&input.name), field_type(input)))
+            .collect();
+        format!(
+            "// Globals holds the global options shared by every command.\ntype Globals struct {{\n{}\n}}",
+            align_fields(&fields, "\t").join("\n")
+        )
+    }
+
+    fn new_fn(&self) -> String {
+        let mut lines = vec![
+            "// New connects the resources declared in bao.toml.".to_string(),
+            "func New(ctx context.Context) (*Context, error) {".to_string(),
+        ];
+        if self.fields.is_empty() {
+            lines.push("\treturn &Context{}, nil".to_string());
+            lines.push("}".to_string());
+            return lines.join("\n");
+        }
+
+        lines.push("\tc := &Context{}".to_string());
+        let fallible = self.fields.iter().any(|f| {
+            matches!(
+                f.field_type,
+                ContextFieldType::Database(_)
+                    | ContextFieldType::Storage
+                    | ContextFieldType::Grpc
+                    | ContextFieldType::Queue(QueueType::Nats)
+                    | ContextFieldType::Email
+                    | ContextFieldType::Config
+                    | ContextFieldType::Llm
+                    | ContextFieldType::Workdir
+            ) || (f.field_type == ContextFieldType::Secrets
+                && Self::secrets_backend(f) == SecretsBackend::File)
+                || Self::is_disk_cache(f)
+        });
+        if fallible {
+            lines.push("\tvar err error".to_string());
+        }
+        for field in &self.fields {
+            let name = to_pascal_case(&field.name);
+            match field.field_type {
+                ContextFieldType::Database(_) | ContextFieldType::Storage => {
+                    lines.push(format!(
+                        "\tif c.{}, err = new{}(ctx); err != nil {{",
+                        name, name
+                    ));
+                    lines.push("\t\t_ = c.Close()".to_string());
+                    lines.push("\t\treturn nil, err".to_string());
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Grpc
+                | ContextFieldType::Queue(QueueType::Nats)
+                | ContextFieldType::Email
+                | ContextFieldType::Config
+                | ContextFieldType::Llm
+                | ContextFieldType::Workdir => {
+                    lines.push(format!(
+                        "\tif c.{}, err = new{}(); err != nil {{",
+                        name, name
+                    ));
+                    lines.push("\t\t_ = c.Close()".to_string());
+                    lines.push("\t\treturn nil, err".to_string());
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Secrets
+                    if Self::secrets_backend(field) == SecretsBackend::File =>
+                {
+                    lines.push(format!(
+                        "\tif c.{}, err = new{}(); err != nil {{",
+                        name, name
+                    ));
+                    lines.push("\t\t_ = c.Close()".to_string());
+                    lines.push("\t\treturn nil, err".to_string());
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Cache if Self::is_disk_cache(field) => {
+                    lines.push(format!(
+                        "\tif c.{}, err = new{}(); err != nil {{",
+                        name, name
+                    ));
+                    lines.push("\t\t_ = c.Close()".to_string());
+                    lines.push("\t\treturn nil, err".to_string());
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Http if Self::http_options(field).is_some() => {
+                    lines.push(format!("\tc.{} = new{}()", name, name));
+                }
+                ContextFieldType::Http => lines.push(format!("\tc.{} = &http.Client{{}}", name)),
+                ContextFieldType::Logger
+                | ContextFieldType::Queue(QueueType::Kafka)
+                | ContextFieldType::Secrets
+                | ContextFieldType::Cache => {
+                    lines.push(format!("\tc.{} = new{}()", name, name));
+                }
+                ContextFieldType::Custom => {
+                    if let Some(code) = self.resources.get(&field.name) {
+                        lines.push(format!("\tc.{} = {}", name, code.init));
+                    }
+                }
+            }
+        }
+        lines.push("\treturn c, nil".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn close_fn(&self) -> String {
+        let mut lines = vec![
+            "// Close releases the resources held by the context.".to_string(),
+            "func (c *Context) Close() error {".to_string(),
+        ];
+        let collects = self.collects_close_errors();
+        if collects {
+            lines.push("\tvar errs []error".to_string());
+        }
+        for field in &self.fields {
+            let name = to_pascal_case(&field.name);
+            match field.field_type {
+                ContextFieldType::Database(DatabaseType::Postgres)
+                | ContextFieldType::Queue(QueueType::Nats) => {
+                    lines.push(format!("\tif c.{} != nil {{", name));
+                    lines.push(format!("\t\tc.{}.Close()", name));
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Database(_)
+                | ContextFieldType::Storage
+                | ContextFieldType::Grpc
+                | ContextFieldType::Queue(QueueType::Kafka) => {
+                    lines.push(format!("\tif c.{} != nil {{", name));
+                    lines.push(format!("\t\terrs = append(errs, c.{}.Close())", name));
+                    lines.push("\t}".to_string());
+                }
+                ContextFieldType::Workdir if Self::has_temp_dir(field) => {
+                    lines.push(format!("\tif c.{} != nil {{", name));
+                    lines.push(format!(
+                        "\t\terrs = append(errs, os.RemoveAll(c.{}.Temp))",
+                        name
+                    ));
+                    lines.push("\t}".to_string());
+                }
+                // The SMTP client only connects while sending
+                ContextFieldType::Email
+                | ContextFieldType::Config
+                | ContextFieldType::Secrets
+                | ContextFieldType::Llm
+                | ContextFieldType::Cache
+                | ContextFieldType::Workdir
+                | ContextFieldType::Http
+                | ContextFieldType::Logger
+                | ContextFieldType::Custom => {}
+            }
+        }
+        if collects {
+            lines.push("\treturn errors.Join(errs...)".to_string());
+        } else {
+            lines.push("\treturn nil".to_string());
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Constructor for a PostgreSQL pool.
+    fn postgres_fn(field: &ContextFieldInfo) -> String {
+        let name = to_pascal_case(&field.name);
+        let mut lines = vec![
+            format!(
+                "func new{}(ctx context.Context) (*pgxpool.Pool, error) {{",
+                name
+            ),
+            format!(
+                "\tconfig, err := pgxpool.ParseConfig(os.Getenv({}))",
+                go_string(&field.env_var)
+            ),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+        ];
+        let pool = &field.pool;
+        if let Some(max) = pool.max_connections {
+            lines.push(format!("\tconfig.MaxConns = {}", max));
+        }
+        if let Some(min) = pool.min_connections {
+            lines.push(format!("\tconfig.MinConns = {}", min));
+        }
+        if let Some(idle) = pool.idle_timeout {
+            lines.push(format!("\tconfig.MaxConnIdleTime = {}", duration(idle)));
+        }
+        if let Some(lifetime) = pool.max_lifetime {
+            lines.push(format!("\tconfig.MaxConnLifetime = {}", duration(lifetime)));
+        }
+        lines.extend([
+            "\tpool, err := pgxpool.NewWithConfig(ctx, config)".to_string(),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\treturn pool, nil".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+
+    /// Constructor for a MySQL, SQLite or DuckDB connection pool through sqlx.
+    fn sqlx_fn(field: &ContextFieldInfo, driver: &str, dsn: String) -> String {
+        let name = to_pascal_case(&field.name);
+        let mut lines = vec![
+            format!("func new{}(ctx context.Context) (*sqlx.DB, error) {{", name),
+            format!(
+                "\tdb, err := sqlx.ConnectContext(ctx, {}, {})",
+                go_string(driver),
+                dsn
+            ),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+        ];
+        lines.extend(Self::pool_settings(&field.pool));
+        lines.push("\treturn db, nil".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn pool_settings(pool: &PoolConfig) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(max) = pool.max_connections {
+            lines.push(format!("\tdb.SetMaxOpenConns({})", max));
+        }
+        if let Some(min) = pool.min_connections {
+            lines.push(format!("\tdb.SetMaxIdleConns({})", min));
+        }
+        if let Some(idle) = pool.idle_timeout {
+            lines.push(format!("\tdb.SetConnMaxIdleTime({})", duration(idle)));
+        }
+        if let Some(lifetime) = pool.max_lifetime {
+            lines.push(format!("\tdb.SetConnMaxLifetime({})", duration(lifetime)));
+        }
+        lines
+    }
+
+    /// SQLite data source: the fixed path or the env var, with pragmas as
+    /// `modernc.org/sqlite` query parameters.
+    fn sqlite_dsn(field: &ContextFieldInfo) -> String {
+        let options = field.sqlite.clone().unwrap_or_default();
+        let params = Self::sqlite_params(&options);
+        let query = if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        };
+        match &options.path {
+            Some(path) => go_string(&format!("file:{}{}", path, query)),
+            None if query.is_empty() => format!("os.Getenv({})", go_string(&field.env_var)),
+            None => format!(
+                "os.Getenv({}) + {}",
+                go_string(&field.env_var),
+                go_string(&query)
+            ),
+        }
+    }
+
+    /// DuckDB data source: the fixed path or the env var, with settings as
+    /// `go-duckdb` query parameters.
+    fn duckdb_dsn(field: &ContextFieldInfo) -> String {
+        let options = field.duckdb.clone().unwrap_or_default();
+        let mut params = Vec::new();
+        if let Some(read_only) = options.read_only {
+            let mode = if read_only { "read_only" } else { "read_write" };
+            params.push(format!("access_mode={}", mode));
+        }
+        if let Some(threads) = options.threads {
+            params.push(format!("threads={}", threads));
+        }
+        let query = if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        };
+        match &options.path {
+            Some(path) => go_string(&format!("{}{}", path, query)),
+            None if query.is_empty() => format!("os.Getenv({})", go_string(&field.env_var)),
+            None => format!(
+                "os.Getenv({}) + {}",
+                go_string(&field.env_var),
+                go_string(&query)
+            ),
+        }
+    }
+
+    fn sqlite_params(options: &SqliteOptions) -> Vec<String> {
+        let mut params = Vec::new();
+        if options.read_only == Some(true) {
+            params.push("mode=ro".to_string());
+        } else if options.create_if_missing == Some(false) {
+            params.push("mode=rw".to_string());
+        }
+        if let Some(mode) = options.journal_mode {
+            params.push(format!(
+                "_pragma=journal_mode({})",
+                mode.as_str().to_uppercase()
+            ));
+        }
+        if let Some(mode) = options.synchronous {
+            params.push(format!(
+                "_pragma=synchronous({})",
+                mode.as_str().to_uppercase()
+            ));
+        }
+        if let Some(timeout) = options.busy_timeout {
+            params.push(format!("_pragma=busy_timeout({})", timeout.as_millis()));
+        }
+        if let Some(enabled) = options.foreign_keys {
+            params.push(format!("_pragma=foreign_keys({})", u8::from(enabled)));
+        }
+        params
+    }
+
+    /// Constructor for an S3 bucket, with credentials from the `AWS_*` variables.
+    fn storage_fn(field: &ContextFieldInfo) -> String {
+        let s3 = field.s3.clone().unwrap_or_default();
+        let mut params = Vec::new();
+        if let Some(region) = &s3.region {
+            params.push(("region", region.as_str()));
+        }
+        if let Some(endpoint) = &s3.endpoint {
+            // S3-compatible services rarely support virtual-hosted buckets
+            params.push(("endpoint", endpoint.as_str()));
+            params.push(("use_path_style", "true"));
+        }
+        let url = format!("s3://{}", s3.bucket);
+        let mut lines = vec![format!(
+            "func new{}(ctx context.Context) (*blob.Bucket, error) {{",
+            to_pascal_case(&field.name)
+        )];
+        if params.is_empty() {
+            lines.push(format!(
+                "\tbucket, err := blob.OpenBucket(ctx, {})",
+                go_string(&url)
+            ));
+        } else {
+            lines.push("\tparams := url.Values{}".to_string());
+            for (key, value) in params {
+                lines.push(format!(
+                    "\tparams.Set({}, {})",
+                    go_string(key),
+                    go_string(value)
+                ));
+            }
+            lines.push(format!(
+                "\tbucket, err := blob.OpenBucket(ctx, {}+params.Encode())",
+                go_string(&format!("{}?", url))
+            ));
+        }
+        lines.extend([
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\treturn bucket, nil".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+
+    /// Constructor for a gRPC channel, applying the timeout to unary calls.
+    fn grpc_fn(field: &ContextFieldInfo, grpc: &GrpcOptions) -> String {
+        let credentials = if grpc.uses_tls() {
+            "credentials.NewTLS(&tls.Config{})"
+        } else {
+            "insecure.NewCredentials()"
+        };
+        let mut lines = vec![
+            format!(
+                "func new{}() (*grpc.ClientConn, error) {{",
+                to_pascal_case(&field.name)
+            ),
+            "\tconn, err := grpc.NewClient(".to_string(),
+            format!("\t\t{},", go_string(grpc_target(&grpc.endpoint))),
+            format!("\t\tgrpc.WithTransportCredentials({}),", credentials),
+        ];
+        if let Some(timeout) = grpc.timeout {
+            lines.extend([
+                "\t\tgrpc.WithUnaryInterceptor(func(ctx context.Context, method string, req, reply any, cc *grpc.ClientConn, invoker grpc.UnaryInvoker, opts ...grpc.CallOption) error {".to_string(),
+                format!("\t\t\tctx, cancel := context.WithTimeout(ctx, {})", duration(timeout)),
+                "\t\t\tdefer cancel()".to_string(),
+                "\t\t\treturn invoker(ctx, method, req, reply, cc, opts...)".to_string(),
+                "\t\t}),".to_string(),
+            ]);
+        }
+        lines.extend([
+            "\t)".to_string(),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\treturn conn, nil".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+
+    /// Constructor for an SMTP client. It connects for each message sent.
+    fn email_fn(field: &ContextFieldInfo, email: &EmailOptions) -> String {
+        let mut lines = vec![
+            format!(
+                "func new{}() (*mail.Client, error) {{",
+                to_pascal_case(&field.name)
+            ),
+            "\tclient, err := mail.NewClient(".to_string(),
+            format!("\t\t{},", go_string(&email.host)),
+            format!("\t\tmail.WithPort({}),", email.port),
+        ];
+        lines.push(
+            match email.tls {
+                SmtpTls::Starttls => "\t\tmail.WithTLSPolicy(mail.TLSMandatory),",
+                SmtpTls::Tls => "\t\tmail.WithSSL(),",
+                SmtpTls::None => "\t\tmail.WithTLSPolicy(mail.NoTLS),",
+            }
+            .to_string(),
+        );
+        if let Some((username, password)) = email.credentials() {
+            lines.extend([
+                "\t\tmail.WithSMTPAuth(mail.SMTPAuthPlain),".to_string(),
+                format!("\t\tmail.WithUsername(os.Getenv({})),", go_string(username)),
+                format!("\t\tmail.WithPassword(os.Getenv({})),", go_string(password)),
+            ]);
+        }
+        lines.extend([
+            "\t)".to_string(),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\treturn client, nil".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+
+    /// The `Config` struct and its constructor, which reads the file and fails
+    /// if a required key is missing. A missing file reads as empty.
+    fn config_fn(field: &ContextFieldInfo, config: &ConfigOptions) -> String {
+        let (tag, unmarshal) = match config.format {
+            ConfigFormat::Toml => ("toml", "toml.Unmarshal"),
+            ConfigFormat::Json => ("json", "json.Unmarshal"),
+            ConfigFormat::Yaml => ("yaml", "yaml.Unmarshal"),
+        };
+        let typed: Vec<(String, String)> = config
+            .fields
+            .iter()
+            .map(|(key, field)| {
+                let ty = match field.field_type {
+                    ConfigFieldType::String => "string",
+                    ConfigFieldType::Int => "int64",
+                    ConfigFieldType::Float => "float64",
+                    ConfigFieldType::Bool => "bool",
+                };
+                let ty = if field.optional {
+                    format!("*{}", ty)
+                } else {
+                    ty.to_string()
+                };
+                (to_pascal_case(key), ty)
+            })
+            .collect();
+        let fields: Vec<(String, String)> = align_fields(&typed, "")
+            .into_iter()
+            .zip(config.fields.keys())
+            .map(|(field, key)| (field, format!("`{}:{}`", tag, go_string(key))))
+            .collect();
+        let mut lines = vec![format!(
+            "// Config holds the settings read from {}.",
+            config.path
+        )];
+        if fields.is_empty() {
+            lines.push("type Config struct{}".to_string());
+        } else {
+            lines.push("type Config struct {".to_string());
+            lines.extend(align_fields(&fields, "\t"));
+            lines.push("}".to_string());
+        }
+        let path = go_string(&config.path);
+        lines.extend([
+            String::new(),
+            format!(
+                "func new{}() (*Config, error) {{",
+                to_pascal_case(&field.name)
+            ),
+            format!("\tdata, err := os.ReadFile({})", path),
+            "\tif err != nil && !errors.Is(err, fs.ErrNotExist) {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\tconfig := &Config{}".to_string(),
+            "\tvalues := map[string]any{}".to_string(),
+            "\tif len(data) > 0 {".to_string(),
+            format!("\t\tif err := {}(data, config); err != nil {{", unmarshal),
+            format!("\t\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t\t}".to_string(),
+            format!("\t\tif err := {}(data, &values); err != nil {{", unmarshal),
+            format!("\t\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t\t}".to_string(),
+            "\t}".to_string(),
+        ]);
+        let required: Vec<String> = config
+            .fields
+            .iter()
+            .filter(|(_, field)| !field.optional)
+            .map(|(key, _)| go_string(key))
+            .collect();
+        if !required.is_empty() {
+            lines.extend([
+                format!(
+                    "\tfor _, key := range []string{{{}}} {{",
+                    required.join(", ")
+                ),
+                "\t\tif _, ok := values[key]; !ok {".to_string(),
+                format!(
+                    "\t\t\treturn nil, fmt.Errorf(\"{}: %s: missing field %q\", {}, key)",
+                    field.name, path
+                ),
+                "\t\t}".to_string(),
+                "\t}".to_string(),
+            ]);
+        }
+        lines.extend(["\treturn config, nil".to_string(), "}".to_string()]);
+        lines.join("\n")
+    }
+
+    /// The `Secrets` accessor and its constructor.
+    ///
+    /// `Get` mirrors `os.LookupEnv`. The keyring backend falls back to the
+    /// environment on any keyring error, such as no keyring in CI.
+    fn secrets_fn(field: &ContextFieldInfo, secrets: &SecretsOptions) -> String {
+        let name = to_pascal_case(&field.name);
+        let env_lookup = if secrets.prefix.is_empty() {
+            "os.LookupEnv(name)".to_string()
+        } else {
+            format!("os.LookupEnv({} + name)", go_string(&secrets.prefix))
+        };
+        let mut lines = match secrets.backend {
+            SecretsBackend::Keyring => vec![
+                "// Secrets looks up credentials in the OS keyring, falling back to environment variables.".to_string(),
+                "type Secrets struct{}".to_string(),
+                String::new(),
+                format!("func new{}() *Secrets {{", name),
+                "\treturn &Secrets{}".to_string(),
+                "}".to_string(),
+                String::new(),
+                "// Get returns the secret name and whether it is set.".to_string(),
+                "func (s *Secrets) Get(name string) (string, bool) {".to_string(),
+                format!(
+                    "\tif secret, err := keyring.Get({}, name); err == nil {{",
+                    go_string(&secrets.service)
+                ),
+                "\t\treturn secret, true".to_string(),
+                "\t}".to_string(),
+                format!("\treturn {}", env_lookup),
+                "}".to_string(),
+            ],
+            SecretsBackend::Env => vec![
+                "// Secrets looks up credentials in environment variables.".to_string(),
+                "type Secrets struct{}".to_string(),
+                String::new(),
+                format!("func new{}() *Secrets {{", name),
+                "\treturn &Secrets{}".to_string(),
+                "}".to_string(),
+                String::new(),
+                "// Get returns the secret name and whether it is set.".to_string(),
+                "func (s *Secrets) Get(name string) (string, bool) {".to_string(),
+                format!("\treturn {}", env_lookup),
+                "}".to_string(),
+            ],
+            SecretsBackend::File => vec![
+                format!(
+                    "// Secrets holds the credentials read from {}.",
+                    secrets.path
+                ),
+                "type Secrets struct {".to_string(),
+                "\tvalues map[string]string".to_string(),
+                "}".to_string(),
+                String::new(),
+                format!("func new{}() (*Secrets, error) {{", name),
+                format!("\tdata, err := os.ReadFile({})", go_string(&secrets.path)),
+                "\tif err != nil && !errors.Is(err, fs.ErrNotExist) {".to_string(),
+                format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+                "\t}".to_string(),
+                "\tvalues := map[string]string{}".to_string(),
+                "\tfor _, line := range strings.Split(string(data), \"\\n\") {".to_string(),
+                "\t\tline = strings.TrimSpace(line)".to_string(),
+                "\t\tif line == \"\" || strings.HasPrefix(line, \"#\") {".to_string(),
+                "\t\t\tcontinue".to_string(),
+                "\t\t}".to_string(),
+                "\t\tif key, value, ok := strings.Cut(line, \"=\"); ok {".to_string(),
+                "\t\t\tvalue = strings.TrimSpace(value)".to_string(),
+                "\t\t\tif len(value) >= 2 && strings.HasPrefix(value, `\"`) && strings.HasSuffix(value, `\"`) {".to_string(),
+                "\t\t\t\tvalue = value[1 : len(value)-1]".to_string(),
+                "\t\t\t}".to_string(),
+                "\t\t\tvalues[strings.TrimSpace(key)] = value".to_string(),
+                "\t\t}".to_string(),
+                "\t}".to_string(),
+                "\treturn &Secrets{values: values}, nil".to_string(),
+                "}".to_string(),
+                String::new(),
+                "// Get returns the secret name and whether it is set.".to_string(),
+                "func (s *Secrets) Get(name string) (string, bool) {".to_string(),
+                "\tsecret, ok := s.values[name]".to_string(),
+                "\treturn secret, ok".to_string(),
+                "}".to_string(),
+            ],
+        };
+        lines.extend([
+            String::new(),
+            "// Require returns the secret name, failing if it is not set.".to_string(),
+            "func (s *Secrets) Require(name string) (string, error) {".to_string(),
+            "\tif secret, ok := s.Get(name); ok {".to_string(),
+            "\t\treturn secret, nil".to_string(),
+            "\t}".to_string(),
+            "\treturn \"\", fmt.Errorf(\"secret %q is not set\", name)".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+
+    /// The LRU cache of `[context.cache]`, wrapped in a `Cache` that also
+    /// writes entries to files for disk caches.
+    fn cache_fn(field: &ContextFieldInfo, cache: &CacheOptions) -> String {
+        let name = to_pascal_case(&field.name);
+        // A zero TTL never expires entries
+        let lru = format!(
+            "expirable.NewLRU[string, string]({}, nil, {})",
+            cache.max_capacity,
+            cache.ttl.map(duration).unwrap_or_else(|| "0".to_string())
+        );
+        if cache.mode == CacheMode::Memory {
+            return format!(
+                "func new{}() *expirable.LRU[string, string] {{\n\treturn {}\n}}",
+                name, lru
+            );
+        }
+        let dir = match &cache.path {
+            Some(path) => format!("\tdir := {}", go_string(path)),
+            None => format!(
+                r#"	dir, err := os.UserCacheDir()
+	if err != nil {{
+		return nil, fmt.Errorf("{field}: %w", err)
+	}}
+	dir = filepath.Join(dir, {namespace})"#,
+                field = field.name,
+                namespace = go_string(&cache.namespace),
+            ),
+        };
+        let expiry = match cache.ttl {
+            Some(ttl) => format!(
+                r#"	if info, err := os.Stat(path); err != nil || time.Since(info.ModTime()) > {ttl}*time.Millisecond {{
+		return "", false
+	}}
+"#,
+                // gofmt drops the spaces around `*` next to `>`
+                ttl = ttl.as_millis()
+            ),
+            None => String::new(),
+        };
+        format!(
+            r#"// Cache keeps string values in memory and in files that outlive the
+// invocation.
+type Cache struct {{
+	memory *expirable.LRU[string, string]
+	dir    string
+}}
+
+func new{name}() (*Cache, error) {{
+{dir}
+	if err := os.MkdirAll(dir, 0o755); err != nil {{
+		return nil, fmt.Errorf("{field}: %w", err)
+	}}
+	return &Cache{{memory: {lru}, dir: dir}}, nil
+}}
+
+// Get returns the value of key, from memory or else from its file, and
+// whether it is cached.
+func (c *Cache) Get(key string) (string, bool) {{
+	if value, ok := c.memory.Get(key); ok {{
+		return value, true
+	}}
+	path := c.path(key)
+{expiry}	data, err := os.ReadFile(path)
+	if err != nil {{
+		return "", false
+	}}
+	// Files start with their key, so a hash collision reads as a miss
+	value, ok := strings.CutPrefix(string(data), key+"\n")
+	if !ok {{
+		return "", false
+	}}
+	c.memory.Add(key, value)
+	return value, true
+}}
+
+// Add stores value under key, in memory and in its file.
+func (c *Cache) Add(key, value string) error {{
+	c.memory.Add(key, value)
+	return os.WriteFile(c.path(key), []byte(key+"\n"+value), 0o600)
+}}
+
+// Remove deletes key from memory and disk.
+func (c *Cache) Remove(key string) error {{
+	c.memory.Remove(key)
+	if err := os.Remove(c.path(key)); err != nil && !errors.Is(err, fs.ErrNotExist) {{
+		return err
+	}}
+	return nil
+}}
+
+// path returns the file of key, named by its hash so any key is a valid name.
+func (c *Cache) path(key string) string {{
+	sum := sha256.Sum256([]byte(key))
+	return filepath.Join(c.dir, hex.EncodeToString(sum[:]))
+}}"#,
+            name = name,
+            field = field.name,
+            dir = dir,
+            lru = lru,
+            expiry = expiry,
+        )
+    }
+
+    /// The `Workdir` of `[context.workdir]` and its constructor.
+    ///
+    /// Go has no user data directory lookup, so `userDataDir` resolves it the
+    /// way the Rust `dirs` crate does.
+    fn workdir_fn(field: &ContextFieldInfo, workdir: &WorkdirOptions) -> String {
+        let name = to_pascal_case(&field.name);
+        let mut fields = Vec::new();
+        let mut body = Vec::new();
+        let mut values = Vec::new();
+        let mut helper = String::new();
+        if workdir.data {
+            fields.push((
+                "\t// Data is the directory for files that outlive the invocation.",
+                "Data string",
+            ));
+            match &workdir.path {
+                Some(path) => body.push(format!("\tdata := {}", go_string(path))),
+                None => {
+                    body.push(format!(
+                        r#"	dir, err := userDataDir()
+	if err != nil {{
+		return nil, fmt.Errorf("{field}: %w", err)
+	}}
+	data := filepath.Join(dir, {namespace})"#,
+                        field = field.name,
+                        namespace = go_string(&workdir.namespace),
+                    ));
+                    helper =
+                        r#"// userDataDir returns the user data directory, as the Rust dirs crate
+// resolves it.
+func userDataDir() (string, error) {
+	switch runtime.GOOS {
+	case "windows", "darwin", "ios":
+		// The data directory is the config directory on these systems
+		return os.UserConfigDir()
+	}
+	if dir := os.Getenv("XDG_DATA_HOME"); dir != "" {
+		return dir, nil
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(home, ".local", "share"), nil
+}
+
+"#
+                        .to_string();
+                }
+            }
+            body.push(format!(
+                "\tif err := os.MkdirAll(data, 0o755); err != nil {{\n\t\treturn nil, fmt.Errorf(\"{}: %w\", err)\n\t}}",
+                field.name
+            ));
+            values.push("Data: data");
+        }
+        if workdir.temp {
+            fields.push((
+                "\t// Temp is the scratch directory, removed by Close.",
+                "Temp string",
+            ));
+            body.push(format!(
+                "\ttemp, err := os.MkdirTemp(\"\", {})\n\tif err != nil {{\n\t\treturn nil, fmt.Errorf(\"{}: %w\", err)\n\t}}",
+                go_string(&format!("{}-", workdir.namespace)),
+                field.name
+            ));
+            values.push("Temp: temp");
+        }
+        let fields = fields
+            .iter()
+            .map(|(doc, field)| format!("{}\n\t{}", doc, field))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{helper}// Workdir holds the data and scratch directories of the CLI.\ntype Workdir struct {{\n{fields}\n}}\n\nfunc new{name}() (*Workdir, error) {{\n{body}\n\treturn &Workdir{{{values}}}, nil\n}}",
+            body = body.join("\n"),
+            values = values.join(", "),
+        )
+    }
+
+    /// The `LlmClient` and its constructor.
+    ///
+    /// Requests are plain `net/http` calls with the provider's JSON shapes, so
+    /// no SDK is required.
+    fn llm_fn(field: &ContextFieldInfo, llm: &LlmOptions) -> String {
+        let (api, chat) = match llm.provider {
+            LlmProvider::Anthropic => (
+                "Anthropic Messages API",
+                format!(
+                    r#"// Chat returns the reply to a conversation. System messages are sent as the
+// system prompt.
+func (c *LlmClient) Chat(ctx context.Context, messages []Message) (string, error) {{
+	var system []string
+	turns := []Message{{}}
+	for _, m := range messages {{
+		if m.Role == RoleSystem {{
+			system = append(system, m.Content)
+		}} else {{
+			turns = append(turns, m)
+		}}
+	}}
+	body := map[string]any{{"model": c.Model, "max_tokens": {max_tokens}, "messages": turns}}
+	if len(system) > 0 {{
+		body["system"] = strings.Join(system, "\n\n")
+	}}
+	header := http.Header{{"X-Api-Key": {{c.apiKey}}, "Anthropic-Version": {{"2023-06-01"}}}}
+	var reply struct {{
+		Content []struct {{
+			Text string `json:"text"`
+		}} `json:"content"`
+	}}
+	if err := c.post(ctx, "/v1/messages", header, body, &reply); err != nil {{
+		return "", err
+	}}
+	var text strings.Builder
+	for _, block := range reply.Content {{
+		text.WriteString(block.Text)
+	}}
+	return text.String(), nil
+}}"#,
+                    max_tokens = llm.max_tokens.unwrap_or(LlmOptions::DEFAULT_MAX_TOKENS)
+                ),
+            ),
+            LlmProvider::Openai => (
+                "OpenAI Chat Completions API",
+                format!(
+                    r#"// Chat returns the reply to a conversation.
+func (c *LlmClient) Chat(ctx context.Context, messages []Message) (string, error) {{
+	body := map[string]any{{"model": c.Model, "messages": messages{max_tokens}}}
+	header := http.Header{{"Authorization": {{"Bearer " + c.apiKey}}}}
+	var reply struct {{
+		Choices []struct {{
+			Message Message `json:"message"`
+		}} `json:"choices"`
+	}}
+	if err := c.post(ctx, "/chat/completions", header, body, &reply); err != nil {{
+		return "", err
+	}}
+	if len(reply.Choices) == 0 {{
+		return "", fmt.Errorf("llm: response has no choices")
+	}}
+	return reply.Choices[0].Message.Content, nil
+}}"#,
+                    max_tokens = llm
+                        .max_tokens
+                        .map(|max| format!(", \"max_completion_tokens\": {}", max))
+                        .unwrap_or_default()
+                ),
+            ),
+        };
+        let client = format!(
+            r#"// LlmClient is a client for the {api}.
+type LlmClient struct {{
+	// Model requests are sent to.
+	Model   string
+	baseURL string
+	apiKey  string
+	http    *http.Client
+}}
+
+func new{name}() (*LlmClient, error) {{
+	apiKey, ok := os.LookupEnv({env})
+	if !ok {{
+		return nil, fmt.Errorf("{field}: %s is not set", {env})
+	}}
+	return &LlmClient{{
+		Model:   {model},
+		baseURL: {base_url},
+		apiKey:  apiKey,
+		http:    &http.Client{{}},
+	}}, nil
+}}
+
+// WithModel returns a copy of the client sending requests to model.
+func (c *LlmClient) WithModel(model string) *LlmClient {{
+	client := *c
+	client.Model = model
+	return &client
+}}
+
+// Complete returns the reply to a single prompt.
+func (c *LlmClient) Complete(ctx context.Context, prompt string) (string, error) {{
+	return c.Chat(ctx, []Message{{{{Role: RoleUser, Content: prompt}}}})
+}}"#,
+            api = api,
+            name = to_pascal_case(&field.name),
+            field = field.name,
+            env = go_string(&llm.api_key_env),
+            model = go_string(&llm.model),
+            base_url = go_string(&llm.base_url),
+        );
+        [LLM_TYPES, &client, &chat, LLM_POST].join("\n\n")
+    }
+
+    /// Constructor for a NATS connection to the servers in the env variable.
+    fn nats_fn(field: &ContextFieldInfo) -> String {
+        [
+            format!(
+                "func new{}() (*nats.Conn, error) {{",
+                to_pascal_case(&field.name)
+            ),
+            format!(
+                "\tconn, err := nats.Connect(os.Getenv({}))",
+                go_string(&field.env_var)
+            ),
+            "\tif err != nil {".to_string(),
+            format!("\t\treturn nil, fmt.Errorf(\"{}: %w\", err)", field.name),
+            "\t}".to_string(),
+            "\treturn conn, nil".to_string(),
+            "}".to_string(),
+        ]
+        .join("\n")
+    }
+
+    /// Constructor for a Kafka writer to the comma-separated brokers in the
+    /// env variable. Messages name their topic when written.
+    fn kafka_fn(field: &ContextFieldInfo) -> String {
+        [
+            format!("func new{}() *kafka.Writer {{", to_pascal_case(&field.name)),
+            "\treturn &kafka.Writer{".to_string(),
+            format!(
+                "\t\tAddr:     kafka.TCP(strings.Split(os.Getenv({}), \",\")...),",
+                go_string(&field.env_var)
+            ),
+            "\t\tBalancer: &kafka.LeastBytes{},".to_string(),
+            "\t}".to_string(),
+            "}".to_string(),
+        ]
+        .join("\n")
+    }
+
+    /// Constructor for an HTTP client with a timeout, base URL, default headers,
+    /// retries or auth.
+    fn http_fn(field: &ContextFieldInfo, http: &HttpOptions) -> String {
+        let mut lines = vec![
+            format!("func new{}() *http.Client {{", to_pascal_case(&field.name)),
+            "\treturn &http.Client{".to_string(),
+        ];
+        // gofmt doesn't align Timeout with the multi-line Transport after it
+        if let Some(timeout) = http.timeout {
+            lines.push(format!("\t\tTimeout: {},", duration(timeout)));
+        }
+        if Self::needs_transport(http) {
+            lines.push("\t\tTransport: &httpTransport{".to_string());
+            let mut entries = Vec::new();
+            if let Some(base_url) = &http.base_url {
+                entries.push(("baseURL".to_string(), go_string(base_url)));
+            }
+            if http.has_retries() {
+                let backoff = http.backoff.unwrap_or(Duration::from_secs(1));
+                entries.push(("retries".to_string(), http.retries.to_string()));
+                entries.push(("backoff".to_string(), duration(backoff)));
+            }
+            if !http.retry_on.is_empty() {
+                let statuses: Vec<String> = http.retry_on.iter().map(u16::to_string).collect();
+                entries.push((
+                    "retryOn".to_string(),
+                    format!("[]int{{{}}}", statuses.join(", ")),
+                ));
+            }
+            if let Some(auth) = &http.auth {
+                entries.push(("auth".to_string(), auth_fn(auth)));
+            }
+            lines.extend(align_entries(&entries, "\t\t\t"));
+            let mut headers: Vec<(String, String)> = http
+                .headers
+                .iter()
+                .map(|(name, value)| (canonical_header(name), value.clone()))
+                .collect();
+            if let Some(user_agent) = &http.user_agent {
+                headers.retain(|(name, _)| name != "User-Agent");
+                headers.push(("User-Agent".to_string(), user_agent.clone()));
+            }
+            if !headers.is_empty() {
+                headers.sort();
+                let entries: Vec<(String, String)> = headers
+                    .iter()
+                    .map(|(name, value)| (go_string(name), format!("{{{}}}", go_string(value))))
+                    .collect();
+                lines.push("\t\t\theader: http.Header{".to_string());
+                lines.extend(align_entries(&entries, "\t\t\t\t"));
+                lines.push("\t\t\t},".to_string());
+            }
+            lines.push("\t\t},".to_string());
+        }
+        lines.push("\t}".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn logger_fn(&self, field: &ContextFieldInfo) -> String {
+        let level = match self.log_level {
+            LogLevel::Trace => "slog.LevelDebug - 4",
+            LogLevel::Debug => "slog.LevelDebug",
+            LogLevel::Info => "slog.LevelInfo",
+            LogLevel::Warn => "slog.LevelWarn",
+            LogLevel::Error => "slog.LevelError",
+        };
+        let handler = match self.log_format {
+            LogFormat::Pretty => "NewTextHandler",
+            LogFormat::Json => "NewJSONHandler",
+        };
+        format!(
+            r#"func new{}() *slog.Logger {{
+	opts := &slog.HandlerOptions{{Level: {}}}
+	return slog.New(slog.{}(os.Stderr, opts))
+}}"#,
+            to_pascal_case(&field.name),
+            level,
+            handler
+        )
+    }
+}
+
+/// Applies a client's base URL, default headers and retries to each request.
+const HTTP_TRANSPORT: &str = r#"// httpTransport resolves relative request URLs against baseURL, adds the
+// default headers and credentials a request doesn't set and retries failed
+// requests.
+type httpTransport struct {
+	baseURL string
+	header  http.Header
+	retries int
+	backoff time.Duration
+	retryOn []int
+	auth    httpAuth
+}
+
+func (t *httpTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	req = req.Clone(req.Context())
+	if t.baseURL != "" && !req.URL.IsAbs() {
+		base, err := url.Parse(t.baseURL)
+		if err != nil {
+			return nil, err
+		}
+		u := base.JoinPath(req.URL.Path)
+		u.RawQuery = req.URL.RawQuery
+		req.URL = u
+		req.Host = u.Host
+	}
+	for key, values := range t.header {
+		if _, ok := req.Header[key]; !ok {
+			req.Header[key] = values
+		}
+	}
+	if t.auth != nil {
+		name, value, err := t.auth()
+		if err != nil {
+			return nil, err
+		}
+		if req.Header.Get(name) == "" {
+			req.Header.Set(name, value)
+		}
+	}
+	// A body that can't be rewound is only sent once
+	retries := t.retries
+	if req.Body != nil && req.Body != http.NoBody && req.GetBody == nil {
+		retries = 0
+	}
+	for attempt := 0; ; attempt++ {
+		resp, err := http.DefaultTransport.RoundTrip(req)
+		if attempt >= retries || !t.retryable(resp, err) {
+			return resp, err
+		}
+		if resp != nil {
+			resp.Body.Close()
+		}
+		select {
+		case <-req.Context().Done():
+			return nil, req.Context().Err()
+		case <-time.After(t.backoff << attempt):
+		}
+		if req.GetBody != nil {
+			body, err := req.GetBody()
+			if err != nil {
+				return nil, err
+			}
+			req.Body = body
+		}
+	}
+}
+
+// retryable reports whether a request is retried, on errors and on the
+// retryOn statuses, or 408, 429 and 5xx when none are set.
+func (t *httpTransport) retryable(resp *http.Response, err error) bool {
+	if err != nil {
+		return true
+	}
+	if t.retryOn == nil {
+		code := resp.StatusCode
+		return code == http.StatusRequestTimeout || code == http.StatusTooManyRequests || code >= 500
+	}
+	return slices.Contains(t.retryOn, resp.StatusCode)
+}"#;
+
+/// The message types of the LLM client.
+const LLM_TYPES: &str = r#"// Role says who a message is from.
+type Role string
+
+const (
+	RoleSystem    Role = "system"
+	RoleUser      Role = "user"
+	RoleAssistant Role = "assistant"
+)
+
+// Message is a message of a conversation.
+type Message struct {
+	Role    Role   `json:"role"`
+	Content string `json:"content"`
+}"#;
+
+/// Sends the LLM client's requests.
+const LLM_POST: &str = r#"// post sends body as JSON to path and decodes the response into reply,
+// failing with the API's message on error statuses.
+func (c *LlmClient) post(ctx context.Context, path string, header http.Header, body, reply any) error {
+	data, err := json.Marshal(body)
+	if err != nil {
+		return err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, c.baseURL+path, bytes.NewReader(data))
+	if err != nil {
+		return err
+	}
+	req.Header = header
+	req.Header.Set("Content-Type", "application/json")
+	resp, err := c.http.Do(req)
+	if err != nil {
+		return fmt.Errorf("llm: %w", err)
+	}
+	defer resp.Body.Close()
+	data, err = io.ReadAll(resp.Body)
+	if err != nil {
+		return fmt.Errorf("llm: %w", err)
+	}
+	if resp.StatusCode >= 300 {
+		var failure struct {
+			Error struct {
+				Message string `json:"message"`
+			} `json:"error"`
+		}
+		if json.Unmarshal(data, &failure) == nil && failure.Error.Message != "" {
+			return fmt.Errorf("llm: request failed (%s): %s", resp.Status, failure.Error.Message)
+		}
+		return fmt.Errorf("llm: request failed (%s): %s", resp.Status, data)
+	}
+	return json.Unmarshal(data, reply)
+}"#;
+
+/// Reads a client's credentials from the environment on each request.
+const HTTP_AUTH: &str = r#"// httpAuth returns the header carrying a client's credentials.
+type httpAuth func() (name, value string, err error)
+
+func authEnv(env string) (string, error) {
+	secret, ok := os.LookupEnv(env)
+	if !ok {
+		return "", fmt.Errorf("%s is not set", env)
+	}
+	return secret, nil
+}"#;
+
+const BEARER_AUTH: &str = r#"// bearerAuth sends the token in env as a bearer token.
+func bearerAuth(env string) httpAuth {
+	return func() (string, string, error) {
+		token, err := authEnv(env)
+		return "Authorization", "Bearer " + token, err
+	}
+}"#;
+
+const BASIC_AUTH: &str = r#"// basicAuth sends username and the password in env with basic auth.
+func basicAuth(username, env string) httpAuth {
+	return func() (string, string, error) {
+		password, err := authEnv(env)
+		credentials := base64.StdEncoding.EncodeToString([]byte(username + ":" + password))
+		return "Authorization", "Basic " + credentials, err
+	}
+}"#;
+
+const HEADER_AUTH: &str = r#"// headerAuth sends the secret in env as the header name.
+func headerAuth(name, env string) httpAuth {
+	return func() (string, string, error) {
+		secret, err := authEnv(env)
+		return name, secret, err
+	}
+}"#;
+
+/// Go expression building the `httpAuth` of a client.
+fn auth_fn(auth: &HttpAuth) -> String {
+    match auth {
+        HttpAuth::Bearer { env } => format!("bearerAuth({})", go_string(env)),
+        HttpAuth::Basic { username, env } => {
+            format!("basicAuth({}, {})", go_string(username), go_string(env))
+        }
+        HttpAuth::Header { name, env } => {
+            format!("headerAuth({}, {})", go_string(name), go_string(env))
+        }
+    }
+}
+
+/// The `host:port` target of a gRPC endpoint URL.
+fn grpc_target(endpoint: &str) -> &str {
+    let host = endpoint
+        .strip_prefix("https://")
+        .or_else(|| endpoint.strip_prefix("http://"))
+        .unwrap_or(endpoint);
+    host.split('/').next().unwrap_or(host)
+}
+
+/// Canonical form of a header name, as `http.CanonicalHeaderKey` returns it.
+fn canonical_header(name: &str) -> String {
+    name.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                }
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Go expression for a duration (e.g., `30000 * time.Millisecond`).
+fn duration(d: Duration) -> String {
+    format!("{} * time.Millisecond", d.as_millis())
+}
+
+impl GeneratedFile for ContextGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("internal").join("app").join("context.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = GoFile::new("app")
+            .generated()
+            .doc("Package app holds the context shared by command handlers.")
+            .import("context");
+
+        let mut constructors = Vec::new();
+        let mut transport = false;
+        let (mut bearer_auth, mut basic_auth, mut header_auth) = (false, false, false);
+        for field in &self.fields {
+            match field.field_type {
+                ContextFieldType::Database(DatabaseType::Postgres) => {
+                    file = file
+                        .import("fmt")
+                        .import("os")
+                        .import("github.com/jackc/pgx/v5/pgxpool");
+                    constructors.push(Self::postgres_fn(field));
+                }
+                ContextFieldType::Database(DatabaseType::Mysql) => {
+                    file = file
+                        .import("fmt")
+                        .import("os")
+                        .import("github.com/jmoiron/sqlx")
+                        .import_as("_", "github.com/go-sql-driver/mysql");
+                    let dsn = format!("os.Getenv({})", go_string(&field.env_var));
+                    constructors.push(Self::sqlx_fn(field, "mysql", dsn));
+                }
+                ContextFieldType::Database(DatabaseType::Sqlite) => {
+                    let dsn = Self::sqlite_dsn(field);
+                    file = file
+                        .import("fmt")
+                        .import("github.com/jmoiron/sqlx")
+                        .import_as("_", "modernc.org/sqlite");
+                    if dsn.contains("os.Getenv") {
+                        file = file.import("os");
+                    }
+                    constructors.push(Self::sqlx_fn(field, "sqlite", dsn));
+                }
+                ContextFieldType::Database(DatabaseType::Duckdb) => {
+                    let dsn = Self::duckdb_dsn(field);
+                    file = file
+                        .import("fmt")
+                        .import("github.com/jmoiron/sqlx")
+                        .import_as("_", "github.com/marcboeker/go-duckdb");
+                    if dsn.contains("os.Getenv") {
+                        file = file.import("os");
+                    }
+                    constructors.push(Self::sqlx_fn(field, "duckdb", dsn));
+                }
+                ContextFieldType::Http => {
+                    file = file.import("net/http");
+                    if let Some(http) = Self::http_options(field) {
+                        if http.timeout.is_some() {
+                            file = file.import("time");
+                        }
+                        if Self::needs_transport(http) {
+                            file = file.import("net/url").import("slices").import("time");
+                            transport = true;
+                        }
+                        match &http.auth {
+                            Some(HttpAuth::Bearer { .. }) => bearer_auth = true,
+                            Some(HttpAuth::Basic { .. }) => {
+                                file = file.import("encoding/base64");
+                                basic_auth = true;
+                            }
+                            Some(HttpAuth::Header { .. }) => header_auth = true,
+                            None => {}
+                        }
+                        constructors.push(Self::http_fn(field, http));
+                    }
+                }
+                ContextFieldType::Logger => {
+                    file = file.import("log/slog").import("os");
+                    constructors.push(self.logger_fn(field));
+                }
+                ContextFieldType::Storage => {
+                    file = file
+                        .import("fmt")
+                        .import("gocloud.dev/blob")
+                        .import_as("_", "gocloud.dev/blob/s3blob");
+                    let s3 = field.s3.as_ref();
+                    if s3.is_some_and(|s3| s3.region.is_some() || s3.endpoint.is_some()) {
+                        file = file.import("net/url");
+                    }
+                    constructors.push(Self::storage_fn(field));
+                }
+                ContextFieldType::Grpc => {
+                    let grpc = field.grpc.clone().unwrap_or_default();
+                    file = file.import("fmt").import("google.golang.org/grpc");
+                    if grpc.uses_tls() {
+                        file = file
+                            .import("crypto/tls")
+                            .import("google.golang.org/grpc/credentials");
+                    } else {
+                        file = file.import("google.golang.org/grpc/credentials/insecure");
+                    }
+                    if grpc.timeout.is_some() {
+                        file = file.import("time");
+                    }
+                    constructors.push(Self::grpc_fn(field, &grpc));
+                }
+                ContextFieldType::Email => {
+                    let email = field.email.clone().unwrap_or_default();
+                    file = file.import("fmt").import("github.com/wneessen/go-mail");
+                    if email.credentials().is_some() {
+                        file = file.import("os");
+                    }
+                    constructors.push(Self::email_fn(field, &email));
+                }
+                ContextFieldType::Config => {
+                    let config = field.config.clone().unwrap_or_default();
+                    file = file
+                        .import("errors")
+                        .import("fmt")
+                        .import("io/fs")
+                        .import("os")
+                        .import(match config.format {
+                            ConfigFormat::Toml => "github.com/BurntSushi/toml",
+                            ConfigFormat::Json => "encoding/json",
+                            ConfigFormat::Yaml => "gopkg.in/yaml.v3",
+                        });
+                    constructors.push(Self::config_fn(field, &config));
+                }
+                ContextFieldType::Secrets => {
+                    let secrets = field.secrets.clone().unwrap_or_default();
+                    file = file.import("fmt").import("os");
+                    match secrets.backend {
+                        SecretsBackend::Keyring => {
+                            file = file.import("github.com/zalando/go-keyring");
+                        }
+                        SecretsBackend::Env => {}
+                        SecretsBackend::File => {
+                            file = file.import("errors").import("io/fs").import("strings");
+                        }
+                    }
+                    constructors.push(Self::secrets_fn(field, &secrets));
+                }
+                ContextFieldType::Cache => {
+                    let cache = field.cache.clone().unwrap_or_default();
+                    file = file.import("github.com/hashicorp/golang-lru/v2/expirable");
+                    if cache.ttl.is_some() {
+                        file = file.import("time");
+                    }
+                    if cache.mode == CacheMode::Disk {
+                        file = file
+                            .import("crypto/sha256")
+                            .import("encoding/hex")
+                            .import("errors")
+                            .import("fmt")
+                            .import("io/fs")
+                            .import("os")
+                            .import("path/filepath")
+                            .import("strings");
+                    }
+                    constructors.push(Self::cache_fn(field, &cache));
+                }
+                ContextFieldType::Workdir => {
+                    let workdir = field.workdir.clone().unwrap_or_default();
+                    file = file.import("fmt").import("os");
+                    if workdir.data && workdir.path.is_none() {
+                        file = file.import("path/filepath").import("runtime");
+                    }
+                    constructors.push(Self::workdir_fn(field, &workdir));
+                }
+                ContextFieldType::Llm => {
+                    let Some(llm) = &field.llm else { continue };
+                    file = file
+                        .import("bytes")
+                        .import("encoding/json")
+                        .import("fmt")
+                        .import("io")
+                        .import("net/http")
+                        .import("os");
+                    if llm.provider == LlmProvider::Anthropic {
+                        file = file.import("strings");
+                    }
+                    constructors.push(Self::llm_fn(field, llm));
+                }
+                ContextFieldType::Queue(QueueType::Nats) => {
+                    file = file
+                        .import("fmt")
+                        .import("os")
+                        .import("github.com/nats-io/nats.go");
+                    constructors.push(Self::nats_fn(field));
+                }
+                ContextFieldType::Queue(QueueType::Kafka) => {
+                    file = file
+                        .import("os")
+                        .import("strings")
+                        .import("github.com/segmentio/kafka-go");
+                    constructors.push(Self::kafka_fn(field));
+                }
+                ContextFieldType::Custom => {}
+            }
+            if field.pool.idle_timeout.is_some() || field.pool.max_lifetime.is_some() {
+                file = file.import("time");
+            }
+        }
+        let has_auth = bearer_auth || basic_auth || header_auth;
+        if has_auth {
+            file = file.import("fmt").import("os");
+        }
+        if self.collects_close_errors() {
+            file = file.import("errors");
+        }
+        for import in self.resources.code.values().flat_map(|code| &code.imports) {
+            file = file.import(&import.module);
+        }
+
+        if !self.globals.is_empty() {
+            file = file.add(self.globals_decl());
+        }
+        let file = file
+            .add(self.struct_decl())
+            .add(self.new_fn())
+            .add(self.close_fn());
+        if transport {
+            constructors.push(HTTP_TRANSPORT.to_string());
+        }
+        if has_auth {
+            constructors.push(HTTP_AUTH.to_string());
+        }
+        for (used, helper) in [
+            (bearer_auth, BEARER_AUTH),
+            (basic_auth, BASIC_AUTH),
+            (header_auth, HEADER_AUTH),
+        ] {
+            if used {
+                constructors.push(helper.to_string());
+            }
+        }
+        constructors
+            .into_iter()
+            .fold(file, |file, block| file.add(block))
+            .render()
+    }
+}
@@ -0,0 +1,84 @@
+//! cmd/docs.go generator for the hidden `docs` subcommand.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile},
+    naming::go_string,
+};
+
+/// Name of the generated docs subcommand.
+pub const DOCS_COMMAND: &str = "docs";
+
+/// Flags and dispatch of the docs command; the pages come from cobra/doc.
+const DOCS: &str = r#"// docsCmd writes Markdown or man pages for every command. It is hidden from help.
+var docsCmd = &cobra.Command{
+	Use:    "docs",
+	Short:  "Write Markdown or man pages for every command",
+	Args:   cobra.NoArgs,
+	Hidden: true,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		format, _ := cmd.Flags().GetString("format")
+		out, _ := cmd.Flags().GetString("out")
+		if err := oneOf("--format", format, "markdown", "man"); err != nil {
+			return err
+		}
+		if err := os.MkdirAll(out, 0o755); err != nil {
+			return err
+		}
+		root := cmd.Root()
+		root.DisableAutoGenTag = true
+		var err error
+		if format == "man" {
+			err = doc.GenManTree(root, &doc.GenManHeader{Title: strings.ToUpper(root.Name()), Section: "1"}, out)
+		} else {
+			err = doc.GenMarkdownTree(root, out)
+		}
+		if err != nil {
+			return err
+		}
+		fmt.Printf("Wrote docs to %s\n", out)
+		return nil
+	},
+}"#;
+
+/// The `docs` subcommand, rendering the command tree with cobra/doc.
+///
+/// cobra/doc lives in the cobra module; `go mod tidy` adds its go-md2man
+/// dependency.
+pub struct DocsGo;
+
+impl DocsGo {
+    fn init_fn(&self) -> String {
+        format!(
+            "func init() {{\n\tflags := docsCmd.Flags()\n\tflags.String(\"format\", {}, \"Format of the pages (markdown, man)\")\n\tflags.String(\"out\", {}, \"Directory to write the pages to\")\n\trootCmd.AddCommand(docsCmd)\n}}",
+            go_string("markdown"),
+            go_string("docs"),
+        )
+    }
+}
+
+impl GeneratedFile for DocsGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("cmd").join("docs.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        GoFile::new("cmd")
+            .generated()
+            .import("fmt")
+            .import("os")
+            .import("strings")
+            .import("github.com/spf13/cobra")
+            .import("github.com/spf13/cobra/doc")
+            .add(DOCS)
+            .add(self.init_fn())
+            .render()
+    }
+}
@@ -0,0 +1,273 @@
+//! Generators for the `doctor` subcommand: the checks in internal/app and the
+//! command reporting them in cmd/doctor.go.
+
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::schema::ContextFieldInfo;
+use baobao_core::{ContextFieldType, DatabaseType, FileRules, GeneratedFile, to_pascal_case};
+use baobao_ir::SecretsBackend;
+
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile},
+    naming::go_string,
+};
+
+/// Name of the generated doctor subcommand.
+pub const DOCTOR_COMMAND: &str = "doctor";
+
+/// Runs every check with a timeout and prints a line for each.
+const DOCTOR: &str = r#"// doctorCmd checks that the environment and resources the CLI needs are in place.
+var doctorCmd = &cobra.Command{
+	Use:   "doctor",
+	Short: "Check that the environment and resources the CLI needs are in place",
+	Args:  cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		checks := app.Checks()
+		if len(checks) == 0 {
+			fmt.Println("Nothing to check")
+			return nil
+		}
+		failed := 0
+		for _, check := range checks {
+			ctx, cancel := context.WithTimeout(cmd.Context(), 10*time.Second)
+			err := check.Run(ctx)
+			cancel()
+			if err != nil {
+				failed++
+				fmt.Printf("✗ %s: %v\n", check.Name, err)
+			} else {
+				fmt.Printf("✓ %s\n", check.Name)
+			}
+		}
+		if failed > 0 {
+			return fmt.Errorf("%d of %d checks failed", failed, len(checks))
+		}
+		fmt.Printf("All %d checks passed\n", len(checks))
+		return nil
+	},
+}
+
+func init() {
+	rootCmd.AddCommand(doctorCmd)
+}"#;
+
+const CHECK: &str = r#"// Check is a runtime prerequisite reported by the doctor command.
+type Check struct {
+	Name string
+	Run  func(ctx context.Context) error
+}"#;
+
+const ENV_SET: &str = r#"func envSet(name string) func(context.Context) error {
+	return func(context.Context) error {
+		if os.Getenv(name) == "" {
+			return errors.New("not set")
+		}
+		return nil
+	}
+}"#;
+
+const REACHABLE: &str = r#"// reachable sends a HEAD request to url; any response means the endpoint is up.
+func reachable(url string) func(context.Context) error {
+	return func(ctx context.Context) error {
+		req, err := http.NewRequestWithContext(ctx, http.MethodHead, url, nil)
+		if err != nil {
+			return err
+		}
+		resp, err := http.DefaultClient.Do(req)
+		if err != nil {
+			return err
+		}
+		return resp.Body.Close()
+	}
+}"#;
+
+/// The internal/app/checks.go file listing the `doctor` checks.
+///
+/// Every environment variable a resource needs gets a check that it is set.
+/// Each database then gets one that it answers a ping, connected through the
+/// same constructor as `New`, each HTTP client with a base URL one that it
+/// answers a request, and the config and secrets files one that they load.
+pub struct ChecksGo {
+    pub fields: Vec<ContextFieldInfo>,
+}
+
+impl ChecksGo {
+    pub fn new(fields: Vec<ContextFieldInfo>) -> Self {
+        Self { fields }
+    }
+
+    /// Environment variables the resources need, in order, once each.
+    fn env_vars(&self) -> Vec<&str> {
+        let mut vars: Vec<&str> = Vec::new();
+        for var in self.fields.iter().flat_map(|f| f.required_env_vars()) {
+            if !vars.contains(&var) {
+                vars.push(var);
+            }
+        }
+        vars
+    }
+
+    /// Whether an HTTP client has a base URL to send a request to.
+    fn has_http_check(&self) -> bool {
+        self.fields.iter().any(|f| {
+            f.field_type == ContextFieldType::Http
+                && f.http.as_ref().is_some_and(|h| h.base_url.is_some())
+        })
+    }
+
+    /// The check of a resource beyond its environment variables, if it has one.
+    fn field_check(field: &ContextFieldInfo) -> Option<Vec<String>> {
+        match field.field_type {
+            ContextFieldType::Database(db_type) => {
+                let ping = match db_type {
+                    DatabaseType::Postgres => "Ping",
+                    _ => "PingContext",
+                };
+                Some(vec![
+                    format!(
+                        "\t\t{{Name: {}, Run: func(ctx context.Context) error {{",
+                        go_string(&format!("database `{}` is reachable", field.name))
+                    ),
+                    format!("\t\t\tdb, err := new{}(ctx)", to_pascal_case(&field.name)),
+                    "\t\t\tif err != nil {".to_string(),
+                    "\t\t\t\treturn err".to_string(),
+                    "\t\t\t}".to_string(),
+                    "\t\t\tdefer db.Close()".to_string(),
+                    format!("\t\t\treturn db.{}(ctx)", ping),
+                    "\t\t}},".to_string(),
+                ])
+            }
+            ContextFieldType::Http => {
+                let base_url = field.http.as_ref()?.base_url.as_deref()?;
+                Some(vec![format!(
+                    "\t\t{{Name: {}, Run: reachable({})}},",
+                    go_string(&format!("http `{}` is reachable", field.name)),
+                    go_string(base_url)
+                )])
+            }
+            ContextFieldType::Config => Some(Self::load_check(
+                &format!("config file `{}` parses", field.config.as_ref()?.path),
+                field,
+            )),
+            ContextFieldType::Secrets => {
+                let secrets = field.secrets.as_ref()?;
+                (secrets.backend == SecretsBackend::File).then(|| {
+                    Self::load_check(
+                        &format!("secrets file `{}` is readable", secrets.path),
+                        field,
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// A check that the constructor of `field` returns no error.
+    fn load_check(name: &str, field: &ContextFieldInfo) -> Vec<String> {
+        vec![
+            format!(
+                "\t\t{{Name: {}, Run: func(context.Context) error {{",
+                go_string(name)
+            ),
+            format!("\t\t\t_, err := new{}()", to_pascal_case(&field.name)),
+            "\t\t\treturn err".to_string(),
+            "\t\t}},".to_string(),
+        ]
+    }
+
+    fn checks(&self) -> Vec<String> {
+        let env_vars = self.env_vars().into_iter().map(|var| {
+            format!(
+                "\t\t{{Name: {}, Run: envSet({})}},",
+                go_string(&format!("{} is set", var)),
+                go_string(var)
+            )
+        });
+        env_vars
+            .chain(self.fields.iter().filter_map(Self::field_check).flatten())
+            .collect()
+    }
+
+    fn checks_fn(&self) -> String {
+        let mut lines = vec![
+            "// Checks lists the prerequisites of the resources declared in bao.toml.".to_string(),
+            "func Checks() []Check {".to_string(),
+        ];
+        let checks = self.checks();
+        if checks.is_empty() {
+            lines.push("\treturn nil".to_string());
+            lines.push("}".to_string());
+            return lines.join("\n");
+        }
+        lines.push("\treturn []Check{".to_string());
+        lines.extend(checks);
+        lines.push("\t}".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GeneratedFile for ChecksGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("internal").join("app").join("checks.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = GoFile::new("app").generated().import("context");
+        let env_vars = !self.env_vars().is_empty();
+        let http = self.has_http_check();
+        if env_vars {
+            file = file.import("errors").import("os");
+        }
+        if http {
+            file = file.import("net/http");
+        }
+        file = file.add(CHECK).add(self.checks_fn());
+        if env_vars {
+            file = file.add(ENV_SET);
+        }
+        if http {
+            file = file.add(REACHABLE);
+        }
+        file.render()
+    }
+}
+
+/// The `doctor` subcommand, reporting the checks from internal/app.
+pub struct DoctorGo {
+    module: String,
+}
+
+impl DoctorGo {
+    pub fn new(module: impl Into<String>) -> Self {
+        Self {
+            module: module.into(),
+        }
+    }
+}
+
+impl GeneratedFile for DoctorGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("cmd").join("doctor.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        GoFile::new("cmd")
+            .generated()
+            .import("context")
+            .import("fmt")
+            .import("time")
+            .import("github.com/spf13/cobra")
+            .local_import(format!("{}/internal/app", self.module))
+            .add(DOCTOR)
+            .render()
+    }
+}
@@ -0,0 +1,59 @@
+//! .gitignore generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+/// The .gitignore file for Go projects.
+pub struct GitIgnore {
+    /// Binary name produced by `go build`.
+    pub binary: String,
+}
+
+impl GitIgnore {
+    pub fn new(binary: impl Into<String>) -> Self {
+        Self {
+            binary: binary.into(),
+        }
+    }
+}
+
+impl GeneratedFile for GitIgnore {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join(".gitignore")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        format!(
+            r#"# Binaries
+/{}
+*.exe
+dist/
+
+# Test output
+*.test
+*.out
+
+# Environment
+.env
+.env.local
+.env.*.local
+
+# IDE
+.idea/
+.vscode/
+*.swp
+*.swo
+
+# OS
+.DS_Store
+Thumbs.db
+"#,
+            self.binary
+        )
+    }
+}
@@ -0,0 +1,67 @@
+//! go.mod generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use crate::{COBRA_VERSION, GO_VERSION, VIPER_VERSION};
+
+/// The go.mod file declaring the module and its direct requirements.
+///
+/// Created once: after the first bake, `go mod tidy` owns the requirement list.
+pub struct GoMod {
+    pub module: String,
+    /// Additional requirements as (module path, version).
+    pub requires: Vec<(String, String)>,
+}
+
+impl GoMod {
+    pub fn new(module: impl Into<String>) -> Self {
+        Self {
+            module: module.into(),
+            requires: Vec::new(),
+        }
+    }
+
+    /// Add a required module.
+    pub fn with_require(mut self, path: impl Into<String>, version: impl Into<String>) -> Self {
+        self.requires.push((path.into(), version.into()));
+        self
+    }
+}
+
+impl GeneratedFile for GoMod {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("go.mod")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        let mut requires = vec![
+            (
+                "github.com/spf13/cobra".to_string(),
+                COBRA_VERSION.to_string(),
+            ),
+            (
+                "github.com/spf13/viper".to_string(),
+                VIPER_VERSION.to_string(),
+            ),
+        ];
+        requires.extend(self.requires.iter().cloned());
+        requires.sort();
+        requires.dedup_by(|a, b| a.0 == b.0);
+
+        let require_lines: String = requires
+            .iter()
+            .map(|(path, version)| format!("\t{} {}\n", path, version))
+            .collect();
+
+        format!(
+            "module {}\n\ngo {}\n\nrequire (\n{})\n",
+            self.module, GO_VERSION, require_lines
+        )
+    }
+}
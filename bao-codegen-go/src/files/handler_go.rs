@@ -0,0 +1,82 @@
+//! Handler stub generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use crate::{
+    go_file::GoFile,
+    naming::{command_ident, file_stem},
+};
+
+/// Marker string indicating an unmodified Go handler stub.
+///
+/// Files containing this marker are considered safe to delete during cleanup.
+pub const STUB_MARKER: &str = "// TODO: implement";
+
+/// A handler stub file for a leaf command.
+pub struct HandlerGo {
+    /// Go module path of the generated project.
+    pub module: String,
+    pub name: String,
+    /// Path segments of the command (e.g., ["db", "migrate"])
+    pub path_segments: Vec<String>,
+    /// Example command lines, listed in the handler's doc comment
+    pub examples: Vec<String>,
+}
+
+impl HandlerGo {
+    pub fn new(
+        module: impl Into<String>,
+        name: impl Into<String>,
+        path_segments: Vec<String>,
+    ) -> Self {
+        Self {
+            module: module.into(),
+            name: name.into(),
+            path_segments,
+            examples: Vec::new(),
+        }
+    }
+
+    /// List the command's examples in the handler's doc comment.
+    pub fn with_examples(mut self, examples: Vec<String>) -> Self {
+        self.examples = examples;
+        self
+    }
+}
+
+impl GeneratedFile for HandlerGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join(format!("{}.go", file_stem(&self.path_segments)))
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        let ident = command_ident(&self.path_segments);
+        let command = self.path_segments.join(" ");
+        let examples: String = if self.examples.is_empty() {
+            String::new()
+        } else {
+            let lines: Vec<String> = self.examples.iter().map(|e| format!("//\t{}", e)).collect();
+            format!("\n//\n// Examples:\n//\n{}", lines.join("\n"))
+        };
+
+        GoFile::new("handlers")
+            .import("fmt")
+            .local_import(format!("{}/internal/app", self.module))
+            .local_import(format!("{}/internal/generated", self.module))
+            .add(format!(
+                r#"// {ident} handles `{name} {command}`.{examples}
+func {ident}(ctx *app.Context, args generated.{ident}Args) error {{
+	{STUB_MARKER}
+	return fmt.Errorf("{command}: not implemented")
+}}"#,
+                name = self.name,
+            ))
+            .render()
+    }
+}
@@ -0,0 +1,37 @@
+//! main.go generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use crate::go_file::GoFile;
+
+/// The main.go entry point, delegating to the generated `cmd` package.
+pub struct MainGo {
+    pub module: String,
+}
+
+impl MainGo {
+    pub fn new(module: impl Into<String>) -> Self {
+        Self {
+            module: module.into(),
+        }
+    }
+}
+
+impl GeneratedFile for MainGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("main.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        GoFile::new("main")
+            .local_import(format!("{}/cmd", self.module))
+            .add("func main() {\n\tcmd.Execute()\n}")
+            .render()
+    }
+}
@@ -0,0 +1,29 @@
+//! Go file generators.
+
+pub use crate::go_file::GO_GENERATED_HEADER;
+
+mod args_go;
+mod command_go;
+mod context_go;
+mod docs_go;
+mod doctor_go;
+mod gitignore;
+mod go_mod;
+mod handler_go;
+mod main_go;
+mod root_go;
+
+pub use args_go::ArgsGo;
+pub use baobao_codegen::generation::BaoToml;
+pub use command_go::CommandGo;
+pub(crate) use command_go::format_helper;
+pub use context_go::{
+    ContextGo, MYSQL_DRIVER_VERSION, PGX_VERSION, SQLITE_DRIVER_VERSION, SQLX_VERSION,
+};
+pub use docs_go::{DOCS_COMMAND, DocsGo};
+pub use doctor_go::{ChecksGo, DOCTOR_COMMAND, DoctorGo};
+pub use gitignore::GitIgnore;
+pub use go_mod::GoMod;
+pub use handler_go::{HandlerGo, STUB_MARKER};
+pub use main_go::MainGo;
+pub use root_go::RootGo;
@@ -0,0 +1,525 @@
+//! cmd/root.go generator for Go projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, to_pascal_case, to_snake_case};
+use baobao_ir::{Input, Locale};
+
+use super::command_go::{
+    choice_check, constraint_check, default_run_e, parse_list, register_flag, viper_getter,
+};
+use crate::{
+    go_file::{GO_GENERATED_HEADER, GoFile, align_entries},
+    naming::{flag_name, go_string, viper_key},
+};
+
+/// Selects the bundle for the user's locale and applies it to the command tree.
+const LOCALIZE: &str = r#"// localize applies the translations for the locale in LC_ALL, LC_MESSAGES
+// or LANG to the help text, matching the full locale (pt-BR) before its
+// language (pt). Text without a translation keeps the default locale.
+func localize(root *cobra.Command) {
+	messages := detectLocale()
+	if messages == nil {
+		return
+	}
+	var walk func(c *cobra.Command, path string)
+	walk = func(c *cobra.Command, path string) {
+		if text, ok := messages[path]; ok {
+			c.Short = text
+		}
+		for key, text := range messages {
+			name, ok := strings.CutPrefix(key, path+":")
+			if !ok {
+				continue
+			}
+			if f := c.Flags().Lookup(name); f != nil {
+				f.Usage = text
+			}
+			if f := c.PersistentFlags().Lookup(name); f != nil {
+				f.Usage = text
+			}
+		}
+		for _, child := range c.Commands() {
+			childPath := child.Name()
+			if path != "" {
+				childPath = path + " " + childPath
+			}
+			walk(child, childPath)
+		}
+	}
+	walk(root, "")
+}
+
+// detectLocale returns the messages for the user's locale, or nil.
+func detectLocale() map[string]string {
+	var locale string
+	for _, name := range []string{"LC_ALL", "LC_MESSAGES", "LANG"} {
+		if locale = os.Getenv(name); locale != "" {
+			break
+		}
+	}
+	if i := strings.IndexAny(locale, ".@"); i >= 0 {
+		locale = locale[:i]
+	}
+	locale = strings.ReplaceAll(locale, "_", "-")
+	language, _, _ := strings.Cut(locale, "-")
+	for _, name := range []string{locale, language} {
+		for key, messages := range locales {
+			if strings.EqualFold(strings.ReplaceAll(key, "_", "-"), name) {
+				return messages
+			}
+		}
+	}
+	return nil
+}"#;
+
+/// Reports the version with the commit and build time.
+const VERSION_STRING: &str = r#"// versionString is the version shown by --version, with the commit and build
+// time. The commit defaults to the VCS revision that go build embeds.
+func versionString() string {
+	revision, built := commit, buildDate
+	if info, ok := debug.ReadBuildInfo(); ok && revision == "" {
+		for _, setting := range info.Settings {
+			if setting.Key == "vcs.revision" {
+				revision = setting.Value
+			}
+		}
+	}
+	if revision == "" {
+		revision = "unknown"
+	}
+	if built == "" {
+		built = "unknown"
+	}
+	return fmt.Sprintf("%s (commit %s, built %s)", version, revision, built)
+}"#;
+
+/// Check an input's value against its bounds, length, pattern and existence
+/// on disk.
+const CONSTRAINT_HELPERS: &str = r#"// atLeast reports an error if value is below bound.
+func atLeast(name string, value, bound float64) error {
+	if value < bound {
+		return fmt.Errorf("invalid value %v for %s: must be at least %v", value, name, bound)
+	}
+	return nil
+}
+
+// atMost reports an error if value is above bound.
+func atMost(name string, value, bound float64) error {
+	if value > bound {
+		return fmt.Errorf("invalid value %v for %s: must be at most %v", value, name, bound)
+	}
+	return nil
+}
+
+// minLength reports an error if value has fewer than limit characters.
+func minLength(name, value string, limit int) error {
+	if utf8.RuneCountInString(value) < limit {
+		return fmt.Errorf("invalid value %q for %s: must be at least %d characters", value, name, limit)
+	}
+	return nil
+}
+
+// maxLength reports an error if value has more than limit characters.
+func maxLength(name, value string, limit int) error {
+	if utf8.RuneCountInString(value) > limit {
+		return fmt.Errorf("invalid value %q for %s: must be at most %d characters", value, name, limit)
+	}
+	return nil
+}
+
+// matches reports an error unless all of value matches pattern.
+func matches(name, value, pattern string) error {
+	if !regexp.MustCompile("^(?:" + pattern + ")$").MatchString(value) {
+		return fmt.Errorf("invalid value %q for %s: must match the pattern %s", value, name, pattern)
+	}
+	return nil
+}
+
+// pathExists reports an error unless value names an existing file or directory.
+func pathExists(name, value string) error {
+	if _, err := os.Stat(value); err != nil {
+		return fmt.Errorf("invalid value %q for %s: does not exist", value, name)
+	}
+	return nil
+}"#;
+
+/// Check the format of URL, duration, datetime and UUID inputs.
+const FORMAT_HELPERS: &str = r#"// uuidPattern matches a UUID in its hyphenated form.
+var uuidPattern = regexp.MustCompile(`^(?i)[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}$`)
+
+// isURL reports an error unless value is an absolute URL.
+func isURL(name, value string) error {
+	if u, err := url.Parse(value); err != nil || u.Scheme == "" {
+		return fmt.Errorf("invalid value %q for %s: expected a URL", value, name)
+	}
+	return nil
+}
+
+// isDuration reports an error unless value is a duration such as 1h30m.
+func isDuration(name, value string) error {
+	if _, err := time.ParseDuration(value); err != nil {
+		return fmt.Errorf("invalid value %q for %s: expected a duration such as 1h30m", value, name)
+	}
+	return nil
+}
+
+// isDateTime reports an error unless value is an RFC 3339 datetime.
+func isDateTime(name, value string) error {
+	if _, err := time.Parse(time.RFC3339, value); err != nil {
+		return fmt.Errorf("invalid value %q for %s: expected an RFC 3339 datetime", value, name)
+	}
+	return nil
+}
+
+// isUUID reports an error unless value is a UUID.
+func isUUID(name, value string) error {
+	if !uuidPattern.MatchString(value) {
+		return fmt.Errorf("invalid value %q for %s: expected a UUID", value, name)
+	}
+	return nil
+}"#;
+
+/// Count the members of an input group that are given.
+const GROUP_HELPERS: &str = r#"// exclusive reports an error if more than one of the named inputs is given.
+func exclusive(names []string, given ...bool) error {
+	count := 0
+	for _, g := range given {
+		if g {
+			count++
+		}
+	}
+	if count > 1 {
+		return fmt.Errorf("%s cannot be used together", strings.Join(names, ", "))
+	}
+	return nil
+}
+
+// oneRequired reports an error unless at least one of the named inputs is given.
+func oneRequired(names []string, given ...bool) error {
+	if !slices.Contains(given, true) {
+		return fmt.Errorf("one of %s is required", strings.Join(names, ", "))
+	}
+	return nil
+}"#;
+
+/// The root cobra command, viper environment binding and shared input helpers.
+///
+/// Global options are persistent flags on the root command; `readGlobals`
+/// copies them into the context's `Globals`. Translated help text is applied
+/// to the whole command tree by `localize` before it runs.
+pub struct RootGo {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub globals: Vec<Input>,
+    pub locales: Vec<Locale>,
+    pub build_info: bool,
+    /// Whether any input has value constraints, which need the check helpers.
+    pub constraints: bool,
+    /// Whether any input has a type whose format needs checking.
+    pub formats: bool,
+    /// Whether any command has input groups, which need the group helpers.
+    pub groups: bool,
+    /// Command run when the CLI is invoked without one.
+    pub default_command: Option<String>,
+}
+
+impl RootGo {
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            description,
+            globals: Vec::new(),
+            locales: Vec::new(),
+            build_info: false,
+            constraints: false,
+            formats: false,
+            groups: false,
+            default_command: None,
+        }
+    }
+
+    /// Include the helpers checking input groups.
+    pub fn with_groups(mut self, groups: bool) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Include the helpers checking inputs' value constraints.
+    pub fn with_constraints(mut self, constraints: bool) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Include the helpers checking the format of URL, duration, datetime and
+    /// UUID inputs.
+    pub fn with_formats(mut self, formats: bool) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Run `default_command` when the CLI is invoked without a command.
+    pub fn with_default_command(mut self, default_command: Option<String>) -> Self {
+        self.default_command = default_command;
+        self
+    }
+
+    /// Show the commit and build time in `--version`.
+    pub fn with_build_info(mut self, build_info: bool) -> Self {
+        self.build_info = build_info;
+        self
+    }
+
+    /// `version`, `commit` and `buildDate`, the latter two set with `-ldflags`.
+    fn version_vars(&self) -> String {
+        format!(
+            r#"// version is the manifest version. commit and buildDate are set at build time:
+//
+//	go build -ldflags "-X {module}/cmd.commit=$(git rev-parse --short HEAD) -X {module}/cmd.buildDate=$(date -u +%Y-%m-%dT%H:%M:%SZ)"
+var (
+	version   = {version}
+	commit    = ""
+	buildDate = ""
+)"#,
+            module = self.name,
+            version = go_string(&self.version),
+        )
+    }
+
+    /// Translate help text from the user's locale.
+    pub fn with_locales(mut self, locales: Vec<Locale>) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// `locales`, the translated help text keyed by command path, then `:`
+    /// and the flag name for flags.
+    fn locales_var(&self) -> String {
+        let mut lines = vec![
+            "// locales holds help text per locale, keyed by command path and flag name."
+                .to_string(),
+            "var locales = map[string]map[string]string{".to_string(),
+        ];
+        for locale in &self.locales {
+            lines.push(format!("\t{}: {{", go_string(&locale.name)));
+            let entries: Vec<_> = locale
+                .messages
+                .iter()
+                .map(|message| {
+                    let mut key = message.command.join(" ");
+                    if let Some(input) = &message.input {
+                        key = format!("{}:{}", key, flag_name(input));
+                    }
+                    (go_string(&key), go_string(&message.text))
+                })
+                .collect();
+            lines.extend(align_entries(&entries, "\t\t"));
+            lines.push("\t},".to_string());
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Register global options as persistent flags.
+    pub fn with_globals(mut self, globals: Vec<Input>) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    fn init_fn(&self) -> String {
+        let mut lines = vec![
+            "func init() {".to_string(),
+            format!("\tviper.SetEnvPrefix({})", go_string(&self.env_prefix())),
+            "\tviper.SetEnvKeyReplacer(strings.NewReplacer(\".\", \"_\", \"-\", \"_\"))"
+                .to_string(),
+            "\tviper.AutomaticEnv()".to_string(),
+        ];
+        if !self.globals.is_empty() {
+            lines.push(String::new());
+            lines.push("\tflags := rootCmd.PersistentFlags()".to_string());
+            for input in &self.globals {
+                lines.extend(register_flag(&[], input));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// `readGlobals`, filling the context's global options from viper.
+    fn read_globals_fn(&self) -> String {
+        let mut lines = vec![
+            "// readGlobals reads the global options shared by every command.".to_string(),
+            "func readGlobals(g *app.Globals) error {".to_string(),
+        ];
+        for input in &self.globals {
+            let value = format!(
+                "viper.{}({})",
+                viper_getter(input),
+                go_string(&viper_key(&[], &input.name))
+            );
+            let field = format!("g.{}", to_pascal_case(&input.name));
+            let assign = if input.multiple {
+                parse_list(input, &field, &value)
+            } else {
+                vec![format!("{} = {}", field, value)]
+            };
+            lines.extend(assign.into_iter().map(|line| format!("\t{}", line)));
+        }
+        for input in &self.globals {
+            let guard = format!("viper.IsSet({})", go_string(&viper_key(&[], &input.name)));
+            lines.extend(
+                choice_check(input, "g")
+                    .into_iter()
+                    .chain(constraint_check(input, "g", Some(&guard)))
+                    .map(|line| format!("\t{}", line)),
+            );
+        }
+        lines.push("\treturn nil".to_string());
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    /// Prefix for environment variables bound to flags (e.g., `my-app` -> `MY_APP`).
+    fn env_prefix(&self) -> String {
+        to_snake_case(&self.name).to_uppercase()
+    }
+
+    fn root_command(&self) -> String {
+        let mut entries = vec![("Use".to_string(), go_string(&self.name))];
+        if let Some(description) = &self.description {
+            entries.push(("Short".to_string(), go_string(description)));
+        }
+        let version = if self.build_info {
+            "versionString()".to_string()
+        } else {
+            go_string(&self.version)
+        };
+        entries.push(("Version".to_string(), version));
+        entries.push(("SilenceUsage".to_string(), "true".to_string()));
+        if self.default_command.is_some() {
+            entries.push(("Args".to_string(), "cobra.NoArgs".to_string()));
+        }
+
+        let mut lines = vec!["var rootCmd = &cobra.Command{".to_string()];
+        lines.extend(align_entries(&entries, "\t"));
+        if let Some(name) = &self.default_command {
+            lines.extend(default_run_e(std::slice::from_ref(name)));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GeneratedFile for RootGo {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("cmd").join("root.go")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GO_GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = GoFile::new("cmd")
+            .generated()
+            .doc("Package cmd wires the cobra command tree.")
+            .import("fmt")
+            .import("os")
+            .import("slices")
+            .import("strconv")
+            .import("strings")
+            .import("github.com/spf13/cobra")
+            .import("github.com/spf13/viper");
+        if self.build_info {
+            file = file.import("runtime/debug").add(self.version_vars());
+        }
+        if self.constraints {
+            file = file.import("regexp").import("unicode/utf8");
+        }
+        if self.formats {
+            file = file.import("net/url").import("regexp").import("time");
+        }
+        file = file.add(self.root_command()).add(self.init_fn());
+        if !self.globals.is_empty() {
+            file = file
+                .local_import(format!("{}/internal/app", self.name))
+                .add(self.read_globals_fn());
+        }
+        let localize = if self.locales.is_empty() {
+            ""
+        } else {
+            "\tlocalize(rootCmd)\n"
+        };
+        file = file.add(format!(
+            r#"// Execute runs the command tree and exits with status 1 on error.
+func Execute() {{
+{}	if err := rootCmd.Execute(); err != nil {{
+		os.Exit(1)
+	}}
+}}"#,
+            localize
+        ));
+        if !self.locales.is_empty() {
+            file = file.add(self.locales_var()).add(LOCALIZE);
+        }
+        if self.build_info {
+            file = file.add(VERSION_STRING);
+        }
+        file = file
+            .add(
+                r#"// oneOf reports an error unless value is empty or one of choices.
+func oneOf(name, value string, choices ...string) error {
+	if value == "" || slices.Contains(choices, value) {
+		return nil
+	}
+	return fmt.Errorf("invalid value %q for %s: expected one of %s", value, name, strings.Join(choices, ", "))
+}"#,
+            )
+            .add(
+                r#"// parseInt parses a positional argument as a base-10 integer.
+func parseInt(name, value string) (int64, error) {
+	n, err := strconv.ParseInt(value, 10, 64)
+	if err != nil {
+		return 0, fmt.Errorf("invalid value %q for %s: expected an integer", value, name)
+	}
+	return n, nil
+}"#,
+            )
+            .add(
+                r#"// parseFloat parses a positional argument as a floating-point number.
+func parseFloat(name, value string) (float64, error) {
+	f, err := strconv.ParseFloat(value, 64)
+	if err != nil {
+		return 0, fmt.Errorf("invalid value %q for %s: expected a number", value, name)
+	}
+	return f, nil
+}"#,
+            )
+            .add(
+                r#"// parseBool parses a positional argument as a boolean.
+func parseBool(name, value string) (bool, error) {
+	b, err := strconv.ParseBool(value)
+	if err != nil {
+		return false, fmt.Errorf("invalid value %q for %s: expected true or false", value, name)
+	}
+	return b, nil
+}"#,
+            );
+        if self.constraints {
+            file = file.add(CONSTRAINT_HELPERS);
+        }
+        if self.formats {
+            file = file.add(FORMAT_HELPERS);
+        }
+        if self.groups {
+            file = file.add(GROUP_HELPERS);
+        }
+        file.render()
+    }
+}
@@ -0,0 +1,424 @@
+//! Go code generator using cobra and viper.
+
+use std::{collections::HashSet, path::Path};
+
+use baobao_codegen::{
+    adapters::RenderedResources,
+    generation::{FileCategory, FileEntry, FileRegistry, HandlerPaths, find_orphan_commands},
+    language::{CleanResult, GenerateResult, LanguageCodegen, PreviewFile},
+    pipeline::CompilationContext,
+    schema::ComputedData,
+};
+use baobao_core::{GeneratedFile, OverwritePrompt, relative_display};
+use baobao_ir::{AppIR, CommandOp, Input, InputKind, InputType, IrVisitor, walk_command};
+use eyre::Result;
+
+use crate::{
+    TERM_VERSION,
+    files::{
+        ArgsGo, ChecksGo, CommandGo, ContextGo, DOCS_COMMAND, DOCTOR_COMMAND, DocsGo, DoctorGo,
+        GitIgnore, GoMod, HandlerGo, MainGo, RootGo, STUB_MARKER, format_helper,
+    },
+    naming::file_stem,
+};
+
+/// Go code generator that produces a cobra + viper CLI module.
+pub struct Generator {
+    ir: AppIR,
+    computed: ComputedData,
+    resources: RenderedResources,
+}
+
+impl LanguageCodegen for Generator {
+    fn language(&self) -> &'static str {
+        "go"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "go"
+    }
+
+    fn preview(&self) -> Vec<PreviewFile> {
+        self.preview_files()
+    }
+
+    fn generate_with_prompt(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.generate_files(output_dir, prompt)
+    }
+
+    fn files(&self) -> Result<Vec<FileEntry>> {
+        self.resources.check()?;
+        let mut files: Vec<FileEntry> = self.build_registry().entries().cloned().collect();
+        files.extend(self.handler_entries());
+        Ok(files)
+    }
+
+    fn clean(&self, output_dir: &Path) -> Result<CleanResult> {
+        self.clean_files(output_dir, false)
+    }
+
+    fn preview_clean(&self, output_dir: &Path) -> Result<CleanResult> {
+        self.clean_files(output_dir, true)
+    }
+}
+
+impl Generator {
+    /// Create a generator from a compilation context.
+    ///
+    /// Use `Pipeline::run()` to create the context, then pass it here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context doesn't have IR or computed data
+    /// (i.e., if the pipeline didn't run successfully).
+    pub fn from_context(mut ctx: CompilationContext) -> Self {
+        let computed = ctx.take_computed();
+        let resources = ctx
+            .resource_adapters
+            .render_all("go", &computed.context_fields);
+        Self {
+            ir: ctx.take_ir(),
+            computed,
+            resources,
+        }
+    }
+
+    /// Go module path, taken from the CLI name.
+    fn module(&self) -> &str {
+        &self.ir.meta.name
+    }
+
+    /// Global inputs rendered as persistent flags; positionals cannot be global.
+    fn global_flags(&self) -> Vec<Input> {
+        self.ir
+            .global_inputs
+            .iter()
+            .filter(|input| matches!(input.kind, InputKind::Flag { .. }))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether any command has input groups.
+    fn has_groups(&self) -> bool {
+        struct Groups(bool);
+
+        impl IrVisitor<'_> for Groups {
+            fn visit_command(&mut self, cmd: &CommandOp) {
+                self.0 |= !cmd.groups.is_empty();
+                walk_command(self, cmd);
+            }
+        }
+
+        let mut groups = Groups(false);
+        groups.visit_app(&self.ir);
+        groups.0
+    }
+
+    /// Whether any input has value constraints.
+    fn has_constraints(&self) -> bool {
+        struct Constraints(bool);
+
+        impl IrVisitor<'_> for Constraints {
+            fn visit_input(&mut self, input: &Input) {
+                self.0 |= !input.constraints.is_empty();
+            }
+        }
+
+        let mut constraints = Constraints(false);
+        constraints.visit_app(&self.ir);
+        constraints.0
+    }
+
+    /// Whether any input is a secret, read with a hidden prompt.
+    fn has_secrets(&self) -> bool {
+        struct Secrets(bool);
+
+        impl IrVisitor<'_> for Secrets {
+            fn visit_input(&mut self, input: &Input) {
+                self.0 |= input.ty == InputType::Secret;
+            }
+        }
+
+        let mut secrets = Secrets(false);
+        secrets.visit_app(&self.ir);
+        secrets.0
+    }
+
+    /// Whether any input has a type whose format needs checking.
+    fn has_formats(&self) -> bool {
+        struct Formats(bool);
+
+        impl IrVisitor<'_> for Formats {
+            fn visit_input(&mut self, input: &Input) {
+                self.0 |= format_helper(input.ty).is_some();
+            }
+        }
+
+        let mut formats = Formats(false);
+        formats.visit_app(&self.ir);
+        formats.0
+    }
+
+    fn context_go(&self) -> ContextGo {
+        let context = ContextGo::new(self.computed.context_fields.clone())
+            .with_resources(self.resources.clone())
+            .with_globals(self.global_flags());
+        match self.ir.logger() {
+            Some(logger) => context.with_logger(logger.level, logger.format),
+            None => context,
+        }
+    }
+
+    /// Build a file registry with all generated files.
+    fn build_registry(&self) -> FileRegistry {
+        let mut registry = FileRegistry::new();
+        let context = self.context_go();
+
+        // Config files (created once)
+        let mut go_mod = context
+            .requires()
+            .into_iter()
+            .fold(GoMod::new(self.module()), |go_mod, (path, version)| {
+                go_mod.with_require(path, version)
+            });
+        if self.has_secrets() {
+            go_mod = go_mod.with_require("golang.org/x/term", TERM_VERSION);
+        }
+        registry.register(FileEntry::from_generated(
+            "go.mod",
+            &go_mod,
+            FileCategory::Config,
+        ));
+        registry.register(FileEntry::from_generated(
+            ".gitignore",
+            &GitIgnore::new(&self.ir.meta.name),
+            FileCategory::Config,
+        ));
+
+        // Infrastructure files
+        registry.register(FileEntry::from_generated(
+            "main.go",
+            &MainGo::new(self.module()),
+            FileCategory::Infrastructure,
+        ));
+        registry.register(FileEntry::infrastructure(
+            "internal/app/context.go",
+            context.render(),
+        ));
+
+        // Generated code
+        let commands: Vec<CommandOp> = self.ir.commands().cloned().collect();
+        registry.register(FileEntry::generated(
+            "cmd/root.go",
+            RootGo::new(
+                &self.ir.meta.name,
+                &self.ir.meta.version,
+                self.ir.meta.description.clone(),
+            )
+            .with_globals(self.global_flags())
+            .with_locales(self.ir.locales.clone())
+            .with_build_info(self.ir.meta.build_info)
+            .with_constraints(self.has_constraints())
+            .with_formats(self.has_formats())
+            .with_groups(self.has_groups())
+            .with_default_command(self.ir.meta.default_command.clone())
+            .render(),
+        ));
+        registry.register(FileEntry::generated(
+            "internal/generated/args.go",
+            ArgsGo::new(&self.ir.meta.name, commands).render(),
+        ));
+        for cmd in self.ir.commands() {
+            self.register_command_files(&mut registry, cmd, &[]);
+        }
+        if self.ir.meta.docs_command && !self.ir.commands().any(|c| c.name == DOCS_COMMAND) {
+            registry.register(FileEntry::generated("cmd/docs.go", DocsGo.render()));
+        }
+        if self.ir.meta.doctor_command && !self.ir.commands().any(|c| c.name == DOCTOR_COMMAND) {
+            registry.register(FileEntry::generated(
+                "internal/app/checks.go",
+                ChecksGo::new(self.computed.context_fields.clone()).render(),
+            ));
+            registry.register(FileEntry::generated(
+                "cmd/doctor.go",
+                DoctorGo::new(self.module()).render(),
+            ));
+        }
+
+        if let Some(header) = &self.ir.meta.header {
+            registry.prepend_header(header);
+        }
+
+        registry
+    }
+
+    /// Recursively register one cmd/*.go file per command.
+    fn register_command_files(
+        &self,
+        registry: &mut FileRegistry,
+        cmd: &CommandOp,
+        ancestors: &[CommandOp],
+    ) {
+        registry.register(FileEntry::generated(
+            format!("cmd/{}.go", file_stem(&cmd.path)),
+            CommandGo::new(self.module(), cmd.clone(), ancestors.to_vec())
+                .with_globals(!self.global_flags().is_empty())
+                .with_cli(&self.ir.meta.name)
+                .render(),
+        ));
+
+        let mut child_ancestors = ancestors.to_vec();
+        child_ancestors.push(cmd.clone());
+        for child in &cmd.children {
+            self.register_command_files(registry, child, &child_ancestors);
+        }
+    }
+
+    /// Preview generated files without writing to disk.
+    fn preview_files(&self) -> Vec<PreviewFile> {
+        self.build_registry()
+            .preview()
+            .into_iter()
+            .map(|entry| PreviewFile {
+                path: entry.path,
+                content: entry.content,
+            })
+            .collect()
+    }
+
+    /// Generate all files into the specified output directory.
+    fn generate_files(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.resources.check()?;
+        let stats = self
+            .build_registry()
+            .write_all_with_prompt(output_dir, prompt)?;
+        let mut result = self.generate_handlers(output_dir)?;
+        result.files = stats.files;
+        result.checksums = stats.checksums;
+        Ok(result)
+    }
+
+    /// All commands in the tree, parents before their children.
+    fn all_commands(&self) -> Vec<&CommandOp> {
+        struct Commands<'ir>(Vec<&'ir CommandOp>);
+
+        impl<'ir> IrVisitor<'ir> for Commands<'ir> {
+            fn visit_command(&mut self, cmd: &'ir CommandOp) {
+                self.0.push(cmd);
+                walk_command(self, cmd);
+            }
+        }
+
+        let mut commands = Commands(Vec::new());
+        commands.visit_app(&self.ir);
+        commands.0
+    }
+
+    /// File stems of all commands (cmd/*.go), plus the root command.
+    fn expected_commands(&self) -> HashSet<String> {
+        let mut expected: HashSet<String> = self
+            .all_commands()
+            .into_iter()
+            .map(|cmd| file_stem(&cmd.path))
+            .collect();
+        expected.insert("root".to_string());
+        expected
+    }
+
+    /// File stems of command handlers (handlers/*.go).
+    fn expected_handlers(&self) -> HashSet<String> {
+        self.computed
+            .command_paths
+            .iter()
+            .map(|path| {
+                let segments: Vec<String> = path.split('/').map(String::from).collect();
+                file_stem(&segments)
+            })
+            .collect()
+    }
+
+    /// Handler stubs for every leaf command (handlers/*.go).
+    fn handler_entries(&self) -> Vec<FileEntry> {
+        self.all_commands()
+            .into_iter()
+            .filter(|cmd| !cmd.has_subcommands())
+            .map(|cmd| {
+                FileEntry::from_generated(
+                    format!("handlers/{}.go", file_stem(&cmd.path)),
+                    &HandlerGo::new(self.module(), &self.ir.meta.name, cmd.path.clone())
+                        .with_examples(
+                            cmd.docs
+                                .examples
+                                .iter()
+                                .map(|e| e.command.clone())
+                                .collect(),
+                        ),
+                    FileCategory::Handler,
+                )
+            })
+            .collect()
+    }
+
+    /// Write stubs for missing handlers and report unused ones.
+    fn generate_handlers(&self, output_dir: &Path) -> Result<GenerateResult> {
+        let handlers_dir = output_dir.join("handlers");
+        std::fs::create_dir_all(&handlers_dir)?;
+
+        let mut created_handlers = Vec::new();
+        for entry in self.handler_entries() {
+            if entry.write(output_dir)?.is_created() {
+                created_handlers.push(entry.path.trim_start_matches("handlers/").to_string());
+            }
+        }
+
+        let handler_paths = HandlerPaths::new(&handlers_dir, "go", STUB_MARKER);
+        let orphan_handlers = handler_paths.find_orphans(&self.expected_handlers())?;
+
+        Ok(GenerateResult {
+            created_handlers,
+            orphan_handlers,
+            ..Default::default()
+        })
+    }
+
+    /// Find orphaned command files and handler stubs, deleting them unless `dry_run`.
+    ///
+    /// Handlers that no longer contain the stub marker are kept and reported as skipped.
+    fn clean_files(&self, output_dir: &Path, dry_run: bool) -> Result<CleanResult> {
+        let mut result = CleanResult::default();
+
+        let commands_dir = output_dir.join("cmd");
+        for path in find_orphan_commands(&commands_dir, "go", &self.expected_commands())? {
+            if !dry_run {
+                std::fs::remove_file(&path)?;
+            }
+            result
+                .deleted_commands
+                .push(relative_display(&path, output_dir));
+        }
+
+        let handlers_dir = output_dir.join("handlers");
+        let handler_paths = HandlerPaths::new(&handlers_dir, "go", STUB_MARKER);
+        for orphan in handler_paths.find_orphans_with_status(&self.expected_handlers())? {
+            let display = format!("handlers/{}.go", orphan.relative_path);
+            if orphan.is_unmodified {
+                if !dry_run {
+                    std::fs::remove_file(&orphan.full_path)?;
+                }
+                result.deleted_handlers.push(display);
+            } else {
+                result.skipped_handlers.push(display);
+            }
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,63 @@
+//! Go code generator for Bao CLI generator.
+//!
+//! This crate generates Go CLI applications using [cobra](https://github.com/spf13/cobra)
+//! for command parsing and [viper](https://github.com/spf13/viper) for binding flags to
+//! environment variables.
+//!
+//! # Usage
+//!
+//! This crate is used internally by the `baobao` CLI tool. You typically don't need
+//! to use it directly.
+//!
+//! ```ignore
+//! use baobao_codegen_go::Generator;
+//! use baobao_codegen::{LanguageCodegen, pipeline::Pipeline};
+//! use baobao_manifest::Manifest;
+//! use std::path::Path;
+//!
+//! let manifest = Manifest::from_file("bao.toml")?;
+//! let ctx = Pipeline::new().run(manifest)?;
+//! let generator = Generator::from_context(ctx);
+//!
+//! // Preview files without writing
+//! let files = generator.preview();
+//!
+//! // Generate files to disk
+//! let result = generator.generate(Path::new("output"))?;
+//! ```
+//!
+//! # Generated Output
+//!
+//! The generator produces a Go module:
+//!
+//! - `cmd/root.go` - Root cobra command, viper setup and input helpers
+//! - `cmd/*.go` - One cobra command per manifest command
+//! - `internal/app/context.go` - Shared context (pgx/sqlx pools, HTTP clients, logger)
+//! - `internal/generated/args.go` - Typed inputs passed to handlers
+//! - `handlers/*.go` - Handler stubs for implementation
+//! - `main.go`, `go.mod`, `bao.toml`, `.gitignore`
+
+/// Go language version declared in the generated go.mod.
+pub const GO_VERSION: &str = "1.22";
+
+/// Target cobra version for generated code.
+pub const COBRA_VERSION: &str = "v1.8.1";
+
+/// Target viper version for generated code.
+pub const VIPER_VERSION: &str = "v1.19.0";
+
+/// Target golang.org/x/term version, used to read secrets without echoing.
+pub const TERM_VERSION: &str = "v0.22.0";
+
+mod generator;
+mod go_file;
+mod naming;
+mod type_mapper;
+
+pub mod files;
+
+pub use baobao_codegen::language::{GenerateResult, LanguageCodegen, PreviewFile};
+pub use generator::Generator;
+pub use go_file::GoFile;
+pub use naming::GO_NAMING;
+pub use type_mapper::{GO_TYPES, GoTypeMapper};
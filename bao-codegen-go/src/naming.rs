@@ -0,0 +1,197 @@
+//! Go-specific naming conventions.
+
+use baobao_codegen::language::NamingConvention;
+use baobao_core::{to_camel_case, to_kebab_case, to_pascal_case, to_snake_case};
+
+fn escape_go_reserved(name: &str) -> String {
+    format!("{}_", name)
+}
+
+/// Go naming conventions.
+pub const GO_NAMING: NamingConvention = NamingConvention {
+    // Exported types use PascalCase
+    command_to_type: to_pascal_case,
+    // Files use snake_case
+    command_to_file: to_snake_case,
+    // Exported struct fields use PascalCase
+    field_to_name: to_pascal_case,
+    reserved_words: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ],
+    escape_reserved: escape_go_reserved,
+};
+
+/// File name suffixes the Go toolchain treats as build constraints.
+///
+/// A file named `db_test.go` or `sync_windows.go` would be silently excluded
+/// from regular builds, so command files ending this way get a `_cmd` suffix.
+const CONSTRAINED_SUFFIXES: &[&str] = &[
+    "test",
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "js",
+    "linux",
+    "nacl",
+    "netbsd",
+    "openbsd",
+    "plan9",
+    "solaris",
+    "wasip1",
+    "windows",
+    "zos",
+    "386",
+    "amd64",
+    "arm",
+    "arm64",
+    "loong64",
+    "mips",
+    "mipsle",
+    "mips64",
+    "mips64le",
+    "ppc64",
+    "ppc64le",
+    "riscv64",
+    "s390x",
+    "wasm",
+];
+
+/// Exported identifier for a command path (e.g., `["db", "migrate"]` -> `DbMigrate`).
+pub(crate) fn command_ident(path: &[String]) -> String {
+    to_pascal_case(&path.join("_"))
+}
+
+/// Package-level cobra command variable (e.g., `["db", "migrate"]` -> `dbMigrateCmd`).
+pub(crate) fn command_var(path: &[String]) -> String {
+    format!("{}Cmd", to_camel_case(&path.join("_")))
+}
+
+/// File stem for a command path (e.g., `["db", "migrate"]` -> `db_migrate`).
+pub(crate) fn file_stem(path: &[String]) -> String {
+    let stem = path
+        .iter()
+        .map(|s| to_snake_case(s))
+        .collect::<Vec<_>>()
+        .join("_");
+    match stem.rsplit_once('_') {
+        Some((_, suffix)) if CONSTRAINED_SUFFIXES.contains(&suffix) => format!("{}_cmd", stem),
+        _ => stem,
+    }
+}
+
+/// Command-line flag name (always kebab-case, e.g., `dry_run` -> `dry-run`).
+pub(crate) fn flag_name(name: &str) -> String {
+    to_kebab_case(name)
+}
+
+/// Viper key for a flag, scoped by the command that declares it
+/// (e.g., `["db", "migrate"]` + `dry_run` -> `db.migrate.dry_run`).
+///
+/// With the env key replacer in `cmd/root.go` this resolves to
+/// `<PREFIX>_DB_MIGRATE_DRY_RUN`.
+pub(crate) fn viper_key(path: &[String], name: &str) -> String {
+    path.iter()
+        .map(|s| to_snake_case(s))
+        .chain(std::iter::once(to_snake_case(name)))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Quote a string as a Go interpreted string literal.
+pub(crate) fn go_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_go_naming_type() {
+        assert_eq!(GO_NAMING.type_name("hello_world"), "HelloWorld");
+        assert_eq!(GO_NAMING.type_name("get-user"), "GetUser");
+    }
+
+    #[test]
+    fn test_go_reserved_words() {
+        assert!(GO_NAMING.is_reserved("func"));
+        assert!(GO_NAMING.is_reserved("range"));
+        assert!(!GO_NAMING.is_reserved("hello"));
+        assert_eq!(GO_NAMING.safe_name("type"), "type_");
+    }
+
+    #[test]
+    fn test_command_identifiers() {
+        let p = path(&["db", "run-migrations"]);
+        assert_eq!(command_ident(&p), "DbRunMigrations");
+        assert_eq!(command_var(&p), "dbRunMigrationsCmd");
+        assert_eq!(file_stem(&p), "db_run_migrations");
+    }
+
+    #[test]
+    fn test_file_stem_avoids_build_constraints() {
+        assert_eq!(file_stem(&path(&["db", "test"])), "db_test_cmd");
+        assert_eq!(file_stem(&path(&["deploy", "linux"])), "deploy_linux_cmd");
+        assert_eq!(file_stem(&path(&["test"])), "test");
+    }
+
+    #[test]
+    fn test_viper_key_and_flag_name() {
+        assert_eq!(
+            viper_key(&path(&["db", "migrate"]), "dry_run"),
+            "db.migrate.dry_run"
+        );
+        assert_eq!(flag_name("dry_run"), "dry-run");
+    }
+
+    #[test]
+    fn test_go_string_escapes() {
+        assert_eq!(go_string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+    }
+}
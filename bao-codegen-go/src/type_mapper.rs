@@ -0,0 +1,99 @@
+//! Go type mapper implementation.
+
+use std::sync::LazyLock;
+
+use baobao_codegen::language::{ScalarType, TypeMapper, TypeRegistry};
+use baobao_core::{ArgType, ContextFieldType, DatabaseType, QueueType};
+
+/// Go types for each argument type. Positional arguments arrive as strings,
+/// so numeric and boolean types convert through helpers in `cmd/root.go`;
+/// the parse snippet receives the argument display name and the raw value.
+/// URLs, durations, datetimes and UUIDs stay strings whose format is checked
+/// by helpers in `cmd/root.go`.
+pub static GO_TYPES: LazyLock<TypeRegistry> = LazyLock::new(|| {
+    TypeRegistry::new()
+        .register(ScalarType::new(ArgType::String, "string"))
+        .register(ScalarType::new(ArgType::Int, "int64").with_parse("parseInt({})"))
+        .register(ScalarType::new(ArgType::Float, "float64").with_parse("parseFloat({})"))
+        .register(ScalarType::new(ArgType::Bool, "bool").with_parse("parseBool({})"))
+        .register(ScalarType::new(ArgType::Path, "string"))
+        .register(ScalarType::new(ArgType::Count, "int"))
+        .register(ScalarType::new(ArgType::Url, "string"))
+        .register(ScalarType::new(ArgType::Duration, "string"))
+        .register(ScalarType::new(ArgType::DateTime, "string"))
+        .register(ScalarType::new(ArgType::Uuid, "string"))
+        .register(ScalarType::new(ArgType::Secret, "string"))
+});
+
+/// Go type mapper implementation.
+pub struct GoTypeMapper;
+
+impl TypeMapper for GoTypeMapper {
+    fn language(&self) -> &'static str {
+        "go"
+    }
+
+    fn map_arg_type(&self, arg_type: ArgType) -> &'static str {
+        GO_TYPES.type_name(arg_type)
+    }
+
+    fn map_optional_arg_type(&self, arg_type: ArgType) -> String {
+        format!("*{}", self.map_arg_type(arg_type))
+    }
+
+    fn map_context_type(&self, field_type: &ContextFieldType) -> &'static str {
+        match field_type {
+            ContextFieldType::Database(DatabaseType::Postgres) => "*pgxpool.Pool",
+            ContextFieldType::Database(DatabaseType::Mysql) => "*sqlx.DB",
+            ContextFieldType::Database(DatabaseType::Sqlite) => "*sqlx.DB",
+            ContextFieldType::Database(DatabaseType::Duckdb) => "*sqlx.DB",
+            ContextFieldType::Http => "*http.Client",
+            ContextFieldType::Logger => "*slog.Logger",
+            ContextFieldType::Storage => "*blob.Bucket",
+            ContextFieldType::Grpc => "*grpc.ClientConn",
+            ContextFieldType::Email => "*mail.Client",
+            ContextFieldType::Config => "*Config",
+            ContextFieldType::Secrets => "*Secrets",
+            ContextFieldType::Llm => "*LlmClient",
+            ContextFieldType::Cache => "*expirable.LRU[string, string]",
+            ContextFieldType::Workdir => "*Workdir",
+            ContextFieldType::Queue(QueueType::Nats) => "*nats.Conn",
+            ContextFieldType::Queue(QueueType::Kafka) => "*kafka.Writer",
+            // Rendered by the resource adapter
+            ContextFieldType::Custom => "any",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_arg_types() {
+        let mapper = GoTypeMapper;
+        assert_eq!(mapper.map_arg_type(ArgType::Int), "int64");
+        assert_eq!(mapper.map_arg_type(ArgType::Path), "string");
+        assert_eq!(mapper.map_arg_type(ArgType::Count), "int");
+        assert_eq!(mapper.map_arg_type(ArgType::Duration), "string");
+        assert_eq!(mapper.map_optional_arg_type(ArgType::Float), "*float64");
+    }
+
+    #[test]
+    fn test_go_types_complete() {
+        assert!(GO_TYPES.missing().is_empty());
+    }
+
+    #[test]
+    fn test_map_context_types() {
+        let mapper = GoTypeMapper;
+        assert_eq!(
+            mapper.map_context_type(&ContextFieldType::Database(DatabaseType::Postgres)),
+            "*pgxpool.Pool"
+        );
+        assert_eq!(
+            mapper.map_context_type(&ContextFieldType::Database(DatabaseType::Sqlite)),
+            "*sqlx.DB"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Property tests for Go code generation.
+//!
+//! Every manifest from [`baobao_codegen::testing::manifest`] must generate
+//! the same files on each run, with no two files sharing a path.
+
+use std::collections::HashSet;
+
+use baobao_codegen::{pipeline::Pipeline, testing::manifest};
+use baobao_codegen_go::{Generator, LanguageCodegen};
+use baobao_manifest::Manifest;
+use proptest::prelude::*;
+
+/// Render every file for a manifest, as path and content.
+fn files(manifest: Manifest) -> Vec<(String, Vec<u8>)> {
+    let ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    Generator::from_context(ctx)
+        .files()
+        .expect("Failed to render files")
+        .into_iter()
+        .map(|f| (f.path, f.content.as_bytes().to_vec()))
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_generation_is_deterministic(manifest in manifest()) {
+        prop_assert_eq!(files(manifest.clone()), files(manifest));
+    }
+
+    #[test]
+    fn test_no_path_collisions(manifest in manifest()) {
+        let mut seen = HashSet::new();
+        for (path, _) in files(manifest) {
+            // Paths differing only in case collide on macOS and Windows
+            prop_assert!(seen.insert(path.to_lowercase()), "{} is generated twice", path);
+        }
+    }
+}
@@ -0,0 +1,1247 @@
+//! Snapshot tests for Go code generation.
+//!
+//! These tests verify that the generated Go code matches expected output.
+//! Run `cargo insta review` to update snapshots when making intentional changes.
+
+use std::str::FromStr;
+
+use baobao_codegen::pipeline::Pipeline;
+use baobao_codegen_go::{Generator, LanguageCodegen};
+use baobao_core::Overwrite;
+use baobao_ir::{DefaultValue, Input, InputKind, InputType};
+use baobao_manifest::Manifest;
+
+fn generator(schema_toml: &str) -> Generator {
+    let manifest = Manifest::from_str(schema_toml).expect("Failed to parse schema");
+    let ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    Generator::from_context(ctx)
+}
+
+/// Generate code from a schema and return files sorted by path for deterministic snapshots.
+fn generate_files(schema_toml: &str) -> Vec<(String, String)> {
+    let mut result: Vec<(String, String)> = generator(schema_toml)
+        .preview()
+        .into_iter()
+        .map(|f| (f.path, f.content))
+        .collect();
+    result.sort_by(|a, b| a.0.cmp(&b.0));
+    result
+}
+
+/// Get a specific file from the generated output.
+fn get_file<'a>(files: &'a [(String, String)], path: &str) -> Option<&'a str> {
+    files
+        .iter()
+        .find(|(p, _)| p == path)
+        .map(|(_, c)| c.as_str())
+}
+
+const NESTED: &str = r#"
+    [cli]
+    name = "myapp"
+    version = "1.0.0"
+    language = "go"
+    description = "A nested CLI"
+
+    [commands.db]
+    description = "Database commands"
+
+    [[commands.db.flags]]
+    name = "dir"
+    type = "path"
+    default = "migrations"
+    description = "Migrations directory"
+
+    [commands.db.commands.migrate]
+    description = "Run migrations"
+
+    [[commands.db.commands.migrate.args]]
+    name = "steps"
+    type = "int"
+    description = "Number of steps"
+
+    [[commands.db.commands.migrate.args]]
+    name = "target"
+    type = "string"
+    required = false
+
+    [[commands.db.commands.migrate.flags]]
+    name = "dry_run"
+    type = "bool"
+    short = "n"
+    description = "Print without applying"
+
+    [[commands.db.commands.migrate.flags]]
+    name = "format"
+    type = "string"
+    default = "text"
+    choices = ["text", "json"]
+"#;
+
+#[test]
+fn test_basic_cli_files() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+        description = "A simple CLI app"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let paths: Vec<&str> = files.iter().map(|(p, _)| p.as_str()).collect();
+    insta::assert_debug_snapshot!("basic_cli_paths", paths);
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    insta::assert_snapshot!("basic_cli_root", root);
+
+    let command = get_file(&files, "cmd/hello.go").expect("hello.go not found");
+    insta::assert_snapshot!("basic_cli_command", command);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    insta::assert_snapshot!("basic_cli_go_mod", go_mod);
+}
+
+#[test]
+fn test_nested_parent_command() {
+    let files = generate_files(NESTED);
+    let parent = get_file(&files, "cmd/db.go").expect("db.go not found");
+    insta::assert_snapshot!("nested_parent_command", parent);
+}
+
+#[test]
+fn test_nested_leaf_command() {
+    let files = generate_files(NESTED);
+    let leaf = get_file(&files, "cmd/db_migrate.go").expect("db_migrate.go not found");
+    insta::assert_snapshot!("nested_leaf_command", leaf);
+}
+
+#[test]
+fn test_nested_args() {
+    let files = generate_files(NESTED);
+    let args = get_file(&files, "internal/generated/args.go").expect("args.go not found");
+    insta::assert_snapshot!("nested_args", args);
+}
+
+#[test]
+fn test_postgres_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.database]
+        type = "postgres"
+        max_connections = 10
+        idle_timeout = 600
+
+        [context.http]
+        type = "http"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("postgres_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/jackc/pgx/v5"));
+}
+
+#[test]
+fn test_sqlite_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.database]
+        type = "sqlite"
+        path = "app.db"
+        journal_mode = "wal"
+        foreign_keys = true
+
+        [context.logging]
+        level = "debug"
+        format = "json"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("sqlite_context", context);
+}
+
+#[test]
+fn test_duckdb_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.database]
+        type = "duckdb"
+        env = "DUCKDB_PATH"
+        read_only = true
+        threads = 4
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("duckdb_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/marcboeker/go-duckdb v1.8.5"));
+    assert!(go_mod.contains("github.com/jmoiron/sqlx"));
+}
+
+#[test]
+fn test_storage_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.storage]
+        type = "s3"
+        bucket = "uploads"
+        region = "eu-west-1"
+        endpoint = "http://localhost:9000"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("storage_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("gocloud.dev v0.40.0"));
+}
+
+#[test]
+fn test_grpc_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.grpc]
+        endpoint = "https://api.example.com"
+        timeout = 10
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("grpc_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("google.golang.org/grpc v1.67.1"));
+}
+
+#[test]
+fn test_email_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.email]
+        host = "smtp.example.com"
+        tls = "tls"
+        username_env = "SMTP_USERNAME"
+        password_env = "SMTP_PASSWORD"
+
+        [commands.send]
+        description = "Send a notification"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("email_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/wneessen/go-mail v0.5.2"));
+}
+
+#[test]
+fn test_config_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.config]
+        path = "myapp.yaml"
+
+        [context.config.fields]
+        api_url = "string"
+        retries = { type = "int", optional = true }
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("config_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("gopkg.in/yaml.v3 v3.0.1"));
+}
+
+#[test]
+fn test_secrets_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.secrets]
+        backend = "keyring"
+        prefix = "MYAPP_"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("secrets_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/zalando/go-keyring v0.2.5"));
+}
+
+#[test]
+fn test_memory_cache_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.cache]
+        max_capacity = 500
+        ttl = 60
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("memory_cache_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/hashicorp/golang-lru/v2 v2.0.7"));
+}
+
+#[test]
+fn test_workdir_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.workdir]
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("workdir_context", context);
+}
+
+#[test]
+fn test_disk_cache_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.cache]
+        mode = "disk"
+        ttl = 3600
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("disk_cache_context", context);
+}
+
+#[test]
+fn test_anthropic_llm_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("anthropic_llm_context", context);
+}
+
+#[test]
+fn test_openai_llm_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.llm]
+        provider = "openai"
+        model = "gpt-4o"
+        max_tokens = 512
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("openai_llm_context", context);
+}
+
+#[test]
+fn test_file_secrets_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.secrets]
+        backend = "file"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("file_secrets_context", context);
+}
+
+#[test]
+fn test_nats_queue_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.queue]
+        type = "nats"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("nats_queue_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/nats-io/nats.go v1.37.0"));
+}
+
+#[test]
+fn test_kafka_queue_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.queue]
+        type = "kafka"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("kafka_queue_context", context);
+
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("github.com/segmentio/kafka-go v0.4.47"));
+}
+
+#[test]
+fn test_named_http_clients() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        timeout = 30
+        user_agent = "myapp/1.0"
+        headers = { accept = "application/vnd.github+json", X-GitHub-Api-Version = "2022-11-28" }
+        retries = 3
+        backoff_ms = 200
+        retry_on = [429, 503]
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.http.internal]
+        timeout = 5
+
+        [context.http.metrics]
+        retries = 2
+        auth = { type = "header", name = "X-Api-Key", env = "METRICS_API_KEY" }
+
+        [context.http.registry]
+        auth = { type = "basic", username = "ci", env = "REGISTRY_PASSWORD" }
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("named_http_context", context);
+}
+
+#[test]
+fn test_global_inputs() {
+    let manifest = Manifest::from_str(NESTED).expect("Failed to parse schema");
+    let mut ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    ctx.ir.as_mut().unwrap().global_inputs = vec![Input {
+        name: "output".into(),
+        ty: InputType::String,
+        kind: InputKind::Flag { short: Some('o') },
+        required: false,
+        default: Some(DefaultValue::String("text".into())),
+        description: Some("Output format".into()),
+        choices: Some(vec!["text".into(), "json".into()]),
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        constraints: Default::default(),
+        requires: vec![],
+        required_if: None,
+        hint: None,
+        long_description: None,
+        deprecated: None,
+        span: None,
+    }];
+    let mut files: Vec<_> = Generator::from_context(ctx)
+        .preview()
+        .into_iter()
+        .map(|f| (f.path, f.content))
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    insta::assert_snapshot!("global_inputs_root", root);
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    assert!(context.contains("type Globals struct {\n\tOutput string\n}"));
+    assert!(context.contains("Globals Globals"));
+
+    let leaf = get_file(&files, "cmd/db_migrate.go").expect("db_migrate.go not found");
+    assert!(leaf.contains("if err := readGlobals(&appCtx.Globals); err != nil {"));
+}
+
+#[test]
+fn test_localized_descriptions() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+        description = { en = "My app", fr = "Mon app" }
+
+        [commands.db]
+        description = { en = "Database commands", fr = "Commandes de base de données" }
+
+        [commands.db.flags.dry_run]
+        description = { en = "Print the plan only", fr = "Afficher le plan" }
+
+        [commands.db.commands.migrate]
+        description = { en = "Run migrations", "pt-BR" = "Executar migrações" }
+        "#,
+    );
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    insta::assert_snapshot!("localized_root", root);
+}
+
+#[test]
+fn test_build_info() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.2.0"
+        language = "go"
+        build_info = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    insta::assert_snapshot!("build_info_root", root);
+}
+
+#[test]
+fn test_docs_command() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+        docs_command = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let docs = get_file(&files, "cmd/docs.go").expect("docs.go not found");
+    insta::assert_snapshot!("docs_command", docs);
+}
+
+#[test]
+fn test_multiple_values() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.sum]
+        description = "Add numbers"
+
+        [commands.sum.args.numbers]
+        type = "int"
+        multiple = true
+
+        [commands.sum.flags.label]
+        type = "string"
+        short = "l"
+        multiple = true
+        choices = ["a", "b"]
+        default = ["a"]
+
+        [commands.sum.flags.weight]
+        type = "float"
+        multiple = true
+
+        [commands.sum.flags.verbose]
+        type = "count"
+        short = "v"
+        "#,
+    );
+
+    let command = get_file(&files, "cmd/sum.go").expect("sum.go not found");
+    insta::assert_snapshot!("multiple_values_command", command);
+    let args = get_file(&files, "internal/generated/args.go").expect("args.go not found");
+    assert!(args.contains("Numbers []int64"));
+    assert!(args.contains("Label   []string"));
+    assert!(args.contains("Verbose int"));
+}
+
+#[test]
+fn test_value_constraints() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        pattern = "[a-z]+"
+        min_length = 2
+        max_length = 16
+
+        [commands.greet.flags.times]
+        type = "int"
+        min = 1
+        max = 5
+        default = 1
+
+        [commands.greet.flags.volume]
+        type = "float"
+        min = 0
+        multiple = true
+
+        [commands.greet.flags.config]
+        type = "path"
+        path_exists = true
+        "#,
+    );
+
+    let command = get_file(&files, "cmd/greet.go").expect("greet.go not found");
+    insta::assert_snapshot!("value_constraints_command", command);
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    assert!(root.contains("func matches(name, value, pattern string) error"));
+    assert!(root.contains("func minLength(name, value string, limit int) error"));
+    assert!(root.contains("func pathExists(name, value string) error"));
+}
+
+#[test]
+fn test_input_groups() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.args.input]
+        type = "path"
+        required = false
+
+        [commands.build.flags.json]
+        type = "bool"
+
+        [commands.build.flags.yaml]
+        type = "bool"
+
+        [commands.build.flags.target]
+        type = "string"
+
+        [commands.build.groups.output]
+        members = ["json", "yaml"]
+        exclusive = true
+
+        [commands.build.groups.source]
+        members = ["input", "target"]
+        required = true
+        "#,
+    );
+
+    let command = get_file(&files, "cmd/build.go").expect("build.go not found");
+    insta::assert_snapshot!("input_groups_command", command);
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    assert!(root.contains("func exclusive(names []string, given ...bool) error"));
+    assert!(root.contains("func oneRequired(names []string, given ...bool) error"));
+}
+
+#[test]
+fn test_command_aliases() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.list]
+        description = "List items"
+        aliases = ["ls", "l"]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        aliases = ["m"]
+        "#,
+    );
+
+    let list = get_file(&files, "cmd/list.go").expect("list.go not found");
+    assert!(list.contains("Aliases: []string{\"ls\", \"l\"},"));
+    let migrate = get_file(&files, "cmd/db_migrate.go").expect("db_migrate.go not found");
+    assert!(migrate.contains("Aliases: []string{\"m\"},"));
+}
+
+#[test]
+fn test_deprecated_commands_and_flags() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.old]
+        description = "Old command"
+        deprecated = "use `new` instead"
+
+        [commands.db]
+        description = "Database commands"
+        deprecated = "use `store`"
+
+        [commands.db.flags.url]
+        type = "string"
+        deprecated = "set DB_URL"
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.db.commands.reset.flags.force]
+        type = "bool"
+        deprecated = "reset always forces"
+        "#,
+    );
+
+    let old = get_file(&files, "cmd/old.go").expect("old.go not found");
+    assert!(old.contains(
+        "fmt.Fprintln(os.Stderr, \"warning: command 'old' is deprecated: use `new` instead\")"
+    ));
+    let reset = get_file(&files, "cmd/db_reset.go").expect("db_reset.go not found");
+    assert!(reset.contains("\"os\""));
+    assert!(
+        reset.contains(
+            "fmt.Fprintln(os.Stderr, \"warning: command 'db' is deprecated: use `store`\")"
+        )
+    );
+    assert!(reset.contains(
+        "if viper.IsSet(\"db.url\") {\n\t\t\tfmt.Fprintln(os.Stderr, \"warning: flag '--url' is deprecated: set DB_URL\")"
+    ));
+    assert!(reset.contains("if viper.IsSet(\"db.reset.force\") {"));
+}
+
+#[test]
+fn test_command_examples() {
+    let manifest = r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        examples = ["myapp deploy prod --force", "myapp deploy \"staging area\""]
+
+        [commands.deploy.args.env]
+        type = "string"
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        "#;
+    let files = generate_files(manifest);
+
+    let deploy = get_file(&files, "cmd/deploy.go").expect("deploy.go not found");
+    assert!(
+        deploy.contains(
+            r#"Example: "  myapp deploy prod --force\n  myapp deploy \"staging area\"","#
+        )
+    );
+    let entries = generator(manifest).files().expect("files failed");
+    let handler = entries
+        .iter()
+        .find(|f| f.path == "handlers/deploy.go")
+        .and_then(|f| f.content.as_text())
+        .expect("handler stub not found");
+    assert!(handler.contains(
+        "// Examples:\n//\n//\tmyapp deploy prod --force\n//\tmyapp deploy \"staging area\"\nfunc Deploy("
+    ));
+}
+
+#[test]
+fn test_url_duration_datetime_uuid_types() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.fetch]
+        description = "Fetch a resource"
+
+        [commands.fetch.args.url]
+        type = "url"
+
+        [commands.fetch.flags.timeout]
+        type = "duration"
+        default = "30s"
+
+        [commands.fetch.flags.since]
+        type = "datetime"
+
+        [commands.fetch.flags.id]
+        type = "uuid"
+        multiple = true
+        "#,
+    );
+
+    let fetch = get_file(&files, "cmd/fetch.go").expect("fetch.go not found");
+    assert!(fetch.contains(r#"if err := isURL("<url>", input.Url); err != nil {"#));
+    assert!(fetch.contains(r#"if err := isUUID("--id", value); err != nil {"#));
+    assert!(fetch.contains(
+        "if viper.IsSet(\"fetch.timeout\") {\n\t\t\tif err := isDuration(\"--timeout\", input.Timeout); err != nil {"
+    ));
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    assert!(root.contains("\"net/url\""));
+    assert!(root.contains("func isDateTime(name, value string) error {"));
+    assert!(!root.contains("func atLeast("));
+}
+
+#[test]
+fn test_positional_order() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.copy]
+        description = "Copy a file"
+
+        [commands.copy.args.source]
+        type = "path"
+        index = 1
+
+        [commands.copy.args.dest]
+        type = "path"
+        index = 2
+        "#,
+    );
+
+    let copy = get_file(&files, "cmd/copy.go").expect("copy.go not found");
+    assert!(copy.contains(r#"Use:   "copy <source> <dest>","#));
+    assert!(copy.contains("input.Source = args[0]\n\t\tinput.Dest = args[1]"));
+}
+
+#[test]
+fn test_long_description() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        long_description = "Builds the \"main\" project.\n\nThen uploads it."
+        "#,
+    );
+
+    let deploy = get_file(&files, "cmd/deploy.go").expect("deploy.go not found");
+    assert!(deploy.contains(
+        r#"Long:  "Deploy the app\n\nBuilds the \"main\" project.\n\nThen uploads it.","#
+    ));
+}
+
+#[test]
+fn test_input_requirements() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.deploy]
+        description = "Deploy the app"
+
+        [commands.deploy.args.target]
+        type = "string"
+        required = false
+
+        [commands.deploy.flags.mode]
+        type = "string"
+        choices = ["local", "remote"]
+        default = "local"
+
+        [commands.deploy.flags.host]
+        type = "string"
+        required_if = { flag = "mode", value = "remote" }
+
+        [commands.deploy.flags.dry-run]
+        type = "bool"
+        requires = ["target", "log-file"]
+
+        [commands.deploy.flags.log-file]
+        type = "path"
+        "#,
+    );
+
+    let command = get_file(&files, "cmd/deploy.go").expect("deploy.go not found");
+    insta::assert_snapshot!("input_requirements_command", command);
+}
+
+#[test]
+fn test_doctor_command() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+        doctor_command = true
+
+        [context.database]
+        type = "postgres"
+        env = "DATABASE_URL"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let checks = get_file(&files, "internal/app/checks.go").expect("checks.go not found");
+    insta::assert_snapshot!("doctor_checks", checks);
+    let doctor = get_file(&files, "cmd/doctor.go").expect("doctor.go not found");
+    insta::assert_snapshot!("doctor_command", doctor);
+}
+
+#[test]
+fn test_doctor_checks_resources() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+        doctor_command = true
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api_url = "string"
+
+        [context.secrets]
+        backend = "file"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let checks = get_file(&files, "internal/app/checks.go").expect("checks.go not found");
+    insta::assert_snapshot!("doctor_checks_resources", checks);
+}
+
+#[test]
+fn test_files_include_handlers() {
+    let files = generator(NESTED).files().expect("files failed");
+
+    let stub = files
+        .iter()
+        .find(|f| f.path == "handlers/db_migrate.go")
+        .expect("handler stub not found");
+    assert_eq!(stub.overwrite(), Overwrite::IfMissing);
+    assert!(files.iter().all(|f| f.path != "handlers/db.go"));
+}
+
+#[test]
+fn test_license_header() {
+    let mut manifest = Manifest::from_str(NESTED).expect("Failed to parse schema");
+    manifest.generation.header =
+        Some("Copyright Acme Corp.\nSPDX-License-Identifier: MIT\n".into());
+    let ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    let files = Generator::from_context(ctx).preview();
+
+    let root = files.iter().find(|f| f.path == "cmd/root.go").unwrap();
+    assert!(root.content.starts_with(
+        "// Copyright Acme Corp.\n// SPDX-License-Identifier: MIT\n\n// Code generated"
+    ));
+    let go_mod = files.iter().find(|f| f.path == "go.mod").unwrap();
+    assert!(!go_mod.content.contains("Acme"));
+}
+
+#[test]
+fn test_handler_stubs_and_clean() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = generator(NESTED).generate(dir.path()).unwrap();
+    assert_eq!(result.created_handlers, vec!["db_migrate.go"]);
+
+    let stub = std::fs::read_to_string(dir.path().join("handlers/db_migrate.go")).unwrap();
+    insta::assert_snapshot!("handler_stub", stub);
+
+    // Stubs are created once and never overwritten
+    std::fs::write(
+        dir.path().join("handlers/db_migrate.go"),
+        "package handlers\n",
+    )
+    .unwrap();
+    let result = generator(NESTED).generate(dir.path()).unwrap();
+    assert!(result.created_handlers.is_empty());
+
+    // Removing the command orphans its generated file and unmodified stubs
+    std::fs::write(dir.path().join("handlers/old.go"), "// TODO: implement\n").unwrap();
+    std::fs::write(dir.path().join("cmd/old.go"), "package cmd\n").unwrap();
+    let cleaned = generator(NESTED).clean(dir.path()).unwrap();
+    assert_eq!(cleaned.deleted_commands, vec!["cmd/old.go"]);
+    assert_eq!(cleaned.deleted_handlers, vec!["handlers/old.go"]);
+    assert!(dir.path().join("handlers/db_migrate.go").exists());
+}
+
+#[test]
+fn test_default_commands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+        default_command = "status"
+
+        [commands.status]
+        description = "Show status"
+
+        [commands.db]
+        description = "Database operations"
+        default_command = "list"
+
+        [commands.db.commands.list]
+        description = "List databases"
+        "#,
+    );
+
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    assert!(root.contains("cobra.NoArgs"));
+    assert!(root.contains("return statusCmd.RunE(statusCmd, args)"));
+    let db = get_file(&files, "cmd/db.go").expect("db.go not found");
+    assert!(db.contains("return dbListCmd.RunE(dbListCmd, args)"));
+}
+
+#[test]
+fn test_secrets() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.login]
+        description = "Log in"
+
+        [commands.login.args.password]
+        type = "secret"
+        description = "Password"
+
+        [commands.login.flags.api-token]
+        type = "secret"
+        "#,
+    );
+
+    let login = get_file(&files, "cmd/login.go").expect("login.go not found");
+    assert!(login.contains("input.Password = os.Getenv(\"MYAPP_LOGIN_PASSWORD\")"));
+    assert!(login.contains("fmt.Fprint(os.Stderr, \"api-token: \")"));
+    assert!(login.contains("secret, err := term.ReadPassword(int(os.Stdin.Fd()))"));
+    assert!(login.contains("\"golang.org/x/term\""));
+    let go_mod = get_file(&files, "go.mod").expect("go.mod not found");
+    assert!(go_mod.contains("golang.org/x/term"));
+}
+
+#[test]
+fn test_prompt_message() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "go"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        prompt = "What is your name?"
+        "#,
+    );
+
+    let greet = get_file(&files, "cmd/greet.go").expect("greet.go not found");
+    assert!(greet.contains("if input.Name == \"\" && !viper.GetBool(\"no_input\") {"));
+    assert!(greet.contains("fmt.Fprint(os.Stderr, \"What is your name? \")"));
+    assert!(greet.contains("return errors.New(\"missing argument <name>\")"));
+    let root = get_file(&files, "cmd/root.go").expect("root.go not found");
+    assert!(root.contains("flags.Bool(\"no-input\", false,"));
+}
+
+#[test]
+fn test_replica_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "go"
+
+        [context.database]
+        type = "postgres"
+        max_connections = 5
+
+        [context.database.replica]
+        env = "READER_URL"
+        max_connections = 20
+
+        [commands.report]
+        description = "Build a report"
+        "#,
+    );
+
+    let context = get_file(&files, "internal/app/context.go").expect("context.go not found");
+    insta::assert_snapshot!("replica_context", context);
+}
@@ -6,7 +6,9 @@ use baobao_codegen::{
 };
 use baobao_core::ArgType;
 
-use crate::{ArgAttr, Arm, ClapAttr, Enum, Field, Fn, Impl, Match, Param, Struct, Variant};
+use crate::{
+    ArgAttr, Arm, ClapAttr, Enum, Field, Fn, Impl, Match, Param, RUST_TYPES, Struct, Variant,
+};
 
 /// Clap adapter for generating derive-based CLI code.
 #[derive(Debug, Clone, Default)]
@@ -133,11 +135,14 @@ impl CliAdapter for ClapAdapter {
             if let Some(default) = &flag.default {
                 arg_attr = arg_attr.default_value(default);
             }
+            if flag.flag_type == ArgType::Count {
+                arg_attr = arg_attr.action("clap::ArgAction::Count");
+            }
 
             let rust_type = self.map_arg_type(flag.flag_type);
-            let field_type = if flag.flag_type == ArgType::Bool {
-                "bool".to_string()
-            } else if flag.default.is_some() {
+            let field_type = if matches!(flag.flag_type, ArgType::Bool | ArgType::Count)
+                || flag.default.is_some()
+            {
                 rust_type.to_string()
             } else {
                 format!("Option<{}>", rust_type)
@@ -246,13 +251,7 @@ impl CliAdapter for ClapAdapter {
     }
 
     fn map_arg_type(&self, arg_type: ArgType) -> &'static str {
-        match arg_type {
-            ArgType::String => "String",
-            ArgType::Int => "i64",
-            ArgType::Float => "f64",
-            ArgType::Bool => "bool",
-            ArgType::Path => "std::path::PathBuf",
-        }
+        RUST_TYPES.type_name(arg_type)
     }
 
     fn map_optional_type(&self, arg_type: ArgType) -> String {
@@ -0,0 +1,143 @@
+//! DuckDB database adapter.
+
+use baobao_codegen::{
+    adapters::{DatabaseAdapter, Dependency, DuckdbOptions, ImportSpec, PoolConfig, PoolInitInfo},
+    builder::{Block, BuilderSpec, Constructor, Value},
+};
+use baobao_ir::DatabaseType;
+
+use crate::RustRenderer;
+
+/// DuckDB adapter, pooling connections with the duckdb crate's r2d2 manager.
+///
+/// DuckDB has no sqlx driver, and its connections are blocking, so pools are
+/// built without awaiting.
+#[derive(Debug, Clone, Default)]
+pub struct DuckdbAdapter;
+
+impl DuckdbAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Build r2d2 pool builder spec from config.
+    fn pool_builder_spec(&self, pool: &PoolConfig) -> BuilderSpec {
+        // r2d2 takes the optional limits as `Option`s
+        let some = |value: Value| {
+            Value::ident(format!(
+                "Some({})",
+                value.render_inline(&RustRenderer::new())
+            ))
+        };
+        BuilderSpec::with_constructor(Constructor::static_method("r2d2::Pool", "builder", vec![]))
+            .apply_config([
+                (
+                    "max_size",
+                    pool.max_connections.map(|v| Value::uint(v.into())),
+                ),
+                (
+                    "min_idle",
+                    pool.min_connections.map(|v| some(Value::uint(v.into()))),
+                ),
+                (
+                    "connection_timeout",
+                    pool.acquire_timeout
+                        .map(|d| Value::duration_secs(d.as_secs())),
+                ),
+                (
+                    "idle_timeout",
+                    pool.idle_timeout
+                        .map(|d| some(Value::duration_secs(d.as_secs()))),
+                ),
+                (
+                    "max_lifetime",
+                    pool.max_lifetime
+                        .map(|d| some(Value::duration_secs(d.as_secs()))),
+                ),
+            ])
+    }
+
+    /// Build the `duckdb::Config` expression, whose setters each return a `Result`.
+    fn config_expr(duckdb: &DuckdbOptions) -> Value {
+        let mut expr = "duckdb::Config::default()".to_string();
+        if let Some(read_only) = duckdb.read_only {
+            let mode = if read_only { "ReadOnly" } else { "ReadWrite" };
+            expr.push_str(&format!(".access_mode(duckdb::AccessMode::{})?", mode));
+        }
+        if let Some(threads) = duckdb.threads {
+            expr.push_str(&format!(".threads({})?", threads));
+        }
+        Value::ident(expr)
+    }
+}
+
+impl DatabaseAdapter for DuckdbAdapter {
+    fn name(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn dependencies(&self, _db_type: DatabaseType) -> Vec<Dependency> {
+        vec![
+            Dependency::new(
+                "duckdb",
+                r#"{ version = "1", features = ["bundled", "r2d2"] }"#,
+            ),
+            Dependency::new("r2d2", "0.8"),
+        ]
+    }
+
+    fn pool_type(&self, _db_type: DatabaseType) -> &'static str {
+        "r2d2::Pool<duckdb::DuckdbConnectionManager>"
+    }
+
+    fn pool_init(&self, info: &PoolInitInfo) -> Value {
+        let duckdb = info.duckdb_config.clone().unwrap_or_default();
+        let path = match &duckdb.path {
+            Some(path) => Value::string(path),
+            None => Value::env_var(&info.env_var),
+        };
+
+        let (manager, config) = if duckdb.has_config() {
+            let manager = Constructor::static_method(
+                "duckdb::DuckdbConnectionManager",
+                "file_with_flags",
+                vec![path, Value::ident("config")],
+            );
+            (manager, Some(Self::config_expr(&duckdb)))
+        } else {
+            let manager =
+                Constructor::static_method("duckdb::DuckdbConnectionManager", "file", vec![path]);
+            (manager, None)
+        };
+
+        let pool = if info.pool_config.has_config() {
+            self.pool_builder_spec(&info.pool_config)
+                .call_arg("build", Value::ident("manager"))
+                .try_()
+        } else {
+            BuilderSpec::with_constructor(Constructor::static_method(
+                "r2d2::Pool",
+                "new",
+                vec![Value::ident("manager")],
+            ))
+            .try_()
+        };
+
+        let mut block = Block::new(Value::builder(pool));
+        if let Some(config) = config {
+            block = block.binding("config", config);
+        }
+        Value::block(block.binding(
+            "manager",
+            Value::builder(BuilderSpec::with_constructor(manager).try_()),
+        ))
+    }
+
+    fn imports(&self, _db_type: DatabaseType) -> Vec<ImportSpec> {
+        vec![ImportSpec::new("duckdb").symbol("DuckdbConnectionManager")]
+    }
+
+    fn requires_async(&self, _db_type: DatabaseType) -> bool {
+        false
+    }
+}
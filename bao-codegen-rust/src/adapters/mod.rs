@@ -1,11 +1,29 @@
 //! Adapter implementations for Rust code generation.
 //!
 //! This module provides concrete implementations of the adapter traits
-//! for Rust-specific frameworks: clap, sqlx, tokio, and eyre.
+//! for Rust-specific frameworks: clap, sqlx, duckdb, reqwest, tonic, tokio, and eyre.
 
 mod clap;
+mod duckdb;
 mod eyre;
+mod reqwest;
 mod sqlx;
 mod tokio;
+mod tonic;
 
-pub use self::{clap::ClapAdapter, eyre::EyreAdapter, sqlx::SqlxAdapter, tokio::TokioAdapter};
+use baobao_codegen::adapters::DatabaseAdapter;
+use baobao_ir::DatabaseType;
+
+pub use self::{
+    clap::ClapAdapter, duckdb::DuckdbAdapter, eyre::EyreAdapter, reqwest::ReqwestAdapter,
+    sqlx::SqlxAdapter, tokio::TokioAdapter, tonic::TonicAdapter,
+};
+
+/// The adapter generating pools of `db_type`: sqlx, except for DuckDB, which
+/// it has no driver for.
+pub fn database_adapter(db_type: DatabaseType) -> &'static dyn DatabaseAdapter {
+    match db_type {
+        DatabaseType::Duckdb => &DuckdbAdapter,
+        DatabaseType::Postgres | DatabaseType::Mysql | DatabaseType::Sqlite => &SqlxAdapter,
+    }
+}
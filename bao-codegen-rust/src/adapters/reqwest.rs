@@ -0,0 +1,187 @@
+//! reqwest HTTP client adapter.
+
+use baobao_codegen::adapters::{Dependency, HttpAdapter, HttpOptions};
+use baobao_ir::HttpAuth;
+
+/// reqwest adapter for HTTP client generation.
+///
+/// Clients with retries are wrapped in reqwest-middleware with a
+/// reqwest-retry exponential backoff policy.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestAdapter;
+
+impl ReqwestAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render a call, splitting the arguments over lines when any of them
+    /// spans several lines or there is more than one.
+    fn call(callee: &str, args: &[String], indent: usize) -> String {
+        if let [arg] = args
+            && !arg.contains('\n')
+        {
+            return format!("{}({})", callee, arg);
+        }
+        let pad = " ".repeat(indent + 4);
+        let args: String = args
+            .iter()
+            .map(|arg| format!("\n{}{},", pad, arg))
+            .collect();
+        format!("{}({}\n{})", callee, args, " ".repeat(indent))
+    }
+
+    /// The reqwest client, with method calls indented past `indent`.
+    fn client(http: &HttpOptions, indent: usize) -> String {
+        if http.headers.is_empty()
+            && http.timeout.is_none()
+            && http.user_agent.is_none()
+            && http.auth.is_none()
+        {
+            return "reqwest::Client::new()".to_string();
+        }
+        let mut lines = vec!["reqwest::Client::builder()".to_string()];
+        if let Some(timeout) = http.timeout {
+            let duration = if timeout.subsec_millis() == 0 {
+                format!("from_secs({})", timeout.as_secs())
+            } else {
+                format!("from_millis({})", timeout.as_millis())
+            };
+            lines.push(format!(".timeout(std::time::Duration::{})", duration));
+        }
+        if let Some(user_agent) = &http.user_agent {
+            lines.push(format!(".user_agent({:?})", user_agent));
+        }
+        if !http.headers.is_empty() || http.auth.is_some() {
+            lines.push(".default_headers(reqwest::header::HeaderMap::from_iter([".to_string());
+            for (name, value) in &http.headers {
+                // from_static only accepts lowercase names
+                lines.push(format!(
+                    "    (reqwest::header::HeaderName::from_static({:?}), reqwest::header::HeaderValue::from_static({:?})),",
+                    name.to_ascii_lowercase(),
+                    value
+                ));
+            }
+            if let Some(auth) = &http.auth {
+                lines.push(format!("    {},", Self::auth_header(auth)));
+            }
+            lines.push("]))".to_string());
+        }
+        lines.push(".build()?".to_string());
+        lines.join(&format!("\n{}", " ".repeat(indent + 4)))
+    }
+
+    /// The `(name, value)` pair of an auth header, with the value built by a
+    /// helper from the generated `http` module.
+    fn auth_header(auth: &HttpAuth) -> String {
+        match auth {
+            HttpAuth::Bearer { env } => format!(
+                "(reqwest::header::AUTHORIZATION, crate::generated::bearer_auth({:?})?)",
+                env
+            ),
+            HttpAuth::Basic { username, env } => format!(
+                "(reqwest::header::AUTHORIZATION, crate::generated::basic_auth({:?}, {:?})?)",
+                username, env
+            ),
+            HttpAuth::Header { name, env } => format!(
+                "(reqwest::header::HeaderName::from_static({:?}), crate::generated::header_auth({:?})?)",
+                name.to_ascii_lowercase(),
+                env
+            ),
+        }
+    }
+
+    /// The exponential backoff policy, doubling the delay for each retry.
+    fn policy(http: &HttpOptions, indent: usize) -> String {
+        let mut lines = vec!["reqwest_retry::policies::ExponentialBackoff::builder()".to_string()];
+        if let Some(backoff) = http.backoff {
+            let min = backoff.as_millis() as u64;
+            let max = min.saturating_mul(1u64.checked_shl(http.retries).unwrap_or(u64::MAX));
+            lines.push(format!(
+                ".retry_bounds(std::time::Duration::from_millis({}), std::time::Duration::from_millis({}))",
+                min, max
+            ));
+        }
+        lines.push(format!(".build_with_max_retries({})", http.retries));
+        lines.join(&format!("\n{}", " ".repeat(indent + 4)))
+    }
+
+    /// The client wrapped in the retry middleware.
+    fn middleware(http: &HttpOptions, indent: usize) -> String {
+        let pad = " ".repeat(indent + 4);
+        let mut args = vec![Self::policy(http, indent + 8)];
+        let retry = if http.retry_on.is_empty() {
+            "reqwest_retry::RetryTransientMiddleware::new_with_policy"
+        } else {
+            let statuses: Vec<String> = http.retry_on.iter().map(u16::to_string).collect();
+            args.push(format!(
+                "crate::generated::RetryOn(&[{}])",
+                statuses.join(", ")
+            ));
+            "reqwest_retry::RetryTransientMiddleware::new_with_policy_and_strategy"
+        };
+        format!(
+            "{}\n{}.with({})\n{}.build()",
+            Self::call(
+                "reqwest_middleware::ClientBuilder::new",
+                &[Self::client(http, indent + 4)],
+                indent
+            ),
+            pad,
+            Self::call(retry, &args, indent + 4),
+            pad
+        )
+    }
+
+    /// The client expression starting at column `indent`.
+    fn init(http: &HttpOptions, indent: usize) -> String {
+        let client = |indent| {
+            if http.has_retries() {
+                Self::middleware(http, indent)
+            } else {
+                Self::client(http, indent)
+            }
+        };
+        match &http.base_url {
+            Some(base_url) => Self::call(
+                "HttpClient::new",
+                &[client(indent + 4), format!("{:?}", base_url)],
+                indent,
+            ),
+            None => client(indent),
+        }
+    }
+}
+
+impl HttpAdapter for ReqwestAdapter {
+    fn name(&self) -> &'static str {
+        "reqwest"
+    }
+
+    fn dependencies(&self, options: &HttpOptions) -> Vec<Dependency> {
+        let mut dependencies = vec![Dependency::new("reqwest", "0.12")];
+        if options.has_retries() {
+            dependencies.push(Dependency::new("reqwest-middleware", "0.4"));
+            dependencies.push(Dependency::new("reqwest-retry", "0.7"));
+        }
+        if matches!(options.auth, Some(HttpAuth::Basic { .. })) {
+            dependencies.push(Dependency::new("base64", "0.22"));
+        }
+        dependencies
+    }
+
+    fn client_type(&self, options: &HttpOptions) -> &'static str {
+        if options.base_url.is_some() {
+            "HttpClient"
+        } else if options.has_retries() {
+            "reqwest_middleware::ClientWithMiddleware"
+        } else {
+            "reqwest::Client"
+        }
+    }
+
+    /// The initializer for a field of the `Context::new` body.
+    fn client_init(&self, options: &HttpOptions) -> String {
+        Self::init(options, 4)
+    }
+}
@@ -6,6 +6,9 @@ use baobao_codegen::{
 };
 use baobao_ir::DatabaseType;
 
+/// sqlx has no DuckDB driver; see [`crate::adapters::database_adapter`].
+const NO_DUCKDB: &str = "DuckDB pools are generated by DuckdbAdapter";
+
 /// SQLx adapter for database pool generation.
 #[derive(Debug, Clone, Default)]
 pub struct SqlxAdapter;
@@ -92,10 +95,13 @@ impl SqlxAdapter {
         if !info.pool_config.has_config() {
             // Simple case: Pool::connect(env_var).await?
             Value::builder(
-                BuilderSpec::with_constructor(Constructor::static_new(pool_type))
-                    .call_arg("connect", Value::env_var(&info.env_var))
-                    .async_()
-                    .try_(),
+                BuilderSpec::with_constructor(Constructor::static_method(
+                    pool_type,
+                    "connect",
+                    vec![Value::env_var(&info.env_var)],
+                ))
+                .async_()
+                .try_(),
             )
         } else {
             // With pool options
@@ -120,10 +126,13 @@ impl SqlxAdapter {
         // Simple case: no options, just connect
         if !has_path && !has_sqlite_opts && !has_pool_opts {
             return Value::builder(
-                BuilderSpec::with_constructor(Constructor::static_new("sqlx::SqlitePool"))
-                    .call_arg("connect", Value::env_var(&info.env_var))
-                    .async_()
-                    .try_(),
+                BuilderSpec::with_constructor(Constructor::static_method(
+                    "sqlx::SqlitePool",
+                    "connect",
+                    vec![Value::env_var(&info.env_var)],
+                ))
+                .async_()
+                .try_(),
             );
         }
 
@@ -166,6 +175,7 @@ impl DatabaseAdapter for SqlxAdapter {
             DatabaseType::Sqlite => {
                 r#"{ version = "0.8", features = ["runtime-tokio", "sqlite"] }"#
             }
+            DatabaseType::Duckdb => unreachable!("{}", NO_DUCKDB),
         };
         vec![Dependency::new("sqlx", features)]
     }
@@ -175,6 +185,7 @@ impl DatabaseAdapter for SqlxAdapter {
             DatabaseType::Postgres => "sqlx::PgPool",
             DatabaseType::Mysql => "sqlx::MySqlPool",
             DatabaseType::Sqlite => "sqlx::SqlitePool",
+            DatabaseType::Duckdb => unreachable!("{}", NO_DUCKDB),
         }
     }
 
@@ -182,6 +193,7 @@ impl DatabaseAdapter for SqlxAdapter {
         match info.db_type {
             DatabaseType::Postgres | DatabaseType::Mysql => self.pool_init_simple(info),
             DatabaseType::Sqlite => self.pool_init_sqlite(info),
+            DatabaseType::Duckdb => unreachable!("{}", NO_DUCKDB),
         }
     }
 
@@ -195,6 +207,7 @@ impl DatabaseAdapter for SqlxAdapter {
             }
             DatabaseType::Postgres => vec![ImportSpec::new("sqlx").symbol("PgPool")],
             DatabaseType::Mysql => vec![ImportSpec::new("sqlx").symbol("MySqlPool")],
+            DatabaseType::Duckdb => unreachable!("{}", NO_DUCKDB),
         }
     }
 
@@ -0,0 +1,71 @@
+//! tonic gRPC client adapter.
+
+use baobao_codegen::adapters::{Dependency, GrpcAdapter, GrpcOptions};
+
+/// tonic adapter for gRPC client generation.
+///
+/// The context holds a lazily connected channel; build.rs compiles the
+/// `.proto` files into clients with tonic-build.
+#[derive(Debug, Clone, Default)]
+pub struct TonicAdapter;
+
+impl TonicAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl GrpcAdapter for TonicAdapter {
+    fn name(&self) -> &'static str {
+        "tonic"
+    }
+
+    fn dependencies(&self, options: &GrpcOptions) -> Vec<Dependency> {
+        let tonic = if options.uses_tls() {
+            r#"{ version = "0.12", features = ["tls-native-roots"] }"#
+        } else {
+            "0.12"
+        };
+        let mut dependencies = vec![Dependency::new("tonic", tonic)];
+        if options.has_protos() {
+            dependencies.push(Dependency::new("prost", "0.13"));
+        }
+        dependencies
+    }
+
+    fn build_dependencies(&self, options: &GrpcOptions) -> Vec<Dependency> {
+        if options.has_protos() {
+            vec![Dependency::new("tonic-build", "0.12")]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn channel_type(&self) -> &'static str {
+        "tonic::transport::Channel"
+    }
+
+    /// The initializer for a field of the `Context::new` body.
+    fn channel_init(&self, options: &GrpcOptions) -> String {
+        let mut lines = vec![format!(
+            "tonic::transport::Endpoint::from_static({:?})",
+            options.endpoint
+        )];
+        if let Some(timeout) = options.timeout {
+            let duration = if timeout.subsec_millis() == 0 {
+                format!("from_secs({})", timeout.as_secs())
+            } else {
+                format!("from_millis({})", timeout.as_millis())
+            };
+            lines.push(format!(".timeout(std::time::Duration::{})", duration));
+        }
+        if options.uses_tls() {
+            lines.push(
+                ".tls_config(tonic::transport::ClientTlsConfig::new().with_native_roots())?"
+                    .to_string(),
+            );
+        }
+        lines.push(".connect_lazy()".to_string());
+        lines.join("\n        ")
+    }
+}
@@ -17,8 +17,27 @@ pub enum ClapAttr {
     CommandVersion(String),
     /// `#[command(about = "...")]` - Sets the command description.
     CommandAbout(String),
+    /// `#[command(long_version = ...)]` - Sets the `--version` text from a
+    /// Rust expression, rendered as is.
+    CommandLongVersion(String),
     /// `#[command(subcommand)]` - Marks a field as containing subcommands.
     CommandSubcommand,
+    /// `#[command(flatten)]` - Flattens an `Args` struct into the parent.
+    CommandFlatten,
+    /// `#[command(hide = true)]` - Hides a subcommand from help output.
+    CommandHide,
+    /// `#[command(alias = "...")]` - Adds another name for a subcommand.
+    CommandAlias(String),
+    /// `#[command(long_about = "...")]` - Description shown by `--help`.
+    CommandLongAbout(String),
+    /// `#[command(after_help = "...")]` - Text shown after the help.
+    CommandAfterHelp(String),
+    /// `#[command(allow_external_subcommands = true)]` - Accepts commands
+    /// the parser does not define.
+    CommandAllowExternalSubcommands,
+    /// `#[command(external_subcommand)]` - Marks the variant collecting an
+    /// undefined command and its arguments.
+    CommandExternalSubcommand,
     /// `#[arg(...)]` - Marks a field as a CLI argument with options.
     Arg(ArgAttr),
     /// `#[value(name = "...")]` - Sets the value name for enum variants.
@@ -36,6 +55,11 @@ impl ClapAttr {
         Self::CommandVersion(version.into())
     }
 
+    /// Create a command long version attribute from a Rust expression.
+    pub fn command_long_version(expr: impl Into<String>) -> Self {
+        Self::CommandLongVersion(expr.into())
+    }
+
     /// Create a command about attribute.
     pub fn command_about(about: impl Into<String>) -> Self {
         Self::CommandAbout(about.into())
@@ -46,6 +70,41 @@ impl ClapAttr {
         Self::CommandSubcommand
     }
 
+    /// Create a command hide attribute.
+    pub fn command_hide() -> Self {
+        Self::CommandHide
+    }
+
+    /// Create a command alias attribute.
+    pub fn command_alias(alias: impl Into<String>) -> Self {
+        Self::CommandAlias(alias.into())
+    }
+
+    /// Create a command long about attribute.
+    pub fn command_long_about(text: impl Into<String>) -> Self {
+        Self::CommandLongAbout(text.into())
+    }
+
+    /// Create a command after help attribute.
+    pub fn command_after_help(text: impl Into<String>) -> Self {
+        Self::CommandAfterHelp(text.into())
+    }
+
+    /// Create a command allow external subcommands attribute.
+    pub fn command_allow_external_subcommands() -> Self {
+        Self::CommandAllowExternalSubcommands
+    }
+
+    /// Create a command external subcommand attribute.
+    pub fn command_external_subcommand() -> Self {
+        Self::CommandExternalSubcommand
+    }
+
+    /// Create a command flatten attribute.
+    pub fn command_flatten() -> Self {
+        Self::CommandFlatten
+    }
+
     /// Create an arg attribute.
     pub fn arg(attr: ArgAttr) -> Self {
         Self::Arg(attr)
@@ -62,8 +121,18 @@ impl fmt::Display for ClapAttr {
         match self {
             Self::CommandName(name) => write!(f, "command(name = \"{}\")", name),
             Self::CommandVersion(version) => write!(f, "command(version = \"{}\")", version),
-            Self::CommandAbout(about) => write!(f, "command(about = \"{}\")", about),
+            Self::CommandAbout(about) => write!(f, "command(about = {:?})", about),
+            Self::CommandLongVersion(expr) => write!(f, "command(long_version = {})", expr),
             Self::CommandSubcommand => write!(f, "command(subcommand)"),
+            Self::CommandFlatten => write!(f, "command(flatten)"),
+            Self::CommandHide => write!(f, "command(hide = true)"),
+            Self::CommandAlias(alias) => write!(f, "command(alias = {:?})", alias),
+            Self::CommandLongAbout(text) => write!(f, "command(long_about = {:?})", text),
+            Self::CommandAfterHelp(text) => write!(f, "command(after_help = {:?})", text),
+            Self::CommandAllowExternalSubcommands => {
+                write!(f, "command(allow_external_subcommands = true)")
+            }
+            Self::CommandExternalSubcommand => write!(f, "command(external_subcommand)"),
             Self::Arg(attr) => write!(f, "{}", attr),
             Self::ValueName(name) => write!(f, "value(name = \"{}\")", name),
         }
@@ -79,6 +148,8 @@ pub struct ArgAttr {
     pub short: Option<char>,
     /// Default value as a string.
     pub default_value: Option<String>,
+    /// Parsing action (e.g., `clap::ArgAction::Count`).
+    pub action: Option<String>,
 }
 
 impl ArgAttr {
@@ -104,6 +175,12 @@ impl ArgAttr {
         self.default_value = Some(value.into());
         self
     }
+
+    /// Set the parsing action.
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
 }
 
 impl fmt::Display for ArgAttr {
@@ -117,7 +194,10 @@ impl fmt::Display for ArgAttr {
             parts.push(format!("short = '{}'", c));
         }
         if let Some(ref default) = self.default_value {
-            parts.push(format!("default_value = \"{}\"", default));
+            parts.push(format!("default_value = {:?}", default));
+        }
+        if let Some(ref action) = self.action {
+            parts.push(format!("action = {}", action));
         }
 
         write!(f, "arg({})", parts.join(", "))
@@ -146,12 +226,42 @@ mod tests {
         assert_eq!(attr.to_string(), "command(about = \"A CLI tool\")");
     }
 
+    #[test]
+    fn test_command_about_escapes() {
+        let attr = ClapAttr::command_about(r#"Say "hi" \ bye"#);
+        assert_eq!(attr.to_string(), r#"command(about = "Say \"hi\" \\ bye")"#);
+    }
+
+    #[test]
+    fn test_command_long_about() {
+        let attr = ClapAttr::command_long_about("Deploy\n\nUploads the build.");
+        assert_eq!(
+            attr.to_string(),
+            r#"command(long_about = "Deploy\n\nUploads the build.")"#
+        );
+    }
+
+    #[test]
+    fn test_command_after_help() {
+        let attr = ClapAttr::command_after_help("Examples:\n  app run");
+        assert_eq!(
+            attr.to_string(),
+            r#"command(after_help = "Examples:\n  app run")"#
+        );
+    }
+
     #[test]
     fn test_command_subcommand() {
         let attr = ClapAttr::command_subcommand();
         assert_eq!(attr.to_string(), "command(subcommand)");
     }
 
+    #[test]
+    fn test_command_flatten() {
+        let attr = ClapAttr::command_flatten();
+        assert_eq!(attr.to_string(), "command(flatten)");
+    }
+
     #[test]
     fn test_arg_long_only() {
         let attr = ClapAttr::arg(ArgAttr::new().long());
@@ -170,6 +280,20 @@ mod tests {
         assert_eq!(attr.to_string(), "arg(long, default_value = \"default\")");
     }
 
+    #[test]
+    fn test_arg_with_action() {
+        let attr = ClapAttr::arg(
+            ArgAttr::new()
+                .long()
+                .short('v')
+                .action("clap::ArgAction::Count"),
+        );
+        assert_eq!(
+            attr.to_string(),
+            "arg(long, short = 'v', action = clap::ArgAction::Count)"
+        );
+    }
+
     #[test]
     fn test_value_name() {
         let attr = ClapAttr::value_name("my-value");
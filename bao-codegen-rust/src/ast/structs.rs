@@ -103,6 +103,11 @@ impl Struct {
         self
     }
 
+    /// Conditionally add a field.
+    pub fn field_if(self, condition: bool, field: Field) -> Self {
+        if condition { self.field(field) } else { self }
+    }
+
     /// Conditionally add a derive.
     pub fn derive_if(self, condition: bool, derive: impl Into<String>) -> Self {
         if condition { self.derive(derive) } else { self }
@@ -1,27 +1,160 @@
 use std::path::{Path, PathBuf};
 
 use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{LogFormat, LoggerResource};
 
-use super::{GENERATED_HEADER, uses};
+use super::{EXTERNAL_VARIANT, GENERATED_HEADER, uses};
 use crate::{Fn, RustFile, Use};
 
 /// The app.rs file that handles Context setup and CLI dispatch
 pub struct AppRs {
     pub is_async: bool,
+    pub globals: bool,
+    pub locales: bool,
+    pub doctor: bool,
+    pub external: bool,
+    pub default_command: bool,
+    pub lazy_context: bool,
+    /// The logger whose tracing subscriber is installed before dispatch.
+    pub logging: Option<LoggerResource>,
 }
 
 impl AppRs {
     pub fn new(is_async: bool) -> Self {
-        Self { is_async }
+        Self {
+            is_async,
+            globals: false,
+            locales: false,
+            doctor: false,
+            external: false,
+            default_command: false,
+            lazy_context: false,
+            logging: None,
+        }
+    }
+
+    /// Translate the parser's help text before parsing.
+    pub fn with_locales(mut self, locales: bool) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Run the `doctor` subcommand before building the context, whose
+    /// connections it checks.
+    pub fn with_doctor(mut self, doctor: bool) -> Self {
+        self.doctor = doctor;
+        self
+    }
+
+    /// Run external commands before building the context, which they do not
+    /// use.
+    pub fn with_external(mut self, external: bool) -> Self {
+        self.external = external;
+        self
+    }
+
+    /// Match on the optional command of a CLI that has a default command.
+    pub fn with_default_command(mut self, default_command: bool) -> Self {
+        self.default_command = default_command;
+        self
+    }
+
+    /// Build a context whose resources are set up on first use, so
+    /// `Context::new` neither awaits nor fails.
+    pub fn with_lazy_context(mut self, lazy_context: bool) -> Self {
+        self.lazy_context = lazy_context;
+        self
+    }
+
+    /// Install a tracing subscriber with the logger's filter and format.
+    pub fn with_logging(mut self, logging: Option<LoggerResource>) -> Self {
+        self.logging = logging;
+        self
+    }
+
+    /// The tracing-subscriber setup: `RUST_LOG` overrides the default
+    /// filter, and `LOG_FORMAT` the default format.
+    fn build_init_logging_fn(logging: &LoggerResource) -> Fn {
+        let formats = match logging.format {
+            LogFormat::Pretty => {
+                "Ok(\"json\") => subscriber.json().init(),\n    _ => subscriber.init(),"
+            }
+            LogFormat::Json => {
+                "Ok(\"pretty\") => subscriber.init(),\n    _ => subscriber.json().init(),"
+            }
+        };
+        Fn::new("init_logging")
+            .private()
+            .doc("Install the tracing subscriber. `RUST_LOG` overrides the filter and `LOG_FORMAT` the format.")
+            .body(format!(
+                "let filter = tracing_subscriber::EnvFilter::try_from_default_env()\n    .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new({:?}));\nlet subscriber = tracing_subscriber::fmt()\n    .with_env_filter(filter)\n    .with_writer(std::io::stderr);\nmatch std::env::var(\"LOG_FORMAT\").as_deref() {{\n    {}\n}}",
+                logging.default_filter(),
+                formats
+            ))
+    }
+
+    /// Pass the parsed global options into `Context::new`.
+    pub fn with_globals(mut self, globals: bool) -> Self {
+        self.globals = globals;
+        self
     }
 
     fn build_run_fn(&self) -> Fn {
         let await_suffix = if self.is_async { ".await" } else { "" };
-        let body = format!(
-            "let ctx = Context::new(){}?;\nCli::parse().dispatch(&ctx){}",
-            await_suffix, await_suffix
-        );
+        let new_suffix = if self.lazy_context {
+            String::new()
+        } else {
+            format!("{}?", await_suffix)
+        };
+        let parse = if self.locales {
+            "Cli::from_arg_matches(&localize(Cli::command()).get_matches())\n    .unwrap_or_else(|e| e.exit())"
+        } else {
+            "Cli::parse()"
+        };
+        let body = if self.globals || self.locales || self.doctor || self.external {
+            // Doctor and external commands run before the context is built
+            let pattern = |variant: &str| {
+                if self.default_command {
+                    format!("Some(Commands::{}(args))", variant)
+                } else {
+                    format!("Commands::{}(args)", variant)
+                }
+            };
+            let mut early = String::new();
+            if self.doctor {
+                early.push_str(&format!(
+                    "if let {} = &cli.command {{\n    return doctor::run(args){};\n}}\n",
+                    pattern("Doctor"),
+                    await_suffix
+                ));
+            }
+            if self.external {
+                early.push_str(&format!(
+                    "if let {} = &cli.command {{\n    return run_external(args);\n}}\n",
+                    pattern(EXTERNAL_VARIANT)
+                ));
+            }
+            let globals = if self.globals {
+                "cli.globals.clone()"
+            } else {
+                ""
+            };
+            format!(
+                "let cli = {};\n{}let ctx = Context::new({}){};\ncli.dispatch(&ctx){}",
+                parse, early, globals, new_suffix, await_suffix
+            )
+        } else {
+            format!(
+                "let ctx = Context::new(){};\nCli::parse().dispatch(&ctx){}",
+                new_suffix, await_suffix
+            )
+        };
 
+        let body = if self.logging.is_some() {
+            format!("init_logging();\n{}", body)
+        } else {
+            body
+        };
         Fn::new("run")
             .returns("eyre::Result<()>")
             .body(body)
@@ -39,11 +172,34 @@ impl GeneratedFile for AppRs {
     }
 
     fn render(&self) -> String {
-        RustFile::new()
-            .use_stmt(uses::clap_parser())
+        let (clap, mut generated) = if self.locales {
+            (
+                Use::new("clap").symbols(["CommandFactory", "FromArgMatches"]),
+                Use::new("crate::generated").symbols(["Cli", "localize"]),
+            )
+        } else {
+            (
+                uses::clap_parser(),
+                Use::new("crate::generated").symbol("Cli"),
+            )
+        };
+        if self.doctor || self.external {
+            generated = generated.symbol("Commands");
+        }
+        if self.doctor {
+            generated = generated.symbol("doctor");
+        }
+        if self.external {
+            generated = generated.symbol("run_external");
+        }
+        let mut file = RustFile::new()
+            .use_stmt(clap)
             .use_stmt(uses::context())
-            .use_stmt(Use::new("crate::generated").symbol("Cli"))
-            .add(self.build_run_fn())
-            .render_with_header(GENERATED_HEADER)
+            .use_stmt(generated)
+            .add(self.build_run_fn());
+        if let Some(logging) = &self.logging {
+            file = file.add(Self::build_init_logging_fn(logging));
+        }
+        file.render_with_header(GENERATED_HEADER)
     }
 }
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::GrpcOptions;
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+/// Build dependency emitting the build metadata.
+pub const VERGEN_DEPENDENCY: (&str, &str) = (
+    "vergen",
+    r#"{ version = "8", features = ["build", "git", "gitcl"] }"#,
+);
+
+/// File in `OUT_DIR` that tonic-build writes the module tree of the compiled
+/// `.proto` packages to.
+pub const GRPC_INCLUDE_FILE: &str = "grpc.rs";
+
+/// Long version of the root command: the version followed by the commit and
+/// build time that build.rs exposes as `VERGEN_*` variables.
+pub fn long_version(version: &str) -> String {
+    format!(
+        r#"concat!("{}", "\ncommit: ", env!("VERGEN_GIT_SHA"), "\nbuilt: ", env!("VERGEN_BUILD_TIMESTAMP"))"#,
+        version
+    )
+}
+
+/// The build.rs script capturing the git commit and build time with vergen,
+/// and compiling gRPC clients from `.proto` files with tonic-build.
+///
+/// Outside a git checkout vergen still sets the commit variable, to a
+/// placeholder, so the project builds from a source archive.
+#[derive(Default)]
+pub struct BuildRs {
+    /// Whether the commit and build time are captured.
+    pub build_info: bool,
+    /// The channel whose `.proto` files are compiled.
+    pub grpc: Option<GrpcOptions>,
+}
+
+impl BuildRs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the commit and build time as `VERGEN_*` variables.
+    pub fn with_build_info(mut self, build_info: bool) -> Self {
+        self.build_info = build_info;
+        self
+    }
+
+    /// Compile the `.proto` files of a gRPC channel into clients, included
+    /// by the generated `grpc` module.
+    pub fn with_grpc(mut self, grpc: Option<GrpcOptions>) -> Self {
+        self.grpc = grpc.filter(GrpcOptions::has_protos);
+        self
+    }
+
+    /// Returns true if the script has anything to do.
+    pub fn is_needed(&self) -> bool {
+        self.build_info || self.grpc.is_some()
+    }
+
+    fn paths(paths: &[String]) -> String {
+        let paths: Vec<String> = paths.iter().map(|path| format!("{:?}", path)).collect();
+        format!("&[{}]", paths.join(", "))
+    }
+}
+
+impl GeneratedFile for BuildRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("build.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut steps = Vec::new();
+        if let Some(grpc) = &self.grpc {
+            steps.push(format!(
+                r#"    tonic_build::configure()
+        .build_server(false)
+        .include_file("{}")
+        .compile_protos({}, {})?;"#,
+                GRPC_INCLUDE_FILE,
+                Self::paths(&grpc.protos),
+                Self::paths(&grpc.includes)
+            ));
+        }
+        if self.build_info {
+            steps.push(
+                r#"    vergen::EmitBuilder::builder()
+        .build_timestamp()
+        .git_sha(true)
+        .emit()?;"#
+                    .to_string(),
+            );
+        }
+        RustFile::new()
+            .add(RawCode::new(format!(
+                "fn main() -> Result<(), Box<dyn std::error::Error>> {{\n{}\n    Ok(())\n}}",
+                steps.join("\n")
+            )))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
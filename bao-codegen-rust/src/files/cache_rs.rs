@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::CacheOptions;
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+const STRUCT: &str = r#"/// String values kept in memory and in files that outlive the invocation.
+///
+/// Serialize structured results, e.g. with serde_json, before caching them.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    memory: moka::future::Cache<String, String>,
+    dir: std::path::PathBuf,
+}"#;
+
+/// The generated/cache.rs file with the disk-backed `Cache` of the
+/// `[context.cache]` resource.
+///
+/// In-memory caches are a plain `moka::future::Cache` on the context, so this
+/// file is only generated for `mode = "disk"`. Files hold the key on their
+/// first line, so a hash collision reads as a miss.
+pub struct CacheRs {
+    pub options: CacheOptions,
+}
+
+impl CacheRs {
+    pub fn new(options: CacheOptions) -> Self {
+        Self { options }
+    }
+
+    /// The moka cache builder, with method calls indented past `indent`.
+    pub fn builder(options: &CacheOptions, indent: usize) -> String {
+        let mut lines = vec![
+            "moka::future::Cache::builder()".to_string(),
+            format!(".max_capacity({})", options.max_capacity),
+        ];
+        if let Some(ttl) = options.ttl {
+            lines.push(format!(
+                ".time_to_live(std::time::Duration::from_millis({}))",
+                ttl.as_millis()
+            ));
+        }
+        lines.push(".build()".to_string());
+        lines.join(&format!("\n{}", " ".repeat(indent + 4)))
+    }
+
+    fn build_dir(&self) -> String {
+        match &self.options.path {
+            Some(path) => format!("std::path::PathBuf::from({:?})", path),
+            None => format!(
+                "dirs::cache_dir()\n            .ok_or_else(|| eyre::eyre!(\"no cache directory on this system\"))?\n            .join({:?})",
+                self.options.namespace
+            ),
+        }
+    }
+
+    fn build_get(&self) -> String {
+        let expiry = match self.options.ttl {
+            Some(ttl) => format!(
+                r#"
+        let age = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()?
+            .elapsed()
+            .unwrap_or_default();
+        if age > std::time::Duration::from_millis({}) {{
+            return None;
+        }}"#,
+                ttl.as_millis()
+            ),
+            None => String::new(),
+        };
+        format!(
+            r#"    /// The value of `key`, from memory or else from its file.
+    pub async fn get(&self, key: &str) -> Option<String> {{
+        if let Some(value) = self.memory.get(key).await {{
+            return Some(value);
+        }}
+        let path = self.path(key);{expiry}
+        let text = std::fs::read_to_string(&path).ok()?;
+        let value = text.strip_prefix(key)?.strip_prefix('\n')?.to_string();
+        self.memory.insert(key.to_string(), value.clone()).await;
+        Some(value)
+    }}"#
+        )
+    }
+
+    fn build_impl(&self) -> String {
+        format!(
+            r#"impl Cache {{
+    /// Create the cache directory.
+    pub fn open() -> eyre::Result<Self> {{
+        let dir = {dir};
+        std::fs::create_dir_all(&dir)
+            .map_err(|err| eyre::eyre!("failed to create {{}}: {{}}", dir.display(), err))?;
+        Ok(Self {{
+            memory: {builder},
+            dir,
+        }})
+    }}
+
+{get}
+
+    /// Store `value` under `key`, in memory and in its file.
+    pub async fn insert(&self, key: impl Into<String>, value: impl Into<String>) -> eyre::Result<()> {{
+        let (key, value) = (key.into(), value.into());
+        std::fs::write(self.path(&key), format!("{{}}\n{{}}", key, value))?;
+        self.memory.insert(key, value).await;
+        Ok(())
+    }}
+
+    /// Remove `key` from memory and disk.
+    pub async fn invalidate(&self, key: &str) -> eyre::Result<()> {{
+        self.memory.invalidate(key).await;
+        match std::fs::remove_file(self.path(key)) {{
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }}
+    }}
+
+    /// The file of `key`, named by its FNV-1a hash so any key is a valid name.
+    fn path(&self, key: &str) -> std::path::PathBuf {{
+        let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {{
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        }});
+        self.dir.join(format!("{{:016x}}", hash))
+    }}
+}}"#,
+            dir = self.build_dir(),
+            builder = Self::builder(&self.options, 12),
+            get = self.build_get(),
+        )
+    }
+}
+
+impl GeneratedFile for CacheRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("cache.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(STRUCT))
+            .add(RawCode::new(self.build_impl()))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -10,6 +10,7 @@ pub struct CargoToml {
     pub version: Version,
     pub edition: String,
     pub dependencies: Vec<(String, String)>,
+    pub build_dependencies: Vec<(String, String)>,
 }
 
 impl CargoToml {
@@ -19,6 +20,7 @@ impl CargoToml {
             version: Version::new(0, 1, 0),
             edition: DEFAULT_EDITION.to_string(),
             dependencies: Vec::new(),
+            build_dependencies: Vec::new(),
         }
     }
 
@@ -48,6 +50,11 @@ impl CargoToml {
         self.dependencies = dependencies;
         self
     }
+
+    pub fn with_build_dependencies(mut self, dependencies: Vec<(String, String)>) -> Self {
+        self.build_dependencies = dependencies;
+        self
+    }
 }
 
 impl GeneratedFile for CargoToml {
@@ -71,15 +78,23 @@ edition = "{}"
             self.name, self.version, self.edition
         );
 
-        for (dep_name, dep_version) in &self.dependencies {
-            if dep_version.contains('{') {
-                // Complex dependency with features
-                out.push_str(&format!("{} = {}\n", dep_name, dep_version));
-            } else {
-                out.push_str(&format!("{} = \"{}\"\n", dep_name, dep_version));
-            }
+        render_dependencies(&mut out, &self.dependencies);
+        if !self.build_dependencies.is_empty() {
+            out.push_str("\n[build-dependencies]\n");
+            render_dependencies(&mut out, &self.build_dependencies);
         }
 
         out
     }
 }
+
+fn render_dependencies(out: &mut String, dependencies: &[(String, String)]) {
+    for (dep_name, dep_version) in dependencies {
+        if dep_version.contains('{') {
+            // Complex dependency with features
+            out.push_str(&format!("{} = {}\n", dep_name, dep_version));
+        } else {
+            out.push_str(&format!("{} = \"{}\"\n", dep_name, dep_version));
+        }
+    }
+}
@@ -1,18 +1,237 @@
 use std::path::{Path, PathBuf};
 
-use baobao_core::{FileRules, GeneratedFile, Version, to_pascal_case, to_snake_case};
-use baobao_ir::CommandOp;
+use baobao_core::{
+    FileRules, GeneratedFile, Version, to_env_var, to_kebab_case, to_pascal_case, to_snake_case,
+};
+use baobao_ir::{CommandOp, Docs, Input, InputKind, InputType, NO_INPUT_FLAG};
 
-use super::{GENERATED_HEADER, uses};
-use crate::{Arm, ClapAttr, Enum, Field, Fn, Impl, Match, Param, RustFile, Struct, Use, Variant};
+use super::{DOCS_COMMAND, DOCTOR_COMMAND, GENERATED_HEADER, long_version, uses};
+use crate::{
+    Arm, ClapAttr, Enum, Field, Fn, Impl, Match, Param, RawCode, RustFile, Struct, Use, Variant,
+};
+
+/// Match arm running `call`, first printing a warning to stderr for the
+/// command and any of its given flags that are deprecated, then asking for
+/// missing arguments and reading secrets that weren't given from the
+/// environment or a hidden prompt, and setting up the context resources the
+/// command requires.
+pub(crate) fn dispatch_arm(
+    pattern: String,
+    cmd: &CommandOp,
+    call: String,
+    cli: &str,
+    is_async: bool,
+) -> Arm {
+    let mut lines = Vec::new();
+    if let Some(message) = &cmd.deprecated {
+        lines.push(eprintln(&format!(
+            "warning: command '{}' is deprecated: {}",
+            cmd.path.join(" "),
+            message
+        )));
+    }
+    if !cmd.has_subcommands() {
+        for input in &cmd.inputs {
+            let Some(message) = &input.deprecated else {
+                continue;
+            };
+            let warning = eprintln(&format!(
+                "warning: flag '--{}' is deprecated: {}",
+                to_kebab_case(&input.name),
+                message
+            ));
+            match given(input) {
+                Some(condition) => lines.push(format!("if {} {{\n    {}\n}}", condition, warning)),
+                None => lines.push(warning),
+            }
+        }
+    }
+    // Ask for missing arguments before secrets
+    let fills: Vec<_> = cmd
+        .inputs
+        .iter()
+        .filter_map(ask_missing)
+        .chain(
+            cmd.inputs
+                .iter()
+                .filter(|input| input.ty == InputType::Secret)
+                .map(|input| read_secret(cmd, input, cli)),
+        )
+        .collect();
+    let pattern = if fills.is_empty() {
+        pattern
+    } else {
+        pattern.replacen("(args)", "(mut args)", 1)
+    };
+    lines.extend(fills);
+    if !cmd.has_subcommands() {
+        let await_suffix = if is_async { ".await" } else { "" };
+        lines.extend(
+            cmd.requires
+                .iter()
+                .map(|field| format!("ctx.{}(){}?;", field, await_suffix)),
+        );
+    }
+    if lines.is_empty() {
+        return Arm::new(pattern).body(call);
+    }
+    lines.push(call);
+    Arm::new(pattern).body_block(lines.join("\n"))
+}
+
+/// Expression for the subcommand to dispatch: the parsed one, or the
+/// `default` command of `enum_name` when none is given.
+pub(crate) fn command_expr(enum_name: &str, default: Option<&str>) -> String {
+    match default {
+        Some(_) => format!(
+            "self.command.map_or_else({}::default_command, Ok)?",
+            enum_name
+        ),
+        None => "self.command".to_string(),
+    }
+}
+
+/// `default_command` on the `enum_name` subcommands, parsing `name` as if it
+/// were given with nothing else, so its arguments get their defaults.
+pub(crate) fn default_command_impl(enum_name: &str, name: &str) -> Impl {
+    let name = to_kebab_case(name);
+    Impl::new(enum_name).method(
+        Fn::new("default_command")
+            .doc(format!("The `{}` command, run when none is given.", name))
+            .private()
+            .returns("eyre::Result<Self>")
+            .body(format!(
+                "let bin = env!(\"CARGO_PKG_NAME\");\n\
+                 let command = <Self as clap::Subcommand>::augment_subcommands(clap::Command::new(bin));\n\
+                 let matches = command.try_get_matches_from([bin, {name:?}])?;\n\
+                 Ok(<Self as clap::FromArgMatches>::from_arg_matches(&matches)?)"
+            )),
+    )
+}
+
+/// Add the command's long description, examples and footer to its `--help`.
+pub(crate) fn with_help(mut variant: Variant, cmd: &CommandOp) -> Variant {
+    if let Some(text) = long_help(&cmd.description, cmd.docs.long_description.as_deref()) {
+        variant = variant.clap_attr(ClapAttr::command_long_about(text));
+    }
+    match after_help(&cmd.docs) {
+        Some(text) => variant.clap_attr(ClapAttr::command_after_help(text)),
+        None => variant,
+    }
+}
+
+/// Text shown after the help: the examples, then the footer, or `None` when
+/// there are neither.
+fn after_help(docs: &Docs) -> Option<String> {
+    let mut sections = Vec::new();
+    if !docs.examples.is_empty() {
+        let mut text = "Examples:".to_string();
+        for example in &docs.examples {
+            text.push_str(&format!("\n  {}", example.command));
+        }
+        sections.push(text);
+    }
+    if let Some(footer) = docs
+        .footer
+        .as_deref()
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+    {
+        sections.push(footer.to_string());
+    }
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+/// Text shown by `--help`: the short description followed by the long one,
+/// or `None` when there is no long description. Clap replaces the short
+/// text with this rather than appending to it.
+pub(crate) fn long_help(description: &str, long_description: Option<&str>) -> Option<String> {
+    let long = long_description.map(str::trim).filter(|l| !l.is_empty())?;
+    Some(if description.is_empty() {
+        long.to_string()
+    } else {
+        format!("{}\n\n{}", description, long)
+    })
+}
+
+/// `eprintln!` statement printing `text` as is.
+fn eprintln(text: &str) -> String {
+    format!(
+        "eprintln!({:?});",
+        text.replace('{', "{{").replace('}', "}}")
+    )
+}
+
+/// Statement asking for an argument with a prompt message when it is empty,
+/// failing instead when `--no-input` is given.
+fn ask_missing(input: &Input) -> Option<String> {
+    let message = input.prompt_message()?;
+    let field = format!("args.{}", to_snake_case(&input.name));
+    Some(format!(
+        "if {field}.is_empty() {{\n    if ctx.globals.{no_input} {{\n        eyre::bail!({missing:?});\n    }}\n    {field} = dialoguer::Input::new()\n        .with_prompt({message:?})\n        .interact_text()?;\n}}",
+        field = field,
+        no_input = to_snake_case(NO_INPUT_FLAG),
+        missing = format!("missing argument <{}>", to_kebab_case(&input.name)),
+        message = message,
+    ))
+}
+
+/// Statement filling in an empty secret from its `<CLI>_<COMMAND>_<NAME>`
+/// environment variable, or else a prompt that doesn't echo the input.
+fn read_secret(cmd: &CommandOp, input: &Input, cli: &str) -> String {
+    let field = format!("args.{}", to_snake_case(&input.name));
+    let var = to_env_var(cli, &cmd.path, &input.name);
+    let label = input
+        .description
+        .clone()
+        .unwrap_or_else(|| to_kebab_case(&input.name));
+    format!(
+        "if {field}.is_empty() {{\n    {field} = match std::env::var({var:?}) {{\n        Ok(value) => value,\n        Err(_) => rpassword::prompt_password({prompt:?})?,\n    }};\n}}",
+        field = field,
+        var = var,
+        prompt = format!("{}: ", label),
+    )
+}
+
+/// Condition on the parsed `args` that is true when the flag was given, or
+/// `None` when it always is.
+fn given(input: &Input) -> Option<String> {
+    let field = format!("args.{}", to_snake_case(&input.name));
+    let is_bool_flag = matches!(input.kind, InputKind::Flag { .. })
+        && input.ty == InputType::Bool
+        && input.choices.is_none();
+    if input.ty == InputType::Count {
+        Some(format!("{} > 0", field))
+    } else if input.asks_when_missing() {
+        Some(format!("!{}.is_empty()", field))
+    } else if is_bool_flag {
+        Some(field)
+    } else if input.multiple {
+        Some(format!("!{}.is_empty()", field))
+    } else if input.required || input.default.is_some() {
+        None
+    } else {
+        Some(format!("{}.is_some()", field))
+    }
+}
+
+/// Name of the `Commands` variant collecting commands the CLI does not define.
+pub const EXTERNAL_VARIANT: &str = "External";
 
 /// The cli.rs file containing the main CLI struct and dispatch logic
 pub struct CliRs {
     pub name: String,
     pub version: Version,
     pub description: Option<String>,
+    pub footer: Option<String>,
     pub commands: Vec<CommandOp>,
     pub is_async: bool,
+    pub globals: bool,
+    pub build_info: bool,
+    pub docs: bool,
+    pub doctor: bool,
+    pub external: bool,
+    pub default_command: Option<String>,
 }
 
 impl CliRs {
@@ -30,11 +249,64 @@ impl CliRs {
                 .parse()
                 .unwrap_or_else(|_| Version::new(0, 1, 0)),
             description,
+            footer: None,
             commands,
             is_async,
+            globals: false,
+            build_info: false,
+            docs: false,
+            doctor: false,
+            external: false,
+            default_command: None,
         }
     }
 
+    /// Show `footer` at the end of the CLI's `--help`.
+    pub fn with_footer(mut self, footer: Option<String>) -> Self {
+        self.footer = footer
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty());
+        self
+    }
+
+    /// Add the hidden `docs` subcommand from generated/docs.rs.
+    pub fn with_docs(mut self, docs: bool) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Add the `doctor` subcommand from generated/doctor.rs.
+    ///
+    /// app.rs runs it before the context is built, so dispatch never sees it.
+    pub fn with_doctor(mut self, doctor: bool) -> Self {
+        self.doctor = doctor;
+        self
+    }
+
+    /// Run `<name>-<command>` from PATH for commands the CLI does not define.
+    pub fn with_external(mut self, external: bool) -> Self {
+        self.external = external;
+        self
+    }
+
+    /// Run `default_command` when the CLI is invoked without a command.
+    pub fn with_default_command(mut self, default_command: Option<String>) -> Self {
+        self.default_command = default_command;
+        self
+    }
+
+    /// Show the commit and build time from build.rs in `--version`.
+    pub fn with_build_info(mut self, build_info: bool) -> Self {
+        self.build_info = build_info;
+        self
+    }
+
+    /// Flatten the generated `GlobalArgs` into the root parser.
+    pub fn with_globals(mut self, globals: bool) -> Self {
+        self.globals = globals;
+        self
+    }
+
     /// Create with a parsed Version (for backwards compatibility).
     pub fn with_version(
         name: impl Into<String>,
@@ -47,8 +319,15 @@ impl CliRs {
             name: name.into(),
             version,
             description,
+            footer: None,
             commands,
             is_async,
+            globals: false,
+            build_info: false,
+            docs: false,
+            doctor: false,
+            external: false,
+            default_command: None,
         }
     }
 
@@ -58,17 +337,45 @@ impl CliRs {
             .derive("Debug")
             .clap_attr(ClapAttr::command_name(&self.name))
             .clap_attr(ClapAttr::command_version(self.version.to_string()))
+            .clap_attr_if(
+                self.build_info,
+                ClapAttr::command_long_version(long_version(&self.version.to_string())),
+            )
             .clap_attr_if(
                 self.description.is_some(),
                 ClapAttr::command_about(self.description.as_deref().unwrap_or("")),
             )
-            .field(Field::new("command", "Commands").clap_attr(ClapAttr::command_subcommand()))
+            .clap_attr_if(
+                self.external,
+                ClapAttr::command_allow_external_subcommands(),
+            )
+            .clap_attr_if(
+                self.footer.is_some(),
+                ClapAttr::command_after_help(self.footer.as_deref().unwrap_or("")),
+            )
+            .field_if(
+                self.globals,
+                Field::new("globals", "GlobalArgs").clap_attr(ClapAttr::command_flatten()),
+            )
+            .field(
+                Field::new("command", self.command_type())
+                    .clap_attr(ClapAttr::command_subcommand()),
+            )
+    }
+
+    /// `Commands`, optional when a default command runs without one.
+    fn command_type(&self) -> &'static str {
+        if self.default_command.is_some() {
+            "Option<Commands>"
+        } else {
+            "Commands"
+        }
     }
 
     fn build_dispatch_impl(&self) -> Impl {
         let await_suffix = if self.is_async { ".await" } else { "" };
 
-        let mut match_expr = Match::new("self.command");
+        let mut match_expr = Match::new(command_expr("Commands", self.default_command.as_deref()));
         for cmd in &self.commands {
             let pascal = to_pascal_case(&cmd.name);
             let (pattern, body) = if cmd.has_subcommands() {
@@ -87,7 +394,24 @@ impl CliRs {
                     ),
                 )
             };
-            match_expr = match_expr.arm(Arm::new(pattern).body(body));
+            match_expr =
+                match_expr.arm(dispatch_arm(pattern, cmd, body, &self.name, self.is_async));
+        }
+        if self.docs {
+            match_expr =
+                match_expr.arm(Arm::new("Commands::Docs(args)").body("super::docs::run(args)"));
+        }
+        if self.doctor {
+            match_expr = match_expr.arm(
+                Arm::new("Commands::Doctor(_)")
+                    .body("unreachable!(\"doctor runs before the context is built\")"),
+            );
+        }
+        if self.external {
+            match_expr = match_expr.arm(
+                Arm::new(format!("Commands::{}(_)", EXTERNAL_VARIANT))
+                    .body("unreachable!(\"external commands run before the context is built\")"),
+            );
         }
 
         let dispatch = Fn::new("dispatch")
@@ -110,11 +434,63 @@ impl CliRs {
             } else {
                 format!("{}Args", pascal)
             };
-            e = e.variant(Variant::new(&pascal).doc(&cmd.description).tuple(data));
+            let variant = cmd.aliases.iter().fold(
+                Variant::new(&pascal).doc(&cmd.description).tuple(data),
+                |variant, alias| variant.clap_attr(ClapAttr::command_alias(alias)),
+            );
+            e = e.variant(with_help(variant, cmd));
+        }
+        if self.docs {
+            e = e.variant(
+                Variant::new(to_pascal_case(DOCS_COMMAND))
+                    .doc("Write Markdown or man pages for every command")
+                    .clap_attr(ClapAttr::command_hide())
+                    .tuple("super::docs::DocsArgs"),
+            );
+        }
+        if self.doctor {
+            e = e.variant(
+                Variant::new(to_pascal_case(DOCTOR_COMMAND))
+                    .doc("Check that the environment and databases the CLI needs are in place")
+                    .tuple("super::doctor::DoctorArgs"),
+            );
+        }
+        if self.external {
+            e = e.variant(
+                Variant::new(EXTERNAL_VARIANT)
+                    .clap_attr(ClapAttr::command_external_subcommand())
+                    .tuple("Vec<String>"),
+            );
         }
 
         e
     }
+
+    /// `run_external`, running `<name>-<command>` from PATH with the rest of
+    /// the arguments and exiting with its status.
+    fn build_run_external(&self) -> RawCode {
+        RawCode::new(format!(
+            r#"/// Run `{name}-<command>` from PATH with the remaining arguments and exit
+/// with its status.
+pub fn run_external(args: &[String]) -> eyre::Result<()> {{
+    let Some((command, rest)) = args.split_first() else {{
+        eyre::bail!("missing command");
+    }};
+    let program = format!("{name}-{{}}", command);
+    let status = std::process::Command::new(&program)
+        .args(rest)
+        .status()
+        .map_err(|e| match e.kind() {{
+            std::io::ErrorKind::NotFound => {{
+                eyre::eyre!("unknown command '{{}}': `{{}}` is not on PATH", command, program)
+            }}
+            _ => eyre::eyre!("failed to run `{{}}`: {{}}", program, e),
+        }})?;
+    std::process::exit(status.code().unwrap_or(1))
+}}"#,
+            name = self.name
+        ))
+    }
 }
 
 impl GeneratedFile for CliRs {
@@ -127,13 +503,25 @@ impl GeneratedFile for CliRs {
     }
 
     fn render(&self) -> String {
-        RustFile::new()
+        let mut file = RustFile::new()
             .use_stmt(uses::clap_parser_subcommand())
-            .use_stmt(Use::new("super::commands").symbol("*"))
+            .use_stmt(Use::new("super::commands").symbol("*"));
+        if self.globals {
+            file = file.use_stmt(Use::new("super::globals").symbol("GlobalArgs"));
+        }
+        let mut file = file
             .use_stmt(uses::context())
             .add(self.build_cli_struct())
             .add(self.build_dispatch_impl())
-            .add(self.build_commands_enum())
-            .render_with_header(GENERATED_HEADER)
+            .add(self.build_commands_enum());
+        if let Some(name) = &self.default_command {
+            file = file.add(default_command_impl("Commands", name));
+        }
+        if self.external {
+            file.add(self.build_run_external())
+        } else {
+            file
+        }
+        .render_with_header(GENERATED_HEADER)
     }
 }
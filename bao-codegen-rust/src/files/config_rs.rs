@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{ConfigFieldType, ConfigFormat, ConfigOptions};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile, naming::RUST_NAMING};
+
+/// The generated/config.rs file with the typed `Config` struct of the
+/// `[context.config]` file and its loader.
+pub struct ConfigRs {
+    pub options: ConfigOptions,
+}
+
+impl ConfigRs {
+    pub fn new(options: ConfigOptions) -> Self {
+        Self { options }
+    }
+
+    /// The figment provider reading the file.
+    fn provider(format: ConfigFormat) -> &'static str {
+        match format {
+            ConfigFormat::Toml => "Toml",
+            ConfigFormat::Json => "Json",
+            ConfigFormat::Yaml => "Yaml",
+        }
+    }
+
+    fn field_type(field_type: ConfigFieldType) -> &'static str {
+        match field_type {
+            ConfigFieldType::String => "String",
+            ConfigFieldType::Int => "i64",
+            ConfigFieldType::Float => "f64",
+            ConfigFieldType::Bool => "bool",
+        }
+    }
+
+    fn build_struct(&self) -> String {
+        let fields: Vec<String> = self
+            .options
+            .fields
+            .iter()
+            .map(|(key, field)| {
+                let name = RUST_NAMING.field_name(key);
+                let rename = if name.trim_start_matches("r#") != key {
+                    format!("    #[serde(rename = {:?})]\n", key)
+                } else {
+                    String::new()
+                };
+                let ty = Self::field_type(field.field_type);
+                let ty = if field.optional {
+                    format!("Option<{}>", ty)
+                } else {
+                    ty.to_string()
+                };
+                format!("{}    pub {}: {},", rename, name, ty)
+            })
+            .collect();
+        let body = if fields.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{\n{}\n}}", fields.join("\n"))
+        };
+        format!(
+            "/// Settings read from `{}` when the CLI starts.\n#[derive(Debug, Clone, serde::Deserialize)]\npub struct Config {}",
+            self.options.path, body
+        )
+    }
+
+    fn build_impl(&self) -> String {
+        format!(
+            r#"impl Config {{
+    /// Load `{path}`, failing if a required field is missing or has the wrong type.
+    pub fn load() -> eyre::Result<Self> {{
+        use figment::providers::Format;
+        Ok(figment::Figment::new()
+            .merge(figment::providers::{provider}::file_exact({path:?}))
+            .extract()?)
+    }}
+}}"#,
+            path = self.options.path,
+            provider = Self::provider(self.options.format)
+        )
+    }
+}
+
+impl GeneratedFile for ConfigRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("config.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(self.build_struct()))
+            .add(RawCode::new(self.build_impl()))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -1,26 +1,61 @@
 use std::path::{Path, PathBuf};
 
 use baobao_codegen::{
-    adapters::{DatabaseAdapter, PoolInitInfo},
+    adapters::{DatabaseAdapter, GrpcAdapter, HttpAdapter, PoolInitInfo, RenderedResources},
     builder::{FieldSpec, RenderOptions, StructSpec, StructureRenderer, TypeRef},
     schema::ContextFieldInfo,
 };
 use baobao_core::{FileRules, GeneratedFile};
-use baobao_ir::{ContextFieldType, DatabaseType};
+use baobao_ir::{
+    CacheMode, ContextFieldType, DatabaseType, EmailOptions, QueueType, S3Options, SmtpTls,
+};
 
-use super::GENERATED_HEADER;
+use super::{CacheRs, GENERATED_HEADER};
 use crate::{
-    Fn, Impl, RawCode, RustFile, RustRenderer, RustStructureRenderer, Use, adapters::SqlxAdapter,
+    Fn, Impl, Param, RawCode, RustFile, RustRenderer, RustStructureRenderer, Use,
+    adapters::{DuckdbAdapter, ReqwestAdapter, TonicAdapter, database_adapter},
 };
 
 /// The context.rs file containing shared application state.
 pub struct ContextRs {
     pub fields: Vec<ContextFieldInfo>,
+    pub resources: RenderedResources,
+    pub globals: bool,
+    pub lazy: bool,
 }
 
 impl ContextRs {
     pub fn new(fields: Vec<ContextFieldInfo>) -> Self {
-        Self { fields }
+        Self {
+            fields,
+            resources: RenderedResources::default(),
+            globals: false,
+            lazy: false,
+        }
+    }
+
+    /// Set resources up on first use, through a getter per field, instead of
+    /// in `Context::new`.
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Add a `globals` field holding the parsed global options.
+    pub fn with_globals(mut self, globals: bool) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    /// Render custom resource fields with their adapter's code.
+    ///
+    /// Custom fields without rendered code are left out.
+    pub fn with_resources(mut self, resources: RenderedResources) -> Self {
+        self.fields.retain(|f| {
+            f.field_type != ContextFieldType::Custom || resources.get(&f.name).is_some()
+        });
+        self.resources = resources;
+        self
     }
 
     fn build_struct(&self) -> String {
@@ -30,44 +65,117 @@ impl ContextRs {
             .doc("Application context shared across all command handlers.");
 
         for field in &self.fields {
-            let type_ref = Self::map_context_type_ref(&field.field_type);
-            spec = spec.field(FieldSpec::new(&field.name, type_ref));
+            spec = spec.field(if self.lazy {
+                let cell = format!("{}<{}>", self.cell_type(), self.field_type(field));
+                FieldSpec::new(&field.name, TypeRef::named(cell)).private()
+            } else {
+                FieldSpec::new(&field.name, TypeRef::named(self.field_type(field)))
+            });
+        }
+        if self.globals {
+            spec = spec.field(FieldSpec::new("globals", TypeRef::named("GlobalArgs")));
         }
 
         renderer.render_struct(&spec)
     }
 
-    /// Map ContextFieldType to TypeRef.
-    fn map_context_type_ref(field_type: &ContextFieldType) -> TypeRef {
+    fn field_type(&self, field: &ContextFieldInfo) -> String {
+        match self.resources.get(&field.name) {
+            Some(code) => code.type_name.clone(),
+            None if Self::is_disk_cache(field) => "Cache".to_string(),
+            None => match &field.http {
+                Some(http) => ReqwestAdapter::new().client_type(http).to_string(),
+                None => Self::map_context_type(&field.field_type).to_string(),
+            },
+        }
+    }
+
+    fn has_async(&self) -> bool {
+        self.fields.iter().any(|f| f.is_async)
+    }
+
+    /// The cell holding a lazy field: async contexts initialize fields with
+    /// `.await`, which only tokio's `OnceCell` can run.
+    fn cell_type(&self) -> &'static str {
+        if self.has_async() {
+            "tokio::sync::OnceCell"
+        } else {
+            "std::sync::OnceLock"
+        }
+    }
+
+    /// HTTP clients with a base URL are wrapped in the generated `HttpClient`.
+    fn has_base_url(field: &ContextFieldInfo) -> bool {
+        field
+            .http
+            .as_ref()
+            .is_some_and(|http| http.base_url.is_some())
+    }
+
+    /// Disk caches are wrapped in the generated `Cache`.
+    fn is_disk_cache(field: &ContextFieldInfo) -> bool {
+        field
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.mode == CacheMode::Disk)
+    }
+
+    /// Map ContextFieldType to its Rust type.
+    fn map_context_type(field_type: &ContextFieldType) -> &'static str {
         match field_type {
-            ContextFieldType::Database(DatabaseType::Postgres) => TypeRef::named("sqlx::PgPool"),
-            ContextFieldType::Database(DatabaseType::Mysql) => TypeRef::named("sqlx::MySqlPool"),
-            ContextFieldType::Database(DatabaseType::Sqlite) => TypeRef::named("sqlx::SqlitePool"),
-            ContextFieldType::Http => TypeRef::named("reqwest::Client"),
+            ContextFieldType::Database(DatabaseType::Postgres) => "sqlx::PgPool",
+            ContextFieldType::Database(DatabaseType::Mysql) => "sqlx::MySqlPool",
+            ContextFieldType::Database(DatabaseType::Sqlite) => "sqlx::SqlitePool",
+            ContextFieldType::Database(DatabaseType::Duckdb) => {
+                DuckdbAdapter::new().pool_type(DatabaseType::Duckdb)
+            }
+            ContextFieldType::Http => "reqwest::Client",
+            ContextFieldType::Logger => "tracing::Dispatch",
+            ContextFieldType::Storage => "object_store::aws::AmazonS3",
+            ContextFieldType::Grpc => TonicAdapter::new().channel_type(),
+            ContextFieldType::Email => "lettre::AsyncSmtpTransport<lettre::Tokio1Executor>",
+            ContextFieldType::Config => "Config",
+            ContextFieldType::Secrets => "Secrets",
+            ContextFieldType::Llm => "LlmClient",
+            ContextFieldType::Cache => "moka::future::Cache<String, String>",
+            ContextFieldType::Workdir => "Workdir",
+            ContextFieldType::Queue(QueueType::Nats) => "async_nats::Client",
+            ContextFieldType::Queue(QueueType::Kafka) => "rdkafka::producer::FutureProducer",
+            // Rendered by the resource adapter
+            ContextFieldType::Custom => "()",
         }
     }
 
     fn build_impl(&self) -> Impl {
-        let has_async = self.fields.iter().any(|f| f.is_async);
-        let adapter = SqlxAdapter::new();
+        if self.lazy {
+            return self.build_lazy_impl();
+        }
+        let has_async = self.has_async();
         let renderer = RustRenderer::new();
 
-        let body = if self.fields.is_empty() {
+        let mut field_inits: Vec<String> = self
+            .fields
+            .iter()
+            .map(|f| {
+                let init_expr = self.generate_field_init(f, &renderer);
+                format!("{}: {},", f.name, init_expr)
+            })
+            .collect();
+        if self.globals {
+            field_inits.push("globals,".to_string());
+        }
+
+        let body = if field_inits.is_empty() {
             "Ok(Self {})".to_string()
         } else {
-            let field_inits = self
-                .fields
-                .iter()
-                .map(|f| {
-                    let init_expr = self.generate_field_init(f, &adapter, &renderer);
-                    format!("{}: {},", f.name, init_expr)
-                })
-                .collect::<Vec<_>>()
-                .join("\n    ");
-            format!("Ok(Self {{\n    {}\n}})", field_inits)
+            format!("Ok(Self {{\n    {}\n}})", field_inits.join("\n    "))
         };
 
-        let new_fn = Fn::new("new")
+        let mut new_fn = Fn::new("new");
+        if self.globals {
+            new_fn = new_fn.param(Param::new("globals", "GlobalArgs"));
+        }
+        let new_fn = new_fn
             .returns("eyre::Result<Self>")
             .body(body)
             .async_if(has_async);
@@ -75,13 +183,61 @@ impl ContextRs {
         Impl::new("Context").method(new_fn)
     }
 
+    /// `new` with empty cells, and a getter initializing each field on first use.
+    fn build_lazy_impl(&self) -> Impl {
+        let renderer = RustRenderer::new();
+        let mut field_inits: Vec<String> = self
+            .fields
+            .iter()
+            .map(|f| format!("{}: {}::new(),", f.name, self.cell_type()))
+            .collect();
+        if self.globals {
+            field_inits.push("globals,".to_string());
+        }
+
+        let mut new_fn = Fn::new("new");
+        if self.globals {
+            new_fn = new_fn.param(Param::new("globals", "GlobalArgs"));
+        }
+        let new_fn = new_fn.returns("Self").body(if field_inits.is_empty() {
+            "Self {}".to_string()
+        } else {
+            format!("Self {{\n    {}\n}}", field_inits.join("\n    "))
+        });
+
+        let has_async = self.has_async();
+        self.fields.iter().fold(
+            Impl::new("Context").method(new_fn),
+            |imp, field| {
+                // Initializers are indented for a field of `Ok(Self { .. })`
+                let init = self.generate_field_init(field, &renderer);
+                let body = if has_async {
+                    format!(
+                        "self.{name}\n    .get_or_try_init(|| async {{\n        Ok({init})\n    }})\n    .await",
+                        name = field.name,
+                        init = init.replace('\n', "\n    "),
+                    )
+                } else {
+                    format!(
+                        "if let Some({name}) = self.{name}.get() {{\n    return Ok({name});\n}}\nlet {name} = {init};\nOk(self.{name}.get_or_init(|| {name}))",
+                        name = field.name,
+                        init = init.replace("\n    ", "\n"),
+                    )
+                };
+                imp.method(
+                    Fn::new(&field.name)
+                        .doc(format!("The `{}` resource, set up on first use.", field.name))
+                        .param(Param::new("&self", ""))
+                        .returns(format!("eyre::Result<&{}>", self.field_type(field)))
+                        .body(body)
+                        .async_if(has_async),
+                )
+            },
+        )
+    }
+
     /// Generate initialization expression for a context field.
-    fn generate_field_init(
-        &self,
-        field: &ContextFieldInfo,
-        adapter: &SqlxAdapter,
-        renderer: &RustRenderer,
-    ) -> String {
+    fn generate_field_init(&self, field: &ContextFieldInfo, renderer: &RustRenderer) -> String {
         match field.field_type {
             ContextFieldType::Database(db_type) => {
                 let info = PoolInitInfo {
@@ -90,11 +246,95 @@ impl ContextRs {
                     env_var: field.env_var.clone(),
                     pool_config: field.pool.clone(),
                     sqlite_config: field.sqlite.clone(),
+                    duckdb_config: field.duckdb.clone(),
                 };
-                let value = adapter.pool_init(&info);
+                let value = database_adapter(db_type).pool_init(&info);
                 value.render_with(renderer, &RenderOptions::default().with_indent(2))
             }
-            ContextFieldType::Http => "reqwest::Client::new()".to_string(),
+            ContextFieldType::Http => {
+                ReqwestAdapter::new().client_init(&field.http.clone().unwrap_or_default())
+            }
+            ContextFieldType::Logger => {
+                "tracing::dispatcher::get_default(|dispatch| dispatch.clone())".to_string()
+            }
+            ContextFieldType::Storage => Self::storage_init(field.s3.clone().unwrap_or_default()),
+            ContextFieldType::Grpc => {
+                TonicAdapter::new().channel_init(&field.grpc.clone().unwrap_or_default())
+            }
+            ContextFieldType::Queue(queue_type) => Self::queue_init(queue_type, &field.env_var),
+            ContextFieldType::Email => Self::email_init(&field.email.clone().unwrap_or_default()),
+            ContextFieldType::Config => "Config::load()?".to_string(),
+            ContextFieldType::Secrets => "Secrets::load()?".to_string(),
+            ContextFieldType::Llm => "LlmClient::new()?".to_string(),
+            ContextFieldType::Cache => match &field.cache {
+                Some(cache) if cache.mode == CacheMode::Disk => "Cache::open()?".to_string(),
+                cache => CacheRs::builder(&cache.clone().unwrap_or_default(), 4),
+            },
+            ContextFieldType::Workdir => "Workdir::open()?".to_string(),
+            ContextFieldType::Custom => self
+                .resources
+                .get(&field.name)
+                .map(|code| code.init.clone())
+                .unwrap_or_else(|| "()".to_string()),
+        }
+    }
+
+    /// Build an S3 bucket client, taking credentials from the `AWS_*` variables.
+    fn storage_init(s3: S3Options) -> String {
+        let mut lines = vec![
+            "object_store::aws::AmazonS3Builder::from_env()".to_string(),
+            format!(".with_bucket_name({:?})", s3.bucket),
+        ];
+        if let Some(region) = &s3.region {
+            lines.push(format!(".with_region({:?})", region));
+        }
+        if let Some(endpoint) = &s3.endpoint {
+            lines.push(format!(".with_endpoint({:?})", endpoint));
+        }
+        if s3.allows_http() {
+            lines.push(".with_allow_http(true)".to_string());
+        }
+        lines.push(".build()?".to_string());
+        lines.join("\n        ")
+    }
+
+    /// Build an SMTP transport, reading credentials from their variables.
+    ///
+    /// The transport connects when the first message is sent.
+    fn email_init(email: &EmailOptions) -> String {
+        let builder = match email.tls {
+            SmtpTls::Starttls => "starttls_relay",
+            SmtpTls::Tls => "relay",
+            SmtpTls::None => "builder_dangerous",
+        };
+        let fallible = if email.tls == SmtpTls::None { "" } else { "?" };
+        let mut lines = vec![format!(
+            "lettre::AsyncSmtpTransport::<lettre::Tokio1Executor>::{}({:?}){}",
+            builder, email.host, fallible
+        )];
+        if email.port != email.tls.default_port() {
+            lines.push(format!(".port({})", email.port));
+        }
+        if let Some((username, password)) = email.credentials() {
+            lines.push(format!(
+                ".credentials((std::env::var({:?})?, std::env::var({:?})?).into())",
+                username, password
+            ));
+        }
+        lines.push(".build()".to_string());
+        lines.join("\n        ")
+    }
+
+    /// Connect to NATS, or build a Kafka producer, from the configured variable.
+    fn queue_init(queue_type: QueueType, env_var: &str) -> String {
+        match queue_type {
+            QueueType::Nats => format!("async_nats::connect(std::env::var({:?})?).await?", env_var),
+            QueueType::Kafka => [
+                "rdkafka::ClientConfig::new()".to_string(),
+                format!(".set(\"bootstrap.servers\", std::env::var({:?})?)", env_var),
+                ".create()?".to_string(),
+            ]
+            .join("\n        "),
         }
     }
 }
@@ -122,6 +362,54 @@ impl GeneratedFile for ContextRs {
         if needs_from_str {
             file = file.use_stmt(Use::new("std::str").symbol("FromStr"));
         }
+        if self.globals {
+            file = file.use_stmt(Use::new("crate::generated").symbol("GlobalArgs"));
+        }
+        if self
+            .fields
+            .iter()
+            .any(|f| self.resources.get(&f.name).is_none() && Self::has_base_url(f))
+        {
+            file = file.use_stmt(Use::new("crate::generated").symbol("HttpClient"));
+        }
+        if self
+            .fields
+            .iter()
+            .any(|f| f.field_type == ContextFieldType::Config)
+        {
+            file = file.use_stmt(Use::new("crate::generated").symbol("Config"));
+        }
+        if self
+            .fields
+            .iter()
+            .any(|f| f.field_type == ContextFieldType::Secrets)
+        {
+            file = file.use_stmt(Use::new("crate::generated").symbol("Secrets"));
+        }
+        if self
+            .fields
+            .iter()
+            .any(|f| f.field_type == ContextFieldType::Llm)
+        {
+            file = file.use_stmt(Use::new("crate::generated").symbol("LlmClient"));
+        }
+        if self.fields.iter().any(Self::is_disk_cache) {
+            file = file.use_stmt(Use::new("crate::generated").symbol("Cache"));
+        }
+        if self
+            .fields
+            .iter()
+            .any(|f| f.field_type == ContextFieldType::Workdir)
+        {
+            file = file.use_stmt(Use::new("crate::generated").symbol("Workdir"));
+        }
+        for import in self.resources.code.values().flat_map(|code| &code.imports) {
+            let use_stmt = import
+                .symbols
+                .iter()
+                .fold(Use::new(&import.module), |u, symbol| u.symbol(symbol));
+            file = file.use_stmt(use_stmt);
+        }
 
         file.add(RawCode::new(self.build_struct()))
             .add(self.build_impl())
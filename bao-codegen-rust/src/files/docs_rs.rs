@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile, Use};
+
+/// Name of the generated docs subcommand.
+pub const DOCS_COMMAND: &str = "docs";
+
+/// Dependency rendering man pages.
+pub const CLAP_MANGEN_DEPENDENCY: (&str, &str) = ("clap_mangen", "0.2");
+
+const DOCS: &str = r##"/// Write Markdown or man pages for every command
+#[derive(Args, Debug)]
+pub struct DocsArgs {
+    /// Format of the pages
+    #[arg(long, value_enum, default_value_t = DocsFormat::Markdown)]
+    pub format: DocsFormat,
+    /// Directory to write the pages to
+    #[arg(long, default_value = "docs")]
+    pub out: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum DocsFormat {
+    Markdown,
+    Man,
+}
+
+/// Write one page per visible command, read from the parser itself so the
+/// pages follow the commands as they are built.
+pub fn run(args: DocsArgs) -> eyre::Result<()> {
+    let mut command = Cli::command();
+    command.build();
+    fs::create_dir_all(&args.out)?;
+    match args.format {
+        DocsFormat::Markdown => write_markdown(&command, &args.out)?,
+        DocsFormat::Man => write_man(&command, &args.out)?,
+    }
+    println!("Wrote docs to {}", args.out.display());
+    Ok(())
+}
+
+fn write_markdown(command: &Command, out: &Path) -> io::Result<()> {
+    let name = command.get_bin_name().unwrap_or(command.get_name());
+    let mut page = format!("# {}\n\n", name);
+    if let Some(about) = command.get_long_about().or(command.get_about()) {
+        page.push_str(&format!("{}\n\n", about));
+    }
+    let help = command.clone().render_long_help().to_string();
+    page.push_str(&format!("```text\n{}\n```\n", help.trim_end()));
+
+    let children = children(command);
+    if !children.is_empty() {
+        page.push_str("\n## Commands\n\n");
+        for child in &children {
+            let about = child.get_about().map(|a| a.to_string()).unwrap_or_default();
+            page.push_str(&format!(
+                "- [{}]({}): {}\n",
+                child.get_name(),
+                file_name(child, "md"),
+                about
+            ));
+        }
+    }
+    fs::write(out.join(file_name(command, "md")), page)?;
+
+    for child in children {
+        write_markdown(child, out)?;
+    }
+    Ok(())
+}
+
+fn write_man(command: &Command, out: &Path) -> io::Result<()> {
+    let mut page = Vec::new();
+    clap_mangen::Man::new(command.clone()).render(&mut page)?;
+    fs::write(out.join(file_name(command, "1")), page)?;
+
+    for child in children(command) {
+        write_man(child, out)?;
+    }
+    Ok(())
+}
+
+/// Subcommands with a page: neither hidden nor the built-in `help`.
+fn children(command: &Command) -> Vec<&Command> {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != "help")
+        .collect()
+}
+
+/// Page file name: the command path joined by dashes.
+fn file_name(command: &Command, extension: &str) -> String {
+    let name = command.get_bin_name().unwrap_or(command.get_name());
+    format!("{}.{}", name.replace(' ', "-"), extension)
+}"##;
+
+/// The generated/docs.rs file implementing the hidden `docs` subcommand.
+///
+/// Pages are rendered at runtime from the clap parser, so they stay current
+/// for whoever builds the CLI.
+pub struct DocsRs;
+
+impl GeneratedFile for DocsRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("docs.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .use_stmt(Use::new("std").symbols(["fs", "io", "path::{Path, PathBuf}"]))
+            .use_stmt(Use::new("clap").symbols(["Args", "Command", "CommandFactory", "ValueEnum"]))
+            .use_stmt(Use::new("super::cli").symbol("Cli"))
+            .add(RawCode::new(DOCS))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
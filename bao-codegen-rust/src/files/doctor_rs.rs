@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::{adapters::PoolInitInfo, builder::RenderOptions, schema::ContextFieldInfo};
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{ContextFieldType, DatabaseType, SecretsBackend};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile, RustRenderer, Use, adapters::database_adapter};
+
+/// Name of the generated doctor subcommand.
+pub const DOCTOR_COMMAND: &str = "doctor";
+
+const ARGS: &str = r#"/// Check that the environment and resources the CLI needs are in place
+#[derive(Args, Debug)]
+pub struct DoctorArgs {}"#;
+
+const REPORT: &str = r#"/// Pass/fail lines printed as checks run.
+#[derive(Default)]
+struct Report {
+    passed: usize,
+    failed: usize,
+}
+
+impl Report {
+    fn check(&mut self, name: &str, result: eyre::Result<()>) {
+        match result {
+            Ok(()) => {
+                self.passed += 1;
+                println!("✓ {}", name);
+            }
+            Err(e) => {
+                self.failed += 1;
+                println!("✗ {}: {}", name, e);
+            }
+        }
+    }
+
+    fn finish(self) -> eyre::Result<()> {
+        if self.failed > 0 {
+            eyre::bail!("{} of {} checks failed", self.failed, self.passed + self.failed);
+        }
+        println!("All {} checks passed", self.passed);
+        Ok(())
+    }
+}"#;
+
+const ENV_VAR: &str = r#"fn env_var(name: &str) -> eyre::Result<()> {
+    match std::env::var_os(name) {
+        Some(value) if !value.is_empty() => Ok(()),
+        _ => eyre::bail!("not set"),
+    }
+}"#;
+
+const CONNECT_TIMEOUT: &str = r#"/// How long a database or HTTP endpoint gets to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);"#;
+
+const CONNECT: &str = r#"async fn connect(check: impl Future<Output = eyre::Result<()>>) -> eyre::Result<()> {
+    tokio::time::timeout(CONNECT_TIMEOUT, check)
+        .await
+        .map_err(|_| eyre::eyre!("timed out after {}s", CONNECT_TIMEOUT.as_secs()))?
+}"#;
+
+const REACHABLE: &str = r#"fn reachable(addr: &str) -> eyre::Result<()> {
+    let resolved = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| eyre::eyre!("{} has no address", addr))?;
+    TcpStream::connect_timeout(&resolved, CONNECT_TIMEOUT)?;
+    Ok(())
+}"#;
+
+/// The generated/doctor.rs file implementing the `doctor` subcommand.
+///
+/// Every environment variable a resource needs gets a check that it is set.
+/// Each database then gets one that it accepts a connection, opened the same
+/// way `Context::new` does, each HTTP client with a base URL one that its
+/// host accepts a TCP connection, and the config and secrets files one that
+/// they load. The command runs before the context is built, so it can report
+/// a resource that would make every other command fail.
+pub struct DoctorRs {
+    pub fields: Vec<ContextFieldInfo>,
+    pub is_async: bool,
+}
+
+impl DoctorRs {
+    pub fn new(fields: Vec<ContextFieldInfo>, is_async: bool) -> Self {
+        Self { fields, is_async }
+    }
+
+    fn databases(&self) -> impl Iterator<Item = &ContextFieldInfo> {
+        self.fields
+            .iter()
+            .filter(|f| matches!(f.field_type, ContextFieldType::Database(_)))
+    }
+
+    /// Environment variables the resources need, in order, once each.
+    fn env_vars(&self) -> Vec<&str> {
+        let mut vars: Vec<&str> = Vec::new();
+        for var in self.fields.iter().flat_map(|f| f.required_env_vars()) {
+            if !vars.contains(&var) {
+                vars.push(var);
+            }
+        }
+        vars
+    }
+
+    /// The check of a resource beyond its environment variables, if it has one.
+    fn field_check(field: &ContextFieldInfo) -> Option<String> {
+        match field.field_type {
+            ContextFieldType::Database(db_type) => Some(Self::connect_check(field, db_type)),
+            ContextFieldType::Http => {
+                let base_url = field.http.as_ref()?.base_url.as_deref()?;
+                Some(format!(
+                    "report.check(\"http `{}` is reachable\", reachable({:?}));",
+                    field.name,
+                    http_addr(base_url)
+                ))
+            }
+            ContextFieldType::Config => Some(format!(
+                "report.check(\"config file `{}` parses\", Config::load().map(|_| ()));",
+                field.config.as_ref()?.path
+            )),
+            ContextFieldType::Secrets => {
+                let secrets = field.secrets.as_ref()?;
+                (secrets.backend == SecretsBackend::File).then(|| {
+                    format!(
+                        "report.check(\"secrets file `{}` is readable\", Secrets::load().map(|_| ()));",
+                        secrets.path
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn checks(&self) -> Vec<String> {
+        let env_vars = self
+            .env_vars()
+            .into_iter()
+            .map(|var| format!("report.check(\"{var} is set\", env_var(\"{var}\"));"));
+        env_vars
+            .chain(self.fields.iter().filter_map(Self::field_check))
+            .collect()
+    }
+
+    fn has_http_check(&self) -> bool {
+        self.fields.iter().any(|f| {
+            f.field_type == ContextFieldType::Http
+                && f.http.as_ref().is_some_and(|h| h.base_url.is_some())
+        })
+    }
+
+    fn has_field_type(&self, field_type: ContextFieldType) -> bool {
+        self.fields.iter().any(|f| f.field_type == field_type)
+    }
+
+    fn has_secrets_file(&self) -> bool {
+        self.fields.iter().any(|f| {
+            f.secrets
+                .as_ref()
+                .is_some_and(|s| s.backend == SecretsBackend::File)
+        })
+    }
+
+    /// Whether SQLite options are parsed from the environment variable.
+    fn needs_from_str(&self) -> bool {
+        self.databases().any(|f| {
+            f.field_type == ContextFieldType::Database(DatabaseType::Sqlite)
+                && f.sqlite.as_ref().is_none_or(|s| s.path.is_none())
+                && (f.sqlite.as_ref().is_some_and(|s| s.has_config()) || f.pool.has_config())
+        })
+    }
+
+    fn connect_check(field: &ContextFieldInfo, db_type: DatabaseType) -> String {
+        let info = PoolInitInfo {
+            field_name: field.name.clone(),
+            db_type,
+            env_var: field.env_var.clone(),
+            pool_config: field.pool.clone(),
+            sqlite_config: field.sqlite.clone(),
+            duckdb_config: field.duckdb.clone(),
+        };
+        let adapter = database_adapter(db_type);
+        let pool = adapter.pool_init(&info).render_with(
+            &RustRenderer::new(),
+            &RenderOptions::default().with_indent(3),
+        );
+        // r2d2 pools have no `close`; checking a connection out proves it opens
+        let release = if db_type == DatabaseType::Duckdb {
+            "pool.get()?;"
+        } else {
+            "pool.close().await;"
+        };
+        format!(
+            "report.check(\n        \"database `{}` is reachable\",\n        connect(async {{\n            let pool: {} = {};\n            {}\n            Ok(())\n        }})\n        .await,\n    );",
+            field.name,
+            adapter.pool_type(db_type),
+            pool,
+            release
+        )
+    }
+
+    fn build_run(&self) -> String {
+        let async_kw = if self.is_async { "async " } else { "" };
+        let checks = self.checks();
+        let mut lines = Vec::new();
+        if checks.is_empty() {
+            lines.push("println!(\"Nothing to check\");".to_string());
+            lines.push("Ok(())".to_string());
+        } else {
+            lines.push("let mut report = Report::default();".to_string());
+            lines.extend(checks);
+            lines.push("report.finish()".to_string());
+        }
+        format!(
+            "/// Run every check, printing a line for each, and fail if any check failed.\npub {}fn run(_args: &DoctorArgs) -> eyre::Result<()> {{\n    {}\n}}",
+            async_kw,
+            lines.join("\n    ")
+        )
+    }
+}
+
+impl GeneratedFile for DoctorRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("doctor.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let has_databases = self.databases().next().is_some();
+        let has_http = self.has_http_check();
+        let has_config = self.has_field_type(ContextFieldType::Config);
+        let has_secrets = self.has_secrets_file();
+
+        let mut std_symbols = Vec::new();
+        if has_databases {
+            std_symbols.push("future::Future");
+        }
+        if has_http {
+            std_symbols.push("net::{TcpStream, ToSocketAddrs}");
+        }
+        if has_databases || has_http {
+            std_symbols.push("time::Duration");
+        }
+        let mut file = RustFile::new();
+        if !std_symbols.is_empty() {
+            file = file.use_stmt(Use::new("std").symbols(std_symbols));
+        }
+        if self.needs_from_str() {
+            file = file.use_stmt(Use::new("std::str").symbol("FromStr"));
+        }
+        file = file.use_stmt(Use::new("clap").symbol("Args"));
+        if has_config {
+            file = file.use_stmt(Use::new("super::config").symbol("Config"));
+        }
+        if has_secrets {
+            file = file.use_stmt(Use::new("super::secrets").symbol("Secrets"));
+        }
+        file = file
+            .add(RawCode::new(ARGS))
+            .add(RawCode::new(self.build_run()));
+        if self.checks().is_empty() {
+            return file.render_with_header(GENERATED_HEADER);
+        }
+        file = file.add(RawCode::new(REPORT));
+        if !self.env_vars().is_empty() {
+            file = file.add(RawCode::new(ENV_VAR));
+        }
+        if has_databases || has_http {
+            file = file.add(RawCode::new(CONNECT_TIMEOUT));
+        }
+        if has_databases {
+            file = file.add(RawCode::new(CONNECT));
+        }
+        if has_http {
+            file = file.add(RawCode::new(REACHABLE));
+        }
+        file.render_with_header(GENERATED_HEADER)
+    }
+}
+
+/// The `host:port` an HTTP base URL connects to, with the scheme's default
+/// port when it names none.
+fn http_addr(base_url: &str) -> String {
+    let (scheme, rest) = base_url.split_once("://").unwrap_or(("https", base_url));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    // An IPv6 address has colons of its own, inside its brackets
+    if host
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.contains(']'))
+    {
+        return host.to_string();
+    }
+    let port = if scheme == "http" { 80 } else { 443 };
+    format!("{}:{}", host, port)
+}
@@ -6,7 +6,108 @@ use super::GENERATED_HEADER;
 use crate::{RawCode, RustFile};
 
 /// The generated/mod.rs file that exports the CLI and commands
-pub struct GeneratedMod;
+pub struct GeneratedMod {
+    /// Whether a `globals` module with `GlobalArgs` is generated.
+    pub globals: bool,
+    /// Whether a `locale` module with translated help text is generated.
+    pub locales: bool,
+    /// Whether a `docs` module with the hidden docs subcommand is generated.
+    pub docs: bool,
+    /// Whether a `doctor` module with the doctor subcommand is generated.
+    pub doctor: bool,
+    /// Whether an `http` module with the HTTP client helpers is generated.
+    pub http: bool,
+    /// Whether a `grpc` module with the compiled gRPC clients is generated.
+    pub grpc: bool,
+    /// Whether a `config` module with the typed `Config` is generated.
+    pub config: bool,
+    /// Whether a `secrets` module with the `Secrets` accessor is generated.
+    pub secrets: bool,
+    /// Whether an `llm` module with the `LlmClient` is generated.
+    pub llm: bool,
+    /// Whether a `cache` module with the disk-backed `Cache` is generated.
+    pub cache: bool,
+    /// Whether a `workdir` module with the `Workdir` directories is generated.
+    pub workdir: bool,
+}
+
+impl GeneratedMod {
+    pub fn new(globals: bool) -> Self {
+        Self {
+            globals,
+            locales: false,
+            docs: false,
+            doctor: false,
+            http: false,
+            grpc: false,
+            config: false,
+            secrets: false,
+            llm: false,
+            cache: false,
+            workdir: false,
+        }
+    }
+
+    /// Export `localize` from the generated `locale` module.
+    pub fn with_locales(mut self, locales: bool) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Declare the generated `docs` module.
+    pub fn with_docs(mut self, docs: bool) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Declare the generated `doctor` module.
+    pub fn with_doctor(mut self, doctor: bool) -> Self {
+        self.doctor = doctor;
+        self
+    }
+
+    /// Export the HTTP client helpers from the generated `http` module.
+    pub fn with_http(mut self, http: bool) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Declare the generated `grpc` module.
+    pub fn with_grpc(mut self, grpc: bool) -> Self {
+        self.grpc = grpc;
+        self
+    }
+
+    /// Export `Config` from the generated `config` module.
+    pub fn with_config(mut self, config: bool) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Export `Secrets` from the generated `secrets` module.
+    pub fn with_secrets(mut self, secrets: bool) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Export `Cache` from the generated `cache` module.
+    pub fn with_cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Export `Workdir` from the generated `workdir` module.
+    pub fn with_workdir(mut self, workdir: bool) -> Self {
+        self.workdir = workdir;
+        self
+    }
+
+    /// Export `LlmClient` and its message types from the generated `llm` module.
+    pub fn with_llm(mut self, llm: bool) -> Self {
+        self.llm = llm;
+        self
+    }
+}
 
 impl GeneratedFile for GeneratedMod {
     fn path(&self, base: &Path) -> PathBuf {
@@ -18,9 +119,52 @@ impl GeneratedFile for GeneratedMod {
     }
 
     fn render(&self) -> String {
+        let mut mods = vec!["pub mod cli;", "pub mod commands;"];
+        let mut uses = vec!["pub use cli::*;"];
+        if self.globals {
+            mods.push("pub mod globals;");
+            uses.push("pub use globals::GlobalArgs;");
+        }
+        if self.config {
+            mods.push("pub mod config;");
+            uses.push("pub use config::Config;");
+        }
+        if self.secrets {
+            mods.push("pub mod secrets;");
+            uses.push("pub use secrets::Secrets;");
+        }
+        if self.cache {
+            mods.push("pub mod cache;");
+            uses.push("pub use cache::Cache;");
+        }
+        if self.workdir {
+            mods.push("pub mod workdir;");
+            uses.push("pub use workdir::Workdir;");
+        }
+        if self.llm {
+            mods.push("pub mod llm;");
+            uses.push("pub use llm::{LlmClient, Message, Role};");
+        }
+        if self.docs {
+            mods.push("pub mod docs;");
+        }
+        if self.doctor {
+            mods.push("pub mod doctor;");
+        }
+        if self.grpc {
+            mods.push("pub mod grpc;");
+        }
+        if self.http {
+            mods.push("pub mod http;");
+            uses.push("pub use http::*;");
+        }
+        if self.locales {
+            mods.push("pub mod locale;");
+            uses.push("pub use locale::localize;");
+        }
         RustFile::new()
-            .add(RawCode::lines(["pub mod cli;", "pub mod commands;"]))
-            .add(RawCode::new("pub use cli::*;"))
+            .add(RawCode::lines(mods))
+            .add(RawCode::lines(uses))
             .render_with_header(GENERATED_HEADER)
     }
 }
@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::{GENERATED_HEADER, build_rs::GRPC_INCLUDE_FILE};
+use crate::{RawCode, RustFile};
+
+/// The generated/grpc.rs file exposing the clients that build.rs compiles
+/// from the `.proto` files, one module per package.
+///
+/// Clients are created from the context channel, e.g.
+/// `GreeterClient::new(ctx.grpc.clone())`.
+pub struct GrpcRs;
+
+impl GeneratedFile for GrpcRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("grpc.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(format!(
+                r#"include!(concat!(env!("OUT_DIR"), "/{}"));"#,
+                GRPC_INCLUDE_FILE
+            )))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use baobao_core::{FileRules, GeneratedFile, to_pascal_case, to_snake_case};
+use baobao_core::{FileRules, GeneratedFile, to_snake_case};
 
 use super::uses;
 use crate::{Fn, Param, RustFile, Use};
@@ -16,6 +16,8 @@ pub struct HandlerStub {
     pub command: String,
     pub args_import: String,
     pub is_async: bool,
+    /// Example command lines, listed in the handler's doc comment
+    pub examples: Vec<String>,
 }
 
 impl HandlerStub {
@@ -24,15 +26,33 @@ impl HandlerStub {
             command: command.into(),
             args_import: args_import.into(),
             is_async,
+            examples: Vec::new(),
         }
     }
 
+    /// List the command's examples in the handler's doc comment.
+    pub fn with_examples(mut self, examples: Vec<String>) -> Self {
+        self.examples = examples;
+        self
+    }
+
     fn build_run_fn(&self) -> Fn {
-        let pascal = to_pascal_case(&self.command);
+        // The args type is the last segment of its import path
+        let args_type = self
+            .args_import
+            .rsplit("::")
+            .next()
+            .unwrap_or(&self.args_import);
 
-        Fn::new("run")
-            .param(Param::new("_ctx", "&Context"))
-            .param(Param::new("args", format!("{}Args", pascal)))
+        let mut run = Fn::new("run");
+        if !self.examples.is_empty() {
+            run = run.doc(format!(
+                "Examples:\n\n```text\n{}\n```",
+                self.examples.join("\n")
+            ));
+        }
+        run.param(Param::new("_ctx", "&Context"))
+            .param(Param::new("args", args_type))
             .returns("eyre::Result<()>")
             .body_line(format!("todo!(\"implement {} command\")", self.command))
             .async_if(self.is_async)
@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{HttpAuth, HttpOptions};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+const RETRY_ON: &str = r##"/// Retries responses with one of the listed statuses, and requests that
+/// failed on a transient error.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryOn(pub &'static [u16]);
+
+impl reqwest_retry::RetryableStrategy for RetryOn {
+    fn handle(
+        &self,
+        res: &Result<reqwest::Response, reqwest_middleware::Error>,
+    ) -> Option<reqwest_retry::Retryable> {
+        match res {
+            Ok(response) if self.0.contains(&response.status().as_u16()) => {
+                Some(reqwest_retry::Retryable::Transient)
+            }
+            Ok(_) => None,
+            Err(error) => reqwest_retry::default_on_request_failure(error),
+        }
+    }
+}"##;
+
+const AUTH: &str = r##"/// The value of the environment variable `env`, sent in an auth header.
+fn auth_env(env: &str) -> eyre::Result<String> {
+    std::env::var(env).map_err(|_| eyre::eyre!("{} is not set", env))
+}
+
+/// A header value that is left out of debug output.
+fn sensitive(value: String) -> eyre::Result<reqwest::header::HeaderValue> {
+    let mut value = reqwest::header::HeaderValue::try_from(value)?;
+    value.set_sensitive(true);
+    Ok(value)
+}"##;
+
+const BEARER_AUTH: &str = r##"/// `Bearer <token>`, with the token read from `env`.
+pub fn bearer_auth(env: &str) -> eyre::Result<reqwest::header::HeaderValue> {
+    sensitive(format!("Bearer {}", auth_env(env)?))
+}"##;
+
+const BASIC_AUTH: &str = r##"/// `Basic <credentials>`, with the password read from `env`.
+pub fn basic_auth(username: &str, env: &str) -> eyre::Result<reqwest::header::HeaderValue> {
+    let credentials = format!("{}:{}", username, auth_env(env)?);
+    sensitive(format!(
+        "Basic {}",
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, credentials)
+    ))
+}"##;
+
+const HEADER_AUTH: &str = r##"/// The secret read from `env`, as a header value.
+pub fn header_auth(env: &str) -> eyre::Result<reqwest::header::HeaderValue> {
+    sensitive(auth_env(env)?)
+}"##;
+
+/// The generated/http.rs file with the helpers used by HTTP context fields.
+#[derive(Debug, Default)]
+pub struct HttpRs {
+    /// Whether a client sets a `base_url`, generating `HttpClient`.
+    pub client: bool,
+    /// Whether a client retries, so `HttpClient` wraps reqwest-middleware.
+    pub middleware: bool,
+    /// Whether a client sets `retry_on`, generating `RetryOn`.
+    pub retry_on: bool,
+    /// Whether a client uses bearer auth, generating `bearer_auth`.
+    pub bearer_auth: bool,
+    /// Whether a client uses basic auth, generating `basic_auth`.
+    pub basic_auth: bool,
+    /// Whether a client uses header auth, generating `header_auth`.
+    pub header_auth: bool,
+}
+
+impl HttpRs {
+    pub fn new<'a>(clients: impl IntoIterator<Item = &'a HttpOptions>) -> Self {
+        clients
+            .into_iter()
+            .fold(Self::default(), |file, options| Self {
+                client: file.client || options.base_url.is_some(),
+                middleware: file.middleware || options.has_retries(),
+                retry_on: file.retry_on || !options.retry_on.is_empty(),
+                bearer_auth: file.bearer_auth
+                    || matches!(options.auth, Some(HttpAuth::Bearer { .. })),
+                basic_auth: file.basic_auth || matches!(options.auth, Some(HttpAuth::Basic { .. })),
+                header_auth: file.header_auth
+                    || matches!(options.auth, Some(HttpAuth::Header { .. })),
+            })
+    }
+
+    /// Whether any helper is generated.
+    pub fn is_needed(&self) -> bool {
+        self.client || self.retry_on || self.has_auth()
+    }
+
+    fn has_auth(&self) -> bool {
+        self.bearer_auth || self.basic_auth || self.header_auth
+    }
+
+    /// `HttpClient`, wrapping reqwest-middleware when a client retries.
+    fn client(&self) -> String {
+        let (client, builder, param, init) = if self.middleware {
+            (
+                "reqwest_middleware::ClientWithMiddleware",
+                "reqwest_middleware::RequestBuilder",
+                "impl Into<reqwest_middleware::ClientWithMiddleware>",
+                "client: client.into()",
+            )
+        } else {
+            (
+                "reqwest::Client",
+                "reqwest::RequestBuilder",
+                "reqwest::Client",
+                "client",
+            )
+        };
+        format!(
+            r#"/// A reqwest client that resolves request paths against a base URL.
+#[derive(Debug, Clone)]
+pub struct HttpClient {{
+    client: {client},
+    base_url: String,
+}}
+
+impl HttpClient {{
+    pub fn new(client: {param}, base_url: impl Into<String>) -> Self {{
+        Self {{
+            {init},
+            base_url: base_url.into(),
+        }}
+    }}
+
+    /// The underlying client, for requests to absolute URLs.
+    pub fn client(&self) -> &{client} {{
+        &self.client
+    }}
+
+    /// The full URL of `path`.
+    pub fn url(&self, path: &str) -> String {{
+        format!(
+            "{{}}/{{}}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }}
+
+    pub fn request(&self, method: reqwest::Method, path: &str) -> {builder} {{
+        self.client.request(method, self.url(path))
+    }}
+
+    pub fn get(&self, path: &str) -> {builder} {{
+        self.request(reqwest::Method::GET, path)
+    }}
+
+    pub fn post(&self, path: &str) -> {builder} {{
+        self.request(reqwest::Method::POST, path)
+    }}
+
+    pub fn put(&self, path: &str) -> {builder} {{
+        self.request(reqwest::Method::PUT, path)
+    }}
+
+    pub fn patch(&self, path: &str) -> {builder} {{
+        self.request(reqwest::Method::PATCH, path)
+    }}
+
+    pub fn delete(&self, path: &str) -> {builder} {{
+        self.request(reqwest::Method::DELETE, path)
+    }}
+}}"#
+        )
+    }
+}
+
+impl GeneratedFile for HttpRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("http.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = RustFile::new();
+        if self.client {
+            file = file.add(RawCode::new(self.client()));
+        }
+        if self.retry_on {
+            file = file.add(RawCode::new(RETRY_ON));
+        }
+        if self.has_auth() {
+            file = file.add(RawCode::new(AUTH));
+        }
+        for (used, helper) in [
+            (self.bearer_auth, BEARER_AUTH),
+            (self.basic_auth, BASIC_AUTH),
+            (self.header_auth, HEADER_AUTH),
+        ] {
+            if used {
+                file = file.add(RawCode::new(helper));
+            }
+        }
+        file.render_with_header(GENERATED_HEADER)
+    }
+}
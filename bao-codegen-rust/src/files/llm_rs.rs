@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{LlmOptions, LlmProvider};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+const MESSAGE: &str = r#"/// Who a message is from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+}
+
+impl Role {
+    fn as_str(self) -> &'static str {
+        match self {
+            Role::System => "system",
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        }
+    }
+}
+
+/// A message of a conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    /// Instructions for the model.
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::System,
+            content: content.into(),
+        }
+    }
+
+    /// A prompt from the user.
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    /// An earlier reply of the model.
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
+}"#;
+
+const ANTHROPIC_CHAT: &str = r#"    /// Reply to a conversation. System messages are sent as the system prompt.
+    pub async fn chat(&self, messages: &[Message]) -> eyre::Result<String> {
+        let system = messages
+            .iter()
+            .filter(|m| m.role == Role::System)
+            .map(|m| m.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let turns: Vec<_> = messages
+            .iter()
+            .filter(|m| m.role != Role::System)
+            .map(|m| serde_json::json!({ "role": m.role.as_str(), "content": m.content }))
+            .collect();
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "max_tokens": MAX_TOKENS,
+            "messages": turns,
+        });
+        if !system.is_empty() {
+            body["system"] = system.into();
+        }
+        let response = self
+            .http
+            .post(format!("{}/v1/messages", BASE_URL))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await?;
+        let reply = Self::read(response).await?;
+        reply["content"]
+            .as_array()
+            .map(|blocks| blocks.iter().filter_map(|b| b["text"].as_str()).collect())
+            .ok_or_else(|| eyre::eyre!("unexpected LLM response: {}", reply))
+    }"#;
+
+const READ: &str = r#"    /// Parse the response body, failing with the API's message on error statuses.
+    async fn read(response: reqwest::Response) -> eyre::Result<serde_json::Value> {
+        let status = response.status();
+        let text = response.text().await?;
+        let body: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|_| eyre::eyre!("LLM request failed ({}): {}", status, text))?;
+        if !status.is_success() {
+            let message = body["error"]["message"].as_str().unwrap_or(&text);
+            return Err(eyre::eyre!("LLM request failed ({}): {}", status, message));
+        }
+        Ok(body)
+    }"#;
+
+/// The generated/llm.rs file with the `LlmClient` of the `[context.llm]`
+/// provider.
+///
+/// Requests are built with serde_json rather than provider SDKs, so the
+/// client only needs reqwest.
+pub struct LlmRs {
+    pub options: LlmOptions,
+}
+
+impl LlmRs {
+    pub fn new(options: LlmOptions) -> Self {
+        Self { options }
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        match self.options.provider {
+            LlmProvider::Anthropic => Some(
+                self.options
+                    .max_tokens
+                    .unwrap_or(LlmOptions::DEFAULT_MAX_TOKENS),
+            ),
+            LlmProvider::Openai => self.options.max_tokens,
+        }
+    }
+
+    fn build_consts(&self) -> String {
+        let mut consts = vec![
+            format!(
+                "/// Base URL of the API.\nconst BASE_URL: &str = {:?};",
+                self.options.base_url
+            ),
+            format!(
+                "/// Environment variable holding the API key.\nconst API_KEY_ENV: &str = {:?};",
+                self.options.api_key_env
+            ),
+            format!(
+                "/// Model requests are sent to unless overridden.\nconst MODEL: &str = {:?};",
+                self.options.model
+            ),
+        ];
+        if let Some(max_tokens) = self.max_tokens() {
+            consts.push(format!(
+                "/// Maximum number of tokens in a reply.\nconst MAX_TOKENS: u32 = {};",
+                max_tokens
+            ));
+        }
+        consts.join("\n\n")
+    }
+
+    fn build_struct(&self) -> String {
+        let api = match self.options.provider {
+            LlmProvider::Openai => "OpenAI Chat Completions API",
+            LlmProvider::Anthropic => "Anthropic Messages API",
+        };
+        format!(
+            r#"/// Client for the {api}.
+#[derive(Clone)]
+pub struct LlmClient {{
+    http: reqwest::Client,
+    api_key: String,
+    model: String,
+}}
+
+// Keeps the API key out of logs and panics
+impl std::fmt::Debug for LlmClient {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        f.debug_struct("LlmClient")
+            .field("model", &self.model)
+            .finish_non_exhaustive()
+    }}
+}}"#
+        )
+    }
+
+    fn build_chat(&self) -> String {
+        match self.options.provider {
+            LlmProvider::Anthropic => ANTHROPIC_CHAT.to_string(),
+            LlmProvider::Openai => {
+                let max_tokens = if self.max_tokens().is_some() {
+                    "\n        body[\"max_completion_tokens\"] = MAX_TOKENS.into();"
+                } else {
+                    ""
+                };
+                format!(
+                    r#"    /// Reply to a conversation.
+    pub async fn chat(&self, messages: &[Message]) -> eyre::Result<String> {{
+        let turns: Vec<_> = messages
+            .iter()
+            .map(|m| serde_json::json!({{ "role": m.role.as_str(), "content": m.content }}))
+            .collect();
+        let {mutability}body = serde_json::json!({{ "model": self.model, "messages": turns }});{max_tokens}
+        let response = self
+            .http
+            .post(format!("{{}}/chat/completions", BASE_URL))
+            .bearer_auth(&self.api_key)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await?;
+        let reply = Self::read(response).await?;
+        reply["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| eyre::eyre!("unexpected LLM response: {{}}", reply))
+    }}"#,
+                    mutability = if max_tokens.is_empty() { "" } else { "mut " },
+                )
+            }
+        }
+    }
+
+    fn build_impl(&self) -> String {
+        format!(
+            r#"impl LlmClient {{
+    /// Read the API key from `API_KEY_ENV`.
+    pub fn new() -> eyre::Result<Self> {{
+        let api_key = std::env::var(API_KEY_ENV)
+            .map_err(|_| eyre::eyre!("{{}} is not set", API_KEY_ENV))?;
+        Ok(Self {{
+            http: reqwest::Client::new(),
+            api_key,
+            model: MODEL.to_string(),
+        }})
+    }}
+
+    /// The model requests are sent to.
+    pub fn model(&self) -> &str {{
+        &self.model
+    }}
+
+    /// Send requests to `model` instead.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {{
+        self.model = model.into();
+        self
+    }}
+
+    /// Reply to a single prompt.
+    pub async fn complete(&self, prompt: &str) -> eyre::Result<String> {{
+        self.chat(&[Message::user(prompt)]).await
+    }}
+
+{chat}
+
+{read}
+}}"#,
+            chat = self.build_chat(),
+            read = READ,
+        )
+    }
+}
+
+impl GeneratedFile for LlmRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("llm.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(self.build_consts()))
+            .add(RawCode::new(MESSAGE))
+            .add(RawCode::new(self.build_struct()))
+            .add(RawCode::new(self.build_impl()))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
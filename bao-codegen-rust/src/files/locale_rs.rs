@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, to_kebab_case, to_snake_case};
+use baobao_ir::Locale;
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile, Use};
+
+/// Selects the bundle for the user's locale and applies it to the parser.
+const LOCALIZE: &str = r#"/// Apply the translations for the user's locale to the CLI's help text.
+///
+/// Text without a translation keeps the manifest's default locale.
+pub fn localize(command: Command) -> Command {
+    match messages() {
+        Some(messages) => translate(command, messages, String::new()),
+        None => command,
+    }
+}
+
+/// Messages for the locale in `LC_ALL`, `LC_MESSAGES` or `LANG`, matching
+/// the full locale (`pt-BR`) before its language (`pt`).
+fn messages() -> Option<Messages> {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))?;
+    let locale = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    let language = locale.split('-').next().unwrap_or_default();
+    [locale.as_str(), language].into_iter().find_map(|name| {
+        LOCALES
+            .iter()
+            .find(|(locale, _)| locale.replace('_', "-").eq_ignore_ascii_case(name))
+            .map(|(_, messages)| *messages)
+    })
+}
+
+fn translate(mut command: Command, messages: Messages, path: String) -> Command {
+    let lookup = |key: &str| {
+        messages
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, text)| *text)
+    };
+    if let Some(about) = lookup(&path) {
+        command = command.about(about);
+    }
+
+    let args: Vec<String> = command
+        .get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in args {
+        if let Some(help) = lookup(&format!("{}:{}", path, id)) {
+            command = command.mut_arg(id, |arg| arg.help(help));
+        }
+    }
+
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        let child = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{} {}", path, name)
+        };
+        command = command.mut_subcommand(name, |sub| translate(sub, messages, child));
+    }
+    command
+}"#;
+
+/// The generated/locale.rs file holding translated help text.
+///
+/// Messages are keyed by the clap names of the command path (`users create`,
+/// empty for the root command), followed by `:` and the argument id for
+/// arguments.
+pub struct LocaleRs {
+    /// Messages per locale, keyed as above.
+    pub locales: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl LocaleRs {
+    pub fn new(locales: &[Locale]) -> Self {
+        let locales = locales
+            .iter()
+            .map(|locale| {
+                let messages = locale
+                    .messages
+                    .iter()
+                    .map(|message| {
+                        let path: Vec<_> =
+                            message.command.iter().map(|c| to_kebab_case(c)).collect();
+                        let mut key = path.join(" ");
+                        if let Some(input) = &message.input {
+                            key = format!("{}:{}", key, to_snake_case(input));
+                        }
+                        (key, message.text.clone())
+                    })
+                    .collect();
+                (locale.name.clone(), messages)
+            })
+            .collect();
+        Self { locales }
+    }
+
+    fn build_locales(&self) -> String {
+        let mut lines = vec![
+            "type Messages = &'static [(&'static str, &'static str)];".to_string(),
+            String::new(),
+            "/// Help text per locale, keyed by command path and argument id.".to_string(),
+            "const LOCALES: &[(&str, Messages)] = &[".to_string(),
+        ];
+        for (name, messages) in &self.locales {
+            lines.push("    (".to_string());
+            lines.push(format!("        {:?},", name));
+            lines.push("        &[".to_string());
+            for (key, text) in messages {
+                lines.push(format!("            ({:?}, {:?}),", key, text));
+            }
+            lines.push("        ],".to_string());
+            lines.push("    ),".to_string());
+        }
+        lines.push("];".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GeneratedFile for LocaleRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("locale.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .use_stmt(Use::new("clap").symbol("Command"))
+            .add(RawCode::new(self.build_locales()))
+            .add(RawCode::new(LOCALIZE))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -24,26 +24,49 @@ pub mod uses {
 }
 
 mod app_rs;
+mod build_rs;
+mod cache_rs;
 mod cargo_toml;
 mod cli_rs;
 mod command_rs;
 mod commands_mod;
+mod config_rs;
 mod context_rs;
+mod docs_rs;
+mod doctor_rs;
 mod generated_mod;
 mod gitignore;
+mod grpc_rs;
 mod handler_stub;
 mod handlers_mod;
+mod http_rs;
+mod llm_rs;
+mod locale_rs;
 mod main_rs;
+mod secrets_rs;
+mod workdir_rs;
 
 pub use app_rs::AppRs;
 pub use baobao_codegen::generation::BaoToml;
+pub use build_rs::{BuildRs, VERGEN_DEPENDENCY, long_version};
+pub use cache_rs::CacheRs;
 pub use cargo_toml::CargoToml;
-pub use cli_rs::CliRs;
+pub use cli_rs::{CliRs, EXTERNAL_VARIANT};
+pub(crate) use cli_rs::{command_expr, default_command_impl, dispatch_arm, long_help, with_help};
 pub use command_rs::CommandRs;
 pub use commands_mod::CommandsMod;
+pub use config_rs::ConfigRs;
 pub use context_rs::ContextRs;
+pub use docs_rs::{CLAP_MANGEN_DEPENDENCY, DOCS_COMMAND, DocsRs};
+pub use doctor_rs::{DOCTOR_COMMAND, DoctorRs};
 pub use generated_mod::GeneratedMod;
 pub use gitignore::GitIgnore;
+pub use grpc_rs::GrpcRs;
 pub use handler_stub::{HandlerStub, STUB_MARKER};
 pub use handlers_mod::HandlersMod;
+pub use http_rs::HttpRs;
+pub use llm_rs::LlmRs;
+pub use locale_rs::LocaleRs;
 pub use main_rs::MainRs;
+pub use secrets_rs::SecretsRs;
+pub use workdir_rs::WorkdirRs;
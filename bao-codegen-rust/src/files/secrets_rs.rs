@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{SecretsBackend, SecretsOptions};
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+/// The generated/secrets.rs file with the `Secrets` accessor of the
+/// `[context.secrets]` backend.
+///
+/// `get` and `require` have the same signatures for every backend, so
+/// switching backends doesn't touch handlers.
+pub struct SecretsRs {
+    pub options: SecretsOptions,
+}
+
+impl SecretsRs {
+    pub fn new(options: SecretsOptions) -> Self {
+        Self { options }
+    }
+
+    fn build_consts(&self) -> String {
+        let mut consts = Vec::new();
+        if self.options.backend == SecretsBackend::Keyring {
+            consts.push(format!(
+                "/// Keyring service the secrets are stored under.\nconst SERVICE: &str = {:?};",
+                self.options.service
+            ));
+        }
+        if self.options.backend == SecretsBackend::File {
+            consts.push(format!(
+                "/// File of `NAME=value` lines.\nconst PATH: &str = {:?};",
+                self.options.path
+            ));
+        } else {
+            consts.push(format!(
+                "/// Prefix of the environment variables.\nconst PREFIX: &str = {:?};",
+                self.options.prefix
+            ));
+        }
+        consts.join("\n\n")
+    }
+
+    fn build_struct(&self) -> String {
+        let (doc, fields) = match self.options.backend {
+            SecretsBackend::Keyring => (
+                "Credentials from the OS keyring, falling back to environment variables.",
+                "{}",
+            ),
+            SecretsBackend::Env => ("Credentials from environment variables.", "{}"),
+            SecretsBackend::File => (
+                "Credentials read from the secrets file when the CLI starts.",
+                "{\n    values: std::collections::HashMap<String, String>,\n}",
+            ),
+        };
+        format!(
+            r#"/// {doc}
+#[derive(Clone)]
+pub struct Secrets {fields}
+
+// Keeps secret values out of logs and panics
+impl std::fmt::Debug for Secrets {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        f.debug_struct("Secrets").finish_non_exhaustive()
+    }}
+}}"#
+        )
+    }
+
+    fn build_load(&self) -> &'static str {
+        match self.options.backend {
+            SecretsBackend::Keyring | SecretsBackend::Env => {
+                r#"    /// Nothing is read until a secret is looked up.
+    pub fn load() -> eyre::Result<Self> {
+        Ok(Self {})
+    }"#
+            }
+            SecretsBackend::File => {
+                r#"    /// Read the secrets file. A missing file reads as empty.
+    pub fn load() -> eyre::Result<Self> {
+        let text = match std::fs::read_to_string(PATH) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(eyre::eyre!("failed to read {}: {}", PATH, err)),
+        };
+        let values = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| {
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                (name.trim().to_string(), value.to_string())
+            })
+            .collect();
+        Ok(Self { values })
+    }"#
+            }
+        }
+    }
+
+    fn build_get(&self) -> &'static str {
+        match self.options.backend {
+            SecretsBackend::Keyring => {
+                r#"    /// Look up `name` in the keyring, then in `PREFIX` + `name`.
+    pub fn get(&self, name: &str) -> eyre::Result<Option<String>> {
+        match keyring::Entry::new(SERVICE, name).and_then(|entry| entry.get_password()) {
+            Ok(secret) => Ok(Some(secret)),
+            // No entry, or no keyring on this machine (e.g. in CI)
+            Err(
+                keyring::Error::NoEntry
+                | keyring::Error::NoStorageAccess(_)
+                | keyring::Error::PlatformFailure(_),
+            ) => Ok(std::env::var(format!("{}{}", PREFIX, name)).ok()),
+            Err(err) => Err(err.into()),
+        }
+    }"#
+            }
+            SecretsBackend::Env => {
+                r#"    /// Look up `PREFIX` + `name`.
+    pub fn get(&self, name: &str) -> eyre::Result<Option<String>> {
+        Ok(std::env::var(format!("{}{}", PREFIX, name)).ok())
+    }"#
+            }
+            SecretsBackend::File => {
+                r#"    /// Look up `name` in the secrets file.
+    pub fn get(&self, name: &str) -> eyre::Result<Option<String>> {
+        Ok(self.values.get(name).cloned())
+    }"#
+            }
+        }
+    }
+
+    fn build_impl(&self) -> String {
+        format!(
+            r#"impl Secrets {{
+{load}
+
+{get}
+
+    /// Look up `name`, failing if it is not set.
+    pub fn require(&self, name: &str) -> eyre::Result<String> {{
+        self.get(name)?
+            .ok_or_else(|| eyre::eyre!("secret '{{}}' is not set", name))
+    }}
+}}"#,
+            load = self.build_load(),
+            get = self.build_get()
+        )
+    }
+}
+
+impl GeneratedFile for SecretsRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("secrets.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(self.build_consts()))
+            .add(RawCode::new(self.build_struct()))
+            .add(RawCode::new(self.build_impl()))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::WorkdirOptions;
+
+use super::GENERATED_HEADER;
+use crate::{RawCode, RustFile};
+
+/// The generated/workdir.rs file with the `Workdir` of the
+/// `[context.workdir]` resource.
+///
+/// The scratch directory is a `tempfile::TempDir`, so it is removed when the
+/// context is dropped at the end of the invocation.
+pub struct WorkdirRs {
+    pub options: WorkdirOptions,
+}
+
+impl WorkdirRs {
+    pub fn new(options: WorkdirOptions) -> Self {
+        Self { options }
+    }
+
+    fn build_struct(&self) -> String {
+        let mut fields = Vec::new();
+        if self.options.data {
+            fields.push("    data: std::path::PathBuf,");
+        }
+        if self.options.temp {
+            fields.push("    temp: tempfile::TempDir,");
+        }
+        format!(
+            "/// The data and scratch directories of the CLI.\n#[derive(Debug)]\npub struct Workdir {{\n{}\n}}",
+            fields.join("\n")
+        )
+    }
+
+    fn build_data(&self) -> String {
+        match &self.options.path {
+            Some(path) => format!("std::path::PathBuf::from({:?})", path),
+            None => format!(
+                "dirs::data_dir()\n            .ok_or_else(|| eyre::eyre!(\"no data directory on this system\"))?\n            .join({:?})",
+                self.options.namespace
+            ),
+        }
+    }
+
+    fn build_impl(&self) -> String {
+        let mut body = Vec::new();
+        let mut fields = Vec::new();
+        let mut accessors = Vec::new();
+        if self.options.data {
+            body.push(format!(
+                r#"        let data = {};
+        std::fs::create_dir_all(&data)
+            .map_err(|err| eyre::eyre!("failed to create {{}}: {{}}", data.display(), err))?;"#,
+                self.build_data()
+            ));
+            fields.push("data");
+            accessors.push(
+                r#"    /// Directory for files that outlive the invocation.
+    pub fn data(&self) -> &std::path::Path {
+        &self.data
+    }"#,
+            );
+        }
+        if self.options.temp {
+            body.push(format!(
+                r#"        let temp = tempfile::Builder::new()
+            .prefix("{}-")
+            .tempdir()
+            .map_err(|err| eyre::eyre!("failed to create a scratch directory: {{}}", err))?;"#,
+                self.options.namespace
+            ));
+            fields.push("temp");
+            accessors.push(
+                r#"    /// Scratch directory, removed when the invocation ends.
+    pub fn temp(&self) -> &std::path::Path {
+        self.temp.path()
+    }"#,
+            );
+        }
+        let doc = match (self.options.data, self.options.temp) {
+            (true, true) => "Create the data directory and a fresh scratch directory.",
+            (true, false) => "Create the data directory.",
+            _ => "Create a fresh scratch directory.",
+        };
+        format!(
+            "impl Workdir {{\n    /// {doc}\n    pub fn open() -> eyre::Result<Self> {{\n{body}\n        Ok(Self {{ {fields} }})\n    }}\n\n{accessors}\n}}",
+            body = body.join("\n"),
+            fields = fields.join(", "),
+            accessors = accessors.join("\n\n"),
+        )
+    }
+}
+
+impl GeneratedFile for WorkdirRs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("generated").join("workdir.rs")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        RustFile::new()
+            .add(RawCode::new(self.build_struct()))
+            .add(RawCode::new(self.build_impl()))
+            .render_with_header(GENERATED_HEADER)
+    }
+}
@@ -1,33 +1,52 @@
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 
 use baobao_codegen::{
-    adapters::{CliAdapter, DatabaseAdapter, ErrorAdapter, RuntimeAdapter},
+    adapters::{
+        CliAdapter, ErrorAdapter, GrpcAdapter, HttpAdapter, RenderedResources, RuntimeAdapter,
+    },
     builder::{
         AttributeSpec, CodeBuilder, EnumSpec, FieldSpec, StructSpec, StructureRenderer, TypeRef,
         VariantSpec, Visibility,
     },
-    generation::{FileEntry, FileRegistry, HandlerPaths, find_orphan_commands},
+    generation::{FileCategory, FileEntry, FileRegistry, HandlerPaths, find_orphan_commands},
     language::{CleanResult, GenerateResult, LanguageCodegen, PreviewFile},
     pipeline::CompilationContext,
     schema::ComputedData,
 };
-use baobao_core::{DatabaseType, GeneratedFile, to_pascal_case, to_snake_case};
-use baobao_ir::{AppIR, CommandOp, InputKind, InputType, Operation, Resource};
+use baobao_core::{
+    GeneratedFile, Overwrite, OverwritePrompt, join_relative, relative_display, to_pascal_case,
+    to_snake_case,
+};
+use baobao_ir::{
+    AppIR, CacheMode, CommandOp, ConfigFormat, DefaultValue, Input, InputGroup, InputHint,
+    InputKind, InputType, IrVisitor, QueueType, Resource, SecretsBackend,
+};
 use eyre::Result;
 
 use crate::{
-    Arm, ClapAdapter, ClapAttr, Enum, EyreAdapter, Field, Fn, Impl, Match, Param, RustFile,
-    RustStructureRenderer, SqlxAdapter, Struct, TokioAdapter, Use, Variant,
+    ClapAdapter, ClapAttr, Enum, EyreAdapter, Field, Fn, Impl, Match, Param, ReqwestAdapter,
+    RustFile, RustStructureRenderer, Struct, TokioAdapter, TonicAdapter, Use, Variant,
+    adapters::database_adapter,
     files::{
-        AppRs, CargoToml, CliRs, CommandRs, CommandsMod, ContextRs, GeneratedMod, HandlerStub,
-        HandlersMod, MainRs, STUB_MARKER,
+        AppRs, BuildRs, CLAP_MANGEN_DEPENDENCY, CacheRs, CargoToml, CliRs, CommandRs, CommandsMod,
+        ConfigRs, ContextRs, DOCS_COMMAND, DOCTOR_COMMAND, DocsRs, DoctorRs, GENERATED_HEADER,
+        GeneratedMod, GrpcRs, HandlerStub, HandlersMod, HttpRs, LlmRs, LocaleRs, MainRs,
+        STUB_MARKER, SecretsRs, VERGEN_DEPENDENCY, WorkdirRs, command_expr, default_command_impl,
+        dispatch_arm, long_help, with_help,
     },
+    type_mapper::{RUST_TYPES, TYPE_DEPENDENCIES},
 };
 
 /// Rust code generator that produces clap-based CLI code
 pub struct Generator {
     ir: AppIR,
     computed: ComputedData,
+    resources: RenderedResources,
+    /// Base type name of each command, by path
+    command_types: HashMap<Vec<String>, String>,
 }
 
 impl LanguageCodegen for Generator {
@@ -43,8 +62,19 @@ impl LanguageCodegen for Generator {
         self.preview_files()
     }
 
-    fn generate(&self, output_dir: &Path) -> Result<GenerateResult> {
-        self.generate_files(output_dir)
+    fn generate_with_prompt(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.generate_files(output_dir, prompt)
+    }
+
+    fn files(&self) -> Result<Vec<FileEntry>> {
+        self.resources.check()?;
+        let mut files: Vec<FileEntry> = self.build_registry().entries().cloned().collect();
+        files.extend(self.handler_entries());
+        Ok(files)
     }
 
     fn clean(&self, output_dir: &Path) -> Result<CleanResult> {
@@ -66,12 +96,90 @@ impl Generator {
     /// Panics if the context doesn't have IR or computed data
     /// (i.e., if the pipeline didn't run successfully).
     pub fn from_context(mut ctx: CompilationContext) -> Self {
+        let computed = ctx.take_computed();
+        let resources = ctx
+            .resource_adapters
+            .render_all("rust", &computed.context_fields);
+        let ir = ctx.take_ir();
+        let command_types = command_type_names(&ir);
         Self {
-            ir: ctx.take_ir(),
-            computed: ctx.take_computed(),
+            ir,
+            computed,
+            resources,
+            command_types,
         }
     }
 
+    /// Base name of a command's types: `Pixel` and `PixelCommands` for a
+    /// command with subcommands, `PixelArgs` for a leaf.
+    ///
+    /// Nested types all live in their top-level command's module, so a nested
+    /// command sharing its name with another command is named by its full
+    /// path (`BirchPixel`) instead.
+    fn command_type_name(&self, cmd: &CommandOp) -> String {
+        self.command_types
+            .get(&cmd.path)
+            .cloned()
+            .unwrap_or_else(|| to_pascal_case(&cmd.name))
+    }
+
+    /// Whether to generate the hidden `docs` subcommand, skipped if the
+    /// manifest defines its own `docs` command.
+    fn docs_command(&self) -> bool {
+        self.ir.meta.docs_command && !self.ir.commands().any(|cmd| cmd.name == DOCS_COMMAND)
+    }
+
+    /// Whether to generate the `doctor` subcommand, skipped if the manifest
+    /// defines its own `doctor` command.
+    fn doctor_command(&self) -> bool {
+        self.ir.meta.doctor_command && !self.ir.commands().any(|cmd| cmd.name == DOCTOR_COMMAND)
+    }
+
+    /// Whether any input's value must match a regular expression.
+    fn has_patterns(&self) -> bool {
+        struct Patterns(bool);
+
+        impl IrVisitor<'_> for Patterns {
+            fn visit_input(&mut self, input: &Input) {
+                self.0 |= input.constraints.pattern.is_some();
+            }
+        }
+
+        let mut patterns = Patterns(false);
+        patterns.visit_app(&self.ir);
+        patterns.0
+    }
+
+    /// Whether any argument asks for its value when missing.
+    fn has_prompt_messages(&self) -> bool {
+        struct Prompts(bool);
+
+        impl IrVisitor<'_> for Prompts {
+            fn visit_input(&mut self, input: &Input) {
+                self.0 |= input.prompt_message().is_some();
+            }
+        }
+
+        let mut prompts = Prompts(false);
+        prompts.visit_app(&self.ir);
+        prompts.0
+    }
+
+    /// Whether any input has the given type.
+    fn uses_type(&self, ty: InputType) -> bool {
+        struct Uses(InputType, bool);
+
+        impl IrVisitor<'_> for Uses {
+            fn visit_input(&mut self, input: &Input) {
+                self.1 |= input.ty == self.0;
+            }
+        }
+
+        let mut uses = Uses(ty, false);
+        uses.visit_app(&self.ir);
+        uses.1
+    }
+
     /// Build a file registry with all generated files.
     ///
     /// This centralizes file registration, making generation declarative.
@@ -84,15 +192,40 @@ impl Generator {
         let context_fields = self.computed.context_fields.clone();
         let is_async = self.computed.is_async;
 
+        let has_globals = !self.global_flags().is_empty();
+        let has_locales = !self.ir.locales.is_empty();
+        let has_docs = self.docs_command();
+        let has_doctor = self.doctor_command();
+        let http = HttpRs::new(self.ir.http_clients().map(|http| &http.options));
+        let build_rs = self.build_rs();
+
         // Config files
-        let dependencies = self.collect_dependencies(is_async);
+        let mut dependencies = self.collect_dependencies(is_async);
+        if has_docs {
+            let (name, version) = CLAP_MANGEN_DEPENDENCY;
+            dependencies.push((name.to_string(), version.to_string()));
+        }
+        let mut build_dependencies = Vec::new();
+        if self.ir.meta.build_info {
+            let (name, version) = VERGEN_DEPENDENCY;
+            build_dependencies.push((name.to_string(), version.to_string()));
+        }
+        if let Some(grpc) = self.ir.grpc() {
+            for dep in TonicAdapter::new().build_dependencies(&grpc.options) {
+                build_dependencies.push((dep.name, dep.version));
+            }
+        }
         registry.register(FileEntry::config(
             "Cargo.toml",
             CargoToml::new(&self.ir.meta.name)
                 .with_version_str(&self.ir.meta.version)
                 .with_dependencies(dependencies)
+                .with_build_dependencies(build_dependencies)
                 .render(),
         ));
+        if build_rs.is_needed() {
+            registry.register(FileEntry::infrastructure("build.rs", build_rs.render()));
+        }
 
         // Infrastructure files
         registry.register(FileEntry::infrastructure(
@@ -101,18 +234,110 @@ impl Generator {
         ));
         registry.register(FileEntry::infrastructure(
             "src/app.rs",
-            AppRs::new(is_async).render(),
+            AppRs::new(is_async)
+                .with_globals(has_globals)
+                .with_locales(has_locales)
+                .with_doctor(has_doctor)
+                .with_external(self.ir.meta.external_subcommands)
+                .with_default_command(self.ir.meta.default_command.is_some())
+                .with_lazy_context(self.ir.has_requirements())
+                .with_logging(self.ir.logger().cloned())
+                .render(),
         ));
         registry.register(FileEntry::infrastructure(
             "src/context.rs",
-            ContextRs::new(context_fields).render(),
+            ContextRs::new(context_fields.clone())
+                .with_resources(self.resources.clone())
+                .with_globals(has_globals)
+                .with_lazy(self.ir.has_requirements())
+                .render(),
         ));
 
         // Generated module files
         registry.register(FileEntry::generated(
             "src/generated/mod.rs",
-            GeneratedMod.render(),
+            GeneratedMod::new(has_globals)
+                .with_locales(has_locales)
+                .with_docs(has_docs)
+                .with_doctor(has_doctor)
+                .with_http(http.is_needed())
+                .with_grpc(build_rs.grpc.is_some())
+                .with_config(self.ir.config().is_some())
+                .with_secrets(self.ir.secrets().is_some())
+                .with_llm(self.ir.llm().is_some())
+                .with_cache(
+                    self.ir
+                        .cache()
+                        .is_some_and(|cache| cache.options.mode == CacheMode::Disk),
+                )
+                .with_workdir(self.ir.workdir().is_some())
+                .render(),
         ));
+        if let Some(config) = self.ir.config() {
+            registry.register(FileEntry::generated(
+                "src/generated/config.rs",
+                ConfigRs::new(config.options.clone()).render(),
+            ));
+        }
+        if let Some(secrets) = self.ir.secrets() {
+            registry.register(FileEntry::generated(
+                "src/generated/secrets.rs",
+                SecretsRs::new(secrets.options.clone()).render(),
+            ));
+        }
+        if let Some(cache) = self.ir.cache()
+            && cache.options.mode == CacheMode::Disk
+        {
+            registry.register(FileEntry::generated(
+                "src/generated/cache.rs",
+                CacheRs::new(cache.options.clone()).render(),
+            ));
+        }
+        if let Some(workdir) = self.ir.workdir() {
+            registry.register(FileEntry::generated(
+                "src/generated/workdir.rs",
+                WorkdirRs::new(workdir.options.clone()).render(),
+            ));
+        }
+        if let Some(llm) = self.ir.llm() {
+            registry.register(FileEntry::generated(
+                "src/generated/llm.rs",
+                LlmRs::new(llm.options.clone()).render(),
+            ));
+        }
+        if http.is_needed() {
+            registry.register(FileEntry::generated("src/generated/http.rs", http.render()));
+        }
+        if build_rs.grpc.is_some() {
+            registry.register(FileEntry::generated(
+                "src/generated/grpc.rs",
+                GrpcRs.render(),
+            ));
+        }
+        if has_docs {
+            registry.register(FileEntry::generated(
+                "src/generated/docs.rs",
+                DocsRs.render(),
+            ));
+        }
+        if has_doctor {
+            registry.register(FileEntry::generated(
+                "src/generated/doctor.rs",
+                DoctorRs::new(context_fields, is_async).render(),
+            ));
+        }
+        if has_locales {
+            registry.register(FileEntry::generated(
+                "src/generated/locale.rs",
+                LocaleRs::new(&self.ir.locales).render(),
+            ));
+        }
+        if has_globals {
+            registry.register(FileEntry::generated(
+                "src/generated/globals.rs",
+                self.generate_globals_file(),
+            ));
+        }
 
         // Collect commands from IR
         let commands: Vec<CommandOp> = self.ir.commands().cloned().collect();
@@ -127,6 +352,13 @@ impl Generator {
                 commands,
                 is_async,
             )
+            .with_footer(self.ir.meta.docs.footer.clone())
+            .with_external(self.ir.meta.external_subcommands)
+            .with_default_command(self.ir.meta.default_command.clone())
+            .with_globals(has_globals)
+            .with_build_info(self.ir.meta.build_info)
+            .with_docs(has_docs)
+            .with_doctor(has_doctor)
             .render(),
         ));
 
@@ -136,8 +368,7 @@ impl Generator {
         ));
 
         // Individual command files from IR
-        for op in &self.ir.operations {
-            let Operation::Command(cmd) = op;
+        for cmd in self.ir.commands() {
             let content = self.generate_command_file_from_ir(cmd, is_async);
             let file_name = to_snake_case(&cmd.name);
             registry.register(FileEntry::generated(
@@ -146,6 +377,10 @@ impl Generator {
             ));
         }
 
+        if let Some(header) = &self.ir.meta.header {
+            registry.prepend_header(header);
+        }
+
         registry
     }
 
@@ -162,16 +397,32 @@ impl Generator {
     }
 
     /// Generate all files into the specified output directory
-    fn generate_files(&self, output_dir: &Path) -> Result<GenerateResult> {
-        let handlers_dir = output_dir.join("src/handlers");
-        let is_async = self.computed.is_async;
+    fn generate_files(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.resources.check()?;
+        let handlers_dir = join_relative(output_dir, "src/handlers");
 
         // Write all registered files using the registry
         let registry = self.build_registry();
-        registry.write_all(output_dir)?;
+        let stats = registry.write_all_with_prompt(output_dir, prompt)?;
+
+        // A stale build.rs would fail without its build dependencies
+        if !self.build_rs().is_needed() {
+            let build_rs = join_relative(output_dir, "build.rs");
+            if let Ok(content) = std::fs::read_to_string(&build_rs)
+                && content.contains(GENERATED_HEADER)
+            {
+                std::fs::remove_file(&build_rs)?;
+            }
+        }
 
         // Generate handlers (handled separately due to special logic)
-        let result = self.generate_handlers(&handlers_dir, output_dir, is_async)?;
+        let mut result = self.generate_handlers(&handlers_dir, output_dir)?;
+        result.files = stats.files;
+        result.checksums = stats.checksums;
 
         Ok(result)
     }
@@ -183,12 +434,8 @@ impl Generator {
         // Collect expected command names from IR (snake_case for file names)
         let expected_commands: HashSet<String> = self
             .ir
-            .operations
-            .iter()
-            .map(|op| {
-                let Operation::Command(cmd) = op;
-                to_snake_case(&cmd.name)
-            })
+            .commands()
+            .map(|cmd| to_snake_case(&cmd.name))
             .collect();
 
         // Collect expected handler paths from computed data (convert to snake_case)
@@ -205,16 +452,17 @@ impl Generator {
             .collect();
 
         // Find and delete orphaned generated command files
-        let commands_dir = output_dir.join("src/generated/commands");
+        let commands_dir = join_relative(output_dir, "src/generated/commands");
         let orphan_commands = find_orphan_commands(&commands_dir, "rs", &expected_commands)?;
         for path in orphan_commands {
             std::fs::remove_file(&path)?;
-            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
-            result.deleted_commands.push(relative.display().to_string());
+            result
+                .deleted_commands
+                .push(relative_display(&path, output_dir));
         }
 
         // Find and handle orphaned handler files
-        let handlers_dir = output_dir.join("src/handlers");
+        let handlers_dir = join_relative(output_dir, "src/handlers");
         let handler_paths = HandlerPaths::new(&handlers_dir, "rs", STUB_MARKER);
         let orphan_handlers = handler_paths.find_orphans_with_status(&expected_handlers)?;
 
@@ -248,12 +496,8 @@ impl Generator {
         // Collect expected command names from IR (snake_case for file names)
         let expected_commands: HashSet<String> = self
             .ir
-            .operations
-            .iter()
-            .map(|op| {
-                let Operation::Command(cmd) = op;
-                to_snake_case(&cmd.name)
-            })
+            .commands()
+            .map(|cmd| to_snake_case(&cmd.name))
             .collect();
 
         // Collect expected handler paths from computed data (convert to snake_case)
@@ -270,15 +514,16 @@ impl Generator {
             .collect();
 
         // Find orphaned generated command files
-        let commands_dir = output_dir.join("src/generated/commands");
+        let commands_dir = join_relative(output_dir, "src/generated/commands");
         let orphan_commands = find_orphan_commands(&commands_dir, "rs", &expected_commands)?;
         for path in orphan_commands {
-            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
-            result.deleted_commands.push(relative.display().to_string());
+            result
+                .deleted_commands
+                .push(relative_display(&path, output_dir));
         }
 
         // Find orphaned handler files
-        let handlers_dir = output_dir.join("src/handlers");
+        let handlers_dir = join_relative(output_dir, "src/handlers");
         let handler_paths = HandlerPaths::new(&handlers_dir, "rs", STUB_MARKER);
         let orphan_handlers = handler_paths.find_orphans_with_status(&expected_handlers)?;
 
@@ -315,12 +560,20 @@ impl Generator {
         Ok(())
     }
 
+    /// The build script, capturing build info and compiling `.proto` files.
+    fn build_rs(&self) -> BuildRs {
+        BuildRs::new()
+            .with_build_info(self.ir.meta.build_info)
+            .with_grpc(self.ir.grpc().map(|grpc| grpc.options.clone()))
+    }
+
     fn collect_dependencies(&self, has_async_context: bool) -> Vec<(String, String)> {
         // Use adapters to collect dependencies
         let cli = ClapAdapter::new();
         let error = EyreAdapter::new();
         let runtime = TokioAdapter::new();
-        let database = SqlxAdapter::new();
+        let http = ReqwestAdapter::new();
+        let grpc = TonicAdapter::new();
 
         let mut dependencies: Vec<(String, String)> = Vec::new();
         let mut seen: HashSet<String> = HashSet::new();
@@ -346,30 +599,167 @@ impl Generator {
                     dependencies.push((dep.name, dep.version));
                 }
             }
+            // Lazy contexts keep async resources in `tokio::sync::OnceCell`s
+            if self.ir.has_requirements() {
+                for (name, version) in &mut dependencies {
+                    if name == "tokio" {
+                        *version = version.replace(r#""macros"]"#, r#""macros", "sync"]"#);
+                    }
+                }
+            }
         }
 
         // Add database and HTTP dependencies based on IR resources
         for resource in &self.ir.resources {
             match resource {
                 Resource::Database(db) => {
-                    let db_type = match db.db_type {
-                        baobao_ir::DatabaseType::Postgres => DatabaseType::Postgres,
-                        baobao_ir::DatabaseType::Mysql => DatabaseType::Mysql,
-                        baobao_ir::DatabaseType::Sqlite => DatabaseType::Sqlite,
-                    };
-                    for dep in database.dependencies(db_type) {
+                    for dep in database_adapter(db.db_type).dependencies(db.db_type) {
+                        if seen.insert(dep.name.clone()) {
+                            dependencies.push((dep.name, dep.version));
+                        }
+                    }
+                }
+                Resource::HttpClient(client) => {
+                    for dep in http.dependencies(&client.options) {
+                        if seen.insert(dep.name.clone()) {
+                            dependencies.push((dep.name, dep.version));
+                        }
+                    }
+                }
+                Resource::Logger(_) => {
+                    for dep in [
+                        ("tracing", "0.1"),
+                        (
+                            "tracing-subscriber",
+                            r#"{ version = "0.3", features = ["env-filter", "json"] }"#,
+                        ),
+                    ] {
+                        if seen.insert(dep.0.to_string()) {
+                            dependencies.push((dep.0.to_string(), dep.1.to_string()));
+                        }
+                    }
+                }
+                Resource::Storage(_) => {
+                    let object_store = (
+                        "object_store".to_string(),
+                        r#"{ version = "0.12", features = ["aws"] }"#.to_string(),
+                    );
+                    if seen.insert(object_store.0.clone()) {
+                        dependencies.push(object_store);
+                    }
+                }
+                Resource::Grpc(channel) => {
+                    for dep in grpc.dependencies(&channel.options) {
                         if seen.insert(dep.name.clone()) {
                             dependencies.push((dep.name, dep.version));
                         }
                     }
                 }
-                Resource::HttpClient(_) => {
-                    // Add reqwest for HTTP client
-                    let reqwest = ("reqwest".to_string(), "0.12".to_string());
-                    if seen.insert(reqwest.0.clone()) {
-                        dependencies.push(reqwest);
+                Resource::Email(_) => {
+                    let lettre = (
+                        "lettre".to_string(),
+                        r#"{ version = "0.11", features = ["tokio1", "tokio1-native-tls"] }"#
+                            .to_string(),
+                    );
+                    if seen.insert(lettre.0.clone()) {
+                        dependencies.push(lettre);
+                    }
+                }
+                Resource::Config(config) => {
+                    let format = match config.options.format {
+                        ConfigFormat::Toml => r#"{ version = "0.10", features = ["toml"] }"#,
+                        ConfigFormat::Json => r#"{ version = "0.10", features = ["json"] }"#,
+                        ConfigFormat::Yaml => r#"{ version = "0.10", features = ["yaml"] }"#,
+                    };
+                    for dep in [
+                        ("serde", r#"{ version = "1", features = ["derive"] }"#),
+                        ("figment", format),
+                    ] {
+                        if seen.insert(dep.0.to_string()) {
+                            dependencies.push((dep.0.to_string(), dep.1.to_string()));
+                        }
+                    }
+                }
+                Resource::Secrets(secrets) => {
+                    if secrets.options.backend == SecretsBackend::Keyring
+                        && seen.insert("keyring".to_string())
+                    {
+                        dependencies.push((
+                            "keyring".to_string(),
+                            r#"{ version = "3", features = ["apple-native", "windows-native", "sync-secret-service", "crypto-rust", "vendored"] }"#.to_string(),
+                        ));
+                    }
+                }
+                Resource::Llm(_) => {
+                    for dep in [("reqwest", "0.12"), ("serde_json", "1")] {
+                        if seen.insert(dep.0.to_string()) {
+                            dependencies.push((dep.0.to_string(), dep.1.to_string()));
+                        }
+                    }
+                }
+                Resource::Cache(cache) => {
+                    let moka = (
+                        "moka".to_string(),
+                        r#"{ version = "0.12", features = ["future"] }"#.to_string(),
+                    );
+                    if seen.insert(moka.0.clone()) {
+                        dependencies.push(moka);
+                    }
+                    if cache.options.mode == CacheMode::Disk
+                        && cache.options.path.is_none()
+                        && seen.insert("dirs".to_string())
+                    {
+                        dependencies.push(("dirs".to_string(), "6".to_string()));
+                    }
+                }
+                Resource::Workdir(workdir) => {
+                    if workdir.options.data
+                        && workdir.options.path.is_none()
+                        && seen.insert("dirs".to_string())
+                    {
+                        dependencies.push(("dirs".to_string(), "6".to_string()));
+                    }
+                    if workdir.options.temp && seen.insert("tempfile".to_string()) {
+                        dependencies.push(("tempfile".to_string(), "3".to_string()));
                     }
                 }
+                Resource::Queue(queue) => {
+                    let client = match queue.queue_type {
+                        QueueType::Nats => ("async-nats".to_string(), "0.42".to_string()),
+                        QueueType::Kafka => ("rdkafka".to_string(), "0.36".to_string()),
+                    };
+                    if seen.insert(client.0.clone()) {
+                        dependencies.push(client);
+                    }
+                }
+                // Dependencies come from the resource adapter
+                Resource::Custom(_) => {}
+            }
+        }
+
+        if self.has_prompt_messages() {
+            let dialoguer = ("dialoguer".to_string(), "0.11".to_string());
+            if seen.insert(dialoguer.0.clone()) {
+                dependencies.push(dialoguer);
+            }
+        }
+
+        if self.has_patterns() {
+            let regex = ("regex".to_string(), "1".to_string());
+            if seen.insert(regex.0.clone()) {
+                dependencies.push(regex);
+            }
+        }
+
+        for (ty, name, version) in TYPE_DEPENDENCIES {
+            if self.uses_type((*ty).into()) && seen.insert(name.to_string()) {
+                dependencies.push((name.to_string(), version.to_string()));
+            }
+        }
+
+        for dep in self.resources.dependencies() {
+            if seen.insert(dep.name.clone()) {
+                dependencies.push((dep.name.clone(), dep.version.clone()));
             }
         }
 
@@ -402,13 +792,56 @@ impl Generator {
             .render_with_header("// Generated by Bao - DO NOT EDIT")
     }
 
+    /// Generate the `globals.rs` file holding the `GlobalArgs` struct.
+    fn generate_globals_file(&self) -> String {
+        // No doc comment: clap would use it as the CLI's about text when
+        // the manifest has no description
+        let content = self.generate_args_struct("Global", "", &self.global_flags(), &[], true);
+
+        RustFile::new()
+            .use_stmt(Use::new("clap").symbol("Args"))
+            .add(crate::RawCode::new(content))
+            .render_with_header("// Generated by Bao - DO NOT EDIT")
+    }
+
+    /// Global inputs rendered as global clap flags.
+    fn global_flags(&self) -> Vec<Input> {
+        self.ir
+            .global_inputs
+            .iter()
+            .filter(|input| matches!(input.kind, InputKind::Flag { .. }))
+            .cloned()
+            .collect()
+    }
+
     /// Generate args struct from IR CommandOp using Code IR.
     fn generate_args_struct_from_ir(&self, pascal_name: &str, cmd: &CommandOp) -> String {
+        self.generate_args_struct(
+            pascal_name,
+            &cmd.description,
+            &cmd.inputs,
+            &cmd.groups,
+            false,
+        )
+    }
+
+    /// Generate an args struct with choice enums for `inputs`.
+    ///
+    /// Global structs also derive `Clone`, and their flags are marked
+    /// `global` so they are accepted after any subcommand.
+    fn generate_args_struct(
+        &self,
+        pascal_name: &str,
+        description: &str,
+        inputs: &[Input],
+        groups: &[InputGroup],
+        global: bool,
+    ) -> String {
         let renderer = RustStructureRenderer::new();
         let mut builder = CodeBuilder::rust();
 
         // First, generate choice enums for inputs that have choices
-        for input in &cmd.inputs {
+        for input in inputs {
             if let Some(choices) = &input.choices {
                 let enum_name = format!("{}{}Choice", pascal_name, to_pascal_case(&input.name));
                 let choice_enum = Self::generate_choice_enum(&enum_name, choices);
@@ -418,12 +851,20 @@ impl Generator {
         }
 
         let mut spec = StructSpec::new(format!("{}Args", pascal_name))
-            .doc(&cmd.description)
             .derive("Args")
             .derive("Debug");
+        if !description.is_empty() {
+            spec = spec.doc(description);
+        }
+        if global {
+            spec = spec.derive("Clone");
+        }
+        for group in groups {
+            spec = spec.attribute(AttributeSpec::simple("command").arg(Self::arg_group(group)));
+        }
 
         // Generate fields for all inputs
-        for input in &cmd.inputs {
+        for input in inputs {
             let rust_type = if input.choices.is_some() {
                 TypeRef::named(format!(
                     "{}{}Choice",
@@ -438,13 +879,16 @@ impl Generator {
                 && input.ty == InputType::Bool
                 && input.choices.is_none();
 
-            let field_type = if is_bool_flag {
-                TypeRef::bool()
-            } else if (input.required && input.default.is_none()) || input.default.is_some() {
-                rust_type.clone()
-            } else {
-                TypeRef::optional(rust_type)
-            };
+            let field_type =
+                if is_bool_flag || input.ty == InputType::Count || input.asks_when_missing() {
+                    rust_type
+                } else if input.multiple {
+                    TypeRef::array(rust_type)
+                } else if (input.required && input.default.is_none()) || input.default.is_some() {
+                    rust_type.clone()
+                } else {
+                    TypeRef::optional(rust_type)
+                };
 
             let mut field = FieldSpec::new(to_snake_case(&input.name), field_type)
                 .visibility(Visibility::Public);
@@ -454,47 +898,291 @@ impl Generator {
             }
 
             // Add clap attribute for flags
-            if let InputKind::Flag { short } = &input.kind {
-                let arg_attr = Self::build_clap_arg_attr(*short, input.default.as_ref());
-                field = field.attribute(arg_attr);
+            match &input.kind {
+                InputKind::Flag { short } => {
+                    let arg_attr = Self::build_clap_arg_attr(*short, input, global);
+                    field = field.attribute(arg_attr);
+                }
+                InputKind::Positional => {
+                    let mut attr = AttributeSpec::simple("arg");
+                    if input.multiple && input.required && input.default.is_none() {
+                        attr = attr.named("required", "true");
+                    }
+                    if input.allow_hyphen_values {
+                        attr = attr.named("allow_hyphen_values", "true");
+                    }
+                    if let Some(parser) = Self::value_parser(input) {
+                        attr = attr.named("value_parser", parser);
+                    }
+                    if let Some(hint) = Self::value_hint(input) {
+                        attr = attr.named("value_hint", hint);
+                    }
+                    if let Some(default) = &input.default {
+                        let (name, value) = Self::clap_default(default, input);
+                        attr = attr.named(name, value);
+                    }
+                    if input.asks_when_missing() {
+                        attr = Self::missing_attr(attr);
+                    }
+                    if let Some(text) = Self::input_long_help(input) {
+                        attr = attr.named("long_help", text);
+                    }
+                    if !attr.args.is_empty() {
+                        field = field.attribute(attr);
+                    }
+                }
             }
 
             spec = spec.field(field);
         }
 
         builder.push_raw(&renderer.render_struct(&spec));
+        for input in inputs {
+            if let Some(parser) = Self::constraint_parser(input) {
+                builder.push_blank();
+                builder.push_raw(&parser);
+            }
+        }
         builder.build()
     }
 
     /// Build a clap arg attribute from flag parameters.
-    fn build_clap_arg_attr(
-        short: Option<char>,
-        default: Option<&baobao_ir::DefaultValue>,
-    ) -> AttributeSpec {
+    fn build_clap_arg_attr(short: Option<char>, input: &Input, global: bool) -> AttributeSpec {
         let mut attr = AttributeSpec::simple("arg").flag("long");
+        if global {
+            attr = attr.named("global", "true");
+        }
 
         if let Some(c) = short {
             attr = attr.named("short", format!("'{}'", c));
         }
 
-        if let Some(default_val) = default {
-            attr = attr.named(
-                "default_value",
-                format!("\"{}\"", default_val.to_code_string()),
-            );
+        if input.ty == InputType::Count {
+            attr = attr.named("action", "clap::ArgAction::Count");
+        }
+
+        if let Some(delimiter) = input.delimiter {
+            attr = attr.named("value_delimiter", format!("{:?}", delimiter));
+        }
+
+        if let Some(parser) = Self::value_parser(input) {
+            attr = attr.named("value_parser", parser);
+        }
+
+        if let Some(hint) = Self::value_hint(input) {
+            attr = attr.named("value_hint", hint);
+        }
+
+        if let Some(default_val) = &input.default {
+            let (name, value) = Self::clap_default(default_val, input);
+            attr = attr.named(name, value);
+        }
+
+        if input.asks_when_missing() {
+            attr = Self::missing_attr(attr);
+        }
+
+        if let Some(text) = Self::input_long_help(input) {
+            attr = attr.named("long_help", text);
+        }
+
+        for other in &input.requires {
+            attr = attr.named("requires", format!("{:?}", to_snake_case(other)));
+        }
+        if let Some(required_if) = &input.required_if {
+            attr = attr.arg(format!(
+                "required_if_eq({:?}, {:?})",
+                to_snake_case(&required_if.flag),
+                required_if.value
+            ));
         }
 
         attr
     }
 
+    /// Default an input read after parsing to empty, so dispatch can tell it
+    /// wasn't given and read it from the environment or a prompt instead.
+    fn missing_attr(attr: AttributeSpec) -> AttributeSpec {
+        attr.named("default_value", "\"\"")
+            .named("hide_default_value", "true")
+    }
+
+    /// Quoted `long_help` value for an input with a long description.
+    fn input_long_help(input: &Input) -> Option<String> {
+        long_help(
+            input.description.as_deref().unwrap_or_default(),
+            input.long_description.as_deref(),
+        )
+        .map(|text| format!("{:?}", text))
+    }
+
+    /// The clap `ValueHint` for an input's completion hint.
+    fn value_hint(input: &Input) -> Option<&'static str> {
+        input.hint.map(|hint| match hint {
+            InputHint::File => "clap::ValueHint::FilePath",
+            InputHint::Dir => "clap::ValueHint::DirPath",
+            InputHint::Hostname => "clap::ValueHint::Hostname",
+            InputHint::Url => "clap::ValueHint::Url",
+            InputHint::Command => "clap::ValueHint::CommandName",
+        })
+    }
+
+    /// A clap `ArgGroup` expression for a group of inputs.
+    ///
+    /// Groups default to allowing one member, so non-exclusive groups set
+    /// `multiple(true)`.
+    fn arg_group(group: &InputGroup) -> String {
+        let members: Vec<_> = group
+            .members
+            .iter()
+            .map(|member| format!("{:?}", to_snake_case(member)))
+            .collect();
+        let mut expr = format!(
+            "group(clap::ArgGroup::new({:?}).args([{}])",
+            group.name,
+            members.join(", ")
+        );
+        if !group.exclusive {
+            expr.push_str(".multiple(true)");
+        }
+        if group.required {
+            expr.push_str(".required(true)");
+        }
+        expr.push(')');
+        expr
+    }
+
+    /// The clap `value_parser` enforcing an input's constraints.
+    ///
+    /// Int ranges use clap's ranged parser; float bounds, patterns, lengths
+    /// and `path_exists` use the function from [`Self::constraint_parser`].
+    fn value_parser(input: &Input) -> Option<String> {
+        let constraints = &input.constraints;
+        match input.ty {
+            InputType::Int if constraints.has_range() => {
+                let bound = |b: Option<f64>| b.map(|b| (b as i64).to_string()).unwrap_or_default();
+                let range = match constraints.max {
+                    Some(_) => format!("{}..={}", bound(constraints.min), bound(constraints.max)),
+                    None => format!("{}..", bound(constraints.min)),
+                };
+                Some(format!("clap::value_parser!(i64).range({})", range))
+            }
+            InputType::Float if constraints.has_range() => Some(Self::parser_name(input)),
+            InputType::Duration if input.choices.is_none() => {
+                Some("humantime::parse_duration".to_string())
+            }
+            InputType::String if constraints.pattern.is_some() || constraints.has_length() => {
+                Some(Self::parser_name(input))
+            }
+            InputType::Path if constraints.path_exists => Some(Self::parser_name(input)),
+            _ => None,
+        }
+    }
+
+    fn parser_name(input: &Input) -> String {
+        format!("parse_{}", to_snake_case(&input.name))
+    }
+
+    /// A function parsing and checking a float, string or path input's value,
+    /// used as its clap `value_parser`.
+    fn constraint_parser(input: &Input) -> Option<String> {
+        let constraints = &input.constraints;
+        let mut checks = Vec::new();
+        let value_type = match input.ty {
+            InputType::Float if constraints.has_range() => {
+                checks.push(
+                    "let value: f64 = value.parse().map_err(|e| format!(\"{e}\"))?;".to_string(),
+                );
+                if let Some(min) = constraints.min {
+                    checks.push(format!(
+                        "if value < {min:?} {{\n        return Err(\"must be at least {min}\".into());\n    }}"
+                    ));
+                }
+                if let Some(max) = constraints.max {
+                    checks.push(format!(
+                        "if value > {max:?} {{\n        return Err(\"must be at most {max}\".into());\n    }}"
+                    ));
+                }
+                checks.push("Ok(value)".to_string());
+                "f64"
+            }
+            InputType::String if constraints.pattern.is_some() || constraints.has_length() => {
+                if let Some(min) = constraints.min_length {
+                    checks.push(format!(
+                        "if value.chars().count() < {min} {{\n        return Err(\"must be at least {min} characters\".into());\n    }}"
+                    ));
+                }
+                if let Some(max) = constraints.max_length {
+                    checks.push(format!(
+                        "if value.chars().count() > {max} {{\n        return Err(\"must be at most {max} characters\".into());\n    }}"
+                    ));
+                }
+                if let Some(pattern) = &constraints.pattern {
+                    let anchored = format!("^(?:{})$", pattern);
+                    checks.push(format!(
+                        "let pattern = regex::Regex::new({anchored:?}).expect(\"valid pattern\");\n    \
+                         if !pattern.is_match(value) {{\n        \
+                         return Err(format!(\"must match the pattern {{:?}}\", {pattern:?}));\n    }}"
+                    ));
+                }
+                checks.push("Ok(value.to_string())".to_string());
+                "String"
+            }
+            InputType::Path if constraints.path_exists => {
+                checks.push(
+                    "let path = std::path::PathBuf::from(value);\n    \
+                     if !path.exists() {\n        \
+                     return Err(\"does not exist\".into());\n    }\n    \
+                     Ok(path)"
+                        .to_string(),
+                );
+                "std::path::PathBuf"
+            }
+            _ => return None,
+        };
+        Some(format!(
+            "fn {}(value: &str) -> Result<{}, String> {{\n    {}\n}}\n",
+            Self::parser_name(input),
+            value_type,
+            checks.join("\n    ")
+        ))
+    }
+
+    /// Render a default as a clap `arg` attribute.
+    ///
+    /// Numbers and booleans use `default_value_t` with a typed literal;
+    /// strings, paths, choice values and lists use `default_value`, which clap
+    /// parses like command-line input. A list default of an input taking
+    /// multiple values uses `default_values`, one string per value.
+    fn clap_default(default: &DefaultValue, input: &Input) -> (&'static str, String) {
+        let has_choices = input.choices.is_some();
+        match default {
+            DefaultValue::List(items) if input.multiple => {
+                let values: Vec<_> = items
+                    .iter()
+                    .map(|item| format!("{:?}", item.to_code_string()))
+                    .collect();
+                ("default_values", format!("[{}]", values.join(", ")))
+            }
+            DefaultValue::Int(i) if !has_choices => ("default_value_t", i.to_string()),
+            DefaultValue::Float(f) if !has_choices => ("default_value_t", format!("{:?}", f)),
+            DefaultValue::Bool(b) if !has_choices => ("default_value_t", b.to_string()),
+            other => ("default_value", format!("{:?}", other.to_code_string())),
+        }
+    }
+
     /// Map IR InputType to TypeRef.
     fn map_input_type_ref(input_type: InputType) -> TypeRef {
         match input_type {
-            InputType::String => TypeRef::string(),
+            InputType::String | InputType::Secret => TypeRef::string(),
             InputType::Int => TypeRef::int(),
             InputType::Float => TypeRef::float(),
             InputType::Bool => TypeRef::bool(),
             InputType::Path => TypeRef::path(),
+            InputType::Count => TypeRef::named("u8"),
+            InputType::Url | InputType::Duration | InputType::DateTime | InputType::Uuid => {
+                TypeRef::named(RUST_TYPES.type_name(input_type.into()))
+            }
         }
     }
 
@@ -509,7 +1197,7 @@ impl Generator {
 
         for choice in choices {
             let variant_name = to_pascal_case(choice);
-            let attr = AttributeSpec::simple("clap").arg(format!("value(name = \"{}\")", choice));
+            let attr = AttributeSpec::simple("value").named("name", format!("{:?}", choice));
             let variant = VariantSpec::unit(&variant_name).attribute(attr);
             spec = spec.variant(variant);
         }
@@ -517,13 +1205,8 @@ impl Generator {
         renderer.render_enum(&spec)
     }
 
-    /// Generate handlers directory with mod.rs and stub files for missing handlers.
-    fn generate_handlers(
-        &self,
-        handlers_dir: &Path,
-        output_dir: &Path,
-        is_async: bool,
-    ) -> Result<GenerateResult> {
+    /// Write handler stubs for new commands and report unused ones.
+    fn generate_handlers(&self, handlers_dir: &Path, output_dir: &Path) -> Result<GenerateResult> {
         let mut created_handlers = Vec::new();
 
         // Collect all expected handler paths from computed data (snake_case for Rust file names)
@@ -539,24 +1222,11 @@ impl Generator {
             })
             .collect();
 
-        // Collect top-level command names
-        let top_level_names: Vec<String> = self
-            .ir
-            .operations
-            .iter()
-            .map(|op| {
-                let Operation::Command(cmd) = op;
-                cmd.name.clone()
-            })
-            .collect();
-
-        // Generate top-level handlers/mod.rs (always regenerated)
-        HandlersMod::new(top_level_names).write(output_dir)?;
-
-        // Process commands recursively
-        for op in &self.ir.operations {
-            let Operation::Command(cmd) = op;
-            self.generate_handlers_for_command(cmd, handlers_dir, is_async, &mut created_handlers)?;
+        for entry in self.handler_entries() {
+            let result = entry.write(output_dir)?;
+            if entry.overwrite() == Overwrite::IfMissing && result.is_created() {
+                created_handlers.push(entry.path.trim_start_matches("src/handlers/").to_string());
+            }
         }
 
         // Find orphan handlers using shared utility
@@ -566,57 +1236,54 @@ impl Generator {
         Ok(GenerateResult {
             created_handlers,
             orphan_handlers,
+            ..Default::default()
         })
     }
 
-    /// Recursively generate handlers for a command and its children.
-    fn generate_handlers_for_command(
-        &self,
-        cmd: &CommandOp,
-        handlers_dir: &Path,
-        is_async: bool,
-        created_handlers: &mut Vec<String>,
-    ) -> Result<()> {
-        use baobao_core::{File, WriteResult};
+    /// Handler stubs and the `mod.rs` files declaring them.
+    ///
+    /// The `mod.rs` files are always regenerated; stubs are only created.
+    fn handler_entries(&self) -> Vec<FileEntry> {
+        // Top-level handlers/mod.rs
+        let top_level_names: Vec<String> = self.ir.commands().map(|cmd| cmd.name.clone()).collect();
+        let mut entries = vec![FileEntry::from_generated(
+            "src/handlers/mod.rs",
+            &HandlersMod::new(top_level_names),
+            FileCategory::Handler,
+        )];
+
+        for cmd in self.ir.commands() {
+            self.handler_entries_for_command(cmd, &mut entries);
+        }
+        entries
+    }
 
-        let handler_path: Vec<&str> = cmd.path.iter().map(|s| s.as_str()).collect();
-        let dir = handler_path
+    /// Recursively collect handler entries for a command and its children.
+    fn handler_entries_for_command(&self, cmd: &CommandOp, entries: &mut Vec<FileEntry>) {
+        let dir = cmd
+            .path
             .iter()
-            .take(handler_path.len().saturating_sub(1))
-            .fold(handlers_dir.to_path_buf(), |acc, segment| {
-                acc.join(to_snake_case(segment))
+            .take(cmd.path.len().saturating_sub(1))
+            .fold("src/handlers".to_string(), |acc, segment| {
+                format!("{}/{}", acc, to_snake_case(segment))
             });
 
         if cmd.has_subcommands() {
-            // Parent command - create directory and mod.rs
-            let cmd_dir = dir.join(to_snake_case(&cmd.name));
-            std::fs::create_dir_all(&cmd_dir)?;
-
+            // Parent command - mod.rs declaring the subcommands
             let subcommand_names: Vec<String> =
                 cmd.children.iter().map(|c| c.name.clone()).collect();
-            let handlers_mod = HandlersMod::new(subcommand_names);
-            File::new(cmd_dir.join("mod.rs"), handlers_mod.render()).write()?;
+            entries.push(FileEntry::from_generated(
+                format!("{}/{}/mod.rs", dir, to_snake_case(&cmd.name)),
+                &HandlersMod::new(subcommand_names),
+                FileCategory::Handler,
+            ));
 
-            // Recursively process children
             for child in &cmd.children {
-                self.generate_handlers_for_command(
-                    child,
-                    handlers_dir,
-                    is_async,
-                    created_handlers,
-                )?;
+                self.handler_entries_for_command(child, entries);
             }
         } else {
-            // Leaf command - create handler stub
-            std::fs::create_dir_all(&dir)?;
-
-            let display_path = cmd
-                .path
-                .iter()
-                .map(|s| to_snake_case(s))
-                .collect::<Vec<_>>()
-                .join("/");
-            let pascal_name = to_pascal_case(&cmd.name);
+            // Leaf command - handler stub
+            let pascal_name = self.command_type_name(cmd);
 
             // Args types are in the top-level command module
             let top_level_cmd = to_snake_case(cmd.path.first().unwrap_or(&cmd.name));
@@ -625,15 +1292,20 @@ impl Generator {
                 top_level_cmd, pascal_name
             );
 
-            let stub = HandlerStub::new(&cmd.name, &args_import, is_async);
-            let result = stub.write(&dir)?;
-
-            if matches!(result, WriteResult::Written) {
-                created_handlers.push(format!("{}.rs", display_path));
-            }
+            let examples = cmd
+                .docs
+                .examples
+                .iter()
+                .map(|e| e.command.clone())
+                .collect();
+            let stub = HandlerStub::new(&cmd.name, &args_import, self.computed.is_async)
+                .with_examples(examples);
+            entries.push(FileEntry::from_generated(
+                format!("{}/{}.rs", dir, to_snake_case(&cmd.name)),
+                &stub,
+                FileCategory::Handler,
+            ));
         }
-
-        Ok(())
     }
 
     /// Generate subcommand struct from IR CommandOp.
@@ -646,37 +1318,42 @@ impl Generator {
     ) -> String {
         let await_suffix = if is_async { ".await" } else { "" };
 
-        // Parent struct with subcommand field
+        // Parent struct with subcommand field, optional when a default
+        // subcommand runs without one
+        let enum_name = format!("{}Commands", pascal_name);
+        let field_type = if cmd.default_command.is_some() {
+            format!("Option<{}>", enum_name)
+        } else {
+            enum_name.clone()
+        };
         let parent_struct = Struct::new(pascal_name)
             .doc(&cmd.description)
             .derive("Args")
             .derive("Debug")
-            .field(
-                Field::new("command", format!("{}Commands", pascal_name))
-                    .clap_attr(ClapAttr::command_subcommand()),
-            );
+            .field(Field::new("command", field_type).clap_attr(ClapAttr::command_subcommand()));
 
         // Subcommands enum
-        let mut commands_enum = Enum::new(format!("{}Commands", pascal_name))
-            .derive("Subcommand")
-            .derive("Debug");
+        let mut commands_enum = Enum::new(&enum_name).derive("Subcommand").derive("Debug");
 
         for child in &cmd.children {
             let sub_pascal = to_pascal_case(&child.name);
+            let sub_type = self.command_type_name(child);
             let data = if child.has_subcommands() {
-                sub_pascal.clone()
+                sub_type
             } else {
-                format!("{}Args", sub_pascal)
+                format!("{}Args", sub_type)
             };
-            commands_enum = commands_enum.variant(
+            let variant = child.aliases.iter().fold(
                 Variant::new(&sub_pascal)
                     .doc(&child.description)
                     .tuple(data),
+                |variant, alias| variant.clap_attr(ClapAttr::command_alias(alias)),
             );
+            commands_enum = commands_enum.variant(with_help(variant, child));
         }
 
         // Dispatch impl
-        let mut match_expr = Match::new("self.command");
+        let mut match_expr = Match::new(command_expr(&enum_name, cmd.default_command.as_deref()));
         for child in &cmd.children {
             let sub_pascal = to_pascal_case(&child.name);
             let (pattern, body) = if child.has_subcommands() {
@@ -700,7 +1377,13 @@ impl Generator {
                     ),
                 )
             };
-            match_expr = match_expr.arm(Arm::new(pattern).body(body));
+            match_expr = match_expr.arm(dispatch_arm(
+                pattern,
+                child,
+                body,
+                &self.ir.meta.name,
+                is_async,
+            ));
         }
 
         let mut dispatch = Fn::new("dispatch")
@@ -724,23 +1407,69 @@ impl Generator {
         builder.push_blank();
         builder.emit(&dispatch_impl);
         builder.push_blank();
+        if let Some(name) = &cmd.default_command {
+            builder.emit(&default_command_impl(&enum_name, name));
+            builder.push_blank();
+        }
 
         // Generate args structs for each subcommand
         for child in &cmd.children {
-            let sub_pascal = to_pascal_case(&child.name);
+            let sub_type = self.command_type_name(child);
             if child.has_subcommands() {
                 let nested_path = format!("{}::{}", handler_path, child.name);
                 builder.push_raw(&self.generate_subcommand_struct_from_ir(
                     &nested_path,
-                    &sub_pascal,
+                    &sub_type,
                     child,
                     is_async,
                 ));
             } else {
-                builder.push_raw(&self.generate_args_struct_from_ir(&sub_pascal, child));
+                builder.push_raw(&self.generate_args_struct_from_ir(&sub_type, child));
             }
         }
 
         builder.build()
     }
 }
+
+/// Base type name of every command, by path (see
+/// [`Generator::command_type_name`]).
+///
+/// Nested commands start with their own name. Any that clash, with each other
+/// or with a path-qualified name, are qualified in turn until names are unique;
+/// full paths can't clash since validation rejects paths that flatten alike.
+fn command_type_names(ir: &AppIR) -> HashMap<Vec<String>, String> {
+    fn collect<'a>(cmds: impl Iterator<Item = &'a CommandOp>, all: &mut Vec<&'a CommandOp>) {
+        for cmd in cmds {
+            all.push(cmd);
+            collect(cmd.children.iter(), all);
+        }
+    }
+
+    let mut commands = Vec::new();
+    collect(ir.commands(), &mut commands);
+
+    let mut names: HashMap<Vec<String>, String> = commands
+        .iter()
+        .map(|cmd| (cmd.path.clone(), to_pascal_case(&cmd.name)))
+        .collect();
+    loop {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for name in names.values() {
+            *counts.entry(name.as_str()).or_default() += 1;
+        }
+        let clashing: Vec<Vec<String>> = commands
+            .iter()
+            .filter(|cmd| cmd.path.len() > 1 && counts[names[&cmd.path].as_str()] > 1)
+            .filter(|cmd| names[&cmd.path] == to_pascal_case(&cmd.name))
+            .map(|cmd| cmd.path.clone())
+            .collect();
+        if clashing.is_empty() {
+            return names;
+        }
+        for path in clashing {
+            let qualified = to_pascal_case(&path.join("_"));
+            names.insert(path, qualified);
+        }
+    }
+}
@@ -47,7 +47,10 @@ pub mod adapters;
 pub mod ast;
 pub mod files;
 
-pub use adapters::{ClapAdapter, EyreAdapter, SqlxAdapter, TokioAdapter};
+pub use adapters::{
+    ClapAdapter, DuckdbAdapter, EyreAdapter, ReqwestAdapter, SqlxAdapter, TokioAdapter,
+    TonicAdapter,
+};
 pub use ast::{
     ArgAttr, Arm, ClapAttr, Enum, Field, Fn, Impl, Match, MethodChain, Param, Struct, Variant,
 };
@@ -58,4 +61,4 @@ pub use render::{RustFileBuilder, render_imports};
 pub use renderer::RustRenderer;
 pub use rust_file::{RawCode, RustFile, Use};
 pub use structure_renderer::RustStructureRenderer;
-pub use type_mapper::{RustCodeTypeMapper, RustTypeMapper};
+pub use type_mapper::{RUST_TYPES, RustCodeTypeMapper, RustTypeMapper};
@@ -51,15 +51,13 @@ impl StructureRenderer for RustStructureRenderer {
         // Doc comment
         result.push_str(&self.render_doc(&spec.doc, ""));
 
-        // Attributes
+        // Derives, then attributes, which may be derive helpers
+        result.push_str(&self.render_derives(&spec.derives));
         for attr in &spec.attributes {
             result.push_str(&self.render_attribute(attr));
             result.push('\n');
         }
 
-        // Derives
-        result.push_str(&self.render_derives(&spec.derives));
-
         // Visibility and struct declaration
         let vis = self.render_visibility(spec.visibility);
         if !vis.is_empty() {
@@ -1,12 +1,44 @@
 //! Rust type mapper implementation.
 
+use std::sync::LazyLock;
+
 #[cfg(test)]
 use baobao_codegen::builder::TypeRef;
 use baobao_codegen::{
     builder::{PrimitiveType, TypeMapper as CodeIRTypeMapper},
-    language::TypeMapper,
+    language::{ScalarType, TypeMapper, TypeRegistry},
 };
-use baobao_core::{ArgType, ContextFieldType, DatabaseType};
+use baobao_core::{ArgType, ContextFieldType, DatabaseType, QueueType};
+
+/// Rust types for each argument type. clap parses values through `FromStr`,
+/// so no parse snippets are needed.
+pub static RUST_TYPES: LazyLock<TypeRegistry> = LazyLock::new(|| {
+    TypeRegistry::new()
+        .register(ScalarType::new(ArgType::String, "String"))
+        .register(ScalarType::new(ArgType::Int, "i64"))
+        .register(ScalarType::new(ArgType::Float, "f64"))
+        .register(ScalarType::new(ArgType::Bool, "bool"))
+        .register(ScalarType::new(ArgType::Path, "std::path::PathBuf"))
+        .register(ScalarType::new(ArgType::Count, "u8"))
+        .register(ScalarType::new(ArgType::Url, "url::Url"))
+        .register(ScalarType::new(ArgType::Duration, "std::time::Duration"))
+        .register(ScalarType::new(
+            ArgType::DateTime,
+            "chrono::DateTime<chrono::Utc>",
+        ))
+        .register(ScalarType::new(ArgType::Uuid, "uuid::Uuid"))
+        .register(ScalarType::new(ArgType::Secret, "String"))
+});
+
+/// Crates providing the types of some arguments, added to Cargo.toml when
+/// an input of that type is used.
+pub const TYPE_DEPENDENCIES: &[(ArgType, &str, &str)] = &[
+    (ArgType::Url, "url", "2"),
+    (ArgType::Duration, "humantime", "2"),
+    (ArgType::DateTime, "chrono", "0.4"),
+    (ArgType::Uuid, "uuid", "1"),
+    (ArgType::Secret, "rpassword", "7"),
+];
 
 /// Rust type mapper implementation.
 pub struct RustTypeMapper;
@@ -63,13 +95,7 @@ impl TypeMapper for RustTypeMapper {
     }
 
     fn map_arg_type(&self, arg_type: ArgType) -> &'static str {
-        match arg_type {
-            ArgType::String => "String",
-            ArgType::Int => "i64",
-            ArgType::Float => "f64",
-            ArgType::Bool => "bool",
-            ArgType::Path => "std::path::PathBuf",
-        }
+        RUST_TYPES.type_name(arg_type)
     }
 
     fn map_optional_arg_type(&self, arg_type: ArgType) -> String {
@@ -81,7 +107,23 @@ impl TypeMapper for RustTypeMapper {
             ContextFieldType::Database(DatabaseType::Postgres) => "sqlx::PgPool",
             ContextFieldType::Database(DatabaseType::Mysql) => "sqlx::MySqlPool",
             ContextFieldType::Database(DatabaseType::Sqlite) => "sqlx::SqlitePool",
+            ContextFieldType::Database(DatabaseType::Duckdb) => {
+                "r2d2::Pool<duckdb::DuckdbConnectionManager>"
+            }
             ContextFieldType::Http => "reqwest::Client",
+            ContextFieldType::Logger => "tracing::Dispatch",
+            ContextFieldType::Storage => "object_store::aws::AmazonS3",
+            ContextFieldType::Grpc => "tonic::transport::Channel",
+            ContextFieldType::Email => "lettre::AsyncSmtpTransport<lettre::Tokio1Executor>",
+            ContextFieldType::Config => "Config",
+            ContextFieldType::Secrets => "Secrets",
+            ContextFieldType::Llm => "LlmClient",
+            ContextFieldType::Cache => "moka::future::Cache<String, String>",
+            ContextFieldType::Workdir => "Workdir",
+            ContextFieldType::Queue(QueueType::Nats) => "async_nats::Client",
+            ContextFieldType::Queue(QueueType::Kafka) => "rdkafka::producer::FutureProducer",
+            // Rendered by the resource adapter
+            ContextFieldType::Custom => "()",
         }
     }
 }
@@ -99,6 +141,23 @@ mod tests {
         assert_eq!(mapper.map_arg_type(ArgType::Float), "f64");
         assert_eq!(mapper.map_arg_type(ArgType::Bool), "bool");
         assert_eq!(mapper.map_arg_type(ArgType::Path), "std::path::PathBuf");
+        assert_eq!(mapper.map_arg_type(ArgType::Count), "u8");
+        assert_eq!(mapper.map_arg_type(ArgType::Url), "url::Url");
+        assert_eq!(
+            mapper.map_arg_type(ArgType::Duration),
+            "std::time::Duration"
+        );
+        assert_eq!(
+            mapper.map_arg_type(ArgType::DateTime),
+            "chrono::DateTime<chrono::Utc>"
+        );
+        assert_eq!(mapper.map_arg_type(ArgType::Uuid), "uuid::Uuid");
+        assert_eq!(mapper.map_arg_type(ArgType::Secret), "String");
+    }
+
+    #[test]
+    fn test_rust_types_complete() {
+        assert!(RUST_TYPES.missing().is_empty());
     }
 
     #[test]
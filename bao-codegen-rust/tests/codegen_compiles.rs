@@ -5,9 +5,11 @@
 
 use std::{path::Path, process::Command, str::FromStr, sync::Mutex};
 
-use baobao_codegen::pipeline::Pipeline;
+use baobao_codegen::{pipeline::Pipeline, testing::manifest_source};
 use baobao_codegen_rust::{Generator, LanguageCodegen};
+use baobao_ir::{AppIR, DefaultValue, Input, InputKind, InputType};
 use baobao_manifest::Manifest;
+use proptest::prelude::*;
 use tempfile::TempDir;
 
 /// Shared target directory for faster compilation across tests.
@@ -22,9 +24,15 @@ static CARGO_LOCK: Mutex<()> = Mutex::new(());
 
 /// Generate code from a schema and verify it compiles with `cargo check`
 fn assert_generated_code_compiles(schema_toml: &str) {
+    assert_generated_code_compiles_with(schema_toml, |_| {});
+}
+
+/// Like [`assert_generated_code_compiles`], editing the IR before generation.
+fn assert_generated_code_compiles_with(schema_toml: &str, edit: impl FnOnce(&mut AppIR)) {
     let schema = Manifest::from_str(schema_toml).expect("Failed to parse schema");
     let pipeline = Pipeline::new();
-    let ctx = pipeline.run(schema).expect("Pipeline failed");
+    let mut ctx = pipeline.run(schema).expect("Pipeline failed");
+    edit(ctx.ir.as_mut().expect("IR not set"));
     let generator = Generator::from_context(ctx);
 
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -168,6 +176,7 @@ fn test_cli_with_all_arg_types_compiles() {
 
         [commands.test.args.int_arg]
         type = "int"
+        allow_hyphen_values = true
 
         [commands.test.args.float_arg]
         type = "float"
@@ -308,48 +317,1101 @@ fn test_cli_with_default_values_compiles() {
         type = "string"
         short = "H"
         default = "localhost"
+
+        [commands.server.flags.ratio]
+        type = "float"
+        default = 4
+
+        [commands.server.flags.root]
+        type = "path"
+        default = "./public"
+
+        [commands.server.flags.label]
+        type = "string"
+        default = 42
+
+        [commands.server.args.mode]
+        type = "string"
+        default = "dev"
         "#,
     );
 }
 
 #[test]
-fn test_cli_with_http_context_compiles() {
+fn test_cli_with_multiple_values_compiles() {
     assert_generated_code_compiles(
         r#"
         [cli]
         name = "myapp"
         language = "rust"
 
-        [context.http]
-        timeout = 30
-        user_agent = "myapp/1.0"
+        [commands.add]
+        description = "Add files"
+
+        [commands.add.args.paths]
+        type = "path"
+        multiple = true
+
+        [commands.add.flags.tag]
+        type = "string"
+        short = "t"
+        multiple = true
+        default = ["latest", "stable"]
+        delimiter = ","
+
+        [commands.add.flags.level]
+        type = "string"
+        multiple = true
+        choices = ["low", "high"]
+        default = ["low"]
+
+        [commands.add.flags.retry]
+        type = "int"
+        multiple = true
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_count_flag_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.flags.verbose]
+        type = "count"
+        short = "v"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_value_constraints_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        pattern = "[a-z]+"
+        min_length = 2
+        max_length = 16
+
+        [commands.greet.flags.times]
+        type = "int"
+        min = 1
+        max = 5
+        default = 1
+
+        [commands.greet.flags.volume]
+        type = "float"
+        min = 0
+        multiple = true
+
+        [commands.greet.flags.config]
+        type = "path"
+        path_exists = true
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_input_groups_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.args.input]
+        type = "path"
+        required = false
+
+        [commands.build.flags.json]
+        type = "bool"
+
+        [commands.build.flags.yaml]
+        type = "bool"
+
+        [commands.build.flags.target]
+        type = "string"
+
+        [commands.build.groups.output]
+        members = ["json", "yaml"]
+        exclusive = true
+
+        [commands.build.groups.source]
+        members = ["input", "target"]
+        required = true
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_input_requirements_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.deploy]
+        description = "Deploy the app"
+
+        [commands.deploy.args.target]
+        type = "string"
+        required = false
+
+        [commands.deploy.flags.mode]
+        type = "string"
+        choices = ["local", "remote"]
+        default = "local"
+
+        [commands.deploy.flags.host]
+        type = "string"
+        required_if = { flag = "mode", value = "remote" }
+
+        [commands.deploy.flags.dry-run]
+        type = "bool"
+        requires = ["target", "log-file"]
+
+        [commands.deploy.flags.log-file]
+        type = "path"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_command_aliases_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.list]
+        description = "List items"
+        aliases = ["ls", "l"]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        aliases = ["m"]
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_deprecations_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.old]
+        description = "Old command"
+        deprecated = "use `new` instead"
+
+        [commands.old.flags.host]
+        type = "string"
+        deprecated = "set {HOST}"
+
+        [commands.db]
+        description = "Database commands"
+        deprecated = "use `store`"
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.db.commands.reset.flags.force]
+        type = "bool"
+        deprecated = "reset always forces"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_examples_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        examples = ["myapp deploy prod --force", "myapp deploy \"staging area\""]
+
+        [commands.deploy.args.env]
+        type = "string"
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_long_descriptions_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.db]
+        description = "Database commands"
+        long_description = "Manage the \"main\" database."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+        long_description = "Drops every table.\n\nCannot be undone."
+
+        [commands.deploy]
+        description = "Deploy the app"
+        long_description = "Builds the project and uploads it."
+
+        [commands.deploy.args.env]
+        type = "string"
+        long_description = "Any environment from deploy.toml."
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        description = "Skip confirmation"
+        long_description = "Skips the confirmation prompt."
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_url_duration_datetime_uuid_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
 
         [commands.fetch]
-        description = "Fetch data from API"
+        description = "Fetch a resource"
 
         [commands.fetch.args.url]
+        type = "url"
+
+        [commands.fetch.args.id]
+        type = "uuid"
+        required = false
+
+        [commands.fetch.flags.timeout]
+        type = "duration"
+        default = "30s"
+
+        [commands.fetch.flags.since]
+        type = "datetime"
+
+        [commands.fetch.flags.retry_after]
+        type = "duration"
+        multiple = true
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_secrets_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.login]
+        description = "Log in"
+
+        [commands.login.args.user]
         type = "string"
+        index = 1
+
+        [commands.login.args.password]
+        type = "secret"
+        description = "Password"
+
+        [commands.login.flags.api_token]
+        type = "secret"
         "#,
     );
 }
 
-// Note: Database context tests require actual database drivers.
-// Skipping them to avoid long compile times in CI.
-// Uncomment to test locally if needed.
+#[test]
+fn test_cli_with_prompt_message_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
 
-// #[test]
-// fn test_cli_with_postgres_context_compiles() {
-//     assert_generated_code_compiles(
-//         r#"
-//         [cli]
-//         name = "myapp"
-//
-//         [context.database]
-//         type = "postgres"
-//         env = "DATABASE_URL"
-//
-//         [commands.query]
-//         description = "Run a query"
-//         "#,
-//     );
-// }
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        prompt = "What is your name?"
+
+        [commands.greet.args.password]
+        type = "secret"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_value_hints_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.ssh]
+        description = "Connect to a host"
+
+        [commands.ssh.args.host]
+        type = "string"
+        hint = "hostname"
+
+        [commands.ssh.flags.identity]
+        type = "path"
+        hint = "file"
+
+        [commands.ssh.flags.workdir]
+        type = "path"
+        hint = "dir"
+
+        [commands.ssh.flags.proxy]
+        type = "url"
+        hint = "url"
+
+        [commands.ssh.flags.shell]
+        type = "string"
+        hint = "command"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_footers_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        footer = "Docs: https://example.com/myapp"
+
+        [commands.db]
+        description = "Database commands"
+        footer = "Ask \"ops\" for help."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+        examples = ["myapp db reset"]
+        footer = "Cannot be undone."
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_global_flags_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [flags.verbose]
+        type = "count"
+        short = "v"
+
+        [flags.config]
+        type = "path"
+        description = "Config file"
+
+        [commands.build]
+        description = "Build the project"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_http_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.http]
+        timeout = 30
+        user_agent = "myapp/1.0"
+
+        [commands.fetch]
+        description = "Fetch data from API"
+
+        [commands.fetch.args.url]
+        type = "string"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_storage_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.storage]
+        type = "s3"
+        bucket = "uploads"
+        region = "eu-west-1"
+        endpoint = "http://localhost:9000"
+
+        [commands.upload]
+        description = "Upload a file"
+        "#,
+    );
+}
+
+// Clients from .proto files need protoc, so only the channel is compiled
+#[test]
+fn test_cli_with_grpc_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.grpc]
+        endpoint = "https://api.example.com"
+        timeout = 10
+
+        [commands.greet]
+        description = "Greet the server"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_email_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.email]
+        host = "smtp.example.com"
+        tls = "tls"
+        username_env = "SMTP_USERNAME"
+        password_env = "SMTP_PASSWORD"
+
+        [commands.send]
+        description = "Send a notification"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_logging_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.logging]
+        level = "debug"
+        env_filter = "debug,hyper=warn"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_config_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api_url = "string"
+        timeout = { type = "float", optional = true }
+        verbose = "bool"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_keyring_secrets_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.secrets]
+        backend = "keyring"
+        prefix = "MYAPP_"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_file_secrets_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.secrets]
+        backend = "file"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_memory_cache_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.cache]
+        ttl = 60
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_disk_cache_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.cache]
+        mode = "disk"
+        ttl = 3600
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_workdir_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.workdir]
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_temp_workdir_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.workdir]
+        data = false
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_anthropic_llm_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_openai_llm_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.llm]
+        provider = "openai"
+        model = "gpt-4o"
+        max_tokens = 512
+
+        [context.http]
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+}
+
+// rdkafka builds librdkafka from source, so only the NATS client is compiled
+#[test]
+fn test_cli_with_nats_queue_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.queue]
+        type = "nats"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_named_http_clients_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        timeout = 30
+        user_agent = "myapp/1.0"
+        headers = { Accept = "application/vnd.github+json", X-GitHub-Api-Version = "2022-11-28" }
+
+        [context.http.internal]
+        timeout = 5
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_http_auth_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.http.registry]
+        headers = { Accept = "application/json" }
+        auth = { type = "basic", username = "ci", env = "REGISTRY_PASSWORD" }
+
+        [context.http.search]
+        retries = 2
+        auth = { type = "header", name = "X-Api-Key", env = "SEARCH_API_KEY" }
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_http_retries_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        retries = 3
+        backoff_ms = 100
+        retry_on = [429, 503]
+
+        [context.http.metrics]
+        retries = 2
+
+        [context.http.internal]
+        base_url = "http://localhost:8080"
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+}
+
+// Note: Database context tests require actual database drivers.
+// Skipping them to avoid long compile times in CI.
+// Uncomment to test locally if needed.
+
+// #[test]
+// fn test_cli_with_postgres_context_compiles() {
+//     assert_generated_code_compiles(
+//         r#"
+//         [cli]
+//         name = "myapp"
+//
+//         [context.database]
+//         type = "postgres"
+//         env = "DATABASE_URL"
+//
+//         [commands.query]
+//         description = "Run a query"
+//         "#,
+//     );
+// }
+
+#[test]
+fn test_cli_with_global_inputs_compiles() {
+    let flag = |name: &str, ty: InputType, short: Option<char>| Input {
+        name: name.into(),
+        ty,
+        kind: InputKind::Flag { short },
+        required: false,
+        default: None,
+        description: None,
+        choices: None,
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        constraints: Default::default(),
+        requires: vec![],
+        required_if: None,
+        hint: None,
+        long_description: None,
+        deprecated: None,
+        span: None,
+    };
+
+    assert_generated_code_compiles_with(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.http]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        "#,
+        |ir| {
+            ir.global_inputs = vec![
+                Input {
+                    description: Some("Show debug output".into()),
+                    ..flag("verbose", InputType::Bool, Some('v'))
+                },
+                Input {
+                    choices: Some(vec!["text".into(), "json".into()]),
+                    multiple: false,
+                    default: Some(DefaultValue::String("text".into())),
+                    ..flag("output-format", InputType::String, None)
+                },
+            ];
+        },
+    );
+}
+
+#[test]
+fn test_cli_with_localized_descriptions_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        description = { en = "My app", fr = "Mon app" }
+
+        [commands.db]
+        description = { en = "Database commands", fr = "Commandes de base de données" }
+
+        [commands.db.commands.run-query]
+        description = { en = "Run a query", "pt-BR" = "Executar uma consulta" }
+
+        [commands.db.commands.run-query.args.query-text]
+        type = "string"
+        description = { en = "Query to run", fr = "Requête à exécuter" }
+
+        [commands.db.commands.run-query.flags.dry-run]
+        description = { en = "Print the query", fr = "Afficher la requête" }
+        "#,
+    );
+}
+
+#[test]
+fn test_build_info_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.2.0"
+        language = "rust"
+        build_info = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+}
+
+#[test]
+fn test_nested_commands_sharing_names_compile() {
+    // Nested types share their top-level command's module, so both `sync`
+    // commands and the nested `db` need distinct type names
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.sync]
+        description = "Sync the database"
+
+        [commands.db.commands.remote]
+        description = "Remote database commands"
+
+        [commands.db.commands.remote.commands.sync]
+        description = "Sync the remote database"
+
+        [commands.db.commands.remote.commands.db]
+        description = "Remote db"
+        "#,
+    );
+}
+
+#[test]
+fn test_docs_command_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        docs_command = true
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+
+        [commands.db.commands.migrate.flags.dry_run]
+        type = "bool"
+        description = "Print without applying"
+        "#,
+    );
+}
+
+#[test]
+fn test_doctor_command_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        doctor_command = true
+
+        [context.http]
+        base_url = "https://api.example.com"
+        auth = { type = "bearer", env = "API_TOKEN" }
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.secrets]
+        backend = "file"
+
+        [commands.fetch]
+        description = "Fetch data from API"
+        "#,
+    );
+}
+
+#[test]
+fn test_external_subcommands_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        external_subcommands = true
+        doctor_command = true
+
+        [context.http]
+
+        [commands.fetch]
+        description = "Fetch data from API"
+        "#,
+    );
+}
+
+#[test]
+fn test_default_commands_compile() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        default_command = "status"
+        doctor_command = true
+
+        [context.http]
+
+        [commands.status]
+        description = "Show status"
+
+        [commands.status.flags.verbose]
+        type = "bool"
+
+        [commands.db]
+        description = "Database commands"
+        default_command = "list"
+
+        [commands.db.commands.list]
+        description = "List tables"
+
+        [commands.db.commands.list.args.schema]
+        type = "string"
+        default = "public"
+
+        [commands.db.commands.drop]
+        description = "Drop a table"
+
+        [commands.db.commands.drop.args.table]
+        type = "string"
+        "#,
+    );
+}
+
+proptest! {
+    // Each case runs `cargo check`, so keep the count low
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn test_generated_manifests_compile(schema in manifest_source()) {
+        assert_generated_code_compiles(&schema);
+    }
+}
+
+#[test]
+fn test_cli_with_lazy_async_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.cache]
+        ttl = 60
+
+        [context.logging]
+        level = "info"
+
+        [commands.fetch]
+        description = "Fetch a page"
+        requires = ["cache"]
+
+        [commands.status]
+        description = "Show the status"
+        "#,
+    );
+}
+
+#[test]
+fn test_cli_with_lazy_sync_context_compiles() {
+    assert_generated_code_compiles(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api_url = "string"
+
+        [commands.db]
+        description = "Database commands"
+        requires = ["config"]
+
+        [commands.db.commands.seed]
+        description = "Seed the database"
+        "#,
+    );
+}
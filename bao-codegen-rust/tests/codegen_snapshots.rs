@@ -7,6 +7,7 @@ use std::str::FromStr;
 
 use baobao_codegen::pipeline::Pipeline;
 use baobao_codegen_rust::{Generator, LanguageCodegen};
+use baobao_core::Overwrite;
 use baobao_manifest::Manifest;
 
 /// Generate code from a schema and return files sorted by path for deterministic snapshots.
@@ -136,8 +137,7 @@ fn test_cli_with_flags() {
     assert!(cmd_rs.contains("release: bool"));
     assert!(cmd_rs.contains("short")); // has short option
     assert!(cmd_rs.contains("jobs:")); // has jobs field
-    // Default value format may vary
-    assert!(cmd_rs.contains("4") || cmd_rs.contains("default"));
+    assert!(cmd_rs.contains("default_value_t = 4"));
 }
 
 #[test]
@@ -284,3 +284,1498 @@ fn test_context_with_http() {
     assert!(context_rs.contains("reqwest"));
     assert!(context_rs.contains("Client"));
 }
+
+#[test]
+fn test_context_with_storage() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.storage]
+        type = "s3"
+        bucket = "uploads"
+        endpoint = "http://localhost:9000"
+
+        [commands.upload]
+        description = "Upload a file"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub storage: object_store::aws::AmazonS3,"));
+    assert!(context_rs.contains("AmazonS3Builder::from_env()"));
+    assert!(context_rs.contains(".with_bucket_name(\"uploads\")"));
+    assert!(context_rs.contains(".with_allow_http(true)"));
+    assert!(!context_rs.contains(".with_region("));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("object_store = { version = \"0.12\", features = [\"aws\"] }"));
+}
+
+#[test]
+fn test_context_with_grpc() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.grpc]
+        endpoint = "https://api.example.com"
+        protos = ["proto/greeter.proto"]
+        timeout = 10
+
+        [commands.greet]
+        description = "Greet the server"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub grpc: tonic::transport::Channel,"));
+    assert!(context_rs.contains("pub async fn new() -> eyre::Result<Self>"));
+    assert!(context_rs.contains(
+        "grpc: tonic::transport::Endpoint::from_static(\"https://api.example.com\")\n                .timeout(std::time::Duration::from_secs(10))\n                .tls_config(tonic::transport::ClientTlsConfig::new().with_native_roots())?\n                .connect_lazy(),"
+    ));
+
+    let build_rs = get_file(&files, "build.rs").expect("build.rs not found");
+    assert!(build_rs.contains(
+        "    tonic_build::configure()\n        .build_server(false)\n        .include_file(\"grpc.rs\")\n        .compile_protos(&[\"proto/greeter.proto\"], &[\"proto\"])?;"
+    ));
+    assert!(!build_rs.contains("vergen"));
+
+    let grpc_rs = get_file(&files, "src/generated/grpc.rs").expect("grpc.rs not found");
+    assert!(grpc_rs.contains("include!(concat!(env!(\"OUT_DIR\"), \"/grpc.rs\"));"));
+    let mod_rs = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(mod_rs.contains("pub mod grpc;"));
+
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("tonic = { version = \"0.12\", features = [\"tls-native-roots\"] }"));
+    assert!(cargo.contains("prost = \"0.13\""));
+    assert!(cargo.contains("[build-dependencies]\ntonic-build = \"0.12\""));
+}
+
+#[test]
+fn test_context_with_grpc_without_protos() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.grpc]
+        endpoint = "http://localhost:50051"
+
+        [commands.greet]
+        description = "Greet the server"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains(
+        "grpc: tonic::transport::Endpoint::from_static(\"http://localhost:50051\")\n                .connect_lazy(),"
+    ));
+    assert!(get_file(&files, "build.rs").is_none());
+    assert!(get_file(&files, "src/generated/grpc.rs").is_none());
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("tonic = \"0.12\""));
+    assert!(!cargo.contains("prost"));
+}
+
+#[test]
+fn test_context_with_logging() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "deploy"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.logging]
+        format = "json"
+        env_filter = "info,deploy::db=debug"
+
+        [commands.run]
+        description = "Run a deployment"
+        "#,
+    );
+
+    let app_rs = get_file(&files, "src/app.rs").expect("app.rs not found");
+    assert!(app_rs.contains("pub fn run() -> eyre::Result<()> {\n    init_logging();\n"));
+    assert!(app_rs.contains(
+        "let filter = tracing_subscriber::EnvFilter::try_from_default_env()\n        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(\"info,deploy::db=debug\"));"
+    ));
+    assert!(app_rs.contains(
+        "match std::env::var(\"LOG_FORMAT\").as_deref() {\n        Ok(\"pretty\") => subscriber.init(),\n        _ => subscriber.json().init(),\n    }"
+    ));
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub logger: tracing::Dispatch,"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains(
+        "tracing-subscriber = { version = \"0.3\", features = [\"env-filter\", \"json\"] }"
+    ));
+}
+
+#[test]
+fn test_context_without_logging() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "deploy"
+        version = "1.0.0"
+        language = "rust"
+
+        [commands.run]
+        description = "Run a deployment"
+        "#,
+    );
+
+    let app_rs = get_file(&files, "src/app.rs").expect("app.rs not found");
+    assert!(!app_rs.contains("init_logging"));
+}
+
+#[test]
+fn test_context_with_email() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "notify"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.email]
+        host = "smtp.example.com"
+        port = 2525
+        username_env = "SMTP_USERNAME"
+        password_env = "SMTP_PASSWORD"
+
+        [commands.send]
+        description = "Send a notification"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub email: lettre::AsyncSmtpTransport<lettre::Tokio1Executor>,"));
+    assert!(context_rs.contains(
+        "email: lettre::AsyncSmtpTransport::<lettre::Tokio1Executor>::starttls_relay(\"smtp.example.com\")?\n                .port(2525)\n                .credentials((std::env::var(\"SMTP_USERNAME\")?, std::env::var(\"SMTP_PASSWORD\")?).into())\n                .build(),"
+    ));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("lettre = { version = \"0.11\""));
+}
+
+#[test]
+fn test_context_with_unencrypted_email() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "notify"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.email]
+        host = "localhost"
+        port = 1025
+        tls = "none"
+
+        [commands.send]
+        description = "Send a notification"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains(
+        "email: lettre::AsyncSmtpTransport::<lettre::Tokio1Executor>::builder_dangerous(\"localhost\")\n                .port(1025)\n                .build(),"
+    ));
+}
+
+#[test]
+fn test_context_with_config_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "deploy"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.config]
+        path = "deploy.yaml"
+
+        [context.config.fields]
+        api-url = "string"
+        retries = { type = "int", optional = true }
+
+        [commands.run]
+        description = "Run a deployment"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::Config;"));
+    assert!(context_rs.contains("pub config: Config,"));
+    assert!(context_rs.contains("config: Config::load()?,"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub mod config;"));
+    assert!(generated_mod.contains("pub use config::Config;"));
+    let config_rs = get_file(&files, "src/generated/config.rs").expect("config.rs not found");
+    assert!(config_rs.contains(
+        "pub struct Config {\n    #[serde(rename = \"api-url\")]\n    pub api_url: String,\n    pub retries: Option<i64>,\n}"
+    ));
+    assert!(config_rs.contains(".merge(figment::providers::Yaml::file_exact(\"deploy.yaml\"))"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("figment = { version = \"0.10\", features = [\"yaml\"] }"));
+    assert!(cargo.contains("serde = { version = \"1\", features = [\"derive\"] }"));
+}
+
+#[test]
+fn test_context_with_keyring_secrets() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "deploy"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.secrets]
+        backend = "keyring"
+        prefix = "DEPLOY_"
+
+        [commands.run]
+        description = "Run a deployment"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::Secrets;"));
+    assert!(context_rs.contains("pub secrets: Secrets,"));
+    assert!(context_rs.contains("secrets: Secrets::load()?,"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub use secrets::Secrets;"));
+    let secrets_rs = get_file(&files, "src/generated/secrets.rs").expect("secrets.rs not found");
+    assert!(secrets_rs.contains("const SERVICE: &str = \"deploy\";"));
+    assert!(secrets_rs.contains("const PREFIX: &str = \"DEPLOY_\";"));
+    assert!(secrets_rs.contains("keyring::Entry::new(SERVICE, name)"));
+    assert!(secrets_rs.contains("f.debug_struct(\"Secrets\").finish_non_exhaustive()"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("keyring = { version = \"3\""));
+}
+
+#[test]
+fn test_context_with_file_secrets() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "deploy"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.secrets]
+        backend = "file"
+        path = "deploy.env"
+
+        [commands.run]
+        description = "Run a deployment"
+        "#,
+    );
+
+    let secrets_rs = get_file(&files, "src/generated/secrets.rs").expect("secrets.rs not found");
+    assert!(secrets_rs.contains("const PATH: &str = \"deploy.env\";"));
+    assert!(secrets_rs.contains("values: std::collections::HashMap<String, String>,"));
+    assert!(!secrets_rs.contains("PREFIX"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(!cargo.contains("keyring"));
+}
+
+#[test]
+fn test_context_with_memory_cache() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "fetch"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.cache]
+        max_capacity = 500
+        ttl = 60
+
+        [commands.get]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub cache: moka::future::Cache<String, String>,"));
+    assert!(context_rs.contains(
+        "cache: moka::future::Cache::builder()\n                .max_capacity(500)\n                .time_to_live(std::time::Duration::from_millis(60000))\n                .build(),"
+    ));
+    assert!(get_file(&files, "src/generated/cache.rs").is_none());
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("moka = { version = \"0.12\", features = [\"future\"] }"));
+    assert!(!cargo.contains("dirs"));
+}
+
+#[test]
+fn test_context_with_workdir() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "site"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.workdir]
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::Workdir;"));
+    assert!(context_rs.contains("pub workdir: Workdir,"));
+    assert!(context_rs.contains("workdir: Workdir::open()?,"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub use workdir::Workdir;"));
+    let workdir_rs = get_file(&files, "src/generated/workdir.rs").expect("workdir.rs not found");
+    assert!(workdir_rs.contains(".join(\"site\")"));
+    assert!(workdir_rs.contains(".prefix(\"site-\")"));
+    assert!(workdir_rs.contains("pub fn data(&self) -> &std::path::Path {"));
+    assert!(workdir_rs.contains("pub fn temp(&self) -> &std::path::Path {"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("dirs = \"6\""));
+    assert!(cargo.contains("tempfile = \"3\""));
+}
+
+#[test]
+fn test_context_with_workdir_path() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "site"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.workdir]
+        path = ".site"
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+
+    let workdir_rs = get_file(&files, "src/generated/workdir.rs").expect("workdir.rs not found");
+    assert!(workdir_rs.contains("let data = std::path::PathBuf::from(\".site\");"));
+    assert!(!workdir_rs.contains("tempfile"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(!cargo.contains("dirs"));
+    assert!(!cargo.contains("tempfile"));
+}
+
+#[test]
+fn test_context_with_disk_cache() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "fetch"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.cache]
+        mode = "disk"
+        ttl = 3600
+
+        [commands.get]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::Cache;"));
+    assert!(context_rs.contains("pub cache: Cache,"));
+    assert!(context_rs.contains("cache: Cache::open()?,"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub use cache::Cache;"));
+    let cache_rs = get_file(&files, "src/generated/cache.rs").expect("cache.rs not found");
+    assert!(cache_rs.contains(".join(\"fetch\")"));
+    assert!(cache_rs.contains("if age > std::time::Duration::from_millis(3600000) {"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("dirs = \"6\""));
+}
+
+#[test]
+fn test_context_with_duckdb() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "report"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.database]
+        type = "duckdb"
+        path = "analytics.duckdb"
+        read_only = true
+        threads = 4
+        max_connections = 2
+
+        [commands.summary]
+        description = "Summarize events"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub db: r2d2::Pool<duckdb::DuckdbConnectionManager>,"));
+    assert!(context_rs.contains(
+        "let config = duckdb::Config::default().access_mode(duckdb::AccessMode::ReadOnly)?.threads(4)?;"
+    ));
+    assert!(context_rs.contains(
+        "let manager = duckdb::DuckdbConnectionManager::file_with_flags(\"analytics.duckdb\", config)?;"
+    ));
+    assert!(context_rs.contains(".max_size(2)\n                        .build(manager)?"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("duckdb = { version = \"1\", features = [\"bundled\", \"r2d2\"] }"));
+    assert!(cargo.contains("r2d2 = \"0.8\""));
+    assert!(!cargo.contains("sqlx"));
+}
+
+#[test]
+fn test_context_with_anthropic_llm() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "assist"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::LlmClient;"));
+    assert!(context_rs.contains("pub llm: LlmClient,"));
+    assert!(context_rs.contains("llm: LlmClient::new()?,"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub use llm::{LlmClient, Message, Role};"));
+    let llm_rs = get_file(&files, "src/generated/llm.rs").expect("llm.rs not found");
+    assert!(llm_rs.contains("const BASE_URL: &str = \"https://api.anthropic.com\";"));
+    assert!(llm_rs.contains("const API_KEY_ENV: &str = \"ANTHROPIC_API_KEY\";"));
+    assert!(llm_rs.contains("const MODEL: &str = \"claude-sonnet-4-5\";"));
+    assert!(llm_rs.contains("const MAX_TOKENS: u32 = 1024;"));
+    assert!(llm_rs.contains(".header(\"anthropic-version\", \"2023-06-01\")"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("reqwest = \"0.12\""));
+    assert!(cargo.contains("serde_json = \"1\""));
+}
+
+#[test]
+fn test_context_with_openai_llm() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "assist"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.llm]
+        provider = "openai"
+        model = "gpt-4o"
+        api_key_env = "ASSIST_KEY"
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let llm_rs = get_file(&files, "src/generated/llm.rs").expect("llm.rs not found");
+    assert!(llm_rs.contains("const API_KEY_ENV: &str = \"ASSIST_KEY\";"));
+    assert!(llm_rs.contains("format!(\"{}/chat/completions\", BASE_URL)"));
+    assert!(llm_rs.contains(".bearer_auth(&self.api_key)"));
+    assert!(!llm_rs.contains("MAX_TOKENS"));
+}
+
+#[test]
+fn test_context_with_nats_queue() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "events"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.queue]
+        type = "nats"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub queue: async_nats::Client,"));
+    assert!(
+        context_rs.contains("queue: async_nats::connect(std::env::var(\"NATS_URL\")?).await?,")
+    );
+    assert!(context_rs.contains("pub async fn new() -> eyre::Result<Self>"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("async-nats = \"0.42\""));
+}
+
+#[test]
+fn test_context_with_kafka_queue() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "events"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.queue]
+        type = "kafka"
+        env = "BROKERS"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub queue: rdkafka::producer::FutureProducer,"));
+    assert!(context_rs.contains(
+        "queue: rdkafka::ClientConfig::new()\n                .set(\"bootstrap.servers\", std::env::var(\"BROKERS\")?)\n                .create()?,"
+    ));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("rdkafka = \"0.36\""));
+}
+
+#[test]
+fn test_named_http_clients() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        timeout = 30
+        headers = { Accept = "application/vnd.github+json" }
+
+        [context.http.metrics]
+        user_agent = "api/1.0"
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("use crate::generated::HttpClient;"));
+    assert!(context_rs.contains("pub github: HttpClient,"));
+    assert!(context_rs.contains("pub metrics: reqwest::Client,"));
+    assert!(context_rs.contains(".timeout(std::time::Duration::from_secs(30))"));
+    assert!(context_rs.contains("HeaderName::from_static(\"accept\")"));
+    assert!(context_rs.contains("\"https://api.github.com\","));
+    assert!(context_rs.contains(".user_agent(\"api/1.0\")"));
+
+    let http_rs = get_file(&files, "src/generated/http.rs").expect("http.rs not found");
+    assert!(http_rs.contains("pub struct HttpClient"));
+    let generated_mod = get_file(&files, "src/generated/mod.rs").expect("mod.rs not found");
+    assert!(generated_mod.contains("pub use http::*;"));
+}
+
+#[test]
+fn test_http_client_retries() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        retries = 3
+        backoff_ms = 100
+        retry_on = [429, 503]
+
+        [context.http.metrics]
+        retries = 2
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub metrics: reqwest_middleware::ClientWithMiddleware,"));
+    assert!(context_rs.contains(
+        ".retry_bounds(std::time::Duration::from_millis(100), std::time::Duration::from_millis(800))"
+    ));
+    assert!(context_rs.contains(".build_with_max_retries(3),"));
+    assert!(context_rs.contains("crate::generated::RetryOn(&[429, 503]),"));
+    assert!(context_rs.contains("RetryTransientMiddleware::new_with_policy(\n"));
+
+    let http_rs = get_file(&files, "src/generated/http.rs").expect("http.rs not found");
+    assert!(http_rs.contains("client: reqwest_middleware::ClientWithMiddleware,"));
+    assert!(http_rs.contains("impl reqwest_retry::RetryableStrategy for RetryOn"));
+
+    let cargo_toml = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo_toml.contains("reqwest-middleware = \"0.4\""));
+    assert!(cargo_toml.contains("reqwest-retry = \"0.7\""));
+}
+
+#[test]
+fn test_http_client_auth() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "api"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.http.github]
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.http.search]
+        auth = { type = "header", name = "X-Api-Key", env = "SEARCH_API_KEY" }
+
+        [commands.sync]
+        description = "Sync repositories"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains(
+        "(reqwest::header::AUTHORIZATION, crate::generated::bearer_auth(\"GITHUB_TOKEN\")?),"
+    ));
+    assert!(context_rs.contains(
+        "(reqwest::header::HeaderName::from_static(\"x-api-key\"), crate::generated::header_auth(\"SEARCH_API_KEY\")?),"
+    ));
+
+    let http_rs = get_file(&files, "src/generated/http.rs").expect("http.rs not found");
+    assert!(http_rs.contains("pub fn bearer_auth(env: &str)"));
+    assert!(http_rs.contains("pub fn header_auth(env: &str)"));
+    assert!(!http_rs.contains("pub fn basic_auth"));
+    assert!(!http_rs.contains("pub struct HttpClient"));
+
+    let cargo_toml = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(!cargo_toml.contains("base64"));
+}
+
+#[test]
+fn test_files_include_handlers() {
+    let manifest = Manifest::from_str(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.hello]
+        description = "Say hello"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        "#,
+    )
+    .expect("Failed to parse schema");
+    let ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    let files = Generator::from_context(ctx).files().expect("files failed");
+
+    let overwrite = |path: &str| {
+        files
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap_or_else(|| panic!("{} not found", path))
+            .overwrite()
+    };
+    assert_eq!(overwrite("src/generated/cli.rs"), Overwrite::Always);
+    assert_eq!(overwrite("src/handlers/mod.rs"), Overwrite::Always);
+    assert_eq!(overwrite("src/handlers/db/mod.rs"), Overwrite::Always);
+    assert_eq!(overwrite("src/handlers/hello.rs"), Overwrite::IfMissing);
+    assert_eq!(
+        overwrite("src/handlers/db/migrate.rs"),
+        Overwrite::IfMissing
+    );
+}
+
+#[test]
+fn test_doctor_command() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        doctor_command = true
+
+        [context.database]
+        type = "postgres"
+        env = "DATABASE_URL"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let doctor_rs = get_file(&files, "src/generated/doctor.rs").expect("doctor.rs not found");
+    insta::assert_snapshot!("doctor_command", doctor_rs);
+
+    let app_rs = get_file(&files, "src/app.rs").expect("app.rs not found");
+    assert!(app_rs.contains("if let Commands::Doctor(args) = &cli.command"));
+}
+
+#[test]
+fn test_doctor_command_resources() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        doctor_command = true
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.http.internal]
+        base_url = "http://localhost:8080/v1"
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api_url = "string"
+
+        [context.secrets]
+        backend = "file"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let doctor_rs = get_file(&files, "src/generated/doctor.rs").expect("doctor.rs not found");
+    insta::assert_snapshot!("doctor_command_resources", doctor_rs);
+}
+
+#[test]
+fn test_external_subcommands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        external_subcommands = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains("#[command(allow_external_subcommands = true)]"));
+    assert!(cli_rs.contains("#[command(external_subcommand)]\n    External(Vec<String>),"));
+    assert!(cli_rs.contains(r#"let program = format!("myapp-{}", command);"#));
+
+    let app_rs = get_file(&files, "src/app.rs").expect("app.rs not found");
+    assert!(app_rs.contains(
+        "if let Commands::External(args) = &cli.command {\n        return run_external(args);\n    }\n    let ctx"
+    ));
+}
+
+#[test]
+fn test_multiple_values() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.add]
+        description = "Add files to the index"
+
+        [commands.add.args.paths]
+        type = "path"
+        multiple = true
+        description = "Files to add"
+
+        [commands.add.flags.tag]
+        type = "string"
+        short = "t"
+        multiple = true
+        default = ["latest", "stable"]
+        description = "Tags to apply"
+
+        [commands.add.flags.level]
+        type = "string"
+        multiple = true
+        choices = ["low", "high"]
+        description = "Levels to include"
+        "#,
+    );
+
+    let add_rs = get_file(&files, "src/generated/commands/add.rs").expect("add.rs not found");
+    insta::assert_snapshot!("multiple_values", add_rs);
+}
+
+#[test]
+fn test_default_commands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        default_command = "status"
+
+        [commands.status]
+        description = "Show status"
+
+        [commands.db]
+        description = "Database commands"
+        default_command = "list"
+
+        [commands.db.commands.list]
+        description = "List tables"
+
+        [commands.db.commands.drop]
+        description = "Drop a table"
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains("pub command: Option<Commands>,"));
+    assert!(cli_rs.contains("match self.command.map_or_else(Commands::default_command, Ok)? {"));
+    assert!(cli_rs.contains("let matches = command.try_get_matches_from([bin, \"status\"])?;"));
+
+    let db_rs = get_file(&files, "src/generated/commands/db.rs").expect("db.rs not found");
+    assert!(db_rs.contains("pub command: Option<DbCommands>,"));
+    assert!(db_rs.contains("impl DbCommands {"));
+    assert!(db_rs.contains("[bin, \"list\"]"));
+}
+
+#[test]
+fn test_delimiter() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.add]
+        description = "Add files to the index"
+
+        [commands.add.flags.tags]
+        type = "string"
+        multiple = true
+        delimiter = ","
+        "#,
+    );
+
+    let add_rs = get_file(&files, "src/generated/commands/add.rs").expect("add.rs not found");
+    assert!(add_rs.contains("#[arg(long, value_delimiter = ',')]"));
+}
+
+#[test]
+fn test_allow_hyphen_values() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.seek]
+        description = "Move the cursor"
+
+        [commands.seek.args.offset]
+        type = "int"
+        allow_hyphen_values = true
+        "#,
+    );
+
+    let seek_rs = get_file(&files, "src/generated/commands/seek.rs").expect("seek.rs not found");
+    assert!(seek_rs.contains("#[arg(allow_hyphen_values = true)]"));
+}
+
+#[test]
+fn test_secrets() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.login]
+        description = "Log in"
+
+        [commands.login.args.password]
+        type = "secret"
+        description = "Password"
+
+        [commands.login.flags.api-token]
+        type = "secret"
+        "#,
+    );
+
+    let login_rs = get_file(&files, "src/generated/commands/login.rs").expect("login.rs not found");
+    assert!(login_rs.contains("#[arg(default_value = \"\", hide_default_value = true)]"));
+    assert!(login_rs.contains("pub api_token: String,"));
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains("Commands::Login(mut args) => {"));
+    assert!(cli_rs.contains("std::env::var(\"MYAPP_LOGIN_API_TOKEN\")"));
+    assert!(cli_rs.contains("rpassword::prompt_password(\"Password: \")?"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("rpassword = \"7\""));
+}
+
+#[test]
+fn test_prompt_message() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        prompt = "What is your name?"
+        "#,
+    );
+
+    let globals = get_file(&files, "src/generated/globals.rs").expect("globals.rs not found");
+    assert!(globals.contains("pub no_input: bool,"));
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains("if ctx.globals.no_input {"));
+    assert!(cli_rs.contains("eyre::bail!(\"missing argument <name>\");"));
+    assert!(cli_rs.contains(".with_prompt(\"What is your name?\")"));
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains("dialoguer = \"0.11\""));
+}
+
+#[test]
+fn test_value_constraints() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        pattern = "[a-z]+"
+        min_length = 2
+        max_length = 16
+
+        [commands.greet.flags.times]
+        type = "int"
+        min = 1
+        max = 5
+        default = 1
+
+        [commands.greet.flags.volume]
+        type = "float"
+        min = 0
+        multiple = true
+
+        [commands.greet.flags.config]
+        type = "path"
+        path_exists = true
+        "#,
+    );
+
+    let greet_rs = get_file(&files, "src/generated/commands/greet.rs").expect("greet.rs not found");
+    insta::assert_snapshot!("value_constraints", greet_rs);
+}
+
+#[test]
+fn test_input_groups() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.args.input]
+        type = "path"
+        required = false
+
+        [commands.build.flags.json]
+        type = "bool"
+
+        [commands.build.flags.yaml]
+        type = "bool"
+
+        [commands.build.flags.target]
+        type = "string"
+
+        [commands.build.groups.output]
+        members = ["json", "yaml"]
+        exclusive = true
+
+        [commands.build.groups.source]
+        members = ["input", "target"]
+        required = true
+        "#,
+    );
+
+    let build_rs = get_file(&files, "src/generated/commands/build.rs").expect("build.rs not found");
+    insta::assert_snapshot!("input_groups", build_rs);
+}
+
+#[test]
+fn test_command_aliases() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.list]
+        description = "List items"
+        aliases = ["ls", "l"]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        aliases = ["m"]
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(
+        "    #[command(alias = \"ls\")]\n    #[command(alias = \"l\")]\n    List(ListArgs),"
+    ));
+    let db_rs = get_file(&files, "src/generated/commands/db.rs").expect("db.rs not found");
+    assert!(db_rs.contains("#[command(alias = \"m\")]"));
+}
+
+#[test]
+fn test_deprecated_commands_and_flags() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.old]
+        description = "Old command"
+        deprecated = "use `new` instead"
+
+        [commands.old.flags.host]
+        type = "string"
+        deprecated = "set {HOST}"
+
+        [commands.db]
+        description = "Database commands"
+        deprecated = "use `store`"
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.db.commands.reset.flags.force]
+        type = "bool"
+        deprecated = "reset always forces"
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(
+        "eprintln!(\"warning: command 'db' is deprecated: use `store`\");\n                cmd.dispatch(ctx)"
+    ));
+    assert!(cli_rs.contains(
+        "if args.host.is_some() {\n                    eprintln!(\"warning: flag '--host' is deprecated: set {{HOST}}\");"
+    ));
+    let db_rs = get_file(&files, "src/generated/commands/db.rs").expect("db.rs not found");
+    assert!(db_rs.contains(
+        "if args.force {\n                    eprintln!(\"warning: flag '--force' is deprecated: reset always forces\");"
+    ));
+}
+
+#[test]
+fn test_command_examples() {
+    let manifest = r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        examples = ["myapp deploy prod --force", "myapp deploy \"staging area\""]
+
+        [commands.deploy.args.env]
+        type = "string"
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        "#;
+    let files = generate_files(manifest);
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(
+        r#"#[command(after_help = "Examples:\n  myapp deploy prod --force\n  myapp deploy \"staging area\"")]"#
+    ));
+    let ctx = Pipeline::new()
+        .run(Manifest::from_str(manifest).expect("Failed to parse schema"))
+        .expect("Pipeline failed");
+    let entries = Generator::from_context(ctx).files().expect("files failed");
+    let handler = entries
+        .iter()
+        .find(|f| f.path == "src/handlers/deploy.rs")
+        .and_then(|f| f.content.as_text())
+        .expect("handler stub not found");
+    assert!(handler.contains(
+        "/// Examples:\n///\n/// ```text\n/// myapp deploy prod --force\n/// myapp deploy \"staging area\"\n/// ```\npub fn run"
+    ));
+}
+
+#[test]
+fn test_url_duration_datetime_uuid_types() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.fetch]
+        description = "Fetch a resource"
+
+        [commands.fetch.args.url]
+        type = "url"
+
+        [commands.fetch.flags.timeout]
+        type = "duration"
+        default = "30s"
+
+        [commands.fetch.flags.since]
+        type = "datetime"
+
+        [commands.fetch.flags.id]
+        type = "uuid"
+        multiple = true
+        "#,
+    );
+
+    let fetch = get_file(&files, "src/generated/commands/fetch.rs").expect("fetch.rs not found");
+    assert!(fetch.contains("pub url: url::Url,"));
+    assert!(fetch.contains("pub id: Vec<uuid::Uuid>,"));
+    assert!(fetch.contains("pub since: Option<chrono::DateTime<chrono::Utc>>,"));
+    assert!(fetch.contains(
+        r#"#[arg(long, value_parser = humantime::parse_duration, default_value = "30s")]"#
+    ));
+    assert!(fetch.contains("pub timeout: std::time::Duration,"));
+
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    for dep in [
+        r#"url = "2""#,
+        r#"humantime = "2""#,
+        r#"chrono = "0.4""#,
+        r#"uuid = "1""#,
+    ] {
+        assert!(cargo.contains(dep), "missing {dep}");
+    }
+}
+
+#[test]
+fn test_value_hints() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.ssh]
+        description = "Connect to a host"
+
+        [commands.ssh.args.host]
+        type = "string"
+        hint = "hostname"
+
+        [commands.ssh.flags.identity]
+        type = "path"
+        hint = "file"
+
+        [commands.ssh.flags.workdir]
+        type = "path"
+        hint = "dir"
+
+        [commands.ssh.flags.proxy]
+        type = "url"
+        hint = "url"
+
+        [commands.ssh.flags.shell]
+        type = "string"
+        hint = "command"
+        "#,
+    );
+
+    let ssh = get_file(&files, "src/generated/commands/ssh.rs").expect("ssh.rs not found");
+    assert!(ssh.contains("#[arg(value_hint = clap::ValueHint::Hostname)]\n    pub host: String,"));
+    assert!(ssh.contains("#[arg(long, value_hint = clap::ValueHint::FilePath)]"));
+    assert!(ssh.contains("#[arg(long, value_hint = clap::ValueHint::DirPath)]"));
+    assert!(ssh.contains("#[arg(long, value_hint = clap::ValueHint::Url)]"));
+    assert!(ssh.contains("#[arg(long, value_hint = clap::ValueHint::CommandName)]"));
+}
+
+#[test]
+fn test_positional_order() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.copy]
+        description = "Copy a file"
+
+        [commands.copy.args.source]
+        type = "path"
+        index = 1
+
+        [commands.copy.args.dest]
+        type = "path"
+        index = 2
+        "#,
+    );
+
+    let copy = get_file(&files, "src/generated/commands/copy.rs").expect("copy.rs not found");
+    assert!(copy.contains("pub source: std::path::PathBuf,\n    pub dest: std::path::PathBuf,"));
+}
+
+#[test]
+fn test_long_descriptions() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.db]
+        description = "Database commands"
+        long_description = "Manage the \"main\" database."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        long_description = "Builds the project and uploads it."
+
+        [commands.deploy.args.env]
+        type = "string"
+        description = "Target environment"
+        long_description = "Any environment from deploy.toml."
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        long_description = "Skips the confirmation prompt."
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(
+        r#"#[command(long_about = "Deploy the app\n\nBuilds the project and uploads it.")]"#
+    ));
+    assert!(cli_rs.contains(
+        r#"#[command(long_about = "Database commands\n\nManage the \"main\" database.")]"#
+    ));
+    let deploy = get_file(&files, "src/generated/commands/deploy.rs").expect("deploy.rs not found");
+    assert!(deploy.contains(
+        r#"#[arg(long_help = "Target environment\n\nAny environment from deploy.toml.")]"#
+    ));
+    assert!(deploy.contains(r#"#[arg(long, long_help = "Skips the confirmation prompt.")]"#));
+}
+
+#[test]
+fn test_footers() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+        footer = "Docs: https://example.com/myapp"
+
+        [commands.db]
+        description = "Database commands"
+        footer = "Ask #db for help."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+        examples = ["myapp db reset"]
+        footer = "Cannot be undone."
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(r#"#[command(after_help = "Docs: https://example.com/myapp")]"#));
+    assert!(cli_rs.contains(r#"#[command(after_help = "Ask #db for help.")]"#));
+    let db = get_file(&files, "src/generated/commands/db.rs").expect("db.rs not found");
+    assert!(db.contains(
+        r#"#[command(after_help = "Examples:\n  myapp db reset\n\nCannot be undone.")]"#
+    ));
+}
+
+#[test]
+fn test_input_requirements() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.deploy]
+        description = "Deploy the app"
+
+        [commands.deploy.args.target]
+        type = "string"
+        required = false
+
+        [commands.deploy.flags.mode]
+        type = "string"
+        choices = ["local", "remote"]
+        default = "local"
+
+        [commands.deploy.flags.host]
+        type = "string"
+        required_if = { flag = "mode", value = "remote" }
+
+        [commands.deploy.flags.dry-run]
+        type = "bool"
+        requires = ["target", "log-file"]
+
+        [commands.deploy.flags.log-file]
+        type = "path"
+        "#,
+    );
+
+    let deploy_rs =
+        get_file(&files, "src/generated/commands/deploy.rs").expect("deploy.rs not found");
+    insta::assert_snapshot!("input_requirements", deploy_rs);
+}
+
+#[test]
+fn test_count_flag() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "rust"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.flags.verbose]
+        type = "count"
+        short = "v"
+        description = "Increase verbosity"
+        "#,
+    );
+
+    let build_rs = get_file(&files, "src/generated/commands/build.rs").expect("build.rs not found");
+    assert!(build_rs.contains("#[arg(long, short = 'v', action = clap::ArgAction::Count)]"));
+    assert!(build_rs.contains("pub verbose: u8,"));
+}
+
+#[test]
+fn test_context_with_requires() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.cache]
+        ttl = 60
+
+        [context.logging]
+        level = "info"
+
+        [commands.fetch]
+        description = "Fetch a page"
+        requires = ["cache", "logging"]
+
+        [commands.status]
+        description = "Show the status"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(
+        context_rs
+            .contains("    cache: tokio::sync::OnceCell<moka::future::Cache<String, String>>,")
+    );
+    assert!(context_rs.contains(
+        "pub async fn cache(&self) -> eyre::Result<&moka::future::Cache<String, String>> {"
+    ));
+    assert!(context_rs.contains("            cache: tokio::sync::OnceCell::new(),"));
+    let app_rs = get_file(&files, "src/app.rs").expect("app.rs not found");
+    assert!(app_rs.contains("let ctx = Context::new();"));
+    let cli_rs = get_file(&files, "src/generated/cli.rs").expect("cli.rs not found");
+    assert!(cli_rs.contains(
+        "ctx.cache().await?;\n                ctx.logger().await?;\n                crate::handlers::fetch::run(ctx, args).await"
+    ));
+    assert!(
+        cli_rs.contains("Commands::Status(args) => crate::handlers::status::run(ctx, args).await,")
+    );
+    let cargo = get_file(&files, "Cargo.toml").expect("Cargo.toml not found");
+    assert!(cargo.contains(r#"features = ["rt-multi-thread", "macros", "sync"]"#));
+}
+
+#[test]
+fn test_context_with_replica() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "report"
+        version = "1.0.0"
+        language = "rust"
+
+        [context.database]
+        type = "postgres"
+        max_connections = 5
+
+        [context.database.replica]
+        env = "READER_URL"
+        max_connections = 20
+
+        [commands.summary]
+        description = "Summarize events"
+        "#,
+    );
+
+    let context_rs = get_file(&files, "src/context.rs").expect("context.rs not found");
+    assert!(context_rs.contains("pub db: sqlx::PgPool,\n    pub db_replica: sqlx::PgPool,"));
+    assert!(context_rs.contains(
+        ".max_connections(20)\n                    .connect(&std::env::var(\"READER_URL\")?).await?,"
+    ));
+}
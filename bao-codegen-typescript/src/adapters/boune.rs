@@ -8,11 +8,10 @@ use baobao_codegen::{
     builder::CodeFragment,
 };
 use baobao_core::ArgType;
-use baobao_ir::{Input, InputKind};
-use baobao_manifest::ArgType as ManifestArgType;
+use baobao_ir::{Input, InputKind, InputType};
 
 use crate::{
-    BOUNE_VERSION,
+    BOUNE_VERSION, TS_TYPES,
     ast::{ArrowFn, JsArray, JsObject},
 };
 
@@ -25,33 +24,16 @@ impl BouneAdapter {
         Self
     }
 
-    /// Convert manifest ArgType to core ArgType.
-    fn convert_manifest_arg_type(arg_type: &ManifestArgType) -> ArgType {
-        match arg_type {
-            ManifestArgType::String => ArgType::String,
-            ManifestArgType::Int => ArgType::Int,
-            ManifestArgType::Float => ArgType::Float,
-            ManifestArgType::Bool => ArgType::Bool,
-            ManifestArgType::Path => ArgType::Path,
-        }
-    }
-
     /// Build an argument object schema for boune's declarative API using manifest types.
     pub fn build_argument_schema_manifest(
         &self,
-        arg_type: &ManifestArgType,
+        arg_type: &ArgType,
         required: bool,
         default: Option<&toml::Value>,
         description: Option<&str>,
         choices: Option<&[String]>,
     ) -> JsObject {
-        self.build_argument_schema(
-            Self::convert_manifest_arg_type(arg_type),
-            required,
-            default,
-            description,
-            choices,
-        )
+        self.build_argument_schema(*arg_type, required, default, description, choices)
     }
 
     /// Build an argument object schema for boune's declarative API.
@@ -81,19 +63,13 @@ impl BouneAdapter {
     /// Build an option object schema for boune's declarative API using manifest types.
     pub fn build_option_schema_manifest(
         &self,
-        flag_type: &ManifestArgType,
+        flag_type: &ArgType,
         short: Option<char>,
         default: Option<&toml::Value>,
         description: Option<&str>,
         choices: Option<&[String]>,
     ) -> JsObject {
-        self.build_option_schema(
-            Self::convert_manifest_arg_type(flag_type),
-            short,
-            default,
-            description,
-            choices,
-        )
+        self.build_option_schema(*flag_type, short, default, description, choices)
     }
 
     /// Build an option object schema for boune's declarative API.
@@ -121,8 +97,8 @@ impl BouneAdapter {
     }
 
     /// Map manifest argument type to TypeScript boune type.
-    pub fn map_manifest_arg_type(&self, arg_type: &ManifestArgType) -> &'static str {
-        self.map_arg_type(Self::convert_manifest_arg_type(arg_type))
+    pub fn map_manifest_arg_type(&self, arg_type: &ArgType) -> &'static str {
+        self.map_arg_type(*arg_type)
     }
 
     /// Build action handler arrow function.
@@ -161,8 +137,10 @@ impl BouneAdapter {
                 "required",
                 "true",
             )
+            .raw_if(input.multiple, "variadic", "true")
             .default_value_opt("default", input.default.as_ref())
             .string_opt("description", input.description.as_deref())
+            .string_opt("longDescription", input.long_description.as_deref())
             .array_opt(
                 "choices",
                 input
@@ -184,8 +162,12 @@ impl BouneAdapter {
         JsObject::new()
             .string("type", boune_type)
             .string_opt("short", short.map(|c| c.to_string()))
+            .raw_if(input.multiple, "multiple", "true")
+            .raw_if(input.ty == InputType::Count, "count", "true")
+            .raw_if(input.ty == InputType::Count, "default", "0")
             .default_value_opt("default", input.default.as_ref())
             .string_opt("description", input.description.as_deref())
+            .string_opt("longDescription", input.long_description.as_deref())
             .array_opt(
                 "choices",
                 input
@@ -296,13 +278,7 @@ impl CliAdapter for BouneAdapter {
     }
 
     fn map_arg_type(&self, arg_type: ArgType) -> &'static str {
-        match arg_type {
-            ArgType::String => "string",
-            ArgType::Int => "number",
-            ArgType::Float => "number",
-            ArgType::Bool => "boolean",
-            ArgType::Path => "string",
-        }
+        TS_TYPES.type_name(arg_type)
     }
 
     fn map_optional_type(&self, arg_type: ArgType) -> String {
@@ -30,7 +30,7 @@ impl DatabaseAdapter for BunSqliteAdapter {
         match db_type {
             DatabaseType::Sqlite => "Database",
             // Bun currently only has native SQLite support
-            DatabaseType::Postgres | DatabaseType::Mysql => "unknown",
+            DatabaseType::Postgres | DatabaseType::Mysql | DatabaseType::Duckdb => "unknown",
         }
     }
 
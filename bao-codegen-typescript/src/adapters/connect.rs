@@ -0,0 +1,63 @@
+//! connect-es gRPC client adapter.
+
+use baobao_codegen::adapters::{Dependency, GrpcAdapter, GrpcOptions};
+
+use crate::ast::JsObject;
+
+/// connect-es adapter for gRPC client generation.
+///
+/// The context holds a gRPC transport from `@connectrpc/connect-node`;
+/// `buf generate` compiles the `.proto` files into service descriptors with
+/// protoc-gen-es.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectAdapter;
+
+impl ConnectAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl GrpcAdapter for ConnectAdapter {
+    fn name(&self) -> &'static str {
+        "connect-es"
+    }
+
+    fn dependencies(&self, options: &GrpcOptions) -> Vec<Dependency> {
+        let mut dependencies = vec![
+            Dependency::new("@connectrpc/connect", "^2.0.0"),
+            Dependency::new("@connectrpc/connect-node", "^2.0.0"),
+        ];
+        if options.has_protos() {
+            dependencies.push(Dependency::new("@bufbuild/protobuf", "^2.0.0"));
+        }
+        dependencies
+    }
+
+    fn build_dependencies(&self, options: &GrpcOptions) -> Vec<Dependency> {
+        if options.has_protos() {
+            vec![
+                Dependency::dev("@bufbuild/buf", "^1.50.0"),
+                Dependency::dev("@bufbuild/protoc-gen-es", "^2.0.0"),
+            ]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn channel_type(&self) -> &'static str {
+        "Transport"
+    }
+
+    fn channel_init(&self, options: &GrpcOptions) -> String {
+        let transport = JsObject::new()
+            .string("baseUrl", &options.endpoint)
+            .raw_opt(
+                "defaultTimeoutMs",
+                options
+                    .timeout
+                    .map(|timeout| timeout.as_millis().to_string()),
+            );
+        format!("createGrpcTransport({})", transport.build())
+    }
+}
@@ -0,0 +1,69 @@
+//! DuckDB database adapter.
+
+use baobao_codegen::{
+    adapters::{DatabaseAdapter, Dependency, ImportSpec, PoolInitInfo},
+    builder::Value,
+};
+use baobao_ir::DatabaseType;
+
+use crate::ast::JsObject;
+
+/// Version of `@duckdb/node-api` added to package.json.
+pub const DUCKDB_NODE_API_VERSION: &str = "^1.3.2-alpha.25";
+
+/// DuckDB adapter using the `@duckdb/node-api` bindings, which run on Bun.
+#[derive(Debug, Clone, Default)]
+pub struct DuckdbAdapter;
+
+impl DuckdbAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DatabaseAdapter for DuckdbAdapter {
+    fn name(&self) -> &'static str {
+        "@duckdb/node-api"
+    }
+
+    fn dependencies(&self, _db_type: DatabaseType) -> Vec<Dependency> {
+        vec![Dependency::new("@duckdb/node-api", DUCKDB_NODE_API_VERSION)]
+    }
+
+    fn pool_type(&self, _db_type: DatabaseType) -> &'static str {
+        "DuckDBConnection"
+    }
+
+    fn pool_init(&self, info: &PoolInitInfo) -> Value {
+        let duckdb = info.duckdb_config.clone().unwrap_or_default();
+        let path = match &duckdb.path {
+            Some(path) => format!("{:?}", path),
+            None => format!("process.env.{} ?? \":memory:\"", info.env_var),
+        };
+
+        // DuckDB takes every setting as a string
+        let access_mode = duckdb.read_only.map(
+            |read_only| {
+                if read_only { "READ_ONLY" } else { "READ_WRITE" }
+            },
+        );
+        let options = JsObject::new()
+            .string_opt("access_mode", access_mode)
+            .string_opt("threads", duckdb.threads.map(|threads| threads.to_string()));
+        let create = if options.is_empty() {
+            format!("DuckDBInstance.create({})", path)
+        } else {
+            format!("DuckDBInstance.create({}, {})", path, options.build())
+        };
+
+        Value::ident(format!("await (await {}).connect()", create))
+    }
+
+    fn imports(&self, _db_type: DatabaseType) -> Vec<ImportSpec> {
+        vec![ImportSpec::new("@duckdb/node-api").symbol("DuckDBInstance")]
+    }
+
+    fn requires_async(&self, _db_type: DatabaseType) -> bool {
+        true
+    }
+}
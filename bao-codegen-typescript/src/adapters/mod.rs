@@ -1,9 +1,17 @@
 //! Adapter implementations for TypeScript code generation.
 //!
 //! This module provides concrete implementations of the adapter traits
-//! for TypeScript-specific frameworks: boune and bun:sqlite.
+//! for TypeScript-specific frameworks: boune, bun:sqlite, @duckdb/node-api and
+//! connect-es.
 
 mod boune;
 mod bun_sqlite;
+mod connect;
+mod duckdb;
 
-pub use self::{boune::BouneAdapter, bun_sqlite::BunSqliteAdapter};
+pub use self::{
+    boune::BouneAdapter,
+    bun_sqlite::BunSqliteAdapter,
+    connect::ConnectAdapter,
+    duckdb::{DUCKDB_NODE_API_VERSION, DuckdbAdapter},
+};
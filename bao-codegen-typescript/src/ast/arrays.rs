@@ -67,7 +67,7 @@ impl JsArray {
             .elements
             .iter()
             .map(|e| match e {
-                ArrayElement::String(s) => format!("\"{}\"", s),
+                ArrayElement::String(s) => format!("{:?}", s),
                 ArrayElement::Raw(s) => s.clone(),
             })
             .collect::<Vec<_>>()
@@ -97,6 +97,12 @@ mod tests {
         assert_eq!(arr, "[\"foo\", \"bar\"]");
     }
 
+    #[test]
+    fn test_string_array_escapes() {
+        let arr = JsArray::new().string(r#"say "hi""#).build();
+        assert_eq!(arr, r#"["say \"hi\""]"#);
+    }
+
     #[test]
     fn test_raw_array() {
         let arr = JsArray::new().raw("42").raw("true").build();
@@ -1,6 +1,7 @@
 //! TypeScript export builder.
 
 use baobao_codegen::builder::{CodeBuilder, CodeFragment, Renderable};
+use baobao_manifest::ModuleFormat;
 
 /// Builder for TypeScript export statements.
 #[derive(Debug, Clone)]
@@ -9,6 +10,7 @@ pub struct Export {
     default: Option<String>,
     named: Vec<String>,
     type_only: bool,
+    format: ModuleFormat,
 }
 
 impl Export {
@@ -18,6 +20,7 @@ impl Export {
             default: None,
             named: Vec::new(),
             type_only: false,
+            format: ModuleFormat::default(),
         }
     }
 
@@ -45,40 +48,19 @@ impl Export {
         self
     }
 
+    /// Set the module format the export is rendered for.
+    ///
+    /// In CommonJS, a default export is rendered as `export = x`.
+    pub fn with_format(mut self, format: ModuleFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Render the export to a CodeBuilder.
     pub fn render(&self, builder: CodeBuilder) -> CodeBuilder {
-        let type_kw = if self.type_only { "type " } else { "" };
-
-        let export_str = match (&self.from, &self.default, self.named.is_empty()) {
-            // Re-export all: export * from "module"
-            (Some(from), None, true) => {
-                format!("export * from \"{}\";", from)
-            }
-            // Re-export named: export { a, b } from "module"
-            (Some(from), None, false) => {
-                format!(
-                    "export {}{{ {} }} from \"{}\";",
-                    type_kw,
-                    self.named.join(", "),
-                    from
-                )
-            }
-            // Export default: export default foo
-            (None, Some(def), true) => {
-                format!("export default {};", def)
-            }
-            // Export named: export { a, b }
-            (None, None, false) => {
-                format!("export {}{{ {} }};", type_kw, self.named.join(", "))
-            }
-            // Invalid combinations - return empty
-            _ => String::new(),
-        };
-
-        if export_str.is_empty() {
-            builder
-        } else {
-            builder.line(&export_str)
+        match self.format_export() {
+            Some(export_str) => builder.line(&export_str),
+            None => builder,
         }
     }
 
@@ -101,7 +83,10 @@ impl Export {
                 self.named.join(", "),
                 from
             )),
-            // Export default: export default foo
+            // Export default: export = foo (CommonJS) or export default foo
+            (None, Some(def), true) if self.format == ModuleFormat::Cjs => {
+                Some(format!("export = {};", def))
+            }
             (None, Some(def), true) => Some(format!("export default {};", def)),
             // Export named: export { a, b }
             (None, None, false) => Some(format!(
@@ -140,6 +125,15 @@ mod tests {
         assert_eq!(e, "export default Foo;\n");
     }
 
+    #[test]
+    fn test_cjs_export_default() {
+        let e = Export::new()
+            .default("Foo")
+            .with_format(ModuleFormat::Cjs)
+            .build();
+        assert_eq!(e, "export = Foo;\n");
+    }
+
     #[test]
     fn test_export_named() {
         let e = Export::new().named("foo").named("bar").build();
@@ -1,6 +1,7 @@
 //! TypeScript import builder.
 
 use baobao_codegen::builder::{CodeBuilder, CodeFragment, Renderable};
+use baobao_manifest::{ImportExtension, ModuleFormat};
 
 /// A named import item that can be either a value or type import.
 #[derive(Debug, Clone)]
@@ -16,6 +17,7 @@ pub struct Import {
     default: Option<String>,
     named: Vec<NamedImport>,
     type_only: bool,
+    format: ModuleFormat,
 }
 
 impl Import {
@@ -25,6 +27,7 @@ impl Import {
             default: None,
             named: Vec::new(),
             type_only: false,
+            format: ModuleFormat::default(),
         }
     }
 
@@ -58,6 +61,26 @@ impl Import {
         self
     }
 
+    /// Set the module format the import is rendered for.
+    ///
+    /// In CommonJS, a lone default import is rendered as
+    /// `import x = require("...")` so it doesn't depend on interop helpers.
+    pub fn with_format(mut self, format: ModuleFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Rewrite the `.ts` extension of a relative specifier.
+    ///
+    /// Package imports (e.g. `"boune"`) are left untouched.
+    pub fn with_extension(mut self, extension: ImportExtension) -> Self {
+        let is_relative = self.from.starts_with("./") || self.from.starts_with("../");
+        if let Some(stem) = self.from.strip_suffix(".ts").filter(|_| is_relative) {
+            self.from = format!("{}{}", stem, extension.as_str());
+        }
+        self
+    }
+
     fn format_named_imports(&self) -> String {
         self.named
             .iter()
@@ -74,31 +97,7 @@ impl Import {
 
     /// Render the import to a CodeBuilder.
     pub fn render(&self, builder: CodeBuilder) -> CodeBuilder {
-        let type_kw = if self.type_only { "type " } else { "" };
-        let named_str = self.format_named_imports();
-
-        let import_str = match (&self.default, self.named.is_empty()) {
-            (Some(def), true) => {
-                format!("import {}{} from \"{}\";", type_kw, def, self.from)
-            }
-            (Some(def), false) => {
-                format!(
-                    "import {}{}, {{ {} }} from \"{}\";",
-                    type_kw, def, named_str, self.from
-                )
-            }
-            (None, false) => {
-                format!(
-                    "import {}{{ {} }} from \"{}\";",
-                    type_kw, named_str, self.from
-                )
-            }
-            (None, true) => {
-                format!("import \"{}\";", self.from)
-            }
-        };
-
-        builder.line(&import_str)
+        builder.line(&self.format_import())
     }
 
     /// Build the import as a string.
@@ -112,6 +111,9 @@ impl Import {
         let named_str = self.format_named_imports();
 
         match (&self.default, self.named.is_empty()) {
+            (Some(def), true) if self.format == ModuleFormat::Cjs && !self.type_only => {
+                format!("import {} = require(\"{}\");", def, self.from)
+            }
             (Some(def), true) => {
                 format!("import {}{} from \"{}\";", type_kw, def, self.from)
             }
@@ -172,6 +174,42 @@ mod tests {
         assert_eq!(i, "import type { Config } from \"./types\";\n");
     }
 
+    #[test]
+    fn test_with_extension() {
+        let js = Import::new("./cli.ts")
+            .named("app")
+            .with_extension(ImportExtension::Js)
+            .build();
+        assert_eq!(js, "import { app } from \"./cli.js\";\n");
+
+        let none = Import::new("../handlers/db.ts")
+            .named("run")
+            .with_extension(ImportExtension::None)
+            .build();
+        assert_eq!(none, "import { run } from \"../handlers/db\";\n");
+
+        let package = Import::new("boune")
+            .named("defineCli")
+            .with_extension(ImportExtension::None)
+            .build();
+        assert_eq!(package, "import { defineCli } from \"boune\";\n");
+    }
+
+    #[test]
+    fn test_cjs_default_import() {
+        let cjs = Import::new("picocolors")
+            .default("pc")
+            .with_format(ModuleFormat::Cjs)
+            .build();
+        assert_eq!(cjs, "import pc = require(\"picocolors\");\n");
+
+        let named = Import::new("boune")
+            .named("defineCli")
+            .with_format(ModuleFormat::Cjs)
+            .build();
+        assert_eq!(named, "import { defineCli } from \"boune\";\n");
+    }
+
     #[test]
     fn test_side_effect_import() {
         let i = Import::new("./polyfill").build();
@@ -24,6 +24,8 @@ pub enum PropertyValue {
     ArrowFn(ArrowFn),
     /// An array literal.
     Array(JsArray),
+    /// A spread of another object (`...key`); the value is unused.
+    Spread,
 }
 
 impl Property {
@@ -75,6 +77,14 @@ impl Property {
             value: PropertyValue::Raw(n),
         }
     }
+
+    /// Create a spread of another object (`...name`).
+    pub fn spread(name: impl Into<String>) -> Self {
+        Self {
+            key: name.into(),
+            value: PropertyValue::Spread,
+        }
+    }
 }
 
 /// An arrow function for use as a property value.
@@ -174,14 +184,28 @@ impl JsObject {
     ///
     /// - Strings are quoted
     /// - Numbers and booleans are raw
-    /// - Other types are ignored
+    /// - Arrays and tables become array and object literals
+    /// - Datetimes are ignored
     pub fn toml(self, key: impl Into<String>, value: &toml::Value) -> Self {
         match value {
             toml::Value::String(s) => self.string(key, s),
             toml::Value::Integer(i) => self.raw(key, i.to_string()),
             toml::Value::Float(f) => self.raw(key, f.to_string()),
             toml::Value::Boolean(b) => self.raw(key, b.to_string()),
-            _ => self,
+            toml::Value::Array(items) => self.array(
+                key,
+                items.iter().fold(JsArray::new(), |arr, item| match item {
+                    toml::Value::String(s) => arr.string(s),
+                    other => arr.raw(toml_literal(other)),
+                }),
+            ),
+            toml::Value::Table(table) => self.object(
+                key,
+                table
+                    .iter()
+                    .fold(JsObject::new(), |obj, (k, v)| obj.toml(k, v)),
+            ),
+            toml::Value::Datetime(_) => self,
         }
     }
 
@@ -196,10 +220,27 @@ impl JsObject {
     /// Add an IR DefaultValue property, quoting strings appropriately.
     pub fn default_value(self, key: impl Into<String>, value: &baobao_ir::DefaultValue) -> Self {
         match value {
-            baobao_ir::DefaultValue::String(s) => self.string(key, s),
+            baobao_ir::DefaultValue::String(s) | baobao_ir::DefaultValue::Path(s) => {
+                self.string(key, s)
+            }
             baobao_ir::DefaultValue::Int(i) => self.raw(key, i.to_string()),
             baobao_ir::DefaultValue::Float(f) => self.raw(key, f.to_string()),
             baobao_ir::DefaultValue::Bool(b) => self.raw(key, b.to_string()),
+            baobao_ir::DefaultValue::List(items) => self.array(
+                key,
+                items.iter().fold(JsArray::new(), |arr, item| match item {
+                    baobao_ir::DefaultValue::String(s) | baobao_ir::DefaultValue::Path(s) => {
+                        arr.string(s)
+                    }
+                    other => arr.raw(default_literal(other)),
+                }),
+            ),
+            baobao_ir::DefaultValue::Table(entries) => self.object(
+                key,
+                entries
+                    .iter()
+                    .fold(JsObject::new(), |obj, (k, v)| obj.default_value(k, v)),
+            ),
         }
     }
 
@@ -215,6 +256,12 @@ impl JsObject {
         }
     }
 
+    /// Spread another object into this one (`...name`).
+    pub fn spread(mut self, name: impl Into<String>) -> Self {
+        self.properties.push(Property::spread(name));
+        self
+    }
+
     /// Add a shorthand property where key equals the variable name.
     pub fn shorthand(mut self, name: impl Into<String>) -> Self {
         self.properties.push(Property::shorthand(name));
@@ -289,7 +336,7 @@ impl JsObject {
         self.properties
             .iter()
             .fold(builder, |b, prop| match &prop.value {
-                PropertyValue::String(s) => b.line(&format!("{}: \"{}\",", prop.key, s)),
+                PropertyValue::String(s) => b.line(&format!("{}: {:?},", prop.key, s)),
                 PropertyValue::Raw(s) => b.line(&format!("{}: {},", prop.key, s)),
                 PropertyValue::Object(obj) => {
                     let b = b.line(&format!("{}: {{", prop.key)).indent();
@@ -307,6 +354,7 @@ impl JsObject {
                     b.dedent().line("},")
                 }
                 PropertyValue::Array(arr) => b.line(&format!("{}: {},", prop.key, arr.build())),
+                PropertyValue::Spread => b.line(&format!("...{},", prop.key)),
             })
     }
 
@@ -320,7 +368,7 @@ impl JsObject {
         self.properties
             .iter()
             .map(|prop| match &prop.value {
-                PropertyValue::String(s) => CodeFragment::Line(format!("{}: \"{}\",", prop.key, s)),
+                PropertyValue::String(s) => CodeFragment::Line(format!("{}: {:?},", prop.key, s)),
                 PropertyValue::Raw(s) => CodeFragment::Line(format!("{}: {},", prop.key, s)),
                 PropertyValue::Object(obj) => {
                     let body = obj.properties_to_fragments();
@@ -347,11 +395,52 @@ impl JsObject {
                 PropertyValue::Array(arr) => {
                     CodeFragment::Line(format!("{}: {},", prop.key, arr.build()))
                 }
+                PropertyValue::Spread => CodeFragment::Line(format!("...{},", prop.key)),
             })
             .collect()
     }
 }
 
+/// Inline TypeScript literal for a TOML value nested inside an array.
+fn toml_literal(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => format!("\"{}\"", s),
+        toml::Value::Array(items) => {
+            let items: Vec<_> = items.iter().map(toml_literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        toml::Value::Table(table) => {
+            let entries: Vec<_> = table
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k, toml_literal(v)))
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Inline TypeScript literal for a default value nested inside an array.
+fn default_literal(value: &baobao_ir::DefaultValue) -> String {
+    match value {
+        baobao_ir::DefaultValue::String(s) | baobao_ir::DefaultValue::Path(s) => {
+            format!("\"{}\"", s)
+        }
+        baobao_ir::DefaultValue::List(items) => {
+            let items: Vec<_> = items.iter().map(default_literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        baobao_ir::DefaultValue::Table(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k, default_literal(v)))
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        other => other.to_code_string(),
+    }
+}
+
 impl Renderable for JsObject {
     fn to_fragments(&self) -> Vec<CodeFragment> {
         if self.properties.is_empty() {
@@ -382,6 +471,14 @@ mod tests {
         assert!(obj.contains("name: \"myapp\","));
     }
 
+    #[test]
+    fn test_object_string_escapes() {
+        let obj = JsObject::new()
+            .string("description", "Say \"hi\"\n\nThen leave")
+            .build();
+        assert!(obj.contains(r#"description: "Say \"hi\"\n\nThen leave","#));
+    }
+
     #[test]
     fn test_object_with_raw() {
         let obj = JsObject::new().raw("count", "42").build();
@@ -394,6 +491,15 @@ mod tests {
         assert!(obj.contains("helloCommand: helloCommand,"));
     }
 
+    #[test]
+    fn test_object_with_spread() {
+        let obj = JsObject::new()
+            .spread("parentOptions")
+            .raw("count", "42")
+            .build();
+        assert!(obj.contains("...parentOptions,\n  count: 42,"));
+    }
+
     #[test]
     fn test_nested_object() {
         let inner = JsObject::new().raw("foo", "fooCommand");
@@ -418,6 +524,38 @@ mod tests {
         assert!(!obj.contains("missing"));
     }
 
+    #[test]
+    fn test_toml_array_and_table() {
+        let value: toml::Table = toml::from_str(
+            r#"
+            tags = ["a", "b"]
+            env = { region = "eu", replicas = 3 }
+            "#,
+        )
+        .unwrap();
+        let obj = JsObject::new()
+            .toml("tags", &value["tags"])
+            .toml("env", &value["env"])
+            .build();
+        assert!(obj.contains("tags: [\"a\", \"b\"],"));
+        assert!(obj.contains("env: {"));
+        assert!(obj.contains("region: \"eu\","));
+        assert!(obj.contains("replicas: 3,"));
+    }
+
+    #[test]
+    fn test_default_value_list() {
+        use baobao_ir::DefaultValue;
+
+        let obj = JsObject::new()
+            .default_value(
+                "default",
+                &DefaultValue::List(vec![DefaultValue::Int(80), DefaultValue::Int(443)]),
+            )
+            .build();
+        assert!(obj.contains("default: [80, 443],"));
+    }
+
     #[test]
     fn test_arrow_fn() {
         let func = ArrowFn::new("{ args, options }")
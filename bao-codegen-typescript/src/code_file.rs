@@ -6,6 +6,7 @@
 use std::any::Any;
 
 use baobao_codegen::builder::{CodeBuilder, CodeFragment, Indent, Renderable};
+use baobao_manifest::{ImportExtension, ModuleFormat};
 
 use crate::ast::{Export, Import};
 
@@ -52,6 +53,8 @@ impl Renderable for Shebang {
 pub struct CodeFile {
     shebang: Option<Shebang>,
     imports: Vec<Import>,
+    import_extension: ImportExtension,
+    module_format: ModuleFormat,
     body: Vec<Vec<CodeFragment>>,
     exports: Vec<Export>,
 }
@@ -74,6 +77,18 @@ impl CodeFile {
         self
     }
 
+    /// Set the extension used for relative import specifiers.
+    pub fn import_extension(mut self, extension: ImportExtension) -> Self {
+        self.import_extension = extension;
+        self
+    }
+
+    /// Set the module format imports and exports are rendered for.
+    pub fn module_format(mut self, format: ModuleFormat) -> Self {
+        self.module_format = format;
+        self
+    }
+
     /// Add imports from an iterator.
     pub fn imports(mut self, imports: impl IntoIterator<Item = Import>) -> Self {
         self.imports.extend(imports);
@@ -136,7 +151,11 @@ impl CodeFile {
 
         // 3. Render imports
         for import in &self.imports {
-            builder.emit(import);
+            let import = import
+                .clone()
+                .with_extension(self.import_extension)
+                .with_format(self.module_format);
+            builder.emit(&import);
         }
 
         // 4. Blank line between imports and body
@@ -161,7 +180,7 @@ impl CodeFile {
 
         // 7. Render exports
         for export in &self.exports {
-            builder.emit(export);
+            builder.emit(&export.clone().with_format(self.module_format));
         }
 
         builder.build()
@@ -0,0 +1,48 @@
+//! buf.gen.yaml generator for the `.proto` files of `[context.grpc]`.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+/// Directory that protoc-gen-es writes the service descriptors to.
+pub const GRPC_OUT_DIR: &str = "src/gen";
+
+/// The buf.gen.yaml file compiling the `.proto` files under the include
+/// directories with protoc-gen-es, run by the `generate` script.
+pub struct BufGenYaml {
+    pub includes: Vec<String>,
+}
+
+impl BufGenYaml {
+    pub fn new(includes: Vec<String>) -> Self {
+        Self { includes }
+    }
+}
+
+impl GeneratedFile for BufGenYaml {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("buf.gen.yaml")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        let inputs: String = self
+            .includes
+            .iter()
+            .map(|include| format!("  - directory: {}\n", include))
+            .collect();
+        format!(
+            r#"version: v2
+inputs:
+{}plugins:
+  - local: protoc-gen-es
+    out: {}
+    opt: target=ts
+"#,
+            inputs, GRPC_OUT_DIR
+        )
+    }
+}
@@ -0,0 +1,61 @@
+//! build-info.ts generator for the `--version` build metadata.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::code_file::{CodeFile, RawCode};
+
+/// `bun build` flags defining the constants read by build-info.ts.
+///
+/// The values are JSON strings, escaped for the shell and then for JSON.
+pub const BUILD_INFO_DEFINES: &str = r#"--define BAO_BUILD_COMMIT=\\\"$(git rev-parse --short HEAD)\\\" --define BAO_BUILD_DATE=\\\"$(date -u +%Y-%m-%dT%H:%M:%SZ)\\\""#;
+
+/// The build-info.ts file exporting the version shown by `--version`.
+///
+/// The commit and build time are constants that the package.json `build`
+/// script injects with `bun build --define`; they read `unknown` when the
+/// sources run directly or outside a git checkout.
+pub struct BuildInfoTs {
+    pub version: String,
+}
+
+impl BuildInfoTs {
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        format!(
+            r#"declare const BAO_BUILD_COMMIT: string | undefined;
+declare const BAO_BUILD_DATE: string | undefined;
+
+const commit = (typeof BAO_BUILD_COMMIT === "string" && BAO_BUILD_COMMIT) || "unknown";
+const built = (typeof BAO_BUILD_DATE === "string" && BAO_BUILD_DATE) || "unknown";
+
+/** Version with the commit and build time, shown by `--version`. */
+export const version = `{} (commit ${{commit}}, built ${{built}})`;"#,
+            self.version
+        )
+    }
+}
+
+impl GeneratedFile for BuildInfoTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("build-info.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .add(RawCode::new(self.body()))
+            .render()
+    }
+}
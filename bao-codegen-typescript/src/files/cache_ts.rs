@@ -0,0 +1,208 @@
+//! cache.ts generator for the `[context.cache]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{CacheMode, CacheOptions};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+const INTERFACE: &str = r#"export interface Cache {
+  /** The value of `key`, or `undefined` if it is missing or expired. */
+  get(key: string): string | undefined;
+  /** Store `value` under `key`. */
+  set(key: string, value: string): void;
+  /** Remove `key`. */
+  delete(key: string): void;
+}"#;
+
+const ENTRIES: &str = r#"interface Entry {
+  value: string;
+  expires: number;
+}
+
+// Map iterates in insertion order, so the first key is the least recently used
+const entries = new Map<string, Entry>();
+
+function lookup(key: string): string | undefined {
+  const entry = entries.get(key);
+  if (!entry) {
+    return undefined;
+  }
+  entries.delete(key);
+  if (entry.expires <= Date.now()) {
+    return undefined;
+  }
+  entries.set(key, entry);
+  return entry.value;
+}
+
+function store(key: string, value: string, expires: number): void {
+  entries.delete(key);
+  entries.set(key, { value, expires });
+  if (entries.size > MAX_CAPACITY) {
+    entries.delete(entries.keys().next().value!);
+  }
+}"#;
+
+const MEMORY_EXPORT: &str = r#"export const cache: Cache = {
+  get: lookup,
+  set(key, value) {
+    store(key, value, Date.now() + TTL_MS);
+  },
+  delete(key) {
+    entries.delete(key);
+  },
+};"#;
+
+const DISK_EXPORT: &str = r#"/** The file of `key`, named by its hash so any key is a valid name. */
+function file(key: string): string {
+  return join(DIR, createHash("sha256").update(key).digest("hex"));
+}
+
+export const cache: Cache = {
+  get(key) {
+    const value = lookup(key);
+    if (value !== undefined) {
+      return value;
+    }
+    const path = file(key);
+    if (!existsSync(path)) {
+      return undefined;
+    }
+    const expires = statSync(path).mtimeMs + TTL_MS;
+    const text = readFileSync(path, "utf8");
+    // Files start with their key, so a hash collision reads as a miss
+    if (expires <= Date.now() || !text.startsWith(`${key}\n`)) {
+      return undefined;
+    }
+    const stored = text.slice(key.length + 1);
+    store(key, stored, expires);
+    return stored;
+  },
+  set(key, value) {
+    mkdirSync(DIR, { recursive: true });
+    writeFileSync(file(key), `${key}\n${value}`);
+    store(key, value, Date.now() + TTL_MS);
+  },
+  delete(key) {
+    entries.delete(key);
+    rmSync(file(key), { force: true });
+  },
+};"#;
+
+/// The user cache directory, as Go's `os.UserCacheDir` resolves it.
+const USER_CACHE_DIR: &str = r#"function userCacheDir(): string {
+  if (process.platform === "win32") {
+    return process.env.LOCALAPPDATA ?? join(homedir(), "AppData", "Local");
+  }
+  if (process.platform === "darwin") {
+    return join(homedir(), "Library", "Caches");
+  }
+  return process.env.XDG_CACHE_HOME || join(homedir(), ".cache");
+}"#;
+
+/// The cache.ts file with the `cache` map.
+///
+/// Values are strings in both modes; disk caches also write each entry to a
+/// file and read it back when it isn't in memory, keeping its original expiry.
+pub struct CacheTs {
+    pub options: CacheOptions,
+}
+
+impl CacheTs {
+    pub fn new(options: CacheOptions) -> Self {
+        Self { options }
+    }
+
+    fn is_disk(&self) -> bool {
+        self.options.mode == CacheMode::Disk
+    }
+
+    fn imports(&self) -> Vec<Import> {
+        if !self.is_disk() {
+            return Vec::new();
+        }
+        let mut imports = vec![
+            Import::new("node:crypto").named("createHash"),
+            Import::new("node:fs")
+                .named("existsSync")
+                .named("mkdirSync")
+                .named("readFileSync")
+                .named("rmSync")
+                .named("statSync")
+                .named("writeFileSync"),
+        ];
+        if self.options.path.is_none() {
+            imports.push(Import::new("node:os").named("homedir"));
+        }
+        imports.push(Import::new("node:path").named("join"));
+        imports
+    }
+
+    fn consts(&self) -> String {
+        let ttl = match self.options.ttl {
+            Some(ttl) => ttl.as_millis().to_string(),
+            None => "Infinity".to_string(),
+        };
+        let mut consts = vec![
+            format!("const MAX_CAPACITY = {};", self.options.max_capacity),
+            format!("const TTL_MS = {};", ttl),
+        ];
+        if self.is_disk() {
+            consts.push(match &self.options.path {
+                Some(path) => format!("const DIR = {:?};", path),
+                None => format!(
+                    "const DIR = join(userCacheDir(), {:?});",
+                    self.options.namespace
+                ),
+            });
+        }
+        consts.join("\n")
+    }
+
+    fn interface(&self) -> String {
+        let doc = if self.is_disk() {
+            "String values kept in memory and in files that outlive the invocation"
+        } else {
+            "String values kept in memory for this invocation"
+        };
+        format!(
+            "/** {}, evicting the least recently used past `MAX_CAPACITY`. Also exposed as `ctx.cache`. */\n{}",
+            doc, INTERFACE
+        )
+    }
+}
+
+impl GeneratedFile for CacheTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("cache.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(self.imports());
+        if self.is_disk() && self.options.path.is_none() {
+            file = file.add(RawCode::new(USER_CACHE_DIR));
+        }
+        file = file
+            .add(RawCode::new(self.consts()))
+            .add(RawCode::new(self.interface()))
+            .add(RawCode::new(ENTRIES));
+        let export = if self.is_disk() {
+            DISK_EXPORT
+        } else {
+            MEMORY_EXPORT
+        };
+        file.add(RawCode::new(export)).render()
+    }
+}
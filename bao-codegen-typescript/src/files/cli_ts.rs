@@ -4,10 +4,14 @@ use std::path::{Path, PathBuf};
 
 use baobao_core::{FileRules, GeneratedFile, Version, to_camel_case, to_kebab_case};
 use baobao_ir::CommandOp;
+use baobao_manifest::ImportExtension;
 
-use super::GENERATED_HEADER;
+use super::{
+    COMPLETIONS_COMMAND, DOCS_COMMAND, DOCTOR_COMMAND, GENERATED_HEADER, MIGRATE_COMMAND,
+    message_key, translate,
+};
 use crate::{
-    ast::{Const, Import, JsObject},
+    ast::{Const, Import, JsArray, JsObject},
     code_file::{CodeFile, RawCode},
 };
 
@@ -17,6 +21,27 @@ pub struct CliTs {
     pub version: Version,
     pub description: Option<String>,
     pub commands: Vec<CommandOp>,
+    pub completions: bool,
+    pub docs: bool,
+    pub doctor: bool,
+    pub migrate: bool,
+    pub logging: bool,
+    pub import_extension: ImportExtension,
+    /// Application-defined global options, keyed by option name.
+    pub global_options: Vec<(String, JsObject)>,
+    /// Whether the description is translated through locale.ts.
+    pub locales: bool,
+    /// Whether the version comes from build-info.ts.
+    pub build_info: bool,
+    /// Text shown at the end of the help.
+    pub footer: Option<String>,
+    /// Whether unknown commands run `<name>-<command>` from PATH.
+    pub external: bool,
+    /// Command run when the CLI is invoked without one.
+    pub default_command: Option<String>,
+    /// Options taking a value, by path of each command with arguments that
+    /// accept a leading dash.
+    pub hyphen_values: Vec<(String, Vec<String>)>,
 }
 
 impl CliTs {
@@ -34,6 +59,19 @@ impl CliTs {
                 .unwrap_or_else(|_| Version::new(0, 1, 0)),
             description,
             commands,
+            completions: false,
+            docs: false,
+            doctor: false,
+            migrate: false,
+            logging: false,
+            import_extension: ImportExtension::default(),
+            global_options: Vec::new(),
+            locales: false,
+            build_info: false,
+            footer: None,
+            external: false,
+            default_command: None,
+            hyphen_values: Vec::new(),
         }
     }
 
@@ -49,9 +87,101 @@ impl CliTs {
             version,
             description,
             commands,
+            completions: false,
+            docs: false,
+            doctor: false,
+            migrate: false,
+            logging: false,
+            import_extension: ImportExtension::default(),
+            global_options: Vec::new(),
+            locales: false,
+            build_info: false,
+            footer: None,
+            external: false,
+            default_command: None,
+            hyphen_values: Vec::new(),
         }
     }
 
+    /// Register the generated `completions` subcommand.
+    pub fn with_completions(mut self, enabled: bool) -> Self {
+        self.completions = enabled;
+        self
+    }
+
+    /// Register the generated `docs` subcommand.
+    pub fn with_docs(mut self, enabled: bool) -> Self {
+        self.docs = enabled;
+        self
+    }
+
+    /// Register the generated `doctor` subcommand.
+    pub fn with_doctor(mut self, enabled: bool) -> Self {
+        self.doctor = enabled;
+        self
+    }
+
+    /// Register the generated `migrate` subcommand.
+    pub fn with_migrate(mut self, enabled: bool) -> Self {
+        self.migrate = enabled;
+        self
+    }
+
+    /// Add `--verbose`/`--quiet` global options (consumed by logger.ts).
+    pub fn with_logging(mut self, enabled: bool) -> Self {
+        self.logging = enabled;
+        self
+    }
+
+    /// Add the application's global options after the built-in ones.
+    pub fn with_global_options(mut self, options: Vec<(String, JsObject)>) -> Self {
+        self.global_options = options;
+        self
+    }
+
+    /// Translate the description through locale.ts.
+    pub fn with_locales(mut self, enabled: bool) -> Self {
+        self.locales = enabled;
+        self
+    }
+
+    /// Show the commit and build time from build-info.ts in `--version`.
+    pub fn with_build_info(mut self, enabled: bool) -> Self {
+        self.build_info = enabled;
+        self
+    }
+
+    /// Show `footer` at the end of the CLI's help.
+    pub fn with_footer(mut self, footer: Option<String>) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    /// Run `<name>-<command>` from PATH for commands the CLI does not define.
+    pub fn with_external(mut self, enabled: bool) -> Self {
+        self.external = enabled;
+        self
+    }
+
+    /// Run `default_command` when the CLI is invoked without a command.
+    pub fn with_default_command(mut self, default_command: Option<String>) -> Self {
+        self.default_command = default_command;
+        self
+    }
+
+    /// Pass negative numbers to the commands at these paths as arguments,
+    /// skipping the values of the options listed for each.
+    pub fn with_hyphen_values(mut self, hyphen_values: Vec<(String, Vec<String>)>) -> Self {
+        self.hyphen_values = hyphen_values;
+        self
+    }
+
+    /// Set the extension used for relative import specifiers.
+    pub fn with_import_extension(mut self, extension: ImportExtension) -> Self {
+        self.import_extension = extension;
+        self
+    }
+
     fn build_imports(&self) -> Vec<Import> {
         let mut imports = vec![Import::new("boune").named("defineCli")];
 
@@ -63,25 +193,332 @@ impl CliTs {
             );
         }
 
+        if self.migrate {
+            imports.push(Import::new("./migrate.ts").named("migrateCommand"));
+        }
+        if self.completions {
+            imports.push(Import::new("./completions.ts").named("completionsCommand"));
+        }
+        if self.docs {
+            imports.push(Import::new("./docs.ts").named("docsCommand"));
+        }
+        if self.doctor {
+            imports.push(Import::new("./doctor.ts").named("doctorCommand"));
+        }
+        if self.locales {
+            imports.push(Import::new("./locale.ts").named("t"));
+        }
+        if self.build_info {
+            imports.push(Import::new("./build-info.ts").named("version"));
+        }
+
         imports
     }
 
     fn build_cli_schema(&self) -> String {
         // Build the commands object
-        let commands = self.commands.iter().fold(JsObject::new(), |obj, cmd| {
-            let camel = to_camel_case(&cmd.name);
-            obj.raw(&camel, format!("{}Command", camel))
-        });
+        let commands = self
+            .commands
+            .iter()
+            .fold(JsObject::new(), |obj, cmd| {
+                let camel = to_camel_case(&cmd.name);
+                obj.raw(&camel, format!("{}Command", camel))
+            })
+            .raw_if(self.migrate, MIGRATE_COMMAND, "migrateCommand")
+            .raw_if(self.completions, COMPLETIONS_COMMAND, "completionsCommand")
+            .raw_if(self.docs, DOCS_COMMAND, "docsCommand")
+            .raw_if(self.doctor, DOCTOR_COMMAND, "doctorCommand");
+
+        // Global options shared by every command (consumed by ui.ts)
+        let color = JsObject::new()
+            .string("type", "string")
+            .array(
+                "choices",
+                JsArray::from_strings(["auto", "always", "never"]).as_const(),
+            )
+            .string("default", "auto")
+            .string("description", "When to use colored output");
+        let mut global_options = JsObject::new().object("color", color);
+        if self.logging {
+            let verbose = JsObject::new()
+                .string("type", "boolean")
+                .string("short", "v")
+                .string("description", "Show debug logs");
+            let quiet = JsObject::new()
+                .string("type", "boolean")
+                .string("short", "q")
+                .string("description", "Only show errors");
+            global_options = global_options
+                .object("verbose", verbose)
+                .object("quiet", quiet);
+        }
+        for (key, option) in &self.global_options {
+            global_options = global_options.object(key, option.clone());
+        }
 
         // Build the CLI config object
-        let config = JsObject::new()
-            .string("name", &self.name)
-            .string("version", self.version.to_string())
-            .string_opt("description", self.description.clone())
+        let config = JsObject::new().string("name", &self.name);
+        let config = if self.build_info {
+            config.raw("version", "version")
+        } else {
+            config.string("version", self.version.to_string())
+        };
+        let config = match &self.description {
+            Some(description) if self.locales => config.raw(
+                "description",
+                translate(&message_key(&[], None), description),
+            ),
+            description => config.string_opt("description", description.clone()),
+        };
+        let config = config
+            .string_opt("epilogue", self.footer.clone())
+            .object("globalOptions", global_options)
             .object("commands", commands);
 
         format!("defineCli({})", config.build().trim_end())
     }
+
+    /// Names and aliases of the commands the CLI defines itself.
+    fn command_names(&self) -> Vec<&str> {
+        self.commands
+            .iter()
+            .flat_map(|cmd| std::iter::once(&cmd.name).chain(&cmd.aliases))
+            .map(String::as_str)
+            .chain(self.builtin_commands())
+            .collect()
+    }
+
+    /// Names of the generated commands the CLI registers.
+    fn builtin_commands(&self) -> Vec<&'static str> {
+        [
+            (self.migrate, MIGRATE_COMMAND),
+            (self.completions, COMPLETIONS_COMMAND),
+            (self.docs, DOCS_COMMAND),
+            (self.doctor, DOCTOR_COMMAND),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+
+    /// Whether the CLI or any command runs a default command when given none.
+    fn has_default_commands(&self) -> bool {
+        fn any(cmds: &[CommandOp]) -> bool {
+            cmds.iter()
+                .any(|cmd| cmd.default_command.is_some() || any(&cmd.children))
+        }
+        self.default_command.is_some() || any(&self.commands)
+    }
+
+    /// Whether the arguments are rewritten before boune parses them.
+    fn rewrites_argv(&self) -> bool {
+        self.has_default_commands() || !self.hyphen_values.is_empty()
+    }
+
+    /// `commandTree` and `commandPath`, finding the command the arguments
+    /// run before boune parses them.
+    fn build_command_tree(&self) -> String {
+        fn entry(path: &str, names: &[(&str, &str)], default: Option<&str>) -> String {
+            let commands: Vec<String> = names
+                .iter()
+                .map(|(name, command)| format!("{:?}: {:?}", name, command))
+                .collect();
+            let default = default
+                .map(|name| format!(", default: {:?}", name))
+                .unwrap_or_default();
+            format!(
+                "  {:?}: {{ commands: {{ {} }}{} }},",
+                path,
+                commands.join(", "),
+                default
+            )
+        }
+        // Aliases resolve to the command's name, which the paths are made of
+        fn names(cmds: &[CommandOp]) -> Vec<(&str, &str)> {
+            cmds.iter()
+                .flat_map(|cmd| {
+                    std::iter::once(&cmd.name)
+                        .chain(&cmd.aliases)
+                        .map(|name| (name.as_str(), cmd.name.as_str()))
+                })
+                .collect()
+        }
+        fn collect(cmd: &CommandOp, entries: &mut Vec<String>) {
+            if !cmd.has_subcommands() {
+                return;
+            }
+            entries.push(entry(
+                &cmd.path.join(" "),
+                &names(&cmd.children),
+                cmd.default_command.as_deref(),
+            ));
+            for child in &cmd.children {
+                collect(child, entries);
+            }
+        }
+
+        let mut root = names(&self.commands);
+        root.extend(self.builtin_commands().into_iter().map(|name| (name, name)));
+        let mut entries = vec![entry("", &root, self.default_command.as_deref())];
+        for cmd in &self.commands {
+            collect(cmd, &mut entries);
+        }
+
+        format!(
+            r#"/** Subcommands of each command by path, and the one run when none is given. */
+const commandTree: Record<string, {{ commands: Record<string, string>; default?: string }}> = {{
+{entries}
+}};
+
+/** Path of the last command given in `argv`, and the index after its name. */
+function commandPath(argv: string[]): [string, number] {{
+  let path = "";
+  let at = 0;
+  for (const [i, arg] of argv.entries()) {{
+    if (arg === "--") {{
+      break;
+    }}
+    // Skips keys such as `constructor` inherited from Object
+    const name = commandTree[path]?.commands[arg];
+    if (typeof name === "string") {{
+      path = path === "" ? name : `${{path}} ${{name}}`;
+      at = i + 1;
+    }}
+  }}
+  return [path, at];
+}}"#,
+            entries = entries.join("\n")
+        )
+    }
+
+    /// `withDefaultCommand`, inserting the default command into the
+    /// arguments when none is given.
+    ///
+    /// boune prints help for a command given no subcommand, so the arguments
+    /// are completed before the CLI parses them.
+    fn build_default_commands(&self) -> String {
+        r#"/** Insert the default command after the last command given, when it has one. */
+function withDefaultCommand(argv: string[]): string[] {
+  const [path, at] = commandPath(argv);
+  const command = commandTree[path]?.default;
+  const help = argv.some((arg) => arg === "-h" || arg === "--help" || arg === "--version");
+  if (command === undefined || help) {
+    return argv;
+  }
+  return [...argv.slice(0, at), command, ...argv.slice(at)];
+}"#
+        .to_string()
+    }
+
+    /// `hyphenValues` and `withHyphenValues`, moving negative numbers given
+    /// to commands with arguments that accept them after `--`.
+    ///
+    /// boune reads `-5` as a short option, so the arguments are reordered
+    /// before the CLI parses them. Options keep their values, and arguments
+    /// their order.
+    fn build_hyphen_values(&self) -> String {
+        let entries: Vec<String> = self
+            .hyphen_values
+            .iter()
+            .map(|(path, options)| {
+                let options: Vec<String> = options.iter().map(|o| format!("{:?}", o)).collect();
+                format!("  {:?}: [{}],", path, options.join(", "))
+            })
+            .collect();
+        format!(
+            r#"/** Options taking a value, by path of the commands whose arguments may be negative numbers. */
+const hyphenValues: Record<string, string[]> = {{
+{entries}
+}};
+
+const isNegative = (arg: string) => /^-(\d+(\.\d*)?|\.\d+)$/.test(arg);
+
+/** Move negative numbers given as arguments after `--`, where boune reads them as values. */
+function withHyphenValues(argv: string[]): string[] {{
+  const [path, at] = commandPath(argv);
+  const options = hyphenValues[path];
+  const end = argv.indexOf("--", at);
+  const rest = argv.slice(at, end === -1 ? undefined : end);
+  if (!Array.isArray(options) || !rest.some(isNegative)) {{
+    return argv;
+  }}
+  const flags: string[] = [];
+  const values: string[] = [];
+  for (let i = 0; i < rest.length; i++) {{
+    const arg = rest[i];
+    if (isNegative(arg) || !arg.startsWith("-")) {{
+      values.push(arg);
+      continue;
+    }}
+    flags.push(arg);
+    if (options.includes(arg) && i + 1 < rest.length) {{
+      flags.push(rest[++i]);
+    }}
+  }}
+  const after = end === -1 ? [] : argv.slice(end + 1);
+  return [...argv.slice(0, at), ...flags, "--", ...values, ...after];
+}}"#,
+            entries = entries.join("\n")
+        )
+    }
+
+    /// The `app` wrapping the boune CLI, running external commands and
+    /// rewriting the arguments before it parses them.
+    fn build_app(&self) -> String {
+        let mut lines = Vec::new();
+        if self.external {
+            lines.push("    runExternal(process.argv.slice(2));");
+        }
+        if self.has_default_commands() {
+            lines.push(
+                "    process.argv.splice(2, Infinity, ...withDefaultCommand(process.argv.slice(2)));",
+            );
+        }
+        if !self.hyphen_values.is_empty() {
+            lines.push(
+                "    process.argv.splice(2, Infinity, ...withHyphenValues(process.argv.slice(2)));",
+            );
+        }
+        format!(
+            "export const app = {{\n  run() {{\n{}\n    return cli.run();\n  }},\n}};",
+            lines.join("\n")
+        )
+    }
+
+    /// `runExternal`, running `<name>-<command>` for commands the CLI does
+    /// not define.
+    ///
+    /// boune rejects commands it does not know, so the first argument is
+    /// checked before the CLI parses it.
+    fn build_external(&self) -> String {
+        let names: Vec<String> = self
+            .command_names()
+            .iter()
+            .map(|name| format!("{:?}", name))
+            .collect();
+        format!(
+            r#"/** Commands the CLI defines; others run `{name}-<command>` from PATH. */
+const commands = new Set([{names}]);
+
+/** Run `{name}-<command>` for an unknown first argument, exiting with its status. */
+function runExternal(argv: string[]): void {{
+  const [command, ...rest] = argv;
+  if (command === undefined || command.startsWith("-") || commands.has(command)) {{
+    return;
+  }}
+  const program = Bun.which(`{name}-${{command}}`);
+  if (program === null) {{
+    return;
+  }}
+  const {{ exitCode }} = Bun.spawnSync([program, ...rest], {{
+    stdio: ["inherit", "inherit", "inherit"],
+  }});
+  process.exit(exitCode ?? 1);
+}}"#,
+            name = self.name,
+            names = names.join(", ")
+        )
+    }
 }
 
 impl GeneratedFile for CliTs {
@@ -95,10 +532,27 @@ impl GeneratedFile for CliTs {
 
     fn render(&self) -> String {
         let file = CodeFile::new()
+            .import_extension(self.import_extension)
             .add(RawCode::new(GENERATED_HEADER))
-            .imports(self.build_imports())
-            .add(Const::new("app", self.build_cli_schema()));
-
-        file.render()
+            .imports(self.build_imports());
+        if self.external || self.rewrites_argv() {
+            let mut file = file.add(Const::new("cli", self.build_cli_schema()).private());
+            if self.external {
+                file = file.add(RawCode::new(self.build_external()));
+            }
+            if self.rewrites_argv() {
+                file = file.add(RawCode::new(self.build_command_tree()));
+            }
+            if self.has_default_commands() {
+                file = file.add(RawCode::new(self.build_default_commands()));
+            }
+            if !self.hyphen_values.is_empty() {
+                file = file.add(RawCode::new(self.build_hyphen_values()));
+            }
+            file.add(RawCode::new(self.build_app())).render()
+        } else {
+            file.add(Const::new("app", self.build_cli_schema()))
+                .render()
+        }
     }
 }
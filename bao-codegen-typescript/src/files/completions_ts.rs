@@ -0,0 +1,433 @@
+//! completions.ts generator for shell completion scripts.
+//!
+//! boune has no completion support, so the scripts are rendered at bake
+//! time from the command tree and embedded in a `completions` subcommand.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{CommandOp, Input, InputKind, InputType};
+use baobao_manifest::FlagCase;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::{ArrowFn, Import, JsArray, JsObject},
+    code_file::{CodeFile, RawCode},
+    naming::flag_name,
+};
+
+/// Name of the generated completions subcommand.
+pub const COMPLETIONS_COMMAND: &str = "completions";
+
+/// Shells supported by the generated completions subcommand.
+const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+/// Options available on the root command.
+const ROOT_OPTIONS: [&str; 3] = ["--help", "--version", "--color"];
+
+/// The completions.ts file defining the `completions` subcommand.
+pub struct CompletionsTs {
+    name: String,
+    commands: Vec<CommandOp>,
+    flag_case: FlagCase,
+}
+
+/// A node in the completion tree, flattened with its full path.
+struct Node<'a> {
+    path: Vec<&'a str>,
+    children: Vec<(&'a str, &'a str)>,
+    flags: Vec<&'a Input>,
+    values: Vec<&'a str>,
+}
+
+impl CompletionsTs {
+    pub fn new(name: impl Into<String>, commands: Vec<CommandOp>) -> Self {
+        Self {
+            name: name.into(),
+            commands,
+            flag_case: FlagCase::default(),
+        }
+    }
+
+    /// Set how flag names are spelled on the command line.
+    pub fn with_flag_case(mut self, flag_case: FlagCase) -> Self {
+        self.flag_case = flag_case;
+        self
+    }
+
+    /// Flatten the command tree (including the completions command itself).
+    fn nodes(&self) -> Vec<Node<'_>> {
+        let mut root_children: Vec<(&str, &str)> = self
+            .commands
+            .iter()
+            .map(|c| (c.name.as_str(), c.description.as_str()))
+            .collect();
+        root_children.push((COMPLETIONS_COMMAND, "Generate shell completion scripts"));
+
+        let mut nodes = vec![Node {
+            path: vec![],
+            children: root_children,
+            flags: vec![],
+            values: vec![],
+        }];
+
+        for cmd in &self.commands {
+            Self::collect(cmd, &mut nodes);
+        }
+
+        nodes.push(Node {
+            path: vec![COMPLETIONS_COMMAND],
+            children: vec![],
+            flags: vec![],
+            values: SHELLS.to_vec(),
+        });
+
+        nodes
+    }
+
+    fn collect<'a>(cmd: &'a CommandOp, nodes: &mut Vec<Node<'a>>) {
+        let values = cmd
+            .inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Positional))
+            .flat_map(|i| i.choices.iter().flatten().map(String::as_str))
+            .collect();
+
+        nodes.push(Node {
+            path: cmd.path.iter().map(String::as_str).collect(),
+            children: cmd
+                .children
+                .iter()
+                .map(|c| (c.name.as_str(), c.description.as_str()))
+                .collect(),
+            flags: cmd
+                .inputs
+                .iter()
+                .filter(|i| matches!(i.kind, InputKind::Flag { .. }))
+                .collect(),
+            values,
+        });
+
+        for child in &cmd.children {
+            Self::collect(child, nodes);
+        }
+    }
+
+    /// Words offered at a node: subcommands, positional choices and flags.
+    fn words(&self, node: &Node<'_>) -> Vec<String> {
+        let mut words: Vec<String> = node.children.iter().map(|(n, _)| n.to_string()).collect();
+        words.extend(node.values.iter().map(|v| v.to_string()));
+        for flag in &node.flags {
+            words.push(format!("--{}", flag_name(&flag.name, self.flag_case)));
+            if let InputKind::Flag { short: Some(c) } = flag.kind {
+                words.push(format!("-{}", c));
+            }
+        }
+        if node.path.is_empty() {
+            words.extend(ROOT_OPTIONS.iter().map(|o| o.to_string()));
+        } else {
+            words.push("--help".to_string());
+        }
+        words
+    }
+
+    /// Shell function name derived from the CLI name.
+    fn function_name(&self) -> String {
+        format!("_{}", self.name.replace(['-', '.'], "_"))
+    }
+
+    /// Case pattern matching every known command path.
+    fn path_pattern(nodes: &[Node<'_>]) -> String {
+        nodes
+            .iter()
+            .filter(|n| !n.path.is_empty())
+            .map(|n| format!("\"{}\"", n.path.join(" ")))
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    pub fn bash_script(&self) -> String {
+        let nodes = self.nodes();
+        let func = self.function_name();
+
+        let mut out = String::new();
+        out.push_str(&format!("{}() {{\n", func));
+        out.push_str("    local cur word i cmd_path=\"\" opts=\"\"\n");
+        out.push_str("    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+        out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+        out.push_str("        word=\"${COMP_WORDS[i]}\"\n");
+        out.push_str("        case \"${cmd_path:+$cmd_path }$word\" in\n");
+        out.push_str(&format!(
+            "            {}) cmd_path=\"${{cmd_path:+$cmd_path }}$word\" ;;\n",
+            Self::path_pattern(&nodes)
+        ));
+        out.push_str("        esac\n");
+        out.push_str("    done\n");
+        out.push_str("    case \"$cmd_path\" in\n");
+        for node in &nodes {
+            out.push_str(&format!(
+                "        \"{}\") opts=\"{}\" ;;\n",
+                node.path.join(" "),
+                self.words(node).join(" ")
+            ));
+        }
+        out.push_str("    esac\n");
+        out.push_str("    COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))\n");
+        out.push_str("}\n\n");
+        out.push_str(&format!("complete -F {} {}\n", func, self.name));
+        out
+    }
+
+    pub fn zsh_script(&self) -> String {
+        let nodes = self.nodes();
+        let func = self.function_name();
+
+        let mut out = format!("#compdef {}\n\n", self.name);
+        out.push_str(&format!("{}() {{\n", func));
+        out.push_str("    local cmd_path=\"\" word\n");
+        out.push_str("    local -a opts\n");
+        out.push_str("    for word in \"${(@)words[2,CURRENT-1]}\"; do\n");
+        out.push_str("        case \"${cmd_path:+$cmd_path }$word\" in\n");
+        out.push_str(&format!(
+            "            {}) cmd_path=\"${{cmd_path:+$cmd_path }}$word\" ;;\n",
+            Self::path_pattern(&nodes)
+        ));
+        out.push_str("        esac\n");
+        out.push_str("    done\n");
+        out.push_str("    case \"$cmd_path\" in\n");
+        for node in &nodes {
+            out.push_str(&format!(
+                "        \"{}\") opts=({}) ;;\n",
+                node.path.join(" "),
+                self.words(node).join(" ")
+            ));
+        }
+        out.push_str("    esac\n");
+        out.push_str("    compadd -- \"${opts[@]}\"\n");
+        out.push_str("}\n\n");
+        out.push_str(&format!("compdef {} {}\n", func, self.name));
+        out
+    }
+
+    pub fn fish_script(&self) -> String {
+        let nodes = self.nodes();
+        let name = &self.name;
+
+        let mut out = format!("complete -c {} -f\n", name);
+        for node in &nodes {
+            let condition = match node.path.last() {
+                None => "__fish_use_subcommand".to_string(),
+                Some(last) if node.children.is_empty() => {
+                    format!("__fish_seen_subcommand_from {}", last)
+                }
+                Some(last) => format!(
+                    "__fish_seen_subcommand_from {}; and not __fish_seen_subcommand_from {}",
+                    last,
+                    node.children
+                        .iter()
+                        .map(|(n, _)| *n)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+            };
+
+            for (child, description) in &node.children {
+                out.push_str(&format!(
+                    "complete -c {} -n \"{}\" -a {} -d {}\n",
+                    name,
+                    condition,
+                    child,
+                    fish_quote(description)
+                ));
+            }
+            if !node.values.is_empty() {
+                out.push_str(&format!(
+                    "complete -c {} -n \"{}\" -a \"{}\"\n",
+                    name,
+                    condition,
+                    node.values.join(" ")
+                ));
+            }
+            for flag in &node.flags {
+                out.push_str(&format!(
+                    "complete -c {} -n \"{}\"{}\n",
+                    name,
+                    condition,
+                    fish_flag(flag, self.flag_case)
+                ));
+            }
+        }
+        out
+    }
+
+    fn build_command(&self) -> String {
+        let scripts = JsObject::new()
+            .raw("bash", template_literal(&self.bash_script()))
+            .raw("zsh", template_literal(&self.zsh_script()))
+            .raw("fish", template_literal(&self.fish_script()));
+
+        let shell = JsObject::new()
+            .string("type", "string")
+            .raw("required", "true")
+            .string("description", "Shell to generate completions for")
+            .array("choices", JsArray::from_strings(SHELLS).as_const());
+        let args = JsObject::new().object("shell", shell);
+
+        let command = JsObject::new()
+            .string("name", COMPLETIONS_COMMAND)
+            .string("description", "Generate shell completion scripts")
+            .raw("arguments", "args")
+            .arrow_fn(
+                "action",
+                ArrowFn::new("{ args }").body_line("process.stdout.write(scripts[args.shell]);"),
+            );
+
+        format!(
+            "const scripts = {} as const;\n\nconst args = {} as const;\n\nexport const completionsCommand = defineCommand({});",
+            scripts.build().trim_end(),
+            args.build().trim_end(),
+            command.build().trim_end()
+        )
+    }
+}
+
+/// Render a fish flag specification (`-l name -s x -r -a "..." -d "..."`).
+fn fish_flag(flag: &Input, flag_case: FlagCase) -> String {
+    let mut spec = format!(" -l {}", flag_name(&flag.name, flag_case));
+    if let InputKind::Flag { short: Some(c) } = flag.kind {
+        spec.push_str(&format!(" -s {}", c));
+    }
+    if !matches!(flag.ty, InputType::Bool | InputType::Count) || flag.choices.is_some() {
+        spec.push_str(" -r");
+    }
+    if let Some(choices) = &flag.choices {
+        spec.push_str(&format!(" -a \"{}\"", choices.join(" ")));
+    }
+    if let Some(description) = &flag.description {
+        spec.push_str(&format!(" -d {}", fish_quote(description)));
+    }
+    spec
+}
+
+/// Quote a string for fish using single quotes.
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Wrap a script in a JavaScript template literal.
+pub(super) fn template_literal(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${");
+    format!("`{}`", escaped)
+}
+
+impl GeneratedFile for CompletionsTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("completions.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("boune").named("defineCommand"))
+            .add(RawCode::new(self.build_command()))
+            .render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completions() -> CompletionsTs {
+        let migrate = CommandOp {
+            name: "migrate".to_string(),
+            path: vec!["db".to_string(), "migrate".to_string()],
+            description: "Run migrations".to_string(),
+            inputs: vec![Input {
+                name: "dry_run".to_string(),
+                ty: InputType::Bool,
+                kind: InputKind::Flag { short: Some('n') },
+                required: false,
+                default: None,
+                description: Some("Don't apply".to_string()),
+                choices: None,
+                multiple: false,
+                delimiter: None,
+                allow_hyphen_values: false,
+                prompt: None,
+                constraints: Default::default(),
+                requires: vec![],
+                required_if: None,
+                hint: None,
+                long_description: None,
+                deprecated: None,
+                span: None,
+            }],
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
+            children: vec![],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
+        };
+        let db = CommandOp {
+            name: "db".to_string(),
+            path: vec!["db".to_string()],
+            description: "Database commands".to_string(),
+            inputs: vec![],
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
+            children: vec![migrate],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
+        };
+        CompletionsTs::new("my-app", vec![db])
+    }
+
+    #[test]
+    fn test_bash_script() {
+        let script = completions().bash_script();
+        assert!(script.starts_with("_my_app() {"));
+        assert!(script.contains("\"db\"|\"db migrate\"|\"completions\")"));
+        assert!(script.contains("\"\") opts=\"db completions --help --version --color\" ;;"));
+        assert!(script.contains("\"db migrate\") opts=\"--dry-run -n --help\" ;;"));
+        assert!(script.ends_with("complete -F _my_app my-app\n"));
+    }
+
+    #[test]
+    fn test_zsh_script() {
+        let script = completions().zsh_script();
+        assert!(script.starts_with("#compdef my-app\n"));
+        assert!(script.contains("\"completions\") opts=(bash zsh fish --help) ;;"));
+    }
+
+    #[test]
+    fn test_fish_script() {
+        let script = completions().fish_script();
+        assert!(script.contains(
+            "complete -c my-app -n \"__fish_use_subcommand\" -a db -d 'Database commands'"
+        ));
+        assert!(script.contains(
+            "complete -c my-app -n \"__fish_seen_subcommand_from migrate\" -l dry-run -s n -d 'Don\\'t apply'"
+        ));
+    }
+
+    #[test]
+    fn test_template_literal_escaping() {
+        assert_eq!(template_literal("a`b${c}\\"), "`a\\`b\\${c}\\\\`");
+    }
+}
@@ -0,0 +1,174 @@
+//! config.ts generator for the `[context.config]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{ConfigFieldType, ConfigFormat, ConfigOptions};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+const CHECK: &str = r#"type FieldType = "string" | "int" | "float" | "bool";
+
+function check(values: Record<string, unknown>, key: string, type: FieldType, optional: boolean): void {
+  const value = values[key];
+  if (value === undefined) {
+    if (optional) {
+      return;
+    }
+    throw new Error(`${PATH}: missing field '${key}'`);
+  }
+  const valid =
+    type === "string"
+      ? typeof value === "string"
+      : type === "bool"
+        ? typeof value === "boolean"
+        : type === "int"
+          ? Number.isInteger(value)
+          : typeof value === "number";
+  if (!valid) {
+    throw new Error(`${PATH}: field '${key}' must be ${type}`);
+  }
+}"#;
+
+/// The config.ts file with the typed `Config` interface and its loader.
+///
+/// The file is read on the first `loadConfig()` call, and every field is
+/// checked against its declared type.
+pub struct ConfigTs {
+    pub options: ConfigOptions,
+}
+
+impl ConfigTs {
+    pub fn new(options: ConfigOptions) -> Self {
+        Self { options }
+    }
+
+    fn field_type(field_type: ConfigFieldType) -> &'static str {
+        match field_type {
+            ConfigFieldType::String => "string",
+            ConfigFieldType::Int | ConfigFieldType::Float => "number",
+            ConfigFieldType::Bool => "boolean",
+        }
+    }
+
+    fn field_type_name(field_type: ConfigFieldType) -> &'static str {
+        match field_type {
+            ConfigFieldType::String => "string",
+            ConfigFieldType::Int => "int",
+            ConfigFieldType::Float => "float",
+            ConfigFieldType::Bool => "bool",
+        }
+    }
+
+    /// The key as a property name, quoted unless it is an identifier.
+    fn property(key: &str) -> String {
+        if key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            key.to_string()
+        } else {
+            format!("{:?}", key)
+        }
+    }
+
+    fn parse(&self) -> &'static str {
+        match self.options.format {
+            ConfigFormat::Toml => "Bun.TOML.parse(text)",
+            ConfigFormat::Json => "JSON.parse(text)",
+            ConfigFormat::Yaml => "Bun.YAML.parse(text)",
+        }
+    }
+
+    fn build_interface(&self) -> String {
+        let fields: Vec<String> = self
+            .options
+            .fields
+            .iter()
+            .map(|(key, field)| {
+                format!(
+                    "  {}{}: {};",
+                    Self::property(key),
+                    if field.optional { "?" } else { "" },
+                    Self::field_type(field.field_type)
+                )
+            })
+            .collect();
+        let body = if fields.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{\n{}\n}}", fields.join("\n"))
+        };
+        format!(
+            "/** Settings read from `{}`, also exposed as `ctx.config`. */\nexport interface Config {}",
+            self.options.path, body
+        )
+    }
+
+    fn build_loader(&self) -> String {
+        let checks: String = self
+            .options
+            .fields
+            .iter()
+            .map(|(key, field)| {
+                format!(
+                    "  check(values, {:?}, {:?}, {});\n",
+                    key,
+                    Self::field_type_name(field.field_type),
+                    field.optional
+                )
+            })
+            .collect();
+        format!(
+            r#"let loaded: Config | undefined;
+
+/**
+ * Load `{path}`, throwing if a required field is missing or has the wrong type.
+ *
+ * A missing file reads as empty.
+ */
+export function loadConfig(): Config {{
+  if (loaded) {{
+    return loaded;
+  }}
+  const text = existsSync(PATH) ? readFileSync(PATH, "utf8") : "";
+  const values = (text ? {parse} : {{}}) as Record<string, unknown>;
+{checks}  loaded = values as unknown as Config;
+  return loaded;
+}}"#,
+            path = self.options.path,
+            parse = self.parse(),
+            checks = checks
+        )
+    }
+}
+
+impl GeneratedFile for ConfigTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("config.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(vec![
+                Import::new("node:fs")
+                    .named("existsSync")
+                    .named("readFileSync"),
+            ])
+            .add(RawCode::new(format!(
+                "const PATH = {:?};",
+                self.options.path
+            )))
+            .add(RawCode::new(self.build_interface()));
+        if !self.options.fields.is_empty() {
+            file = file.add(RawCode::new(CHECK));
+        }
+        file.add(RawCode::new(self.build_loader())).render()
+    }
+}
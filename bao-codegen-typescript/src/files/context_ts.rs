@@ -3,26 +3,59 @@
 use std::path::{Path, PathBuf};
 
 use baobao_codegen::{
+    adapters::{DatabaseAdapter, PoolInitInfo, RenderedResources},
     builder::{FieldSpec, StructSpec, StructureRenderer, TypeRef},
     schema::ContextFieldInfo,
 };
-use baobao_core::{ContextFieldType, DatabaseType, FileRules, GeneratedFile};
+use baobao_core::{ContextFieldType, DatabaseType, FileRules, GeneratedFile, QueueType};
 
-use super::GENERATED_HEADER;
+use super::{GENERATED_HEADER, QueueTs};
 use crate::{
-    TypeScriptStructureRenderer,
+    BunSqliteAdapter, DuckdbAdapter, TypeScriptRenderer, TypeScriptStructureRenderer,
     ast::Import,
     code_file::{CodeFile, RawCode},
 };
 
+/// Memoizes a resource's setup, as `queue()` does for its connection.
+const LAZY: &str = r#"/** Set up a resource on the first call and share it with later ones. */
+function lazy<T>(init: () => Promise<T>): () => Promise<T> {
+  let value: Promise<T> | undefined;
+  return () => (value ??= init());
+}"#;
+
 /// The context.ts file containing shared application state.
 pub struct ContextTs {
     pub fields: Vec<ContextFieldInfo>,
+    pub resources: RenderedResources,
+    /// Export a `ctx` whose resources are set up on first use.
+    pub lazy: bool,
 }
 
 impl ContextTs {
     pub fn new(fields: Vec<ContextFieldInfo>) -> Self {
-        Self { fields }
+        Self {
+            fields,
+            resources: RenderedResources::default(),
+            lazy: false,
+        }
+    }
+
+    /// Export a `ctx` whose fields are async getters, importing each
+    /// resource's module only when a handler first asks for it.
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Type custom resource fields with their adapter's code.
+    ///
+    /// Custom fields without rendered code are left out.
+    pub fn with_resources(mut self, resources: RenderedResources) -> Self {
+        self.fields.retain(|f| {
+            f.field_type != ContextFieldType::Custom || resources.get(&f.name).is_some()
+        });
+        self.resources = resources;
+        self
     }
 
     fn needs_sqlite(&self) -> bool {
@@ -34,11 +67,138 @@ impl ContextTs {
         })
     }
 
+    fn needs_duckdb(&self) -> bool {
+        self.fields.iter().any(|f| {
+            matches!(
+                f.field_type,
+                ContextFieldType::Database(DatabaseType::Duckdb)
+            )
+        })
+    }
+
+    fn needs_logger(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Logger))
+    }
+
+    fn needs_http(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Http))
+    }
+
+    fn needs_storage(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Storage))
+    }
+
+    fn needs_grpc(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Grpc))
+    }
+
+    fn needs_email(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Email))
+    }
+
+    fn needs_config(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Config))
+    }
+
+    fn needs_secrets(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Secrets))
+    }
+
+    fn needs_cache(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Cache))
+    }
+
+    fn needs_workdir(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Workdir))
+    }
+
+    fn needs_llm(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.field_type, ContextFieldType::Llm))
+    }
+
+    fn queue_type(&self) -> Option<QueueType> {
+        self.fields.iter().find_map(|f| match f.field_type {
+            ContextFieldType::Queue(queue_type) => Some(queue_type),
+            _ => None,
+        })
+    }
+
     fn build_imports(&self) -> Vec<Import> {
         let mut imports = Vec::new();
+        match self.queue_type() {
+            Some(QueueType::Nats) => imports.push(Import::new("nats").named_type("NatsConnection")),
+            Some(QueueType::Kafka) => imports.push(Import::new("kafkajs").named_type("Producer")),
+            None => {}
+        }
+        if self.needs_email() {
+            imports.push(Import::new("nodemailer").named_type("Transporter"));
+        }
+        if self.needs_config() {
+            imports.push(Import::new("./config.ts").named_type("Config"));
+        }
+        if self.needs_secrets() {
+            imports.push(Import::new("./secrets.ts").named_type("Secrets"));
+        }
+        if self.needs_cache() {
+            imports.push(Import::new("./cache.ts").named_type("Cache"));
+        }
+        if self.needs_workdir() {
+            imports.push(Import::new("./workdir.ts").named_type("Workdir"));
+        }
+        if self.needs_llm() {
+            imports.push(Import::new("./llm.ts").named_type("LlmClient"));
+        }
+        if self.needs_grpc() {
+            imports.push(Import::new("@connectrpc/connect").named_type("Transport"));
+        }
+        if self.needs_storage() {
+            imports.push(Import::new("bun").named_type("S3Client"));
+        }
         if self.needs_sqlite() {
             imports.push(Import::new("bun:sqlite").named("Database"));
         }
+        if self.needs_duckdb() {
+            imports.push(Import::new("@duckdb/node-api").named_type("DuckDBConnection"));
+        }
+        if self.needs_http() {
+            imports.push(Import::new("./http.ts").named_type("HttpClient"));
+        }
+        if self.needs_logger() {
+            imports.push(Import::new("./logger.ts").named_type("Logger"));
+        }
+        for spec in self.resources.code.values().flat_map(|code| &code.imports) {
+            let import = spec
+                .symbols
+                .iter()
+                .fold(Import::new(&spec.module), |import, symbol| {
+                    if spec.type_only {
+                        import.named_type(symbol)
+                    } else {
+                        import.named(symbol)
+                    }
+                });
+            imports.push(import);
+        }
         imports
     }
 
@@ -48,20 +208,119 @@ impl ContextTs {
         let mut spec = StructSpec::new("Context");
 
         for field in &self.fields {
-            let type_ref = Self::map_context_type_ref(&field.field_type);
+            let mut type_ref = match self.resources.get(&field.name) {
+                Some(code) => TypeRef::named(&code.type_name),
+                None => Self::map_context_type_ref(&field.field_type),
+            };
+            // Queues are already connected on first use
+            if self.lazy && !matches!(field.field_type, ContextFieldType::Queue(_)) {
+                type_ref = TypeRef::generic("() => Promise", vec![type_ref]);
+            }
             spec = spec.field(FieldSpec::new(&field.name, type_ref));
         }
 
         renderer.render_struct(&spec)
     }
 
+    /// The exported `ctx`, with a lazy getter for each field.
+    fn build_lazy_ctx(&self) -> String {
+        let getters: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| format!("  {}: {},", field.name, self.lazy_init(field)))
+            .collect();
+        format!(
+            "/** The context of every command, whose resources are set up on first use. */\nexport const ctx: Context = {{\n{}\n}};",
+            getters.join("\n")
+        )
+    }
+
+    /// The getter of a field, importing its module when it is first called.
+    fn lazy_init(&self, field: &ContextFieldInfo) -> String {
+        let module = |path: &str, export: &str| {
+            format!(
+                "lazy(async () => (await import(\"./{}\")).{})",
+                path, export
+            )
+        };
+        match field.field_type {
+            ContextFieldType::Database(db_type) => {
+                let info = PoolInitInfo {
+                    field_name: field.name.clone(),
+                    db_type,
+                    env_var: field.env_var.clone(),
+                    pool_config: field.pool.clone(),
+                    sqlite_config: field.sqlite.clone(),
+                    duckdb_config: field.duckdb.clone(),
+                };
+                let renderer = TypeScriptRenderer::new();
+                match db_type {
+                    DatabaseType::Sqlite => format!(
+                        "lazy(async () => {})",
+                        BunSqliteAdapter::new()
+                            .pool_init(&info)
+                            .render_inline(&renderer)
+                    ),
+                    DatabaseType::Duckdb => format!(
+                        "lazy(async () => {{\n    const {{ DuckDBInstance }} = await import(\"@duckdb/node-api\");\n    return {};\n  }})",
+                        DuckdbAdapter::new()
+                            .pool_init(&info)
+                            .render_inline(&renderer)
+                    ),
+                    DatabaseType::Postgres | DatabaseType::Mysql => Self::unsupported(field),
+                }
+            }
+            ContextFieldType::Http => module("http.ts", &field.name),
+            ContextFieldType::Logger => module("logger.ts", "logger"),
+            ContextFieldType::Storage => module("storage.ts", "storage"),
+            ContextFieldType::Grpc => module("grpc.ts", "grpc"),
+            ContextFieldType::Email => module("email.ts", "email"),
+            ContextFieldType::Config => module("config.ts", "loadConfig()"),
+            ContextFieldType::Secrets => module("secrets.ts", "secrets"),
+            ContextFieldType::Llm => module("llm.ts", "llm"),
+            ContextFieldType::Cache => module("cache.ts", "cache"),
+            ContextFieldType::Workdir => module("workdir.ts", "workdir"),
+            ContextFieldType::Queue(_) => {
+                "async () => (await import(\"./queue.ts\")).queue()".to_string()
+            }
+            ContextFieldType::Custom => match self.resources.get(&field.name) {
+                Some(code) => format!("lazy(async () => {})", code.init),
+                None => Self::unsupported(field),
+            },
+        }
+    }
+
+    /// A getter for a resource Bun has no client for.
+    fn unsupported(field: &ContextFieldInfo) -> String {
+        format!(
+            "() => Promise.reject(new Error(\"ctx.{} is not available in TypeScript\"))",
+            field.name
+        )
+    }
+
     /// Map ContextFieldType to TypeRef.
     fn map_context_type_ref(field_type: &ContextFieldType) -> TypeRef {
         match field_type {
             ContextFieldType::Database(DatabaseType::Sqlite) => TypeRef::named("Database"),
             ContextFieldType::Database(DatabaseType::Postgres) => TypeRef::named("unknown"),
             ContextFieldType::Database(DatabaseType::Mysql) => TypeRef::named("unknown"),
-            ContextFieldType::Http => TypeRef::named("unknown"),
+            ContextFieldType::Database(DatabaseType::Duckdb) => TypeRef::named("DuckDBConnection"),
+            ContextFieldType::Http => TypeRef::named("HttpClient"),
+            ContextFieldType::Logger => TypeRef::named("Logger"),
+            ContextFieldType::Storage => TypeRef::named("S3Client"),
+            ContextFieldType::Grpc => TypeRef::named("Transport"),
+            ContextFieldType::Email => TypeRef::named("Transporter"),
+            ContextFieldType::Config => TypeRef::named("Config"),
+            ContextFieldType::Secrets => TypeRef::named("Secrets"),
+            ContextFieldType::Llm => TypeRef::named("LlmClient"),
+            ContextFieldType::Cache => TypeRef::named("Cache"),
+            ContextFieldType::Workdir => TypeRef::named("Workdir"),
+            ContextFieldType::Queue(queue_type) => TypeRef::named(format!(
+                "() => Promise<{}>",
+                QueueTs::client_type(*queue_type)
+            )),
+            // Rendered by the resource adapter
+            ContextFieldType::Custom => TypeRef::named("unknown"),
         }
     }
 }
@@ -76,10 +335,15 @@ impl GeneratedFile for ContextTs {
     }
 
     fn render(&self) -> String {
-        CodeFile::new()
+        let file = CodeFile::new()
             .add(RawCode::new(GENERATED_HEADER))
             .imports(self.build_imports())
-            .add(RawCode::new(self.build_context_type()))
+            .add(RawCode::new(self.build_context_type()));
+        if !self.lazy {
+            return file.render();
+        }
+        file.add(RawCode::new(LAZY))
+            .add(RawCode::new(self.build_lazy_ctx()))
             .render()
     }
 }
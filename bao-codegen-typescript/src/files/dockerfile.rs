@@ -0,0 +1,127 @@
+//! Dockerfile generator for Bun-based CLIs.
+
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::schema::ContextFieldInfo;
+use baobao_core::{ContextFieldType, DatabaseType, FileRules, GeneratedFile};
+
+/// A multi-stage Dockerfile that compiles the CLI with `bun build --compile`.
+///
+/// Dependencies are installed from the lockfile when one is present, and the
+/// final image only contains the compiled binary plus any runtime libraries
+/// required by the context (e.g. `libpq5` for PostgreSQL). SQLite needs no
+/// extra packages since `bun:sqlite` is built into the runtime, and DuckDB's
+/// bindings ship the engine with them.
+pub struct Dockerfile {
+    pub name: String,
+    pub packages: Vec<&'static str>,
+}
+
+impl Dockerfile {
+    pub fn new(name: impl Into<String>, context_fields: &[ContextFieldInfo]) -> Self {
+        let mut packages = vec!["ca-certificates"];
+        for field in context_fields {
+            let package = match field.field_type {
+                ContextFieldType::Database(DatabaseType::Postgres) => "libpq5",
+                ContextFieldType::Database(DatabaseType::Mysql) => "libmariadb3",
+                ContextFieldType::Database(DatabaseType::Sqlite | DatabaseType::Duckdb)
+                | ContextFieldType::Http
+                | ContextFieldType::Logger
+                | ContextFieldType::Storage
+                | ContextFieldType::Grpc
+                | ContextFieldType::Queue(_)
+                | ContextFieldType::Email
+                | ContextFieldType::Config
+                | ContextFieldType::Secrets
+                | ContextFieldType::Llm
+                | ContextFieldType::Cache
+                | ContextFieldType::Workdir
+                | ContextFieldType::Custom => {
+                    continue;
+                }
+            };
+            if !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
+
+        Self {
+            name: name.into(),
+            packages,
+        }
+    }
+}
+
+impl GeneratedFile for Dockerfile {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("Dockerfile")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        format!(
+            r#"# syntax=docker/dockerfile:1
+
+FROM oven/bun:1 AS deps
+WORKDIR /app
+COPY package.json bun.lock* ./
+RUN if ls bun.lock* >/dev/null 2>&1; then bun install --frozen-lockfile; else bun install; fi
+
+FROM oven/bun:1 AS build
+WORKDIR /app
+COPY --from=deps /app/node_modules ./node_modules
+COPY . .
+RUN bun build src/index.ts --compile --outfile dist/{name}
+
+FROM debian:bookworm-slim
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends {packages} \
+    && rm -rf /var/lib/apt/lists/*
+COPY --from=build /app/dist/{name} /usr/local/bin/{name}
+ENTRYPOINT ["{name}"]
+"#,
+            name = self.name,
+            packages = self.packages.join(" "),
+        )
+    }
+}
+
+/// The .dockerignore file that keeps local artifacts out of the build context.
+pub struct DockerIgnore;
+
+impl GeneratedFile for DockerIgnore {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join(".dockerignore")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        r#"node_modules/
+dist/
+.env
+.env.*
+.git/
+*.log
+"#
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_name() {
+        let content = Dockerfile::new("myapp", &[]).render();
+        assert!(content.contains("--compile --outfile dist/myapp"));
+        assert!(content.contains("ENTRYPOINT [\"myapp\"]"));
+        assert!(content.contains("--no-install-recommends ca-certificates \\\n"));
+    }
+}
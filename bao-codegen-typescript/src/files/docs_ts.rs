@@ -0,0 +1,387 @@
+//! docs.ts generator for the `docs` subcommand.
+//!
+//! boune cannot describe its command tree at runtime, so the Markdown and
+//! man pages are rendered at bake time and embedded, like the completion
+//! scripts. boune has no hidden commands either, so `docs` is listed in help.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, to_kebab_case};
+use baobao_ir::{CommandOp, Example, Input, InputKind};
+use baobao_manifest::FlagCase;
+
+use super::{GENERATED_HEADER, completions_ts::template_literal};
+use crate::{
+    ast::{ArrowFn, Import, JsArray, JsObject},
+    code_file::{CodeFile, RawCode},
+    naming::flag_name,
+};
+
+/// Name of the generated docs subcommand.
+pub const DOCS_COMMAND: &str = "docs";
+
+/// Formats supported by the generated docs subcommand.
+const FORMATS: [&str; 2] = ["markdown", "man"];
+
+/// The docs.ts file defining the `docs` subcommand.
+pub struct DocsTs {
+    name: String,
+    description: Option<String>,
+    commands: Vec<CommandOp>,
+    flag_case: FlagCase,
+}
+
+/// A page of the docs: the root command when `path` is empty.
+struct Page<'a> {
+    path: Vec<&'a str>,
+    description: Option<&'a str>,
+    long_description: Option<&'a str>,
+    inputs: &'a [Input],
+    children: &'a [CommandOp],
+    examples: &'a [Example],
+}
+
+impl DocsTs {
+    pub fn new(
+        name: impl Into<String>,
+        description: Option<String>,
+        commands: Vec<CommandOp>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description,
+            commands,
+            flag_case: FlagCase::default(),
+        }
+    }
+
+    /// Set how flag names are spelled on the command line.
+    pub fn with_flag_case(mut self, flag_case: FlagCase) -> Self {
+        self.flag_case = flag_case;
+        self
+    }
+
+    /// Every page, the root command first.
+    fn pages(&self) -> Vec<Page<'_>> {
+        fn collect<'a>(cmd: &'a CommandOp, pages: &mut Vec<Page<'a>>) {
+            pages.push(Page {
+                path: cmd.path.iter().map(String::as_str).collect(),
+                description: Some(cmd.description.as_str()),
+                long_description: cmd.docs.long_description.as_deref(),
+                inputs: &cmd.inputs,
+                children: &cmd.children,
+                examples: &cmd.docs.examples,
+            });
+            for child in &cmd.children {
+                collect(child, pages);
+            }
+        }
+
+        let mut pages = vec![Page {
+            path: vec![],
+            description: self.description.as_deref(),
+            long_description: None,
+            inputs: &[],
+            children: &self.commands,
+            examples: &[],
+        }];
+        for cmd in &self.commands {
+            collect(cmd, &mut pages);
+        }
+        pages
+    }
+
+    /// Command line of a page, such as `my-app db migrate`.
+    fn command_line(&self, page: &Page<'_>) -> String {
+        let mut words = vec![self.name.as_str()];
+        words.extend(page.path.iter().copied());
+        words
+            .iter()
+            .map(|w| to_kebab_case(w))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Page file name: the command line joined by dashes.
+    fn file_name(&self, page: &Page<'_>, extension: &str) -> String {
+        format!(
+            "{}.{}",
+            self.command_line(page).replace(' ', "-"),
+            extension
+        )
+    }
+
+    fn child_file_name(&self, page: &Page<'_>, child: &CommandOp, extension: &str) -> String {
+        let mut path = page.path.clone();
+        path.push(&child.name);
+        let child_page = Page {
+            path,
+            description: None,
+            long_description: None,
+            inputs: &[],
+            children: &[],
+            examples: &[],
+        };
+        self.file_name(&child_page, extension)
+    }
+
+    fn usage(&self, page: &Page<'_>) -> String {
+        let mut usage = self.command_line(page);
+        if !page.children.is_empty() {
+            usage.push_str(" <command>");
+        }
+        if page.inputs.iter().any(is_flag) {
+            usage.push_str(" [options]");
+        }
+        for input in page.inputs.iter().filter(|i| !is_flag(i)) {
+            let name = to_kebab_case(&input.name);
+            let repeat = if input.multiple { "..." } else { "" };
+            if input.required {
+                usage.push_str(&format!(" <{}>{}", name, repeat));
+            } else {
+                usage.push_str(&format!(" [{}]{}", name, repeat));
+            }
+        }
+        usage
+    }
+
+    /// Spelling of an input: `<name>` for arguments, `--name, -n` for flags.
+    fn input_name(&self, input: &Input) -> String {
+        match input.kind {
+            InputKind::Positional => format!("<{}>", to_kebab_case(&input.name)),
+            InputKind::Flag { short } => {
+                let long = format!("--{}", flag_name(&input.name, self.flag_case));
+                match short {
+                    Some(c) => format!("{}, -{}", long, c),
+                    None => long,
+                }
+            }
+        }
+    }
+
+    /// Description of an input followed by its choices and default.
+    fn input_help(input: &Input) -> String {
+        let mut help = input.description.clone().unwrap_or_default();
+        if let Some(choices) = &input.choices {
+            help.push_str(&format!(" (one of: {})", choices.join(", ")));
+        }
+        if let Some(default) = &input.default {
+            help.push_str(&format!(" (default: {})", default.to_code_string()));
+        }
+        help.trim().to_string()
+    }
+
+    pub fn markdown(&self) -> Vec<(String, String)> {
+        self.pages()
+            .iter()
+            .map(|page| {
+                let mut out = format!("# {}\n\n", self.command_line(page));
+                if let Some(description) = page.description {
+                    out.push_str(&format!("{}\n\n", description));
+                }
+                if let Some(long_description) = page.long_description {
+                    out.push_str(&format!("{}\n\n", long_description.trim()));
+                }
+                out.push_str(&format!("## Usage\n\n```text\n{}\n```\n", self.usage(page)));
+
+                let sections = [
+                    (
+                        "Arguments",
+                        page.inputs.iter().filter(|i| !is_flag(i)).collect(),
+                    ),
+                    (
+                        "Options",
+                        page.inputs.iter().filter(|i| is_flag(i)).collect(),
+                    ),
+                ];
+                for (title, inputs) in sections {
+                    let inputs: Vec<&Input> = inputs;
+                    if inputs.is_empty() {
+                        continue;
+                    }
+                    out.push_str(&format!("\n## {}\n\n", title));
+                    for input in inputs {
+                        out.push_str(&format!(
+                            "- `{}`: {}\n",
+                            self.input_name(input),
+                            Self::input_help(input)
+                        ));
+                    }
+                }
+
+                if !page.children.is_empty() {
+                    out.push_str("\n## Commands\n\n");
+                    for child in page.children {
+                        out.push_str(&format!(
+                            "- [{}]({}): {}\n",
+                            to_kebab_case(&child.name),
+                            self.child_file_name(page, child, "md"),
+                            child.description
+                        ));
+                    }
+                }
+
+                if !page.examples.is_empty() {
+                    out.push_str("\n## Examples\n\n```sh\n");
+                    for example in page.examples {
+                        out.push_str(&format!("{}\n", example.command));
+                    }
+                    out.push_str("```\n");
+                }
+                (self.file_name(page, "md"), out)
+            })
+            .collect()
+    }
+
+    pub fn man(&self) -> Vec<(String, String)> {
+        self.pages()
+            .iter()
+            .map(|page| {
+                let title = self.command_line(page).replace(' ', "-");
+                let mut out = format!(".TH \"{}\" \"1\"\n", roff(&title.to_uppercase()));
+                out.push_str(".SH NAME\n");
+                match page.description {
+                    Some(description) => {
+                        out.push_str(&format!("{} \\- {}\n", roff(&title), roff(description)))
+                    }
+                    None => out.push_str(&format!("{}\n", roff(&title))),
+                }
+                out.push_str(&format!(".SH SYNOPSIS\n{}\n", roff(&self.usage(page))));
+                if let Some(long_description) = page.long_description {
+                    out.push_str(&format!(
+                        ".SH DESCRIPTION\n{}\n",
+                        roff(long_description.trim()).replace("\n\n", "\n.PP\n")
+                    ));
+                }
+
+                let sections = [
+                    (
+                        "ARGUMENTS",
+                        page.inputs.iter().filter(|i| !is_flag(i)).collect(),
+                    ),
+                    (
+                        "OPTIONS",
+                        page.inputs.iter().filter(|i| is_flag(i)).collect(),
+                    ),
+                ];
+                for (title, inputs) in sections {
+                    let inputs: Vec<&Input> = inputs;
+                    if inputs.is_empty() {
+                        continue;
+                    }
+                    out.push_str(&format!(".SH {}\n", title));
+                    for input in inputs {
+                        out.push_str(&format!(
+                            ".TP\n\\fB{}\\fR\n{}\n",
+                            roff(&self.input_name(input)),
+                            roff(&Self::input_help(input))
+                        ));
+                    }
+                }
+
+                if !page.children.is_empty() {
+                    out.push_str(".SH COMMANDS\n");
+                    for child in page.children {
+                        out.push_str(&format!(
+                            ".TP\n\\fB{}\\fR\n{}\n",
+                            roff(&to_kebab_case(&child.name)),
+                            roff(&child.description)
+                        ));
+                    }
+                }
+
+                if !page.examples.is_empty() {
+                    out.push_str(".SH EXAMPLES\n.nf\n");
+                    for example in page.examples {
+                        out.push_str(&format!("{}\n", roff(&example.command)));
+                    }
+                    out.push_str(".fi\n");
+                }
+                (self.file_name(page, "1"), out)
+            })
+            .collect()
+    }
+
+    fn build_command(&self) -> String {
+        let bundle = |pages: Vec<(String, String)>| {
+            pages
+                .into_iter()
+                .fold(JsObject::new(), |obj, (file, content)| {
+                    obj.raw(format!("{:?}", file), template_literal(&content))
+                })
+        };
+        let pages = JsObject::new()
+            .object("markdown", bundle(self.markdown()))
+            .object("man", bundle(self.man()));
+
+        let format = JsObject::new()
+            .string("type", "string")
+            .array("choices", JsArray::from_strings(FORMATS).as_const())
+            .string("default", "markdown")
+            .string("description", "Format of the pages");
+        let out = JsObject::new()
+            .string("type", "string")
+            .string("default", "docs")
+            .string("description", "Directory to write the pages to");
+        let options = JsObject::new().object("format", format).object("out", out);
+
+        let command = JsObject::new()
+            .string("name", DOCS_COMMAND)
+            .string(
+                "description",
+                "Write Markdown or man pages for every command",
+            )
+            .raw("options", "options")
+            .arrow_fn(
+                "action",
+                ArrowFn::new("{ options }")
+                    .body_line("mkdirSync(options.out, { recursive: true });")
+                    .body_line(
+                        "for (const [file, content] of Object.entries(pages[options.format])) {",
+                    )
+                    .body_line("  writeFileSync(join(options.out, file), content);")
+                    .body_line("}")
+                    .body_line("console.log(`Wrote docs to ${options.out}`);"),
+            );
+
+        format!(
+            "const pages = {} as const;\n\nconst options = {} as const;\n\nexport const docsCommand = defineCommand({});",
+            pages.build().trim_end(),
+            options.build().trim_end(),
+            command.build().trim_end()
+        )
+    }
+}
+
+fn is_flag(input: &Input) -> bool {
+    matches!(input.kind, InputKind::Flag { .. })
+}
+
+/// Escape text for roff.
+fn roff(s: &str) -> String {
+    s.replace('\\', "\\e").replace('-', "\\-")
+}
+
+impl GeneratedFile for DocsTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("docs.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(
+                Import::new("node:fs")
+                    .named("mkdirSync")
+                    .named("writeFileSync"),
+            )
+            .import(Import::new("node:path").named("join"))
+            .import(Import::new("boune").named("defineCommand"))
+            .add(RawCode::new(self.build_command()))
+            .render()
+    }
+}
@@ -0,0 +1,244 @@
+//! doctor.ts generator for the `doctor` subcommand.
+//!
+//! bun:sqlite is the only database driver doctor opens, so PostgreSQL, MySQL
+//! and DuckDB databases are checked for their environment variable only.
+//! HTTP clients with a base URL get a request sent to it, and the config and
+//! secrets files a check that they load.
+
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::schema::ContextFieldInfo;
+use baobao_core::{ContextFieldType, DatabaseType, FileRules, GeneratedFile};
+use baobao_ir::SecretsBackend;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::{ArrowFn, Import, JsObject},
+    code_file::{CodeFile, RawCode},
+};
+
+/// Name of the generated doctor subcommand.
+pub const DOCTOR_COMMAND: &str = "doctor";
+
+const DESCRIPTION: &str = "Check that the environment and resources the CLI needs are in place";
+
+/// The type of an entry of `checks`.
+const CHECK_TYPE: &str = "{ name: string; run: () => void | Promise<void> }";
+
+const REQUIRE_ENV: &str = r#"function requireEnv(name: string): string {
+  const value = process.env[name];
+  if (!value) {
+    throw new Error("not set");
+  }
+  return value;
+}"#;
+
+const OPEN_SQLITE: &str = r#"/** Open an existing database read-only and run a query. */
+function openSqlite(path: string): void {
+  const db = new Database(path.replace(/^sqlite:(\/\/)?/, ""), { readonly: true });
+  try {
+    db.query("SELECT 1").get();
+  } finally {
+    db.close();
+  }
+}"#;
+
+const REACHABLE: &str = r#"/** Send a HEAD request; any response means the endpoint is up. */
+async function reachable(url: string): Promise<void> {
+  await fetch(url, { method: "HEAD", signal: AbortSignal.timeout(10_000) });
+}"#;
+
+const READ_IF_EXISTS: &str = r#"/** Read a file that may be missing, throwing if it exists but can't be read. */
+function readIfExists(path: string): void {
+  if (existsSync(path)) {
+    readFileSync(path);
+  }
+}"#;
+
+/// The doctor.ts file defining the `doctor` subcommand.
+pub struct DoctorTs {
+    fields: Vec<ContextFieldInfo>,
+}
+
+/// A check: its report line and the statement running it.
+struct Check {
+    name: String,
+    run: String,
+    is_async: bool,
+}
+
+impl Check {
+    fn new(name: String, run: String) -> Self {
+        Self {
+            name,
+            run,
+            is_async: false,
+        }
+    }
+}
+
+impl DoctorTs {
+    pub fn new(fields: Vec<ContextFieldInfo>) -> Self {
+        Self { fields }
+    }
+
+    fn checks(&self) -> Vec<Check> {
+        let mut env_vars: Vec<&str> = Vec::new();
+        for var in self.fields.iter().flat_map(|f| f.required_env_vars()) {
+            if !env_vars.contains(&var) {
+                env_vars.push(var);
+            }
+        }
+
+        env_vars
+            .into_iter()
+            .map(|var| Check::new(format!("{} is set", var), format!("requireEnv({:?})", var)))
+            .chain(self.fields.iter().filter_map(Self::field_check))
+            .collect()
+    }
+
+    /// The check of a resource beyond its environment variables, if it has one.
+    fn field_check(field: &ContextFieldInfo) -> Option<Check> {
+        match field.field_type {
+            ContextFieldType::Database(DatabaseType::Sqlite) => {
+                let path = match field.database_path() {
+                    Some(path) => format!("{:?}", path),
+                    None => format!("requireEnv({:?})", field.env_var),
+                };
+                Some(Check::new(
+                    format!("database `{}` is reachable", field.name),
+                    format!("openSqlite({})", path),
+                ))
+            }
+            ContextFieldType::Http => {
+                let base_url = field.http.as_ref()?.base_url.as_deref()?;
+                Some(Check {
+                    name: format!("http `{}` is reachable", field.name),
+                    run: format!("await reachable({:?})", base_url),
+                    is_async: true,
+                })
+            }
+            ContextFieldType::Config => Some(Check::new(
+                format!("config file `{}` parses", field.config.as_ref()?.path),
+                "loadConfig()".to_string(),
+            )),
+            ContextFieldType::Secrets => {
+                let secrets = field.secrets.as_ref()?;
+                (secrets.backend == SecretsBackend::File).then(|| {
+                    Check::new(
+                        format!("secrets file `{}` is readable", secrets.path),
+                        format!("readIfExists({:?})", secrets.path),
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn has_field_type(&self, field_type: ContextFieldType) -> bool {
+        self.fields.iter().any(|f| f.field_type == field_type)
+    }
+
+    fn build_checks(&self) -> String {
+        let checks: Vec<String> = self
+            .checks()
+            .into_iter()
+            .map(|check| {
+                let async_kw = if check.is_async { "async " } else { "" };
+                format!(
+                    "  {{ name: {:?}, run: {}() => {{ {}; }} }},",
+                    check.name, async_kw, check.run
+                )
+            })
+            .collect();
+        if checks.is_empty() {
+            return format!("const checks: {}[] = [];", CHECK_TYPE);
+        }
+        format!(
+            "const checks: {}[] = [\n{}\n];",
+            CHECK_TYPE,
+            checks.join("\n")
+        )
+    }
+
+    fn build_command(&self) -> String {
+        let command = JsObject::new()
+            .string("name", DOCTOR_COMMAND)
+            .string("description", DESCRIPTION)
+            .arrow_fn(
+                "action",
+                ArrowFn::new("")
+                    .async_()
+                    .body_line("if (checks.length === 0) {")
+                    .body_line("  console.log(\"Nothing to check\");")
+                    .body_line("  return;")
+                    .body_line("}")
+                    .body_line("let failed = 0;")
+                    .body_line("for (const check of checks) {")
+                    .body_line("  try {")
+                    .body_line("    await check.run();")
+                    .body_line("    console.log(`✓ ${check.name}`);")
+                    .body_line("  } catch (error) {")
+                    .body_line("    failed++;")
+                    .body_line(
+                        "    console.log(`✗ ${check.name}: ${error instanceof Error ? error.message : error}`);",
+                    )
+                    .body_line("  }")
+                    .body_line("}")
+                    .body_line("if (failed > 0) {")
+                    .body_line("  console.error(`${failed} of ${checks.length} checks failed`);")
+                    .body_line("  process.exitCode = 1;")
+                    .body_line("  return;")
+                    .body_line("}")
+                    .body_line("console.log(`All ${checks.length} checks passed`);"),
+            );
+        format!(
+            "export const doctorCommand = defineCommand({});",
+            command.build().trim_end()
+        )
+    }
+}
+
+impl GeneratedFile for DoctorTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("doctor.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let checks = self.checks();
+        let uses = |helper: &str| checks.iter().any(|c| c.run.contains(helper));
+        let mut file = CodeFile::new().add(RawCode::new(GENERATED_HEADER));
+        if uses("openSqlite") {
+            file = file.import(Import::new("bun:sqlite").named("Database"));
+        }
+        if uses("readIfExists") {
+            file = file.import(
+                Import::new("node:fs")
+                    .named("existsSync")
+                    .named("readFileSync"),
+            );
+        }
+        file = file.import(Import::new("boune").named("defineCommand"));
+        if self.has_field_type(ContextFieldType::Config) {
+            file = file.import(Import::new("./config.ts").named("loadConfig"));
+        }
+        file = file.add(RawCode::new(self.build_checks()));
+        if uses("requireEnv") {
+            file = file.add(RawCode::new(REQUIRE_ENV));
+        }
+        if uses("openSqlite") {
+            file = file.add(RawCode::new(OPEN_SQLITE));
+        }
+        if uses("reachable") {
+            file = file.add(RawCode::new(REACHABLE));
+        }
+        if uses("readIfExists") {
+            file = file.add(RawCode::new(READ_IF_EXISTS));
+        }
+        file.add(RawCode::new(self.build_command())).render()
+    }
+}
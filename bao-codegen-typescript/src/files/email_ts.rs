@@ -0,0 +1,78 @@
+//! email.ts generator for the `[context.email]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{EmailOptions, SmtpTls};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::{Import, JsObject},
+    code_file::{CodeFile, RawCode},
+};
+
+/// Version of the `nodemailer` package.
+pub const NODEMAILER_VERSION: &str = "^6.9.16";
+
+/// Version of the `@types/nodemailer` package.
+pub const NODEMAILER_TYPES_VERSION: &str = "^6.4.17";
+
+/// The email.ts file with a nodemailer SMTP transport.
+///
+/// The transport connects when the first message is sent, and reads its
+/// credentials from the configured variables.
+pub struct EmailTs {
+    pub options: EmailOptions,
+}
+
+impl EmailTs {
+    pub fn new(options: EmailOptions) -> Self {
+        Self { options }
+    }
+
+    fn transport(&self) -> String {
+        let mut options = JsObject::new()
+            .string("host", &self.options.host)
+            .raw("port", self.options.port.to_string());
+        options = match self.options.tls {
+            SmtpTls::Starttls => options.raw("requireTLS", "true"),
+            SmtpTls::Tls => options.raw("secure", "true"),
+            SmtpTls::None => options.raw("ignoreTLS", "true"),
+        };
+        if let Some((username, password)) = self.options.credentials() {
+            options = options.object(
+                "auth",
+                JsObject::new()
+                    .raw("user", format!("process.env.{}", username))
+                    .raw("pass", format!("process.env.{}", password)),
+            );
+        }
+        format!(
+            "/** SMTP transport for `{}`, also exposed as `ctx.email`. */\nexport const email: Transporter = createTransport({});",
+            self.options.host,
+            options.build()
+        )
+    }
+}
+
+impl GeneratedFile for EmailTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("email.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(vec![
+                Import::new("nodemailer")
+                    .named("createTransport")
+                    .named_type("Transporter"),
+            ])
+            .add(RawCode::new(self.transport()))
+            .render()
+    }
+}
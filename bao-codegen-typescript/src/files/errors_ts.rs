@@ -0,0 +1,78 @@
+//! errors.ts generator for handler error reporting and exit codes.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// The errors.ts file with `CliError` and the `reportError()` helper.
+///
+/// Every command action catches handler errors and passes them to
+/// `reportError()`, which prints the message and sets the exit code from the
+/// command's `exit_codes` table, a `CliError`'s own code, or 1.
+pub struct ErrorsTs;
+
+impl ErrorsTs {
+    fn body() -> &'static str {
+        r#"/** An error carrying the process exit code to use. */
+export class CliError extends Error {
+  constructor(
+    message: string,
+    readonly exitCode = 1,
+    options?: ErrorOptions,
+  ) {
+    super(message, options);
+    this.name = new.target.name;
+  }
+}
+
+/** Exit codes keyed by error name, as declared in bao.toml. */
+export type ExitCodes = Readonly<Record<string, number>>;
+
+/** Resolve the exit code for an error thrown by a handler. */
+export function exitCodeFor(err: unknown, codes: ExitCodes = {}): number {
+  if (err instanceof Error) {
+    const mapped = codes[err.name];
+    if (mapped !== undefined) return mapped;
+    if (err instanceof CliError) return err.exitCode;
+  }
+  return 1;
+}
+
+/**
+ * Print an error and set the exit code.
+ *
+ * Set `DEBUG` to also print the stack trace.
+ */
+export function reportError(err: unknown, codes: ExitCodes = {}): void {
+  error(err instanceof Error ? err.message : String(err));
+  if (process.env.DEBUG && err instanceof Error && err.stack) {
+    console.error(palette().dim(err.stack));
+  }
+  process.exitCode = exitCodeFor(err, codes);
+}"#
+    }
+}
+
+impl GeneratedFile for ErrorsTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("errors.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("./ui.ts").named("error").named("palette"))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
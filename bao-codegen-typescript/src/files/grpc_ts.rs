@@ -0,0 +1,56 @@
+//! grpc.ts generator for the `[context.grpc]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_codegen::adapters::GrpcAdapter;
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::GrpcOptions;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ConnectAdapter,
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// The grpc.ts file with a connect-es gRPC transport for the endpoint.
+///
+/// Clients are created from the transport and the service descriptors that
+/// `buf generate` writes to `src/gen`, e.g.
+/// `createClient(GreeterService, ctx.grpc)`.
+pub struct GrpcTs {
+    pub options: GrpcOptions,
+}
+
+impl GrpcTs {
+    pub fn new(options: GrpcOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl GeneratedFile for GrpcTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("grpc.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let adapter = ConnectAdapter::new();
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(vec![
+                Import::new("@connectrpc/connect").named_type(adapter.channel_type()),
+                Import::new("@connectrpc/connect-node").named("createGrpcTransport"),
+            ])
+            .add(RawCode::new(format!(
+                "/** Transport for `{}`, also exposed as `ctx.grpc`. */\nexport const grpc: {} = {};",
+                self.options.endpoint,
+                adapter.channel_type(),
+                adapter.channel_init(&self.options)
+            )))
+            .render()
+    }
+}
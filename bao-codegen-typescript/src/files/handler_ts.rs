@@ -3,10 +3,11 @@
 use std::path::{Path, PathBuf};
 
 use baobao_core::{FileRules, GeneratedFile, to_kebab_case, to_pascal_case};
+use baobao_manifest::ImportExtension;
 
 use crate::{
     ast::{Fn, Import, Param},
-    code_file::CodeFile,
+    code_file::{CodeFile, RawCode},
 };
 
 /// Marker string indicating an unmodified TypeScript handler stub.
@@ -24,6 +25,12 @@ pub struct HandlerTs {
     pub has_args: bool,
     /// Whether the command has options/flags
     pub has_options: bool,
+    /// Path segments of the nearest parent command that resolves shared state
+    pub parent_state: Option<Vec<String>>,
+    /// Extension used for relative import specifiers
+    pub import_extension: ImportExtension,
+    /// Example command lines, listed in the handler's doc comment
+    pub examples: Vec<String>,
 }
 
 impl HandlerTs {
@@ -34,6 +41,9 @@ impl HandlerTs {
             path_segments: vec![cmd],
             has_args: true,
             has_options: false,
+            parent_state: None,
+            import_extension: ImportExtension::default(),
+            examples: Vec::new(),
         }
     }
 
@@ -49,9 +59,30 @@ impl HandlerTs {
             path_segments,
             has_args,
             has_options,
+            parent_state: None,
+            import_extension: ImportExtension::default(),
+            examples: Vec::new(),
         }
     }
 
+    /// Set the extension used for relative import specifiers.
+    pub fn with_import_extension(mut self, extension: ImportExtension) -> Self {
+        self.import_extension = extension;
+        self
+    }
+
+    /// List the command's examples in the handler's doc comment.
+    pub fn with_examples(mut self, examples: Vec<String>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// Receive the state resolved by the parent command at `parent_path`.
+    pub fn with_parent_state(mut self, parent_path: Vec<String>) -> Self {
+        self.parent_state = Some(parent_path);
+        self
+    }
+
     fn build_import(&self) -> Import {
         let pascal = to_pascal_case(&self.command);
 
@@ -81,6 +112,9 @@ impl HandlerTs {
         let pascal = to_pascal_case(&self.command);
 
         let mut handler = Fn::new("run").async_();
+        if !self.examples.is_empty() {
+            handler = handler.doc(format!("@example\n{}", self.examples.join("\n")));
+        }
 
         if self.has_args {
             handler = handler.param(Param::new("args", format!("{}Args", pascal)));
@@ -88,13 +122,25 @@ impl HandlerTs {
         if self.has_options {
             handler = handler.param(Param::new("options", format!("{}Options", pascal)));
         }
+        if let Some(parent) = &self.parent_state {
+            handler = handler.param(Param::new("state", state_type(parent)));
+        }
 
         // Build console.log based on what's available
-        let log_args = match (self.has_args, self.has_options) {
-            (true, true) => "console.log(args, options);",
-            (true, false) => "console.log(args);",
-            (false, true) => "console.log(options);",
-            (false, false) => "// no args or options",
+        let mut logged = Vec::new();
+        if self.has_args {
+            logged.push("args");
+        }
+        if self.has_options {
+            logged.push("options");
+        }
+        if self.parent_state.is_some() {
+            logged.push("state");
+        }
+        let log_args = if logged.is_empty() {
+            "// no args or options".to_string()
+        } else {
+            format!("console.log({});", logged.join(", "))
         };
 
         handler
@@ -104,7 +150,98 @@ impl HandlerTs {
     }
 }
 
-impl GeneratedFile for HandlerTs {
+/// Name of the state type exported by a parent command's state handler.
+fn state_type(path: &[String]) -> String {
+    let name = path.last().map(String::as_str).unwrap_or_default();
+    format!("{}State", to_pascal_case(name))
+}
+
+/// Import the state type of the parent command at `parent` from a handler
+/// nested `depth` segments below `handlers/`.
+fn state_import(parent: &[String], depth: usize) -> Import {
+    let parent_path = parent
+        .iter()
+        .map(|s| to_kebab_case(s))
+        .collect::<Vec<_>>()
+        .join("/");
+    let up_path = match depth.saturating_sub(1) {
+        0 => "./".to_string(),
+        n => "../".repeat(n),
+    };
+    Import::new(format!("{}{}.ts", up_path, parent_path)).named_type(state_type(parent))
+}
+
+/// A state handler stub for a parent command with flags.
+///
+/// The exported `state()` function turns the parent's options into state
+/// that is shared with every subcommand handler.
+pub struct StateHandlerTs {
+    pub command: String,
+    /// Path segments of the parent command
+    pub path_segments: Vec<String>,
+    /// Path segments of the nearest ancestor that also resolves state
+    pub parent_state: Option<Vec<String>>,
+    /// Extension used for relative import specifiers
+    pub import_extension: ImportExtension,
+}
+
+impl StateHandlerTs {
+    pub fn new(
+        command: impl Into<String>,
+        path_segments: Vec<String>,
+        parent_state: Option<Vec<String>>,
+    ) -> Self {
+        Self {
+            command: command.into(),
+            path_segments,
+            parent_state,
+            import_extension: ImportExtension::default(),
+        }
+    }
+
+    /// Set the extension used for relative import specifiers.
+    pub fn with_import_extension(mut self, extension: ImportExtension) -> Self {
+        self.import_extension = extension;
+        self
+    }
+
+    fn build_state_type(&self) -> String {
+        let pascal = to_pascal_case(&self.command);
+        match &self.parent_state {
+            Some(parent) => format!(
+                "export type {}State = {} & {}Options;",
+                pascal,
+                state_type(parent),
+                pascal
+            ),
+            None => format!("export type {}State = {}Options;", pascal, pascal),
+        }
+    }
+
+    fn build_handler(&self) -> Fn {
+        let pascal = to_pascal_case(&self.command);
+
+        let mut handler = Fn::new("state")
+            .async_()
+            .param(Param::new("options", format!("{}Options", pascal)));
+        if let Some(parent) = &self.parent_state {
+            handler = handler.param(Param::new("parent", state_type(parent)));
+        }
+
+        let result = if self.parent_state.is_some() {
+            "return { ...parent, ...options };"
+        } else {
+            "return options;"
+        };
+
+        handler
+            .returns(format!("Promise<{}State>", pascal))
+            .body_line(format!("// TODO: implement {} state", self.command))
+            .body_line(result)
+    }
+}
+
+impl GeneratedFile for StateHandlerTs {
     fn path(&self, base: &Path) -> PathBuf {
         let file_name = to_kebab_case(&self.command);
         base.join(format!("{}.ts", file_name))
@@ -115,9 +252,83 @@ impl GeneratedFile for HandlerTs {
     }
 
     fn render(&self) -> String {
-        CodeFile::new()
-            .import(self.build_import())
+        let depth = self.path_segments.len();
+        let command_path = self
+            .path_segments
+            .iter()
+            .map(|s| to_kebab_case(s))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut file = CodeFile::new()
+            .import_extension(self.import_extension)
+            .import(
+                Import::new(format!(
+                    "{}commands/{}.ts",
+                    "../".repeat(depth),
+                    command_path
+                ))
+                .named_type(format!("{}Options", to_pascal_case(&self.command))),
+            );
+        if let Some(parent) = &self.parent_state {
+            file = file.import(state_import(parent, depth));
+        }
+        file.add(RawCode::new(self.build_state_type()))
             .add(self.build_handler())
             .render()
     }
 }
+
+impl GeneratedFile for HandlerTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        let file_name = to_kebab_case(&self.command);
+        base.join(format!("{}.ts", file_name))
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        let mut file = CodeFile::new()
+            .import_extension(self.import_extension)
+            .import(self.build_import());
+        if let Some(parent) = &self.parent_state {
+            file = file.import(state_import(parent, self.path_segments.len()));
+        }
+        file.add(self.build_handler()).render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_handler_receives_parent_state() {
+        let content = HandlerTs::nested("migrate", path(&["db", "migrate"]), false, true)
+            .with_parent_state(path(&["db"]))
+            .render();
+
+        assert!(content.contains("import { type DbState } from \"../db.ts\";"));
+        assert!(content.contains("options: MigrateOptions, state: DbState"));
+        assert!(content.contains("console.log(options, state);"));
+    }
+
+    #[test]
+    fn test_state_handler_chains_parent_state() {
+        let content =
+            StateHandlerTs::new("tables", path(&["db", "tables"]), Some(path(&["db"]))).render();
+
+        assert!(
+            content.contains("import { type TablesOptions } from \"../../commands/db/tables.ts\";")
+        );
+        assert!(content.contains("import { type DbState } from \"../db.ts\";"));
+        assert!(content.contains("export type TablesState = DbState & TablesOptions;"));
+        assert!(content.contains("return { ...parent, ...options };"));
+    }
+}
@@ -0,0 +1,231 @@
+//! http.ts generator for the `[context.http]` clients.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{HttpAuth, HttpClientResource};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::JsObject,
+    code_file::{CodeFile, RawCode},
+};
+
+const HTTP_CLIENT: &str = r#"/** Credentials attached to every request, read from the `env` variable. */
+export type HttpAuth =
+  | { type: "bearer"; env: string }
+  | { type: "basic"; username: string; env: string }
+  | { type: "header"; name: string; env: string };
+
+export interface HttpClientOptions {
+  /** Base URL that request paths are resolved against. */
+  baseUrl?: string;
+  /** Headers sent with every request. */
+  headers?: Record<string, string>;
+  /** Request timeout in milliseconds. */
+  timeout?: number;
+  /** Times a failed request is retried. */
+  retries?: number;
+  /** Delay before the first retry in milliseconds, doubled for each retry. */
+  backoff?: number;
+  /** Response statuses that are retried, 408, 429 and 5xx by default. */
+  retryOn?: number[];
+  /** Credentials attached to every request. */
+  auth?: HttpAuth;
+}
+
+export interface HttpClient {
+  request(method: string, path: string, init?: RequestInit): Promise<Response>;
+  get(path: string, init?: RequestInit): Promise<Response>;
+  post(path: string, body?: unknown, init?: RequestInit): Promise<Response>;
+  put(path: string, body?: unknown, init?: RequestInit): Promise<Response>;
+  patch(path: string, body?: unknown, init?: RequestInit): Promise<Response>;
+  delete(path: string, init?: RequestInit): Promise<Response>;
+}
+
+const isJson = (body: unknown) =>
+  typeof body === "object" &&
+  body !== null &&
+  (Array.isArray(body) || Object.getPrototypeOf(body) === Object.prototype);
+
+const authHeader = (auth: HttpAuth): [string, string] => {
+  const secret = process.env[auth.env];
+  if (secret === undefined) {
+    throw new Error(`${auth.env} is not set`);
+  }
+  switch (auth.type) {
+    case "bearer":
+      return ["authorization", `Bearer ${secret}`];
+    case "basic": {
+      const credentials = Buffer.from(`${auth.username}:${secret}`).toString("base64");
+      return ["authorization", `Basic ${credentials}`];
+    }
+    case "header":
+      return [auth.name, secret];
+  }
+};
+
+const isRetryable = (status: number, retryOn?: number[]) =>
+  retryOn ? retryOn.includes(status) : status === 408 || status === 429 || status >= 500;
+
+/** Create a fetch wrapper applying `options` to every request. */
+export function createHttpClient(options: HttpClientOptions = {}): HttpClient {
+  const url = (path: string) =>
+    options.baseUrl
+      ? `${options.baseUrl.replace(/\/+$/, "")}/${path.replace(/^\/+/, "")}`
+      : path;
+
+  const request = async (method: string, path: string, init: RequestInit = {}) => {
+    const headers = new Headers(options.headers);
+    if (options.auth) {
+      headers.set(...authHeader(options.auth));
+    }
+    new Headers(init.headers).forEach((value, key) => headers.set(key, value));
+    // A streamed body is consumed by the first attempt
+    const retries = init.body instanceof ReadableStream ? 0 : (options.retries ?? 0);
+    for (let attempt = 0; ; attempt++) {
+      const signal =
+        init.signal ?? (options.timeout ? AbortSignal.timeout(options.timeout) : undefined);
+      const last = attempt >= retries;
+      try {
+        const response = await fetch(url(path), { ...init, method, headers, signal });
+        if (last || !isRetryable(response.status, options.retryOn)) {
+          return response;
+        }
+        await response.body?.cancel();
+      } catch (error) {
+        if (last || init.signal?.aborted) {
+          throw error;
+        }
+      }
+      await Bun.sleep((options.backoff ?? 1000) * 2 ** attempt);
+    }
+  };
+
+  // Plain objects and arrays are sent as JSON, other bodies as they are
+  const withBody =
+    (method: string) =>
+    (path: string, body?: unknown, init: RequestInit = {}) => {
+      if (!isJson(body)) {
+        return request(method, path, { ...init, body: body as BodyInit | undefined });
+      }
+      const headers = new Headers(init.headers);
+      if (!headers.has("content-type")) {
+        headers.set("content-type", "application/json");
+      }
+      return request(method, path, { ...init, headers, body: JSON.stringify(body) });
+    };
+
+  return {
+    request,
+    get: (path, init) => request("GET", path, init),
+    post: withBody("POST"),
+    put: withBody("PUT"),
+    patch: withBody("PATCH"),
+    delete: (path, init) => request("DELETE", path, init),
+  };
+}"#;
+
+/// The http.ts file with a fetch wrapper per configured HTTP client.
+pub struct HttpTs {
+    pub clients: Vec<HttpClientResource>,
+}
+
+impl HttpTs {
+    pub fn new(clients: Vec<HttpClientResource>) -> Self {
+        Self { clients }
+    }
+
+    fn client(http: &HttpClientResource) -> String {
+        let options = &http.options;
+        let mut headers = JsObject::new();
+        for (name, value) in &options.headers {
+            headers = headers.string(format!("{:?}", name), value);
+        }
+        if let Some(user_agent) = &options.user_agent {
+            headers = headers.string("\"User-Agent\"", user_agent);
+        }
+        let config = JsObject::new()
+            .string_opt("baseUrl", options.base_url.as_deref())
+            .object_if(!headers.is_empty(), "headers", headers)
+            .raw_opt(
+                "timeout",
+                options
+                    .timeout
+                    .map(|timeout| timeout.as_millis().to_string()),
+            )
+            .raw_if(
+                options.has_retries(),
+                "retries",
+                options.retries.to_string(),
+            )
+            .raw_opt(
+                "backoff",
+                options
+                    .backoff
+                    .map(|backoff| backoff.as_millis().to_string()),
+            )
+            .raw_if(
+                !options.retry_on.is_empty(),
+                "retryOn",
+                format!(
+                    "[{}]",
+                    options
+                        .retry_on
+                        .iter()
+                        .map(u16::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        let config = match &options.auth {
+            Some(auth) => config.object("auth", Self::auth(auth)),
+            None => config,
+        };
+        let config = if config.is_empty() {
+            String::new()
+        } else {
+            config.build()
+        };
+        format!(
+            "/** HTTP client, also exposed as `ctx.{}`. */\nexport const {}: HttpClient = createHttpClient({});",
+            http.name, http.name, config
+        )
+    }
+
+    fn auth(auth: &HttpAuth) -> JsObject {
+        match auth {
+            HttpAuth::Bearer { env } => JsObject::new().string("type", "bearer").string("env", env),
+            HttpAuth::Basic { username, env } => JsObject::new()
+                .string("type", "basic")
+                .string("username", username)
+                .string("env", env),
+            HttpAuth::Header { name, env } => JsObject::new()
+                .string("type", "header")
+                .string("name", name)
+                .string("env", env),
+        }
+    }
+}
+
+impl GeneratedFile for HttpTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("http.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        self.clients
+            .iter()
+            .fold(
+                CodeFile::new()
+                    .add(RawCode::new(GENERATED_HEADER))
+                    .add(RawCode::new(HTTP_CLIENT)),
+                |file, http| file.add(RawCode::new(Self::client(http))),
+            )
+            .render()
+    }
+}
@@ -3,6 +3,7 @@
 use std::path::{Path, PathBuf};
 
 use baobao_core::{FileRules, GeneratedFile};
+use baobao_manifest::ImportExtension;
 
 use crate::{
     Shebang,
@@ -11,7 +12,22 @@ use crate::{
 };
 
 /// The index.ts entry point file.
-pub struct IndexTs;
+#[derive(Debug, Clone, Default)]
+pub struct IndexTs {
+    pub import_extension: ImportExtension,
+}
+
+impl IndexTs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the extension used for relative import specifiers.
+    pub fn with_import_extension(mut self, extension: ImportExtension) -> Self {
+        self.import_extension = extension;
+        self
+    }
+}
 
 impl GeneratedFile for IndexTs {
     fn path(&self, base: &Path) -> PathBuf {
@@ -19,14 +35,19 @@ impl GeneratedFile for IndexTs {
     }
 
     fn rules(&self) -> FileRules {
-        FileRules::create_once()
+        // Executable so the bun shebang works when run directly
+        FileRules::create_once().with_mode(0o755)
     }
 
     fn render(&self) -> String {
         CodeFile::new()
+            .import_extension(self.import_extension)
             .add(Shebang::bun())
             .import(Import::new("./cli.ts").named("app"))
-            .add(RawCode::new("app.run();"))
+            .import(Import::new("./errors.ts").named("reportError"))
+            .add(RawCode::new(
+                "process.on(\"unhandledRejection\", (err) => reportError(err));\n\napp.run();",
+            ))
             .render()
     }
 }
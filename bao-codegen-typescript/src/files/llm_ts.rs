@@ -0,0 +1,181 @@
+//! llm.ts generator for the `[context.llm]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{LlmOptions, LlmProvider};
+
+use super::GENERATED_HEADER;
+use crate::code_file::{CodeFile, RawCode};
+
+const TYPES: &str = r#"/** Who a message is from. */
+export type Role = "system" | "user" | "assistant";
+
+/** A message of a conversation. */
+export interface Message {
+  role: Role;
+  content: string;
+}
+
+export interface LlmClient {
+  /** The model requests are sent to. */
+  readonly model: string;
+  /** A client sending requests to `model` instead. */
+  withModel(model: string): LlmClient;
+  /** Reply to a single prompt. */
+  complete(prompt: string): Promise<string>;
+  /** Reply to a conversation. */
+  chat(messages: Message[]): Promise<string>;
+}"#;
+
+const API_KEY: &str = r#"function apiKey(): string {
+  const key = process.env[API_KEY_ENV];
+  if (!key) {
+    throw new Error(`${API_KEY_ENV} is not set`);
+  }
+  return key;
+}
+
+/** Parse the response body, throwing with the API's message on error statuses. */
+async function read(response: Response): Promise<any> {
+  const text = await response.text();
+  let body: any;
+  try {
+    body = JSON.parse(text);
+  } catch {
+    throw new Error(`LLM request failed (${response.status}): ${text}`);
+  }
+  if (!response.ok) {
+    throw new Error(`LLM request failed (${response.status}): ${body?.error?.message ?? text}`);
+  }
+  return body;
+}"#;
+
+const ANTHROPIC_CHAT: &str = r#"async function chat(model: string, messages: Message[]): Promise<string> {
+  const system = messages
+    .filter((m) => m.role === "system")
+    .map((m) => m.content)
+    .join("\n\n");
+  const response = await fetch(`${BASE_URL}/v1/messages`, {
+    method: "POST",
+    headers: {
+      "content-type": "application/json",
+      "x-api-key": apiKey(),
+      "anthropic-version": "2023-06-01",
+    },
+    body: JSON.stringify({
+      model,
+      max_tokens: MAX_TOKENS,
+      messages: messages.filter((m) => m.role !== "system"),
+      ...(system ? { system } : {}),
+    }),
+  });
+  const reply = await read(response);
+  return (reply.content as { text?: string }[]).map((block) => block.text ?? "").join("");
+}"#;
+
+const EXPORT: &str = r#"/** Create a client sending requests to `model`. The API key is read on each request. */
+export function createLlmClient(model: string = MODEL): LlmClient {
+  return {
+    model,
+    withModel: (other) => createLlmClient(other),
+    complete: (prompt) => chat(model, [{ role: "user", content: prompt }]),
+    chat: (messages) => chat(model, messages),
+  };
+}"#;
+
+/// The llm.ts file with the `llm` client.
+pub struct LlmTs {
+    pub options: LlmOptions,
+}
+
+impl LlmTs {
+    pub fn new(options: LlmOptions) -> Self {
+        Self { options }
+    }
+
+    fn max_tokens(&self) -> Option<u32> {
+        match self.options.provider {
+            LlmProvider::Anthropic => Some(
+                self.options
+                    .max_tokens
+                    .unwrap_or(LlmOptions::DEFAULT_MAX_TOKENS),
+            ),
+            LlmProvider::Openai => self.options.max_tokens,
+        }
+    }
+
+    fn consts(&self) -> String {
+        let mut consts = vec![
+            format!("const BASE_URL = {:?};", self.options.base_url),
+            format!("const API_KEY_ENV = {:?};", self.options.api_key_env),
+            format!("const MODEL = {:?};", self.options.model),
+        ];
+        if let Some(max_tokens) = self.max_tokens() {
+            consts.push(format!("const MAX_TOKENS = {};", max_tokens));
+        }
+        consts.join("\n")
+    }
+
+    fn interface(&self) -> String {
+        let api = match self.options.provider {
+            LlmProvider::Openai => "OpenAI Chat Completions API",
+            LlmProvider::Anthropic => "Anthropic Messages API",
+        };
+        TYPES.replace(
+            "export interface LlmClient",
+            &format!("/** Client for the {}. */\nexport interface LlmClient", api),
+        )
+    }
+
+    fn chat(&self) -> String {
+        match self.options.provider {
+            LlmProvider::Anthropic => ANTHROPIC_CHAT.to_string(),
+            LlmProvider::Openai => {
+                let max_tokens = if self.max_tokens().is_some() {
+                    ", max_completion_tokens: MAX_TOKENS"
+                } else {
+                    ""
+                };
+                format!(
+                    r#"async function chat(model: string, messages: Message[]): Promise<string> {{
+  const response = await fetch(`${{BASE_URL}}/chat/completions`, {{
+    method: "POST",
+    headers: {{
+      "content-type": "application/json",
+      authorization: `Bearer ${{apiKey()}}`,
+    }},
+    body: JSON.stringify({{ model, messages{max_tokens} }}),
+  }});
+  const reply = await read(response);
+  return reply.choices[0].message.content;
+}}"#
+                )
+            }
+        }
+    }
+}
+
+impl GeneratedFile for LlmTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("llm.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .add(RawCode::new(self.consts()))
+            .add(RawCode::new(self.interface()))
+            .add(RawCode::new(API_KEY))
+            .add(RawCode::new(self.chat()))
+            .add(RawCode::new(EXPORT))
+            .add(RawCode::new(
+                "/** LLM client, also exposed as `ctx.llm`. */\nexport const llm: LlmClient = createLlmClient();",
+            ))
+            .render()
+    }
+}
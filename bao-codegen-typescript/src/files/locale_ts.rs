@@ -0,0 +1,95 @@
+//! locale.ts generator for translated help text.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::Locale;
+
+use super::GENERATED_HEADER;
+use crate::code_file::{CodeFile, RawCode};
+
+/// The locale.ts file with the translation bundles and the `t()` helper.
+///
+/// Descriptions in cli.ts and the command files are wrapped in `t()`, which
+/// looks them up in the bundle for the locale in `LC_ALL`, `LC_MESSAGES` or
+/// `LANG` and falls back to the manifest's default locale.
+pub struct LocaleTs {
+    pub locales: Vec<Locale>,
+}
+
+impl LocaleTs {
+    pub fn new(locales: Vec<Locale>) -> Self {
+        Self { locales }
+    }
+
+    fn build_bundles(&self) -> String {
+        let mut lines = vec![
+            "/** Help text per locale, keyed by command path and input name. */".to_string(),
+            "const locales: Record<string, Record<string, string>> = {".to_string(),
+        ];
+        for locale in &self.locales {
+            lines.push(format!("  {:?}: {{", locale.name));
+            for message in &locale.messages {
+                let key = message_key(&message.command, message.input.as_deref());
+                lines.push(format!("    {:?}: {:?},", key, message.text));
+            }
+            lines.push("  },".to_string());
+        }
+        lines.push("};".to_string());
+        lines.join("\n")
+    }
+
+    fn body() -> &'static str {
+        r#"/**
+ * Bundle for the locale in `LC_ALL`, `LC_MESSAGES` or `LANG`, matching the
+ * full locale (`pt-BR`) before its language (`pt`).
+ */
+function detect(): Record<string, string> | undefined {
+  const env = process.env.LC_ALL || process.env.LC_MESSAGES || process.env.LANG || "";
+  const locale = env.split(/[.@]/)[0].replace(/_/g, "-").toLowerCase();
+  const find = (name: string) =>
+    Object.entries(locales).find(([key]) => key.replace(/_/g, "-").toLowerCase() === name)?.[1];
+  return find(locale) ?? find(locale.split("-")[0]);
+}
+
+const messages = detect();
+
+/** Translate help text, keeping the default locale's when missing. */
+export function t(key: string, fallback: string): string {
+  return messages?.[key] ?? fallback;
+}"#
+    }
+}
+
+/// Key of a description in the bundles: the command path joined by spaces
+/// (empty for the application), then `:` and the input name for inputs.
+pub fn message_key(command: &[String], input: Option<&str>) -> String {
+    let path = command.join(" ");
+    match input {
+        Some(input) => format!("{}:{}", path, input),
+        None => path,
+    }
+}
+
+/// A `t()` call translating `text`, for use as a raw property value.
+pub fn translate(key: &str, text: &str) -> String {
+    format!("t({:?}, {:?})", key, text)
+}
+
+impl GeneratedFile for LocaleTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("locale.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .add(RawCode::new(self.build_bundles()))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
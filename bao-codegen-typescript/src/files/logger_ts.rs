@@ -0,0 +1,165 @@
+//! logger.ts generator for the `[context.logging]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::LogFormat;
+
+use super::GENERATED_HEADER;
+use crate::code_file::{CodeFile, RawCode};
+
+/// The logger.ts file with a small leveled logger writing to stderr.
+///
+/// The filter comes from `--verbose`/`--quiet`, then `LOG_LEVEL`, then the
+/// manifest default. Filters use `RUST_LOG` syntax, e.g. `info,db=debug`,
+/// where targets name the loggers made with `logger.child(target)`.
+/// `LOG_FORMAT=json` switches to one JSON object per line.
+pub struct LoggerTs {
+    pub filter: String,
+    pub format: LogFormat,
+}
+
+impl LoggerTs {
+    pub fn new(filter: impl Into<String>, format: LogFormat) -> Self {
+        Self {
+            filter: filter.into(),
+            format,
+        }
+    }
+
+    fn defaults(&self) -> String {
+        format!(
+            "const DEFAULT_FILTER = {:?};\nconst DEFAULT_FORMAT: LogFormat = \"{}\";",
+            self.filter,
+            self.format.as_str()
+        )
+    }
+
+    fn types() -> &'static str {
+        r#"export type LogLevel = "trace" | "debug" | "info" | "warn" | "error";
+export type LogFormat = "pretty" | "json";
+
+export interface Logger {
+  trace(message: string, fields?: Record<string, unknown>): void;
+  debug(message: string, fields?: Record<string, unknown>): void;
+  info(message: string, fields?: Record<string, unknown>): void;
+  warn(message: string, fields?: Record<string, unknown>): void;
+  error(message: string, fields?: Record<string, unknown>): void;
+  /** A logger for `target`, filtered by the directives naming it. */
+  child(target: string): Logger;
+}
+
+/** A filter directive: a level, optionally limited to a target prefix. */
+interface Directive {
+  target?: string;
+  level: LogLevel;
+}
+
+const LEVELS: LogLevel[] = ["trace", "debug", "info", "warn", "error"];"#
+    }
+
+    fn body() -> &'static str {
+        r#"function isLevel(value: string | undefined): value is LogLevel {
+  return LEVELS.includes(value as LogLevel);
+}
+
+/** Resolve the active filter from CLI flags, then LOG_LEVEL, then the default. */
+function resolveFilter(argv: string[]): string {
+  if (argv.includes("--quiet") || argv.includes("-q")) return "error";
+  if (argv.includes("--verbose") || argv.includes("-v")) return "debug";
+  return process.env.LOG_LEVEL || DEFAULT_FILTER;
+}
+
+/** Parse `RUST_LOG`-style directives such as `info,db=debug`, skipping invalid ones. */
+function parseFilter(filter: string): Directive[] {
+  const directives: Directive[] = [];
+  for (const part of filter.split(",")) {
+    const [target, level] = part.includes("=") ? part.split("=", 2) : [undefined, part];
+    const name = level?.trim().toLowerCase();
+    if (isLevel(name) && target?.trim() !== "") {
+      directives.push({ target: target?.trim(), level: name });
+    }
+  }
+  return directives;
+}
+
+/** The level of the longest directive target that prefixes `target`, if any. */
+function levelFor(directives: Directive[], target: string | undefined): LogLevel | undefined {
+  let match: Directive | undefined;
+  for (const directive of directives) {
+    if (directive.target === undefined) {
+      match ??= directive;
+    } else if (
+      target?.startsWith(directive.target) &&
+      directive.target.length > (match?.target?.length ?? -1)
+    ) {
+      match = directive;
+    }
+  }
+  return match?.level;
+}
+
+function resolveFormat(): LogFormat {
+  const env = process.env.LOG_FORMAT?.toLowerCase();
+  return env === "json" || env === "pretty" ? env : DEFAULT_FORMAT;
+}
+
+/** Create a logger that writes the records `filter` enables to stderr. */
+export function createLogger(
+  filter: string = resolveFilter(process.argv),
+  format: LogFormat = resolveFormat(),
+  target?: string,
+): Logger {
+  const directives = parseFilter(filter);
+  const level = levelFor(directives, target);
+  // Records are dropped when no directive covers the target
+  const threshold = level === undefined ? LEVELS.length : LEVELS.indexOf(level);
+  const write = (at: LogLevel) => (message: string, fields?: Record<string, unknown>) => {
+    if (LEVELS.indexOf(at) < threshold) return;
+    if (format === "json") {
+      const record = { time: new Date().toISOString(), level: at, target, msg: message, ...fields };
+      process.stderr.write(`${JSON.stringify(record)}\n`);
+      return;
+    }
+    const extra = fields
+      ? Object.entries(fields)
+          .map(([key, value]) => ` ${key}=${JSON.stringify(value)}`)
+          .join("")
+      : "";
+    const prefix = target ? `${target}: ` : "";
+    process.stderr.write(`${at.toUpperCase().padEnd(5)} ${prefix}${message}${extra}\n`);
+  };
+
+  return {
+    trace: write("trace"),
+    debug: write("debug"),
+    info: write("info"),
+    warn: write("warn"),
+    error: write("error"),
+    child: (child: string) => createLogger(filter, format, child),
+  };
+}
+
+/** Shared logger instance, also exposed as `ctx.logger`. */
+export const logger: Logger = createLogger();"#
+    }
+}
+
+impl GeneratedFile for LoggerTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("logger.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .add(RawCode::new(Self::types()))
+            .add(RawCode::new(self.defaults()))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
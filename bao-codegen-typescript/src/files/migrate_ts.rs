@@ -0,0 +1,276 @@
+//! migrate.ts generator for SQLite migrations on bun:sqlite.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::{ArrowFn, Import, JsArray, JsObject},
+    code_file::{CodeFile, RawCode},
+};
+
+/// Name of the generated migrate subcommand.
+pub const MIGRATE_COMMAND: &str = "migrate";
+
+/// Table recording which migrations have been applied.
+const MIGRATIONS_TABLE: &str = "_bao_migrations";
+
+/// The migrate.ts file with the migration runner and `migrate` subcommand.
+///
+/// Migrations are read at runtime from `<version>_<name>.up.sql` and
+/// `<version>_<name>.down.sql` files (a plain `<version>_<name>.sql` is an
+/// irreversible up migration), matching the sqlx layout.
+pub struct MigrateTs {
+    /// Directory containing the migration files.
+    pub dir: String,
+    /// Database file path, if fixed in the manifest.
+    pub path: Option<String>,
+    /// Environment variable holding the database path otherwise.
+    pub env_var: String,
+}
+
+impl MigrateTs {
+    pub fn new(dir: impl Into<String>, path: Option<String>, env_var: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            path,
+            env_var: env_var.into(),
+        }
+    }
+
+    fn constants(&self) -> String {
+        format!(
+            "const MIGRATIONS_DIR = {};\nconst MIGRATIONS_TABLE = \"{}\";",
+            json_string(&self.dir),
+            MIGRATIONS_TABLE
+        )
+    }
+
+    fn open_database(&self) -> String {
+        let url = match &self.path {
+            Some(path) => format!("const url = {};", json_string(path)),
+            None => format!(
+                "const url = process.env.{env};\n  if (!url) {{\n    throw new Error(\"{env} is not set\");\n  }}",
+                env = self.env_var
+            ),
+        };
+        format!(
+            r#"/** Open the configured database, creating the file if needed. */
+export function openDatabase(): Database {{
+  {url}
+  return new Database(url.replace(/^sqlite:(\/\/)?/, ""), {{ create: true }});
+}}"#
+        )
+    }
+
+    fn runner() -> &'static str {
+        r#"export interface Migration {
+  version: string;
+  name: string;
+  up: string;
+  down?: string;
+}
+
+export interface MigrationStatus {
+  version: string;
+  name: string;
+  appliedAt: string | null;
+}
+
+/** Load migrations from disk, ordered by version. */
+export function loadMigrations(dir: string = MIGRATIONS_DIR): Migration[] {
+  const migrations = new Map<string, Migration>();
+  for (const file of readdirSync(dir)) {
+    const match = /^(\d+)_(.+?)(?:\.(up|down))?\.sql$/.exec(file);
+    if (!match) continue;
+    const [, version, name, direction] = match;
+    const sql = readFileSync(join(dir, file), "utf8");
+    const migration = migrations.get(version) ?? { version, name, up: "" };
+    if (direction === "down") {
+      migration.down = sql;
+    } else {
+      migration.up = sql;
+    }
+    migrations.set(version, migration);
+  }
+  return [...migrations.values()].sort((a, b) =>
+    a.version.localeCompare(b.version, undefined, { numeric: true }),
+  );
+}
+
+function ensureTable(db: Database): void {
+  db.exec(
+    `CREATE TABLE IF NOT EXISTS ${MIGRATIONS_TABLE} (version TEXT PRIMARY KEY, name TEXT NOT NULL, applied_at TEXT NOT NULL)`,
+  );
+}
+
+function appliedVersions(db: Database): Map<string, string> {
+  ensureTable(db);
+  const rows = db
+    .query(`SELECT version, applied_at FROM ${MIGRATIONS_TABLE}`)
+    .all() as { version: string; applied_at: string }[];
+  return new Map(rows.map((row) => [row.version, row.applied_at]));
+}
+
+/** Apply every pending migration in order, each in its own transaction. */
+export function apply(db: Database, migrations: Migration[] = loadMigrations()): Migration[] {
+  const applied = appliedVersions(db);
+  const pending = migrations.filter((m) => !applied.has(m.version));
+  for (const migration of pending) {
+    db.transaction(() => {
+      db.exec(migration.up);
+      db.run(`INSERT INTO ${MIGRATIONS_TABLE} (version, name, applied_at) VALUES (?, ?, ?)`, [
+        migration.version,
+        migration.name,
+        new Date().toISOString(),
+      ]);
+    })();
+  }
+  return pending;
+}
+
+/** Revert the most recently applied migrations. */
+export function rollback(
+  db: Database,
+  steps = 1,
+  migrations: Migration[] = loadMigrations(),
+): Migration[] {
+  const applied = appliedVersions(db);
+  const targets = migrations
+    .filter((m) => applied.has(m.version))
+    .reverse()
+    .slice(0, steps);
+  for (const migration of targets) {
+    if (migration.down === undefined) {
+      throw new Error(`Migration ${migration.version}_${migration.name} has no down script`);
+    }
+    db.transaction(() => {
+      db.exec(migration.down!);
+      db.run(`DELETE FROM ${MIGRATIONS_TABLE} WHERE version = ?`, [migration.version]);
+    })();
+  }
+  return targets;
+}
+
+/** List every known migration with the time it was applied, if any. */
+export function status(db: Database, migrations: Migration[] = loadMigrations()): MigrationStatus[] {
+  const applied = appliedVersions(db);
+  return migrations.map((m) => ({
+    version: m.version,
+    name: m.name,
+    appliedAt: applied.get(m.version) ?? null,
+  }));
+}
+
+function runMigrations(action: "up" | "down" | "status", steps: number): void {
+  const db = openDatabase();
+  try {
+    if (action === "status") {
+      for (const m of status(db)) {
+        console.log(`${m.appliedAt ? "applied" : "pending"}  ${m.version}_${m.name}`);
+      }
+      return;
+    }
+    const changed = action === "down" ? rollback(db, steps) : apply(db);
+    const verb = action === "down" ? "Reverted" : "Applied";
+    for (const m of changed) {
+      console.log(`${verb} ${m.version}_${m.name}`);
+    }
+    if (changed.length === 0) {
+      console.log("Nothing to do.");
+    }
+  } finally {
+    db.close();
+  }
+}"#
+    }
+
+    fn build_command() -> String {
+        let action = JsObject::new()
+            .string("type", "string")
+            .string("default", "up")
+            .string("description", "Migration action to run")
+            .array(
+                "choices",
+                JsArray::from_strings(["up", "down", "status"]).as_const(),
+            );
+        let args = JsObject::new().object("action", action);
+
+        let steps = JsObject::new()
+            .string("type", "number")
+            .raw("default", "1")
+            .string("description", "Number of migrations to revert with `down`");
+        let options = JsObject::new().object("steps", steps);
+
+        let command = JsObject::new()
+            .string("name", MIGRATE_COMMAND)
+            .string("description", "Apply, revert or list database migrations")
+            .raw("arguments", "args")
+            .raw("options", "options")
+            .arrow_fn(
+                "action",
+                ArrowFn::new("{ args, options }")
+                    .body_line("runMigrations(args.action, options.steps);"),
+            );
+
+        format!(
+            "const args = {} as const;\n\nconst options = {} as const;\n\nexport const migrateCommand = defineCommand({});",
+            args.build().trim_end(),
+            options.build().trim_end(),
+            command.build().trim_end()
+        )
+    }
+}
+
+/// Render a string as a double-quoted JavaScript literal.
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl GeneratedFile for MigrateTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("migrate.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("bun:sqlite").named("Database"))
+            .import(
+                Import::new("node:fs")
+                    .named("readdirSync")
+                    .named("readFileSync"),
+            )
+            .import(Import::new("node:path").named("join"))
+            .import(Import::new("boune").named("defineCommand"))
+            .add(RawCode::new(self.constants()))
+            .add(RawCode::new(Self::runner()))
+            .add(RawCode::new(self.open_database()))
+            .add(RawCode::new(Self::build_command()))
+            .render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_path_from_env() {
+        let content = MigrateTs::new("migrations", None, "DATABASE_URL").render();
+        assert!(content.contains("const url = process.env.DATABASE_URL;"));
+        assert!(content.contains("const MIGRATIONS_DIR = \"migrations\";"));
+    }
+
+    #[test]
+    fn test_fixed_path() {
+        let content =
+            MigrateTs::new("db/migrations", Some("app.db".into()), "DATABASE_URL").render();
+        assert!(content.contains("const url = \"app.db\";"));
+    }
+}
@@ -3,21 +3,69 @@
 // Re-export from bao-core for backwards compatibility
 pub use baobao_core::GENERATED_HEADER;
 
+mod buf_gen_yaml;
+mod build_info_ts;
+mod cache_ts;
 mod cli_ts;
 mod command_ts;
+mod completions_ts;
+mod config_ts;
 mod context_ts;
+mod dockerfile;
+mod docs_ts;
+mod doctor_ts;
+mod email_ts;
+mod errors_ts;
 mod gitignore;
+mod grpc_ts;
 mod handler_ts;
+mod http_ts;
 mod index_ts;
+mod llm_ts;
+mod locale_ts;
+mod logger_ts;
+mod migrate_ts;
 mod package_json;
+mod progress_ts;
+mod prompts_ts;
+mod queue_ts;
+mod readme;
+mod secrets_ts;
+mod storage_ts;
 mod tsconfig;
+mod ui_ts;
+mod workdir_ts;
 
 pub use baobao_codegen::generation::BaoToml;
+pub use buf_gen_yaml::{BufGenYaml, GRPC_OUT_DIR};
+pub use build_info_ts::{BUILD_INFO_DEFINES, BuildInfoTs};
+pub use cache_ts::CacheTs;
 pub use cli_ts::CliTs;
 pub use command_ts::CommandTs;
+pub use completions_ts::{COMPLETIONS_COMMAND, CompletionsTs};
+pub use config_ts::ConfigTs;
 pub use context_ts::ContextTs;
+pub use dockerfile::{DockerIgnore, Dockerfile};
+pub use docs_ts::{DOCS_COMMAND, DocsTs};
+pub use doctor_ts::{DOCTOR_COMMAND, DoctorTs};
+pub use email_ts::{EmailTs, NODEMAILER_TYPES_VERSION, NODEMAILER_VERSION};
+pub use errors_ts::ErrorsTs;
 pub use gitignore::GitIgnore;
-pub use handler_ts::{HandlerTs, STUB_MARKER};
+pub use grpc_ts::GrpcTs;
+pub use handler_ts::{HandlerTs, STUB_MARKER, StateHandlerTs};
+pub use http_ts::HttpTs;
 pub use index_ts::IndexTs;
+pub use llm_ts::LlmTs;
+pub use locale_ts::{LocaleTs, message_key, translate};
+pub use logger_ts::LoggerTs;
+pub use migrate_ts::{MIGRATE_COMMAND, MigrateTs};
 pub use package_json::{Dependency, PackageJson};
+pub use progress_ts::{ORA_VERSION, ProgressTs};
+pub use prompts_ts::PromptsTs;
+pub use queue_ts::QueueTs;
+pub use readme::{README_REGION_END, README_REGION_START, Readme};
+pub use secrets_ts::SecretsTs;
+pub use storage_ts::StorageTs;
 pub use tsconfig::TsConfig;
+pub use ui_ts::{PICOCOLORS_VERSION, UiTs};
+pub use workdir_ts::WorkdirTs;
@@ -1,9 +1,17 @@
 //! package.json generator for TypeScript projects.
 
-use std::path::{Path, PathBuf};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
-use baobao_core::{FileRules, GeneratedFile, Version};
+use baobao_core::{
+    FileRules, GeneratedFile, LineChanges, Version, WriteResult, json_value_span, write_file,
+};
+use baobao_manifest::ModuleFormat;
+use eyre::Result;
 
+use super::BUILD_INFO_DEFINES;
 use crate::BOUNE_VERSION;
 
 const DEFAULT_DESCRIPTION: &str = "A CLI application";
@@ -15,6 +23,11 @@ pub struct PackageJson {
     pub description: String,
     pub dependencies: Vec<Dependency>,
     pub dev_dependencies: Vec<Dependency>,
+    pub module_format: ModuleFormat,
+    /// Inject the commit and build time into `bun build`.
+    pub build_info: bool,
+    /// Add a `generate` script compiling the `.proto` files with buf.
+    pub protos: bool,
 }
 
 impl PackageJson {
@@ -28,6 +41,9 @@ impl PackageJson {
                 Dependency::new("@types/bun", "latest"),
                 Dependency::new("typescript", "^5.0.0"),
             ],
+            module_format: ModuleFormat::default(),
+            build_info: false,
+            protos: false,
         }
     }
 
@@ -48,6 +64,23 @@ impl PackageJson {
         self
     }
 
+    pub fn with_module_format(mut self, format: ModuleFormat) -> Self {
+        self.module_format = format;
+        self
+    }
+
+    /// Define the build-info.ts constants in the `build` script.
+    pub fn with_build_info(mut self, enabled: bool) -> Self {
+        self.build_info = enabled;
+        self
+    }
+
+    /// Add a `generate` script running `buf generate`.
+    pub fn with_protos(mut self, enabled: bool) -> Self {
+        self.protos = enabled;
+        self
+    }
+
     pub fn with_dependency(mut self, dep: impl Into<Dependency>) -> Self {
         self.dependencies.push(dep.into());
         self
@@ -75,6 +108,35 @@ impl PackageJson {
         self
     }
 
+    /// Add the dependencies missing from existing package.json content.
+    ///
+    /// Dependencies already listed in either table keep the version the user
+    /// chose, and the rest of the file is left as is. Returns `None` if the
+    /// content is not a JSON object.
+    pub fn add_missing_dependencies(&self, existing: &str) -> Option<String> {
+        let json: serde_json::Value = serde_json::from_str(existing).ok()?;
+        let root = json.as_object()?;
+        let listed = |name: &str| {
+            ["dependencies", "devDependencies"].iter().any(|table| {
+                root.get(*table)
+                    .and_then(|deps| deps.as_object())
+                    .is_some_and(|deps| deps.contains_key(name))
+            })
+        };
+
+        let mut content = existing.to_string();
+        for (table, deps) in [
+            ("dependencies", &self.dependencies),
+            ("devDependencies", &self.dev_dependencies),
+        ] {
+            let missing: Vec<_> = deps.iter().filter(|dep| !listed(&dep.name)).collect();
+            if !missing.is_empty() {
+                content = add_entries(&content, table, &missing)?;
+            }
+        }
+        Some(content)
+    }
+
     fn render_dependencies(deps: &[Dependency]) -> String {
         deps.iter()
             .map(|d| format!("    \"{}\": \"{}\"", d.name, d.version))
@@ -92,19 +154,57 @@ impl GeneratedFile for PackageJson {
         FileRules::create_once()
     }
 
+    /// Create package.json if missing, otherwise add the dependencies it lacks.
+    ///
+    /// Files that are not a JSON object are left untouched.
+    fn write(&self, base: &Path) -> Result<WriteResult> {
+        let path = self.path(base);
+        if !path.exists() {
+            let content = self.render();
+            write_file(&path, &content)?;
+            return Ok(WriteResult::Created(LineChanges::between("", &content)));
+        }
+
+        let existing = std::fs::read_to_string(&path)?;
+        match self.add_missing_dependencies(&existing) {
+            Some(updated) if updated != existing => {
+                write_file(&path, &updated)?;
+                Ok(WriteResult::Updated(LineChanges::between(
+                    &existing, &updated,
+                )))
+            }
+            Some(_) => Ok(WriteResult::Unchanged),
+            None => Ok(WriteResult::Skipped),
+        }
+    }
+
     fn render(&self) -> String {
         let dependencies = Self::render_dependencies(&self.dependencies);
         let dev_dependencies = Self::render_dependencies(&self.dev_dependencies);
+        let build_format = match self.module_format {
+            ModuleFormat::Esm => "",
+            ModuleFormat::Cjs => " --format cjs",
+        };
+        let defines = if self.build_info {
+            format!(" {}", BUILD_INFO_DEFINES)
+        } else {
+            String::new()
+        };
+        let generate = if self.protos {
+            "\n    \"generate\": \"buf generate\","
+        } else {
+            ""
+        };
 
         format!(
             r#"{{
   "name": "{}",
   "version": "{}",
   "description": "{}",
-  "type": "module",
-  "scripts": {{
+  "type": "{}",
+  "scripts": {{{}
     "dev": "bun run src/index.ts",
-    "build": "bun build src/index.ts --outdir dist --target bun",
+    "build": "bun build src/index.ts --outdir dist --target bun{}{}",
     "start": "bun run dist/index.js"
   }},
   "dependencies": {{
@@ -115,11 +215,91 @@ impl GeneratedFile for PackageJson {
   }}
 }}
 "#,
-            self.name, self.version, self.description, dependencies, dev_dependencies
+            self.name,
+            self.version,
+            self.description,
+            self.module_format.package_type(),
+            generate,
+            build_format,
+            defines,
+            dependencies,
+            dev_dependencies
         )
     }
 }
 
+/// Add `deps` to the top-level `table` object of `content`, creating the
+/// table if needed.
+fn add_entries(content: &str, table: &str, deps: &[&Dependency]) -> Option<String> {
+    let root = content.find('{')? + 1;
+    let unit = entry_indent(content, root).unwrap_or("  ");
+    let entries = |indent: &str| -> Vec<String> {
+        deps.iter()
+            .map(|dep| format!("{}\"{}\": \"{}\"", indent, dep.name, dep.version))
+            .collect()
+    };
+
+    let updated = match json_value_span(content, table) {
+        Some(span) if content[span.clone()].starts_with('{') => {
+            let open = span.start + 1;
+            let close_indent = line_indent(content, open);
+            let indent = entry_indent(content, open)
+                .map_or_else(|| format!("{}{}", close_indent, unit), str::to_string);
+            insert(content, open..span.end - 1, &entries(&indent), close_indent)
+        }
+        // Not an object: leave the user's value alone
+        Some(_) => return None,
+        None => {
+            let object = format!(
+                "{}\"{}\": {{\n{}\n{}}}",
+                unit,
+                table,
+                entries(&unit.repeat(2)).join(",\n"),
+                unit
+            );
+            insert(content, root..content.rfind('}')?, &[object], "")
+        }
+    };
+    // Never write a file the user can no longer parse
+    serde_json::from_str::<serde_json::Value>(&updated).ok()?;
+    Some(updated)
+}
+
+/// Insert `lines` at the end of the object contents spanning `inner`.
+fn insert(content: &str, inner: Range<usize>, lines: &[String], close_indent: &str) -> String {
+    let added = lines.join(",\n");
+    if content[inner.clone()].trim().is_empty() {
+        return format!(
+            "{}\n{}\n{}{}",
+            &content[..inner.start],
+            added,
+            close_indent,
+            &content[inner.end..]
+        );
+    }
+    let end = inner.start + content[inner].trim_end().len();
+    format!("{},\n{}{}", &content[..end], added, &content[end..])
+}
+
+/// Indentation of the first entry of the object whose contents start at `open`.
+fn entry_indent(content: &str, open: usize) -> Option<&str> {
+    let line = content[open..]
+        .lines()
+        .skip(1)
+        .find(|line| !line.trim().is_empty())?;
+    let entry = line.trim_start();
+    entry
+        .starts_with('"')
+        .then(|| &line[..line.len() - entry.len()])
+}
+
+/// Indentation of the line containing `pos`.
+fn line_indent(content: &str, pos: usize) -> &str {
+    let start = content[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &content[start..pos];
+    &line[..line.len() - line.trim_start().len()]
+}
+
 /// A dependency with name and version.
 #[derive(Debug, Clone)]
 pub struct Dependency {
@@ -141,3 +321,69 @@ impl<N: Into<String>, V: Into<String>> From<(N, V)> for Dependency {
         Self::new(name, version)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package_json() -> PackageJson {
+        PackageJson::new("myapp").with_dependency(("picocolors", "^1.1.0"))
+    }
+
+    #[test]
+    fn test_add_missing_dependencies() {
+        let existing = r#"{
+  "name": "myapp",
+  "dependencies": {
+    "boune": "^0.9.0",
+    "zod": "^3.0.0"
+  },
+  "devDependencies": {
+    "@types/bun": "latest",
+    "typescript": "^5.4.0"
+  }
+}
+"#;
+
+        assert_eq!(
+            package_json().add_missing_dependencies(existing).unwrap(),
+            r#"{
+  "name": "myapp",
+  "dependencies": {
+    "boune": "^0.9.0",
+    "zod": "^3.0.0",
+    "picocolors": "^1.1.0"
+  },
+  "devDependencies": {
+    "@types/bun": "latest",
+    "typescript": "^5.4.0"
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_add_missing_dependencies_tables() {
+        let updated = package_json()
+            .add_missing_dependencies("{\n  \"name\": \"myapp\",\n  \"dependencies\": {}\n}\n")
+            .unwrap();
+        assert_eq!(
+            updated,
+            format!(
+                "{{\n  \"name\": \"myapp\",\n  \"dependencies\": {{\n    \"boune\": \"{0}\",\n    \"picocolors\": \"^1.1.0\"\n  }},\n  \"devDependencies\": {{\n    \"@types/bun\": \"latest\",\n    \"typescript\": \"^5.0.0\"\n  }}\n}}\n",
+                BOUNE_VERSION
+            )
+        );
+
+        // Already listed, even as a dev dependency: left as is
+        let existing = r#"{ "dependencies": { "boune": "1", "typescript": "5" }, "devDependencies": { "picocolors": "1", "@types/bun": "1" } }"#;
+        assert_eq!(
+            package_json().add_missing_dependencies(existing).as_deref(),
+            Some(existing)
+        );
+
+        assert_eq!(package_json().add_missing_dependencies("not json"), None);
+        assert_eq!(package_json().add_missing_dependencies("[]"), None);
+    }
+}
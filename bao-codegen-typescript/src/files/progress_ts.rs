@@ -0,0 +1,66 @@
+//! progress.ts generator for spinner helpers.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// Target ora version for generated code.
+pub const ORA_VERSION: &str = "^8.1.0";
+
+/// The progress.ts file with spinner helpers built on ora.
+///
+/// Only generated when `[typescript] progress = true` is set in bao.toml.
+pub struct ProgressTs;
+
+impl ProgressTs {
+    fn body() -> &'static str {
+        r#"/** Create a spinner that only animates when stderr is a terminal. */
+export function spinner(text: string): Ora {
+  return ora({ text, isEnabled: process.stderr.isTTY === true });
+}
+
+/**
+ * Run an async task while showing a spinner.
+ *
+ * The spinner succeeds when the task resolves and fails when it throws.
+ */
+export async function withSpinner<T>(
+  text: string,
+  task: (spinner: Ora) => Promise<T>,
+): Promise<T> {
+  const active = spinner(text).start();
+  try {
+    const result = await task(active);
+    active.succeed();
+    return result;
+  } catch (err) {
+    active.fail();
+    throw err;
+  }
+}"#
+    }
+}
+
+impl GeneratedFile for ProgressTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("progress.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("ora").default("ora").named_type("Ora"))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
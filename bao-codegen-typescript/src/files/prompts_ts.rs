@@ -0,0 +1,93 @@
+//! prompts.ts generator for interactive confirm/secret inputs.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// The prompts.ts file with `confirm()` and `secret()` helpers.
+///
+/// Built on `node:readline`, which Bun supports natively, so no extra
+/// dependency is needed. Only generated when an input declares `prompt`.
+pub struct PromptsTs;
+
+impl PromptsTs {
+    fn body() -> &'static str {
+        r#"/**
+ * Ask a yes/no question on stderr.
+ *
+ * Returns false without prompting when stdin is not a terminal.
+ */
+export async function confirm(message: string): Promise<boolean> {
+  if (!process.stdin.isTTY) return false;
+  const rl = createInterface({ input: process.stdin, output: process.stderr });
+  try {
+    const answer = await rl.question(`${message} [y/N] `);
+    return /^y(es)?$/i.test(answer.trim());
+  } finally {
+    rl.close();
+  }
+}
+
+/** Read a value from the terminal without echoing it. */
+export function secret(message: string): Promise<string> {
+  const stdin = process.stdin;
+  if (!stdin.isTTY) {
+    return Promise.reject(new Error(`${message}: cannot prompt without a terminal`));
+  }
+
+  process.stderr.write(`${message}: `);
+  stdin.setRawMode(true);
+  stdin.setEncoding("utf8");
+  stdin.resume();
+
+  return new Promise((resolve) => {
+    let value = "";
+    const finish = () => {
+      stdin.off("data", onData);
+      stdin.setRawMode(false);
+      stdin.pause();
+      process.stderr.write("\n");
+    };
+    const onData = (chunk: string) => {
+      for (const char of chunk) {
+        if (char === "\r" || char === "\n") {
+          finish();
+          resolve(value);
+          return;
+        }
+        if (char === "\u0003") {
+          finish();
+          process.exit(130);
+        }
+        value = char === "\u007f" || char === "\b" ? value.slice(0, -1) : value + char;
+      }
+    };
+    stdin.on("data", onData);
+  });
+}"#
+    }
+}
+
+impl GeneratedFile for PromptsTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("prompts.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("node:readline/promises").named("createInterface"))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
@@ -0,0 +1,111 @@
+//! queue.ts generator for the `[context.queue]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, QueueType};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// Version of the `nats` package.
+const NATS_VERSION: &str = "^2.29.0";
+
+/// Version of the `kafkajs` package.
+const KAFKAJS_VERSION: &str = "^2.2.4";
+
+/// The queue.ts file with a NATS connection or Kafka producer.
+///
+/// The connection is opened on first use and shared afterwards, so commands
+/// that don't publish never need the broker variable to be set.
+pub struct QueueTs {
+    pub name: String,
+    pub queue_type: QueueType,
+    pub env_var: String,
+}
+
+impl QueueTs {
+    pub fn new(name: impl Into<String>, queue_type: QueueType, env_var: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            queue_type,
+            env_var: env_var.into(),
+        }
+    }
+
+    /// The package providing the client.
+    pub fn dependency(&self) -> (&'static str, &'static str) {
+        match self.queue_type {
+            QueueType::Nats => ("nats", NATS_VERSION),
+            QueueType::Kafka => ("kafkajs", KAFKAJS_VERSION),
+        }
+    }
+
+    /// The type resolved by `queue()`.
+    pub fn client_type(queue_type: QueueType) -> &'static str {
+        match queue_type {
+            QueueType::Nats => "NatsConnection",
+            QueueType::Kafka => "Producer",
+        }
+    }
+
+    fn import(&self) -> Import {
+        match self.queue_type {
+            QueueType::Nats => Import::new("nats")
+                .named("connect")
+                .named_type("NatsConnection"),
+            QueueType::Kafka => Import::new("kafkajs").named("Kafka").named_type("Producer"),
+        }
+    }
+
+    fn connect(&self) -> String {
+        let client_type = Self::client_type(self.queue_type);
+        let body = match self.queue_type {
+            QueueType::Nats => "  return connect({ servers: servers.split(\",\") });".to_string(),
+            QueueType::Kafka => format!(
+                "  const producer = new Kafka({{ clientId: {:?}, brokers: servers.split(\",\") }}).producer();\n  await producer.connect();\n  return producer;",
+                self.name
+            ),
+        };
+        format!(
+            r#"let connection: Promise<{client_type}> | undefined;
+
+async function connectQueue(): Promise<{client_type}> {{
+  const servers = process.env.{env_var};
+  if (!servers) {{
+    throw new Error("{env_var} is not set");
+  }}
+{body}
+}}
+
+/** Connection from `{env_var}`, also exposed as `ctx.queue`. */
+export function queue(): Promise<{client_type}> {{
+  connection ??= connectQueue();
+  return connection;
+}}"#,
+            client_type = client_type,
+            env_var = self.env_var,
+            body = body
+        )
+    }
+}
+
+impl GeneratedFile for QueueTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("queue.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(vec![self.import()])
+            .add(RawCode::new(self.connect()))
+            .render()
+    }
+}
@@ -0,0 +1,345 @@
+//! README.md generator for TypeScript projects.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile, LineChanges, WriteResult, to_kebab_case, write_file};
+use baobao_ir::{CommandOp, DefaultValue, Input, InputKind, InputType};
+use baobao_manifest::FlagCase;
+use eyre::Result;
+
+use crate::naming::flag_name;
+
+/// Marker opening the region of the README that Bao keeps up to date.
+pub const README_REGION_START: &str = "<!-- bao:generated:start -->";
+
+/// Marker closing the region of the README that Bao keeps up to date.
+pub const README_REGION_END: &str = "<!-- bao:generated:end -->";
+
+/// The README.md file for the generated project.
+///
+/// The file is created once with a title and description. Everything between
+/// [`README_REGION_START`] and [`README_REGION_END`] is regenerated on each
+/// bake, so users are free to edit the rest of the file.
+pub struct Readme {
+    name: String,
+    description: Option<String>,
+    commands: Vec<CommandOp>,
+    flag_case: FlagCase,
+}
+
+impl Readme {
+    pub fn new(
+        name: impl Into<String>,
+        description: Option<String>,
+        commands: Vec<CommandOp>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description,
+            commands,
+            flag_case: FlagCase::default(),
+        }
+    }
+
+    /// Set how flag names are spelled in the examples.
+    pub fn with_flag_case(mut self, flag_case: FlagCase) -> Self {
+        self.flag_case = flag_case;
+        self
+    }
+
+    /// Render the generated region, including its markers.
+    pub fn render_region(&self) -> String {
+        let mut out = String::new();
+        out.push_str(README_REGION_START);
+        out.push('\n');
+
+        out.push_str("## Installation\n\n");
+        out.push_str("```sh\nbun install\n# or\nnpm install\n```\n\n");
+
+        out.push_str("## Usage\n\n");
+        out.push_str("```sh\nbun run dev -- <command>\n# or\nnpm run dev -- <command>\n\n");
+        out.push_str(
+            "# build and run the bundled CLI\nbun run build\nbun run start -- <command>\n```\n\n",
+        );
+
+        if !self.commands.is_empty() {
+            out.push_str("## Commands\n\n```text\n");
+            for cmd in &self.commands {
+                Self::render_tree(&mut out, cmd, 0);
+            }
+            out.push_str("```\n\n");
+
+            out.push_str("## Examples\n\n```sh\n");
+            for cmd in &self.commands {
+                self.render_examples(&mut out, cmd);
+            }
+            out.push_str("```\n");
+        }
+
+        out.push_str(README_REGION_END);
+        out.push('\n');
+        out
+    }
+
+    /// Replace the generated region in existing README content.
+    ///
+    /// Returns `None` if the content doesn't contain both markers.
+    pub fn update_region(&self, existing: &str) -> Option<String> {
+        let start = existing.find(README_REGION_START)?;
+        let end_marker = existing[start..].find(README_REGION_END)? + start;
+        let mut end = end_marker + README_REGION_END.len();
+        if existing[end..].starts_with('\n') {
+            end += 1;
+        }
+
+        let mut updated = String::with_capacity(existing.len());
+        updated.push_str(&existing[..start]);
+        updated.push_str(&self.render_region());
+        updated.push_str(&existing[end..]);
+        Some(updated)
+    }
+
+    fn render_tree(out: &mut String, cmd: &CommandOp, depth: usize) {
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(&cmd.name);
+        if !cmd.description.is_empty() {
+            out.push_str(" - ");
+            out.push_str(&cmd.description);
+        }
+        out.push('\n');
+
+        for child in &cmd.children {
+            Self::render_tree(out, child, depth + 1);
+        }
+    }
+
+    fn render_examples(&self, out: &mut String, cmd: &CommandOp) {
+        if cmd.has_subcommands() {
+            for child in &cmd.children {
+                self.render_examples(out, child);
+            }
+            return;
+        }
+        if !cmd.docs.examples.is_empty() {
+            for example in &cmd.docs.examples {
+                out.push_str(&example.command);
+                out.push('\n');
+            }
+            return;
+        }
+
+        let mut parts = vec![self.name.clone()];
+        parts.extend(cmd.path.iter().cloned());
+        for input in &cmd.inputs {
+            if let Some(part) = self.example_input(input) {
+                parts.push(part);
+            }
+        }
+        out.push_str(&parts.join(" "));
+        out.push('\n');
+    }
+
+    /// Build an example token for an input, or `None` if it should be omitted.
+    fn example_input(&self, input: &Input) -> Option<String> {
+        match input.kind {
+            InputKind::Positional => Some(format!("<{}>", to_kebab_case(&input.name))),
+            InputKind::Flag { .. } => {
+                let flag = format!("--{}", flag_name(&input.name, self.flag_case));
+                if matches!(input.ty, InputType::Bool | InputType::Count) && input.choices.is_none()
+                {
+                    return Some(flag);
+                }
+                let value = Self::example_value(input);
+                Some(format!("{} {}", flag, value))
+            }
+        }
+    }
+
+    fn example_value(input: &Input) -> String {
+        if let Some(choice) = input.choices.as_ref().and_then(|c| c.first()) {
+            return choice.clone();
+        }
+        match &input.default {
+            Some(DefaultValue::String(s) | DefaultValue::Path(s))
+                if s.contains(char::is_whitespace) =>
+            {
+                format!("\"{}\"", s)
+            }
+            Some(default) => default.to_code_string(),
+            None => format!("<{}>", to_kebab_case(&input.name)),
+        }
+    }
+}
+
+impl GeneratedFile for Readme {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("README.md")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::create_once()
+    }
+
+    fn render(&self) -> String {
+        let mut out = format!("# {}\n\n", self.name);
+        if let Some(description) = &self.description {
+            out.push_str(description);
+            out.push_str("\n\n");
+        }
+        out.push_str(&self.render_region());
+        out
+    }
+
+    /// Create the README if missing, otherwise refresh only the generated region.
+    ///
+    /// READMEs without the region markers are left untouched.
+    fn write(&self, base: &Path) -> Result<WriteResult> {
+        let path = self.path(base);
+        if !path.exists() {
+            let content = self.render();
+            write_file(&path, &content)?;
+            return Ok(WriteResult::Created(LineChanges::between("", &content)));
+        }
+
+        let existing = std::fs::read_to_string(&path)?;
+        match self.update_region(&existing) {
+            Some(updated) if updated != existing => {
+                write_file(&path, &updated)?;
+                Ok(WriteResult::Updated(LineChanges::between(
+                    &existing, &updated,
+                )))
+            }
+            Some(_) => Ok(WriteResult::Unchanged),
+            None => Ok(WriteResult::Skipped),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn command(name: &str, inputs: Vec<Input>) -> CommandOp {
+        CommandOp {
+            name: name.to_string(),
+            path: vec![name.to_string()],
+            description: format!("Run {}", name),
+            inputs,
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
+            children: vec![],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
+        }
+    }
+
+    fn readme() -> Readme {
+        Readme::new(
+            "myapp",
+            Some("A CLI app".to_string()),
+            vec![command(
+                "greet",
+                vec![
+                    Input {
+                        name: "name".to_string(),
+                        ty: InputType::String,
+                        kind: InputKind::Positional,
+                        required: true,
+                        default: None,
+                        description: None,
+                        choices: None,
+                        multiple: false,
+                        delimiter: None,
+                        allow_hyphen_values: false,
+                        prompt: None,
+                        constraints: Default::default(),
+                        requires: vec![],
+                        required_if: None,
+                        hint: None,
+                        long_description: None,
+                        deprecated: None,
+                        span: None,
+                    },
+                    Input {
+                        name: "loud".to_string(),
+                        ty: InputType::Bool,
+                        kind: InputKind::Flag { short: None },
+                        required: false,
+                        default: None,
+                        description: None,
+                        choices: None,
+                        multiple: false,
+                        delimiter: None,
+                        allow_hyphen_values: false,
+                        prompt: None,
+                        constraints: Default::default(),
+                        requires: vec![],
+                        required_if: None,
+                        hint: None,
+                        long_description: None,
+                        deprecated: None,
+                        span: None,
+                    },
+                ],
+            )],
+        )
+    }
+
+    #[test]
+    fn test_render_includes_examples() {
+        let content = readme().render();
+        assert!(content.starts_with("# myapp\n\nA CLI app\n"));
+        assert!(content.contains("greet - Run greet"));
+        assert!(content.contains("myapp greet <name> --loud"));
+    }
+
+    #[test]
+    fn test_render_prefers_manifest_examples() {
+        let mut readme = readme();
+        readme.commands[0].docs.examples = vec![baobao_ir::Example::new("myapp greet world")];
+        let content = readme.render();
+        assert!(content.contains("```sh\nmyapp greet world\n```"));
+        assert!(!content.contains("myapp greet <name>"));
+    }
+
+    #[test]
+    fn test_write_refreshes_only_region() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("README.md");
+        std::fs::write(
+            &path,
+            format!(
+                "# Custom title\n\n{}\nstale\n{}\n\nUser notes\n",
+                README_REGION_START, README_REGION_END
+            ),
+        )
+        .unwrap();
+
+        let result = readme().write(temp.path()).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        assert!(matches!(result, WriteResult::Updated(_)));
+        assert!(content.starts_with("# Custom title\n"));
+        assert!(content.ends_with("\nUser notes\n"));
+        assert!(!content.contains("stale"));
+        assert!(content.contains("myapp greet <name> --loud"));
+    }
+
+    #[test]
+    fn test_write_skips_readme_without_markers() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("README.md");
+        std::fs::write(&path, "# Hand written\n").unwrap();
+
+        let result = readme().write(temp.path()).unwrap();
+
+        assert_eq!(result, WriteResult::Skipped);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Hand written\n");
+    }
+}
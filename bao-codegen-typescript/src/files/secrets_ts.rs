@@ -0,0 +1,149 @@
+//! secrets.ts generator for the `[context.secrets]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::{SecretsBackend, SecretsOptions};
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+const INTERFACE: &str = r#"export interface Secrets {
+  /** Look up `name`, or `undefined` if it is not set. */
+  get(name: string): Promise<string | undefined>;
+  /** Look up `name`, throwing if it is not set. */
+  require(name: string): Promise<string>;
+}"#;
+
+const LOAD_FILE: &str = r##"let values: Map<string, string> | undefined;
+
+/** Read the `NAME=value` lines of the secrets file once. A missing file reads as empty. */
+function load(): Map<string, string> {
+  if (values) {
+    return values;
+  }
+  values = new Map();
+  const text = existsSync(PATH) ? readFileSync(PATH, "utf8") : "";
+  for (const raw of text.split("\n")) {
+    const line = raw.trim();
+    const eq = line.indexOf("=");
+    if (line === "" || line.startsWith("#") || eq === -1) {
+      continue;
+    }
+    const value = line.slice(eq + 1).trim();
+    const quoted = value.length >= 2 && value.startsWith('"') && value.endsWith('"');
+    values.set(line.slice(0, eq).trim(), quoted ? value.slice(1, -1) : value);
+  }
+  return values;
+}"##;
+
+const EXPORT: &str = r#"export const secrets: Secrets = {
+  get: lookup,
+  async require(name) {
+    const secret = await lookup(name);
+    if (secret === undefined) {
+      throw new Error(`secret '${name}' is not set`);
+    }
+    return secret;
+  },
+};"#;
+
+/// The secrets.ts file with the `secrets` accessor.
+///
+/// Lookups are async for every backend, since `Bun.secrets` is, so switching
+/// backends doesn't touch handlers.
+pub struct SecretsTs {
+    pub options: SecretsOptions,
+}
+
+impl SecretsTs {
+    pub fn new(options: SecretsOptions) -> Self {
+        Self { options }
+    }
+
+    fn consts(&self) -> String {
+        match self.options.backend {
+            SecretsBackend::Keyring => format!(
+                "const SERVICE = {:?};\nconst PREFIX = {:?};",
+                self.options.service, self.options.prefix
+            ),
+            SecretsBackend::Env => format!("const PREFIX = {:?};", self.options.prefix),
+            SecretsBackend::File => format!("const PATH = {:?};", self.options.path),
+        }
+    }
+
+    fn interface(&self) -> String {
+        let doc = match self.options.backend {
+            SecretsBackend::Keyring => {
+                "Credentials from the OS keyring, falling back to environment variables"
+            }
+            SecretsBackend::Env => "Credentials from environment variables",
+            SecretsBackend::File => "Credentials from the secrets file",
+        };
+        format!(
+            "/** {}. Also exposed as `ctx.secrets`. */\n{}",
+            doc, INTERFACE
+        )
+    }
+
+    fn lookup(&self) -> &'static str {
+        match self.options.backend {
+            SecretsBackend::Keyring => {
+                r#"async function lookup(name: string): Promise<string | undefined> {
+  try {
+    const secret = await Bun.secrets.get({ service: SERVICE, name });
+    if (secret !== null) {
+      return secret;
+    }
+  } catch {
+    // No keyring on this machine (e.g. in CI)
+  }
+  return process.env[PREFIX + name];
+}"#
+            }
+            SecretsBackend::Env => {
+                r#"async function lookup(name: string): Promise<string | undefined> {
+  return process.env[PREFIX + name];
+}"#
+            }
+            SecretsBackend::File => {
+                r#"async function lookup(name: string): Promise<string | undefined> {
+  return load().get(name);
+}"#
+            }
+        }
+    }
+}
+
+impl GeneratedFile for SecretsTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("secrets.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = CodeFile::new().add(RawCode::new(GENERATED_HEADER));
+        if self.options.backend == SecretsBackend::File {
+            file = file.imports(vec![
+                Import::new("node:fs")
+                    .named("existsSync")
+                    .named("readFileSync"),
+            ]);
+        }
+        file = file
+            .add(RawCode::new(self.consts()))
+            .add(RawCode::new(self.interface()));
+        if self.options.backend == SecretsBackend::File {
+            file = file.add(RawCode::new(LOAD_FILE));
+        }
+        file.add(RawCode::new(self.lookup()))
+            .add(RawCode::new(EXPORT))
+            .render()
+    }
+}
@@ -0,0 +1,59 @@
+//! storage.ts generator for the `[context.storage]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::S3Options;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::{Import, JsObject},
+    code_file::{CodeFile, RawCode},
+};
+
+/// The storage.ts file with a Bun `S3Client` for the configured bucket.
+///
+/// Credentials come from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+/// `AWS_SESSION_TOKEN`; Bun falls back to `AWS_REGION` when no region is set.
+pub struct StorageTs {
+    pub s3: S3Options,
+}
+
+impl StorageTs {
+    pub fn new(s3: S3Options) -> Self {
+        Self { s3 }
+    }
+
+    fn client(&self) -> String {
+        let options = JsObject::new()
+            .string("bucket", &self.s3.bucket)
+            .string_opt("region", self.s3.region.as_deref())
+            .string_opt("endpoint", self.s3.endpoint.as_deref())
+            .raw("accessKeyId", "process.env.AWS_ACCESS_KEY_ID")
+            .raw("secretAccessKey", "process.env.AWS_SECRET_ACCESS_KEY")
+            .raw("sessionToken", "process.env.AWS_SESSION_TOKEN");
+        format!(
+            "/** Client for the `{}` bucket, also exposed as `ctx.storage`. */\nexport const storage = new S3Client({});",
+            self.s3.bucket,
+            options.build()
+        )
+    }
+}
+
+impl GeneratedFile for StorageTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("storage.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(vec![Import::new("bun").named("S3Client")])
+            .add(RawCode::new(self.client()))
+            .render()
+    }
+}
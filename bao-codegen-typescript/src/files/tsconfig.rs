@@ -1,11 +1,132 @@
 //! tsconfig.json generator for TypeScript projects.
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use baobao_core::{FileRules, GeneratedFile};
+use baobao_manifest::{ModuleFormat, TypeScriptConfig};
 
 /// The tsconfig.json configuration file.
-pub struct TsConfig;
+#[derive(Debug, Clone)]
+pub struct TsConfig {
+    pub target: String,
+    pub module: String,
+    pub module_resolution: String,
+    pub verbatim_module_syntax: bool,
+    pub strict: bool,
+    pub no_unused_locals: bool,
+    pub no_unused_parameters: bool,
+    pub no_fallthrough_cases_in_switch: bool,
+    pub no_property_access_from_index_signature: bool,
+    pub no_unchecked_indexed_access: bool,
+    pub paths: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for TsConfig {
+    fn default() -> Self {
+        Self {
+            target: "ESNext".to_string(),
+            module: "ESNext".to_string(),
+            module_resolution: "bundler".to_string(),
+            verbatim_module_syntax: true,
+            strict: true,
+            no_unused_locals: true,
+            no_unused_parameters: true,
+            no_fallthrough_cases_in_switch: true,
+            no_property_access_from_index_signature: true,
+            no_unchecked_indexed_access: false,
+            paths: BTreeMap::new(),
+        }
+    }
+}
+
+impl TsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply options from the manifest's `[typescript]` section.
+    ///
+    /// CommonJS output uses Node module resolution by default and disables
+    /// `verbatimModuleSyntax`, so ES import syntax is compiled to `require`.
+    pub fn from_config(config: &TypeScriptConfig) -> Self {
+        let defaults = Self::default();
+        let cjs = config.module == ModuleFormat::Cjs;
+        let default_resolution = if cjs {
+            "node10".to_string()
+        } else {
+            defaults.module_resolution
+        };
+        Self {
+            target: config.target.clone().unwrap_or(defaults.target),
+            module: config.module.compiler_module().to_string(),
+            module_resolution: config
+                .module_resolution
+                .clone()
+                .unwrap_or(default_resolution),
+            verbatim_module_syntax: !cjs,
+            strict: config.strict.unwrap_or(defaults.strict),
+            no_unused_locals: config.no_unused_locals.unwrap_or(defaults.no_unused_locals),
+            no_unused_parameters: config
+                .no_unused_parameters
+                .unwrap_or(defaults.no_unused_parameters),
+            no_fallthrough_cases_in_switch: config
+                .no_fallthrough_cases_in_switch
+                .unwrap_or(defaults.no_fallthrough_cases_in_switch),
+            no_property_access_from_index_signature: config
+                .no_property_access_from_index_signature
+                .unwrap_or(defaults.no_property_access_from_index_signature),
+            no_unchecked_indexed_access: config
+                .no_unchecked_indexed_access
+                .unwrap_or(defaults.no_unchecked_indexed_access),
+            paths: config.paths.clone(),
+        }
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    pub fn with_module_resolution(mut self, module_resolution: impl Into<String>) -> Self {
+        self.module_resolution = module_resolution.into();
+        self
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn with_path(mut self, alias: impl Into<String>, targets: Vec<String>) -> Self {
+        self.paths.insert(alias.into(), targets);
+        self
+    }
+
+    fn render_paths(&self) -> String {
+        if self.paths.is_empty() {
+            return String::new();
+        }
+
+        let entries = self
+            .paths
+            .iter()
+            .map(|(alias, targets)| {
+                let targets = targets
+                    .iter()
+                    .map(|t| format!("\"{}\"", t))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("      \"{}\": [{}]", alias, targets)
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+
+        format!(",\n    \"paths\": {{\n{}\n    }}", entries)
+    }
+}
 
 impl GeneratedFile for TsConfig {
     fn path(&self, base: &Path) -> PathBuf {
@@ -17,28 +138,90 @@ impl GeneratedFile for TsConfig {
     }
 
     fn render(&self) -> String {
-        r#"{
-  "compilerOptions": {
+        format!(
+            r#"{{
+  "compilerOptions": {{
     "lib": ["ESNext"],
-    "target": "ESNext",
-    "module": "ESNext",
+    "target": "{}",
+    "module": "{}",
     "moduleDetection": "force",
-    "moduleResolution": "bundler",
+    "moduleResolution": "{}",
     "allowImportingTsExtensions": true,
-    "verbatimModuleSyntax": true,
+    "verbatimModuleSyntax": {},
     "noEmit": true,
-    "strict": true,
+    "strict": {},
     "skipLibCheck": true,
-    "noFallthroughCasesInSwitch": true,
-    "noUnusedLocals": true,
-    "noUnusedParameters": true,
-    "noPropertyAccessFromIndexSignature": true,
+    "noFallthroughCasesInSwitch": {},
+    "noUnusedLocals": {},
+    "noUnusedParameters": {},
+    "noPropertyAccessFromIndexSignature": {},{}
     "resolveJsonModule": true,
-    "esModuleInterop": true
-  },
+    "esModuleInterop": true{}
+  }},
   "include": ["src/**/*.ts"]
+}}
+"#,
+            self.target,
+            self.module,
+            self.module_resolution,
+            self.verbatim_module_syntax,
+            self.strict,
+            self.no_fallthrough_cases_in_switch,
+            self.no_unused_locals,
+            self.no_unused_parameters,
+            self.no_property_access_from_index_signature,
+            if self.no_unchecked_indexed_access {
+                "\n    \"noUncheckedIndexedAccess\": true,"
+            } else {
+                ""
+            },
+            self.render_paths()
+        )
+    }
 }
-"#
-        .to_string()
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_render() {
+        let content = TsConfig::new().render();
+        assert!(content.contains("\"target\": \"ESNext\""));
+        assert!(content.contains("\"moduleResolution\": \"bundler\""));
+        assert!(content.contains("\"strict\": true"));
+        assert!(!content.contains("\"paths\""));
+        assert!(!content.contains("noUncheckedIndexedAccess"));
+    }
+
+    #[test]
+    fn test_cjs_module() {
+        let config = TypeScriptConfig {
+            module: ModuleFormat::Cjs,
+            ..Default::default()
+        };
+        let content = TsConfig::from_config(&config).render();
+
+        assert!(content.contains("\"module\": \"CommonJS\""));
+        assert!(content.contains("\"moduleResolution\": \"node10\""));
+        assert!(content.contains("\"verbatimModuleSyntax\": false"));
+    }
+
+    #[test]
+    fn test_from_config_overrides() {
+        let config = TypeScriptConfig {
+            target: Some("ES2022".to_string()),
+            strict: Some(false),
+            paths: BTreeMap::from([("@/*".to_string(), vec!["./src/*".to_string()])]),
+            ..Default::default()
+        };
+        let content = TsConfig::from_config(&config).render();
+
+        assert!(content.contains("\"target\": \"ES2022\""));
+        assert!(content.contains("\"strict\": false"));
+        assert!(content.contains("\"moduleResolution\": \"bundler\""));
+        assert!(content.contains(
+            "\"esModuleInterop\": true,\n    \"paths\": {\n      \"@/*\": [\"./src/*\"]\n    }\n  },"
+        ));
     }
 }
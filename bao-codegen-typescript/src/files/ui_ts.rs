@@ -0,0 +1,105 @@
+//! ui.ts generator for colored terminal output.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_manifest::ModuleFormat;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// Target picocolors version for generated code.
+pub const PICOCOLORS_VERSION: &str = "^1.1.0";
+
+/// The ui.ts file with colored output helpers built on picocolors.
+///
+/// Colors are resolved from the `--color` global option (`auto`, `always`,
+/// `never`), falling back to `NO_COLOR` and terminal detection.
+#[derive(Debug, Clone, Default)]
+pub struct UiTs {
+    pub module_format: ModuleFormat,
+}
+
+impl UiTs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the module format the picocolors import is rendered for.
+    pub fn with_module_format(mut self, format: ModuleFormat) -> Self {
+        self.module_format = format;
+        self
+    }
+
+    fn body() -> &'static str {
+        r#"export type ColorMode = "auto" | "always" | "never";
+
+/** Resolve the color mode from `--color` in the process arguments. */
+function colorModeFromArgs(argv: string[]): ColorMode {
+  for (const [i, arg] of argv.entries()) {
+    if (arg === "--color") return parseColorMode(argv[i + 1]);
+    if (arg.startsWith("--color=")) return parseColorMode(arg.slice("--color=".length));
+  }
+  return "auto";
+}
+
+function parseColorMode(value: string | undefined): ColorMode {
+  return value === "always" || value === "never" ? value : "auto";
+}
+
+function shouldColor(mode: ColorMode): boolean {
+  if (mode === "always") return true;
+  if (mode === "never") return false;
+  return !("NO_COLOR" in process.env) && pc.isColorSupported;
+}
+
+let colors = pc.createColors(shouldColor(colorModeFromArgs(process.argv)));
+
+/** Override the color mode, e.g. after parsing global options. */
+export function setColorMode(mode: ColorMode): void {
+  colors = pc.createColors(shouldColor(mode));
+}
+
+/** The active color palette. */
+export function palette(): typeof colors {
+  return colors;
+}
+
+/** Print a success message to stdout. */
+export function success(message: string): void {
+  console.log(`${colors.green("✔")} ${message}`);
+}
+
+/** Print a warning message to stderr. */
+export function warn(message: string): void {
+  console.warn(`${colors.yellow("⚠")} ${colors.yellow(message)}`);
+}
+
+/** Print an error message to stderr. */
+export function error(message: string): void {
+  console.error(`${colors.red("✖")} ${colors.red(message)}`);
+}"#
+    }
+}
+
+impl GeneratedFile for UiTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("ui.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        CodeFile::new()
+            .module_format(self.module_format)
+            .add(RawCode::new(GENERATED_HEADER))
+            .import(Import::new("picocolors").default("pc"))
+            .add(RawCode::new(Self::body()))
+            .render()
+    }
+}
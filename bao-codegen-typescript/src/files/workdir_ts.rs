@@ -0,0 +1,125 @@
+//! workdir.ts generator for the `[context.workdir]` resource.
+
+use std::path::{Path, PathBuf};
+
+use baobao_core::{FileRules, GeneratedFile};
+use baobao_ir::WorkdirOptions;
+
+use super::GENERATED_HEADER;
+use crate::{
+    ast::Import,
+    code_file::{CodeFile, RawCode},
+};
+
+/// The user data directory, as the Rust `dirs` crate resolves it.
+const USER_DATA_DIR: &str = r#"function userDataDir(): string {
+  if (process.platform === "win32") {
+    return process.env.APPDATA ?? join(homedir(), "AppData", "Roaming");
+  }
+  if (process.platform === "darwin") {
+    return join(homedir(), "Library", "Application Support");
+  }
+  return process.env.XDG_DATA_HOME || join(homedir(), ".local", "share");
+}"#;
+
+/// The workdir.ts file with the `workdir` directories.
+///
+/// Both directories are created when the module is first imported; the
+/// scratch directory is removed when the process exits.
+pub struct WorkdirTs {
+    pub options: WorkdirOptions,
+}
+
+impl WorkdirTs {
+    pub fn new(options: WorkdirOptions) -> Self {
+        Self { options }
+    }
+
+    fn imports(&self) -> Vec<Import> {
+        let user_data_dir = self.options.data && self.options.path.is_none();
+        let mut fs = Import::new("node:fs");
+        if self.options.data {
+            fs = fs.named("mkdirSync");
+        }
+        if self.options.temp {
+            fs = fs.named("mkdtempSync").named("rmSync");
+        }
+        let mut imports = vec![fs];
+        match (user_data_dir, self.options.temp) {
+            (true, true) => imports.push(Import::new("node:os").named("homedir").named("tmpdir")),
+            (true, false) => imports.push(Import::new("node:os").named("homedir")),
+            (false, true) => imports.push(Import::new("node:os").named("tmpdir")),
+            (false, false) => {}
+        }
+        if user_data_dir || self.options.temp {
+            imports.push(Import::new("node:path").named("join"));
+        }
+        imports
+    }
+
+    fn interface(&self) -> String {
+        let mut fields = Vec::new();
+        if self.options.data {
+            fields.push("  /** Directory for files that outlive the invocation. */\n  readonly data: string;");
+        }
+        if self.options.temp {
+            fields.push(
+                "  /** Scratch directory, removed when the process exits. */\n  readonly temp: string;",
+            );
+        }
+        format!(
+            "/** The data and scratch directories of the CLI. Also exposed as `ctx.workdir`. */\nexport interface Workdir {{\n{}\n}}",
+            fields.join("\n")
+        )
+    }
+
+    fn dirs(&self) -> String {
+        let mut dirs = Vec::new();
+        let mut names = Vec::new();
+        if self.options.data {
+            let data = match &self.options.path {
+                Some(path) => format!("{:?}", path),
+                None => format!("join(userDataDir(), {:?})", self.options.namespace),
+            };
+            dirs.push(format!(
+                "const data = {};\nmkdirSync(data, {{ recursive: true }});",
+                data
+            ));
+            names.push("data");
+        }
+        if self.options.temp {
+            dirs.push(format!(
+                "const temp = mkdtempSync(join(tmpdir(), {:?}));\nprocess.on(\"exit\", () => rmSync(temp, {{ recursive: true, force: true }}));",
+                format!("{}-", self.options.namespace)
+            ));
+            names.push("temp");
+        }
+        dirs.push(format!(
+            "export const workdir: Workdir = {{ {} }};",
+            names.join(", ")
+        ));
+        dirs.join("\n\n")
+    }
+}
+
+impl GeneratedFile for WorkdirTs {
+    fn path(&self, base: &Path) -> PathBuf {
+        base.join("src").join("workdir.ts")
+    }
+
+    fn rules(&self) -> FileRules {
+        FileRules::always_overwrite().with_header(GENERATED_HEADER)
+    }
+
+    fn render(&self) -> String {
+        let mut file = CodeFile::new()
+            .add(RawCode::new(GENERATED_HEADER))
+            .imports(self.imports());
+        if self.options.data && self.options.path.is_none() {
+            file = file.add(RawCode::new(USER_DATA_DIR));
+        }
+        file.add(RawCode::new(self.interface()))
+            .add(RawCode::new(self.dirs()))
+            .render()
+    }
+}
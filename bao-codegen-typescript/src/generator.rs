@@ -1,30 +1,49 @@
 //! TypeScript code generator using boune framework.
 
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
 
 use baobao_codegen::{
+    adapters::{DatabaseAdapter, GrpcAdapter, RenderedResources},
     generation::{FileCategory, FileEntry, FileRegistry, HandlerPaths, find_orphan_commands},
     language::{CleanResult, GenerateResult, LanguageCodegen, PreviewFile},
     pipeline::CompilationContext,
     schema::ComputedData,
 };
-use baobao_core::{GeneratedFile, to_camel_case, to_kebab_case, to_pascal_case};
-use baobao_ir::{AppIR, CommandOp, InputKind, Operation};
+use baobao_core::{
+    GeneratedFile, OverwritePrompt, join_relative, relative_display, to_camel_case, to_env_var,
+    to_kebab_case, to_pascal_case,
+};
+use baobao_ir::{
+    AppIR, CommandOp, DatabaseType, InputGroup, InputKind, InputPrompt, InputType, NO_INPUT_FLAG,
+    QueueResource, Resource,
+};
+use baobao_manifest::TypeScriptConfig;
 use eyre::Result;
 
 use crate::{
-    adapters::BouneAdapter,
-    ast::{Import, JsObject},
+    adapters::{BouneAdapter, ConnectAdapter, DuckdbAdapter},
+    ast::{ArrowFn, Import, JsArray, JsObject},
     files::{
-        CliTs, CommandTs, ContextTs, GitIgnore, HandlerTs, IndexTs, PackageJson, STUB_MARKER,
-        TsConfig,
+        BufGenYaml, BuildInfoTs, COMPLETIONS_COMMAND, CacheTs, CliTs, CommandTs, CompletionsTs,
+        ConfigTs, ContextTs, DOCS_COMMAND, DOCTOR_COMMAND, DockerIgnore, Dockerfile, DocsTs,
+        DoctorTs, EmailTs, ErrorsTs, GitIgnore, GrpcTs, HandlerTs, HttpTs, IndexTs, LlmTs,
+        LocaleTs, LoggerTs, MIGRATE_COMMAND, MigrateTs, NODEMAILER_TYPES_VERSION,
+        NODEMAILER_VERSION, ORA_VERSION, PICOCOLORS_VERSION, PackageJson, ProgressTs, PromptsTs,
+        QueueTs, Readme, STUB_MARKER, SecretsTs, StateHandlerTs, StorageTs, TsConfig, UiTs,
+        WorkdirTs, message_key, translate,
     },
+    naming::{flag_name, option_access, option_key},
 };
 
 /// TypeScript code generator that produces boune-based CLI code for Bun.
 pub struct Generator {
     ir: AppIR,
     computed: ComputedData,
+    resources: RenderedResources,
+    config: TypeScriptConfig,
     cli_adapter: BouneAdapter,
 }
 
@@ -41,8 +60,19 @@ impl LanguageCodegen for Generator {
         self.preview_files()
     }
 
-    fn generate(&self, output_dir: &Path) -> Result<GenerateResult> {
-        self.generate_files(output_dir)
+    fn generate_with_prompt(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.generate_files(output_dir, prompt)
+    }
+
+    fn files(&self) -> Result<Vec<FileEntry>> {
+        self.resources.check()?;
+        let mut files: Vec<FileEntry> = self.build_registry().entries().cloned().collect();
+        files.extend(self.handler_entries());
+        Ok(files)
     }
 
     fn clean(&self, output_dir: &Path) -> Result<CleanResult> {
@@ -64,9 +94,15 @@ impl Generator {
     /// Panics if the context doesn't have IR or computed data
     /// (i.e., if the pipeline didn't run successfully).
     pub fn from_context(mut ctx: CompilationContext) -> Self {
+        let computed = ctx.take_computed();
+        let resources = ctx
+            .resource_adapters
+            .render_all("typescript", &computed.context_fields);
         Self {
             ir: ctx.take_ir(),
-            computed: ctx.take_computed(),
+            computed,
+            resources,
+            config: ctx.manifest.typescript.clone(),
             cli_adapter: BouneAdapter::new(),
         }
     }
@@ -81,16 +117,14 @@ impl Generator {
         let context_fields = self.computed.context_fields.clone();
 
         // Config files (respecting create_once rules)
-        let package_json = PackageJson::new(&self.ir.meta.name)
-            .with_version_str(&self.ir.meta.version);
         registry.register(FileEntry::from_generated(
             "package.json",
-            &package_json,
+            &self.package_json(),
             FileCategory::Config,
         ));
         registry.register(FileEntry::from_generated(
             "tsconfig.json",
-            &TsConfig,
+            &TsConfig::from_config(&self.config),
             FileCategory::Config,
         ));
         registry.register(FileEntry::from_generated(
@@ -98,17 +132,171 @@ impl Generator {
             &GitIgnore,
             FileCategory::Config,
         ));
+        registry.register(FileEntry::from_generated(
+            "README.md",
+            &self.readme(),
+            FileCategory::Config,
+        ));
+        if self.config.docker {
+            registry.register(FileEntry::from_generated(
+                "Dockerfile",
+                &Dockerfile::new(&self.ir.meta.name, &context_fields),
+                FileCategory::Config,
+            ));
+            registry.register(FileEntry::from_generated(
+                ".dockerignore",
+                &DockerIgnore,
+                FileCategory::Config,
+            ));
+        }
 
         // Infrastructure files
-        registry.register(FileEntry::infrastructure("src/index.ts", IndexTs.render()));
+        registry.register(FileEntry::infrastructure(
+            "src/index.ts",
+            IndexTs::new()
+                .with_import_extension(self.config.import_extension)
+                .render(),
+        ));
         registry.register(FileEntry::infrastructure(
             "src/context.ts",
-            ContextTs::new(context_fields).render(),
+            ContextTs::new(context_fields)
+                .with_resources(self.resources.clone())
+                .with_lazy(self.ir.has_requirements())
+                .render(),
         ));
+        registry.register(FileEntry::generated("src/errors.ts", ErrorsTs.render()));
+        registry.register(FileEntry::generated(
+            "src/ui.ts",
+            UiTs::new().with_module_format(self.config.module).render(),
+        ));
+        if self.config.progress {
+            registry.register(FileEntry::generated("src/progress.ts", ProgressTs.render()));
+        }
+        if let Some(logger) = self.ir.logger() {
+            registry.register(FileEntry::generated(
+                "src/logger.ts",
+                LoggerTs::new(logger.default_filter(), logger.format).render(),
+            ));
+        }
+        let http_clients: Vec<_> = self.ir.http_clients().cloned().collect();
+        if !http_clients.is_empty() {
+            registry.register(FileEntry::generated(
+                "src/http.ts",
+                HttpTs::new(http_clients).render(),
+            ));
+        }
+        if let Some(storage) = self.ir.storage() {
+            registry.register(FileEntry::generated(
+                "src/storage.ts",
+                StorageTs::new(storage.s3.clone()).render(),
+            ));
+        }
+        if let Some(grpc) = self.ir.grpc() {
+            registry.register(FileEntry::generated(
+                "src/grpc.ts",
+                GrpcTs::new(grpc.options.clone()).render(),
+            ));
+            if grpc.options.has_protos() {
+                registry.register(FileEntry::from_generated(
+                    "buf.gen.yaml",
+                    &BufGenYaml::new(grpc.options.includes.clone()),
+                    FileCategory::Config,
+                ));
+            }
+        }
+        if let Some(email) = self.ir.email() {
+            registry.register(FileEntry::generated(
+                "src/email.ts",
+                EmailTs::new(email.options.clone()).render(),
+            ));
+        }
+        if let Some(config) = self.ir.config() {
+            registry.register(FileEntry::generated(
+                "src/config.ts",
+                ConfigTs::new(config.options.clone()).render(),
+            ));
+        }
+        if let Some(secrets) = self.ir.secrets() {
+            registry.register(FileEntry::generated(
+                "src/secrets.ts",
+                SecretsTs::new(secrets.options.clone()).render(),
+            ));
+        }
+        if let Some(cache) = self.ir.cache() {
+            registry.register(FileEntry::generated(
+                "src/cache.ts",
+                CacheTs::new(cache.options.clone()).render(),
+            ));
+        }
+        if let Some(workdir) = self.ir.workdir() {
+            registry.register(FileEntry::generated(
+                "src/workdir.ts",
+                WorkdirTs::new(workdir.options.clone()).render(),
+            ));
+        }
+        if let Some(llm) = self.ir.llm() {
+            registry.register(FileEntry::generated(
+                "src/llm.ts",
+                LlmTs::new(llm.options.clone()).render(),
+            ));
+        }
+        if let Some(queue) = self.ir.queue() {
+            registry.register(FileEntry::generated(
+                "src/queue.ts",
+                self.queue_ts(queue).render(),
+            ));
+        }
+        if self.ir.commands().any(Self::has_prompts) {
+            registry.register(FileEntry::generated("src/prompts.ts", PromptsTs.render()));
+        }
 
         // Collect commands from IR
         let commands: Vec<CommandOp> = self.ir.commands().cloned().collect();
 
+        // Skip the built-in completions command if the manifest defines its own
+        let completions = !commands.iter().any(|c| c.name == COMPLETIONS_COMMAND);
+        if completions {
+            registry.register(FileEntry::generated(
+                "src/completions.ts",
+                CompletionsTs::new(&self.ir.meta.name, commands.clone())
+                    .with_flag_case(self.config.flag_case)
+                    .render(),
+            ));
+        }
+
+        // The docs command is opt-in and yields to a manifest-defined one
+        let docs = self.ir.meta.docs_command && !commands.iter().any(|c| c.name == DOCS_COMMAND);
+        if docs {
+            registry.register(FileEntry::generated(
+                "src/docs.ts",
+                DocsTs::new(
+                    &self.ir.meta.name,
+                    self.ir.meta.description.clone(),
+                    commands.clone(),
+                )
+                .with_flag_case(self.config.flag_case)
+                .render(),
+            ));
+        }
+
+        // The doctor command is opt-in and yields to a manifest-defined one
+        let doctor =
+            self.ir.meta.doctor_command && !commands.iter().any(|c| c.name == DOCTOR_COMMAND);
+        if doctor {
+            registry.register(FileEntry::generated(
+                "src/doctor.ts",
+                DoctorTs::new(self.computed.context_fields.clone()).render(),
+            ));
+        }
+
+        // Skip the built-in migrate command if the manifest defines its own
+        let migrate = self
+            .migrations()
+            .filter(|_| !commands.iter().any(|c| c.name == MIGRATE_COMMAND));
+        if let Some(migrate_ts) = &migrate {
+            registry.register(FileEntry::generated("src/migrate.ts", migrate_ts.render()));
+        }
+
         registry.register(FileEntry::generated(
             "src/cli.ts",
             CliTs::new(
@@ -117,21 +305,126 @@ impl Generator {
                 self.ir.meta.description.clone(),
                 commands,
             )
+            .with_completions(completions)
+            .with_docs(docs)
+            .with_doctor(doctor)
+            .with_migrate(migrate.is_some())
+            .with_logging(self.ir.logger().is_some())
+            .with_global_options(self.global_options())
+            .with_import_extension(self.config.import_extension)
+            .with_locales(self.localized())
+            .with_build_info(self.ir.meta.build_info)
+            .with_footer(self.ir.meta.docs.footer.clone())
+            .with_external(self.ir.meta.external_subcommands)
+            .with_default_command(self.ir.meta.default_command.clone())
+            .with_hyphen_values(self.hyphen_values())
             .render(),
         ));
+        if self.ir.meta.build_info {
+            registry.register(FileEntry::generated(
+                "src/build-info.ts",
+                BuildInfoTs::new(&self.ir.meta.version).render(),
+            ));
+        }
+        if self.localized() {
+            registry.register(FileEntry::generated(
+                "src/locale.ts",
+                LocaleTs::new(self.ir.locales.clone()).render(),
+            ));
+        }
 
         // Individual command files from IR (recursively collect all commands)
-        for op in &self.ir.operations {
-            let Operation::Command(cmd) = op;
-            self.register_command_files_from_ir(&mut registry, cmd);
+        for cmd in self.ir.commands() {
+            self.register_command_files_from_ir(&mut registry, cmd, &[]);
+        }
+
+        if let Some(header) = &self.ir.meta.header {
+            registry.prepend_header(header);
         }
 
         registry
     }
 
+    /// The migration runner, when a SQLite database declares `migrations`.
+    fn migrations(&self) -> Option<MigrateTs> {
+        self.ir.resources.iter().find_map(|r| match r {
+            Resource::Database(db) => {
+                let sqlite = db.sqlite.as_ref()?;
+                let dir = sqlite.migrations.clone()?;
+                Some(MigrateTs::new(dir, sqlite.path.clone(), &db.env_var))
+            }
+            _ => None,
+        })
+    }
+
+    /// Build package.json with the dependencies of every enabled feature.
+    fn package_json(&self) -> PackageJson {
+        let mut package_json = PackageJson::new(&self.ir.meta.name)
+            .with_version_str(&self.ir.meta.version)
+            .with_module_format(self.config.module)
+            .with_build_info(self.ir.meta.build_info)
+            .with_dependency(("picocolors", PICOCOLORS_VERSION));
+        if self.config.progress {
+            package_json = package_json.with_dependency(("ora", ORA_VERSION));
+        }
+        if let Some(grpc) = self.ir.grpc() {
+            let connect = ConnectAdapter::new();
+            let build_dependencies = connect.build_dependencies(&grpc.options);
+            package_json = package_json.with_protos(!build_dependencies.is_empty());
+            for dep in connect
+                .dependencies(&grpc.options)
+                .into_iter()
+                .chain(build_dependencies)
+            {
+                package_json = if dep.dev {
+                    package_json.with_dev_dependency((dep.name, dep.version))
+                } else {
+                    package_json.with_dependency((dep.name, dep.version))
+                };
+            }
+        }
+        if self.ir.email().is_some() {
+            package_json = package_json
+                .with_dependency(("nodemailer", NODEMAILER_VERSION))
+                .with_dev_dependency(("@types/nodemailer", NODEMAILER_TYPES_VERSION));
+        }
+        if let Some(queue) = self.ir.queue() {
+            package_json = package_json.with_dependency(self.queue_ts(queue).dependency());
+        }
+        let duckdb = self
+            .ir
+            .resources
+            .iter()
+            .any(|r| matches!(r, Resource::Database(db) if db.db_type == DatabaseType::Duckdb));
+        if duckdb {
+            for dep in DuckdbAdapter::new().dependencies(DatabaseType::Duckdb) {
+                package_json = package_json.with_dependency((dep.name, dep.version));
+            }
+        }
+        for dep in self.resources.dependencies() {
+            package_json = package_json.with_dependency((dep.name.as_str(), dep.version.as_str()));
+        }
+        package_json
+    }
+
+    /// Build the README with the command tree and examples from IR.
+    fn readme(&self) -> Readme {
+        Readme::new(
+            &self.ir.meta.name,
+            self.ir.meta.description.clone(),
+            self.ir.commands().cloned().collect(),
+        )
+        .with_flag_case(self.config.flag_case)
+    }
+
     /// Recursively register command files from IR.
-    fn register_command_files_from_ir(&self, registry: &mut FileRegistry, cmd: &CommandOp) {
-        let content = self.generate_command_file_from_ir(cmd);
+    fn register_command_files_from_ir(
+        &self,
+        registry: &mut FileRegistry,
+        cmd: &CommandOp,
+        ancestors: &[&CommandOp],
+    ) {
+        let content = self.generate_command_file_from_ir(cmd, ancestors);
         let file_path = cmd
             .path
             .iter()
@@ -145,8 +438,10 @@ impl Generator {
         ));
 
         // Recursively register subcommand files
+        let mut child_ancestors = ancestors.to_vec();
+        child_ancestors.push(cmd);
         for child in &cmd.children {
-            self.register_command_files_from_ir(registry, child);
+            self.register_command_files_from_ir(registry, child, &child_ancestors);
         }
     }
 
@@ -163,15 +458,30 @@ impl Generator {
     }
 
     /// Generate all files into the specified output directory.
-    fn generate_files(&self, output_dir: &Path) -> Result<GenerateResult> {
-        let handlers_dir = output_dir.join("src/handlers");
-
-        // Write all registered files using the registry
-        let registry = self.build_registry();
-        registry.write_all(output_dir)?;
+    fn generate_files(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult> {
+        self.resources.check()?;
+        let handlers_dir = join_relative(output_dir, "src/handlers");
+
+        // Write all registered files using the registry, except package.json
+        // and README.md which update existing files in place
+        let mut registry = self.build_registry();
+        registry.retain(|entry| !matches!(entry.path.as_str(), "package.json" | "README.md"));
+        let package_json = self.package_json().write(output_dir)?;
+        let stats = registry.write_all_with_prompt(output_dir, prompt)?;
+        let mut files = vec![("package.json".to_string(), package_json)];
+        files.extend(stats.files);
+
+        // Refresh the generated region of an existing README
+        files.push(("README.md".to_string(), self.readme().write(output_dir)?));
 
         // Generate handlers (handled separately due to special logic)
-        let result = self.generate_handlers(&handlers_dir, output_dir)?;
+        let mut result = self.generate_handlers(&handlers_dir, output_dir)?;
+        result.files = files;
+        result.checksums = stats.checksums;
 
         Ok(result)
     }
@@ -181,11 +491,68 @@ impl Generator {
     // ========================================================================
 
     /// Generate a command file from IR CommandOp.
-    fn generate_command_file_from_ir(&self, cmd: &CommandOp) -> String {
+    fn generate_command_file_from_ir(&self, cmd: &CommandOp, ancestors: &[&CommandOp]) -> String {
         if cmd.has_subcommands() {
             self.generate_parent_command_file_from_ir(cmd)
         } else {
-            self.generate_leaf_command_file_from_ir(cmd)
+            self.generate_leaf_command_file_from_ir(cmd, ancestors)
+        }
+    }
+
+    /// Check whether a command declares any flags.
+    fn has_flags(cmd: &CommandOp) -> bool {
+        cmd.inputs
+            .iter()
+            .any(|i| matches!(i.kind, InputKind::Flag { .. }))
+    }
+
+    /// The queue.ts file for the `[context.queue]` resource.
+    fn queue_ts(&self, queue: &QueueResource) -> QueueTs {
+        QueueTs::new(&self.ir.meta.name, queue.queue_type, &queue.env_var)
+    }
+
+    /// Check whether a command or any of its subcommands prompts for input.
+    fn has_prompts(cmd: &CommandOp) -> bool {
+        cmd.inputs
+            .iter()
+            .any(|i| matches!(i.prompt, Some(InputPrompt::Confirm | InputPrompt::Secret)))
+            || cmd.children.iter().any(Self::has_prompts)
+    }
+
+    /// Parent commands whose flags are inherited by descendants and resolved
+    /// into shared state, outermost first.
+    fn stateful_ancestors<'a>(ancestors: &[&'a CommandOp]) -> Vec<&'a CommandOp> {
+        ancestors
+            .iter()
+            .copied()
+            .filter(|a| Self::has_flags(a))
+            .collect()
+    }
+
+    /// Build the options schema object for a command's flags.
+    fn build_options_object_from_ir(&self, cmd: &CommandOp) -> JsObject {
+        cmd.inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Flag { .. }))
+            .fold(JsObject::new(), |obj, input| {
+                let key = option_key(&input.name, self.config.flag_case);
+                obj.object(&key, self.build_option_schema_from_ir(&cmd.path, input))
+            })
+    }
+
+    /// Whether the manifest has translations, so descriptions go through
+    /// locale.ts.
+    fn localized(&self) -> bool {
+        !self.ir.locales.is_empty()
+    }
+
+    /// Add a `description` property, wrapped in `t()` when localized.
+    fn with_description(&self, obj: JsObject, key: String, description: Option<&str>) -> JsObject {
+        match description {
+            Some(description) if self.localized() => {
+                obj.raw("description", translate(&key, description))
+            }
+            description => obj.string_opt("description", description),
         }
     }
 
@@ -206,6 +573,10 @@ impl Generator {
                     .named(format!("{}Command", sub_camel)),
             );
         }
+        if self.localized() {
+            let up_path = "../".repeat(cmd.path.len());
+            imports.push(Import::new(format!("{}locale.ts", up_path)).named("t"));
+        }
 
         // Build subcommands object
         let subcommands = cmd.children.iter().fold(JsObject::new(), |obj, child| {
@@ -214,9 +585,23 @@ impl Generator {
         });
 
         // Build command schema
-        let schema = JsObject::new()
-            .string("name", &cmd.name)
-            .string("description", &cmd.description)
+        let schema = self
+            .with_description(
+                JsObject::new().string("name", &cmd.name).array_if(
+                    !cmd.aliases.is_empty(),
+                    "aliases",
+                    JsArray::from_strings(&cmd.aliases),
+                ),
+                message_key(&cmd.path, None),
+                Some(&cmd.description),
+            )
+            .string_opt("longDescription", cmd.docs.long_description.as_deref())
+            .array_if(
+                !cmd.docs.examples.is_empty(),
+                "examples",
+                JsArray::from_strings(cmd.docs.examples.iter().map(|e| e.command.as_str())),
+            )
+            .string_opt("epilogue", cmd.docs.footer.as_deref())
             .object("subcommands", subcommands);
 
         // Build the command definition string
@@ -227,14 +612,39 @@ impl Generator {
             schema_obj.trim_end()
         );
 
-        CodeFile::new()
-            .imports(imports)
-            .add(RawCode::new(command_def))
-            .render()
+        // Flags on a parent are inherited by every subcommand; export their
+        // type so the parent's state handler can consume them
+        let mut body_parts = Vec::new();
+        if Self::has_flags(cmd) {
+            imports[0] = Import::new("boune")
+                .named("defineCommand")
+                .named_type("InferOpts");
+            let options = self.build_options_object_from_ir(cmd).build();
+            body_parts.push(format!("const options = {} as const;", options.trim_end()));
+        }
+        body_parts.push(command_def);
+        if Self::has_flags(cmd) {
+            body_parts.push(format!(
+                "export type {}Options = InferOpts<typeof options>;",
+                to_pascal_case(&cmd.name)
+            ));
+        }
+
+        let mut file = CodeFile::new()
+            .import_extension(self.config.import_extension)
+            .imports(imports);
+        for part in body_parts {
+            file = file.add(RawCode::new(part));
+        }
+        file.render()
     }
 
     /// Generate a leaf command file from IR.
-    fn generate_leaf_command_file_from_ir(&self, cmd: &CommandOp) -> String {
+    fn generate_leaf_command_file_from_ir(
+        &self,
+        cmd: &CommandOp,
+        ancestors: &[&CommandOp],
+    ) -> String {
         use crate::code_file::{CodeFile, RawCode};
 
         let camel_name = to_camel_case(&cmd.name);
@@ -257,10 +667,8 @@ impl Generator {
             .inputs
             .iter()
             .any(|i| matches!(i.kind, InputKind::Positional));
-        let has_options = cmd
-            .inputs
-            .iter()
-            .any(|i| matches!(i.kind, InputKind::Flag { .. }));
+        let stateful = Self::stateful_ancestors(ancestors);
+        let has_options = Self::has_flags(cmd) || !stateful.is_empty();
 
         // Build imports
         let mut boune_import = Import::new("boune").named("defineCommand");
@@ -271,14 +679,84 @@ impl Generator {
             boune_import = boune_import.named_type("InferOpts");
         }
 
-        let imports = vec![
-            boune_import,
-            Import::new(format!("{}handlers/{}.ts", up_path, handler_path)).named("run"),
-        ];
+        let mut imports = Vec::new();
+        if cmd.inputs.iter().any(|i| i.constraints.path_exists) {
+            imports.push(Import::new("node:fs").named("existsSync"));
+        }
+        imports.push(boune_import);
+        let prompts: Vec<&InputPrompt> = cmd
+            .inputs
+            .iter()
+            .filter_map(|i| i.prompt.as_ref())
+            .filter(|prompt| !matches!(prompt, InputPrompt::Text(_)))
+            .collect();
+        if !prompts.is_empty() {
+            let mut prompts_import = Import::new(format!("{}prompts.ts", up_path));
+            if prompts.contains(&&InputPrompt::Confirm) {
+                prompts_import = prompts_import.named("confirm");
+            }
+            if prompts.contains(&&InputPrompt::Secret) {
+                prompts_import = prompts_import.named("secret");
+            }
+            imports.push(prompts_import);
+        }
+        let mut errors_import = Import::new(format!("{}errors.ts", up_path));
+        if !cmd.groups.is_empty()
+            || cmd.inputs.iter().any(|i| {
+                !self.constraint_lines(i).is_empty()
+                    || !i.requires.is_empty()
+                    || i.required_if.is_some()
+                    || i.prompt_message().is_some()
+            })
+        {
+            errors_import = errors_import.named("CliError");
+        }
+        imports.push(errors_import.named("reportError"));
+        if self.localized() {
+            imports.push(Import::new(format!("{}locale.ts", up_path)).named("t"));
+        }
+        for ancestor in &stateful {
+            let ancestor_path = ancestor
+                .path
+                .iter()
+                .map(|s| to_kebab_case(s))
+                .collect::<Vec<_>>()
+                .join("/");
+            imports.push(
+                Import::new(format!("{}handlers/{}.ts", up_path, ancestor_path)).named(format!(
+                    "state as resolve{}State",
+                    to_pascal_case(&ancestor.name)
+                )),
+            );
+        }
+        imports.push(Import::new(format!("{}handlers/{}.ts", up_path, handler_path)).named("run"));
 
         // Build body parts
         let mut body_parts: Vec<String> = Vec::new();
 
+        // Exit codes declared on this command or inherited from its parents
+        let exit_codes: BTreeMap<&str, u8> = ancestors
+            .iter()
+            .copied()
+            .chain([cmd])
+            .flat_map(|c| {
+                c.exit_codes
+                    .iter()
+                    .map(|(name, code)| (name.as_str(), *code))
+            })
+            .collect();
+        if !exit_codes.is_empty() {
+            let codes = exit_codes
+                .iter()
+                .fold(JsObject::new(), |obj, (name, code)| {
+                    obj.raw(*name, code.to_string())
+                });
+            body_parts.push(format!(
+                "const exitCodes = {} as const;",
+                codes.build().trim_end()
+            ));
+        }
+
         // Arguments schema as const
         if has_args {
             let arguments = cmd
@@ -287,22 +765,36 @@ impl Generator {
                 .filter(|i| matches!(i.kind, InputKind::Positional))
                 .fold(JsObject::new(), |obj, input| {
                     let camel = to_camel_case(&input.name);
-                    obj.object(&camel, self.build_argument_schema_from_ir(input))
+                    obj.object(&camel, self.build_argument_schema_from_ir(&cmd.path, input))
                 });
 
             let args_obj = arguments.build();
             body_parts.push(format!("const args = {} as const;", args_obj.trim_end()));
         }
 
+        // Options inherited from parent commands, declared on the leaf so
+        // they can be passed alongside its own flags
+        for ancestor in &stateful {
+            let inherited = self.build_options_object_from_ir(ancestor).build();
+            body_parts.push(format!(
+                "const {}Options = {} as const;",
+                to_camel_case(&ancestor.name),
+                inherited.trim_end()
+            ));
+        }
+
         // Options schema as const
         if has_options {
+            let options = stateful.iter().fold(JsObject::new(), |obj, ancestor| {
+                obj.spread(format!("{}Options", to_camel_case(&ancestor.name)))
+            });
             let options = cmd
                 .inputs
                 .iter()
                 .filter(|i| matches!(i.kind, InputKind::Flag { .. }))
-                .fold(JsObject::new(), |obj, input| {
-                    let camel = to_camel_case(&input.name);
-                    obj.object(&camel, self.build_option_schema_from_ir(input))
+                .fold(options, |obj, input| {
+                    let key = option_key(&input.name, self.config.flag_case);
+                    obj.object(&key, self.build_option_schema_from_ir(&cmd.path, input))
                 });
 
             let opts_obj = options.build();
@@ -310,8 +802,14 @@ impl Generator {
         }
 
         // Command definition
-        let command_def =
-            self.build_command_definition_from_ir(&camel_name, cmd, has_args, has_options);
+        let command_def = self.build_command_definition_from_ir(
+            &camel_name,
+            cmd,
+            has_args,
+            has_options,
+            ancestors,
+            !exit_codes.is_empty(),
+        );
         body_parts.push(command_def);
 
         // Export inferred types
@@ -332,7 +830,9 @@ impl Generator {
             body_parts.push(type_exports.join("\n"));
         }
 
-        let mut file = CodeFile::new().imports(imports);
+        let mut file = CodeFile::new()
+            .import_extension(self.config.import_extension)
+            .imports(imports);
         for part in body_parts {
             file = file.add(RawCode::new(part));
         }
@@ -345,14 +845,30 @@ impl Generator {
         cmd: &CommandOp,
         has_args: bool,
         has_options: bool,
+        ancestors: &[&CommandOp],
+        has_exit_codes: bool,
     ) -> String {
-        // Build action handler body
-        let action = self.cli_adapter.build_action_handler(has_args, has_options);
+        let action =
+            self.build_action_from_ir(cmd, has_args, has_options, ancestors, has_exit_codes);
 
         // Build command schema - reference extracted consts
-        let schema = JsObject::new()
-            .string("name", &cmd.name)
-            .string("description", &cmd.description)
+        let schema = self
+            .with_description(
+                JsObject::new().string("name", &cmd.name).array_if(
+                    !cmd.aliases.is_empty(),
+                    "aliases",
+                    JsArray::from_strings(&cmd.aliases),
+                ),
+                message_key(&cmd.path, None),
+                Some(&cmd.description),
+            )
+            .string_opt("longDescription", cmd.docs.long_description.as_deref())
+            .array_if(
+                !cmd.docs.examples.is_empty(),
+                "examples",
+                JsArray::from_strings(cmd.docs.examples.iter().map(|e| e.command.as_str())),
+            )
+            .string_opt("epilogue", cmd.docs.footer.as_deref())
             .raw_if(has_args, "arguments", "args")
             .raw_if(has_options, "options", "options")
             .arrow_fn("action", action);
@@ -365,16 +881,485 @@ impl Generator {
         )
     }
 
-    fn build_argument_schema_from_ir(&self, input: &baobao_ir::Input) -> JsObject {
-        self.cli_adapter.build_argument_schema_ir(input)
+    /// Build an action that warns about deprecated commands and flags,
+    /// prompts for inputs and resolves parent state before calling the
+    /// handler, reporting any error it throws.
+    ///
+    /// Each stateful parent's `state()` receives the parsed options and the
+    /// state of the next stateful parent above it; the handler receives the
+    /// nearest parent's state.
+    fn build_action_from_ir(
+        &self,
+        cmd: &CommandOp,
+        has_args: bool,
+        has_options: bool,
+        ancestors: &[&CommandOp],
+        has_exit_codes: bool,
+    ) -> ArrowFn {
+        let params = match (has_args, has_options) {
+            (true, true) => "{ args, options }",
+            (true, false) => "{ args }",
+            (false, true) => "{ options }",
+            (false, false) => "{}",
+        };
+        let stateful = Self::stateful_ancestors(ancestors);
+
+        let mut lines = self.deprecation_lines(cmd, ancestors);
+        for input in &cmd.inputs {
+            lines.extend(self.split_lines(input));
+        }
+        // Confirm first so declining doesn't waste input, and ask for
+        // missing arguments before secrets
+        let mut prompted: Vec<_> = cmd
+            .inputs
+            .iter()
+            .filter_map(|input| {
+                let rank = match input.prompt.as_ref()? {
+                    InputPrompt::Confirm => 0,
+                    InputPrompt::Text(_) => 1,
+                    InputPrompt::Secret => 2,
+                };
+                Some((rank, input))
+            })
+            .collect();
+        prompted.sort_by_key(|(rank, _)| *rank);
+        for (_, input) in prompted {
+            lines.extend(self.prompt_lines(cmd, input));
+        }
+        for group in &cmd.groups {
+            lines.extend(self.group_lines(cmd, group));
+        }
+        for input in &cmd.inputs {
+            lines.extend(self.requirement_lines(cmd, input));
+        }
+        for input in &cmd.inputs {
+            lines.extend(self.constraint_lines(input));
+        }
+
+        let mut parent_var: Option<String> = None;
+        for ancestor in &stateful {
+            let var = format!("{}State", to_camel_case(&ancestor.name));
+            let resolve = format!("resolve{}State", to_pascal_case(&ancestor.name));
+            let call = match &parent_var {
+                Some(parent) => format!("{}(options, {})", resolve, parent),
+                None => format!("{}(options)", resolve),
+            };
+            lines.push(format!("const {} = await {};", var, call));
+            parent_var = Some(var);
+        }
+
+        let mut run_args = Vec::new();
+        if has_args {
+            run_args.push("args".to_string());
+        }
+        if has_options {
+            run_args.push("options".to_string());
+        }
+        run_args.extend(parent_var);
+        lines.push(format!("await run({});", run_args.join(", ")));
+
+        // Report handler errors instead of leaving the rejection unhandled
+        let report = if has_exit_codes {
+            "  reportError(err, exitCodes);"
+        } else {
+            "  reportError(err);"
+        };
+        ArrowFn::new(params)
+            .async_()
+            .body_line("try {")
+            .body_lines(lines.iter().map(|line| format!("  {}", line)))
+            .body_line("} catch (err) {")
+            .body_line(report)
+            .body_line("}")
+    }
+
+    /// Lines that print a warning to stderr for each deprecated command on
+    /// the path to `cmd` and each deprecated flag that is given.
+    fn deprecation_lines(&self, cmd: &CommandOp, ancestors: &[&CommandOp]) -> Vec<String> {
+        let path = ancestors.iter().copied().chain([cmd]);
+        let mut lines: Vec<String> = path
+            .clone()
+            .filter_map(|c| {
+                let message = c.deprecated.as_ref()?;
+                let warning = format!(
+                    "warning: command '{}' is deprecated: {}",
+                    c.path.join(" "),
+                    message
+                );
+                Some(format!("console.error({:?});", warning))
+            })
+            .collect();
+        for input in path.flat_map(|c| &c.inputs) {
+            if let Some(message) = &input.deprecated {
+                let warning = format!(
+                    "warning: flag '{}' is deprecated: {}",
+                    self.label(input),
+                    message
+                );
+                lines.push(format!(
+                    "if ({}) console.error({:?});",
+                    self.given(input),
+                    warning
+                ));
+            }
+        }
+        lines
+    }
+
+    /// Lines that prompt for an input before the handler runs.
+    ///
+    /// A `confirm` flag aborts the command unless it is set or the user
+    /// agrees; a `secret` input is read from the terminal when missing, and
+    /// an argument with a prompt message is asked for unless `--no-input`
+    /// is given.
+    fn prompt_lines(&self, cmd: &CommandOp, input: &baobao_ir::Input) -> Vec<String> {
+        let message = input
+            .description
+            .clone()
+            .unwrap_or_else(|| to_kebab_case(&input.name));
+        let (source, key, access) = match input.kind {
+            InputKind::Positional => {
+                let key = to_camel_case(&input.name);
+                ("args", key.clone(), format!("args.{}", key))
+            }
+            InputKind::Flag { .. } => (
+                "options",
+                option_key(&input.name, self.config.flag_case),
+                option_access("options", &input.name, self.config.flag_case),
+            ),
+        };
+
+        match &input.prompt {
+            Some(InputPrompt::Confirm) => vec![
+                format!("if (!{} && !(await confirm(\"{}\"))) {{", access, message),
+                "  console.error(\"Aborted.\");".to_string(),
+                "  process.exitCode = 1;".to_string(),
+                "  return;".to_string(),
+                "}".to_string(),
+            ],
+            Some(InputPrompt::Secret) => {
+                // Secret inputs are read from the environment before prompting
+                let env = if input.ty == InputType::Secret {
+                    format!(
+                        "process.env.{} ?? ",
+                        to_env_var(&self.ir.meta.name, &cmd.path, &input.name)
+                    )
+                } else {
+                    String::new()
+                };
+                vec![format!(
+                    "{source} = {{ ...{source}, {key}: {access} ?? {env}(await secret(\"{message}\")) }};"
+                )]
+            }
+            // Bun's global `prompt()` reads a line, returning null at the end
+            // of input
+            Some(InputPrompt::Text(question)) => vec![
+                format!(
+                    "if ({access} === undefined && !process.argv.includes({:?})) {{",
+                    format!("--{}", flag_name(NO_INPUT_FLAG, self.config.flag_case))
+                ),
+                format!(
+                    "  {source} = {{ ...{source}, {key}: prompt({question:?}) || undefined }};"
+                ),
+                "}".to_string(),
+                format!(
+                    "if ({access} === undefined) throw new CliError({:?}, 2);",
+                    format!("missing argument {}", self.label(input))
+                ),
+            ],
+            None => vec![],
+        }
+    }
+
+    /// Lines splitting each value of a flag with a `delimiter`, so the checks
+    /// and the handler see `--tags a,b` as two values.
+    fn split_lines(&self, input: &baobao_ir::Input) -> Vec<String> {
+        let Some(delimiter) = input.delimiter else {
+            return vec![];
+        };
+        let key = option_key(&input.name, self.config.flag_case);
+        let access = option_access("options", &input.name, self.config.flag_case);
+        vec![format!(
+            "options = {{ ...options, {key}: {access}?.flatMap((value) => value.split({:?})) }};",
+            delimiter.to_string()
+        )]
+    }
+
+    /// Lines that check the inputs a flag requires, throwing a `CliError`
+    /// with exit code 2 when one is missing.
+    fn requirement_lines(&self, cmd: &CommandOp, input: &baobao_ir::Input) -> Vec<String> {
+        let find = |name: &str| cmd.inputs.iter().find(|i| i.name == name);
+        let mut lines = Vec::new();
+        for other in input.requires.iter().filter_map(|name| find(name)) {
+            lines.push(format!(
+                "if ({} && {}) throw new CliError({:?}, 2);",
+                self.given(input),
+                self.missing(other),
+                format!("{} requires {}", self.label(input), self.label(other))
+            ));
+        }
+        if let Some(required_if) = &input.required_if
+            && let Some(flag) = find(&required_if.flag)
+        {
+            lines.push(format!(
+                "if ({} === {:?} && {}) throw new CliError({:?}, 2);",
+                self.input_access(flag),
+                required_if.value,
+                self.missing(input),
+                format!(
+                    "{} is required when {} is {}",
+                    self.label(input),
+                    self.label(flag),
+                    required_if.value
+                )
+            ));
+        }
+        lines
+    }
+
+    /// Expression that is true when an input is given on the command line.
+    fn given(&self, input: &baobao_ir::Input) -> String {
+        let access = self.input_access(input);
+        match input.ty {
+            _ if input.multiple => format!("({}?.length ?? 0) > 0", access),
+            InputType::Bool => format!("{} === true", access),
+            InputType::Count => format!("{} > 0", access),
+            _ => format!("{} !== undefined", access),
+        }
+    }
+
+    /// Expression that is true when an input is not given, the negation of
+    /// [`Self::given`].
+    fn missing(&self, input: &baobao_ir::Input) -> String {
+        let access = self.input_access(input);
+        match input.ty {
+            _ if input.multiple => format!("({}?.length ?? 0) === 0", access),
+            InputType::Bool => format!("{} !== true", access),
+            InputType::Count => format!("!{}", access),
+            _ => format!("{} === undefined", access),
+        }
+    }
+
+    fn input_access(&self, input: &baobao_ir::Input) -> String {
+        match input.kind {
+            InputKind::Positional => format!("args.{}", to_camel_case(&input.name)),
+            InputKind::Flag { .. } => option_access("options", &input.name, self.config.flag_case),
+        }
+    }
+
+    /// An input as written on the command line, for error messages.
+    fn label(&self, input: &baobao_ir::Input) -> String {
+        match input.kind {
+            InputKind::Positional => format!("<{}>", to_kebab_case(&input.name)),
+            InputKind::Flag { .. } => {
+                format!("--{}", flag_name(&input.name, self.config.flag_case))
+            }
+        }
+    }
+
+    /// Lines that check how many members of a group are given, throwing a
+    /// `CliError` with exit code 2 when an exclusive group has more than one
+    /// or a required group has none.
+    fn group_lines(&self, cmd: &CommandOp, group: &InputGroup) -> Vec<String> {
+        let members: Vec<&baobao_ir::Input> = group
+            .members
+            .iter()
+            .filter_map(|member| cmd.inputs.iter().find(|i| &i.name == member))
+            .collect();
+        let given: Vec<String> = members.iter().map(|input| self.given(input)).collect();
+        let labels: Vec<String> = members.iter().map(|input| self.label(input)).collect();
+        let var = format!("{}Given", to_camel_case(&group.name));
+        let mut lines = vec![format!(
+            "const {} = [{}].filter(Boolean).length;",
+            var,
+            given.join(", ")
+        )];
+        if group.exclusive {
+            lines.push(format!(
+                "if ({} > 1) throw new CliError({:?}, 2);",
+                var,
+                format!("{} cannot be used together", labels.join(", "))
+            ));
+        }
+        if group.required {
+            lines.push(format!(
+                "if ({} === 0) throw new CliError({:?}, 2);",
+                var,
+                format!("one of {} is required", labels.join(", "))
+            ));
+        }
+        lines
+    }
+
+    /// Lines that check an input's value against its type's format and its
+    /// constraints, throwing a `CliError` with exit code 2 when it does not
+    /// satisfy them.
+    fn constraint_lines(&self, input: &baobao_ir::Input) -> Vec<String> {
+        let constraints = &input.constraints;
+        let (access, label) = match input.kind {
+            InputKind::Positional => (
+                format!("args.{}", to_camel_case(&input.name)),
+                format!("<{}>", to_kebab_case(&input.name)),
+            ),
+            InputKind::Flag { .. } => (
+                option_access("options", &input.name, self.config.flag_case),
+                format!("--{}", to_kebab_case(&input.name)),
+            ),
+        };
+        let mut checks = Vec::new();
+        if let Some((condition, message)) = format_check(input.ty) {
+            checks.push((condition.to_string(), message.to_string()));
+        }
+        if let Some(min) = constraints.min {
+            checks.push((
+                format!("value < {}", min),
+                format!("must be at least {}", min),
+            ));
+        }
+        if let Some(max) = constraints.max {
+            checks.push((
+                format!("value > {}", max),
+                format!("must be at most {}", max),
+            ));
+        }
+        if let Some(min) = constraints.min_length {
+            checks.push((
+                format!("[...value].length < {}", min),
+                format!("must be at least {} characters", min),
+            ));
+        }
+        if let Some(max) = constraints.max_length {
+            checks.push((
+                format!("[...value].length > {}", max),
+                format!("must be at most {} characters", max),
+            ));
+        }
+        if let Some(pattern) = &constraints.pattern {
+            checks.push((
+                format!(
+                    "!new RegExp({:?}).test(value)",
+                    format!("^(?:{})$", pattern)
+                ),
+                format!("must match the pattern {}", pattern),
+            ));
+        }
+        if constraints.path_exists {
+            checks.push((
+                "!existsSync(value)".to_string(),
+                "does not exist".to_string(),
+            ));
+        }
+        if checks.is_empty() {
+            return vec![];
+        }
+
+        // Optional inputs may be missing and multiple ones are arrays
+        let mut lines = vec![format!("for (const value of [{} ?? []].flat()) {{", access)];
+        for (condition, message) in checks {
+            lines.push(format!(
+                "  if ({}) throw new CliError({:?}, 2);",
+                condition,
+                format!("{} {}", label, message)
+            ));
+        }
+        lines.push("}".to_string());
+        lines
+    }
+
+    /// Global flags from the IR as `globalOptions` entries.
+    fn global_options(&self) -> Vec<(String, JsObject)> {
+        self.ir
+            .global_inputs
+            .iter()
+            .filter(|input| matches!(input.kind, InputKind::Flag { .. }))
+            .map(|input| {
+                (
+                    option_key(&input.name, self.config.flag_case),
+                    self.build_option_schema_from_ir(&[], input),
+                )
+            })
+            .collect()
+    }
+
+    /// Options taking a value, by path of each command with int or float
+    /// arguments that accept a leading dash.
+    fn hyphen_values(&self) -> Vec<(String, Vec<String>)> {
+        let mut hyphen_values = Vec::new();
+        let mut stack: Vec<&CommandOp> = self.ir.commands().collect();
+        while let Some(cmd) = stack.pop() {
+            stack.extend(&cmd.children);
+            let negative = cmd.inputs.iter().any(|input| {
+                input.allow_hyphen_values && matches!(input.ty, InputType::Int | InputType::Float)
+            });
+            if !negative {
+                continue;
+            }
+            let options = self
+                .ir
+                .global_inputs
+                .iter()
+                .chain(&cmd.inputs)
+                .filter(|input| !matches!(input.ty, InputType::Bool | InputType::Count))
+                .filter_map(|input| match input.kind {
+                    InputKind::Flag { short } => Some((input, short)),
+                    InputKind::Positional => None,
+                })
+                .flat_map(|(input, short)| {
+                    std::iter::once(format!(
+                        "--{}",
+                        flag_name(&input.name, self.config.flag_case)
+                    ))
+                    .chain(short.map(|c| format!("-{}", c)))
+                })
+                .collect();
+            hyphen_values.push((cmd.path.join(" "), options));
+        }
+        hyphen_values.sort();
+        hyphen_values
     }
 
-    fn build_option_schema_from_ir(&self, input: &baobao_ir::Input) -> JsObject {
-        self.cli_adapter.build_option_schema_ir(input)
+    fn build_argument_schema_from_ir(
+        &self,
+        command: &[String],
+        input: &baobao_ir::Input,
+    ) -> JsObject {
+        if !self.localized() {
+            return self.cli_adapter.build_argument_schema_ir(input);
+        }
+        let schema = self
+            .cli_adapter
+            .build_argument_schema_ir(&baobao_ir::Input {
+                description: None,
+                ..input.clone()
+            });
+        self.with_description(
+            schema,
+            message_key(command, Some(&input.name)),
+            input.description.as_deref(),
+        )
     }
 
-    /// Generate handlers directory with stub files for missing handlers.
-    fn generate_handlers(&self, handlers_dir: &Path, _output_dir: &Path) -> Result<GenerateResult> {
+    fn build_option_schema_from_ir(
+        &self,
+        command: &[String],
+        input: &baobao_ir::Input,
+    ) -> JsObject {
+        if !self.localized() {
+            return self.cli_adapter.build_option_schema_ir(input);
+        }
+        let schema = self.cli_adapter.build_option_schema_ir(&baobao_ir::Input {
+            description: None,
+            ..input.clone()
+        });
+        self.with_description(
+            schema,
+            message_key(command, Some(&input.name)),
+            input.description.as_deref(),
+        )
+    }
+
+    /// Write handler stubs for new commands and report unused ones.
+    fn generate_handlers(&self, handlers_dir: &Path, output_dir: &Path) -> Result<GenerateResult> {
         let mut created_handlers = Vec::new();
 
         // Collect all expected handler paths from computed data (kebab-case for TypeScript file names)
@@ -393,10 +1378,10 @@ impl Generator {
         // Ensure handlers directory exists
         std::fs::create_dir_all(handlers_dir)?;
 
-        // Process commands recursively from IR
-        for op in &self.ir.operations {
-            let Operation::Command(cmd) = op;
-            self.generate_handlers_for_command(cmd, handlers_dir, &mut created_handlers)?;
+        for entry in self.handler_entries() {
+            if entry.write(output_dir)?.is_created() {
+                created_handlers.push(entry.path.trim_start_matches("src/handlers/").to_string());
+            }
         }
 
         // Find orphan handlers using shared utility
@@ -406,64 +1391,83 @@ impl Generator {
         Ok(GenerateResult {
             created_handlers,
             orphan_handlers,
+            ..Default::default()
         })
     }
 
-    /// Recursively generate handlers for a command and its children.
-    fn generate_handlers_for_command(
+    /// Handler stubs for every leaf command, and state handlers for parents
+    /// with flags.
+    fn handler_entries(&self) -> Vec<FileEntry> {
+        let mut entries = Vec::new();
+        for cmd in self.ir.commands() {
+            self.handler_entries_for_command(cmd, &[], &mut entries);
+        }
+        entries
+    }
+
+    /// Recursively collect handler stubs for a command and its children.
+    fn handler_entries_for_command(
         &self,
         cmd: &CommandOp,
-        handlers_dir: &Path,
-        created_handlers: &mut Vec<String>,
-    ) -> Result<()> {
-        use baobao_core::WriteResult;
-
-        let handler_path: Vec<&str> = cmd.path.iter().map(|s| s.as_str()).collect();
-        let dir = handler_path
-            .iter()
-            .take(handler_path.len().saturating_sub(1))
-            .fold(handlers_dir.to_path_buf(), |acc, segment| {
-                acc.join(to_kebab_case(segment))
-            });
+        ancestors: &[&CommandOp],
+        entries: &mut Vec<FileEntry>,
+    ) {
+        let path = format!(
+            "src/handlers/{}.ts",
+            cmd.path
+                .iter()
+                .map(|s| to_kebab_case(s))
+                .collect::<Vec<_>>()
+                .join("/")
+        );
+        let parent_state = Self::stateful_ancestors(ancestors)
+            .last()
+            .map(|a| a.path.clone());
 
         if cmd.has_subcommands() {
-            // Parent command - create directory
-            let cmd_dir = dir.join(to_kebab_case(&cmd.name));
-            std::fs::create_dir_all(&cmd_dir)?;
+            // Parent flags are resolved into state shared with subcommands
+            if Self::has_flags(cmd) {
+                let stub = StateHandlerTs::new(&cmd.name, cmd.path.clone(), parent_state)
+                    .with_import_extension(self.config.import_extension);
+                entries.push(FileEntry::from_generated(
+                    path,
+                    &stub,
+                    FileCategory::Handler,
+                ));
+            }
 
             // Recursively process children
+            let mut child_ancestors = ancestors.to_vec();
+            child_ancestors.push(cmd);
             for child in &cmd.children {
-                self.generate_handlers_for_command(child, handlers_dir, created_handlers)?;
+                self.handler_entries_for_command(child, &child_ancestors, entries);
             }
         } else {
-            // Leaf command - create handler stub
-            std::fs::create_dir_all(&dir)?;
-
-            let display_path = cmd
-                .path
-                .iter()
-                .map(|s| to_kebab_case(s))
-                .collect::<Vec<_>>()
-                .join("/");
-            let path_segments = cmd.path.clone();
+            // Leaf command - handler stub
             let has_args = cmd
                 .inputs
                 .iter()
                 .any(|i| matches!(i.kind, InputKind::Positional));
-            let has_options = cmd
-                .inputs
-                .iter()
-                .any(|i| matches!(i.kind, InputKind::Flag { .. }));
-
-            let stub = HandlerTs::nested(&cmd.name, path_segments, has_args, has_options);
-            let result = stub.write(&dir)?;
+            let has_options = Self::has_flags(cmd) || parent_state.is_some();
 
-            if matches!(result, WriteResult::Written) {
-                created_handlers.push(format!("{}.ts", display_path));
+            let examples = cmd
+                .docs
+                .examples
+                .iter()
+                .map(|e| e.command.clone())
+                .collect();
+            let mut stub = HandlerTs::nested(&cmd.name, cmd.path.clone(), has_args, has_options)
+                .with_import_extension(self.config.import_extension)
+                .with_examples(examples);
+            if let Some(parent) = parent_state {
+                stub = stub.with_parent_state(parent);
             }
+            entries.push(FileEntry::from_generated(
+                path,
+                &stub,
+                FileCategory::Handler,
+            ));
         }
-
-        Ok(())
     }
 
     /// Clean orphaned generated files.
@@ -473,12 +1477,8 @@ impl Generator {
         // Collect expected command names from IR (kebab-case for file names)
         let expected_commands: HashSet<String> = self
             .ir
-            .operations
-            .iter()
-            .map(|op| {
-                let Operation::Command(cmd) = op;
-                to_kebab_case(&cmd.name)
-            })
+            .commands()
+            .map(|cmd| to_kebab_case(&cmd.name))
             .collect();
 
         // Collect expected handler paths from computed data (kebab-case)
@@ -495,16 +1495,17 @@ impl Generator {
             .collect();
 
         // Find and delete orphaned generated command files
-        let commands_dir = output_dir.join("src/commands");
+        let commands_dir = join_relative(output_dir, "src/commands");
         let orphan_commands = find_orphan_commands(&commands_dir, "ts", &expected_commands)?;
         for path in orphan_commands {
             std::fs::remove_file(&path)?;
-            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
-            result.deleted_commands.push(relative.display().to_string());
+            result
+                .deleted_commands
+                .push(relative_display(&path, output_dir));
         }
 
         // Find and handle orphaned handler files
-        let handlers_dir = output_dir.join("src/handlers");
+        let handlers_dir = join_relative(output_dir, "src/handlers");
         let handler_paths = HandlerPaths::new(&handlers_dir, "ts", STUB_MARKER);
         let orphan_handlers = handler_paths.find_orphans_with_status(&expected_handlers)?;
 
@@ -538,12 +1539,8 @@ impl Generator {
         // Collect expected command names from IR (kebab-case for file names)
         let expected_commands: HashSet<String> = self
             .ir
-            .operations
-            .iter()
-            .map(|op| {
-                let Operation::Command(cmd) = op;
-                to_kebab_case(&cmd.name)
-            })
+            .commands()
+            .map(|cmd| to_kebab_case(&cmd.name))
             .collect();
 
         // Collect expected handler paths from computed data (kebab-case)
@@ -560,15 +1557,16 @@ impl Generator {
             .collect();
 
         // Find orphaned generated command files
-        let commands_dir = output_dir.join("src/commands");
+        let commands_dir = join_relative(output_dir, "src/commands");
         let orphan_commands = find_orphan_commands(&commands_dir, "ts", &expected_commands)?;
         for path in orphan_commands {
-            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
-            result.deleted_commands.push(relative.display().to_string());
+            result
+                .deleted_commands
+                .push(relative_display(&path, output_dir));
         }
 
         // Find orphaned handler files
-        let handlers_dir = output_dir.join("src/handlers");
+        let handlers_dir = join_relative(output_dir, "src/handlers");
         let handler_paths = HandlerPaths::new(&handlers_dir, "ts", STUB_MARKER);
         let orphan_handlers = handler_paths.find_orphans_with_status(&expected_handlers)?;
 
@@ -605,3 +1603,24 @@ impl Generator {
         Ok(())
     }
 }
+
+/// Condition under which a value of a string-backed type is malformed, and the
+/// message describing the expected format.
+fn format_check(ty: InputType) -> Option<(&'static str, &'static str)> {
+    match ty {
+        InputType::Url => Some(("!URL.canParse(value)", "must be a URL")),
+        InputType::Duration => Some((
+            r"!/^(\d+(\.\d+)?(ns|us|µs|ms|s|m|h))+$/.test(value)",
+            "must be a duration such as 1h30m",
+        )),
+        InputType::DateTime => Some((
+            r"!/^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$/.test(value) || Number.isNaN(Date.parse(value))",
+            "must be an RFC 3339 datetime",
+        )),
+        InputType::Uuid => Some((
+            "!/^[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}$/i.test(value)",
+            "must be a UUID",
+        )),
+        _ => None,
+    }
+}
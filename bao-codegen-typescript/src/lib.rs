@@ -31,9 +31,14 @@
 //! - `src/cli.ts` - Main CLI setup with boune
 //! - `src/context.ts` - Shared context (database pools, HTTP clients)
 //! - `src/index.ts` - Entry point
+//! - `src/ui.ts` - Colored output helpers (success/warn/error)
+//! - `src/errors.ts` - `CliError` and error reporting with per-command exit codes
+//! - `src/completions.ts` - `completions` subcommand emitting bash/zsh/fish scripts
+//! - `src/progress.ts` - Spinner helpers (opt-in via `[typescript] progress = true`)
 //! - `src/commands/*.ts` - Command definitions
 //! - `src/handlers/*.ts` - Handler stubs for implementation
 //! - `package.json`, `tsconfig.json`, `bao.toml`, `.gitignore`
+//! - `README.md` - Usage docs, with a generated region refreshed on each bake
 
 /// Target boune version for generated code.
 pub const BOUNE_VERSION: &str = "^0.9.0";
@@ -49,7 +54,7 @@ pub mod adapters;
 pub mod ast;
 pub mod files;
 
-pub use adapters::{BouneAdapter, BunSqliteAdapter};
+pub use adapters::{BouneAdapter, BunSqliteAdapter, ConnectAdapter, DuckdbAdapter};
 pub use ast::{ArrowFn, Import, JsObject};
 pub use baobao_codegen::language::{GenerateResult, LanguageCodegen, PreviewFile};
 pub use code_file::{CodeFile, RawCode, Shebang};
@@ -57,4 +62,4 @@ pub use generator::Generator;
 pub use naming::TS_NAMING;
 pub use renderer::TypeScriptRenderer;
 pub use structure_renderer::TypeScriptStructureRenderer;
-pub use type_mapper::{TypeScriptCodeTypeMapper, TypeScriptTypeMapper};
+pub use type_mapper::{TS_TYPES, TypeScriptCodeTypeMapper, TypeScriptTypeMapper};
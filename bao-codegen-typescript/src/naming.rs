@@ -2,6 +2,7 @@
 
 use baobao_codegen::language::NamingConvention;
 use baobao_core::{to_camel_case, to_kebab_case, to_pascal_case};
+use baobao_manifest::FlagCase;
 
 fn escape_ts_reserved(name: &str) -> String {
     format!("_{}", name)
@@ -88,10 +89,58 @@ pub const TS_NAMING: NamingConvention = NamingConvention {
     escape_reserved: escape_ts_reserved,
 };
 
+/// Key of a flag in the generated `options` object.
+///
+/// Preserved names that are not valid identifiers are quoted.
+pub(crate) fn option_key(name: &str, case: FlagCase) -> String {
+    match case {
+        FlagCase::Camel => to_camel_case(name),
+        FlagCase::Preserve if is_identifier(name) => name.to_string(),
+        FlagCase::Preserve => format!("\"{}\"", name),
+    }
+}
+
+/// Expression reading a flag from `source` (e.g. `options.dryRun`).
+pub(crate) fn option_access(source: &str, name: &str, case: FlagCase) -> String {
+    match case {
+        FlagCase::Preserve if !is_identifier(name) => format!("{}[\"{}\"]", source, name),
+        _ => format!("{}.{}", source, option_key(name, case)),
+    }
+}
+
+/// Long flag as typed on the command line, without the leading dashes.
+pub(crate) fn flag_name(name: &str, case: FlagCase) -> String {
+    match case {
+        FlagCase::Camel => to_kebab_case(name),
+        FlagCase::Preserve => name.to_string(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_option_naming() {
+        assert_eq!(option_key("dry_run", FlagCase::Camel), "dryRun");
+        assert_eq!(option_key("dry_run", FlagCase::Preserve), "dry_run");
+        assert_eq!(option_key("dry-run", FlagCase::Preserve), "\"dry-run\"");
+        assert_eq!(
+            option_access("options", "dry-run", FlagCase::Preserve),
+            "options[\"dry-run\"]"
+        );
+        assert_eq!(flag_name("dry_run", FlagCase::Camel), "dry-run");
+        assert_eq!(flag_name("dry_run", FlagCase::Preserve), "dry_run");
+    }
+
     #[test]
     fn test_ts_naming_type() {
         assert_eq!(TS_NAMING.type_name("hello-world"), "HelloWorld");
@@ -1,12 +1,32 @@
 //! TypeScript type mapper implementation.
 
+use std::sync::LazyLock;
+
 #[cfg(test)]
 use baobao_codegen::builder::TypeRef;
 use baobao_codegen::{
     builder::{PrimitiveType, TypeMapper as CodeIRTypeMapper},
-    language::TypeMapper,
+    language::{ScalarType, TypeMapper, TypeRegistry},
 };
-use baobao_core::{ArgType, ContextFieldType, DatabaseType};
+use baobao_core::{ArgType, ContextFieldType, DatabaseType, QueueType};
+
+/// TypeScript types for each argument type. boune parses values from the
+/// declared option type, so no parse snippets are needed. URLs, durations,
+/// datetimes and UUIDs stay strings, checked by the command's action.
+pub static TS_TYPES: LazyLock<TypeRegistry> = LazyLock::new(|| {
+    TypeRegistry::new()
+        .register(ScalarType::new(ArgType::String, "string"))
+        .register(ScalarType::new(ArgType::Int, "number"))
+        .register(ScalarType::new(ArgType::Float, "number"))
+        .register(ScalarType::new(ArgType::Bool, "boolean"))
+        .register(ScalarType::new(ArgType::Path, "string"))
+        .register(ScalarType::new(ArgType::Count, "number"))
+        .register(ScalarType::new(ArgType::Url, "string"))
+        .register(ScalarType::new(ArgType::Duration, "string"))
+        .register(ScalarType::new(ArgType::DateTime, "string"))
+        .register(ScalarType::new(ArgType::Uuid, "string"))
+        .register(ScalarType::new(ArgType::Secret, "string"))
+});
 
 /// TypeScript type mapper implementation.
 pub struct TypeScriptTypeMapper;
@@ -55,13 +75,7 @@ impl TypeMapper for TypeScriptTypeMapper {
     }
 
     fn map_arg_type(&self, arg_type: ArgType) -> &'static str {
-        match arg_type {
-            ArgType::String => "string",
-            ArgType::Int => "number",
-            ArgType::Float => "number",
-            ArgType::Bool => "boolean",
-            ArgType::Path => "string",
-        }
+        TS_TYPES.type_name(arg_type)
     }
 
     fn map_optional_arg_type(&self, arg_type: ArgType) -> String {
@@ -75,7 +89,31 @@ impl TypeMapper for TypeScriptTypeMapper {
             // For Postgres/MySQL, we'll use placeholder types for now
             ContextFieldType::Database(DatabaseType::Postgres) => "unknown",
             ContextFieldType::Database(DatabaseType::Mysql) => "unknown",
-            ContextFieldType::Http => "unknown",
+            // @duckdb/node-api connection
+            ContextFieldType::Database(DatabaseType::Duckdb) => "DuckDBConnection",
+            ContextFieldType::Http => "HttpClient",
+            ContextFieldType::Logger => "Logger",
+            // Bun's native S3 client
+            ContextFieldType::Storage => "S3Client",
+            // connect-es transport
+            ContextFieldType::Grpc => "Transport",
+            // nodemailer transport
+            ContextFieldType::Email => "Transporter",
+            // Interface generated in config.ts
+            ContextFieldType::Config => "Config",
+            // Accessor generated in secrets.ts
+            ContextFieldType::Secrets => "Secrets",
+            // fetch-based client generated in llm.ts
+            ContextFieldType::Llm => "LlmClient",
+            // LRU map generated in cache.ts
+            ContextFieldType::Cache => "Cache",
+            // Directories created in workdir.ts
+            ContextFieldType::Workdir => "Workdir",
+            // Lazily connected client
+            ContextFieldType::Queue(QueueType::Nats) => "() => Promise<NatsConnection>",
+            ContextFieldType::Queue(QueueType::Kafka) => "() => Promise<Producer>",
+            // Rendered by the resource adapter
+            ContextFieldType::Custom => "unknown",
         }
     }
 }
@@ -84,6 +122,11 @@ impl TypeMapper for TypeScriptTypeMapper {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ts_types_complete() {
+        assert!(TS_TYPES.missing().is_empty());
+    }
+
     #[test]
     fn test_typescript_arg_types() {
         let mapper = TypeScriptTypeMapper;
@@ -93,6 +136,10 @@ mod tests {
         assert_eq!(mapper.map_arg_type(ArgType::Float), "number");
         assert_eq!(mapper.map_arg_type(ArgType::Bool), "boolean");
         assert_eq!(mapper.map_arg_type(ArgType::Path), "string");
+        assert_eq!(mapper.map_arg_type(ArgType::Count), "number");
+        assert_eq!(mapper.map_arg_type(ArgType::Url), "string");
+        assert_eq!(mapper.map_arg_type(ArgType::Uuid), "string");
+        assert_eq!(mapper.map_arg_type(ArgType::Secret), "string");
     }
 
     #[test]
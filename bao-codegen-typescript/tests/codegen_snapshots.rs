@@ -7,6 +7,7 @@ use std::str::FromStr;
 
 use baobao_codegen::pipeline::Pipeline;
 use baobao_codegen_typescript::{Generator, LanguageCodegen};
+use baobao_ir::{Input, InputKind, InputType};
 use baobao_manifest::Manifest;
 
 /// Generate code from a schema and return files sorted by path for deterministic snapshots.
@@ -179,151 +180,1833 @@ fn test_cli_with_choices() {
 }
 
 #[test]
-fn test_cli_with_subcommands_structure() {
+fn test_cli_with_multiple_values() {
     let files = generate_files(
         r#"
         [cli]
-        name = "dbcli"
-        version = "1.0.0"
+        name = "git"
         language = "typescript"
 
-        [commands.db]
-        description = "Database commands"
+        [commands.add]
+        description = "Add files to the index"
 
-        [commands.db.commands.migrate]
-        description = "Run migrations"
+        [commands.add.args.paths]
+        type = "path"
+        multiple = true
 
-        [commands.db.commands.seed]
-        description = "Seed the database"
+        [commands.add.flags.tag]
+        type = "string"
+        short = "t"
+        multiple = true
+        default = ["latest"]
 
-        [commands.db.commands.seed.args.file]
-        type = "path"
-        description = "Seed file"
+        [commands.add.flags.verbose]
+        type = "count"
+        short = "v"
         "#,
     );
 
-    // Parent command for subcommands - check structure
-    let db_command = get_file(&files, "src/commands/db.ts").expect("DB command file not found");
-    assert!(db_command.contains("export const dbCommand = defineCommand"));
-    assert!(db_command.contains("subcommands:"));
-    assert!(db_command.contains("migrate: migrateCommand"));
-    assert!(db_command.contains("seed: seedCommand"));
+    let command = get_file(&files, "src/commands/add.ts").expect("Command file not found");
+    insta::assert_snapshot!("multiple_values_command", command);
+}
 
-    // Verify subcommand files exist
-    assert!(get_file(&files, "src/commands/db/migrate.ts").is_some());
-    assert!(get_file(&files, "src/commands/db/seed.ts").is_some());
+#[test]
+fn test_cli_with_delimiter() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "git"
+        language = "typescript"
+
+        [commands.add]
+        description = "Add files to the index"
+
+        [commands.add.flags.tags]
+        type = "string"
+        multiple = true
+        delimiter = ","
+        max_length = 8
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/add.ts").expect("Command file not found");
+    let split = command
+        .find(
+            "options = { ...options, tags: options.tags?.flatMap((value) => value.split(\",\")) };",
+        )
+        .expect("split line not found");
+    let check = command
+        .find("for (const value of [options.tags ?? []].flat())")
+        .expect("constraint check not found");
+    assert!(split < check);
 }
 
 #[test]
-fn test_cli_with_all_arg_types() {
+fn test_cli_with_value_constraints() {
     let files = generate_files(
         r#"
         [cli]
-        name = "typetest"
-        version = "1.0.0"
+        name = "myapp"
         language = "typescript"
 
-        [commands.test]
-        description = "Test all argument types"
+        [commands.greet]
+        description = "Greet someone"
 
-        [commands.test.args.str_arg]
+        [commands.greet.args.name]
         type = "string"
-        description = "A string argument"
+        pattern = "[a-z]+"
+        min_length = 2
+        max_length = 16
 
-        [commands.test.args.int_arg]
+        [commands.greet.flags.times]
         type = "int"
-        description = "An integer argument"
+        min = 1
+        max = 5
+        default = 1
 
-        [commands.test.args.float_arg]
+        [commands.greet.flags.volume]
         type = "float"
-        description = "A float argument"
+        min = 0
+        multiple = true
 
-        [commands.test.args.bool_arg]
-        type = "bool"
-        description = "A boolean argument"
+        [commands.greet.flags.config]
+        type = "path"
+        path_exists = true
+        "#,
+    );
 
-        [commands.test.args.path_arg]
+    let command = get_file(&files, "src/commands/greet.ts").expect("Command file not found");
+    insta::assert_snapshot!("value_constraints_command", command);
+}
+
+#[test]
+fn test_cli_with_format_check_imports_cli_error() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.site]
+        type = "url"
+
+        [commands.greet.args.name]
+        type = "string"
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/greet.ts").expect("Command file not found");
+    assert!(command.contains(r#"import { CliError, reportError } from "../errors.ts";"#));
+    assert!(command.contains("for (const value of [args.site ?? []].flat())"));
+    assert!(!command.contains("for (const value of [args.name ?? []].flat())"));
+}
+
+#[test]
+fn test_cli_with_input_groups() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.build]
+        description = "Build the project"
+
+        [commands.build.args.input]
         type = "path"
-        description = "A path argument"
+        required = false
+
+        [commands.build.flags.json]
+        type = "bool"
+
+        [commands.build.flags.yaml]
+        type = "bool"
+
+        [commands.build.flags.target]
+        type = "string"
+
+        [commands.build.groups.output]
+        members = ["json", "yaml"]
+        exclusive = true
+
+        [commands.build.groups.source]
+        members = ["input", "target"]
+        required = true
         "#,
     );
 
-    let command = get_file(&files, "src/commands/test.ts").expect("Command file not found");
+    let command = get_file(&files, "src/commands/build.ts").expect("Command file not found");
+    insta::assert_snapshot!("input_groups_command", command);
+}
 
-    // Verify all type mappings are correct (order may vary due to HashMap)
-    assert!(command.contains("strArg:"));
-    assert!(command.contains("type: \"string\""));
-    assert!(command.contains("intArg:"));
-    assert!(command.contains("floatArg:"));
-    assert!(command.contains("type: \"number\"")); // both int and float map to number
-    assert!(command.contains("boolArg:"));
-    assert!(command.contains("type: \"boolean\""));
-    assert!(command.contains("pathArg:"));
-    assert!(command.contains("export type TestArgs = InferArgs<typeof args>"));
+#[test]
+fn test_cli_with_command_aliases() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.list]
+        description = "List items"
+        aliases = ["ls", "l"]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        aliases = ["m"]
+        "#,
+    );
+
+    let list = get_file(&files, "src/commands/list.ts").expect("list.ts not found");
+    assert!(list.contains("aliases: [\"ls\", \"l\"],"));
+    let migrate = get_file(&files, "src/commands/db/migrate.ts").expect("migrate.ts not found");
+    assert!(migrate.contains("aliases: [\"m\"],"));
 }
 
 #[test]
-fn test_handler_stub() {
+fn test_cli_with_deprecations() {
     let files = generate_files(
         r#"
         [cli]
         name = "myapp"
-        version = "1.0.0"
         language = "typescript"
 
-        [commands.hello]
-        description = "Say hello"
+        [commands.old]
+        description = "Old command"
+        deprecated = "use `new` instead"
 
-        [commands.hello.args.name]
+        [commands.db]
+        description = "Database commands"
+        deprecated = "use `store`"
+
+        [commands.db.flags.url]
         type = "string"
-        description = "Name to greet"
+        deprecated = "set DB_URL"
 
-        [commands.hello.flags.loud]
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.db.commands.reset.flags.force]
         type = "bool"
-        description = "Shout the greeting"
+        deprecated = "reset always forces"
         "#,
     );
 
-    // Handler stubs are only created when generating to disk (not in preview)
-    // since they should not overwrite existing implementations.
-    // Test command file instead to verify handler import path is correct.
-    let command = get_file(&files, "src/commands/hello.ts").expect("Command file not found");
-    insta::assert_snapshot!("command_with_handler_import", command);
+    let old = get_file(&files, "src/commands/old.ts").expect("old.ts not found");
+    assert!(
+        old.contains("console.error(\"warning: command 'old' is deprecated: use `new` instead\");")
+    );
+    let reset = get_file(&files, "src/commands/db/reset.ts").expect("reset.ts not found");
+    assert!(reset.contains("console.error(\"warning: command 'db' is deprecated: use `store`\");"));
+    assert!(reset.contains(
+        "if (options.url !== undefined) console.error(\"warning: flag '--url' is deprecated: set DB_URL\");"
+    ));
+    assert!(reset.contains(
+        "if (options.force === true) console.error(\"warning: flag '--force' is deprecated: reset always forces\");"
+    ));
 }
 
 #[test]
-fn test_index_file() {
+fn test_cli_with_examples() {
+    let manifest = r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        examples = ["myapp deploy prod --force", "myapp deploy \"staging area\""]
+
+        [commands.deploy.args.env]
+        type = "string"
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        "#;
+    let files = generate_files(manifest);
+
+    let deploy = get_file(&files, "src/commands/deploy.ts").expect("deploy.ts not found");
+    assert!(
+        deploy.contains(
+            r#"examples: ["myapp deploy prod --force", "myapp deploy \"staging area\""],"#
+        )
+    );
+    let ctx = Pipeline::new()
+        .run(Manifest::from_str(manifest).expect("Failed to parse schema"))
+        .expect("Pipeline failed");
+    let entries = Generator::from_context(ctx).files().expect("files failed");
+    let handler = entries
+        .iter()
+        .find(|f| f.path == "src/handlers/deploy.ts")
+        .and_then(|f| f.content.as_text())
+        .expect("handler stub not found");
+    assert!(handler.contains(
+        "/**\n * @example\n * myapp deploy prod --force\n * myapp deploy \"staging area\"\n */\nexport async function run("
+    ));
+}
+
+#[test]
+fn test_url_duration_datetime_uuid_types() {
     let files = generate_files(
         r#"
         [cli]
         name = "myapp"
-        version = "1.0.0"
         language = "typescript"
 
-        [commands.hello]
-        description = "Say hello"
+        [commands.fetch]
+        description = "Fetch a resource"
+
+        [commands.fetch.args.url]
+        type = "url"
+
+        [commands.fetch.flags.timeout]
+        type = "duration"
+        default = "30s"
+
+        [commands.fetch.flags.since]
+        type = "datetime"
+
+        [commands.fetch.flags.id]
+        type = "uuid"
+        multiple = true
         "#,
     );
 
-    let index = get_file(&files, "src/index.ts").expect("Index file not found");
-    insta::assert_snapshot!("index_file", index);
+    let fetch = get_file(&files, "src/commands/fetch.ts").expect("fetch.ts not found");
+    assert!(
+        fetch
+            .contains(r#"if (!URL.canParse(value)) throw new CliError("<url> must be a URL", 2);"#)
+    );
+    assert!(
+        fetch.contains(r#"throw new CliError("--timeout must be a duration such as 1h30m", 2);"#)
+    );
+    assert!(fetch.contains(r#"throw new CliError("--since must be an RFC 3339 datetime", 2);"#));
+    assert!(fetch.contains(r#"throw new CliError("--id must be a UUID", 2);"#));
 }
 
 #[test]
-fn test_package_json() {
+fn test_positional_order() {
     let files = generate_files(
         r#"
         [cli]
         name = "myapp"
-        version = "1.2.3"
         language = "typescript"
-        description = "My awesome CLI"
+
+        [commands.copy]
+        description = "Copy a file"
+
+        [commands.copy.args.source]
+        type = "path"
+        index = 1
+
+        [commands.copy.args.dest]
+        type = "path"
+        index = 2
+        "#,
+    );
+
+    let copy = get_file(&files, "src/commands/copy.ts").expect("copy.ts not found");
+    assert!(copy.find("source: {").unwrap() < copy.find("dest: {").unwrap());
+}
+
+#[test]
+fn test_cli_with_long_descriptions() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        docs_command = true
+
+        [commands.db]
+        description = "Database commands"
+        long_description = "Manage the \"main\" database."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        long_description = "Builds the project.\n\nThen uploads it."
+
+        [commands.deploy.args.env]
+        type = "string"
+        long_description = "Any environment from deploy.toml."
+
+        [commands.deploy.flags.force]
+        type = "bool"
+        long_description = "Skips the confirmation prompt."
+        "#,
+    );
+
+    let deploy = get_file(&files, "src/commands/deploy.ts").expect("deploy.ts not found");
+    assert!(deploy.contains(r#"longDescription: "Builds the project.\n\nThen uploads it.","#));
+    assert!(deploy.contains(r#"longDescription: "Any environment from deploy.toml.","#));
+    assert!(deploy.contains(r#"longDescription: "Skips the confirmation prompt.","#));
+    let db = get_file(&files, "src/commands/db.ts").expect("db.ts not found");
+    assert!(db.contains(r#"longDescription: "Manage the \"main\" database.","#));
+    let docs = get_file(&files, "src/docs.ts").expect("docs.ts not found");
+    assert!(docs.contains("Deploy the app\n\nBuilds the project.\n\nThen uploads it.\n\n## Usage"));
+    assert!(docs.contains(".SH DESCRIPTION\nBuilds the project.\n.PP\nThen uploads it.\n"));
+}
+
+#[test]
+fn test_cli_with_footers() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        footer = "Docs: https://example.com/myapp"
+
+        [commands.db]
+        description = "Database commands"
+        footer = "Ask \"ops\" for help."
+
+        [commands.db.commands.reset]
+        description = "Reset the database"
+        footer = "Cannot be undone."
+        "#,
+    );
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains(r#"epilogue: "Docs: https://example.com/myapp","#));
+    let db = get_file(&files, "src/commands/db.ts").expect("db.ts not found");
+    assert!(db.contains(r#"epilogue: "Ask \"ops\" for help.","#));
+    let reset = get_file(&files, "src/commands/db/reset.ts").expect("reset.ts not found");
+    assert!(reset.contains(r#"epilogue: "Cannot be undone.","#));
+}
+
+#[test]
+fn test_external_subcommands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        external_subcommands = true
 
         [commands.hello]
         description = "Say hello"
+        aliases = ["hi"]
         "#,
     );
 
-    let package = get_file(&files, "package.json").expect("package.json not found");
-    insta::assert_snapshot!("package_json", package);
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("const cli = defineCli({"));
+    assert!(cli.contains(r#"const commands = new Set(["hello", "hi", "completions"]);"#));
+    assert!(cli.contains("const program = Bun.which(`myapp-${command}`);"));
+    assert!(cli.contains("export const app = {"));
+    let index = get_file(&files, "src/index.ts").expect("index.ts not found");
+    assert!(index.contains("app.run();"));
+}
+
+#[test]
+fn test_default_commands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        default_command = "status"
+
+        [commands.status]
+        description = "Show status"
+
+        [commands.db]
+        description = "Database commands"
+        aliases = ["d"]
+        default_command = "list"
+
+        [commands.db.commands.list]
+        description = "List tables"
+
+        [commands.db.commands.drop]
+        description = "Drop a table"
+        "#,
+    );
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("const cli = defineCli({"));
+    assert!(cli.contains(
+        r#"  "": { commands: { "db": "db", "d": "db", "status": "status", "completions": "completions" }, default: "status" },"#
+    ));
+    assert!(
+        cli.contains(
+            r#"  "db": { commands: { "drop": "drop", "list": "list" }, default: "list" },"#
+        )
+    );
+    assert!(cli.contains(
+        "    process.argv.splice(2, Infinity, ...withDefaultCommand(process.argv.slice(2)));\n    return cli.run();"
+    ));
+    assert!(!cli.contains("runExternal"));
+}
+
+#[test]
+fn test_allow_hyphen_values() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.seek]
+        description = "Move the cursor"
+
+        [commands.seek.args.offset]
+        type = "int"
+        allow_hyphen_values = true
+
+        [commands.seek.flags.unit]
+        type = "string"
+        short = "u"
+
+        [commands.seek.flags.relative]
+        type = "bool"
+        "#,
+    );
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains(r#"  "seek": ["--unit", "-u"],"#));
+    assert!(cli.contains(
+        "    process.argv.splice(2, Infinity, ...withHyphenValues(process.argv.slice(2)));\n    return cli.run();"
+    ));
+    assert!(cli.contains("function commandPath(argv: string[]): [string, number] {"));
+    assert!(!cli.contains("withDefaultCommand"));
+}
+
+#[test]
+fn test_secrets() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.login]
+        description = "Log in"
+
+        [commands.login.args.password]
+        type = "secret"
+        description = "Password"
+
+        [commands.login.flags.api-token]
+        type = "secret"
+        "#,
+    );
+
+    let login = get_file(&files, "src/commands/login.ts").expect("login.ts not found");
+    assert!(login.contains("import { secret } from"));
+    assert!(login.contains(
+        "password: args.password ?? process.env.MYAPP_LOGIN_PASSWORD ?? (await secret(\"Password\"))"
+    ));
+    assert!(login.contains("process.env.MYAPP_LOGIN_API_TOKEN ?? (await secret(\"api-token\"))"));
+    assert!(get_file(&files, "src/prompts.ts").is_some());
+}
+
+#[test]
+fn test_prompt_message() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.greet]
+        description = "Greet someone"
+
+        [commands.greet.args.name]
+        type = "string"
+        prompt = "What is your name?"
+        "#,
+    );
+
+    let greet = get_file(&files, "src/commands/greet.ts").expect("greet.ts not found");
+    assert!(
+        greet.contains("if (args.name === undefined && !process.argv.includes(\"--no-input\")) {")
+    );
+    assert!(greet.contains("name: prompt(\"What is your name?\") || undefined"));
+    assert!(greet.contains("throw new CliError(\"missing argument <name>\", 2);"));
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("noInput: {"));
+}
+
+#[test]
+fn test_cli_with_input_requirements() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.deploy]
+        description = "Deploy the app"
+
+        [commands.deploy.args.target]
+        type = "string"
+        required = false
+
+        [commands.deploy.flags.mode]
+        type = "string"
+        choices = ["local", "remote"]
+        default = "local"
+
+        [commands.deploy.flags.host]
+        type = "string"
+        required_if = { flag = "mode", value = "remote" }
+
+        [commands.deploy.flags.dry-run]
+        type = "bool"
+        requires = ["target", "log-file"]
+
+        [commands.deploy.flags.log-file]
+        type = "path"
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/deploy.ts").expect("Command file not found");
+    insta::assert_snapshot!("input_requirements_command", command);
+}
+
+#[test]
+fn test_cli_with_subcommands_structure() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "dbcli"
+        version = "1.0.0"
+        language = "typescript"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+
+        [commands.db.commands.seed]
+        description = "Seed the database"
+
+        [commands.db.commands.seed.args.file]
+        type = "path"
+        description = "Seed file"
+        "#,
+    );
+
+    // Parent command for subcommands - check structure
+    let db_command = get_file(&files, "src/commands/db.ts").expect("DB command file not found");
+    assert!(db_command.contains("export const dbCommand = defineCommand"));
+    assert!(db_command.contains("subcommands:"));
+    assert!(db_command.contains("migrate: migrateCommand"));
+    assert!(db_command.contains("seed: seedCommand"));
+
+    // Verify subcommand files exist
+    assert!(get_file(&files, "src/commands/db/migrate.ts").is_some());
+    assert!(get_file(&files, "src/commands/db/seed.ts").is_some());
+}
+
+#[test]
+fn test_cli_with_all_arg_types() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "typetest"
+        version = "1.0.0"
+        language = "typescript"
+
+        [commands.test]
+        description = "Test all argument types"
+
+        [commands.test.args.str_arg]
+        type = "string"
+        description = "A string argument"
+
+        [commands.test.args.int_arg]
+        type = "int"
+        description = "An integer argument"
+
+        [commands.test.args.float_arg]
+        type = "float"
+        description = "A float argument"
+
+        [commands.test.args.bool_arg]
+        type = "bool"
+        description = "A boolean argument"
+
+        [commands.test.args.path_arg]
+        type = "path"
+        description = "A path argument"
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/test.ts").expect("Command file not found");
+
+    // Verify all type mappings are correct (order may vary due to HashMap)
+    assert!(command.contains("strArg:"));
+    assert!(command.contains("type: \"string\""));
+    assert!(command.contains("intArg:"));
+    assert!(command.contains("floatArg:"));
+    assert!(command.contains("type: \"number\"")); // both int and float map to number
+    assert!(command.contains("boolArg:"));
+    assert!(command.contains("type: \"boolean\""));
+    assert!(command.contains("pathArg:"));
+    assert!(command.contains("export type TestArgs = InferArgs<typeof args>"));
+}
+
+#[test]
+fn test_handler_stub() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+
+        [commands.hello.args.name]
+        type = "string"
+        description = "Name to greet"
+
+        [commands.hello.flags.loud]
+        type = "bool"
+        description = "Shout the greeting"
+        "#,
+    );
+
+    // Handler stubs are only created when generating to disk (not in preview)
+    // since they should not overwrite existing implementations.
+    // Test command file instead to verify handler import path is correct.
+    let command = get_file(&files, "src/commands/hello.ts").expect("Command file not found");
+    insta::assert_snapshot!("command_with_handler_import", command);
+}
+
+#[test]
+fn test_index_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let index = get_file(&files, "src/index.ts").expect("Index file not found");
+    insta::assert_snapshot!("index_file", index);
+}
+
+#[test]
+fn test_package_json() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.2.3"
+        language = "typescript"
+        description = "My awesome CLI"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let package = get_file(&files, "package.json").expect("package.json not found");
+    insta::assert_snapshot!("package_json", package);
+}
+
+#[test]
+fn test_readme() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.0.0"
+        language = "typescript"
+        description = "My awesome CLI"
+
+        [commands.hello]
+        description = "Say hello"
+
+        [commands.hello.args.name]
+        type = "string"
+
+        [commands.hello.flags.loud]
+        type = "bool"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+
+        [commands.db.commands.migrate.flags.target]
+        type = "int"
+        default = 3
+        "#,
+    );
+
+    let readme = get_file(&files, "README.md").expect("README.md not found");
+    insta::assert_snapshot!("readme", readme);
+}
+
+#[test]
+fn test_ui_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let ui = get_file(&files, "src/ui.ts").expect("ui.ts not found");
+    insta::assert_snapshot!("ui_file", ui);
+}
+
+#[test]
+fn test_progress_opt_in() {
+    let without = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+    assert!(get_file(&without, "src/progress.ts").is_none());
+    let package = get_file(&without, "package.json").expect("package.json not found");
+    assert!(!package.contains("\"ora\""));
+
+    let with = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [typescript]
+        progress = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+    let package = get_file(&with, "package.json").expect("package.json not found");
+    assert!(package.contains("\"ora\": \"^8.1.0\""));
+
+    let progress = get_file(&with, "src/progress.ts").expect("progress.ts not found");
+    insta::assert_snapshot!("progress_file", progress);
+}
+
+#[test]
+fn test_completions_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+
+        [commands.hello.flags.format]
+        type = "string"
+        short = "f"
+        choices = ["text", "json"]
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        "#,
+    );
+
+    let completions = get_file(&files, "src/completions.ts").expect("completions.ts not found");
+    insta::assert_snapshot!("completions_file", completions);
+}
+
+#[test]
+fn test_completions_skipped_when_user_defines_command() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.completions]
+        description = "Custom completions"
+        "#,
+    );
+
+    assert!(get_file(&files, "src/completions.ts").is_none());
+    let cli = get_file(&files, "src/cli.ts").expect("CLI file not found");
+    assert!(!cli.contains("./completions.ts"));
+}
+
+#[test]
+fn test_docs_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        description = "My app"
+        docs_command = true
+
+        [commands.hello]
+        description = "Say hello"
+
+        [commands.hello.args.name]
+        type = "string"
+        description = "Who to greet"
+
+        [commands.hello.flags.format]
+        type = "string"
+        short = "f"
+        choices = ["text", "json"]
+        default = "text"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+        "#,
+    );
+
+    let docs = get_file(&files, "src/docs.ts").expect("docs.ts not found");
+    insta::assert_snapshot!("docs_file", docs);
+    let cli = get_file(&files, "src/cli.ts").expect("CLI file not found");
+    assert!(cli.contains("docs: docsCommand"));
+}
+
+#[test]
+fn test_doctor_file() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        doctor_command = true
+
+        [context.database]
+        type = "sqlite"
+        env = "DATABASE_URL"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let doctor = get_file(&files, "src/doctor.ts").expect("doctor.ts not found");
+    insta::assert_snapshot!("doctor_file", doctor);
+    let cli = get_file(&files, "src/cli.ts").expect("CLI file not found");
+    assert!(cli.contains("doctor: doctorCommand"));
+}
+
+#[test]
+fn test_doctor_file_resources() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        doctor_command = true
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api_url = "string"
+
+        [context.secrets]
+        backend = "file"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let doctor = get_file(&files, "src/doctor.ts").expect("doctor.ts not found");
+    insta::assert_snapshot!("doctor_file_resources", doctor);
+}
+
+#[test]
+fn test_parent_state_shared_with_subcommands() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.db]
+        description = "Database commands"
+
+        [commands.db.flags.dir]
+        type = "string"
+        description = "Project root"
+        default = "."
+
+        [commands.db.commands.migrate]
+        description = "Run migrations"
+
+        [commands.db.commands.migrate.args.target]
+        type = "string"
+        required = false
+        "#,
+    );
+
+    let parent = get_file(&files, "src/commands/db.ts").expect("Parent file not found");
+    insta::assert_snapshot!("parent_state_parent", parent);
+
+    let leaf = get_file(&files, "src/commands/db/migrate.ts").expect("Leaf file not found");
+    insta::assert_snapshot!("parent_state_leaf", leaf);
+}
+
+#[test]
+fn test_import_extension_js() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [typescript]
+        import_extension = "js"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let cli = get_file(&files, "src/cli.ts").expect("CLI file not found");
+    insta::assert_snapshot!("import_extension_js_cli", cli);
+
+    let command = get_file(&files, "src/commands/hello.ts").expect("Command file not found");
+    assert!(command.contains("from \"../handlers/hello.js\""));
+    assert!(command.contains("from \"boune\""));
+
+    let index = get_file(&files, "src/index.ts").expect("Index file not found");
+    assert!(index.contains("from \"./cli.js\""));
+}
+
+#[test]
+fn test_cjs_module_format() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [typescript]
+        module = "cjs"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    insta::assert_snapshot!("cjs_package_json", package_json);
+
+    let tsconfig = get_file(&files, "tsconfig.json").expect("tsconfig.json not found");
+    assert!(tsconfig.contains("\"module\": \"CommonJS\""));
+
+    let ui = get_file(&files, "src/ui.ts").expect("ui.ts not found");
+    assert!(ui.contains("import pc = require(\"picocolors\");"));
+}
+
+#[test]
+fn test_dockerfile() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [typescript]
+        docker = true
+
+        [context.database]
+        type = "postgres"
+        env = "DATABASE_URL"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let dockerfile = get_file(&files, "Dockerfile").expect("Dockerfile not found");
+    insta::assert_snapshot!("dockerfile", dockerfile);
+    assert!(get_file(&files, ".dockerignore").is_some());
+}
+
+#[test]
+fn test_dockerfile_opt_in() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        "#,
+    );
+
+    assert!(get_file(&files, "Dockerfile").is_none());
+}
+
+#[test]
+fn test_prompted_inputs() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.drop]
+        description = "Drop the database"
+
+        [commands.drop.flags.yes]
+        type = "bool"
+        short = "y"
+        description = "Drop the database?"
+        prompt = "confirm"
+
+        [commands.drop.flags.password]
+        type = "string"
+        description = "Database password"
+        prompt = "secret"
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/drop.ts").expect("Command file not found");
+    insta::assert_snapshot!("prompted_inputs_command", command);
+
+    let prompts = get_file(&files, "src/prompts.ts").expect("Prompts file not found");
+    insta::assert_snapshot!("prompts_file", prompts);
+}
+
+#[test]
+fn test_prompts_file_omitted_without_prompts() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    assert!(get_file(&files, "src/prompts.ts").is_none());
+}
+
+#[test]
+fn test_logging_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.logging]
+        level = "warn"
+        format = "json"
+        env_filter = "warn,db=debug"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let logger = get_file(&files, "src/logger.ts").expect("logger.ts not found");
+    insta::assert_snapshot!("logger_file", logger);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    insta::assert_snapshot!("logging_context", context);
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("verbose: {"));
+    assert!(cli.contains("quiet: {"));
+}
+
+#[test]
+fn test_storage_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.storage]
+        type = "s3"
+        bucket = "uploads"
+        region = "eu-west-1"
+        endpoint = "http://localhost:9000"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let storage = get_file(&files, "src/storage.ts").expect("storage.ts not found");
+    insta::assert_snapshot!("storage_file", storage);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type S3Client } from \"bun\";"));
+    assert!(context.contains("storage: S3Client;"));
+}
+
+#[test]
+fn test_grpc_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.grpc]
+        endpoint = "http://localhost:50051"
+        protos = ["proto/greeter.proto"]
+        timeout = 10
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let grpc = get_file(&files, "src/grpc.ts").expect("grpc.ts not found");
+    insta::assert_snapshot!("grpc_file", grpc);
+
+    let buf_gen = get_file(&files, "buf.gen.yaml").expect("buf.gen.yaml not found");
+    insta::assert_snapshot!("grpc_buf_gen", buf_gen);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Transport } from \"@connectrpc/connect\";"));
+    assert!(context.contains("grpc: Transport;"));
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package_json.contains("\"generate\": \"buf generate\","));
+    assert!(package_json.contains("\"@connectrpc/connect-node\": \"^2.0.0\""));
+    assert!(package_json.contains("\"@bufbuild/protobuf\": \"^2.0.0\""));
+    assert!(package_json.contains("\"@bufbuild/protoc-gen-es\": \"^2.0.0\""));
+}
+
+#[test]
+fn test_email_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.email]
+        host = "smtp.example.com"
+        username_env = "SMTP_USERNAME"
+        password_env = "SMTP_PASSWORD"
+
+        [commands.send]
+        description = "Send a notification"
+        "#,
+    );
+
+    let email = get_file(&files, "src/email.ts").expect("email.ts not found");
+    insta::assert_snapshot!("email_file", email);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Transporter } from \"nodemailer\";"));
+    assert!(context.contains("email: Transporter;"));
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package_json.contains("\"nodemailer\": \"^6.9.16\""));
+    assert!(package_json.contains("\"@types/nodemailer\": \"^6.4.17\""));
+}
+
+#[test]
+fn test_config_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.config]
+        path = "myapp.toml"
+
+        [context.config.fields]
+        api-url = "string"
+        retries = { type = "int", optional = true }
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let config = get_file(&files, "src/config.ts").expect("config.ts not found");
+    insta::assert_snapshot!("config_file", config);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Config } from \"./config.ts\";"));
+    assert!(context.contains("config: Config;"));
+}
+
+#[test]
+fn test_secrets_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.secrets]
+        backend = "keyring"
+        prefix = "MYAPP_"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let secrets = get_file(&files, "src/secrets.ts").expect("secrets.ts not found");
+    insta::assert_snapshot!("secrets_file", secrets);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Secrets } from \"./secrets.ts\";"));
+    assert!(context.contains("secrets: Secrets;"));
+}
+
+#[test]
+fn test_file_secrets_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.secrets]
+        backend = "file"
+
+        [commands.deploy]
+        description = "Deploy the app"
+        "#,
+    );
+
+    let secrets = get_file(&files, "src/secrets.ts").expect("secrets.ts not found");
+    insta::assert_snapshot!("file_secrets_file", secrets);
+}
+
+#[test]
+fn test_memory_cache_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.cache]
+        max_capacity = 500
+        ttl = 60
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let cache = get_file(&files, "src/cache.ts").expect("cache.ts not found");
+    insta::assert_snapshot!("memory_cache_file", cache);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Cache } from \"./cache.ts\";"));
+    assert!(context.contains("cache: Cache;"));
+}
+
+#[test]
+fn test_workdir_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.workdir]
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+
+    let workdir = get_file(&files, "src/workdir.ts").expect("workdir.ts not found");
+    insta::assert_snapshot!("workdir_file", workdir);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type Workdir } from \"./workdir.ts\";"));
+    assert!(context.contains("workdir: Workdir;"));
+}
+
+#[test]
+fn test_temp_workdir_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.workdir]
+        data = false
+        temp = true
+
+        [commands.build]
+        description = "Build the site"
+        "#,
+    );
+
+    let workdir = get_file(&files, "src/workdir.ts").expect("workdir.ts not found");
+    insta::assert_snapshot!("temp_workdir_file", workdir);
+}
+
+#[test]
+fn test_disk_cache_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.cache]
+        mode = "disk"
+        ttl = 3600
+
+        [commands.fetch]
+        description = "Fetch a page"
+        "#,
+    );
+
+    let cache = get_file(&files, "src/cache.ts").expect("cache.ts not found");
+    insta::assert_snapshot!("disk_cache_file", cache);
+}
+
+#[test]
+fn test_duckdb_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.database]
+        type = "duckdb"
+        path = "analytics.duckdb"
+
+        [commands.summary]
+        description = "Summarize events"
+        "#,
+    );
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type DuckDBConnection } from \"@duckdb/node-api\";"));
+    assert!(context.contains("db: DuckDBConnection;"));
+
+    let package = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package.contains("\"@duckdb/node-api\""));
+}
+
+#[test]
+fn test_anthropic_llm_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.llm]
+        provider = "anthropic"
+        model = "claude-sonnet-4-5"
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let llm = get_file(&files, "src/llm.ts").expect("llm.ts not found");
+    insta::assert_snapshot!("anthropic_llm_file", llm);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type LlmClient } from \"./llm.ts\";"));
+    assert!(context.contains("llm: LlmClient;"));
+}
+
+#[test]
+fn test_openai_llm_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.llm]
+        provider = "openai"
+        model = "gpt-4o"
+        max_tokens = 512
+
+        [commands.ask]
+        description = "Ask a question"
+        "#,
+    );
+
+    let llm = get_file(&files, "src/llm.ts").expect("llm.ts not found");
+    insta::assert_snapshot!("openai_llm_file", llm);
+}
+
+#[test]
+fn test_nats_queue_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.queue]
+        type = "nats"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let queue = get_file(&files, "src/queue.ts").expect("queue.ts not found");
+    insta::assert_snapshot!("nats_queue_file", queue);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type NatsConnection } from \"nats\";"));
+    assert!(context.contains("queue: () => Promise<NatsConnection>;"));
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package_json.contains("\"nats\": \"^2.29.0\""));
+}
+
+#[test]
+fn test_kafka_queue_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.queue]
+        type = "kafka"
+
+        [commands.publish]
+        description = "Publish an event"
+        "#,
+    );
+
+    let queue = get_file(&files, "src/queue.ts").expect("queue.ts not found");
+    insta::assert_snapshot!("kafka_queue_file", queue);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("queue: () => Promise<Producer>;"));
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package_json.contains("\"kafkajs\": \"^2.2.4\""));
+}
+
+#[test]
+fn test_named_http_clients() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.http.github]
+        base_url = "https://api.github.com"
+        timeout = 30
+        user_agent = "myapp/1.0"
+        headers = { Accept = "application/vnd.github+json" }
+        retries = 3
+        backoff_ms = 200
+
+        [context.http.internal]
+        auth = { type = "basic", username = "ci", env = "INTERNAL_PASSWORD" }
+
+        [context.http.metrics]
+        retries = 2
+        retry_on = [429, 503]
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let http = get_file(&files, "src/http.ts").expect("http.ts not found");
+    insta::assert_snapshot!("http_file", http);
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    assert!(context.contains("import { type HttpClient } from \"./http.ts\";"));
+    assert!(context.contains("github: HttpClient;"));
+    assert!(context.contains("internal: HttpClient;"));
+}
+
+#[test]
+fn test_global_inputs() {
+    let manifest = Manifest::from_str(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    )
+    .expect("Failed to parse schema");
+    let mut ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    ctx.ir.as_mut().unwrap().global_inputs = vec![Input {
+        name: "dry_run".into(),
+        ty: InputType::Bool,
+        kind: InputKind::Flag { short: Some('n') },
+        required: false,
+        default: None,
+        description: Some("Print without applying".into()),
+        choices: None,
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        constraints: Default::default(),
+        requires: vec![],
+        required_if: None,
+        hint: None,
+        long_description: None,
+        deprecated: None,
+        span: None,
+    }];
+    let files = Generator::from_context(ctx).preview();
+
+    let cli = files
+        .iter()
+        .find(|f| f.path == "src/cli.ts")
+        .expect("cli.ts not found");
+    insta::assert_snapshot!("global_inputs_cli", cli.content);
+}
+
+#[test]
+fn test_sqlite_migrations() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.database]
+        type = "sqlite"
+        path = "app.db"
+        migrations = "migrations"
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let migrate = get_file(&files, "src/migrate.ts").expect("migrate.ts not found");
+    insta::assert_snapshot!("migrate_file", migrate);
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("import { migrateCommand } from \"./migrate.ts\";"));
+    assert!(cli.contains("migrate: migrateCommand"));
+}
+
+#[test]
+fn test_migrate_command_omitted_without_migrations() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.database]
+        type = "sqlite"
+        path = "app.db"
+        "#,
+    );
+
+    assert!(get_file(&files, "src/migrate.ts").is_none());
+}
+
+#[test]
+fn test_preserved_flag_case() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [typescript]
+        flag_case = "preserve"
+
+        [commands.deploy]
+        description = "Deploy the app"
+
+        [commands.deploy.flags.dry_run]
+        type = "bool"
+        description = "Print the plan only"
+
+        [commands.deploy.flags.api-token]
+        type = "string"
+        description = "API token"
+        prompt = "secret"
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/deploy.ts").expect("Command file not found");
+    insta::assert_snapshot!("preserved_flag_case_command", command);
+
+    let completions = get_file(&files, "src/completions.ts").expect("completions.ts not found");
+    assert!(completions.contains("--dry_run"));
+}
+
+#[test]
+fn test_exit_codes() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [commands.deploy]
+        description = "Deployment commands"
+
+        [commands.deploy.exit_codes]
+        AuthError = 4
+        NotFoundError = 3
+
+        [commands.deploy.commands.run]
+        description = "Run a deployment"
+
+        [commands.deploy.commands.run.exit_codes]
+        NotFoundError = 5
+        "#,
+    );
+
+    let command = get_file(&files, "src/commands/deploy/run.ts").expect("Command file not found");
+    insta::assert_snapshot!("exit_codes_command", command);
+
+    let errors = get_file(&files, "src/errors.ts").expect("errors.ts not found");
+    insta::assert_snapshot!("errors_file", errors);
+}
+
+#[test]
+fn test_localized_descriptions() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+        description = { en = "My app", fr = "Mon app" }
+
+        [commands.db]
+        description = { en = "Database commands", fr = "Commandes de base de données" }
+
+        [commands.db.commands.query]
+        description = { en = "Run a query", "pt-BR" = "Executar uma consulta" }
+
+        [commands.db.commands.query.args.sql]
+        type = "string"
+        description = { en = "Query to run", fr = "Requête à exécuter" }
+
+        [commands.db.commands.query.flags.dry-run]
+        description = "Print the query"
+        "#,
+    );
+
+    let locale = get_file(&files, "src/locale.ts").expect("locale.ts not found");
+    insta::assert_snapshot!("localized_locale", locale);
+
+    let command = get_file(&files, "src/commands/db/query.ts").expect("Command file not found");
+    insta::assert_snapshot!("localized_command", command);
+
+    let cli = get_file(&files, "src/cli.ts").expect("CLI file not found");
+    assert!(cli.contains(r#"import { t } from "./locale.ts";"#));
+    assert!(cli.contains(r#"description: t("", "My app"),"#));
+}
+
+#[test]
+fn test_build_info() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        version = "1.2.0"
+        language = "typescript"
+        build_info = true
+
+        [commands.hello]
+        description = "Say hello"
+        "#,
+    );
+
+    let build_info = get_file(&files, "src/build-info.ts").expect("build-info.ts not found");
+    insta::assert_snapshot!("build_info", build_info);
+
+    let cli = get_file(&files, "src/cli.ts").expect("cli.ts not found");
+    assert!(cli.contains("import { version } from \"./build-info.ts\";"));
+    assert!(cli.contains("version: version,"));
+
+    let package_json = get_file(&files, "package.json").expect("package.json not found");
+    assert!(package_json.contains("--define BAO_BUILD_COMMIT="));
+}
+
+#[test]
+fn test_lazy_context() {
+    let files = generate_files(
+        r#"
+        [cli]
+        name = "myapp"
+        language = "typescript"
+
+        [context.database]
+        type = "duckdb"
+        path = "analytics.duckdb"
+
+        [context.cache]
+        ttl = 60
+
+        [context.http]
+
+        [commands.summary]
+        description = "Summarize events"
+        requires = ["database"]
+
+        [commands.fetch]
+        description = "Fetch a page"
+        requires = ["cache", "http"]
+        "#,
+    );
+
+    let context = get_file(&files, "src/context.ts").expect("context.ts not found");
+    insta::assert_snapshot!("lazy_context", context);
 }
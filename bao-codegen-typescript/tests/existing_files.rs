@@ -0,0 +1,102 @@
+//! Integration tests that verify bake updates an existing package.json and
+//! README.md in place without overwriting the user's changes.
+
+use std::str::FromStr;
+
+use baobao_codegen::{language::LanguageCodegen, pipeline::Pipeline};
+use baobao_codegen_typescript::Generator;
+use baobao_core::WriteResult;
+use baobao_manifest::Manifest;
+use tempfile::TempDir;
+
+/// Create a generator from a manifest using the Pipeline.
+fn create_generator(manifest: &str) -> Generator {
+    let manifest = Manifest::from_str(manifest).expect("Failed to parse schema");
+    let ctx = Pipeline::new().run(manifest).expect("Pipeline failed");
+    Generator::from_context(ctx)
+}
+
+const MANIFEST: &str = r#"
+    [cli]
+    name = "myapp"
+    language = "typescript"
+
+    [commands.hello]
+    description = "Say hello"
+"#;
+
+#[test]
+fn test_package_json_gains_new_dependencies() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path();
+    let package_json = output_dir.join("package.json");
+
+    create_generator(MANIFEST)
+        .generate(output_dir)
+        .expect("Failed to generate code");
+    let created = std::fs::read_to_string(&package_json).unwrap();
+    assert!(!created.contains("\"ora\""));
+
+    // The user adds a dependency of their own, then opts into spinners
+    let edited = created.replace(
+        "\"dependencies\": {\n",
+        "\"dependencies\": {\n    \"zod\": \"^3.0.0\",\n",
+    );
+    std::fs::write(&package_json, &edited).unwrap();
+    let result = create_generator(&format!("{}\n[typescript]\nprogress = true\n", MANIFEST))
+        .generate(output_dir)
+        .expect("Failed to generate code");
+
+    let updated = std::fs::read_to_string(&package_json).unwrap();
+    assert!(updated.contains("\"zod\": \"^3.0.0\""));
+    assert!(updated.contains("\"ora\""));
+    assert!(matches!(
+        result.files.iter().find(|(path, _)| path == "package.json"),
+        Some((_, WriteResult::Updated(_)))
+    ));
+
+    // Nothing left to add
+    let result = create_generator(&format!("{}\n[typescript]\nprogress = true\n", MANIFEST))
+        .generate(output_dir)
+        .expect("Failed to generate code");
+    assert_eq!(std::fs::read_to_string(&package_json).unwrap(), updated);
+    assert!(
+        result
+            .files
+            .contains(&("package.json".to_string(), WriteResult::Unchanged))
+    );
+}
+
+#[test]
+fn test_readme_written_once() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path();
+    let readme = output_dir.join("README.md");
+
+    let result = create_generator(MANIFEST)
+        .generate(output_dir)
+        .expect("Failed to generate code");
+    let readmes: Vec<_> = result
+        .files
+        .iter()
+        .filter(|(path, _)| path == "README.md")
+        .collect();
+    assert!(matches!(readmes[..], [(_, WriteResult::Created(_))]));
+
+    // Notes outside the generated region survive a bake
+    let edited = std::fs::read_to_string(&readme).unwrap() + "\n## Notes\n";
+    std::fs::write(&readme, &edited).unwrap();
+    let result = create_generator(&MANIFEST.replace("Say hello", "Greet someone"))
+        .generate(output_dir)
+        .expect("Failed to generate code");
+
+    let updated = std::fs::read_to_string(&readme).unwrap();
+    assert!(updated.contains("Greet someone"));
+    assert!(updated.ends_with("\n## Notes\n"));
+    let readmes: Vec<_> = result
+        .files
+        .iter()
+        .filter(|(path, _)| path == "README.md")
+        .collect();
+    assert!(matches!(readmes[..], [(_, WriteResult::Updated(_))]));
+}
@@ -0,0 +1,121 @@
+//! Parsing and pipeline benchmarks for large manifests.
+//!
+//! Run with `cargo bench -p baobao-codegen`, optionally followed by `--` and
+//! a filter such as `parse/1000`. Without `--bench`, as under
+//! `cargo test --all-targets`, the smallest cases run once as a smoke test.
+
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use baobao_codegen::pipeline::Pipeline;
+use baobao_manifest::Manifest;
+
+/// Number of top-level commands in each benchmarked manifest.
+const SIZES: [usize; 3] = [100, 1_000, 5_000];
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let full = args.iter().any(|arg| arg == "--bench");
+    let filter = args.iter().find(|arg| !arg.starts_with('-'));
+    let sizes = if full { &SIZES[..] } else { &SIZES[..1] };
+
+    for &size in sizes {
+        let src = manifest(size);
+        let parsed: Manifest = src.parse().expect("benchmark manifest is valid");
+
+        let cases: [(&str, &dyn Fn()); 2] = [
+            ("parse", &|| {
+                black_box(src.parse::<Manifest>().unwrap());
+            }),
+            ("pipeline", &|| {
+                black_box(Pipeline::new().run(parsed.clone()).unwrap());
+            }),
+        ];
+        for (case, f) in cases {
+            let name = format!("{}/{}", case, size);
+            if filter.is_none_or(|filter| name.contains(filter.as_str())) {
+                bench(&name, full, f);
+            }
+        }
+    }
+}
+
+/// Time `f`, repeating it for about a second when `full` is set.
+fn bench(name: &str, full: bool, f: &dyn Fn()) {
+    let start = Instant::now();
+    f();
+    if !full {
+        return;
+    }
+    let once = start.elapsed().max(Duration::from_micros(1));
+    let iterations = (Duration::from_secs(1).as_nanos() / once.as_nanos()).clamp(1, 1_000) as u32;
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    println!(
+        "{:<20} {:>12.2?}/iter ({} iterations)",
+        name,
+        start.elapsed() / iterations,
+        iterations
+    );
+}
+
+/// A manifest with `size` commands, each with a subcommand, arguments in
+/// both formats and flags.
+fn manifest(size: usize) -> String {
+    let mut src = String::from(
+        r#"[cli]
+name = "bench"
+language = "rust"
+description = "Benchmark CLI"
+
+[context.database]
+type = "postgres"
+
+[context.http]
+timeout = 30
+"#,
+    );
+    for i in 0..size {
+        write!(
+            src,
+            r#"
+[commands.command{i}]
+description = "Command {i}"
+
+[commands.command{i}.args.name]
+type = "string"
+description = "Name"
+
+[commands.command{i}.args.count]
+type = "int"
+required = false
+default = 1
+
+[commands.command{i}.flags.verbose]
+type = "bool"
+short = "v"
+description = "Verbose output"
+
+[commands.command{i}.flags.format]
+type = "string"
+choices = ["json", "text"]
+default = "text"
+
+[commands.command{i}.commands.sub]
+description = "Subcommand {i}"
+args = [
+    {{ name = "path", type = "path", description = "Path" }},
+    {{ name = "limit", type = "int", required = false }},
+]
+"#
+        )
+        .unwrap();
+    }
+    src
+}
@@ -13,13 +13,7 @@ use crate::builder::CodeFragment;
 /// This is a convenience function for adapters that need to work with both
 /// IR types and legacy ArgType-based APIs.
 pub fn input_type_to_arg_type(input_type: InputType) -> ArgType {
-    match input_type {
-        InputType::String => ArgType::String,
-        InputType::Int => ArgType::Int,
-        InputType::Float => ArgType::Float,
-        InputType::Bool => ArgType::Bool,
-        InputType::Path => ArgType::Path,
-    }
+    input_type.into()
 }
 
 /// IR-based argument metadata for code generation.
@@ -1,11 +1,11 @@
 //! Database adapter abstraction.
 //!
 //! This module defines the [`DatabaseAdapter`] trait for abstracting database
-//! connection and pool code generation (sqlx, diesel, bun:sqlite, etc.).
+//! connection and pool code generation (sqlx, duckdb, bun:sqlite, etc.).
 
 use baobao_ir::DatabaseType;
 // Re-export IR types for convenience
-pub use baobao_ir::{PoolConfig, SqliteOptions};
+pub use baobao_ir::{DuckdbOptions, PoolConfig, SqliteOptions};
 
 use super::cli::{Dependency, ImportSpec};
 use crate::builder::Value;
@@ -23,6 +23,8 @@ pub struct PoolInitInfo {
     pub pool_config: PoolConfig,
     /// SQLite-specific config (only for SQLite)
     pub sqlite_config: Option<SqliteOptions>,
+    /// DuckDB-specific config (only for DuckDB)
+    pub duckdb_config: Option<DuckdbOptions>,
 }
 
 /// Trait for database adapters.
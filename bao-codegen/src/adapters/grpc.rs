@@ -0,0 +1,31 @@
+//! gRPC client adapter abstraction.
+//!
+//! This module defines the [`GrpcAdapter`] trait for abstracting gRPC client
+//! code generation (tonic, connect-es, etc.).
+
+// Re-export IR types for convenience
+pub use baobao_ir::GrpcOptions;
+
+use super::cli::Dependency;
+
+/// Trait for gRPC client adapters.
+///
+/// Implement this trait to support a specific gRPC library. Clients are
+/// generated from the `.proto` files at build time, so adapters declare
+/// the dependencies of that step separately.
+pub trait GrpcAdapter {
+    /// Adapter name for identification.
+    fn name(&self) -> &'static str;
+
+    /// Dependencies required for a channel with these options.
+    fn dependencies(&self, options: &GrpcOptions) -> Vec<Dependency>;
+
+    /// Dependencies of the step that generates clients from `.proto` files.
+    fn build_dependencies(&self, options: &GrpcOptions) -> Vec<Dependency>;
+
+    /// The type name for the channel that clients are created from.
+    fn channel_type(&self) -> &'static str;
+
+    /// Generate the channel initialization expression.
+    fn channel_init(&self, options: &GrpcOptions) -> String;
+}
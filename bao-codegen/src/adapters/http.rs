@@ -0,0 +1,26 @@
+//! HTTP client adapter abstraction.
+//!
+//! This module defines the [`HttpAdapter`] trait for abstracting HTTP client
+//! code generation (reqwest, reqwest-middleware, etc.).
+
+// Re-export IR types for convenience
+pub use baobao_ir::HttpOptions;
+
+use super::cli::Dependency;
+
+/// Trait for HTTP client adapters.
+///
+/// Implement this trait to support a specific HTTP client library.
+pub trait HttpAdapter {
+    /// Adapter name for identification.
+    fn name(&self) -> &'static str;
+
+    /// Dependencies required for a client with these options.
+    fn dependencies(&self, options: &HttpOptions) -> Vec<Dependency>;
+
+    /// The type name for a client with these options.
+    fn client_type(&self, options: &HttpOptions) -> &'static str;
+
+    /// Generate the client initialization expression.
+    fn client_init(&self, options: &HttpOptions) -> String;
+}
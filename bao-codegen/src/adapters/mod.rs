@@ -12,18 +12,29 @@
 //!
 //! - [`CliAdapter`] - CLI framework abstraction (clap, argh, boune, etc.)
 //! - [`DatabaseAdapter`] - Database connection/pool abstraction (sqlx, diesel, etc.)
+//! - [`GrpcAdapter`] - gRPC client abstraction (tonic, connect-es, etc.)
+//! - [`HttpAdapter`] - HTTP client abstraction (reqwest, reqwest-middleware, etc.)
 //! - [`RuntimeAdapter`] - Async runtime abstraction (tokio, async-std, etc.)
 //! - [`ErrorAdapter`] - Error handling abstraction (eyre, anyhow, etc.)
+//! - [`ResourceAdapter`] - Plugin-defined context resources (queues, feature flags, etc.)
 
 mod async_runtime;
 mod cli;
 mod database;
 mod error;
+mod grpc;
+mod http;
+mod resource;
 
 pub use async_runtime::{RuntimeAdapter, RuntimeInfo};
 pub use cli::{
     ArgMeta, CliAdapter, CliInfo, CommandMeta, Dependency, DispatchInfo, FlagMeta, IRArgMeta,
     IRFlagMeta, ImportSpec, SubcommandMeta, input_type_to_arg_type,
 };
-pub use database::{DatabaseAdapter, PoolConfig, PoolInitInfo, SqliteOptions};
+pub use database::{DatabaseAdapter, DuckdbOptions, PoolConfig, PoolInitInfo, SqliteOptions};
 pub use error::ErrorAdapter;
+pub use grpc::{GrpcAdapter, GrpcOptions};
+pub use http::{HttpAdapter, HttpOptions};
+pub use resource::{
+    CustomResource, RenderedResources, ResourceAdapter, ResourceAdapters, ResourceCode,
+};
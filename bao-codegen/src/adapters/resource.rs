@@ -0,0 +1,227 @@
+//! Custom resource adapter abstraction.
+//!
+//! This module defines the [`ResourceAdapter`] trait that plugins implement to
+//! render their own context resources ([`CustomResource`]), such as queues or
+//! feature-flag clients, in each target language.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::Arc,
+};
+
+use baobao_ir::ContextFieldInfo;
+pub use baobao_ir::CustomResource;
+use eyre::{Result, bail};
+
+use super::cli::{Dependency, ImportSpec};
+
+/// Code for a custom resource in one target language.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceCode {
+    /// Type of the context field (e.g., `lapin::Channel`).
+    pub type_name: String,
+    /// Expression that initializes the field. Languages whose context is
+    /// only declared, not constructed (TypeScript), ignore it.
+    pub init: String,
+    /// Imports needed by the type and initializer.
+    pub imports: Vec<ImportSpec>,
+    /// Packages needed by the generated project.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl ResourceCode {
+    /// Create resource code from a type and an initializer.
+    pub fn new(type_name: impl Into<String>, init: impl Into<String>) -> Self {
+        Self {
+            type_name: type_name.into(),
+            init: init.into(),
+            imports: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Add an import.
+    pub fn import(mut self, import: ImportSpec) -> Self {
+        self.imports.push(import);
+        self
+    }
+
+    /// Add a dependency.
+    pub fn dependency(mut self, dependency: Dependency) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+}
+
+/// Trait for custom resource adapters.
+///
+/// Plugins return adapters from [`Plugin::resource_adapters`](crate::pipeline::Plugin::resource_adapters);
+/// generators call them for every [`CustomResource`] of the matching kind.
+pub trait ResourceAdapter: Send + Sync {
+    /// The resource kind this adapter renders.
+    fn kind(&self) -> &str;
+
+    /// Render a resource for a target language (`"rust"`, `"typescript"`,
+    /// `"go"`), or `None` if the language is not supported.
+    fn render(&self, language: &str, resource: &CustomResource) -> Option<ResourceCode>;
+}
+
+/// Registered resource adapters, keyed by kind.
+#[derive(Clone, Default)]
+pub struct ResourceAdapters {
+    adapters: BTreeMap<String, Arc<dyn ResourceAdapter>>,
+}
+
+impl ResourceAdapters {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an adapter, replacing any adapter for the same kind.
+    pub fn register(&mut self, adapter: Arc<dyn ResourceAdapter>) {
+        self.adapters.insert(adapter.kind().to_string(), adapter);
+    }
+
+    /// Get the adapter for a kind.
+    pub fn get(&self, kind: &str) -> Option<&dyn ResourceAdapter> {
+        self.adapters.get(kind).map(Arc::as_ref)
+    }
+
+    /// Render the custom resources among `fields` for `language`.
+    pub fn render_all(&self, language: &str, fields: &[ContextFieldInfo]) -> RenderedResources {
+        let mut rendered = RenderedResources::default();
+        for custom in fields.iter().filter_map(|f| f.custom.as_ref()) {
+            match self
+                .get(&custom.kind)
+                .and_then(|adapter| adapter.render(language, custom))
+            {
+                Some(code) => {
+                    rendered.code.insert(custom.name.clone(), code);
+                }
+                None => rendered.missing.push(format!(
+                    "no {} adapter registered for resource '{}' of kind '{}'",
+                    language, custom.name, custom.kind
+                )),
+            }
+        }
+        rendered
+    }
+}
+
+impl fmt::Debug for ResourceAdapters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.adapters.keys()).finish()
+    }
+}
+
+/// Custom resources rendered for one language.
+#[derive(Debug, Clone, Default)]
+pub struct RenderedResources {
+    /// Code for each custom context field, keyed by field name.
+    pub code: HashMap<String, ResourceCode>,
+    /// Resources no adapter could render.
+    pub missing: Vec<String>,
+}
+
+impl RenderedResources {
+    /// Code for a context field, if it is a rendered custom resource.
+    pub fn get(&self, field: &str) -> Option<&ResourceCode> {
+        self.code.get(field)
+    }
+
+    /// Fail if any custom resource could not be rendered.
+    pub fn check(&self) -> Result<()> {
+        if !self.missing.is_empty() {
+            bail!("{}", self.missing.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// Dependencies of all rendered resources.
+    pub fn dependencies(&self) -> impl Iterator<Item = &Dependency> {
+        self.code.values().flat_map(|code| &code.dependencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baobao_ir::{ContextFieldType, PoolConfig};
+
+    use super::*;
+
+    struct QueueAdapter;
+
+    impl ResourceAdapter for QueueAdapter {
+        fn kind(&self) -> &str {
+            "queue"
+        }
+
+        fn render(&self, language: &str, resource: &CustomResource) -> Option<ResourceCode> {
+            (language == "rust").then(|| {
+                let url = resource.config["url"].as_str().unwrap_or_default();
+                ResourceCode::new("queue::Client", format!("queue::Client::new({:?})", url))
+                    .dependency(Dependency::new("queue", "1"))
+            })
+        }
+    }
+
+    fn field(kind: &str) -> ContextFieldInfo {
+        let custom = CustomResource {
+            name: "jobs".into(),
+            kind: kind.into(),
+            config: serde_json::json!({ "url": "amqp://localhost" }),
+            is_async: false,
+        };
+        ContextFieldInfo {
+            name: custom.name.clone(),
+            field_type: ContextFieldType::Custom,
+            env_var: String::new(),
+            is_async: false,
+            pool: PoolConfig::default(),
+            sqlite: None,
+            duckdb: None,
+            http: None,
+            s3: None,
+            grpc: None,
+            email: None,
+            config: None,
+            secrets: None,
+            llm: None,
+            cache: None,
+            workdir: None,
+            custom: Some(custom),
+        }
+    }
+
+    #[test]
+    fn test_render_all() {
+        let mut adapters = ResourceAdapters::new();
+        adapters.register(Arc::new(QueueAdapter));
+
+        let rendered = adapters.render_all("rust", &[field("queue")]);
+        rendered.check().unwrap();
+        let code = rendered.get("jobs").unwrap();
+        assert_eq!(code.init, "queue::Client::new(\"amqp://localhost\")");
+        assert_eq!(rendered.dependencies().count(), 1);
+    }
+
+    #[test]
+    fn test_render_all_reports_missing() {
+        let mut adapters = ResourceAdapters::new();
+        adapters.register(Arc::new(QueueAdapter));
+
+        let unsupported = adapters.render_all("go", &[field("queue")]);
+        let unknown = adapters.render_all("rust", &[field("flags")]);
+
+        assert!(
+            unsupported
+                .check()
+                .unwrap_err()
+                .to_string()
+                .contains("no go adapter registered for resource 'jobs' of kind 'queue'")
+        );
+        assert!(unknown.check().is_err());
+    }
+}
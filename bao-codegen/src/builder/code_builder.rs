@@ -105,21 +105,39 @@ impl CodeBuilder {
         self
     }
 
-    /// Add a JSDoc comment (mutable).
+    /// Add a JSDoc comment (mutable), as a block when `text` has several lines.
     pub fn push_jsdoc(&mut self, text: &str) -> &mut Self {
+        if !text.contains('\n') {
+            self.write_indent();
+            self.buffer.push_str("/** ");
+            self.buffer.push_str(text);
+            self.buffer.push_str(" */\n");
+            return self;
+        }
+        self.write_indent();
+        self.buffer.push_str("/**\n");
+        self.push_comment_lines(" *", text);
         self.write_indent();
-        self.buffer.push_str("/** ");
-        self.buffer.push_str(text);
         self.buffer.push_str(" */\n");
         self
     }
 
-    /// Add a Rust doc comment (mutable).
+    /// Add a Rust doc comment (mutable), one `///` line per line of `text`.
     pub fn push_rust_doc(&mut self, text: &str) -> &mut Self {
-        self.write_indent();
-        self.buffer.push_str("/// ");
-        self.buffer.push_str(text);
-        self.buffer.push('\n');
+        self.push_comment_lines("///", text)
+    }
+
+    /// Write each line of `text` after `prefix`, leaving blank lines bare.
+    fn push_comment_lines(&mut self, prefix: &str, text: &str) -> &mut Self {
+        for line in text.split('\n') {
+            self.write_indent();
+            self.buffer.push_str(prefix);
+            if !line.is_empty() {
+                self.buffer.push(' ');
+                self.buffer.push_str(line);
+            }
+            self.buffer.push('\n');
+        }
         self
     }
 
@@ -257,13 +275,9 @@ impl CodeBuilder {
         f(builder).dedent().line(close)
     }
 
-    /// Add a doc comment line (e.g., `/// text` for Rust).
+    /// Add doc comment lines (e.g., `/// text` for Rust).
     pub fn doc(mut self, prefix: &str, text: &str) -> Self {
-        self.write_indent();
-        self.buffer.push_str(prefix);
-        self.buffer.push(' ');
-        self.buffer.push_str(text);
-        self.buffer.push('\n');
+        self.push_comment_lines(prefix, text);
         self
     }
 
@@ -383,6 +397,17 @@ mod tests {
         assert_eq!(code, "/// A test function\nfn test() {}\n");
     }
 
+    #[test]
+    fn test_multiline_doc_comment() {
+        let code = CodeBuilder::rust()
+            .rust_doc("Examples:\n\n  app run")
+            .build();
+        assert_eq!(code, "/// Examples:\n///\n///   app run\n");
+
+        let code = CodeBuilder::typescript().jsdoc("@example\napp run").build();
+        assert_eq!(code, "/**\n * @example\n * app run\n */\n");
+    }
+
     #[test]
     fn test_conditional() {
         let with_debug = CodeBuilder::rust()
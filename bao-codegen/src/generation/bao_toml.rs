@@ -3,7 +3,7 @@
 use std::path::{Path, PathBuf};
 
 use baobao_core::{FileRules, GeneratedFile, Overwrite, Version};
-use baobao_manifest::Language;
+use baobao_manifest::{Language, SCHEMA_VERSION};
 
 /// The bao.toml configuration file.
 pub struct BaoToml {
@@ -50,12 +50,16 @@ impl GeneratedFile for BaoToml {
         FileRules {
             overwrite: self.overwrite,
             header: None,
+            backup: false,
+            mode: None,
         }
     }
 
     fn render(&self) -> String {
         format!(
-            r#"[cli]
+            r#"schema_version = {}
+
+[cli]
 name = "{}"
 version = "{}"
 description = "{}"
@@ -92,7 +96,7 @@ type = "bool"
 short = "u"
 description = "Print in uppercase"
 "#,
-            self.name, self.version, self.description, self.language
+            SCHEMA_VERSION, self.name, self.version, self.description, self.language
         )
     }
 }
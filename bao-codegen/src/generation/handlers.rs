@@ -5,6 +5,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use baobao_core::path_key;
 use eyre::Result;
 
 /// Manages handler file paths for a code generator.
@@ -104,10 +105,13 @@ impl HandlerPaths {
 
     /// Find orphaned handler files that are no longer in the schema.
     ///
-    /// Returns paths relative to the base directory.
+    /// Returns `/`-separated paths relative to the base directory. Paths are
+    /// matched with [`path_key`], so a handler differing only in case is not an
+    /// orphan on case-insensitive filesystems.
     pub fn find_orphans(&self, expected_paths: &HashSet<String>) -> Result<Vec<String>> {
         let mut orphans = Vec::new();
-        self.scan_for_orphans(&self.base_dir, "", expected_paths, &mut orphans)?;
+        let expected = path_keys(expected_paths);
+        self.scan_for_orphans(&self.base_dir, "", &expected, &mut orphans)?;
         Ok(orphans)
     }
 
@@ -140,7 +144,7 @@ impl HandlerPaths {
                 };
 
                 // Check if this directory is expected
-                if !expected.contains(&new_prefix) {
+                if !expected.contains(&path_key(&new_prefix)) {
                     orphans.push(new_prefix.clone());
                 } else {
                     self.scan_for_orphans(&path, &new_prefix, expected, orphans)?;
@@ -156,7 +160,7 @@ impl HandlerPaths {
                     format!("{}/{}", prefix, stem)
                 };
 
-                if !expected.contains(&handler_path) {
+                if !expected.contains(&path_key(&handler_path)) {
                     orphans.push(handler_path);
                 }
             }
@@ -174,7 +178,8 @@ impl HandlerPaths {
         expected_paths: &HashSet<String>,
     ) -> Result<Vec<OrphanHandler>> {
         let mut orphans = Vec::new();
-        self.scan_for_orphans_with_status(&self.base_dir, "", expected_paths, &mut orphans)?;
+        let expected = path_keys(expected_paths);
+        self.scan_for_orphans_with_status(&self.base_dir, "", &expected, &mut orphans)?;
         Ok(orphans)
     }
 
@@ -207,7 +212,7 @@ impl HandlerPaths {
                 };
 
                 // Check if this directory is expected
-                if !expected.contains(&new_prefix) {
+                if !expected.contains(&path_key(&new_prefix)) {
                     // Recursively collect all files in this orphaned directory
                     self.collect_all_files(&path, &new_prefix, orphans)?;
                 } else {
@@ -224,7 +229,7 @@ impl HandlerPaths {
                     format!("{}/{}", prefix, stem)
                 };
 
-                if !expected.contains(&relative_path) {
+                if !expected.contains(&path_key(&relative_path)) {
                     let is_unmodified = self.is_handler_unmodified(&path);
                     orphans.push(OrphanHandler {
                         relative_path,
@@ -283,6 +288,11 @@ impl HandlerPaths {
     }
 }
 
+/// Comparison keys for a set of expected relative paths.
+fn path_keys(paths: &HashSet<String>) -> HashSet<String> {
+    paths.iter().map(|p| path_key(p)).collect()
+}
+
 /// Information about an orphaned handler file.
 #[derive(Debug, Clone)]
 pub struct OrphanHandler {
@@ -309,6 +319,8 @@ pub fn find_orphan_commands(
         return Ok(orphans);
     }
 
+    let expected = path_keys(expected_commands);
+
     for entry in std::fs::read_dir(commands_dir)? {
         let entry = entry?;
         let path = entry.path();
@@ -332,7 +344,7 @@ pub fn find_orphan_commands(
         let stem = path.file_stem().unwrap().to_string_lossy();
 
         // Check if this command is expected
-        if !expected_commands.contains(stem.as_ref()) {
+        if !expected.contains(&path_key(&stem)) {
             orphans.push(path);
         }
     }
@@ -391,4 +403,17 @@ mod tests {
             PathBuf::from("src/handlers/hello.ts")
         );
     }
+
+    #[test]
+    fn test_find_orphans_normalizes_expected_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("db")).unwrap();
+        std::fs::write(temp.path().join("db").join("migrate.rs"), "").unwrap();
+        std::fs::write(temp.path().join("old.rs"), "").unwrap();
+
+        let paths = HandlerPaths::new(temp.path(), "rs", RUST_STUB_MARKER);
+        let expected: HashSet<String> = ["db", "db\\migrate"].map(String::from).into();
+
+        assert_eq!(paths.find_orphans(&expected).unwrap(), vec!["old"]);
+    }
 }
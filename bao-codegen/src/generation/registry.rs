@@ -22,9 +22,15 @@
 //! registry.write_all(&output_dir)?;
 //! ```
 
-use std::path::{Path, PathBuf};
-
-use baobao_core::{FileRules, GeneratedFile, Overwrite, WriteResult};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use baobao_core::{
+    FileContent, FileRules, GeneratedFile, Overwrite, OverwritePrompt, WriteResult, checksum,
+    join_relative, write_content_with_prompt, write_content_with_rules,
+};
 use eyre::Result;
 
 /// Category of generated file, determining generation order and behavior.
@@ -59,19 +65,23 @@ impl FileCategory {
 pub struct FileEntry {
     /// Relative path from output directory.
     pub path: String,
-    /// File content.
-    pub content: String,
+    /// File content (text or binary).
+    pub content: FileContent,
     /// Category determining generation behavior.
     pub category: FileCategory,
     /// Override default overwrite behavior.
     pub overwrite: Option<Overwrite>,
+    /// Back up the existing file before overwriting it.
+    pub backup: bool,
+    /// Unix permission bits applied after writing.
+    pub mode: Option<u32>,
 }
 
 impl FileEntry {
     /// Create a new file entry with the given category.
     pub fn new(
         path: impl Into<String>,
-        content: impl Into<String>,
+        content: impl Into<FileContent>,
         category: FileCategory,
     ) -> Self {
         Self {
@@ -79,26 +89,40 @@ impl FileEntry {
             content: content.into(),
             category,
             overwrite: None,
+            backup: false,
+            mode: None,
         }
     }
 
     /// Create a config file (always overwritten, generated first).
-    pub fn config(path: impl Into<String>, content: impl Into<String>) -> Self {
+    pub fn config(path: impl Into<String>, content: impl Into<FileContent>) -> Self {
         Self::new(path, content, FileCategory::Config)
     }
 
     /// Create an infrastructure file (always overwritten).
-    pub fn infrastructure(path: impl Into<String>, content: impl Into<String>) -> Self {
+    pub fn infrastructure(path: impl Into<String>, content: impl Into<FileContent>) -> Self {
         Self::new(path, content, FileCategory::Infrastructure)
     }
 
     /// Create a generated code file (always overwritten).
-    pub fn generated(path: impl Into<String>, content: impl Into<String>) -> Self {
+    pub fn generated(path: impl Into<String>, content: impl Into<FileContent>) -> Self {
         Self::new(path, content, FileCategory::Generated)
     }
 
+    /// Create a binary asset (icons, seed databases, ...), always overwritten.
+    ///
+    /// Assets are written with the generated code; previews show a size
+    /// placeholder instead of the content.
+    pub fn asset(path: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Self::new(
+            path,
+            FileContent::Binary(content.into()),
+            FileCategory::Generated,
+        )
+    }
+
     /// Create a handler stub file (only if missing).
-    pub fn handler(path: impl Into<String>, content: impl Into<String>) -> Self {
+    pub fn handler(path: impl Into<String>, content: impl Into<FileContent>) -> Self {
         Self::new(path, content, FileCategory::Handler)
     }
 
@@ -108,7 +132,11 @@ impl FileEntry {
         file: &F,
         category: FileCategory,
     ) -> Self {
-        Self::new(path, file.render(), category).with_overwrite(file.rules().overwrite)
+        let rules = file.rules();
+        let mut entry = Self::new(path, file.content(), category).with_overwrite(rules.overwrite);
+        entry.backup = rules.backup;
+        entry.mode = rules.mode;
+        entry
     }
 
     /// Override the default overwrite behavior.
@@ -117,6 +145,23 @@ impl FileEntry {
         self
     }
 
+    /// Back up the existing file to `<file>.bak` before overwriting it.
+    pub fn with_backup(mut self) -> Self {
+        self.backup = true;
+        self
+    }
+
+    /// Set Unix permission bits on the written file (e.g., `0o755`).
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Checksum of the entry content, as recorded in `bao.lock`.
+    pub fn checksum(&self) -> String {
+        checksum(self.content.as_bytes())
+    }
+
     /// Get the effective overwrite behavior.
     pub fn overwrite(&self) -> Overwrite {
         self.overwrite
@@ -128,33 +173,19 @@ impl FileEntry {
         FileRules {
             overwrite: self.overwrite(),
             header: None,
+            backup: self.backup,
+            mode: self.mode,
         }
     }
 
     /// Get the full path for this entry.
     pub fn full_path(&self, base: &Path) -> PathBuf {
-        base.join(&self.path)
+        join_relative(base, &self.path)
     }
 
     /// Write this file to disk.
     pub fn write(&self, base: &Path) -> Result<WriteResult> {
-        let path = self.full_path(base);
-        let overwrite = self.overwrite();
-
-        match overwrite {
-            Overwrite::Always => {
-                write_file(&path, &self.content)?;
-                Ok(WriteResult::Written)
-            }
-            Overwrite::IfMissing => {
-                if path.exists() {
-                    Ok(WriteResult::Skipped)
-                } else {
-                    write_file(&path, &self.content)?;
-                    Ok(WriteResult::Written)
-                }
-            }
-        }
+        write_content_with_rules(&self.full_path(base), &self.content, &self.rules())
     }
 }
 
@@ -210,7 +241,7 @@ impl FileRegistry {
         self.entries()
             .map(|e| PreviewEntry {
                 path: e.path.clone(),
-                content: e.content.clone(),
+                content: e.content.to_string(),
                 category: e.category,
             })
             .collect()
@@ -220,30 +251,95 @@ impl FileRegistry {
     ///
     /// Files are written in category order. Returns statistics about what was written.
     pub fn write_all(&self, base: &Path) -> Result<WriteStats> {
+        self.write_all_with_prompt(base, None)
+    }
+
+    /// Write all files, asking `prompt` before replacing a file that differs.
+    ///
+    /// With a prompt, always-overwritten files are written as
+    /// [`Overwrite::Prompt`]; create-once files are still never replaced.
+    pub fn write_all_with_prompt(
+        &self,
+        base: &Path,
+        mut prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<WriteStats> {
         let mut stats = WriteStats::default();
 
         for entry in self.entries() {
-            match entry.write(base)? {
-                WriteResult::Written => {
+            let result = match prompt.as_deref_mut() {
+                Some(prompt) => {
+                    let mut rules = entry.rules();
+                    if rules.overwrite == Overwrite::Always {
+                        rules.overwrite = Overwrite::Prompt;
+                    }
+                    write_content_with_prompt(
+                        &entry.full_path(base),
+                        &entry.content,
+                        &rules,
+                        prompt,
+                    )?
+                }
+                None => entry.write(base)?,
+            };
+            match result {
+                WriteResult::Created(_) | WriteResult::Updated(_) => {
                     stats.written += 1;
                     stats.written_paths.push(entry.path.clone());
                 }
+                WriteResult::Unchanged => stats.unchanged += 1,
                 WriteResult::Skipped => {
                     stats.skipped += 1;
                     stats.skipped_paths.push(entry.path.clone());
                 }
             }
+            if entry.overwrite() == Overwrite::Always && result != WriteResult::Skipped {
+                stats.checksums.insert(entry.path.clone(), entry.checksum());
+            }
+            stats.files.push((entry.path.clone(), result));
         }
 
         Ok(stats)
     }
 
+    /// Prepend a license header to every registered source file.
+    ///
+    /// The header is commented in the syntax of each file's language and
+    /// placed above the DO NOT EDIT banner. Config files and assets are left
+    /// untouched.
+    pub fn prepend_header(&mut self, header: &str) {
+        for entry in &mut self.entries {
+            let Some(prefix) = line_comment(&entry.path) else {
+                continue;
+            };
+            if let FileContent::Text(content) = &entry.content {
+                let comment: String = header
+                    .lines()
+                    .map(|line| format!("{} {}", prefix, line).trim_end().to_string() + "\n")
+                    .collect();
+                entry.content = FileContent::Text(format!("{}\n{}", comment, content));
+            }
+        }
+    }
+
+    /// Keep only the entries for which `keep` returns `true`.
+    pub fn retain(&mut self, keep: impl FnMut(&FileEntry) -> bool) {
+        self.entries.retain(keep);
+    }
+
     /// Clear all registered entries.
     pub fn clear(&mut self) {
         self.entries.clear();
     }
 }
 
+/// Line comment prefix of a source file, from its extension.
+fn line_comment(path: &str) -> Option<&'static str> {
+    match Path::new(path).extension()?.to_str()? {
+        "rs" | "ts" | "js" | "mjs" | "cjs" | "go" => Some("//"),
+        _ => None,
+    }
+}
+
 /// A preview entry for displaying what would be generated.
 #[derive(Debug, Clone)]
 pub struct PreviewEntry {
@@ -258,29 +354,27 @@ pub struct PreviewEntry {
 /// Statistics from a write operation.
 #[derive(Debug, Default)]
 pub struct WriteStats {
-    /// Number of files written.
+    /// Number of files created or updated.
     pub written: usize,
+    /// Number of files that already had the generated content.
+    pub unchanged: usize,
     /// Number of files skipped (already existed).
     pub skipped: usize,
     /// Paths of written files.
     pub written_paths: Vec<String>,
     /// Paths of skipped files.
     pub skipped_paths: Vec<String>,
+    /// Every processed file with its write result, in write order.
+    pub files: Vec<(String, WriteResult)>,
+    /// Checksums of the written always-overwrite files, keyed by path.
+    pub checksums: BTreeMap<String, String>,
 }
 
 impl WriteStats {
     /// Total number of files processed.
     pub fn total(&self) -> usize {
-        self.written + self.skipped
-    }
-}
-
-fn write_file(path: &Path, content: &str) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        self.written + self.unchanged + self.skipped
     }
-    std::fs::write(path, content)?;
-    Ok(())
 }
 
 #[cfg(test)]
@@ -318,6 +412,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_registry_binary_asset() {
+        let temp = TempDir::new().unwrap();
+        let mut registry = FileRegistry::new();
+        registry.register(FileEntry::asset("assets/seed.db", vec![0u8, 1, 2]));
+
+        let preview = registry.preview();
+        assert_eq!(preview[0].content, "<binary file, 3 bytes>");
+
+        registry.write_all(temp.path()).unwrap();
+        assert_eq!(
+            std::fs::read(temp.path().join("assets/seed.db")).unwrap(),
+            [0u8, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_registry_write_all_checksums() {
+        let temp = TempDir::new().unwrap();
+        let mut registry = FileRegistry::new();
+
+        registry.register(FileEntry::config("test.txt", "content"));
+        registry.register(FileEntry::handler("stub.txt", "stub"));
+
+        let stats = registry.write_all(temp.path()).unwrap();
+
+        assert_eq!(stats.checksums.len(), 1);
+        assert_eq!(stats.checksums["test.txt"], checksum(b"content"));
+    }
+
     #[test]
     fn test_registry_write_all() {
         let temp = TempDir::new().unwrap();
@@ -349,10 +473,98 @@ mod tests {
 
         assert_eq!(stats.written, 0);
         assert_eq!(stats.skipped, 1);
+        assert_eq!(
+            stats.files,
+            vec![("handler.rs".to_string(), WriteResult::Skipped)]
+        );
         // Original content preserved
         assert_eq!(std::fs::read_to_string(&path).unwrap(), "user code");
     }
 
+    #[test]
+    fn test_unchanged_files_counted() {
+        let temp = TempDir::new().unwrap();
+
+        let mut registry = FileRegistry::new();
+        registry.register(FileEntry::generated("a.txt", "content"));
+
+        assert_eq!(registry.write_all(temp.path()).unwrap().written, 1);
+        let stats = registry.write_all(temp.path()).unwrap();
+        assert_eq!(stats.written, 0);
+        assert_eq!(stats.unchanged, 1);
+        assert_eq!(stats.total(), 1);
+    }
+
+    #[test]
+    fn test_write_all_with_prompt() {
+        struct Decline;
+        impl OverwritePrompt for Decline {
+            fn confirm(&mut self, path: &Path, _existing: &str, _new: &str) -> Result<bool> {
+                assert!(path.ends_with("a.txt"));
+                Ok(false)
+            }
+        }
+
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.txt"), "manual edits").unwrap();
+        std::fs::write(temp.path().join("stub.txt"), "user code").unwrap();
+
+        let mut registry = FileRegistry::new();
+        registry.register(FileEntry::generated("a.txt", "content"));
+        registry.register(FileEntry::handler("stub.txt", "stub"));
+        registry.register(FileEntry::generated("b.txt", "content"));
+
+        let stats = registry
+            .write_all_with_prompt(temp.path(), Some(&mut Decline))
+            .unwrap();
+
+        // Declined files keep their content and are left out of bao.lock
+        assert_eq!(stats.skipped_paths, vec!["a.txt", "stub.txt"]);
+        assert_eq!(stats.written_paths, vec!["b.txt"]);
+        assert!(!stats.checksums.contains_key("a.txt"));
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a.txt")).unwrap(),
+            "manual edits"
+        );
+    }
+
+    #[test]
+    fn test_backup_before_overwrite() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("main.rs");
+
+        std::fs::write(&path, "manual edits").unwrap();
+
+        let mut registry = FileRegistry::new();
+        registry.register(FileEntry::infrastructure("main.rs", "generated").with_backup());
+        registry.write_all(temp.path()).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "generated");
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("main.rs.bak")).unwrap(),
+            "manual edits"
+        );
+    }
+
+    #[test]
+    fn test_prepend_header() {
+        let mut registry = FileRegistry::new();
+        registry.register(FileEntry::config("Cargo.toml", "[package]\n"));
+        registry.register(FileEntry::generated(
+            "src/cli.rs",
+            "// Generated by Bao - DO NOT EDIT\n",
+        ));
+        registry.prepend_header("Copyright Acme Corp.\n\nSPDX-License-Identifier: MIT");
+
+        let preview = registry.preview();
+
+        assert_eq!(preview[0].content, "[package]\n");
+        assert_eq!(
+            preview[1].content,
+            "// Copyright Acme Corp.\n//\n// SPDX-License-Identifier: MIT\n\n// Generated by Bao - DO NOT EDIT\n"
+        );
+    }
+
     #[test]
     fn test_preview() {
         let mut registry = FileRegistry::new();
@@ -4,12 +4,15 @@
 //! - [`LanguageCodegen`] - Main trait for language code generators
 //! - [`TypeMapper`] - Trait for mapping schema types to language types
 //! - [`NamingConvention`] - Language-specific naming rules
+//! - [`TypeRegistry`] - Per-language argument type mappings
 //! - [`GenerateResult`] - Result of code generation
 //! - [`CleanResult`] - Result of cleaning orphaned files
 //! - [`PreviewFile`] - Generated file preview
 
 mod naming;
 mod traits;
+mod type_registry;
 
 pub use naming::NamingConvention;
 pub use traits::{CleanResult, GenerateResult, LanguageCodegen, PreviewFile, TypeMapper};
+pub use type_registry::{ScalarType, TypeRegistry};
@@ -1,10 +1,12 @@
 //! Language-agnostic code generation traits.
 
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
-use baobao_core::{ArgType, ContextFieldType};
+use baobao_core::{ArgType, ContextFieldType, OverwritePrompt, WriteResult};
 use eyre::Result;
 
+use crate::generation::FileEntry;
+
 /// Trait for language-specific code generators.
 ///
 /// Implement this trait to add support for generating CLI code in a new language.
@@ -19,7 +21,26 @@ pub trait LanguageCodegen {
     fn preview(&self) -> Vec<PreviewFile>;
 
     /// Generate all files into the specified output directory
-    fn generate(&self, output_dir: &Path) -> Result<GenerateResult>;
+    fn generate(&self, output_dir: &Path) -> Result<GenerateResult> {
+        self.generate_with_prompt(output_dir, None)
+    }
+
+    /// Generate all files, asking `prompt` before replacing generated files
+    /// that differ on disk.
+    ///
+    /// Skipped files are reported as [`WriteResult::Skipped`].
+    fn generate_with_prompt(
+        &self,
+        output_dir: &Path,
+        prompt: Option<&mut dyn OverwritePrompt>,
+    ) -> Result<GenerateResult>;
+
+    /// Render every file in memory, handler stubs included, without touching
+    /// the filesystem.
+    ///
+    /// Unlike [`preview`](Self::preview), entries keep their category and
+    /// write rules, so callers can archive or write them elsewhere.
+    fn files(&self) -> Result<Vec<FileEntry>>;
 
     /// Clean orphaned generated files.
     ///
@@ -49,6 +70,10 @@ pub struct GenerateResult {
     pub created_handlers: Vec<String>,
     /// Handler files that exist but are no longer used
     pub orphan_handlers: Vec<String>,
+    /// Generated files (relative to the output directory) with their write results
+    pub files: Vec<(String, WriteResult)>,
+    /// Checksums of the always-overwritten files, for `bao.lock`
+    pub checksums: BTreeMap<String, String>,
 }
 
 /// Result of cleaning orphaned files
@@ -0,0 +1,124 @@
+//! Per-language scalar type registry.
+
+use baobao_core::ArgType;
+
+/// How a target language represents one argument type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarType {
+    /// The argument type being mapped.
+    pub arg_type: ArgType,
+    /// Type name in generated code (e.g., `i64`, `number`, `int64`).
+    pub type_name: &'static str,
+    /// Expression converting a raw command-line string into the type, with `{}`
+    /// standing for the source expression. `None` when the string is used as is
+    /// or the CLI framework parses the value itself.
+    pub parse: Option<&'static str>,
+}
+
+impl ScalarType {
+    /// Map an argument type to a language type name.
+    pub const fn new(arg_type: ArgType, type_name: &'static str) -> Self {
+        Self {
+            arg_type,
+            type_name,
+            parse: None,
+        }
+    }
+
+    /// Set the parse expression (e.g., `parseInt({})`).
+    pub const fn with_parse(mut self, parse: &'static str) -> Self {
+        self.parse = Some(parse);
+        self
+    }
+}
+
+/// Registry of the argument types a language generator supports.
+///
+/// Each generator builds one registry describing its type names and parse
+/// snippets, so supporting a new argument type means registering one entry
+/// per language instead of extending `match` statements across adapters.
+///
+/// # Example
+///
+/// ```
+/// use baobao_codegen::language::{ScalarType, TypeRegistry};
+/// use baobao_core::ArgType;
+///
+/// let types = TypeRegistry::new()
+///     .register(ScalarType::new(ArgType::String, "string"))
+///     .register(ScalarType::new(ArgType::Int, "int64").with_parse("parseInt({})"));
+///
+/// assert_eq!(types.type_name(ArgType::Int), "int64");
+/// assert_eq!(types.parse_expr(ArgType::Int, "args[0]").unwrap(), "parseInt(args[0])");
+/// assert_eq!(types.parse_expr(ArgType::String, "args[0]"), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry {
+    types: Vec<ScalarType>,
+}
+
+impl TypeRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a type, replacing any previous mapping for the same argument type.
+    pub fn register(mut self, ty: ScalarType) -> Self {
+        self.types.retain(|t| t.arg_type != ty.arg_type);
+        self.types.push(ty);
+        self
+    }
+
+    /// Get the mapping for an argument type.
+    pub fn get(&self, arg_type: ArgType) -> Option<&ScalarType> {
+        self.types.iter().find(|t| t.arg_type == arg_type)
+    }
+
+    /// Get the language type name for an argument type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type was not registered; generators register every
+    /// [`ArgType`], which [`TypeRegistry::missing`] checks in tests.
+    pub fn type_name(&self, arg_type: ArgType) -> &'static str {
+        self.get(arg_type)
+            .unwrap_or_else(|| panic!("no type mapping registered for '{}'", arg_type))
+            .type_name
+    }
+
+    /// Render the parse expression for `source`, if the type needs one.
+    pub fn parse_expr(&self, arg_type: ArgType, source: &str) -> Option<String> {
+        self.get(arg_type)
+            .and_then(|t| t.parse)
+            .map(|parse| parse.replace("{}", source))
+    }
+
+    /// Argument types without a registered mapping.
+    pub fn missing(&self) -> Vec<ArgType> {
+        ArgType::all()
+            .filter(|ty| self.get(*ty).is_none())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_replaces_existing() {
+        let types = TypeRegistry::new()
+            .register(ScalarType::new(ArgType::Path, "string"))
+            .register(ScalarType::new(ArgType::Path, "PathBuf"));
+
+        assert_eq!(types.type_name(ArgType::Path), "PathBuf");
+        assert_eq!(types.missing().len(), ArgType::all().count() - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "no type mapping registered for 'int'")]
+    fn test_type_name_missing() {
+        TypeRegistry::new().type_name(ArgType::Int);
+    }
+}
@@ -4,7 +4,7 @@ use baobao_ir::AppIR;
 use baobao_manifest::Manifest;
 
 use super::diagnostic::{Diagnostic, Severity};
-use crate::schema::ComputedData;
+use crate::{adapters::ResourceAdapters, schema::ComputedData};
 
 /// Context passed through all pipeline phases.
 ///
@@ -14,12 +14,16 @@ use crate::schema::ComputedData;
 pub struct CompilationContext {
     /// The original manifest being compiled.
     pub manifest: Manifest,
+    /// Profile whose overrides LowerPhase applies to the manifest.
+    pub profile: Option<String>,
     /// The lowered Application IR (populated by LowerPhase).
     pub ir: Option<AppIR>,
     /// Pre-computed analysis data (populated by AnalyzePhase).
     pub computed: Option<ComputedData>,
     /// Diagnostics collected during compilation.
     pub diagnostics: Vec<Diagnostic>,
+    /// Adapters for custom resources, registered by plugins.
+    pub resource_adapters: ResourceAdapters,
 }
 
 impl CompilationContext {
@@ -27,9 +31,11 @@ impl CompilationContext {
     pub fn new(manifest: Manifest) -> Self {
         Self {
             manifest,
+            profile: None,
             ir: None,
             computed: None,
             diagnostics: Vec::new(),
+            resource_adapters: ResourceAdapters::new(),
         }
     }
 
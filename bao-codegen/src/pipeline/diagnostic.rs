@@ -3,6 +3,7 @@
 //! This module provides types for collecting errors, warnings, and informational
 //! messages during compilation phases.
 
+use baobao_ir::SourceSpan;
 use serde::Serialize;
 
 /// Severity level for a diagnostic message.
@@ -49,6 +50,8 @@ pub struct Diagnostic {
     pub message: String,
     /// Optional location in the manifest (e.g., "commands.deploy").
     pub location: Option<String>,
+    /// Optional source range in the manifest file.
+    pub span: Option<SourceSpan>,
 }
 
 impl Diagnostic {
@@ -59,6 +62,7 @@ impl Diagnostic {
             phase: phase.into(),
             message: message.into(),
             location: None,
+            span: None,
         }
     }
 
@@ -69,6 +73,7 @@ impl Diagnostic {
             phase: phase.into(),
             message: message.into(),
             location: None,
+            span: None,
         }
     }
 
@@ -79,6 +84,7 @@ impl Diagnostic {
             phase: phase.into(),
             message: message.into(),
             location: None,
+            span: None,
         }
     }
 
@@ -87,13 +93,29 @@ impl Diagnostic {
         self.location = Some(location.into());
         self
     }
+
+    /// Attach the manifest span of the offending item, if known.
+    pub fn with_span(mut self, span: Option<SourceSpan>) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Where the diagnostic points: the source position if known,
+    /// otherwise the manifest location.
+    pub fn position(&self) -> Option<String> {
+        match (&self.span, &self.location) {
+            (Some(span), _) => Some(span.to_string()),
+            (None, Some(location)) => Some(location.clone()),
+            (None, None) => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Diagnostic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}: {}", self.severity, self.message)?;
-        if let Some(loc) = &self.location {
-            write!(f, " (at {})", loc)?;
+        if let Some(position) = self.position() {
+            write!(f, " (at {})", position)?;
         }
         Ok(())
     }
@@ -117,6 +139,19 @@ mod tests {
         assert_eq!(diag.location.as_deref(), Some("commands.deploy"));
     }
 
+    #[test]
+    fn test_diagnostic_with_span() {
+        let src = "[commands.deploy]\n";
+        let span = SourceSpan::new("bao.toml", src, 10, 16);
+        let diag = Diagnostic::warning("validate", "missing description")
+            .at("commands.deploy")
+            .with_span(Some(span));
+        assert_eq!(
+            diag.to_string(),
+            "warning: missing description (at bao.toml:1:11)"
+        );
+    }
+
     #[test]
     fn test_severity_display() {
         assert_eq!(Severity::Error.to_string(), "error");
@@ -0,0 +1,275 @@
+//! Canonicalize phase - normalizes the Application IR.
+//!
+//! Generators receive the IR in a single canonical form, so output does not
+//! depend on how a manifest was written or on each backend re-sorting:
+//!
+//! - Commands come before routes, each sorted by name; subcommands are sorted
+//!   recursively.
+//! - Command paths are rebuilt from the command tree.
+//! - Inputs are deduplicated by name (the first declaration wins) and
+//!   positionals come before flags, otherwise keeping their order.
+//! - Inputs with a default are not required.
+//! - Global inputs are deduplicated and resolved like command inputs.
+//! - Route paths have repeated and trailing slashes removed.
+//! - Translated messages are sorted by command path, then input name.
+
+use std::collections::HashSet;
+
+use baobao_ir::{AppIR, CommandOp, Input, InputKind, Operation, RouteOp};
+use eyre::Result;
+
+use crate::pipeline::{CompilationContext, Diagnostic, Phase};
+
+/// Phase that canonicalizes the Application IR.
+///
+/// This phase must run after `LowerPhase` as it requires the IR to be populated.
+pub struct CanonicalizePhase;
+
+impl Phase for CanonicalizePhase {
+    fn name(&self) -> &'static str {
+        "canonicalize"
+    }
+
+    fn description(&self) -> &'static str {
+        "Normalize IR ordering, names and defaults"
+    }
+
+    fn run(&self, ctx: &mut CompilationContext) -> Result<()> {
+        let ir = ctx.ir.as_mut().ok_or_else(|| {
+            eyre::eyre!("IR not set - CanonicalizePhase must run after LowerPhase")
+        })?;
+
+        let mut diagnostics = Vec::new();
+        canonicalize(ir, &mut diagnostics);
+        ctx.diagnostics.extend(diagnostics);
+        Ok(())
+    }
+}
+
+/// Canonicalize an IR in place, reporting dropped duplicate inputs.
+fn canonicalize(ir: &mut AppIR, diagnostics: &mut Vec<Diagnostic>) {
+    canonicalize_inputs(&mut ir.global_inputs, "the application", diagnostics);
+
+    for op in &mut ir.operations {
+        match op {
+            Operation::Command(cmd) => canonicalize_command(cmd, &[], diagnostics),
+            Operation::Route(route) => canonicalize_route(route, diagnostics),
+        }
+    }
+
+    ir.operations.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+
+    for locale in &mut ir.locales {
+        locale
+            .messages
+            .sort_by(|a, b| (&a.command, &a.input).cmp(&(&b.command, &b.input)));
+    }
+}
+
+/// Commands first, then routes, each by name.
+fn sort_key(op: &Operation) -> (u8, &str) {
+    match op {
+        Operation::Command(cmd) => (0, &cmd.name),
+        Operation::Route(route) => (1, &route.name),
+    }
+}
+
+fn canonicalize_command(cmd: &mut CommandOp, parent: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    cmd.path = parent.to_vec();
+    cmd.path.push(cmd.name.clone());
+
+    let location = format!("command '{}'", cmd.path.join(" "));
+    canonicalize_inputs(&mut cmd.inputs, &location, diagnostics);
+
+    for child in &mut cmd.children {
+        canonicalize_command(child, &cmd.path, diagnostics);
+    }
+    cmd.children.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+fn canonicalize_route(route: &mut RouteOp, diagnostics: &mut Vec<Diagnostic>) {
+    route.path = normalize_route_path(&route.path);
+
+    let location = format!("route '{}'", route.name);
+    canonicalize_inputs(&mut route.inputs, &location, diagnostics);
+}
+
+fn canonicalize_inputs(inputs: &mut Vec<Input>, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut seen = HashSet::new();
+    inputs.retain(|input| {
+        let first = seen.insert(input.name.clone());
+        if !first {
+            diagnostics.push(
+                Diagnostic::warning(
+                    "canonicalize",
+                    format!(
+                        "input '{}' of {} is declared more than once; keeping the first",
+                        input.name, location
+                    ),
+                )
+                .with_span(input.span.clone()),
+            );
+        }
+        first
+    });
+
+    // Stable sort keeps declaration order within each kind
+    inputs.sort_by_key(|input| !matches!(input.kind, InputKind::Positional));
+
+    for input in inputs.iter_mut() {
+        if input.default.is_some() {
+            input.required = false;
+        }
+    }
+}
+
+/// Collapse repeated slashes and drop a trailing one (`//users/` -> `/users`).
+fn normalize_route_path(path: &str) -> String {
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use baobao_ir::{AppMeta, DefaultValue, HttpMethod, InputType};
+
+    use super::*;
+
+    fn input(name: &str, kind: InputKind) -> Input {
+        Input {
+            name: name.into(),
+            ty: InputType::String,
+            kind,
+            required: true,
+            default: None,
+            description: None,
+            choices: None,
+            multiple: false,
+            delimiter: None,
+            allow_hyphen_values: false,
+            prompt: None,
+            constraints: Default::default(),
+            requires: vec![],
+            required_if: None,
+            hint: None,
+            long_description: None,
+            deprecated: None,
+            span: None,
+        }
+    }
+
+    fn command(name: &str, inputs: Vec<Input>, children: Vec<CommandOp>) -> CommandOp {
+        CommandOp {
+            name: name.into(),
+            path: vec![],
+            description: String::new(),
+            inputs,
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
+            children,
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
+        }
+    }
+
+    fn route(name: &str, path: &str) -> RouteOp {
+        RouteOp {
+            name: name.into(),
+            method: HttpMethod::Get,
+            path: path.into(),
+            description: None,
+            inputs: vec![],
+            span: None,
+        }
+    }
+
+    fn app(operations: Vec<Operation>) -> AppIR {
+        AppIR {
+            meta: AppMeta {
+                name: "test".into(),
+                version: "0.1.0".into(),
+                description: None,
+                author: None,
+                docs: Default::default(),
+                build_info: false,
+                docs_command: false,
+                doctor_command: false,
+                external_subcommands: false,
+                default_command: None,
+                header: None,
+            },
+            resources: vec![],
+            operations,
+            global_inputs: vec![],
+            locales: vec![],
+        }
+    }
+
+    #[test]
+    fn test_operations_sorted() {
+        let mut ir = app(vec![
+            Operation::Route(route("list", "/items")),
+            Operation::Command(command(
+                "zeta",
+                vec![],
+                vec![command("b", vec![], vec![]), command("a", vec![], vec![])],
+            )),
+            Operation::Command(command("alpha", vec![], vec![])),
+        ]);
+        canonicalize(&mut ir, &mut Vec::new());
+
+        let names: Vec<_> = ir.operations.iter().map(|op| sort_key(op).1).collect();
+        assert_eq!(names, vec!["alpha", "zeta", "list"]);
+
+        let zeta = ir.commands().nth(1).unwrap();
+        let children: Vec<_> = zeta.children.iter().map(|c| c.handler_path()).collect();
+        assert_eq!(children, vec!["zeta/a", "zeta/b"]);
+    }
+
+    #[test]
+    fn test_inputs_deduplicated_and_ordered() {
+        let mut ir = app(vec![Operation::Command(command(
+            "run",
+            vec![
+                input("verbose", InputKind::Flag { short: None }),
+                input("target", InputKind::Positional),
+                input("target", InputKind::Flag { short: None }),
+                input("source", InputKind::Positional),
+            ],
+            vec![],
+        ))]);
+        let mut diagnostics = Vec::new();
+        canonicalize(&mut ir, &mut diagnostics);
+
+        let cmd = ir.commands().next().unwrap();
+        let names: Vec<_> = cmd.inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["target", "source", "verbose"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'target' of command 'run'"));
+    }
+
+    #[test]
+    fn test_defaults_resolved() {
+        let mut ir = app(vec![Operation::Command(command(
+            "run",
+            vec![Input {
+                default: Some(DefaultValue::String("out".into())),
+                ..input("dir", InputKind::Positional)
+            }],
+            vec![],
+        ))]);
+        canonicalize(&mut ir, &mut Vec::new());
+
+        assert!(!ir.commands().next().unwrap().inputs[0].required);
+    }
+
+    #[test]
+    fn test_normalize_route_path() {
+        assert_eq!(normalize_route_path("//users//{id}/"), "/users/{id}");
+        assert_eq!(normalize_route_path("/"), "/");
+    }
+}
@@ -1,15 +1,26 @@
 //! Lower phase - transforms manifest to Application IR.
 //!
 //! This module transforms the parsed manifest into the unified Application IR
-//! that generators consume.
+//! that generators consume. Commands, routes and inputs carry the span of
+//! their name in the manifest, so later diagnostics can point at it.
 
-use std::{collections::HashMap, time::Duration};
+use std::{collections::BTreeMap, path::Path, time::Duration};
 
 use baobao_ir::{
-    AppIR, AppMeta, CommandOp, DatabaseResource, DatabaseType, DefaultValue, HttpClientResource,
-    Input, InputKind, InputType, Operation, PoolConfig, Resource, SqliteOptions,
+    AppIR, AppMeta, CacheMode, CacheOptions, CacheResource, CommandOp, ConfigField,
+    ConfigFieldType, ConfigFormat, ConfigOptions, ConfigResource, DatabaseResource, DatabaseType,
+    DefaultValue, Docs, DuckdbOptions, EmailOptions, EmailResource, Example, GrpcOptions,
+    GrpcResource, HttpAuth, HttpClientResource, HttpMethod, HttpOptions, Input, InputConstraints,
+    InputGroup, InputHint, InputKind, InputPrompt, InputType, LlmOptions, LlmProvider, LlmResource,
+    Locale, LogFormat, LogLevel, LoggerResource, Message, NO_INPUT_FLAG, Operation, PoolConfig,
+    QueueResource, QueueType, RequiredIf, Resource, RouteOp, S3Options, SecretsBackend,
+    SecretsOptions, SecretsResource, SmtpTls, SourceSpan, SqliteOptions, StorageResource,
+    WorkdirOptions, WorkdirResource,
+};
+use baobao_manifest::{
+    ArgType, Command, ContextField, Description, Flag, GrpcConfig, HintKind, Manifest, Param,
+    PromptKind, Route, SourceMap,
 };
-use baobao_manifest::{ArgType, Command, ContextField, Flag, Manifest};
 use eyre::Result;
 
 use crate::pipeline::{CompilationContext, Phase};
@@ -17,6 +28,7 @@ use crate::pipeline::{CompilationContext, Phase};
 /// Phase that transforms the manifest into Application IR.
 ///
 /// This phase converts the parsed manifest into the unified IR that generators consume.
+/// The selected profile, if any, is applied to the manifest first.
 pub struct LowerPhase;
 
 impl Phase for LowerPhase {
@@ -29,6 +41,9 @@ impl Phase for LowerPhase {
     }
 
     fn run(&self, ctx: &mut CompilationContext) -> Result<()> {
+        if let Some(profile) = &ctx.profile {
+            ctx.manifest = ctx.manifest.with_profile(profile)?;
+        }
         ctx.ir = Some(lower_manifest(&ctx.manifest));
         Ok(())
     }
@@ -39,8 +54,109 @@ fn lower_manifest(manifest: &Manifest) -> AppIR {
     AppIR {
         meta: lower_meta(manifest),
         resources: lower_resources(manifest),
-        operations: lower_commands(&manifest.commands),
+        operations: lower_commands(manifest)
+            .into_iter()
+            .chain(lower_routes(&manifest.routes, &manifest.spans))
+            .collect(),
+        global_inputs: lower_global_flags(manifest),
+        locales: lower_locales(manifest),
+    }
+}
+
+/// Lower the manifest's global flags, sorted for deterministic output.
+///
+/// Adds `--no-input` when an argument asks for a missing value, unless the
+/// manifest declares it.
+fn lower_global_flags(manifest: &Manifest) -> Vec<Input> {
+    let mut inputs: Vec<_> = manifest
+        .flags
+        .iter()
+        .map(|(name, flag)| lower_flag(name, flag, manifest.spans.global_flag(name)))
+        .collect();
+    if manifest.commands.values().any(Command::has_prompt_messages)
+        && !inputs.iter().any(|input| input.name == NO_INPUT_FLAG)
+    {
+        inputs.push(no_input_flag());
+    }
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    inputs
+}
+
+/// The global flag turning off prompts for missing arguments.
+fn no_input_flag() -> Input {
+    Input {
+        name: NO_INPUT_FLAG.into(),
+        ty: InputType::Bool,
+        kind: InputKind::Flag { short: None },
+        required: false,
+        default: None,
+        description: Some("Fail instead of asking for missing arguments".into()),
+        long_description: None,
+        choices: None,
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        hint: None,
+        constraints: InputConstraints::default(),
+        requires: vec![],
+        required_if: None,
+        deprecated: None,
+        span: None,
+    }
+}
+
+/// Collect the translations of every description into one bundle per
+/// locale, leaving out the default locale.
+fn lower_locales(manifest: &Manifest) -> Vec<Locale> {
+    let mut locales: BTreeMap<String, Vec<Message>> = BTreeMap::new();
+    let default = manifest.cli.locale();
+    let mut add = |command: &[String], input: Option<&str>, description: &Description| {
+        for (locale, text) in description.translations_except(default) {
+            locales.entry(locale.into()).or_default().push(Message {
+                command: command.to_vec(),
+                input: input.map(Into::into),
+                text: text.into(),
+            });
+        }
+    };
+
+    if let Some(description) = &manifest.cli.description {
+        add(&[], None, description);
+    }
+    let mut stack: Vec<(Vec<String>, &Command)> = manifest
+        .commands
+        .iter()
+        .map(|(name, cmd)| (vec![name.clone()], cmd))
+        .collect();
+    while let Some((path, cmd)) = stack.pop() {
+        add(&path, None, &cmd.description);
+        let inputs = cmd
+            .args
+            .iter()
+            .map(|(name, arg)| (name, &arg.description))
+            .chain(
+                cmd.flags
+                    .iter()
+                    .map(|(name, flag)| (name, &flag.description)),
+            );
+        for (name, description) in inputs {
+            if let Some(description) = description {
+                add(&path, Some(name), description);
+            }
+        }
+        for (name, child) in &cmd.commands {
+            let mut child_path = path.clone();
+            child_path.push(name.clone());
+            stack.push((child_path, child));
+        }
     }
+
+    // Commands are visited in hash order; canonicalization sorts the messages
+    locales
+        .into_iter()
+        .map(|(name, messages)| Locale { name, messages })
+        .collect()
 }
 
 /// Lower CLI metadata from manifest.
@@ -48,8 +164,19 @@ fn lower_meta(manifest: &Manifest) -> AppMeta {
     AppMeta {
         name: manifest.cli.name.clone(),
         version: manifest.cli.version.to_string(),
-        description: manifest.cli.description.clone(),
+        description: manifest.cli.description.clone().map(Into::into),
         author: manifest.cli.author.clone(),
+        docs: Docs {
+            footer: manifest.cli.footer.clone(),
+            authors: manifest.cli.author.iter().cloned().collect(),
+            ..Default::default()
+        },
+        build_info: manifest.cli.build_info,
+        docs_command: manifest.cli.docs_command,
+        doctor_command: manifest.cli.doctor_command,
+        external_subcommands: manifest.cli.external_subcommands,
+        default_command: manifest.cli.default_command.clone(),
+        header: manifest.generation.header().map(String::from),
     }
 }
 
@@ -60,40 +187,288 @@ fn lower_resources(manifest: &Manifest) -> Vec<Resource> {
     if let Some(db) = &manifest.context.database
         && let Some(resource) = lower_database_resource("db", db)
     {
+        // The replica shares the primary's type and takes its own pool
+        let replica = db.replica_config().map(|replica| DatabaseResource {
+            name: "db_replica".into(),
+            env_var: default_env_var(replica.env.as_deref(), "DATABASE_REPLICA_URL"),
+            pool: lower_pool_config(&replica.pool),
+            ..resource.clone()
+        });
         resources.push(Resource::Database(resource));
+        resources.extend(replica.map(Resource::Database));
     }
 
-    if manifest.context.http.is_some() {
+    for (name, http) in manifest.context.http_configs() {
         resources.push(Resource::HttpClient(HttpClientResource {
-            name: "http".into(),
+            name: name.into(),
+            options: HttpOptions {
+                base_url: http.base_url.clone(),
+                headers: http.headers.clone(),
+                timeout: http.timeout.map(Duration::from_secs),
+                user_agent: http.user_agent.clone(),
+                retries: http.retries.unwrap_or(0),
+                backoff: http.backoff_ms.map(Duration::from_millis),
+                retry_on: http.retry_on.clone(),
+                auth: http.auth.as_ref().map(lower_http_auth),
+            },
+        }));
+    }
+
+    if let Some(logging) = manifest.context.logging_config() {
+        resources.push(Resource::Logger(LoggerResource {
+            name: "logger".into(),
+            level: match logging.level {
+                baobao_manifest::LogLevel::Trace => LogLevel::Trace,
+                baobao_manifest::LogLevel::Debug => LogLevel::Debug,
+                baobao_manifest::LogLevel::Info => LogLevel::Info,
+                baobao_manifest::LogLevel::Warn => LogLevel::Warn,
+                baobao_manifest::LogLevel::Error => LogLevel::Error,
+            },
+            format: match logging.format {
+                baobao_manifest::LogFormat::Pretty => LogFormat::Pretty,
+                baobao_manifest::LogFormat::Json => LogFormat::Json,
+            },
+            env_filter: logging.env_filter.clone(),
+        }));
+    }
+
+    if let Some(s3) = manifest.context.s3_config() {
+        resources.push(Resource::Storage(StorageResource {
+            name: "storage".into(),
+            s3: S3Options {
+                bucket: s3.bucket.clone(),
+                region: s3.region.clone(),
+                endpoint: s3.endpoint.clone(),
+            },
+        }));
+    }
+
+    if let Some(grpc) = manifest.context.grpc_config() {
+        resources.push(Resource::Grpc(GrpcResource {
+            name: "grpc".into(),
+            options: GrpcOptions {
+                endpoint: grpc.endpoint.clone(),
+                protos: grpc.protos.clone(),
+                includes: lower_grpc_includes(grpc),
+                timeout: grpc.timeout.map(Duration::from_secs),
+            },
+        }));
+    }
+
+    if let Some(email) = manifest.context.email_config() {
+        let tls = match email.tls {
+            baobao_manifest::SmtpTls::Starttls => SmtpTls::Starttls,
+            baobao_manifest::SmtpTls::Tls => SmtpTls::Tls,
+            baobao_manifest::SmtpTls::None => SmtpTls::None,
+        };
+        resources.push(Resource::Email(EmailResource {
+            name: "email".into(),
+            options: EmailOptions {
+                host: email.host.clone(),
+                port: email.port.unwrap_or_else(|| tls.default_port()),
+                tls,
+                username_env: email.username_env.clone(),
+                password_env: email.password_env.clone(),
+            },
+        }));
+    }
+
+    if let Some(queue) = &manifest.context.queue {
+        let queue_type = match queue {
+            ContextField::Kafka(_) => QueueType::Kafka,
+            _ => QueueType::Nats,
+        };
+        resources.push(Resource::Queue(QueueResource {
+            name: "queue".into(),
+            queue_type,
+            env_var: default_env_var(queue.env(), queue.default_env()),
+        }));
+    }
+
+    if let Some(config) = manifest.context.app_config() {
+        let format = match config.resolved_format() {
+            Some(baobao_manifest::ConfigFormat::Json) => ConfigFormat::Json,
+            Some(baobao_manifest::ConfigFormat::Yaml) => ConfigFormat::Yaml,
+            // Validation rejects files whose format can't be inferred
+            Some(baobao_manifest::ConfigFormat::Toml) | None => ConfigFormat::Toml,
+        };
+        let fields = config
+            .fields
+            .iter()
+            .map(|(name, field)| {
+                let field_type = match field.field_type {
+                    baobao_manifest::ConfigFieldType::String => ConfigFieldType::String,
+                    baobao_manifest::ConfigFieldType::Int => ConfigFieldType::Int,
+                    baobao_manifest::ConfigFieldType::Float => ConfigFieldType::Float,
+                    baobao_manifest::ConfigFieldType::Bool => ConfigFieldType::Bool,
+                };
+                let field = ConfigField {
+                    field_type,
+                    optional: field.optional,
+                };
+                (name.clone(), field)
+            })
+            .collect();
+        resources.push(Resource::Config(ConfigResource {
+            name: "config".into(),
+            options: ConfigOptions {
+                path: config.path.clone(),
+                format,
+                fields,
+            },
+        }));
+    }
+
+    if let Some(llm) = manifest.context.llm_config() {
+        let provider = match llm.provider {
+            baobao_manifest::LlmProvider::Openai => LlmProvider::Openai,
+            baobao_manifest::LlmProvider::Anthropic => LlmProvider::Anthropic,
+        };
+        resources.push(Resource::Llm(LlmResource {
+            name: "llm".into(),
+            options: LlmOptions {
+                provider,
+                model: llm.model.clone(),
+                api_key_env: llm.resolved_api_key_env().to_string(),
+                base_url: llm.resolved_base_url().trim_end_matches('/').to_string(),
+                max_tokens: llm.max_tokens,
+            },
+        }));
+    }
+
+    if let Some(cache) = manifest.context.cache_config() {
+        let mode = match cache.mode {
+            baobao_manifest::CacheMode::Memory => CacheMode::Memory,
+            baobao_manifest::CacheMode::Disk => CacheMode::Disk,
+        };
+        resources.push(Resource::Cache(CacheResource {
+            name: "cache".into(),
+            options: CacheOptions {
+                max_capacity: cache.resolved_max_capacity(),
+                ttl: cache.ttl.map(Duration::from_secs),
+                mode,
+                path: cache.path.clone(),
+                namespace: manifest.cli.name.clone(),
+            },
+        }));
+    }
+
+    if let Some(workdir) = manifest.context.workdir_config() {
+        resources.push(Resource::Workdir(WorkdirResource {
+            name: "workdir".into(),
+            options: WorkdirOptions {
+                data: workdir.has_data(),
+                temp: workdir.temp,
+                path: workdir.path.clone(),
+                namespace: manifest.cli.name.clone(),
+            },
+        }));
+    }
+
+    if let Some(secrets) = manifest.context.secrets_config() {
+        let backend = match secrets.backend {
+            baobao_manifest::SecretsBackend::Keyring => SecretsBackend::Keyring,
+            baobao_manifest::SecretsBackend::Env => SecretsBackend::Env,
+            baobao_manifest::SecretsBackend::File => SecretsBackend::File,
+        };
+        resources.push(Resource::Secrets(SecretsResource {
+            name: "secrets".into(),
+            options: SecretsOptions {
+                backend,
+                service: secrets
+                    .service
+                    .clone()
+                    .unwrap_or_else(|| manifest.cli.name.clone()),
+                path: secrets.resolved_path().to_string(),
+                prefix: secrets.prefix.clone().unwrap_or_default(),
+            },
         }));
     }
 
     resources
 }
 
+/// The include directories of a gRPC channel, defaulting to the directories
+/// of its proto files.
+fn lower_grpc_includes(grpc: &GrpcConfig) -> Vec<String> {
+    if !grpc.includes.is_empty() {
+        return grpc.includes.clone();
+    }
+    let mut includes: Vec<String> = Vec::new();
+    for proto in &grpc.protos {
+        let dir = match Path::new(proto).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_string_lossy().into_owned(),
+            _ => ".".to_string(),
+        };
+        if !includes.contains(&dir) {
+            includes.push(dir);
+        }
+    }
+    includes
+}
+
+/// Lower the authentication of an HTTP client.
+fn lower_http_auth(auth: &baobao_manifest::HttpAuth) -> HttpAuth {
+    match auth {
+        baobao_manifest::HttpAuth::Bearer { env } => HttpAuth::Bearer { env: env.clone() },
+        baobao_manifest::HttpAuth::Basic { username, env } => HttpAuth::Basic {
+            username: username.clone(),
+            env: env.clone(),
+        },
+        baobao_manifest::HttpAuth::Header { name, env } => HttpAuth::Header {
+            name: name.clone(),
+            env: env.clone(),
+        },
+    }
+}
+
 /// Lower a database context field to a DatabaseResource.
 fn lower_database_resource(name: &str, field: &ContextField) -> Option<DatabaseResource> {
-    let (db_type, env_var, pool_config, sqlite_opts) = match field {
+    let (db_type, env_var, pool_config, sqlite_opts, duckdb_opts) = match field {
         ContextField::Postgres(config) => (
             DatabaseType::Postgres,
             default_env_var(config.env(), "DATABASE_URL"),
             lower_pool_config(config.pool()),
             None,
+            None,
         ),
         ContextField::Mysql(config) => (
             DatabaseType::Mysql,
             default_env_var(config.env(), "DATABASE_URL"),
             lower_pool_config(config.pool()),
             None,
+            None,
         ),
         ContextField::Sqlite(config) => (
             DatabaseType::Sqlite,
             default_env_var(config.env.as_deref(), "DATABASE_URL"),
             lower_pool_config(&config.pool),
             Some(lower_sqlite_options(config)),
+            None,
+        ),
+        ContextField::Duckdb(config) => (
+            DatabaseType::Duckdb,
+            default_env_var(config.env.as_deref(), "DATABASE_URL"),
+            lower_pool_config(&config.pool),
+            None,
+            Some(DuckdbOptions {
+                path: config.path.clone(),
+                read_only: config.read_only,
+                threads: config.threads,
+            }),
         ),
-        ContextField::Http(_) => return None,
+        ContextField::Http(_)
+        | ContextField::Logging(_)
+        | ContextField::S3(_)
+        | ContextField::Grpc(_)
+        | ContextField::Nats(_)
+        | ContextField::Kafka(_)
+        | ContextField::Email(_)
+        | ContextField::Config(_)
+        | ContextField::Secrets(_)
+        | ContextField::Llm(_)
+        | ContextField::Cache(_)
+        | ContextField::Workdir(_) => return None,
     };
 
     Some(DatabaseResource {
@@ -102,6 +477,7 @@ fn lower_database_resource(name: &str, field: &ContextField) -> Option<DatabaseR
         env_var,
         pool: pool_config,
         sqlite: sqlite_opts,
+        duckdb: duckdb_opts,
     })
 }
 
@@ -142,41 +518,77 @@ fn lower_sqlite_options(config: &baobao_manifest::SqliteConfig) -> SqliteOptions
         }),
         busy_timeout: config.busy_timeout.map(Duration::from_millis),
         foreign_keys: config.foreign_keys,
+        migrations: config.migrations.clone(),
     }
 }
 
 /// Lower commands to operations.
-fn lower_commands(commands: &HashMap<String, Command>) -> Vec<Operation> {
+fn lower_commands(manifest: &Manifest) -> Vec<Operation> {
     // Sort commands for deterministic output
-    let mut names: Vec<_> = commands.keys().collect();
+    let mut names: Vec<_> = manifest.commands.keys().collect();
     names.sort();
 
     names
         .into_iter()
         .map(|name| {
-            let cmd = &commands[name];
-            Operation::Command(lower_command(name, cmd, vec![name.clone()]))
+            let cmd = &manifest.commands[name];
+            Operation::Command(lower_command(name, cmd, vec![name.clone()], &[], manifest))
         })
         .collect()
 }
 
 /// Lower a single command.
-fn lower_command(name: &str, cmd: &Command, path: Vec<String>) -> CommandOp {
+fn lower_command(
+    name: &str,
+    cmd: &Command,
+    path: Vec<String>,
+    inherited: &[String],
+    manifest: &Manifest,
+) -> CommandOp {
+    let spans = &manifest.spans;
+    let mut requires = inherited.to_vec();
+    for key in &cmd.requires {
+        for field in context_field_names(key, manifest) {
+            if !requires.contains(&field) {
+                requires.push(field);
+            }
+        }
+    }
+
     let mut inputs = Vec::new();
 
-    // Lower positional arguments (sorted for deterministic output)
-    let mut arg_names: Vec<_> = cmd.args.keys().collect();
-    arg_names.sort();
-    for arg_name in arg_names {
-        let arg = &cmd.args[arg_name];
+    // Lower positional arguments in command line order
+    for (arg_name, arg) in cmd.positional_args() {
         inputs.push(Input {
             name: arg_name.clone(),
             ty: lower_arg_type(&arg.arg_type),
             kind: InputKind::Positional,
-            required: arg.required,
-            default: arg.default.as_ref().and_then(lower_default_value),
-            description: arg.description.clone(),
+            required: arg.required && !arg.asks_when_missing(),
+            default: arg
+                .default
+                .as_ref()
+                .and_then(lower_default_value)
+                .map(|d| d.coerce(lower_arg_type(&arg.arg_type))),
+            description: arg.description.clone().map(Into::into),
             choices: arg.choices.clone(),
+            multiple: arg.multiple,
+            delimiter: None,
+            allow_hyphen_values: arg.allow_hyphen_values,
+            prompt: lower_input_prompt(&arg.arg_type, arg.prompt.as_ref()),
+            hint: arg.hint.map(lower_hint),
+            constraints: lower_constraints(
+                arg.min,
+                arg.max,
+                &arg.pattern,
+                arg.min_length,
+                arg.max_length,
+                arg.path_exists,
+            ),
+            requires: vec![],
+            required_if: None,
+            long_description: arg.long_description.clone(),
+            deprecated: None,
+            span: spans.arg(&path, arg_name),
         });
     }
 
@@ -185,7 +597,7 @@ fn lower_command(name: &str, cmd: &Command, path: Vec<String>) -> CommandOp {
     flag_names.sort();
     for flag_name in flag_names {
         let flag = &cmd.flags[flag_name];
-        inputs.push(lower_flag(flag_name, flag));
+        inputs.push(lower_flag(flag_name, flag, spans.flag(&path, flag_name)));
     }
 
     // Lower subcommands
@@ -197,21 +609,62 @@ fn lower_command(name: &str, cmd: &Command, path: Vec<String>) -> CommandOp {
             let child_cmd = &cmd.commands[child_name];
             let mut child_path = path.clone();
             child_path.push(child_name.clone());
-            lower_command(child_name, child_cmd, child_path)
+            lower_command(child_name, child_cmd, child_path, &requires, manifest)
         })
         .collect();
 
     CommandOp {
         name: name.into(),
+        span: spans.command(&path),
         path,
-        description: cmd.description.clone(),
+        description: cmd.description.to_string(),
+        aliases: cmd.aliases.clone(),
+        deprecated: cmd.deprecated.clone(),
         inputs,
+        groups: cmd
+            .groups
+            .iter()
+            .map(|(name, group)| InputGroup {
+                name: name.clone(),
+                members: group.members.clone(),
+                exclusive: group.exclusive,
+                required: group.required,
+            })
+            .collect(),
         children,
+        default_command: cmd.default_command.clone(),
+        exit_codes: cmd.exit_codes.clone(),
+        requires,
+        docs: Docs {
+            long_description: cmd.long_description.clone(),
+            examples: cmd.examples.iter().map(Example::new).collect(),
+            footer: cmd.footer.clone(),
+            ..Default::default()
+        },
+    }
+}
+
+/// The context fields of a `[context]` key, as named by [`lower_resources`].
+///
+/// A database with a replica has two fields, and requiring it requires both.
+fn context_field_names(key: &str, manifest: &Manifest) -> Vec<String> {
+    match key {
+        "database" => {
+            let mut fields = vec!["db".to_string()];
+            if let Some(db) = &manifest.context.database
+                && db.replica_config().is_some()
+            {
+                fields.push("db_replica".to_string());
+            }
+            fields
+        }
+        "logging" => vec!["logger".to_string()],
+        key => vec![key.to_string()],
     }
 }
 
 /// Lower a flag to an Input.
-fn lower_flag(name: &str, flag: &Flag) -> Input {
+fn lower_flag(name: &str, flag: &Flag, span: Option<SourceSpan>) -> Input {
     Input {
         name: name.into(),
         ty: lower_arg_type(&flag.flag_type),
@@ -219,23 +672,167 @@ fn lower_flag(name: &str, flag: &Flag) -> Input {
             short: flag.short.as_ref().map(|s| *s.get_ref()),
         },
         required: false,
-        default: flag.default.as_ref().and_then(lower_default_value),
-        description: flag.description.clone(),
+        default: flag
+            .default
+            .as_ref()
+            .and_then(lower_default_value)
+            .map(|d| d.coerce(lower_arg_type(&flag.flag_type))),
+        description: flag.description.clone().map(Into::into),
+        long_description: flag.long_description.clone(),
         choices: flag.choices.clone(),
+        multiple: flag.multiple,
+        delimiter: flag.delimiter,
+        allow_hyphen_values: false,
+        prompt: lower_input_prompt(&flag.flag_type, flag.prompt.as_ref()),
+        hint: flag.hint.map(lower_hint),
+        constraints: lower_constraints(
+            flag.min,
+            flag.max,
+            &flag.pattern,
+            flag.min_length,
+            flag.max_length,
+            flag.path_exists,
+        ),
+        requires: flag.requires.clone(),
+        required_if: flag.required_if.as_ref().map(|required_if| RequiredIf {
+            flag: required_if.flag.clone(),
+            value: required_if.value.clone(),
+        }),
+        deprecated: flag.deprecated.clone(),
+        span,
     }
 }
 
-/// Lower argument type.
-fn lower_arg_type(ty: &ArgType) -> InputType {
+/// Lower an input's value constraints.
+fn lower_constraints(
+    min: Option<f64>,
+    max: Option<f64>,
+    pattern: &Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    path_exists: bool,
+) -> InputConstraints {
+    InputConstraints {
+        min,
+        max,
+        pattern: pattern.clone(),
+        min_length,
+        max_length,
+        path_exists,
+    }
+}
+
+/// Lower routes to operations, in name order.
+fn lower_routes(routes: &BTreeMap<String, Route>, spans: &SourceMap) -> Vec<Operation> {
+    routes
+        .iter()
+        .map(|(name, route)| Operation::Route(lower_route(name, route, spans)))
+        .collect()
+}
+
+/// Lower a single route.
+fn lower_route(name: &str, route: &Route, spans: &SourceMap) -> RouteOp {
+    let lower = |param_name: &str, p: &Param, kind: InputKind| Input {
+        span: spans.param(name, param_name),
+        ..lower_param(param_name, p, kind)
+    };
+    // Path parameters in path order, then query parameters in name order
+    let mut inputs: Vec<_> = route
+        .path_params()
+        .into_iter()
+        .filter_map(|param| {
+            let (name, p) = route.params.get_key_value(param)?;
+            Some(lower(name, p, InputKind::Positional))
+        })
+        .collect();
+    inputs.extend(
+        route
+            .params
+            .iter()
+            .filter(|(name, _)| !route.is_path_param(name))
+            .map(|(name, p)| lower(name, p, InputKind::Flag { short: None })),
+    );
+
+    RouteOp {
+        name: name.into(),
+        method: match route.method {
+            baobao_manifest::HttpMethod::Get => HttpMethod::Get,
+            baobao_manifest::HttpMethod::Post => HttpMethod::Post,
+            baobao_manifest::HttpMethod::Put => HttpMethod::Put,
+            baobao_manifest::HttpMethod::Patch => HttpMethod::Patch,
+            baobao_manifest::HttpMethod::Delete => HttpMethod::Delete,
+            baobao_manifest::HttpMethod::Head => HttpMethod::Head,
+            baobao_manifest::HttpMethod::Options => HttpMethod::Options,
+        },
+        path: route.path.clone(),
+        description: route.description.clone(),
+        inputs,
+        span: spans.route(name),
+    }
+}
+
+/// Lower a route parameter. Path parameters are always required.
+fn lower_param(name: &str, param: &Param, kind: InputKind) -> Input {
+    Input {
+        name: name.into(),
+        ty: lower_arg_type(&param.param_type),
+        required: param.required || kind == InputKind::Positional,
+        kind,
+        default: param
+            .default
+            .as_ref()
+            .and_then(lower_default_value)
+            .map(|d| d.coerce(lower_arg_type(&param.param_type))),
+        description: param.description.clone(),
+        choices: param.choices.clone(),
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        hint: None,
+        constraints: Default::default(),
+        requires: vec![],
+        required_if: None,
+        long_description: None,
+        deprecated: None,
+        span: None,
+    }
+}
+
+/// Lower the prompt of an input. Secrets always prompt without echoing when
+/// not given, so the parser treats them as optional.
+fn lower_input_prompt(ty: &ArgType, prompt: Option<&PromptKind>) -> Option<InputPrompt> {
     match ty {
-        ArgType::String => InputType::String,
-        ArgType::Int => InputType::Int,
-        ArgType::Float => InputType::Float,
-        ArgType::Bool => InputType::Bool,
-        ArgType::Path => InputType::Path,
+        ArgType::Secret => Some(InputPrompt::Secret),
+        _ => prompt.map(lower_prompt),
+    }
+}
+
+/// Lower an interactive prompt kind.
+fn lower_prompt(prompt: &PromptKind) -> InputPrompt {
+    match prompt {
+        PromptKind::Confirm => InputPrompt::Confirm,
+        PromptKind::Secret => InputPrompt::Secret,
+        PromptKind::Text(message) => InputPrompt::Text(message.clone()),
+    }
+}
+
+/// Lower a shell completion hint.
+fn lower_hint(hint: HintKind) -> InputHint {
+    match hint {
+        HintKind::File => InputHint::File,
+        HintKind::Dir => InputHint::Dir,
+        HintKind::Hostname => InputHint::Hostname,
+        HintKind::Url => InputHint::Url,
+        HintKind::Command => InputHint::Command,
     }
 }
 
+/// Lower argument type.
+fn lower_arg_type(ty: &ArgType) -> InputType {
+    (*ty).into()
+}
+
 /// Lower a TOML value to a DefaultValue.
 fn lower_default_value(value: &toml::Value) -> Option<DefaultValue> {
     match value {
@@ -243,7 +840,17 @@ fn lower_default_value(value: &toml::Value) -> Option<DefaultValue> {
         toml::Value::Integer(i) => Some(DefaultValue::Int(*i)),
         toml::Value::Float(f) => Some(DefaultValue::Float(*f)),
         toml::Value::Boolean(b) => Some(DefaultValue::Bool(*b)),
-        _ => None, // Arrays, tables, etc. are not supported as defaults
+        toml::Value::Array(items) => items
+            .iter()
+            .map(lower_default_value)
+            .collect::<Option<_>>()
+            .map(DefaultValue::List),
+        toml::Value::Table(table) => table
+            .iter()
+            .map(|(k, v)| lower_default_value(v).map(|v| (k.clone(), v)))
+            .collect::<Option<_>>()
+            .map(DefaultValue::Table),
+        toml::Value::Datetime(_) => None, // Datetimes are not supported as defaults
     }
 }
 
@@ -286,27 +893,1058 @@ mod tests {
     }
 
     #[test]
-    fn test_lower_arg_type() {
-        assert_eq!(lower_arg_type(&ArgType::String), InputType::String);
-        assert_eq!(lower_arg_type(&ArgType::Int), InputType::Int);
-        assert_eq!(lower_arg_type(&ArgType::Float), InputType::Float);
-        assert_eq!(lower_arg_type(&ArgType::Bool), InputType::Bool);
-        assert_eq!(lower_arg_type(&ArgType::Path), InputType::Path);
+    fn test_lower_spans() {
+        let manifest: Manifest = r#"[cli]
+name = "test"
+language = "rust"
+
+[commands.db]
+description = "Database"
+
+[commands.db.commands.migrate]
+description = "Migrate"
+
+[[commands.db.commands.migrate.flags]]
+name = "dry_run"
+type = "bool"
+
+[routes.get_user]
+method = "GET"
+path = "/users/{id}"
+params = { id = { type = "int" } }
+"#
+        .parse()
+        .unwrap();
+        let ir = lower_manifest(&manifest);
+
+        let migrate = &ir.commands().next().unwrap().children[0];
+        let span = migrate.span.as_ref().unwrap();
+        assert_eq!(span.to_string(), "bao.toml:8:23");
+        assert_eq!(migrate.inputs[0].span.as_ref().unwrap().line, 12);
+
+        let route = ir.routes().next().unwrap();
+        assert_eq!(route.span.as_ref().unwrap().line, 15);
+        assert!(route.inputs[0].span.is_some());
     }
 
     #[test]
-    fn test_lower_default_value() {
+    fn test_lower_constraints() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.greet]
+            description = "Greet"
+
+            [commands.greet.args.name]
+            type = "string"
+            pattern = "[a-z]+"
+            min_length = 2
+            max_length = 16
+
+            [commands.greet.args.config]
+            type = "path"
+            path_exists = true
+
+            [commands.greet.flags.times]
+            type = "int"
+            min = 1
+            max = 5
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let inputs = &ir.commands().next().unwrap().inputs;
+        assert!(inputs[0].constraints.path_exists);
+        assert_eq!(inputs[1].constraints.pattern.as_deref(), Some("[a-z]+"));
+        assert_eq!(inputs[1].constraints.min_length, Some(2));
+        assert_eq!(inputs[1].constraints.max_length, Some(16));
+        assert_eq!(inputs[2].constraints.min, Some(1.0));
+        assert_eq!(inputs[2].constraints.max, Some(5.0));
+        assert!(!inputs[2].constraints.has_length());
+    }
+
+    #[test]
+    fn test_lower_groups() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.build]
+            description = "Build"
+
+            [commands.build.flags.json]
+            type = "bool"
+
+            [commands.build.flags.yaml]
+            type = "bool"
+
+            [commands.build.groups.output]
+            members = ["json", "yaml"]
+            exclusive = true
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let groups = &ir.commands().next().unwrap().groups;
         assert_eq!(
-            lower_default_value(&toml::Value::String("hello".into())),
-            Some(DefaultValue::String("hello".into()))
+            groups,
+            &[InputGroup {
+                name: "output".into(),
+                members: vec!["json".into(), "yaml".into()],
+                exclusive: true,
+                required: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lower_aliases() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.list]
+            description = "List"
+            aliases = ["ls", "l"]
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        assert_eq!(ir.commands().next().unwrap().aliases, ["ls", "l"]);
+    }
+
+    #[test]
+    fn test_lower_deprecated() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.old]
+            description = "Old"
+            deprecated = "use `new` instead"
+
+            [commands.old.flags.legacy]
+            type = "bool"
+            deprecated = "has no effect"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let old = ir.commands().next().unwrap();
+        assert_eq!(old.deprecated.as_deref(), Some("use `new` instead"));
+        assert_eq!(old.inputs[0].deprecated.as_deref(), Some("has no effect"));
+    }
+
+    #[test]
+    fn test_lower_examples() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+            examples = ["test deploy prod --force"]
+            "#,
         );
+        let ir = lower_manifest(&manifest);
+
+        let deploy = ir.commands().next().unwrap();
         assert_eq!(
-            lower_default_value(&toml::Value::Integer(42)),
-            Some(DefaultValue::Int(42))
+            deploy.docs.examples,
+            [Example::new("test deploy prod --force")]
         );
+    }
+
+    #[test]
+    fn test_lower_footers() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            footer = "Docs: https://example.com"
+
+            [commands.deploy]
+            description = "Deploy"
+            footer = "Report issues to ops@example.com"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
         assert_eq!(
-            lower_default_value(&toml::Value::Boolean(true)),
-            Some(DefaultValue::Bool(true))
+            ir.meta.docs.footer.as_deref(),
+            Some("Docs: https://example.com")
+        );
+        let deploy = ir.commands().next().unwrap();
+        assert_eq!(
+            deploy.docs.footer.as_deref(),
+            Some("Report issues to ops@example.com")
+        );
+    }
+
+    #[test]
+    fn test_lower_default_commands() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            default_command = "db"
+
+            [commands.db]
+            description = "Database"
+            default_command = "status"
+
+            [commands.db.commands.status]
+            description = "Show status"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        assert_eq!(ir.meta.default_command.as_deref(), Some("db"));
+        let db = ir.commands().next().unwrap();
+        assert_eq!(db.default_command.as_deref(), Some("status"));
+        assert_eq!(db.children[0].default_command, None);
+    }
+
+    #[test]
+    fn test_lower_allow_hyphen_values() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.seek]
+            description = "Seek"
+
+            [commands.seek.args.offset]
+            type = "int"
+            allow_hyphen_values = true
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let cmd = ir.commands().next().unwrap();
+        assert!(cmd.inputs[0].allow_hyphen_values);
+    }
+
+    #[test]
+    fn test_lower_secret() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.login]
+            description = "Log in"
+
+            [commands.login.args.password]
+            type = "secret"
+
+            [commands.login.flags.token]
+            type = "secret"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let cmd = ir.commands().next().unwrap();
+        for input in &cmd.inputs {
+            assert_eq!(input.ty, InputType::Secret);
+            assert_eq!(input.prompt, Some(InputPrompt::Secret));
+            assert!(!input.required);
+        }
+    }
+
+    #[test]
+    fn test_lower_prompt_message() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.greet]
+            description = "Greet someone"
+
+            [commands.greet.args.name]
+            type = "string"
+            prompt = "What is your name?"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let cmd = ir.commands().next().unwrap();
+        let input = &cmd.inputs[0];
+        assert!(!input.required);
+        assert_eq!(input.prompt_message(), Some("What is your name?"));
+        assert!(input.asks_when_missing());
+
+        let no_input = ir
+            .global_inputs
+            .iter()
+            .find(|g| g.name == NO_INPUT_FLAG)
+            .unwrap();
+        assert_eq!(no_input.ty, InputType::Bool);
+    }
+
+    #[test]
+    fn test_lower_positional_order() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.copy]
+            description = "Copy"
+
+            [commands.copy.flags.force]
+            type = "bool"
+
+            [commands.copy.args.source]
+            type = "path"
+            index = 1
+
+            [commands.copy.args.dest]
+            type = "path"
+            index = 2
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let copy = ir.commands().next().unwrap();
+        let names: Vec<_> = copy.inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["source", "dest", "force"]);
+    }
+
+    #[test]
+    fn test_lower_long_descriptions() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+            long_description = "Builds and uploads the current project."
+
+            [commands.deploy.args.target]
+            type = "string"
+            long_description = "Name of an environment from deploy.toml."
+
+            [commands.deploy.flags.force]
+            type = "bool"
+            long_description = "Skips the confirmation prompt."
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let deploy = ir.commands().next().unwrap();
+        assert_eq!(
+            deploy.docs.long_description.as_deref(),
+            Some("Builds and uploads the current project.")
+        );
+        let long = |name: &str| {
+            deploy
+                .inputs
+                .iter()
+                .find(|i| i.name == name)
+                .and_then(|i| i.long_description.as_deref())
+        };
+        assert_eq!(
+            long("target"),
+            Some("Name of an environment from deploy.toml.")
+        );
+        assert_eq!(long("force"), Some("Skips the confirmation prompt."));
+    }
+
+    #[test]
+    fn test_lower_profile() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "postgres"
+            max_connections = 20
+
+            [profile.dev]
+            cli = { name = "test-dev" }
+            context = { database = { max_connections = 2 } }
+            "#,
+        );
+        let mut ctx = CompilationContext::new(manifest);
+        ctx.profile = Some("dev".into());
+        LowerPhase.run(&mut ctx).unwrap();
+
+        let ir = ctx.ir.take().unwrap();
+        assert_eq!(ir.meta.name, "test-dev");
+        let Resource::Database(db) = &ir.resources[0] else {
+            panic!("expected a database resource");
+        };
+        assert_eq!(db.pool.max_connections, Some(2));
+
+        ctx.profile = Some("staging".into());
+        let err = LowerPhase.run(&mut ctx).unwrap_err();
+        assert!(err.to_string().contains("unknown profile 'staging'"));
+    }
+
+    #[test]
+    fn test_lower_duckdb() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "duckdb"
+            path = "analytics.duckdb"
+            read_only = true
+            threads = 4
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let Resource::Database(db) = &ir.resources[0] else {
+            panic!("expected a database resource");
+        };
+        assert_eq!(db.db_type, DatabaseType::Duckdb);
+        assert_eq!(db.env_var, "DATABASE_URL");
+        assert_eq!(db.sqlite, None);
+        assert_eq!(
+            db.duckdb,
+            Some(DuckdbOptions {
+                path: Some("analytics.duckdb".into()),
+                read_only: Some(true),
+                threads: Some(4),
+            })
+        );
+    }
+
+    #[test]
+    fn test_lower_requires() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "sqlite"
+
+            [context.logging]
+
+            [context.cache]
+
+            [commands.db]
+            description = "Database commands"
+            requires = ["database", "logging"]
+
+            [commands.db.commands.seed]
+            description = "Seed the database"
+            requires = ["cache", "database"]
+
+            [commands.hello]
+            description = "Say hello"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        assert!(ir.has_requirements());
+        let mut commands = ir.commands();
+        let db = commands.next().unwrap();
+        assert_eq!(db.requires, ["db", "logger"]);
+        assert_eq!(db.children[0].requires, ["db", "logger", "cache"]);
+        assert!(commands.next().unwrap().requires.is_empty());
+    }
+
+    #[test]
+    fn test_lower_replica() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "postgres"
+            max_connections = 5
+
+            [context.database.replica]
+            max_connections = 20
+
+            [commands.report]
+            description = "Build a report"
+            requires = ["database"]
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let [Resource::Database(primary), Resource::Database(replica)] = &ir.resources[..] else {
+            panic!("expected a primary and a replica");
+        };
+        assert_eq!(primary.name, "db");
+        assert_eq!(primary.env_var, "DATABASE_URL");
+        assert_eq!(primary.pool.max_connections, Some(5));
+        assert_eq!(replica.name, "db_replica");
+        assert_eq!(replica.db_type, DatabaseType::Postgres);
+        assert_eq!(replica.env_var, "DATABASE_REPLICA_URL");
+        assert_eq!(replica.pool.max_connections, Some(20));
+        assert_eq!(ir.commands().next().unwrap().requires, ["db", "db_replica"]);
+    }
+
+    #[test]
+    fn test_lower_storage() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.storage]
+            type = "s3"
+            bucket = "uploads"
+            region = "eu-west-1"
+
+            [profile.dev]
+            context = { storage = { bucket = "uploads-dev", endpoint = "http://localhost:9000" } }
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let storage = ir.storage().unwrap();
+        assert_eq!(storage.name, "storage");
+        assert_eq!(storage.s3.bucket, "uploads");
+        assert_eq!(storage.s3.region.as_deref(), Some("eu-west-1"));
+        assert_eq!(storage.s3.endpoint, None);
+
+        let mut ctx = CompilationContext::new(manifest);
+        ctx.profile = Some("dev".into());
+        LowerPhase.run(&mut ctx).unwrap();
+        let ir = ctx.ir.take().unwrap();
+        let storage = ir.storage().unwrap();
+        assert_eq!(storage.s3.bucket, "uploads-dev");
+        assert_eq!(storage.s3.region.as_deref(), Some("eu-west-1"));
+        assert!(storage.s3.allows_http());
+    }
+
+    #[test]
+    fn test_lower_named_http_clients() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http.github]
+            base_url = "https://api.github.com"
+            timeout = 30
+            headers = { Accept = "application/vnd.github+json" }
+            retries = 3
+            backoff_ms = 200
+            auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+            [context.http.internal]
+            base_url = "http://localhost:8080"
+
+            [profile.dev.context.http.internal]
+            base_url = "http://localhost:9090"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let clients: Vec<_> = ir.http_clients().collect();
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].name, "github");
+        assert_eq!(clients[0].options.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(
+            clients[0].options.headers["Accept"],
+            "application/vnd.github+json"
+        );
+        assert_eq!(clients[0].options.retries, 3);
+        assert_eq!(clients[0].options.backoff, Some(Duration::from_millis(200)));
+        assert_eq!(
+            clients[0].options.auth,
+            Some(HttpAuth::Bearer {
+                env: "GITHUB_TOKEN".into()
+            })
+        );
+        assert_eq!(clients[1].name, "internal");
+        assert!(!clients[1].options.has_retries());
+
+        let mut ctx = CompilationContext::new(manifest);
+        ctx.profile = Some("dev".into());
+        LowerPhase.run(&mut ctx).unwrap();
+        let ir = ctx.ir.take().unwrap();
+        let internal = ir.http_clients().find(|c| c.name == "internal").unwrap();
+        assert_eq!(
+            internal.options.base_url.as_deref(),
+            Some("http://localhost:9090")
+        );
+    }
+
+    #[test]
+    fn test_lower_grpc() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.grpc]
+            endpoint = "http://localhost:50051"
+            protos = ["proto/greeter.proto", "proto/admin.proto", "health.proto"]
+            timeout = 5
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let grpc = ir.grpc().unwrap();
+        assert_eq!(grpc.name, "grpc");
+        assert_eq!(grpc.options.endpoint, "http://localhost:50051");
+        assert_eq!(grpc.options.includes, ["proto", "."]);
+        assert_eq!(grpc.options.timeout, Some(Duration::from_secs(5)));
+        assert!(ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_logging() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.logging]
+            level = "warn"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let logger = ir.logger().unwrap();
+        assert_eq!(logger.default_filter(), "warn");
+
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.logging]
+            level = "warn"
+            env_filter = "info,test::db=debug"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let logger = ir.logger().unwrap();
+        assert_eq!(logger.default_filter(), "info,test::db=debug");
+    }
+
+    #[test]
+    fn test_lower_email() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.email]
+            host = "smtp.example.com"
+            tls = "tls"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let email = ir.email().unwrap();
+        assert_eq!(email.name, "email");
+        assert_eq!(email.options.port, 465);
+        assert_eq!(email.options.tls, SmtpTls::Tls);
+        assert_eq!(email.options.credentials(), None);
+        assert!(ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_config() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.config]
+            path = "settings.yml"
+            fields = { api_url = "string", retries = { type = "int", optional = true } }
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let config = ir.config().unwrap();
+        assert_eq!(config.name, "config");
+        assert_eq!(config.options.format, ConfigFormat::Yaml);
+        assert_eq!(
+            config.options.fields["retries"],
+            ConfigField {
+                field_type: ConfigFieldType::Int,
+                optional: true,
+            }
+        );
+        assert!(!ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_cache() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "acme"
+            language = "rust"
+
+            [context.cache]
+            ttl = 300
+            mode = "disk"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let cache = ir.cache().unwrap();
+        assert_eq!(cache.name, "cache");
+        assert_eq!(
+            cache.options,
+            CacheOptions {
+                max_capacity: 1000,
+                ttl: Some(Duration::from_secs(300)),
+                mode: CacheMode::Disk,
+                path: None,
+                namespace: "acme".into(),
+            }
+        );
+        assert!(ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_workdir() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "acme"
+            language = "rust"
+
+            [context.workdir]
+            temp = true
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let workdir = ir.workdir().unwrap();
+        assert_eq!(workdir.name, "workdir");
+        assert_eq!(
+            workdir.options,
+            WorkdirOptions {
+                data: true,
+                temp: true,
+                path: None,
+                namespace: "acme".into(),
+            }
+        );
+        assert!(!ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_llm() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.llm]
+            provider = "openai"
+            model = "gpt-4o"
+            base_url = "http://localhost:11434/v1/"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let llm = ir.llm().unwrap();
+        assert_eq!(llm.name, "llm");
+        assert_eq!(
+            llm.options,
+            LlmOptions {
+                provider: LlmProvider::Openai,
+                model: "gpt-4o".into(),
+                api_key_env: "OPENAI_API_KEY".into(),
+                base_url: "http://localhost:11434/v1".into(),
+                max_tokens: None,
+            }
+        );
+        assert!(ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_secrets() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "acme"
+            language = "rust"
+
+            [context.secrets]
+            backend = "keyring"
+            prefix = "ACME_"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let secrets = ir.secrets().unwrap();
+        assert_eq!(secrets.name, "secrets");
+        assert_eq!(
+            secrets.options,
+            SecretsOptions {
+                backend: SecretsBackend::Keyring,
+                service: "acme".into(),
+                path: ".secrets".into(),
+                prefix: "ACME_".into(),
+            }
+        );
+        assert!(!ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_queue() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.queue]
+            type = "kafka"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+        let queue = ir.queue().unwrap();
+        assert_eq!(queue.name, "queue");
+        assert_eq!(queue.queue_type, QueueType::Kafka);
+        assert_eq!(queue.env_var, "KAFKA_BROKERS");
+        assert!(ir.has_async());
+    }
+
+    #[test]
+    fn test_lower_hints() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.ssh]
+            description = "Connect to a host"
+
+            [commands.ssh.args.host]
+            type = "string"
+            hint = "hostname"
+
+            [commands.ssh.flags.identity]
+            type = "path"
+            hint = "file"
+
+            [commands.ssh.flags.port]
+            type = "int"
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let ssh = ir.commands().next().unwrap();
+        let hint = |name: &str| ssh.inputs.iter().find(|i| i.name == name).unwrap().hint;
+        assert_eq!(hint("host"), Some(InputHint::Hostname));
+        assert_eq!(hint("identity"), Some(InputHint::File));
+        assert_eq!(hint("port"), None);
+    }
+
+    #[test]
+    fn test_lower_requirements() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+
+            [commands.deploy.flags.mode]
+            type = "string"
+
+            [commands.deploy.flags.host]
+            type = "string"
+            requires = ["mode"]
+            required_if = { flag = "mode", value = "remote" }
+            "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        let inputs = &ir.commands().next().unwrap().inputs;
+        assert_eq!(inputs[0].requires, ["mode"]);
+        assert_eq!(
+            inputs[0].required_if,
+            Some(RequiredIf {
+                flag: "mode".into(),
+                value: "remote".into(),
+            })
+        );
+        assert!(inputs[1].requires.is_empty());
+    }
+
+    #[test]
+    fn test_lower_global_flags() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.verbose]
+            type = "bool"
+            short = "v"
+
+            [flags.config]
+            type = "path"
+
+            [commands.build]
+            description = "Build"
+            "#
+        .parse()
+        .unwrap();
+        let ir = lower_manifest(&manifest);
+
+        let names: Vec<_> = ir.global_inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["config", "verbose"]);
+        assert!(matches!(
+            ir.global_inputs[1].kind,
+            InputKind::Flag { short: Some('v') }
+        ));
+        assert!(ir.global_inputs[1].span.is_some());
+        assert!(ir.commands().next().unwrap().inputs.is_empty());
+    }
+
+    #[test]
+    fn test_lower_routes() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.hello]
+            description = "Say hello"
+
+            [routes.get_post]
+            method = "GET"
+            path = "/users/{user}/posts/{post}"
+
+            [routes.get_post.params]
+            post = { type = "int" }
+            user = { type = "string" }
+            verbose = { type = "bool" }
+        "#,
+        );
+        let ir = lower_manifest(&manifest);
+
+        assert_eq!(ir.commands().count(), 1);
+        let route = ir.routes().next().unwrap();
+        assert_eq!(route.method, HttpMethod::Get);
+
+        let path: Vec<_> = route.path_params().map(|i| i.name.as_str()).collect();
+        assert_eq!(path, vec!["user", "post"]);
+        assert!(route.path_params().all(|i| i.required));
+
+        let query: Vec<_> = route.query_params().collect();
+        assert_eq!(query.len(), 1);
+        assert_eq!(query[0].name, "verbose");
+        assert!(!query[0].required);
+    }
+
+    #[test]
+    fn test_lower_locales() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            description = { en = "Test", fr = "Essai" }
+
+            [commands.hello]
+            description = { en = "Say hello", de = "Hallo sagen" }
+
+            [commands.hello.flags.loud]
+            description = { en = "Shout", fr = "Crier" }
+        "#
+        .parse()
+        .unwrap();
+        let ir = lower_manifest(&manifest);
+
+        assert_eq!(ir.meta.description.as_deref(), Some("Test"));
+        let names: Vec<_> = ir.locales.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["de", "fr"]);
+        assert_eq!(ir.locales[0].messages[0].command, vec!["hello"]);
+        let loud = ir.locales[1]
+            .messages
+            .iter()
+            .find(|m| m.input.as_deref() == Some("loud"))
+            .unwrap();
+        assert_eq!(loud.text, "Crier");
+    }
+
+    #[test]
+    fn test_lower_arg_type() {
+        assert_eq!(lower_arg_type(&ArgType::String), InputType::String);
+        assert_eq!(lower_arg_type(&ArgType::Int), InputType::Int);
+        assert_eq!(lower_arg_type(&ArgType::Float), InputType::Float);
+        assert_eq!(lower_arg_type(&ArgType::Bool), InputType::Bool);
+        assert_eq!(lower_arg_type(&ArgType::Path), InputType::Path);
+    }
+
+    #[test]
+    fn test_lower_default_value() {
+        assert_eq!(
+            lower_default_value(&toml::Value::String("hello".into())),
+            Some(DefaultValue::String("hello".into()))
+        );
+        assert_eq!(
+            lower_default_value(&toml::Value::Integer(42)),
+            Some(DefaultValue::Int(42))
+        );
+        assert_eq!(
+            lower_default_value(&toml::Value::Boolean(true)),
+            Some(DefaultValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_lower_default_value_array_and_table() {
+        let value: toml::Table = toml::from_str(
+            r#"
+            list = ["a", "b"]
+            env = { region = "eu" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            lower_default_value(&value["list"]),
+            Some(DefaultValue::List(vec![
+                DefaultValue::String("a".into()),
+                DefaultValue::String("b".into()),
+            ]))
+        );
+        assert_eq!(
+            lower_default_value(&value["env"]),
+            Some(DefaultValue::Table(vec![(
+                "region".into(),
+                DefaultValue::String("eu".into())
+            )]))
         );
     }
 
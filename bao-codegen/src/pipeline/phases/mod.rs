@@ -4,14 +4,18 @@
 //!
 //! - [`ValidatePhase`] - validates the manifest and collects diagnostics
 //! - [`LowerPhase`] - transforms manifest to Application IR
+//! - [`CanonicalizePhase`] - normalizes the IR for generators
 //! - [`AnalyzePhase`] - computes shared data from IR
 
 mod analyze;
+mod canonicalize;
 mod lower;
 mod validate;
 
 pub use analyze::AnalyzePhase;
+pub use canonicalize::CanonicalizePhase;
 pub use lower::LowerPhase;
 pub use validate::{
-    CommandNamingLint, DuplicateCommandLint, EmptyDescriptionLint, Lint, LintInfo, ValidatePhase,
+    CommandNamingLint, DefaultTypeLint, DeprecatedLint, DuplicateCommandLint, EmptyDescriptionLint,
+    Lint, LintInfo, ValidatePhase,
 };
@@ -22,8 +22,9 @@ impl Lint for CommandNamingLint {
 
     fn check(&self, manifest: &Manifest, diagnostics: &mut Vec<Diagnostic>) {
         for (name, cmd) in &manifest.commands {
-            check_command_name(name, name, diagnostics);
-            check_subcommand_names(name, cmd, diagnostics);
+            let path = std::slice::from_ref(name);
+            check_command_name(manifest, path, diagnostics);
+            check_subcommand_names(manifest, path, cmd, diagnostics);
         }
     }
 }
@@ -56,7 +57,8 @@ fn is_kebab_case(s: &str) -> bool {
     !prev_hyphen
 }
 
-fn check_command_name(name: &str, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+fn check_command_name(manifest: &Manifest, path: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    let name = &path[path.len() - 1];
     if !is_kebab_case(name) {
         diagnostics.push(
             Diagnostic::warning(
@@ -66,20 +68,23 @@ fn check_command_name(name: &str, path: &str, diagnostics: &mut Vec<Diagnostic>)
                     name
                 ),
             )
-            .at(format!("commands.{}", path)),
+            .at(format!("commands.{}", path.join(".")))
+            .with_span(manifest.spans.command(path)),
         );
     }
 }
 
 fn check_subcommand_names(
-    parent_path: &str,
+    manifest: &Manifest,
+    parent_path: &[String],
     cmd: &baobao_manifest::Command,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
     for (name, subcmd) in &cmd.commands {
-        let path = format!("{}.{}", parent_path, name);
-        check_command_name(name, &path, diagnostics);
-        check_subcommand_names(&path, subcmd, diagnostics);
+        let mut path = parent_path.to_vec();
+        path.push(name.clone());
+        check_command_name(manifest, &path, diagnostics);
+        check_subcommand_names(manifest, &path, subcmd, diagnostics);
     }
 }
 
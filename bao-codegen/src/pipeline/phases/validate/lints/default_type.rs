@@ -0,0 +1,156 @@
+//! Lint for defaults that don't match their input's type.
+
+use std::collections::HashMap;
+
+use baobao_manifest::{Command, Flag, Manifest};
+
+use super::super::Lint;
+use crate::pipeline::Diagnostic;
+
+/// Lint that errors on argument and flag defaults of the wrong type.
+///
+/// Parsing already rejects them; this catches manifests built or edited in
+/// code, which would otherwise generate code that doesn't compile.
+pub struct DefaultTypeLint;
+
+impl Lint for DefaultTypeLint {
+    fn name(&self) -> &'static str {
+        "default-type"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detect defaults that don't match their argument or flag type"
+    }
+
+    fn check(&self, manifest: &Manifest, diagnostics: &mut Vec<Diagnostic>) {
+        check_flags(manifest, None, &manifest.flags, diagnostics);
+        for (name, cmd) in &manifest.commands {
+            check_command(manifest, std::slice::from_ref(name), cmd, diagnostics);
+        }
+    }
+}
+
+fn check_command(
+    manifest: &Manifest,
+    path: &[String],
+    cmd: &Command,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let dotted = path.join(".");
+    for (name, arg) in &cmd.args {
+        if let (Some(default), Some(mismatch)) = (&arg.default, arg.default_mismatch()) {
+            diagnostics.push(
+                Diagnostic::error(
+                    "validate",
+                    format!(
+                        "default {} of argument '{}' in '{}' {}",
+                        default, name, dotted, mismatch
+                    ),
+                )
+                .at(format!("commands.{}.args.{}", dotted, name))
+                .with_span(manifest.spans.arg(path, name)),
+            );
+        }
+    }
+    check_flags(manifest, Some(path), &cmd.flags, diagnostics);
+    for (name, subcmd) in &cmd.commands {
+        let mut path = path.to_vec();
+        path.push(name.clone());
+        check_command(manifest, &path, subcmd, diagnostics);
+    }
+}
+
+/// Check the flags of the command at `path`, or the global flags.
+fn check_flags(
+    manifest: &Manifest,
+    path: Option<&[String]>,
+    flags: &HashMap<String, Flag>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (name, flag) in flags {
+        let (Some(default), Some(mismatch)) = (&flag.default, flag.default_mismatch()) else {
+            continue;
+        };
+        let (message, location, span) = match path {
+            Some(path) => {
+                let dotted = path.join(".");
+                (
+                    format!(
+                        "default {} of flag '{}' in '{}' {}",
+                        default, name, dotted, mismatch
+                    ),
+                    format!("commands.{}.flags.{}", dotted, name),
+                    manifest.spans.flag(path, name),
+                )
+            }
+            None => (
+                format!("default {} of global flag '{}' {}", default, name, mismatch),
+                format!("flags.{}", name),
+                manifest.spans.global_flag(name),
+            ),
+        };
+        diagnostics.push(
+            Diagnostic::error("validate", message)
+                .at(location)
+                .with_span(span),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Severity;
+
+    fn parse_manifest(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse test manifest")
+    }
+
+    #[test]
+    fn test_default_type() {
+        let mut manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.retries]
+            type = "int"
+            default = 3
+
+            [commands.seek]
+            description = "Move the cursor"
+
+            [commands.seek.args.offset]
+            type = "int"
+            default = 0
+
+            [commands.seek.flags.unit]
+            type = "string"
+            default = "px"
+        "#,
+        );
+        let mut diagnostics = Vec::new();
+        DefaultTypeLint.check(&manifest, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+
+        let seek = manifest.commands.get_mut("seek").unwrap();
+        seek.args.get_mut("offset").unwrap().default = Some("abc".into());
+        seek.flags.get_mut("unit").unwrap().default = Some(toml::Value::Array(vec![]));
+        manifest.flags.get_mut("retries").unwrap().default = Some(true.into());
+
+        DefaultTypeLint.check(&manifest, &mut diagnostics);
+        let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        messages.sort();
+
+        assert_eq!(
+            messages,
+            [
+                "default \"abc\" of argument 'offset' in 'seek' is not an integer",
+                "default [] of flag 'unit' in 'seek' is a list, but 'multiple' is not set",
+                "default true of global flag 'retries' is not an integer",
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    }
+}
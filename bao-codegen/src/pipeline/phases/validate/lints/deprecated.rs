@@ -0,0 +1,110 @@
+//! Lint listing deprecated commands and flags.
+
+use baobao_manifest::{Command, Manifest};
+
+use super::super::Lint;
+use crate::pipeline::Diagnostic;
+
+/// Lint that reports commands and flags still marked as deprecated.
+pub struct DeprecatedLint;
+
+impl Lint for DeprecatedLint {
+    fn name(&self) -> &'static str {
+        "deprecated"
+    }
+
+    fn description(&self) -> &'static str {
+        "List deprecated commands and flags still in the manifest"
+    }
+
+    fn check(&self, manifest: &Manifest, diagnostics: &mut Vec<Diagnostic>) {
+        for (name, cmd) in &manifest.commands {
+            check_command(manifest, std::slice::from_ref(name), cmd, diagnostics);
+        }
+    }
+}
+
+fn check_command(
+    manifest: &Manifest,
+    path: &[String],
+    cmd: &Command,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let dotted = path.join(".");
+    if let Some(message) = &cmd.deprecated {
+        diagnostics.push(
+            Diagnostic::info(
+                "validate",
+                format!("command '{}' is deprecated: {}", dotted, message),
+            )
+            .at(format!("commands.{}", dotted))
+            .with_span(manifest.spans.command(path)),
+        );
+    }
+    for (name, flag) in &cmd.flags {
+        if let Some(message) = &flag.deprecated {
+            diagnostics.push(
+                Diagnostic::info(
+                    "validate",
+                    format!("flag '{}' of '{}' is deprecated: {}", name, dotted, message),
+                )
+                .at(format!("commands.{}.flags.{}", dotted, name))
+                .with_span(manifest.spans.flag(path, name)),
+            );
+        }
+    }
+    for (name, subcmd) in &cmd.commands {
+        let mut path = path.to_vec();
+        path.push(name.clone());
+        check_command(manifest, &path, subcmd, diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Severity;
+
+    fn parse_manifest(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse test manifest")
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.db]
+            description = "Database"
+
+            [commands.db.commands.reset]
+            description = "Reset"
+            deprecated = "use `db drop` instead"
+
+            [commands.deploy]
+            description = "Deploy"
+
+            [commands.deploy.flags.legacy]
+            type = "bool"
+            deprecated = "has no effect"
+        "#,
+        );
+
+        let mut diagnostics = Vec::new();
+        DeprecatedLint.check(&manifest, &mut diagnostics);
+        let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        messages.sort();
+
+        assert_eq!(
+            messages,
+            [
+                "command 'db.reset' is deprecated: use `db drop` instead",
+                "flag 'legacy' of 'deploy' is deprecated: has no effect",
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Info));
+    }
+}
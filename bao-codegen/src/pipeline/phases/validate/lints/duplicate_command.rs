@@ -2,12 +2,14 @@
 
 use std::collections::HashMap;
 
-use baobao_manifest::Manifest;
+use baobao_core::to_snake_case;
+use baobao_manifest::{Command, Manifest};
 
 use super::super::Lint;
 use crate::pipeline::Diagnostic;
 
-/// Lint that errors on duplicate command paths.
+/// Lint that errors on duplicate command paths, and on aliases that clash
+/// with the name or an alias of a sibling command.
 pub struct DuplicateCommandLint;
 
 impl Lint for DuplicateCommandLint {
@@ -23,8 +25,7 @@ impl Lint for DuplicateCommandLint {
         let mut seen: HashMap<String, String> = HashMap::new();
 
         for (name, cmd) in &manifest.commands {
-            // Normalize to handle case-insensitive duplicates
-            let normalized = name.to_lowercase();
+            let normalized = normalize(name);
             if let Some(first) = seen.get(&normalized) {
                 diagnostics.push(
                     Diagnostic::error(
@@ -39,6 +40,56 @@ impl Lint for DuplicateCommandLint {
 
             collect_subcommand_paths(name, cmd, &mut seen, diagnostics);
         }
+
+        check_aliases(None, &manifest.commands, diagnostics);
+    }
+}
+
+/// Check the aliases of sibling commands under `parent`, then of their
+/// subcommands.
+fn check_aliases(
+    parent: Option<&str>,
+    commands: &HashMap<String, Command>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let path = |name: &str| match parent {
+        Some(parent) => format!("{}/{}", parent, name),
+        None => name.to_string(),
+    };
+    let mut names: Vec<&String> = commands.keys().collect();
+    names.sort();
+
+    // Names first, so an alias clashing with a name reports the command
+    let mut taken: HashMap<&str, String> = names
+        .iter()
+        .map(|name| (name.as_str(), format!("command '{}'", path(name))))
+        .collect();
+    for name in &names {
+        for alias in &commands[*name].aliases {
+            if let Some(owner) = taken.get(alias.as_str()) {
+                diagnostics.push(
+                    Diagnostic::error(
+                        "validate",
+                        format!(
+                            "alias '{}' of '{}' conflicts with {}",
+                            alias,
+                            path(name),
+                            owner
+                        ),
+                    )
+                    .at(format!(
+                        "commands.{}.aliases",
+                        path(name).replace('/', ".commands.")
+                    )),
+                );
+            } else {
+                taken.insert(alias, format!("an alias of '{}'", path(name)));
+            }
+        }
+    }
+
+    for name in names {
+        check_aliases(Some(&path(name)), &commands[name].commands, diagnostics);
     }
 }
 
@@ -50,7 +101,7 @@ fn collect_subcommand_paths(
 ) {
     for (name, subcmd) in &cmd.commands {
         let path = format!("{}/{}", parent_path, name);
-        let normalized = path.to_lowercase();
+        let normalized = normalize(&path);
 
         if let Some(first) = seen.get(&normalized) {
             diagnostics.push(
@@ -71,6 +122,16 @@ fn collect_subcommand_paths(
     }
 }
 
+/// Flatten a command path the way generators name files and identifiers, so
+/// paths differing only in case or separators (`db/migrate`, `db-migrate`,
+/// `dbMigrate`) are caught as duplicates.
+fn normalize(path: &str) -> String {
+    path.split('/')
+        .map(to_snake_case)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +190,71 @@ mod tests {
         // db/migrate and api/migrate are different paths, so no duplicates
         assert!(diagnostics.is_empty());
     }
+
+    #[test]
+    fn test_flattened_paths_collide() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.db-migrate]
+            description = "Migrate"
+
+            [commands.db]
+            description = "Database commands"
+
+            [commands.db.commands.migrate]
+            description = "Another migrate"
+        "#,
+        );
+
+        let mut diagnostics = Vec::new();
+        DuplicateCommandLint.check(&manifest, &mut diagnostics);
+
+        // Both become db_migrate in flat file and identifier names
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn test_alias_conflicts() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.list]
+            description = "List"
+            aliases = ["ls", "show"]
+
+            [commands.show]
+            description = "Show"
+
+            [commands.db]
+            description = "Database commands"
+
+            [commands.db.commands.migrate]
+            description = "Migrate"
+            aliases = ["m"]
+
+            [commands.db.commands.make]
+            description = "Make"
+            aliases = ["m"]
+        "#,
+        );
+
+        let mut diagnostics = Vec::new();
+        DuplicateCommandLint.check(&manifest, &mut diagnostics);
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "alias 'show' of 'list' conflicts with command 'show'",
+                "alias 'm' of 'db/migrate' conflicts with an alias of 'db/make'",
+            ]
+        );
+    }
 }
@@ -19,35 +19,52 @@ impl Lint for EmptyDescriptionLint {
 
     fn check(&self, manifest: &Manifest, diagnostics: &mut Vec<Diagnostic>) {
         for (name, cmd) in &manifest.commands {
-            if cmd.description.is_empty() {
+            if is_undescribed(cmd) {
                 diagnostics.push(
                     Diagnostic::warning(
                         "validate",
                         format!("command '{}' has no description", name),
                     )
-                    .at(format!("commands.{}", name)),
+                    .at(format!("commands.{}", name))
+                    .with_span(manifest.spans.command(std::slice::from_ref(name))),
                 );
             }
 
-            check_subcommand_descriptions(name, cmd, diagnostics);
+            check_subcommand_descriptions(manifest, std::slice::from_ref(name), cmd, diagnostics);
         }
     }
 }
 
+/// A command is described by either its short or its long description.
+fn is_undescribed(cmd: &baobao_manifest::Command) -> bool {
+    cmd.description.is_empty()
+        && cmd
+            .long_description
+            .as_deref()
+            .is_none_or(|d| d.trim().is_empty())
+}
+
 fn check_subcommand_descriptions(
-    parent_path: &str,
+    manifest: &Manifest,
+    parent_path: &[String],
     cmd: &baobao_manifest::Command,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
     for (name, subcmd) in &cmd.commands {
-        let path = format!("{}.{}", parent_path, name);
-        if subcmd.description.is_empty() {
+        let mut path = parent_path.to_vec();
+        path.push(name.clone());
+        let dotted = path.join(".");
+        if is_undescribed(subcmd) {
             diagnostics.push(
-                Diagnostic::warning("validate", format!("command '{}' has no description", path))
-                    .at(format!("commands.{}", path)),
+                Diagnostic::warning(
+                    "validate",
+                    format!("command '{}' has no description", dotted),
+                )
+                .at(format!("commands.{}", dotted))
+                .with_span(manifest.spans.command(&path)),
             );
         }
-        check_subcommand_descriptions(&path, subcmd, diagnostics);
+        check_subcommand_descriptions(manifest, &path, subcmd, diagnostics);
     }
 }
 
@@ -98,4 +115,29 @@ mod tests {
 
         assert!(diagnostics.is_empty());
     }
+
+    #[test]
+    fn test_long_description_only() {
+        let manifest = parse_manifest(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = ""
+            long_description = "Build the project and upload it"
+
+            [commands.deploy.commands.rollback]
+            description = ""
+            long_description = " "
+        "#,
+        );
+
+        let mut diagnostics = Vec::new();
+        EmptyDescriptionLint.check(&manifest, &mut diagnostics);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("deploy.rollback"));
+    }
 }
@@ -1,9 +1,13 @@
 //! Built-in lints for manifest validation.
 
 mod command_naming;
+mod default_type;
+mod deprecated;
 mod duplicate_command;
 mod empty_description;
 
 pub use command_naming::CommandNamingLint;
+pub use default_type::DefaultTypeLint;
+pub use deprecated::DeprecatedLint;
 pub use duplicate_command::DuplicateCommandLint;
 pub use empty_description::EmptyDescriptionLint;
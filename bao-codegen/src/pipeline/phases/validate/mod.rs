@@ -1,13 +1,25 @@
 //! Validate phase - runs lints on the manifest.
+//!
+//! Warnings can be silenced for one table with a comment above its header:
+//!
+//! ```toml
+//! # bao: allow(empty-description)
+//! [commands.internal]
+//! description = ""
+//! ```
+//!
+//! Errors can't be allowed.
 
 mod lint;
 pub mod lints;
 
 use eyre::{Result, bail};
 pub use lint::{Lint, LintInfo};
-pub use lints::{CommandNamingLint, DuplicateCommandLint, EmptyDescriptionLint};
+pub use lints::{
+    CommandNamingLint, DefaultTypeLint, DeprecatedLint, DuplicateCommandLint, EmptyDescriptionLint,
+};
 
-use crate::pipeline::{CompilationContext, Phase};
+use crate::pipeline::{CompilationContext, Diagnostic, Phase, Severity};
 
 /// Phase that validates the manifest using configurable lints.
 pub struct ValidatePhase {
@@ -20,6 +32,8 @@ impl ValidatePhase {
         Self {
             lints: vec![
                 Box::new(CommandNamingLint),
+                Box::new(DefaultTypeLint),
+                Box::new(DeprecatedLint),
                 Box::new(DuplicateCommandLint),
                 Box::new(EmptyDescriptionLint),
             ],
@@ -66,9 +80,30 @@ impl Phase for ValidatePhase {
     }
 
     fn run(&self, ctx: &mut CompilationContext) -> Result<()> {
-        // Run all lints
+        let spans = &ctx.manifest.spans;
+
+        // Run all lints, dropping warnings allowed where they point
         for lint in &self.lints {
+            let start = ctx.diagnostics.len();
             lint.check(&ctx.manifest, &mut ctx.diagnostics);
+            let found = ctx.diagnostics.split_off(start);
+            ctx.diagnostics
+                .extend(found.into_iter().filter(|diagnostic| {
+                    matches!(diagnostic.severity, Severity::Error)
+                        || !diagnostic
+                            .span
+                            .as_ref()
+                            .is_some_and(|span| spans.is_allowed(lint.name(), span.start))
+                }));
+        }
+
+        for (name, span) in spans.allowed_lints() {
+            if !self.lints.iter().any(|lint| lint.name() == name) {
+                ctx.diagnostics.push(
+                    Diagnostic::warning("validate", format!("unknown lint '{}' in allow", name))
+                        .with_span(Some(span)),
+                );
+            }
         }
 
         // Fail if there are any errors (warnings are allowed)
@@ -150,4 +185,52 @@ mod tests {
         assert!(ctx.has_warnings());
         assert!(!ctx.has_errors());
     }
+
+    #[test]
+    fn test_allow_comment() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            # bao: allow(empty-description)
+            [commands.deploy]
+            description = ""
+
+            [commands.build]
+            description = ""
+        "#
+        .parse()
+        .unwrap();
+
+        let mut ctx = CompilationContext::new(manifest);
+        ValidatePhase::new().run(&mut ctx).unwrap();
+
+        let messages: Vec<&str> = ctx.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, ["command 'build' has no description"]);
+    }
+
+    #[test]
+    fn test_allow_unknown_lint() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy] # bao: allow(empty-descripton)
+            description = "Deploy"
+        "#
+        .parse()
+        .unwrap();
+
+        let mut ctx = CompilationContext::new(manifest);
+        ValidatePhase::new().run(&mut ctx).unwrap();
+
+        assert_eq!(ctx.diagnostics.len(), 1);
+        assert_eq!(
+            ctx.diagnostics[0].message,
+            "unknown lint 'empty-descripton' in allow"
+        );
+        assert!(ctx.diagnostics[0].span.is_some());
+    }
 }
@@ -1,8 +1,11 @@
 //! Pipeline plugin trait for extensibility.
 
+use std::sync::Arc;
+
 use eyre::Result;
 
 use super::CompilationContext;
+use crate::adapters::ResourceAdapter;
 
 /// A plugin that can hook into the compilation pipeline.
 ///
@@ -65,4 +68,13 @@ pub trait Plugin: Send + Sync {
     fn on_after_phase(&self, phase: &str, ctx: &mut CompilationContext) -> Result<()> {
         Ok(())
     }
+
+    /// Adapters that render the custom resources this plugin adds.
+    ///
+    /// A plugin that pushes [`Resource::Custom`](baobao_ir::Resource::Custom)
+    /// resources into the IR returns an adapter for each kind, so generators
+    /// can render them. Adapters are registered before the first phase runs.
+    fn resource_adapters(&self) -> Vec<Arc<dyn ResourceAdapter>> {
+        Vec::new()
+    }
 }
@@ -6,13 +6,13 @@ use eyre::Result;
 use super::{
     CompilationContext, Phase, Plugin,
     phase::PhaseInfo,
-    phases::{AnalyzePhase, LowerPhase, ValidatePhase},
+    phases::{AnalyzePhase, CanonicalizePhase, LowerPhase, ValidatePhase},
 };
 
 /// The compilation pipeline orchestrator.
 ///
 /// The pipeline manages the execution of compilation phases and plugin hooks.
-/// It runs built-in phases (validate, lower, analyze) followed by any user
+/// It runs built-in phases (validate, lower, canonicalize, analyze) followed by any user
 /// phases, calling plugin hooks before and after each phase.
 ///
 /// # Example
@@ -28,6 +28,7 @@ pub struct Pipeline {
     builtin_phases: Vec<Box<dyn Phase>>,
     user_phases: Vec<Box<dyn Phase>>,
     plugins: Vec<Box<dyn Plugin>>,
+    profile: Option<String>,
 }
 
 impl Pipeline {
@@ -37,10 +38,12 @@ impl Pipeline {
             builtin_phases: vec![
                 Box::new(ValidatePhase::new()),
                 Box::new(LowerPhase),
+                Box::new(CanonicalizePhase),
                 Box::new(AnalyzePhase),
             ],
             user_phases: Vec::new(),
             plugins: Vec::new(),
+            profile: None,
         }
     }
 
@@ -56,6 +59,12 @@ impl Pipeline {
         self
     }
 
+    /// Select a manifest profile, applied before lowering.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
     /// Iterate over all phases (builtin + user).
     fn all_phases(&self) -> impl Iterator<Item = &Box<dyn Phase>> {
         self.builtin_phases.iter().chain(self.user_phases.iter())
@@ -76,8 +85,9 @@ impl Pipeline {
     /// Executes all phases in order:
     /// 1. ValidatePhase - validates manifest, collects diagnostics
     /// 2. LowerPhase - transforms manifest to IR
-    /// 3. AnalyzePhase - computes shared data
-    /// 4. User phases (if any)
+    /// 3. CanonicalizePhase - normalizes the IR
+    /// 4. AnalyzePhase - computes shared data
+    /// 5. User phases (if any)
     ///
     /// Plugin hooks are called before and after each phase.
     ///
@@ -86,6 +96,12 @@ impl Pipeline {
     /// Returns an error if any phase fails fatally.
     pub fn run(&self, manifest: Manifest) -> Result<CompilationContext> {
         let mut ctx = CompilationContext::new(manifest);
+        ctx.profile = self.profile.clone();
+        for plugin in &self.plugins {
+            for adapter in plugin.resource_adapters() {
+                ctx.resource_adapters.register(adapter);
+            }
+        }
 
         for phase in self.all_phases() {
             self.run_phase(phase.as_ref(), &mut ctx)?;
@@ -200,8 +216,8 @@ mod tests {
         let pipeline = Pipeline::new().plugin(plugin);
         let _ = pipeline.run(manifest).expect("pipeline should succeed");
 
-        // 3 built-in phases = 3 before + 3 after hooks
-        assert_eq!(before_count.load(Ordering::SeqCst), 3);
-        assert_eq!(after_count.load(Ordering::SeqCst), 3);
+        // 4 built-in phases = 4 before + 4 after hooks
+        assert_eq!(before_count.load(Ordering::SeqCst), 4);
+        assert_eq!(after_count.load(Ordering::SeqCst), 4);
     }
 }
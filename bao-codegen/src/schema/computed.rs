@@ -63,6 +63,13 @@ mod tests {
                 version: "1.0.0".into(),
                 description: None,
                 author: None,
+                docs: Default::default(),
+                build_info: false,
+                docs_command: false,
+                doctor_command: false,
+                external_subcommands: false,
+                default_command: None,
+                header: None,
             },
             resources: vec![Resource::Database(DatabaseResource {
                 name: "db".into(),
@@ -70,8 +77,11 @@ mod tests {
                 env_var: "DATABASE_URL".into(),
                 pool: PoolConfig::default(),
                 sqlite: None,
+                duckdb: None,
             })],
             operations: vec![],
+            global_inputs: vec![],
+            locales: vec![],
         }
     }
 
@@ -213,10 +213,8 @@ impl<'a> CommandTreeDisplay<'a> {
     fn format_signature(name: &str, cmd: &baobao_manifest::Command) -> String {
         let mut parts = vec![name.to_string()];
 
-        // Add args (sorted)
-        let mut sorted_args: Vec<_> = cmd.args.iter().collect();
-        sorted_args.sort_by_key(|(n, _)| *n);
-        for (arg_name, arg) in sorted_args {
+        // Add args in command line order
+        for (arg_name, arg) in cmd.positional_args() {
             if arg.required {
                 parts.push(format!("<{}>", arg_name));
             } else {
@@ -2,11 +2,38 @@
 //!
 //! This module is only available when the `testing` feature is enabled
 //! or during tests.
+//!
+//! # Fuzzing
+//!
+//! With the `arbitrary` feature, manifests and IR can be generated from fuzzer
+//! input. [`compile_roundtrip`] puts a generated manifest through the same
+//! parsing, validation and pipeline as a bao.toml, so only manifests a user
+//! could write reach the generators. The fuzz targets live in `fuzz/`.
+//!
+//! # Property testing
+//!
+//! [`manifest`] is a [`proptest`] strategy for manifests that always pass
+//! validation, for asserting invariants every generator must uphold:
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn deterministic(manifest in baobao_codegen::testing::manifest()) {
+//!         // generate twice, compare
+//!     }
+//! }
+//! ```
+
+mod strategies;
 
 use std::{path::Path, process::Command};
 
+use baobao_manifest::Manifest;
 use eyre::{Result, eyre};
 
+pub use self::strategies::{manifest, manifest_source};
+use crate::pipeline::{CompilationContext, Pipeline};
+
 /// Error from compile checking.
 #[derive(Debug)]
 pub struct CompileError {
@@ -134,6 +161,16 @@ pub fn assert_content_eq(expected: &str, actual: &str) {
     }
 }
 
+/// Compile a manifest as `bao bake` would see it: written as bao.toml,
+/// parsed and validated, then run through the pipeline.
+///
+/// Returns `None` if the manifest is rejected along the way. Panics are left
+/// to propagate, for fuzz targets to report.
+pub fn compile_roundtrip(manifest: &Manifest) -> Option<CompilationContext> {
+    let manifest: Manifest = manifest.to_toml_string().parse().ok()?;
+    Pipeline::new().run(manifest).ok()
+}
+
 /// Generate code into a temporary directory and return the path.
 ///
 /// The directory will be cleaned up when the returned `TempDir` is dropped.
@@ -169,3 +206,49 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Deterministic stand-in for fuzzer input.
+    fn bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compile_arbitrary_manifests() {
+        let mut compiled = 0;
+        for seed in 0..200 {
+            let data = bytes(seed, 512);
+            let Ok(manifest) = Manifest::arbitrary(&mut Unstructured::new(&data)) else {
+                continue;
+            };
+            if compile_roundtrip(&manifest).is_some() {
+                compiled += 1;
+            }
+        }
+        assert!(compiled > 0, "no generated manifest passed validation");
+    }
+
+    proptest! {
+        #[test]
+        fn test_generated_manifests_pass_pipeline(manifest in manifest()) {
+            let result = Pipeline::new().run(manifest);
+            prop_assert!(result.is_ok(), "{:?}", result.err());
+        }
+    }
+}
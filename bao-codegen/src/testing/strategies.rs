@@ -0,0 +1,215 @@
+//! [`proptest`] strategies for valid manifests.
+//!
+//! Unlike the `arbitrary` implementations, which explore what the parser
+//! rejects, every manifest produced here parses and passes the pipeline:
+//! names are kebab-case words that no target language reserves, command paths
+//! stay distinct once flattened, nesting is bounded, short flags are unique
+//! within a command and defaults match their input's type. Generator invariants can then be asserted for every case.
+
+use std::collections::BTreeMap;
+
+use baobao_core::{ARG_TYPES, ArgType, to_snake_case};
+use baobao_manifest::Manifest;
+use proptest::{
+    collection::{btree_map, vec},
+    option,
+    prelude::*,
+    sample::{select, subsequence},
+};
+use toml::{Table, Value};
+
+/// Deepest level of nested subcommands.
+const MAX_DEPTH: u32 = 3;
+
+/// Most entries in one table of commands, args or flags.
+const MAX_ENTRIES: usize = 4;
+
+/// Words names are built from. None is a keyword or a generated module name
+/// in any target language, alone or joined with another.
+const WORDS: &[&str] = &[
+    "amber", "birch", "cedar", "delta", "ember", "fjord", "grove", "heron", "iris", "kiwi",
+    "lotus", "maple", "nova", "orbit", "pixel", "quartz",
+];
+
+/// Short flags, leaving out `-h` and `-V` for help and version.
+const SHORTS: &[char] = &[
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't',
+    'u', 'v', 'w', 'x', 'y', 'z',
+];
+
+/// A valid manifest for a Rust CLI.
+///
+/// Generators don't read `cli.language`, so the same manifests exercise every
+/// backend.
+pub fn manifest() -> impl Strategy<Value = Manifest> {
+    manifest_source().prop_map(|src| {
+        src.parse()
+            .unwrap_or_else(|e| panic!("generated manifest is invalid: {:?}\n\n{}", e, src))
+    })
+}
+
+/// The bao.toml source of a valid manifest.
+pub fn manifest_source() -> impl Strategy<Value = String> {
+    (name(), option::of(description()), commands()).prop_map(|(name, description, commands)| {
+        let mut cli = Table::new();
+        cli.insert("name".into(), name.into());
+        cli.insert("version".into(), "0.1.0".into());
+        cli.insert("language".into(), "rust".into());
+        if let Some(description) = description {
+            cli.insert("description".into(), description.into());
+        }
+
+        let mut root = Table::new();
+        root.insert("cli".into(), cli.into());
+        root.insert("commands".into(), commands.into());
+        toml::to_string(&root).expect("manifest table serializes")
+    })
+}
+
+/// A table of one to [`MAX_ENTRIES`] commands, nested up to [`MAX_DEPTH`].
+fn commands() -> impl Strategy<Value = Table> {
+    let leaf = (description(), args(), flags()).prop_map(|(description, args, flags)| {
+        let mut command = Table::new();
+        command.insert("description".into(), description.into());
+        if !args.is_empty() {
+            command.insert("args".into(), args.into());
+        }
+        if !flags.is_empty() {
+            command.insert("flags".into(), flags.into());
+        }
+        Value::Table(command)
+    });
+
+    let command = leaf.prop_recursive(MAX_DEPTH, 32, MAX_ENTRIES as u32, |inner| {
+        (description(), btree_map(name(), inner, 1..=MAX_ENTRIES)).prop_map(
+            |(description, commands)| {
+                let mut command = Table::new();
+                command.insert("description".into(), description.into());
+                command.insert("commands".into(), table(commands).into());
+                Value::Table(command)
+            },
+        )
+    });
+
+    btree_map(name(), command, 1..=MAX_ENTRIES)
+        .prop_map(table)
+        .prop_filter("command paths collide once flattened", |commands| {
+            let mut paths = Vec::new();
+            flattened_paths(commands, "", &mut paths);
+            let len = paths.len();
+            paths.sort();
+            paths.dedup();
+            paths.len() == len
+        })
+}
+
+/// Command paths as generators flatten them into names: `db migrate` and
+/// `db-migrate` both become `db_migrate`.
+fn flattened_paths(commands: &Table, prefix: &str, paths: &mut Vec<String>) {
+    for (name, command) in commands {
+        let path = format!("{}{}", prefix, to_snake_case(name));
+        if let Some(Value::Table(subcommands)) = command.get("commands") {
+            flattened_paths(subcommands, &format!("{}_", path), paths);
+        }
+        paths.push(path);
+    }
+}
+
+fn args() -> impl Strategy<Value = Table> {
+    btree_map(name(), (input(false), any::<bool>()), 0..=MAX_ENTRIES).prop_map(|args| {
+        args.into_iter()
+            .map(|(name, (mut arg, required))| {
+                if !arg.contains_key("default") {
+                    arg.insert("required".into(), required.into());
+                }
+                (name, Value::Table(arg))
+            })
+            .collect()
+    })
+}
+
+/// Flags, some with a short form. Shorts are drawn without replacement so no
+/// two flags of a command share one.
+fn flags() -> impl Strategy<Value = Table> {
+    btree_map(name(), input(true), 0..=MAX_ENTRIES)
+        .prop_flat_map(|flags| {
+            let len = flags.len();
+            (
+                Just(flags),
+                subsequence(SHORTS, len).prop_shuffle(),
+                vec(any::<bool>(), len),
+            )
+        })
+        .prop_map(|(flags, shorts, with_short)| {
+            flags
+                .into_iter()
+                .zip(shorts.into_iter().zip(with_short))
+                .map(|((name, mut flag), (short, with_short))| {
+                    if with_short {
+                        flag.insert("short".into(), short.to_string().into());
+                    }
+                    (name, Value::Table(flag))
+                })
+                .collect()
+        })
+}
+
+/// The fields shared by args and flags: a type, an optional description and
+/// an optional default of that type. Only flags count, and never have a
+/// default. Secrets are left out, since they can't be global flags or
+/// precede required args.
+fn input(flag: bool) -> impl Strategy<Value = Table> {
+    let types: Vec<_> = ARG_TYPES
+        .iter()
+        .filter(|spec| spec.arg_type != ArgType::Secret)
+        .filter(|spec| flag || spec.arg_type != ArgType::Count)
+        .collect();
+    (select(types), option::of(description()))
+        .prop_flat_map(|(spec, description)| {
+            let default = match spec.arg_type {
+                ArgType::Count => Just(None).boxed(),
+                _ => option::of(default_value(spec.name)).boxed(),
+            };
+            (Just(spec.name), Just(description), default)
+        })
+        .prop_map(|(ty, description, default)| {
+            let mut input = Table::new();
+            input.insert("type".into(), ty.into());
+            if let Some(description) = description {
+                input.insert("description".into(), description.into());
+            }
+            if let Some(default) = default {
+                input.insert("default".into(), default);
+            }
+            input
+        })
+}
+
+fn default_value(ty: &str) -> BoxedStrategy<Value> {
+    match ty {
+        "int" => (-1_000_000i64..1_000_000).prop_map(Value::from).boxed(),
+        "float" => (-1.0e6..1.0e6f64).prop_map(Value::from).boxed(),
+        "bool" => any::<bool>().prop_map(Value::from).boxed(),
+        "path" => "[a-z]{1,8}(/[a-z]{1,8}){0,2}(\\.[a-z]{1,3})?"
+            .prop_map(Value::from)
+            .boxed(),
+        _ => "[a-zA-Z0-9 _-]{0,16}".prop_map(Value::from).boxed(),
+    }
+}
+
+/// A kebab-case name of one or two [`WORDS`].
+fn name() -> impl Strategy<Value = String> {
+    (select(WORDS), option::of(select(WORDS))).prop_map(|(first, second)| match second {
+        Some(second) => format!("{}-{}", first, second),
+        None => first.to_string(),
+    })
+}
+
+/// Help text, including characters generated code has to escape.
+fn description() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 .,:;!?'\"\\\\`{}$-]{1,40}"
+}
+
+fn table(entries: BTreeMap<String, Value>) -> Table {
+    entries.into_iter().collect()
+}
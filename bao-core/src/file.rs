@@ -1,6 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
-use eyre::Result;
+use eyre::{Result, WrapErr};
+use similar::{ChangeTag, TextDiff};
 
 /// Trait for types that represent a generated file
 pub trait GeneratedFile {
@@ -13,55 +18,384 @@ pub trait GeneratedFile {
     /// Render the file content
     fn render(&self) -> String;
 
+    /// Content written to disk
+    ///
+    /// Defaults to the rendered text. Binary assets override this and
+    /// only use [`GeneratedFile::render`] for previews.
+    fn content(&self) -> FileContent {
+        FileContent::Text(self.render())
+    }
+
+    /// Checksum of the written content, as recorded in `bao.lock`
+    fn checksum(&self) -> String {
+        crate::checksum(self.content().as_bytes())
+    }
+
     /// Write the file to disk
     fn write(&self, base: &Path) -> Result<WriteResult> {
-        let path = self.path(base);
-        let rules = self.rules();
+        write_content_with_rules(&self.path(base), &self.content(), &self.rules())
+    }
+}
 
-        match rules.overwrite {
-            Overwrite::Always => {
-                write_file(&path, &self.render())?;
-                Ok(WriteResult::Written)
-            }
-            Overwrite::IfMissing => {
-                if path.exists() {
-                    Ok(WriteResult::Skipped)
-                } else {
-                    write_file(&path, &self.render())?;
-                    Ok(WriteResult::Written)
-                }
-            }
+/// Content of a generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileContent {
+    /// UTF-8 source text
+    Text(String),
+    /// Binary asset (icons, seed databases, ...)
+    Binary(Vec<u8>),
+}
+
+impl FileContent {
+    /// Raw bytes written to disk.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Binary(bytes) => bytes,
+        }
+    }
+
+    /// The text, if this is text content.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Binary(_) => None,
+        }
+    }
+
+    /// Whether this is binary content.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary(_))
+    }
+}
+
+/// Text as is, binary content as a `<binary file, N bytes>` placeholder.
+impl std::fmt::Display for FileContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Binary(bytes) => write!(f, "<binary file, {} bytes>", bytes.len()),
         }
     }
 }
 
-fn write_file(path: &Path, content: &str) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+impl From<String> for FileContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for FileContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for FileContent {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Binary(bytes)
+    }
+}
+
+impl From<&[u8]> for FileContent {
+    fn from(bytes: &[u8]) -> Self {
+        Self::Binary(bytes.to_vec())
+    }
+}
+
+/// Write `content` to `path` according to `rules`.
+///
+/// Handles the overwrite policy and backups without asking; use
+/// [`write_content_with_prompt`] to confirm [`Overwrite::Prompt`] files.
+pub fn write_with_rules(path: &Path, content: &str, rules: &FileRules) -> Result<WriteResult> {
+    write_rules_impl(path, content.as_bytes(), Some(content), rules, None)
+}
+
+/// Write text or binary `content` to `path` according to `rules`.
+///
+/// Binary writes report no line changes, and the overwrite prompt is shown
+/// the file sizes instead of a diff.
+pub fn write_content_with_rules(
+    path: &Path,
+    content: &FileContent,
+    rules: &FileRules,
+) -> Result<WriteResult> {
+    write_rules_impl(path, content.as_bytes(), content.as_text(), rules, None)
+}
+
+/// Write `content` to `path`, asking `prompt` before an [`Overwrite::Prompt`]
+/// file that differs on disk is replaced.
+///
+/// Other overwrite policies are applied without asking.
+pub fn write_content_with_prompt(
+    path: &Path,
+    content: &FileContent,
+    rules: &FileRules,
+    prompt: &mut dyn OverwritePrompt,
+) -> Result<WriteResult> {
+    write_rules_impl(
+        path,
+        content.as_bytes(),
+        content.as_text(),
+        rules,
+        Some(prompt),
+    )
+}
+
+fn write_rules_impl(
+    path: &Path,
+    content: &[u8],
+    text: Option<&str>,
+    rules: &FileRules,
+    prompt: Option<&mut dyn OverwritePrompt>,
+) -> Result<WriteResult> {
+    let result = write_contents(path, content, text, rules, prompt)?;
+    if let Some(mode) = rules.mode
+        && result != WriteResult::Skipped
+    {
+        set_mode(path, mode)?;
     }
-    std::fs::write(path, content)?;
+    Ok(result)
+}
+
+/// Set Unix permission bits on `path`.
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+/// File modes have no equivalent outside Unix; scripts are run through
+/// their interpreter instead.
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<()> {
     Ok(())
 }
 
+fn write_contents(
+    path: &Path,
+    content: &[u8],
+    text: Option<&str>,
+    rules: &FileRules,
+    prompt: Option<&mut dyn OverwritePrompt>,
+) -> Result<WriteResult> {
+    if rules.overwrite == Overwrite::IfMissing && path.exists() {
+        return Ok(WriteResult::Skipped);
+    }
+    let Some(existing) = read_existing(path)? else {
+        write_bytes(path, content)?;
+        let changes = text.map_or_else(LineChanges::default, |t| LineChanges::between("", t));
+        return Ok(WriteResult::Created(changes));
+    };
+    if existing == content {
+        return Ok(WriteResult::Unchanged);
+    }
+
+    // Non-UTF-8 files are shown as binary and treated as entirely replaced
+    let (existing_text, new_text) = match text {
+        Some(text) => (
+            String::from_utf8(existing)
+                .unwrap_or_else(|e| FileContent::Binary(e.into_bytes()).to_string()),
+            text.to_string(),
+        ),
+        None => (
+            FileContent::Binary(existing).to_string(),
+            format!("<binary file, {} bytes>", content.len()),
+        ),
+    };
+
+    let overwrite = match (rules.overwrite, prompt) {
+        (Overwrite::IfMissing, _) => false,
+        (Overwrite::Always, _) | (Overwrite::Prompt, None) => true,
+        (Overwrite::Prompt, Some(prompt)) => prompt.confirm(path, &existing_text, &new_text)?,
+    };
+    if !overwrite {
+        return Ok(WriteResult::Skipped);
+    }
+
+    if rules.backup {
+        backup_bytes(path, content)?;
+    }
+    write_bytes(path, content)?;
+    let changes = match text {
+        Some(_) => LineChanges::between(&existing_text, &new_text),
+        None => LineChanges::default(),
+    };
+    Ok(WriteResult::Updated(changes))
+}
+
+/// Asks whether an existing file should be overwritten.
+pub trait OverwritePrompt {
+    /// Return `true` to replace `existing` at `path` with `new`.
+    fn confirm(&mut self, path: &Path, existing: &str, new: &str) -> Result<bool>;
+}
+
+/// Copy an existing file to `<file>.bak` before it is overwritten with `content`.
+///
+/// Nothing is copied when the file is missing or already has the new content.
+/// Returns the backup path if one was written.
+pub fn backup_file(path: &Path, content: &str) -> Result<Option<PathBuf>> {
+    backup_bytes(path, content.as_bytes())
+}
+
+fn backup_bytes(path: &Path, content: &[u8]) -> Result<Option<PathBuf>> {
+    let Some(existing) = read_existing(path)? else {
+        return Ok(None);
+    };
+    if existing == content {
+        return Ok(None);
+    }
+
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    write_bytes(&backup, &existing)?;
+    Ok(Some(backup))
+}
+
+/// Read the file at `path`, or `None` if it doesn't exist.
+fn read_existing(path: &Path) -> Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(existing) => Ok(Some(existing)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).wrap_err_with(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Write a file atomically, creating parent directories as needed.
+///
+/// Content goes to a temporary file in the same directory which is then
+/// renamed over `path`, so an interrupted write never leaves a truncated file.
+/// An existing file keeps its permissions, and a symlink is written through
+/// rather than replaced.
+pub fn write_file(path: &Path, content: &str) -> Result<()> {
+    write_bytes(path, content.as_bytes())
+}
+
+/// Write raw bytes atomically, creating parent directories as needed.
+///
+/// Binary counterpart of [`write_file`].
+pub fn write_bytes(path: &Path, content: &[u8]) -> Result<()> {
+    let path = &resolve_symlink(path)?;
+    let permissions = fs::metadata(path)
+        .ok()
+        .map(|metadata| metadata.permissions());
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| eyre::eyre!("invalid file path: {}", path.display()))?;
+    let tmp_path = parent.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let result = (|| -> Result<()> {
+        let mut tmp = fs::File::create(&tmp_path)?;
+        if let Some(permissions) = permissions {
+            tmp.set_permissions(permissions)?;
+        }
+        tmp.write_all(content)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// The file a symlink at `path` points to, or `path` itself if it isn't one.
+///
+/// Dangling links resolve to their missing target, so writing creates it.
+fn resolve_symlink(path: &Path) -> Result<PathBuf> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => fs::canonicalize(path).or_else(|_| {
+            let link = fs::read_link(path)?;
+            Ok(path.parent().unwrap_or(Path::new("")).join(link))
+        }),
+        _ => Ok(path.to_path_buf()),
+    }
+}
+
 /// Result of a write operation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WriteResult {
-    /// File was written
-    Written,
-    /// File was skipped (already exists)
+    /// File did not exist and was created
+    Created(LineChanges),
+    /// Existing file was overwritten with different content
+    Updated(LineChanges),
+    /// Existing file already had this content and was left untouched
+    Unchanged,
+    /// File was skipped (already exists, or overwrite was declined)
     Skipped,
 }
 
+impl WriteResult {
+    /// Whether the file was newly created.
+    pub fn is_created(&self) -> bool {
+        matches!(self, Self::Created(_))
+    }
+
+    /// Whether anything was written to disk.
+    pub fn is_written(&self) -> bool {
+        matches!(self, Self::Created(_) | Self::Updated(_))
+    }
+
+    /// Line changes, if anything was written.
+    pub fn changes(&self) -> Option<LineChanges> {
+        match self {
+            Self::Created(changes) | Self::Updated(changes) => Some(*changes),
+            Self::Unchanged | Self::Skipped => None,
+        }
+    }
+}
+
+/// Number of lines added and removed by a write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineChanges {
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl LineChanges {
+    /// Count the lines changed between `old` and `new`.
+    pub fn between(old: &str, new: &str) -> Self {
+        TextDiff::from_lines(old, new).iter_all_changes().fold(
+            Self::default(),
+            |mut changes, change| {
+                match change.tag() {
+                    ChangeTag::Insert => changes.added += 1,
+                    ChangeTag::Delete => changes.removed += 1,
+                    ChangeTag::Equal => {}
+                }
+                changes
+            },
+        )
+    }
+}
+
 /// A file to be generated
 pub struct File {
     path: PathBuf,
-    content: String,
+    content: FileContent,
     rules: FileRules,
 }
 
 impl File {
     /// Create a new file with the given path and content (default rules: always overwrite)
-    pub fn new(path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+    ///
+    /// Pass a `Vec<u8>` or `&[u8]` for binary content.
+    pub fn new(path: impl Into<PathBuf>, content: impl Into<FileContent>) -> Self {
         Self {
             path: path.into(),
             content: content.into(),
@@ -75,7 +409,7 @@ impl File {
     }
 
     /// Get the file content
-    pub fn content(&self) -> &str {
+    pub fn content(&self) -> &FileContent {
         &self.content
     }
 
@@ -84,22 +418,15 @@ impl File {
         self.path.exists()
     }
 
+    /// Set the rules for writing this file
+    pub fn with_rules(mut self, rules: FileRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// Write the file according to its rules
     pub fn write(&self) -> Result<WriteResult> {
-        match self.rules.overwrite {
-            Overwrite::Always => {
-                write_file(&self.path, &self.content)?;
-                Ok(WriteResult::Written)
-            }
-            Overwrite::IfMissing => {
-                if self.exists() {
-                    Ok(WriteResult::Skipped)
-                } else {
-                    write_file(&self.path, &self.content)?;
-                    Ok(WriteResult::Written)
-                }
-            }
-        }
+        write_content_with_rules(&self.path, &self.content, &self.rules)
     }
 }
 
@@ -108,6 +435,10 @@ impl File {
 pub struct FileRules {
     pub overwrite: Overwrite,
     pub header: Option<&'static str>,
+    /// Save the previous content to `<file>.bak` before overwriting
+    pub backup: bool,
+    /// Unix permission bits applied after writing (e.g., `0o755`)
+    pub mode: Option<u32>,
 }
 
 /// How to handle existing files
@@ -117,6 +448,9 @@ pub enum Overwrite {
     Always,
     /// Only create if file doesn't exist (stubs)
     IfMissing,
+    /// Ask an [`OverwritePrompt`] when an existing file differs
+    /// (behaves like `Always` when written without one)
+    Prompt,
 }
 
 impl FileRules {
@@ -125,6 +459,8 @@ impl FileRules {
         Self {
             overwrite: Overwrite::Always,
             header: None,
+            backup: false,
+            mode: None,
         }
     }
 
@@ -133,6 +469,18 @@ impl FileRules {
         Self {
             overwrite: Overwrite::IfMissing,
             header: None,
+            backup: false,
+            mode: None,
+        }
+    }
+
+    /// Rules for files that ask before overwriting local changes.
+    pub fn prompt_overwrite() -> Self {
+        Self {
+            overwrite: Overwrite::Prompt,
+            header: None,
+            backup: false,
+            mode: None,
         }
     }
 
@@ -141,6 +489,26 @@ impl FileRules {
         self.header = Some(header);
         self
     }
+
+    /// Back up the existing file to `<file>.bak` before overwriting it.
+    ///
+    /// Gives users a recovery path when an always-overwritten file
+    /// contained manual edits.
+    pub fn with_backup(mut self) -> Self {
+        self.backup = true;
+        self
+    }
+
+    /// Set Unix permission bits on the written file (e.g., `0o755` for
+    /// scripts and git hooks).
+    ///
+    /// The mode is applied whenever the file is created or overwritten, and
+    /// restored on unchanged files. It is ignored on platforms without Unix
+    /// permissions, where executability does not depend on file mode.
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
 }
 
 impl Default for FileRules {
@@ -190,6 +558,76 @@ mod tests {
         assert_eq!(fs::read_to_string(&path).unwrap(), "second");
     }
 
+    #[test]
+    fn test_write_file_leaves_no_temp_files() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("test.txt");
+
+        write_file(&path, "content").unwrap();
+
+        let entries: Vec<_> = fs::read_dir(temp.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_file_failure_keeps_original() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dir");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("keep.txt"), "original").unwrap();
+
+        // Renaming a file over a non-empty directory fails
+        assert!(write_file(&path, "content").is_err());
+
+        assert_eq!(
+            fs::read_to_string(path.join("keep.txt")).unwrap(),
+            "original"
+        );
+        let entries: Vec<_> = fs::read_dir(temp.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("run.sh");
+        fs::write(&path, "first").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+
+        write_file(&path, "second").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o750);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_through_symlink() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("shared")).unwrap();
+        let target = temp.path().join("shared/config.json");
+        let link = temp.path().join("config.json");
+        fs::write(&target, "first").unwrap();
+        std::os::unix::fs::symlink("shared/config.json", &link).unwrap();
+
+        write_file(&link, "second").unwrap();
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "second");
+
+        // Dangling links create their target
+        fs::remove_file(&target).unwrap();
+        write_file(&link, "third").unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "third");
+    }
+
     #[test]
     fn test_file_write_always_overwrites() {
         let temp = TempDir::new().unwrap();
@@ -200,7 +638,13 @@ mod tests {
         let file = File::new(&path, "updated");
         let result = file.write().unwrap();
 
-        assert_eq!(result, WriteResult::Written);
+        assert_eq!(
+            result,
+            WriteResult::Updated(LineChanges {
+                added: 1,
+                removed: 1
+            })
+        );
         assert_eq!(fs::read_to_string(&path).unwrap(), "updated");
     }
 
@@ -211,15 +655,17 @@ mod tests {
 
         let file = File {
             path: path.clone(),
-            content: "new content".to_string(),
+            content: "new content".into(),
             rules: FileRules {
                 overwrite: Overwrite::IfMissing,
                 header: None,
+                backup: false,
+                mode: None,
             },
         };
         let result = file.write().unwrap();
 
-        assert_eq!(result, WriteResult::Written);
+        assert!(result.is_created());
         assert_eq!(fs::read_to_string(&path).unwrap(), "new content");
     }
 
@@ -232,10 +678,12 @@ mod tests {
 
         let file = File {
             path: path.clone(),
-            content: "should not write".to_string(),
+            content: "should not write".into(),
             rules: FileRules {
                 overwrite: Overwrite::IfMissing,
                 header: None,
+                backup: false,
+                mode: None,
             },
         };
         let result = file.write().unwrap();
@@ -244,6 +692,201 @@ mod tests {
         assert_eq!(fs::read_to_string(&path).unwrap(), "original");
     }
 
+    #[test]
+    fn test_file_write_with_backup() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("test.txt");
+        let backup = temp.path().join("test.txt.bak");
+
+        fs::write(&path, "manual edits").unwrap();
+
+        let file =
+            File::new(&path, "generated").with_rules(FileRules::always_overwrite().with_backup());
+        file.write().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "generated");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "manual edits");
+
+        // Unchanged content doesn't replace the backup
+        file.write().unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "manual edits");
+    }
+
+    #[test]
+    fn test_file_write_prompt() {
+        struct Decline(usize);
+        impl OverwritePrompt for Decline {
+            fn confirm(&mut self, _path: &Path, existing: &str, new: &str) -> Result<bool> {
+                assert_eq!((existing, new), ("original", "updated"));
+                self.0 += 1;
+                Ok(false)
+            }
+        }
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("prompted.txt");
+        let content = FileContent::from("updated");
+        let mut prompt = Decline(0);
+
+        // Without a prompt: behaves like Always
+        fs::write(&path, "original").unwrap();
+        let file = File::new(&path, "updated").with_rules(FileRules::prompt_overwrite());
+        assert!(file.write().unwrap().is_written());
+
+        fs::write(&path, "original").unwrap();
+        let result =
+            write_content_with_prompt(&path, &content, &FileRules::prompt_overwrite(), &mut prompt)
+                .unwrap();
+        assert_eq!(result, WriteResult::Skipped);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+        assert_eq!(prompt.0, 1);
+
+        // Always never asks
+        let result =
+            write_content_with_prompt(&path, &content, &FileRules::always_overwrite(), &mut prompt)
+                .unwrap();
+        assert!(result.is_written());
+        assert_eq!(prompt.0, 1);
+    }
+
+    #[test]
+    fn test_file_write_unreadable() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("generated");
+        fs::create_dir(&path).unwrap();
+
+        let err = File::new(&path, "content").write().unwrap_err();
+        assert!(err.to_string().contains("failed to read"));
+        assert!(path.is_dir());
+    }
+
+    #[test]
+    fn test_file_write_non_utf8_diff_base() {
+        struct Record(String);
+        impl OverwritePrompt for Record {
+            fn confirm(&mut self, _path: &Path, existing: &str, _new: &str) -> Result<bool> {
+                self.0 = existing.to_string();
+                Ok(true)
+            }
+        }
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("notes.txt");
+        fs::write(&path, [0xff, 0xfe]).unwrap();
+
+        let mut prompt = Record(String::new());
+        let result = write_content_with_prompt(
+            &path,
+            &FileContent::from("text\n"),
+            &FileRules::prompt_overwrite(),
+            &mut prompt,
+        )
+        .unwrap();
+        assert_eq!(prompt.0, "<binary file, 2 bytes>");
+        assert_eq!(
+            result,
+            WriteResult::Updated(LineChanges {
+                added: 1,
+                removed: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_backup_file_missing() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("missing.txt");
+
+        assert_eq!(backup_file(&path, "content").unwrap(), None);
+        assert!(!temp.path().join("missing.txt.bak").exists());
+    }
+
+    #[test]
+    fn test_file_write_unchanged() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("test.txt");
+
+        let file = File::new(&path, "a\nb\n");
+        assert_eq!(
+            file.write().unwrap(),
+            WriteResult::Created(LineChanges {
+                added: 2,
+                removed: 0
+            })
+        );
+        assert_eq!(file.write().unwrap(), WriteResult::Unchanged);
+    }
+
+    #[test]
+    fn test_file_write_binary() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("icon.png");
+        let bytes = vec![0x89, b'P', b'N', b'G', 0xff, 0x00];
+
+        let file = File::new(&path, bytes.clone()).with_rules(FileRules::default().with_backup());
+        assert_eq!(
+            file.write().unwrap(),
+            WriteResult::Created(LineChanges::default())
+        );
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        assert_eq!(file.write().unwrap(), WriteResult::Unchanged);
+
+        fs::write(&path, [0xfe, 0xfe]).unwrap();
+        assert_eq!(
+            file.write().unwrap(),
+            WriteResult::Updated(LineChanges::default())
+        );
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        assert_eq!(
+            fs::read(temp.path().join("icon.png.bak")).unwrap(),
+            [0xfe, 0xfe]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_write_with_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("install.sh");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let file =
+            File::new(&path, "#!/bin/sh\n").with_rules(FileRules::default().with_mode(0o755));
+        file.write().unwrap();
+        assert_eq!(mode(&path), 0o755);
+
+        // Restored on unchanged files
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(file.write().unwrap(), WriteResult::Unchanged);
+        assert_eq!(mode(&path), 0o755);
+    }
+
+    #[test]
+    fn test_file_content_display() {
+        assert_eq!(FileContent::from("text").to_string(), "text");
+        assert_eq!(
+            FileContent::from(vec![0u8; 4]).to_string(),
+            "<binary file, 4 bytes>"
+        );
+    }
+
+    #[test]
+    fn test_line_changes_between() {
+        assert_eq!(
+            LineChanges::between("a\nb\nc\n", "a\nx\nc\nd\n"),
+            LineChanges {
+                added: 2,
+                removed: 1
+            }
+        );
+        assert_eq!(
+            LineChanges::between("same\n", "same\n"),
+            LineChanges::default()
+        );
+    }
+
     #[test]
     fn test_file_exists() {
         let temp = TempDir::new().unwrap();
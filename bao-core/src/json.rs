@@ -0,0 +1,108 @@
+//! In-place edits of JSON files.
+
+use std::ops::Range;
+
+/// Byte range of the value of the top-level `key` in a JSON object.
+///
+/// Keys of nested objects are never matched, so callers can splice in a new
+/// value while keeping the rest of the file as written. Returns `None` if the
+/// key is missing or the content is malformed.
+pub fn json_value_span(content: &str, key: &str) -> Option<Range<usize>> {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i)?;
+                if depth == 1
+                    && content[i + 1..end] == *key
+                    && let Some(value) = content[end + 1..].trim_start().strip_prefix(':')
+                {
+                    let start = content.len() - value.trim_start().len();
+                    return Some(start..value_end(bytes, start)?);
+                }
+                i = end;
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the quote closing the string opened at `start`.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'"' => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Offset just past the value starting at `start`.
+fn value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    match bytes.get(start)? {
+        b'"' => string_end(bytes, start).map(|end| end + 1),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => i = string_end(bytes, i)?,
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            let len = bytes[start..]
+                .iter()
+                .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                .unwrap_or(bytes.len() - start);
+            Some(start + len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+        json_value_span(content, key).map(|span| &content[span])
+    }
+
+    #[test]
+    fn test_json_value_span() {
+        let content = r#"{
+  "name": "version",
+  "engines": { "version": ">=1" },
+  "tags": ["a}", "b"],
+  "version": "1.0.0",
+  "private": true
+}"#;
+
+        assert_eq!(value(content, "version"), Some("\"1.0.0\""));
+        assert_eq!(value(content, "engines"), Some("{ \"version\": \">=1\" }"));
+        assert_eq!(value(content, "tags"), Some("[\"a}\", \"b\"]"));
+        assert_eq!(value(content, "private"), Some("true"));
+        assert_eq!(value(content, "missing"), None);
+        assert_eq!(value("{\"version\": \"1\"", "missing"), None);
+    }
+}
@@ -7,18 +7,31 @@
 pub const GENERATED_HEADER: &str = "// Generated by Bao - DO NOT EDIT";
 
 mod file;
+mod json;
+mod lock;
+mod path;
 mod type_mapper;
 mod types;
 mod utils;
 mod version;
 
 // File operations
-pub use file::{File, FileRules, GeneratedFile, Overwrite, WriteResult};
+pub use file::{
+    File, FileContent, FileRules, GeneratedFile, LineChanges, Overwrite, OverwritePrompt,
+    WriteResult, backup_file, write_bytes, write_content_with_prompt, write_content_with_rules,
+    write_file, write_with_rules,
+};
+// In-place JSON edits
+pub use json::json_value_span;
 // Fundamental types
-pub use type_mapper::ArgType;
-pub use types::{ContextFieldType, DatabaseType};
+// Generated file checksums
+pub use lock::{LOCK_FILE, Lockfile, checksum};
+// Portable paths
+pub use path::{join_relative, normalize_path, path_key, relative_display, to_slash_path};
+pub use type_mapper::{ARG_TYPES, ArgType, ArgTypeSpec};
+pub use types::{ContextFieldType, DatabaseType, QueueType};
 // String utilities
 pub use utils::{
-    to_camel_case, to_kebab_case, to_pascal_case, to_snake_case, toml_value_to_string,
+    to_camel_case, to_env_var, to_kebab_case, to_pascal_case, to_snake_case, toml_value_to_string,
 };
 pub use version::Version;
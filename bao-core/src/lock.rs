@@ -0,0 +1,175 @@
+//! Checksums of generated files, persisted in `bao.lock`.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{join_relative, write_file};
+
+/// Name of the lock file written next to the generated project.
+pub const LOCK_FILE: &str = "bao.lock";
+
+/// Current `bao.lock` format version.
+const LOCK_VERSION: u32 = 1;
+
+const LOCK_HEADER: &str = "# Generated by Bao - DO NOT EDIT\n\
+                           # Checksums of generated files, used to detect manual edits.\n\n";
+
+/// Compute the checksum of file content (`sha256:<hex>`).
+///
+/// Line endings of text content are normalized first, so a checkout with
+/// CRLF line endings matches the generated content.
+pub fn checksum(content: &[u8]) -> String {
+    let digest = match std::str::from_utf8(content) {
+        Ok(text) => Sha256::digest(text.replace("\r\n", "\n").as_bytes()),
+        Err(_) => Sha256::digest(content),
+    };
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Checksums of the files bao overwrites on every bake, keyed by path
+/// relative to the output directory.
+///
+/// Committing `bao.lock` lets `bao bake` tell, on any machine, whether a
+/// generated file was edited by hand since it was last generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    version: u32,
+    #[serde(default)]
+    files: BTreeMap<String, String>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Create a lock file from path and checksum pairs.
+    pub fn new(files: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            version: LOCK_VERSION,
+            files: files.into_iter().collect(),
+        }
+    }
+
+    /// Load `bao.lock` from `dir`, or an empty lock file if there is none.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let lock: Self = toml::from_str(&content)
+            .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+        if lock.version > LOCK_VERSION {
+            eyre::bail!(
+                "{} has version {}, but this bao only supports version {}",
+                path.display(),
+                lock.version,
+                LOCK_VERSION
+            );
+        }
+        Ok(lock)
+    }
+
+    /// Render the contents of `bao.lock`.
+    pub fn render(&self) -> Result<String> {
+        let content = toml::to_string(self).wrap_err("Failed to serialize lock file")?;
+        Ok(format!("{}{}", LOCK_HEADER, content))
+    }
+
+    /// Write the lock file to `dir/bao.lock`.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        write_file(&dir.join(LOCK_FILE), &self.render()?)
+    }
+
+    /// Recorded checksum of a file.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Iterate over recorded paths and checksums.
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Tracked files under `dir` whose content no longer matches the recorded
+    /// checksum. Missing or unreadable files are not reported.
+    pub fn modified_files(&self, dir: &Path) -> Vec<String> {
+        self.files
+            .iter()
+            .filter(|(path, recorded)| {
+                fs::read(join_relative(dir, path))
+                    .is_ok_and(|content| checksum(&content) != **recorded)
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            checksum(b"hello"),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(checksum(b"a\r\nb\r\n"), checksum(b"a\nb\n"));
+        assert_ne!(checksum(&[0xff, 0x0d, 0x0a]), checksum(&[0xff, 0x0a]));
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(Lockfile::load(temp.path()).unwrap(), Lockfile::default());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let lock = Lockfile::new([("src/main.rs".to_string(), checksum(b"fn main() {}"))]);
+        lock.save(temp.path()).unwrap();
+
+        let content = fs::read_to_string(temp.path().join(LOCK_FILE)).unwrap();
+        assert!(content.starts_with("# Generated by Bao"));
+        assert_eq!(Lockfile::load(temp.path()).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_modified_files() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("clean.rs"), "clean").unwrap();
+        fs::write(temp.path().join("edited.rs"), "edited by hand").unwrap();
+
+        let lock = Lockfile::new([
+            ("clean.rs".to_string(), checksum(b"clean")),
+            ("edited.rs".to_string(), checksum(b"edited")),
+            ("deleted.rs".to_string(), checksum(b"deleted")),
+        ]);
+
+        assert_eq!(lock.modified_files(temp.path()), vec!["edited.rs"]);
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(LOCK_FILE), "version = 99\n").unwrap();
+
+        let err = Lockfile::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("version 99"));
+    }
+}
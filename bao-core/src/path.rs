@@ -0,0 +1,122 @@
+//! Portable path helpers.
+//!
+//! Generators describe files with `/`-separated paths relative to the output
+//! directory. These helpers convert them to native paths for filesystem access
+//! and back to `/`-separated strings for reports and lock files, so output is
+//! the same on Windows and Unix.
+
+use std::path::{Component, Path, PathBuf};
+
+/// Whether the host filesystem usually compares file names case-insensitively.
+const CASE_INSENSITIVE_FS: bool = cfg!(any(windows, target_os = "macos"));
+
+/// Render a path with `/` separators.
+pub fn to_slash_path(path: &Path) -> String {
+    let mut out = String::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => out.push_str(&prefix.as_os_str().to_string_lossy()),
+            Component::RootDir => out.push('/'),
+            Component::CurDir => {}
+            Component::ParentDir | Component::Normal(_) => {
+                if !out.is_empty() && !out.ends_with('/') {
+                    out.push('/');
+                }
+                out.push_str(&component.as_os_str().to_string_lossy());
+            }
+        }
+    }
+    if out.is_empty() {
+        out.push('.');
+    }
+    out
+}
+
+/// Normalize a relative path string to `/` separators, dropping `.` and
+/// empty segments (`.\src\\main.rs` becomes `src/main.rs`).
+pub fn normalize_path(path: &str) -> String {
+    path.split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Key for comparing relative paths as the host filesystem would.
+///
+/// Paths are normalized and, on Windows and macOS where file names are
+/// case-insensitive by default, lowercased, so `Db/Migrate` and `db/migrate`
+/// refer to the same file.
+pub fn path_key(path: &str) -> String {
+    let normalized = normalize_path(path);
+    if CASE_INSENSITIVE_FS {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+/// Join a `/`-separated relative path onto `base` using native separators.
+pub fn join_relative(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for segment in relative
+        .split(['/', '\\'])
+        .filter(|s| !s.is_empty() && *s != ".")
+    {
+        path.push(segment);
+    }
+    path
+}
+
+/// Display `path` relative to `base` with `/` separators, falling back to
+/// the full path when it is not under `base`.
+pub fn relative_display(path: &Path, base: &Path) -> String {
+    to_slash_path(path.strip_prefix(base).unwrap_or(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_slash_path() {
+        let path: PathBuf = ["src", "handlers", "db", "migrate.rs"].iter().collect();
+        assert_eq!(to_slash_path(&path), "src/handlers/db/migrate.rs");
+        assert_eq!(to_slash_path(Path::new("./out")), "out");
+        assert_eq!(to_slash_path(Path::new(".")), ".");
+        assert_eq!(to_slash_path(Path::new("/tmp/out")), "/tmp/out");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(".\\src\\\\main.rs"), "src/main.rs");
+        assert_eq!(normalize_path("src/handlers/"), "src/handlers");
+        assert_eq!(normalize_path(""), "");
+    }
+
+    #[test]
+    fn test_path_key() {
+        assert_eq!(path_key("db\\migrate"), path_key("db/migrate"));
+        assert_eq!(
+            path_key("Db/Migrate") == path_key("db/migrate"),
+            CASE_INSENSITIVE_FS
+        );
+    }
+
+    #[test]
+    fn test_join_relative() {
+        let joined = join_relative(Path::new("out"), "src/handlers/hello.rs");
+        let expected: PathBuf = ["out", "src", "handlers", "hello.rs"].iter().collect();
+        assert_eq!(joined, expected);
+    }
+
+    #[test]
+    fn test_relative_display() {
+        let base = Path::new("out");
+        let path = join_relative(base, "src/generated/commands/hello.rs");
+        assert_eq!(
+            relative_display(&path, base),
+            "src/generated/commands/hello.rs"
+        );
+        assert_eq!(relative_display(Path::new("other"), base), "other");
+    }
+}
@@ -1,27 +1,180 @@
 //! Argument type definitions.
 
+use std::fmt;
+
+use baobao_ir::InputType;
+use serde::{Deserialize, Serialize, Serializer};
+
 /// Supported argument types in the schema.
 ///
-/// This is a language-agnostic representation of argument types.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// This is a language-agnostic representation of argument types. Every type
+/// is described once in [`ARG_TYPES`]; per-language handling lives in each
+/// generator's `TypeRegistry`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
 pub enum ArgType {
     String,
     Int,
     Float,
+    #[default]
     Bool,
     Path,
+    /// Flag counting its occurrences (`-vvv` is 3)
+    Count,
+    /// Absolute URL
+    Url,
+    /// Length of time in humantime syntax (`1h30m`)
+    Duration,
+    /// RFC 3339 timestamp (`2024-05-01T12:00:00Z`)
+    DateTime,
+    /// UUID (`67e55044-10b1-426f-9247-bb680e5fe0c8`)
+    Uuid,
+    /// Text read from an environment variable or a hidden prompt when not
+    /// given, such as a password
+    Secret,
+}
+
+/// Description of a built-in argument type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgTypeSpec {
+    /// The argument type
+    pub arg_type: ArgType,
+    /// Schema type name (used in bao.toml)
+    pub name: &'static str,
+    /// IR type the argument lowers to
+    pub input_type: InputType,
 }
 
+/// All argument types, in declaration order.
+pub const ARG_TYPES: &[ArgTypeSpec] = &[
+    ArgTypeSpec {
+        arg_type: ArgType::String,
+        name: "string",
+        input_type: InputType::String,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Int,
+        name: "int",
+        input_type: InputType::Int,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Float,
+        name: "float",
+        input_type: InputType::Float,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Bool,
+        name: "bool",
+        input_type: InputType::Bool,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Path,
+        name: "path",
+        input_type: InputType::Path,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Count,
+        name: "count",
+        input_type: InputType::Count,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Url,
+        name: "url",
+        input_type: InputType::Url,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Duration,
+        name: "duration",
+        input_type: InputType::Duration,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::DateTime,
+        name: "datetime",
+        input_type: InputType::DateTime,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Uuid,
+        name: "uuid",
+        input_type: InputType::Uuid,
+    },
+    ArgTypeSpec {
+        arg_type: ArgType::Secret,
+        name: "secret",
+        input_type: InputType::Secret,
+    },
+];
+
 impl ArgType {
+    /// Get the description of this type from [`ARG_TYPES`].
+    pub fn spec(&self) -> &'static ArgTypeSpec {
+        ARG_TYPES
+            .iter()
+            .find(|spec| spec.arg_type == *self)
+            .expect("every ArgType is listed in ARG_TYPES")
+    }
+
+    /// Look up a type by its schema name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        ARG_TYPES
+            .iter()
+            .find(|spec| spec.name == name)
+            .map(|spec| spec.arg_type)
+    }
+
+    /// Iterate over all argument types.
+    pub fn all() -> impl Iterator<Item = Self> {
+        ARG_TYPES.iter().map(|spec| spec.arg_type)
+    }
+
     /// Get the schema type name (used in bao.toml)
     pub fn as_str(&self) -> &'static str {
-        match self {
-            ArgType::String => "string",
-            ArgType::Int => "int",
-            ArgType::Float => "float",
-            ArgType::Bool => "bool",
-            ArgType::Path => "path",
-        }
+        self.spec().name
+    }
+}
+
+impl fmt::Display for ArgType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<String> for ArgType {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::from_name(&name).ok_or_else(|| {
+            let names: Vec<&str> = ARG_TYPES.iter().map(|spec| spec.name).collect();
+            format!(
+                "unknown type '{}', expected one of: {}",
+                name,
+                names.join(", ")
+            )
+        })
+    }
+}
+
+impl Serialize for ArgType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl From<ArgType> for InputType {
+    fn from(arg_type: ArgType) -> Self {
+        arg_type.spec().input_type
+    }
+}
+
+impl From<InputType> for ArgType {
+    fn from(input_type: InputType) -> Self {
+        ARG_TYPES
+            .iter()
+            .find(|spec| spec.input_type == input_type)
+            .map(|spec| spec.arg_type)
+            .expect("every InputType is listed in ARG_TYPES")
     }
 }
 
@@ -36,5 +189,39 @@ mod tests {
         assert_eq!(ArgType::Float.as_str(), "float");
         assert_eq!(ArgType::Bool.as_str(), "bool");
         assert_eq!(ArgType::Path.as_str(), "path");
+        assert_eq!(ArgType::Count.as_str(), "count");
+        assert_eq!(ArgType::Url.as_str(), "url");
+        assert_eq!(ArgType::Duration.as_str(), "duration");
+        assert_eq!(ArgType::DateTime.as_str(), "datetime");
+        assert_eq!(ArgType::Uuid.as_str(), "uuid");
+        assert_eq!(ArgType::Secret.as_str(), "secret");
+    }
+
+    #[test]
+    fn test_arg_type_from_name() {
+        for ty in ArgType::all() {
+            assert_eq!(ArgType::from_name(ty.as_str()), Some(ty));
+        }
+        assert_eq!(ArgType::from_name("number"), None);
+    }
+
+    #[test]
+    fn test_arg_type_input_type_round_trip() {
+        for ty in ArgType::all() {
+            assert_eq!(ArgType::from(InputType::from(ty)), ty);
+        }
+    }
+
+    #[test]
+    fn test_arg_type_deserialize_error() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            #[allow(dead_code)]
+            r#type: ArgType,
+        }
+        let err = toml::from_str::<Config>(r#"type = "number""#).unwrap_err();
+        assert!(err.to_string().contains(
+            "unknown type 'number', expected one of: string, int, float, bool, path, count, url, duration, datetime, uuid, secret"
+        ));
     }
 }
@@ -2,4 +2,4 @@
 //!
 //! Re-exports from `baobao-ir` for backwards compatibility.
 
-pub use baobao_ir::{ContextFieldType, DatabaseType};
+pub use baobao_ir::{ContextFieldType, DatabaseType, QueueType};
@@ -40,14 +40,39 @@ pub fn to_kebab_case(s: &str) -> String {
     to_snake_case(s).replace('_', "-")
 }
 
+/// Environment variable for an input of the command at `path`
+/// (e.g., "myapp", ["db", "login"], "api-token" -> "MYAPP_DB_LOGIN_API_TOKEN")
+pub fn to_env_var(cli: &str, path: &[String], name: &str) -> String {
+    std::iter::once(cli)
+        .chain(path.iter().map(String::as_str))
+        .chain(std::iter::once(name))
+        .map(|part| to_snake_case(part).to_uppercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
 /// Convert a TOML value to its string representation
+///
+/// Arrays render as comma-separated items (`a,b`) and tables as
+/// comma-separated `key=value` pairs, matching how list values are
+/// passed on the command line.
 pub fn toml_value_to_string(value: &toml::Value) -> String {
     match value {
         toml::Value::String(s) => s.clone(),
         toml::Value::Integer(i) => i.to_string(),
         toml::Value::Float(f) => f.to_string(),
         toml::Value::Boolean(b) => b.to_string(),
-        _ => String::new(),
+        toml::Value::Datetime(d) => d.to_string(),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(toml_value_to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+        toml::Value::Table(table) => table
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, toml_value_to_string(v)))
+            .collect::<Vec<_>>()
+            .join(","),
     }
 }
 
@@ -79,6 +104,15 @@ mod tests {
         assert_eq!(to_snake_case(""), "");
     }
 
+    #[test]
+    fn test_to_env_var() {
+        assert_eq!(to_env_var("myapp", &[], "token"), "MYAPP_TOKEN");
+        assert_eq!(
+            to_env_var("my-app", &["db".into(), "login".into()], "api-token"),
+            "MY_APP_DB_LOGIN_API_TOKEN"
+        );
+    }
+
     #[test]
     fn test_toml_value_to_string() {
         assert_eq!(
@@ -92,4 +126,20 @@ mod tests {
         );
         assert_eq!(toml_value_to_string(&toml::Value::Boolean(true)), "true");
     }
+
+    #[test]
+    fn test_toml_value_to_string_array_and_table() {
+        let value: toml::Table = toml::from_str(
+            r#"
+            list = ["a", "b"]
+            ports = [80, 443]
+            env = { region = "eu", replicas = 3 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(toml_value_to_string(&value["list"]), "a,b");
+        assert_eq!(toml_value_to_string(&value["ports"]), "80,443");
+        assert_eq!(toml_value_to_string(&value["env"]), "region=eu,replicas=3");
+    }
 }
@@ -1,13 +1,19 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+/// A semantic version (`MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]`).
+///
+/// Versions are ordered by precedence as defined by semver 2.0.0, with build
+/// metadata only used to break ties so ordering stays consistent with equality.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
 #[serde(try_from = "String")]
 pub struct Version {
     major: u32,
     minor: u32,
     patch: u32,
+    pre: Option<String>,
+    build: Option<String>,
 }
 
 impl TryFrom<String> for Version {
@@ -33,13 +39,165 @@ impl Version {
             major,
             minor,
             patch,
+            pre: None,
+            build: None,
         }
     }
+
+    /// Set the pre-release identifiers (e.g., `rc.1`).
+    pub fn with_pre(mut self, pre: &str) -> Result<Self, String> {
+        validate_identifiers(pre, "pre-release", true)?;
+        self.pre = Some(pre.to_string());
+        Ok(self)
+    }
+
+    /// Set the build metadata (e.g., `build.5`).
+    pub fn with_build(mut self, build: &str) -> Result<Self, String> {
+        validate_identifiers(build, "build metadata", false)?;
+        self.build = Some(build.to_string());
+        Ok(self)
+    }
+
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// Pre-release identifiers, if any.
+    pub fn pre(&self) -> Option<&str> {
+        self.pre.as_deref()
+    }
+
+    /// Build metadata, if any.
+    pub fn build(&self) -> Option<&str> {
+        self.build.as_deref()
+    }
+
+    /// Whether this is a pre-release version.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// Next major version (`1.2.3` -> `2.0.0`).
+    ///
+    /// A pre-release of a major version is released as is (`2.0.0-rc.1` -> `2.0.0`).
+    pub fn bump_major(&self) -> Self {
+        if self.is_prerelease() && self.minor == 0 && self.patch == 0 {
+            return Self::new(self.major, 0, 0);
+        }
+        Self::new(self.major + 1, 0, 0)
+    }
+
+    /// Next minor version (`1.2.3` -> `1.3.0`).
+    ///
+    /// A pre-release of a minor version is released as is (`1.3.0-rc.1` -> `1.3.0`).
+    pub fn bump_minor(&self) -> Self {
+        if self.is_prerelease() && self.patch == 0 {
+            return Self::new(self.major, self.minor, 0);
+        }
+        Self::new(self.major, self.minor + 1, 0)
+    }
+
+    /// Next patch version (`1.2.3` -> `1.2.4`).
+    ///
+    /// A pre-release is released as is (`1.2.3-rc.1` -> `1.2.3`).
+    pub fn bump_patch(&self) -> Self {
+        if self.is_prerelease() {
+            return Self::new(self.major, self.minor, self.patch);
+        }
+        Self::new(self.major, self.minor, self.patch + 1)
+    }
+}
+
+/// Validate dot-separated pre-release or build identifiers.
+fn validate_identifiers(s: &str, what: &str, numeric_rules: bool) -> Result<(), String> {
+    for ident in s.split('.') {
+        if ident.is_empty() {
+            return Err(format!("invalid {} '{}': empty identifier", what, s));
+        }
+        if !ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!(
+                "invalid {} '{}': only [0-9A-Za-z-] allowed",
+                what, s
+            ));
+        }
+        if numeric_rules
+            && ident.len() > 1
+            && ident.starts_with('0')
+            && ident.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(format!(
+                "invalid {} '{}': numeric identifier '{}' has a leading zero",
+                what, s, ident
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compare pre-release identifiers by semver precedence.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    // Numeric identifiers have lower precedence than alphanumeric ones
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                // A pre-release has lower precedence than the release
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(a), Some(b)) => compare_pre(a, b),
+            })
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
     }
 }
 
@@ -47,15 +205,38 @@ impl FromStr for Version {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split('.').collect();
+        let (rest, build) = match s.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (s, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
         if parts.len() != 3 {
             return Err(format!("invalid version '{}', expected 'X.Y.Z'", s));
         }
-        Ok(Self {
-            major: parts[0].parse().map_err(|_| "invalid major")?,
-            minor: parts[1].parse().map_err(|_| "invalid minor")?,
-            patch: parts[2].parse().map_err(|_| "invalid patch")?,
-        })
+        let number = |part: &str, name: &str| -> Result<u32, String> {
+            if part.len() > 1 && part.starts_with('0') {
+                return Err(format!("invalid {} '{}': leading zero", name, part));
+            }
+            part.parse().map_err(|_| format!("invalid {}", name))
+        };
+
+        let mut version = Self::new(
+            number(parts[0], "major")?,
+            number(parts[1], "minor")?,
+            number(parts[2], "patch")?,
+        );
+        if let Some(pre) = pre {
+            version = version.with_pre(pre)?;
+        }
+        if let Some(build) = build {
+            version = version.with_build(build)?;
+        }
+        Ok(version)
     }
 }
 
@@ -140,4 +321,94 @@ mod tests {
         let config: Config = toml::from_str(r#"version = "1.2.3""#).unwrap();
         assert_eq!(config.version, Version::new(1, 2, 3));
     }
+
+    #[test]
+    fn test_prerelease_and_build_round_trip() {
+        for input in [
+            "1.0.0-alpha",
+            "1.0.0-rc.1+build.5",
+            "1.0.0+20240101",
+            "2.1.3-x-y.7",
+        ] {
+            assert_eq!(input.parse::<Version>().unwrap().to_string(), input);
+        }
+
+        let v: Version = "1.2.3-rc.1+sha.abc".parse().unwrap();
+        assert_eq!(v.pre(), Some("rc.1"));
+        assert_eq!(v.build(), Some("sha.abc"));
+        assert!(v.is_prerelease());
+    }
+
+    #[test]
+    fn test_invalid_prerelease_and_build() {
+        assert!("1.0.0-".parse::<Version>().is_err());
+        assert!("1.0.0-rc..1".parse::<Version>().is_err());
+        assert!("1.0.0-01".parse::<Version>().is_err());
+        assert!("1.0.0+bad_char".parse::<Version>().is_err());
+        assert!("01.0.0".parse::<Version>().is_err());
+        // Leading zeros are fine in build metadata
+        assert!("1.0.0+001".parse::<Version>().is_ok());
+    }
+
+    #[test]
+    fn test_precedence() {
+        // Example ordering from the semver specification
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        let versions: Vec<Version> = ordered.iter().map(|v| v.parse().unwrap()).collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_bump() {
+        let v: Version = "1.2.3+build".parse().unwrap();
+        assert_eq!(v.bump_major(), Version::new(2, 0, 0));
+        assert_eq!(v.bump_minor(), Version::new(1, 3, 0));
+        assert_eq!(v.bump_patch(), Version::new(1, 2, 4));
+    }
+
+    #[test]
+    fn test_bump_prerelease() {
+        let bump = |v: &str| {
+            let v: Version = v.parse().unwrap();
+            (v.bump_major(), v.bump_minor(), v.bump_patch())
+        };
+        assert_eq!(
+            bump("1.2.3-rc.1+build"),
+            (
+                Version::new(2, 0, 0),
+                Version::new(1, 3, 0),
+                Version::new(1, 2, 3)
+            )
+        );
+        assert_eq!(
+            bump("1.3.0-rc.1"),
+            (
+                Version::new(2, 0, 0),
+                Version::new(1, 3, 0),
+                Version::new(1, 3, 0)
+            )
+        );
+        assert_eq!(
+            bump("2.0.0-alpha"),
+            (
+                Version::new(2, 0, 0),
+                Version::new(2, 0, 0),
+                Version::new(2, 0, 0)
+            )
+        );
+    }
 }
@@ -10,12 +10,29 @@
 //! bao.toml → Manifest (parsing) → AppIR (lowering) → Generator (codegen)
 //! ```
 
-use serde::Serialize;
+use std::collections::BTreeMap;
 
-use crate::{ContextFieldInfo, ContextFieldType, DatabaseType, PoolConfig, SqliteOptions};
+use serde::{Deserialize, Serialize, de::Error as _};
+
+use crate::{
+    CacheOptions, ConfigOptions, ContextFieldInfo, ContextFieldType, DatabaseType, DuckdbOptions,
+    EmailOptions, GrpcOptions, HttpMethod, HttpOptions, IrVisitor, LlmOptions, LogFormat, LogLevel,
+    PoolConfig, QueueType, S3Options, SecretsOptions, SourceSpan, SqliteOptions, WorkdirOptions,
+    walk_command,
+};
+
+/// Version of the JSON format written by [`AppIR::to_json`].
+///
+/// The version is bumped whenever a change could break an existing consumer:
+/// removing or renaming a field or variant, changing a field's type, or adding
+/// a field that must be present. Adding optional fields or new variants does
+/// not bump it, so consumers should ignore fields they don't know.
+/// [`AppIR::from_json`] rejects JSON written with a newer version.
+pub const IR_VERSION: u32 = 1;
 
 /// Application IR - unified representation for code generation.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AppIR {
     /// Application metadata.
     pub meta: AppMeta,
@@ -23,14 +40,74 @@ pub struct AppIR {
     pub resources: Vec<Resource>,
     /// Operations (commands for CLI, routes for HTTP).
     pub operations: Vec<Operation>,
+    /// CLI-level flags accepted by every command (e.g., verbosity, color,
+    /// output format). Generators render them on the root parser and make
+    /// their values available to handlers through the context.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub global_inputs: Vec<Input>,
+    /// Translated help text, one bundle per locale other than the default.
+    /// Generated CLIs select a bundle from the user's locale at runtime and
+    /// fall back to the descriptions above for missing entries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<Locale>,
 }
 
 impl AppIR {
+    /// Serialize the IR to pretty-printed JSON, tagged with [`IR_VERSION`].
+    ///
+    /// The output is deterministic: the IR is canonicalized before
+    /// generation and maps are ordered by key, so the same manifest always
+    /// yields the same JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct Versioned<'a> {
+            version: u32,
+            #[serde(flatten)]
+            ir: &'a AppIR,
+        }
+
+        serde_json::to_string_pretty(&Versioned {
+            version: IR_VERSION,
+            ir: self,
+        })
+    }
+
+    /// Load an IR from JSON produced by [`AppIR::to_json`].
+    ///
+    /// Fails if the JSON has no version or a version newer than
+    /// [`IR_VERSION`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| serde_json::Error::custom("missing IR version"))?;
+        if version > u64::from(IR_VERSION) {
+            return Err(serde_json::Error::custom(format!(
+                "IR version {} is newer than the supported version {}",
+                version, IR_VERSION
+            )));
+        }
+        serde_json::from_value(value)
+    }
+
     /// Returns true if any resource requires async initialization.
     pub fn has_async(&self) -> bool {
-        self.resources
-            .iter()
-            .any(|r| matches!(r, Resource::Database(_)))
+        self.resources.iter().any(|r| match r {
+            Resource::Database(_)
+            | Resource::Grpc(_)
+            | Resource::Queue(_)
+            | Resource::Email(_)
+            | Resource::Llm(_)
+            | Resource::Cache(_) => true,
+            Resource::Custom(custom) => custom.is_async,
+            Resource::HttpClient(_)
+            | Resource::Logger(_)
+            | Resource::Storage(_)
+            | Resource::Config(_)
+            | Resource::Secrets(_)
+            | Resource::Workdir(_) => false,
+        })
     }
 
     /// Returns true if a database resource is configured.
@@ -40,6 +117,86 @@ impl AppIR {
             .any(|r| matches!(r, Resource::Database(_)))
     }
 
+    /// Returns the logger resource, if configured.
+    pub fn logger(&self) -> Option<&LoggerResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Logger(logger) => Some(logger),
+            _ => None,
+        })
+    }
+
+    /// Returns the object storage resource, if configured.
+    pub fn storage(&self) -> Option<&StorageResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Storage(storage) => Some(storage),
+            _ => None,
+        })
+    }
+
+    /// Returns the gRPC client resource, if configured.
+    pub fn grpc(&self) -> Option<&GrpcResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Grpc(grpc) => Some(grpc),
+            _ => None,
+        })
+    }
+
+    /// Returns the SMTP transport resource, if configured.
+    pub fn email(&self) -> Option<&EmailResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Email(email) => Some(email),
+            _ => None,
+        })
+    }
+
+    /// Returns the config file resource, if configured.
+    pub fn config(&self) -> Option<&ConfigResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Config(config) => Some(config),
+            _ => None,
+        })
+    }
+
+    /// Returns the secrets resource, if configured.
+    pub fn secrets(&self) -> Option<&SecretsResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Secrets(secrets) => Some(secrets),
+            _ => None,
+        })
+    }
+
+    /// Returns the cache resource, if configured.
+    pub fn cache(&self) -> Option<&CacheResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Cache(cache) => Some(cache),
+            _ => None,
+        })
+    }
+
+    /// Returns the working directory resource, if configured.
+    pub fn workdir(&self) -> Option<&WorkdirResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Workdir(workdir) => Some(workdir),
+            _ => None,
+        })
+    }
+
+    /// Returns the LLM client resource, if configured.
+    pub fn llm(&self) -> Option<&LlmResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Llm(llm) => Some(llm),
+            _ => None,
+        })
+    }
+
+    /// Returns the message queue resource, if configured.
+    pub fn queue(&self) -> Option<&QueueResource> {
+        self.resources.iter().find_map(|r| match r {
+            Resource::Queue(queue) => Some(queue),
+            _ => None,
+        })
+    }
+
     /// Returns true if an HTTP client resource is configured.
     pub fn has_http(&self) -> bool {
         self.resources
@@ -47,41 +204,79 @@ impl AppIR {
             .any(|r| matches!(r, Resource::HttpClient(_)))
     }
 
+    /// Iterate over the HTTP client resources.
+    pub fn http_clients(&self) -> impl Iterator<Item = &HttpClientResource> {
+        self.resources.iter().filter_map(|r| match r {
+            Resource::HttpClient(http) => Some(http),
+            _ => None,
+        })
+    }
+
     /// Iterate over all commands.
     pub fn commands(&self) -> impl Iterator<Item = &CommandOp> {
-        self.operations.iter().map(|op| {
-            let Operation::Command(cmd) = op;
-            cmd
+        self.operations.iter().filter_map(|op| match op {
+            Operation::Command(cmd) => Some(cmd),
+            Operation::Route(_) => None,
+        })
+    }
+
+    /// Iterate over all HTTP routes.
+    pub fn routes(&self) -> impl Iterator<Item = &RouteOp> {
+        self.operations.iter().filter_map(|op| match op {
+            Operation::Route(route) => Some(route),
+            Operation::Command(_) => None,
         })
     }
 
     /// Collect all handler paths from commands (for orphan detection).
     pub fn handler_paths(&self) -> Vec<String> {
-        fn collect(cmd: &CommandOp, paths: &mut Vec<String>) {
-            paths.push(cmd.handler_path());
-            for child in &cmd.children {
-                collect(child, paths);
+        struct Paths(Vec<String>);
+
+        impl IrVisitor<'_> for Paths {
+            fn visit_command(&mut self, cmd: &CommandOp) {
+                self.0.push(cmd.handler_path());
+                walk_command(self, cmd);
             }
         }
 
-        let mut paths = Vec::new();
-        for cmd in self.commands() {
-            collect(cmd, &mut paths);
-        }
-        paths
+        let mut paths = Paths(Vec::new());
+        paths.visit_app(self);
+        paths.0
     }
 
     /// Count total number of leaf commands (commands without subcommands).
     pub fn command_count(&self) -> usize {
-        fn count(cmd: &CommandOp) -> usize {
-            if cmd.children.is_empty() {
-                1
-            } else {
-                cmd.children.iter().map(count).sum()
+        struct Leaves(usize);
+
+        impl IrVisitor<'_> for Leaves {
+            fn visit_command(&mut self, cmd: &CommandOp) {
+                if !cmd.has_subcommands() {
+                    self.0 += 1;
+                }
+                walk_command(self, cmd);
             }
         }
 
-        self.commands().map(count).sum()
+        let mut leaves = Leaves(0);
+        leaves.visit_app(self);
+        leaves.0
+    }
+
+    /// Returns true if any command declares the context fields it requires,
+    /// in which case resources are set up on first use.
+    pub fn has_requirements(&self) -> bool {
+        struct Requires(bool);
+
+        impl IrVisitor<'_> for Requires {
+            fn visit_command(&mut self, cmd: &CommandOp) {
+                self.0 |= !cmd.requires.is_empty();
+                walk_command(self, cmd);
+            }
+        }
+
+        let mut requires = Requires(false);
+        requires.visit_app(self);
+        requires.0
     }
 
     /// Collect context fields from resources.
@@ -96,6 +291,17 @@ impl AppIR {
                     is_async: true, // Database operations are always async
                     pool: db.pool.clone(),
                     sqlite: db.sqlite.clone(),
+                    duckdb: db.duckdb.clone(),
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
                 },
                 Resource::HttpClient(http) => ContextFieldInfo {
                     name: http.name.clone(),
@@ -104,6 +310,226 @@ impl AppIR {
                     is_async: false,        // HTTP client creation is sync
                     pool: PoolConfig::default(),
                     sqlite: None,
+                    duckdb: None,
+                    http: Some(http.options.clone()),
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Logger(logger) => ContextFieldInfo {
+                    name: logger.name.clone(),
+                    field_type: ContextFieldType::Logger,
+                    env_var: String::new(),
+                    is_async: false,
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Custom(custom) => ContextFieldInfo {
+                    name: custom.name.clone(),
+                    field_type: ContextFieldType::Custom,
+                    env_var: String::new(),
+                    is_async: custom.is_async,
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: Some(custom.clone()),
+                },
+                Resource::Storage(storage) => ContextFieldInfo {
+                    name: storage.name.clone(),
+                    field_type: ContextFieldType::Storage,
+                    env_var: String::new(), // Credentials use the standard AWS variables
+                    is_async: false,
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: Some(storage.s3.clone()),
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Grpc(grpc) => ContextFieldInfo {
+                    name: grpc.name.clone(),
+                    field_type: ContextFieldType::Grpc,
+                    env_var: String::new(),
+                    is_async: true, // The channel connects on the async runtime
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: Some(grpc.options.clone()),
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Email(email) => ContextFieldInfo {
+                    name: email.name.clone(),
+                    field_type: ContextFieldType::Email,
+                    env_var: String::new(), // Credentials name their own variables
+                    is_async: true,         // Mail is sent on the async runtime
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: Some(email.options.clone()),
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Config(config) => ContextFieldInfo {
+                    name: config.name.clone(),
+                    field_type: ContextFieldType::Config,
+                    env_var: String::new(),
+                    is_async: false, // The file is read synchronously at startup
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: Some(config.options.clone()),
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Secrets(secrets) => ContextFieldInfo {
+                    name: secrets.name.clone(),
+                    field_type: ContextFieldType::Secrets,
+                    env_var: String::new(),
+                    is_async: false, // Secrets are looked up on demand
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: Some(secrets.options.clone()),
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Llm(llm) => ContextFieldInfo {
+                    name: llm.name.clone(),
+                    field_type: ContextFieldType::Llm,
+                    env_var: llm.options.api_key_env.clone(),
+                    is_async: true, // Requests run on the async runtime
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: Some(llm.options.clone()),
+                    cache: None,
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Cache(cache) => ContextFieldInfo {
+                    name: cache.name.clone(),
+                    field_type: ContextFieldType::Cache,
+                    env_var: String::new(),
+                    is_async: true, // Lookups are awaited
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: Some(cache.options.clone()),
+                    workdir: None,
+                    custom: None,
+                },
+                Resource::Workdir(workdir) => ContextFieldInfo {
+                    name: workdir.name.clone(),
+                    field_type: ContextFieldType::Workdir,
+                    env_var: String::new(),
+                    is_async: false,
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: Some(workdir.options.clone()),
+                    custom: None,
+                },
+                Resource::Queue(queue) => ContextFieldInfo {
+                    name: queue.name.clone(),
+                    field_type: ContextFieldType::Queue(queue.queue_type),
+                    env_var: queue.env_var.clone(),
+                    is_async: true, // Clients are used from async handlers
+                    pool: PoolConfig::default(),
+                    sqlite: None,
+                    duckdb: None,
+                    http: None,
+                    s3: None,
+                    grpc: None,
+                    email: None,
+                    config: None,
+                    secrets: None,
+                    llm: None,
+                    cache: None,
+                    workdir: None,
+                    custom: None,
                 },
             })
             .collect()
@@ -111,7 +537,8 @@ impl AppIR {
 }
 
 /// Application metadata.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AppMeta {
     /// Application name.
     pub name: String,
@@ -121,23 +548,144 @@ pub struct AppMeta {
     pub description: Option<String>,
     /// Author information.
     pub author: Option<String>,
+    /// Documentation metadata.
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
+    /// Whether `--version` shows the git commit and build time.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub build_info: bool,
+    /// Whether a hidden `docs` subcommand writes the CLI's own docs.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub docs_command: bool,
+    /// Whether a `doctor` subcommand checks the context's prerequisites.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub doctor_command: bool,
+    /// Whether unknown commands run `<name>-<command>` from PATH.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external_subcommands: bool,
+    /// Command run when the CLI is invoked without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_command: Option<String>,
+    /// License header prepended to every generated source file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+}
+
+/// Documentation metadata for the application or a command.
+///
+/// Carries everything docs/man generation and extended help need, so those
+/// can be driven from the IR alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Docs {
+    /// Extended description, shown in long help and generated docs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
+    /// Usage examples.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Example>,
+    /// Text shown at the end of the help, after the examples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    /// Category used to group commands in help output and docs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Authors or maintainers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+}
+
+impl Docs {
+    /// Returns true if no documentation metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Translated help text for one locale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Locale {
+    /// Locale name as written in the manifest (e.g., "fr", "pt-BR").
+    pub name: String,
+    /// Translated descriptions.
+    pub messages: Vec<Message>,
+}
+
+/// A translated description of the application, a command or an input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /// Path of the command described; empty for the application and its
+    /// global inputs.
+    pub command: Vec<String>,
+    /// Name of the input described, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// Translated text.
+    pub text: String,
+}
+
+/// A usage example.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Example {
+    /// Command line (e.g., "mycli deploy prod --force").
+    pub command: String,
+    /// What the example does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Example {
+    /// Create an example from a command line.
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            description: None,
+        }
+    }
 }
 
 /// A shared resource in the application context.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Resource {
     /// Database connection pool.
     Database(DatabaseResource),
     /// HTTP client.
     HttpClient(HttpClientResource),
+    /// Application logger.
+    Logger(LoggerResource),
+    /// Object storage bucket.
+    Storage(StorageResource),
+    /// gRPC client channel.
+    Grpc(GrpcResource),
+    /// Message queue connection.
+    Queue(QueueResource),
+    /// SMTP transport.
+    Email(EmailResource),
+    /// Typed config file.
+    Config(ConfigResource),
+    /// Secrets accessor.
+    Secrets(SecretsResource),
+    /// LLM API client.
+    Llm(LlmResource),
+    /// In-memory cache.
+    Cache(CacheResource),
+    /// Data and scratch directories.
+    Workdir(WorkdirResource),
+    /// Resource declared by a plugin and rendered by its adapter.
+    Custom(CustomResource),
 }
 
 /// Database resource configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DatabaseResource {
     /// Field name in the context struct.
     pub name: String,
-    /// Database type (Postgres, MySQL, SQLite).
+    /// Database type (Postgres, MySQL, SQLite, DuckDB).
     pub db_type: DatabaseType,
     /// Environment variable for the connection string.
     pub env_var: String,
@@ -145,25 +693,170 @@ pub struct DatabaseResource {
     pub pool: PoolConfig,
     /// SQLite-specific options.
     pub sqlite: Option<SqliteOptions>,
+    /// DuckDB-specific options.
+    pub duckdb: Option<DuckdbOptions>,
 }
 
 /// HTTP client resource configuration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct HttpClientResource {
     /// Field name in the context struct.
     pub name: String,
+    /// Client options.
+    #[serde(default)]
+    pub options: HttpOptions,
+}
+
+/// Logger resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LoggerResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Minimum level emitted by default.
+    pub level: LogLevel,
+    /// Output format used by default.
+    pub format: LogFormat,
+    /// Default filter in `RUST_LOG` syntax, replacing the level.
+    #[serde(default)]
+    pub env_filter: Option<String>,
+}
+
+impl LoggerResource {
+    /// The filter applied unless the environment overrides it.
+    pub fn default_filter(&self) -> &str {
+        self.env_filter.as_deref().unwrap_or(self.level.as_str())
+    }
+}
+
+/// Object storage resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct StorageResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// S3 bucket options.
+    pub s3: S3Options,
+}
+
+/// gRPC client resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct GrpcResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Channel options.
+    pub options: GrpcOptions,
+}
+
+/// Message queue resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct QueueResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Queue type (NATS, Kafka).
+    pub queue_type: QueueType,
+    /// Environment variable for the server URL or broker list.
+    pub env_var: String,
+}
+
+/// SMTP transport resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EmailResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Transport options.
+    pub options: EmailOptions,
+}
+
+/// Config file resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ConfigResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// File options.
+    pub options: ConfigOptions,
+}
+
+/// Secrets resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SecretsResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Backend options.
+    pub options: SecretsOptions,
+}
+
+/// LLM client resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LlmResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Client options.
+    pub options: LlmOptions,
+}
+
+/// Cache resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CacheResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Cache options.
+    pub options: CacheOptions,
+}
+
+/// Working directory resource configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct WorkdirResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Directory options.
+    pub options: WorkdirOptions,
+}
+
+/// A plugin-defined resource.
+///
+/// Bao doesn't interpret `config`; generators hand the resource to the
+/// adapter registered for `kind`, which renders its type and initialization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CustomResource {
+    /// Field name in the context struct.
+    pub name: String,
+    /// Resource kind, used to find the adapter (e.g., "queue").
+    pub kind: String,
+    /// Adapter-specific configuration.
+    #[serde(default)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(value = serde_json::Value::Null))]
+    pub config: serde_json::Value,
+    /// Whether initialization is async.
+    #[serde(default)]
+    pub is_async: bool,
 }
 
 /// An operation in the application.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+// Operations are built once per run; boxing commands isn't worth the noise
+#[allow(clippy::large_enum_variant)]
 pub enum Operation {
     /// CLI command.
     Command(CommandOp),
-    // Future: Route(RouteOp),
+    /// HTTP route.
+    Route(RouteOp),
 }
 
 /// A CLI command operation.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CommandOp {
     /// Command name.
     pub name: String,
@@ -171,10 +864,35 @@ pub struct CommandOp {
     pub path: Vec<String>,
     /// Command description.
     pub description: String,
+    /// Other names the command can be invoked by.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Deprecation message printed when the command is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
     /// Input parameters (args and flags).
     pub inputs: Vec<Input>,
+    /// Groups of inputs that conflict with each other or of which one is
+    /// required.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<InputGroup>,
     /// Child commands (subcommands).
     pub children: Vec<CommandOp>,
+    /// Child run when the command is invoked without a subcommand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_command: Option<String>,
+    /// Exit codes for handler errors, keyed by error name.
+    pub exit_codes: BTreeMap<String, u8>,
+    /// Context fields the handler uses, including those required by its
+    /// parents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Documentation metadata.
+    #[serde(default, skip_serializing_if = "Docs::is_empty")]
+    pub docs: Docs,
+    /// Where the command is declared in the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
 }
 
 impl CommandOp {
@@ -189,8 +907,61 @@ impl CommandOp {
     }
 }
 
+/// A named group of a command's inputs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct InputGroup {
+    /// Group name.
+    pub name: String,
+    /// Names of the member inputs.
+    pub members: Vec<String>,
+    /// At most one member may be given.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive: bool,
+    /// At least one member must be given.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+}
+
+/// An HTTP route operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RouteOp {
+    /// Route name.
+    pub name: String,
+    /// HTTP method.
+    pub method: HttpMethod,
+    /// URL path with `{name}` placeholders (e.g., "/users/{id}").
+    pub path: String,
+    /// Route description.
+    pub description: Option<String>,
+    /// Path parameters as [`InputKind::Positional`] inputs in path order,
+    /// followed by query parameters as [`InputKind::Flag`] inputs.
+    pub inputs: Vec<Input>,
+    /// Where the route is declared in the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+}
+
+impl RouteOp {
+    /// Path parameters, in the order they appear in the path.
+    pub fn path_params(&self) -> impl Iterator<Item = &Input> {
+        self.inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Positional))
+    }
+
+    /// Query string parameters.
+    pub fn query_params(&self) -> impl Iterator<Item = &Input> {
+        self.inputs
+            .iter()
+            .filter(|i| matches!(i.kind, InputKind::Flag { .. }))
+    }
+}
+
 /// An input parameter for a command.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Input {
     /// Parameter name.
     pub name: String,
@@ -204,22 +975,174 @@ pub struct Input {
     pub default: Option<DefaultValue>,
     /// Description for help text.
     pub description: Option<String>,
+    /// Extended description shown in full help after `description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
     /// Allowed choices (creates enum in generated code).
     pub choices: Option<Vec<String>>,
+    /// Whether the input accepts several values.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub multiple: bool,
+    /// Character splitting one occurrence of a multiple flag into several values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<char>,
+    /// Whether a positional input takes values starting with a dash, such as `-5`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_hyphen_values: bool,
+    /// Interactive prompt used when the input is not provided.
+    pub prompt: Option<InputPrompt>,
+    /// Kind of value, used by shell completion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<InputHint>,
+    /// Validation rules generators check the value against.
+    #[serde(default, skip_serializing_if = "InputConstraints::is_empty")]
+    pub constraints: InputConstraints,
+    /// Inputs that must also be given when this one is.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Flag value that makes this input required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_if: Option<RequiredIf>,
+    /// Deprecation message printed when the input is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Where the input is declared in the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+}
+
+impl Input {
+    /// Message asking for the input when it is missing, if it has one.
+    pub fn prompt_message(&self) -> Option<&str> {
+        match &self.prompt {
+            Some(InputPrompt::Text(message)) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Whether a missing value is filled in after parsing, from the
+    /// environment or a prompt, so the parser must accept it being left out.
+    pub fn asks_when_missing(&self) -> bool {
+        self.ty == InputType::Secret || self.prompt_message().is_some()
+    }
+}
+
+/// Validation rules for an input's value.
+///
+/// Bounds are inclusive. Numeric bounds apply to int and float inputs,
+/// length bounds and `pattern` to string inputs, and `path_exists` to path
+/// inputs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(default)]
+pub struct InputConstraints {
+    /// Smallest allowed numeric value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Largest allowed numeric value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Regular expression the whole value must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Smallest allowed length, in characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Largest allowed length, in characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// The path must exist when the command runs.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub path_exists: bool,
+}
+
+impl InputConstraints {
+    /// Returns true if no constraint is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns true if a numeric range is set.
+    pub fn has_range(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+
+    /// Returns true if a length bound is set.
+    pub fn has_length(&self) -> bool {
+        self.min_length.is_some() || self.max_length.is_some()
+    }
+}
+
+/// Condition under which an input is required.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RequiredIf {
+    /// Name of the string flag to test.
+    pub flag: String,
+    /// Value of the flag that makes the input required.
+    pub value: String,
+}
+
+/// Interactive prompt for an input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum InputPrompt {
+    /// Ask for confirmation unless the bool flag is set.
+    Confirm,
+    /// Read a string without echoing it.
+    Secret,
+    /// Ask for a missing argument with this message, unless the
+    /// [`NO_INPUT_FLAG`] global flag is given.
+    Text(String),
+}
+
+/// Global flag turning off the prompts for missing arguments, added when any
+/// argument has a prompt message.
+pub const NO_INPUT_FLAG: &str = "no-input";
+
+/// Kind of value an input takes, used by shell completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum InputHint {
+    /// Path to a file.
+    File,
+    /// Path to a directory.
+    Dir,
+    /// Host name.
+    Hostname,
+    /// URL.
+    Url,
+    /// Name of a command on the `PATH`.
+    Command,
 }
 
 /// Input parameter type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum InputType {
     String,
     Int,
     Float,
     Bool,
     Path,
+    /// Number of times a flag is given.
+    Count,
+    /// Absolute URL.
+    Url,
+    /// Length of time, such as `1h30m`.
+    Duration,
+    /// RFC 3339 date and time, such as `2024-05-01T12:00:00Z`.
+    DateTime,
+    /// UUID, such as `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Uuid,
+    /// Text such as a password, read from an environment variable or a
+    /// hidden prompt when not given.
+    Secret,
 }
 
 /// Input parameter kind.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum InputKind {
     /// Positional argument.
     Positional,
@@ -231,22 +1154,67 @@ pub enum InputKind {
 }
 
 /// A default value for an input.
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DefaultValue {
     String(String),
     Int(i64),
     Float(f64),
     Bool(bool),
+    /// A filesystem path default.
+    Path(String),
+    /// An array default (`default = ["a", "b"]`).
+    List(Vec<DefaultValue>),
+    /// An inline-table default, in declaration order.
+    Table(Vec<(String, DefaultValue)>),
 }
 
 impl DefaultValue {
     /// Convert to a string representation suitable for code generation.
+    ///
+    /// Lists render as `a,b` and tables as `key=value` pairs, the form the
+    /// value takes on the command line.
     pub fn to_code_string(&self) -> String {
         match self {
-            Self::String(s) => s.clone(),
+            Self::String(s) | Self::Path(s) => s.clone(),
             Self::Int(i) => i.to_string(),
             Self::Float(f) => f.to_string(),
             Self::Bool(b) => b.to_string(),
+            Self::List(items) => items
+                .iter()
+                .map(Self::to_code_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            Self::Table(entries) => entries
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v.to_code_string()))
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+
+    /// Convert to the variant matching an input's type.
+    ///
+    /// Integers become floats for float inputs, strings become paths for path
+    /// inputs, and scalars become strings for string inputs. Lists and tables
+    /// are converted element-wise; other mismatches are left unchanged.
+    pub fn coerce(self, ty: InputType) -> Self {
+        match (self, ty) {
+            (Self::Int(i), InputType::Float) => Self::Float(i as f64),
+            (Self::String(s), InputType::Path) => Self::Path(s),
+            (value @ (Self::Int(_) | Self::Float(_) | Self::Bool(_)), InputType::String) => {
+                Self::String(value.to_code_string())
+            }
+            (Self::List(items), ty) => {
+                Self::List(items.into_iter().map(|item| item.coerce(ty)).collect())
+            }
+            (Self::Table(entries), ty) => Self::Table(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, value.coerce(ty)))
+                    .collect(),
+            ),
+            (value, _) => value,
         }
     }
 }
@@ -255,6 +1223,30 @@ impl DefaultValue {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_value_coerce() {
+        assert_eq!(
+            DefaultValue::Int(4).coerce(InputType::Float),
+            DefaultValue::Float(4.0)
+        );
+        assert_eq!(
+            DefaultValue::Int(4).coerce(InputType::String),
+            DefaultValue::String("4".into())
+        );
+        assert_eq!(
+            DefaultValue::String("out".into()).coerce(InputType::Path),
+            DefaultValue::Path("out".into())
+        );
+        assert_eq!(
+            DefaultValue::List(vec![DefaultValue::Int(1)]).coerce(InputType::Float),
+            DefaultValue::List(vec![DefaultValue::Float(1.0)])
+        );
+        assert_eq!(
+            DefaultValue::Bool(true).coerce(InputType::Int),
+            DefaultValue::Bool(true)
+        );
+    }
+
     #[test]
     fn test_command_has_subcommands() {
         let cmd = CommandOp {
@@ -262,7 +1254,15 @@ mod tests {
             path: vec!["test".into()],
             description: "A test command".into(),
             inputs: vec![],
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
             children: vec![],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
         };
         assert!(!cmd.has_subcommands());
 
@@ -271,7 +1271,15 @@ mod tests {
             path: vec!["parent".into()],
             description: "A parent command".into(),
             inputs: vec![],
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
             children: vec![cmd],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
         };
         assert!(parent.has_subcommands());
     }
@@ -283,7 +1291,15 @@ mod tests {
             path: vec!["users".into(), "create".into()],
             description: "Create a user".into(),
             inputs: vec![],
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
             children: vec![],
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
         };
         assert_eq!(cmd.handler_path(), "users/create");
     }
@@ -296,5 +1312,17 @@ mod tests {
         );
         assert_eq!(DefaultValue::Int(42).to_code_string(), "42");
         assert_eq!(DefaultValue::Bool(true).to_code_string(), "true");
+        assert_eq!(
+            DefaultValue::List(vec![
+                DefaultValue::String("a".into()),
+                DefaultValue::String("b".into())
+            ])
+            .to_code_string(),
+            "a,b"
+        );
+        assert_eq!(
+            DefaultValue::Table(vec![("retries".into(), DefaultValue::Int(3))]).to_code_string(),
+            "retries=3"
+        );
     }
 }
@@ -13,16 +13,33 @@
 //! The IR types are designed to be:
 //! - Language-agnostic (no Rust/TypeScript-specific concerns)
 //! - Application-type agnostic (CLI, HTTP server, etc.)
-//! - Serializable for debugging and visualization
+//! - Serializable to JSON and back, for debugging, diffing and external tooling
+//!
+//! # Features
+//!
+//! - `arbitrary` - [`arbitrary::Arbitrary`] for every IR type, for fuzzing
+//!   code that consumes the IR
 
 mod app;
 mod resource;
 mod serde_helpers;
+mod span;
 mod types;
+mod visit;
 
 pub use app::{
-    AppIR, AppMeta, CommandOp, DatabaseResource, DefaultValue, HttpClientResource, Input,
-    InputKind, InputType, Operation, Resource,
+    AppIR, AppMeta, CacheResource, CommandOp, ConfigResource, CustomResource, DatabaseResource,
+    DefaultValue, Docs, EmailResource, Example, GrpcResource, HttpClientResource, IR_VERSION,
+    Input, InputConstraints, InputGroup, InputHint, InputKind, InputPrompt, InputType, LlmResource,
+    Locale, LoggerResource, Message, NO_INPUT_FLAG, Operation, QueueResource, RequiredIf, Resource,
+    RouteOp, SecretsResource, StorageResource, WorkdirResource,
+};
+pub use resource::{
+    CacheMode, CacheOptions, ConfigField, ConfigFieldType, ConfigFormat, ConfigOptions,
+    DuckdbOptions, EmailOptions, GrpcOptions, HttpAuth, HttpOptions, JournalMode, LlmOptions,
+    LlmProvider, LogFormat, LogLevel, PoolConfig, S3Options, SecretsBackend, SecretsOptions,
+    SmtpTls, SqliteOptions, SynchronousMode, WorkdirOptions,
 };
-pub use resource::{JournalMode, PoolConfig, SqliteOptions, SynchronousMode};
-pub use types::{ContextFieldInfo, ContextFieldType, DatabaseType};
+pub use span::SourceSpan;
+pub use types::{ContextFieldInfo, ContextFieldType, DatabaseType, HttpMethod, QueueType};
+pub use visit::{IrVisitor, walk_app, walk_command, walk_route};
@@ -1,33 +1,34 @@
 //! Resource configuration types.
 //!
 //! These types represent the unified configuration for database pools,
-//! SQLite options, and other resources. They serve as the single source
+//! SQLite and DuckDB options, and other resources. They serve as the single source
 //! of truth for code generation, eliminating duplication between crates.
 
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::serde_helpers::serialize_option_duration;
+use crate::serde_helpers::option_duration_millis;
 
 /// Connection pool configuration.
 ///
 /// This is the unified type for pool configuration, replacing the duplicate
 /// `PoolConfigInfo` (bao-codegen) and `PoolConfig` (bao-codegen adapters).
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PoolConfig {
     /// Maximum number of connections in the pool.
     pub max_connections: Option<u32>,
     /// Minimum number of connections to maintain.
     pub min_connections: Option<u32>,
     /// Timeout for acquiring a connection from the pool (milliseconds).
-    #[serde(serialize_with = "serialize_option_duration")]
+    #[serde(with = "option_duration_millis")]
     pub acquire_timeout: Option<Duration>,
     /// Maximum time a connection can remain idle before being closed (milliseconds).
-    #[serde(serialize_with = "serialize_option_duration")]
+    #[serde(with = "option_duration_millis")]
     pub idle_timeout: Option<Duration>,
     /// Maximum lifetime of a connection (milliseconds).
-    #[serde(serialize_with = "serialize_option_duration")]
+    #[serde(with = "option_duration_millis")]
     pub max_lifetime: Option<Duration>,
 }
 
@@ -46,7 +47,8 @@ impl PoolConfig {
 ///
 /// This is the unified type for SQLite configuration, replacing the duplicate
 /// `SqliteConfigInfo` (bao-codegen) and `SqliteConfig` (bao-codegen adapters).
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SqliteOptions {
     /// Direct file path to the SQLite database.
     pub path: Option<String>,
@@ -59,10 +61,12 @@ pub struct SqliteOptions {
     /// Synchronous mode.
     pub synchronous: Option<SynchronousMode>,
     /// Busy timeout (milliseconds).
-    #[serde(serialize_with = "serialize_option_duration")]
+    #[serde(with = "option_duration_millis")]
     pub busy_timeout: Option<Duration>,
     /// Enable foreign key constraints.
     pub foreign_keys: Option<bool>,
+    /// Directory containing SQL migration files.
+    pub migrations: Option<String>,
 }
 
 impl SqliteOptions {
@@ -77,8 +81,424 @@ impl SqliteOptions {
     }
 }
 
+/// DuckDB-specific configuration options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct DuckdbOptions {
+    /// Direct file path to the DuckDB database, or `:memory:`.
+    pub path: Option<String>,
+    /// Open the database in read-only mode.
+    pub read_only: Option<bool>,
+    /// Number of threads queries run on.
+    pub threads: Option<u32>,
+}
+
+impl DuckdbOptions {
+    /// Returns true if any DuckDB-specific option is configured.
+    pub fn has_config(&self) -> bool {
+        self.read_only.is_some() || self.threads.is_some()
+    }
+}
+
+/// HTTP client options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HttpOptions {
+    /// Base URL that request paths are resolved against.
+    pub base_url: Option<String>,
+    /// Headers sent with every request, by name.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Request timeout (milliseconds).
+    #[serde(with = "option_duration_millis")]
+    pub timeout: Option<Duration>,
+    /// User agent string.
+    pub user_agent: Option<String>,
+    /// Times a failed request is retried.
+    #[serde(default)]
+    pub retries: u32,
+    /// Delay before the first retry, doubled for each retry (milliseconds).
+    #[serde(default, with = "option_duration_millis")]
+    pub backoff: Option<Duration>,
+    /// Response statuses that are retried; empty retries 408, 429 and 5xx.
+    #[serde(default)]
+    pub retry_on: Vec<u16>,
+    /// Credentials attached to every request.
+    pub auth: Option<HttpAuth>,
+}
+
+impl HttpOptions {
+    /// Returns true if any option is configured.
+    pub fn has_config(&self) -> bool {
+        self.base_url.is_some()
+            || !self.headers.is_empty()
+            || self.timeout.is_some()
+            || self.user_agent.is_some()
+            || self.has_retries()
+            || self.auth.is_some()
+    }
+
+    /// Returns true if failed requests are retried.
+    pub fn has_retries(&self) -> bool {
+        self.retries > 0
+    }
+}
+
+/// HTTP client authentication, with the secret read from an environment
+/// variable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HttpAuth {
+    /// `Authorization: Bearer <token>`.
+    Bearer { env: String },
+    /// `Authorization: Basic <base64(username:password)>`.
+    Basic { username: String, env: String },
+    /// A custom header carrying the secret.
+    Header { name: String, env: String },
+}
+
+impl HttpAuth {
+    /// The environment variable holding the secret.
+    pub fn env(&self) -> &str {
+        match self {
+            Self::Bearer { env } | Self::Basic { env, .. } | Self::Header { env, .. } => env,
+        }
+    }
+
+    /// The header the credentials are sent in.
+    pub fn header(&self) -> &str {
+        match self {
+            Self::Bearer { .. } | Self::Basic { .. } => "Authorization",
+            Self::Header { name, .. } => name,
+        }
+    }
+}
+
+/// S3-compatible object storage options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct S3Options {
+    /// Bucket name.
+    pub bucket: String,
+    /// Region, read from `AWS_REGION` when unset.
+    pub region: Option<String>,
+    /// Endpoint URL of an S3-compatible service.
+    pub endpoint: Option<String>,
+}
+
+impl S3Options {
+    /// Returns true if the endpoint is plain HTTP, which clients refuse by default.
+    pub fn allows_http(&self) -> bool {
+        self.endpoint
+            .as_deref()
+            .is_some_and(|endpoint| endpoint.starts_with("http://"))
+    }
+}
+
+/// gRPC client channel options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct GrpcOptions {
+    /// Server endpoint.
+    pub endpoint: String,
+    /// `.proto` files that clients are generated from.
+    #[serde(default)]
+    pub protos: Vec<String>,
+    /// Directories searched for imported `.proto` files.
+    #[serde(default)]
+    pub includes: Vec<String>,
+    /// Request timeout (milliseconds).
+    #[serde(default, with = "option_duration_millis")]
+    pub timeout: Option<Duration>,
+}
+
+impl GrpcOptions {
+    /// Returns true if the endpoint uses TLS.
+    pub fn uses_tls(&self) -> bool {
+        self.endpoint.starts_with("https://")
+    }
+
+    /// Returns true if clients are generated from `.proto` files.
+    pub fn has_protos(&self) -> bool {
+        !self.protos.is_empty()
+    }
+}
+
+/// SMTP transport options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EmailOptions {
+    /// SMTP server host name.
+    pub host: String,
+    /// SMTP server port.
+    pub port: u16,
+    /// Connection encryption.
+    #[serde(default)]
+    pub tls: SmtpTls,
+    /// Environment variable holding the username.
+    pub username_env: Option<String>,
+    /// Environment variable holding the password.
+    pub password_env: Option<String>,
+}
+
+impl EmailOptions {
+    /// The username and password variables, if the transport authenticates.
+    pub fn credentials(&self) -> Option<(&str, &str)> {
+        Some((self.username_env.as_deref()?, self.password_env.as_deref()?))
+    }
+}
+
+/// Typed config file options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ConfigOptions {
+    /// Path of the file, relative to the working directory.
+    pub path: String,
+    /// File format.
+    pub format: ConfigFormat,
+    /// Fields of the file, by key.
+    #[serde(default)]
+    pub fields: BTreeMap<String, ConfigField>,
+}
+
+/// Config file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Json => "json",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// A field of the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ConfigField {
+    /// Value type.
+    pub field_type: ConfigFieldType,
+    /// Whether the key may be left out of the file.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+/// Value type of a config field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ConfigFieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+/// Cache options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CacheOptions {
+    /// Maximum number of entries.
+    pub max_capacity: u64,
+    /// Time an entry lives after it is inserted (milliseconds).
+    #[serde(with = "option_duration_millis")]
+    pub ttl: Option<Duration>,
+    /// Where entries are kept.
+    #[serde(default)]
+    pub mode: CacheMode,
+    /// Directory of the disk cache, if set explicitly.
+    pub path: Option<String>,
+    /// Subdirectory of the user cache directory used when `path` is unset.
+    #[serde(default)]
+    pub namespace: String,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            max_capacity: 1000,
+            ttl: None,
+            mode: CacheMode::default(),
+            path: None,
+            namespace: String::new(),
+        }
+    }
+}
+
+/// Where cache entries are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CacheMode {
+    /// In memory, for a single invocation.
+    #[default]
+    Memory,
+    /// In memory, backed by files that outlive the invocation.
+    Disk,
+}
+
+impl CacheMode {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheMode::Memory => "memory",
+            CacheMode::Disk => "disk",
+        }
+    }
+}
+
+/// Working directory options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct WorkdirOptions {
+    /// Keep a data directory that outlives the invocation.
+    pub data: bool,
+    /// Create a scratch directory, removed when the invocation ends.
+    pub temp: bool,
+    /// Data directory, if set explicitly.
+    pub path: Option<String>,
+    /// Subdirectory of the user data directory used when `path` is unset.
+    #[serde(default)]
+    pub namespace: String,
+}
+
+impl Default for WorkdirOptions {
+    fn default() -> Self {
+        Self {
+            data: true,
+            temp: false,
+            path: None,
+            namespace: String::new(),
+        }
+    }
+}
+
+/// LLM API client options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LlmOptions {
+    /// API provider.
+    pub provider: LlmProvider,
+    /// Model requests are sent to.
+    pub model: String,
+    /// Environment variable holding the API key.
+    pub api_key_env: String,
+    /// Base URL of the API.
+    pub base_url: String,
+    /// Maximum number of tokens in a reply.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+impl LlmOptions {
+    /// Default reply limit, since the Anthropic API requires one.
+    pub const DEFAULT_MAX_TOKENS: u32 = 1024;
+}
+
+/// LLM API provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LlmProvider {
+    /// OpenAI Chat Completions API.
+    Openai,
+    /// Anthropic Messages API.
+    Anthropic,
+}
+
+impl LlmProvider {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LlmProvider::Openai => "openai",
+            LlmProvider::Anthropic => "anthropic",
+        }
+    }
+}
+
+/// Secrets accessor options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SecretsOptions {
+    /// Where secrets are read from.
+    pub backend: SecretsBackend,
+    /// Keyring service name.
+    pub service: String,
+    /// Path of the secrets file, relative to the working directory.
+    pub path: String,
+    /// Prefix of the environment variables.
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Secrets backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SecretsBackend {
+    /// The OS keyring, falling back to environment variables.
+    #[default]
+    Keyring,
+    /// Environment variables.
+    Env,
+    /// A file of `NAME=value` lines.
+    File,
+}
+
+impl SecretsBackend {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretsBackend::Keyring => "keyring",
+            SecretsBackend::Env => "env",
+            SecretsBackend::File => "file",
+        }
+    }
+}
+
+/// SMTP connection encryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SmtpTls {
+    /// Upgrade with STARTTLS, failing if the server doesn't support it.
+    #[default]
+    Starttls,
+    /// Implicit TLS.
+    Tls,
+    /// Unencrypted.
+    None,
+}
+
+impl SmtpTls {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SmtpTls::Starttls => "starttls",
+            SmtpTls::Tls => "tls",
+            SmtpTls::None => "none",
+        }
+    }
+
+    /// The conventional port for this encryption.
+    pub fn default_port(&self) -> u16 {
+        match self {
+            SmtpTls::Starttls => 587,
+            SmtpTls::Tls => 465,
+            SmtpTls::None => 25,
+        }
+    }
+}
+
 /// SQLite journal mode.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum JournalMode {
     #[default]
     Wal,
@@ -104,7 +524,8 @@ impl JournalMode {
 }
 
 /// SQLite synchronous mode.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SynchronousMode {
     Off,
     Normal,
@@ -125,6 +546,50 @@ impl SynchronousMode {
     }
 }
 
+/// Minimum log level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +631,54 @@ mod tests {
         assert!(!with_path_only.has_config());
     }
 
+    #[test]
+    fn test_s3_options_allows_http() {
+        let mut options = S3Options {
+            bucket: "uploads".to_string(),
+            ..Default::default()
+        };
+        assert!(!options.allows_http());
+
+        options.endpoint = Some("https://s3.example.com".to_string());
+        assert!(!options.allows_http());
+
+        options.endpoint = Some("http://localhost:9000".to_string());
+        assert!(options.allows_http());
+    }
+
+    #[test]
+    fn test_grpc_options_uses_tls() {
+        let mut options = GrpcOptions {
+            endpoint: "http://localhost:50051".to_string(),
+            ..Default::default()
+        };
+        assert!(!options.uses_tls());
+        assert!(!options.has_protos());
+
+        options.endpoint = "https://api.example.com".to_string();
+        options.protos = vec!["proto/greeter.proto".to_string()];
+        assert!(options.uses_tls());
+        assert!(options.has_protos());
+    }
+
+    #[test]
+    fn test_email_options_credentials() {
+        let mut options = EmailOptions {
+            host: "smtp.example.com".to_string(),
+            port: SmtpTls::Starttls.default_port(),
+            username_env: Some("SMTP_USERNAME".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(options.port, 587);
+        assert_eq!(options.credentials(), None);
+
+        options.password_env = Some("SMTP_PASSWORD".to_string());
+        assert_eq!(
+            options.credentials(),
+            Some(("SMTP_USERNAME", "SMTP_PASSWORD"))
+        );
+    }
+
     #[test]
     fn test_journal_mode_as_str() {
         assert_eq!(JournalMode::Wal.as_str(), "Wal");
@@ -1,16 +1,22 @@
 //! Serde helpers for types that don't impl Serialize by default.
 
-use std::time::Duration;
+/// (De)serialize an `Option<Duration>` as optional milliseconds.
+pub mod option_duration_millis {
+    use std::time::Duration;
 
-use serde::{Serialize, Serializer};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-/// Serialize an Option<Duration> as Option<milliseconds>.
-pub fn serialize_option_duration<S>(
-    duration: &Option<Duration>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    duration.map(|d| d.as_millis()).serialize(serializer)
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        duration.map(|d| d.as_millis() as u64).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+    }
 }
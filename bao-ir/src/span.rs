@@ -0,0 +1,71 @@
+//! Source locations of IR nodes.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A range of a source file that an IR node was lowered from.
+///
+/// `start` and `end` are byte offsets; `line` and `column` are 1-based and
+/// point at `start`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SourceSpan {
+    /// File name, as given to the manifest parser.
+    pub file: String,
+    /// Byte offset of the first character.
+    pub start: usize,
+    /// Byte offset one past the last character.
+    pub end: usize,
+    /// Line of `start`.
+    pub line: usize,
+    /// Column of `start`, in characters.
+    pub column: usize,
+}
+
+impl SourceSpan {
+    /// Create a span for the byte range `start..end` of `src`.
+    pub fn new(file: impl Into<String>, src: &str, start: usize, end: usize) -> Self {
+        let before = &src[..start.min(src.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            file: file.into(),
+            start,
+            end,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Length of the span in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the span covers no characters.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_and_column() {
+        let src = "[cli]\nname = \"app\"\n\n[commands.héllo]\n";
+        let start = src.find("héllo").unwrap();
+        let span = SourceSpan::new("bao.toml", src, start, start + "héllo".len());
+
+        assert_eq!((span.line, span.column), (4, 11));
+        assert_eq!(span.len(), 6);
+        assert_eq!(span.to_string(), "bao.toml:4:11");
+    }
+}
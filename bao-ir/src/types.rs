@@ -1,15 +1,20 @@
 //! Core type definitions.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{PoolConfig, SqliteOptions};
+use crate::{
+    CacheOptions, ConfigOptions, CustomResource, DuckdbOptions, EmailOptions, GrpcOptions,
+    HttpOptions, LlmOptions, PoolConfig, S3Options, SecretsOptions, SqliteOptions, WorkdirOptions,
+};
 
 /// Database type for context fields.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DatabaseType {
     Postgres,
     Mysql,
     Sqlite,
+    Duckdb,
 }
 
 impl DatabaseType {
@@ -19,28 +24,107 @@ impl DatabaseType {
             DatabaseType::Postgres => "postgres",
             DatabaseType::Mysql => "mysql",
             DatabaseType::Sqlite => "sqlite",
+            DatabaseType::Duckdb => "duckdb",
+        }
+    }
+}
+
+/// Message queue type for context fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum QueueType {
+    Nats,
+    Kafka,
+}
+
+impl QueueType {
+    /// Get the lowercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueType::Nats => "nats",
+            QueueType::Kafka => "kafka",
+        }
+    }
+}
+
+/// HTTP request method of a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+    Options,
+}
+
+impl HttpMethod {
+    /// Get the uppercase string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Options => "OPTIONS",
         }
     }
 }
 
 /// Context field type - language-agnostic representation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ContextFieldType {
     /// Database connection pool.
     Database(DatabaseType),
     /// HTTP client.
     Http,
+    /// Application logger.
+    Logger,
+    /// Object storage bucket.
+    Storage,
+    /// gRPC client channel.
+    Grpc,
+    /// Message queue connection.
+    Queue(QueueType),
+    /// SMTP transport.
+    Email,
+    /// Typed config file.
+    Config,
+    /// Secrets accessor.
+    Secrets,
+    /// LLM API client.
+    Llm,
+    /// In-memory cache.
+    Cache,
+    /// Data and scratch directories.
+    Workdir,
+    /// Plugin-defined resource (see [`ContextFieldInfo::custom`]).
+    Custom,
 }
 
 impl ContextFieldType {
     /// Returns true if this field type requires async initialization.
     pub fn is_async(&self) -> bool {
-        matches!(self, ContextFieldType::Database(_))
+        matches!(
+            self,
+            ContextFieldType::Database(_)
+                | ContextFieldType::Grpc
+                | ContextFieldType::Queue(_)
+                | ContextFieldType::Email
+                | ContextFieldType::Llm
+                | ContextFieldType::Cache
+        )
     }
 }
 
 /// Info about a context field for code generation.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ContextFieldInfo {
     /// Field name in the context struct.
     pub name: String,
@@ -54,6 +138,70 @@ pub struct ContextFieldInfo {
     pub pool: PoolConfig,
     /// SQLite-specific options.
     pub sqlite: Option<SqliteOptions>,
+    /// DuckDB-specific options.
+    pub duckdb: Option<DuckdbOptions>,
+    /// Client options, for [`ContextFieldType::Http`] fields.
+    pub http: Option<HttpOptions>,
+    /// S3 bucket options, for [`ContextFieldType::Storage`] fields.
+    pub s3: Option<S3Options>,
+    /// Channel options, for [`ContextFieldType::Grpc`] fields.
+    pub grpc: Option<GrpcOptions>,
+    /// Transport options, for [`ContextFieldType::Email`] fields.
+    pub email: Option<EmailOptions>,
+    /// File options, for [`ContextFieldType::Config`] fields.
+    pub config: Option<ConfigOptions>,
+    /// Backend options, for [`ContextFieldType::Secrets`] fields.
+    pub secrets: Option<SecretsOptions>,
+    /// Client options, for [`ContextFieldType::Llm`] fields.
+    pub llm: Option<LlmOptions>,
+    /// Cache options, for [`ContextFieldType::Cache`] fields.
+    pub cache: Option<CacheOptions>,
+    /// Directory options, for [`ContextFieldType::Workdir`] fields.
+    pub workdir: Option<WorkdirOptions>,
+    /// The plugin-defined resource, for [`ContextFieldType::Custom`] fields.
+    pub custom: Option<CustomResource>,
+}
+
+impl ContextFieldInfo {
+    /// The `path` of a SQLite or DuckDB database, which then opens without its
+    /// environment variable.
+    pub fn database_path(&self) -> Option<&str> {
+        let sqlite = self.sqlite.as_ref().and_then(|s| s.path.as_deref());
+        sqlite.or_else(|| self.duckdb.as_ref().and_then(|d| d.path.as_deref()))
+    }
+
+    /// Environment variables the resource reads and fails without.
+    ///
+    /// Storage credentials always come from the standard AWS variables, and
+    /// the region too when the manifest sets none.
+    pub fn required_env_vars(&self) -> Vec<&str> {
+        match self.field_type {
+            ContextFieldType::Database(_) if self.database_path().is_none() => {
+                vec![&self.env_var]
+            }
+            ContextFieldType::Queue(_) | ContextFieldType::Llm => vec![&self.env_var],
+            ContextFieldType::Http => self
+                .http
+                .as_ref()
+                .and_then(|h| h.auth.as_ref())
+                .map(|auth| vec![auth.env()])
+                .unwrap_or_default(),
+            ContextFieldType::Email => self
+                .email
+                .iter()
+                .flat_map(|e| [e.username_env.as_deref(), e.password_env.as_deref()])
+                .flatten()
+                .collect(),
+            ContextFieldType::Storage => {
+                let mut vars = vec!["AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"];
+                if self.s3.as_ref().is_none_or(|s3| s3.region.is_none()) {
+                    vars.push("AWS_REGION");
+                }
+                vars
+            }
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +213,7 @@ mod tests {
         assert_eq!(DatabaseType::Postgres.as_str(), "postgres");
         assert_eq!(DatabaseType::Mysql.as_str(), "mysql");
         assert_eq!(DatabaseType::Sqlite.as_str(), "sqlite");
+        assert_eq!(DatabaseType::Duckdb.as_str(), "duckdb");
     }
 
     #[test]
@@ -72,6 +221,16 @@ mod tests {
         assert!(ContextFieldType::Database(DatabaseType::Postgres).is_async());
         assert!(ContextFieldType::Database(DatabaseType::Mysql).is_async());
         assert!(ContextFieldType::Database(DatabaseType::Sqlite).is_async());
+        assert!(ContextFieldType::Database(DatabaseType::Duckdb).is_async());
         assert!(!ContextFieldType::Http.is_async());
+        assert!(ContextFieldType::Grpc.is_async());
+        assert!(ContextFieldType::Queue(QueueType::Nats).is_async());
+        assert!(ContextFieldType::Queue(QueueType::Kafka).is_async());
+        assert!(ContextFieldType::Email.is_async());
+        assert!(!ContextFieldType::Config.is_async());
+        assert!(!ContextFieldType::Secrets.is_async());
+        assert!(ContextFieldType::Llm.is_async());
+        assert!(ContextFieldType::Cache.is_async());
+        assert!(!ContextFieldType::Workdir.is_async());
     }
 }
@@ -0,0 +1,242 @@
+//! Traversal of the Application IR.
+//!
+//! [`IrVisitor`] lets lints, analyzers and generators walk the IR without
+//! re-implementing recursion over [`Operation`]s and command children.
+//! Override the `visit_*` methods for the nodes you care about; the default
+//! implementations call the matching `walk_*` function to visit the node's
+//! children. An overriding method calls `walk_*` itself to keep descending.
+//!
+//! ```
+//! use baobao_ir::{AppIR, CommandOp, IrVisitor, walk_command};
+//!
+//! #[derive(Default)]
+//! struct Leaves<'ir>(Vec<&'ir CommandOp>);
+//!
+//! impl<'ir> IrVisitor<'ir> for Leaves<'ir> {
+//!     fn visit_command(&mut self, cmd: &'ir CommandOp) {
+//!         if !cmd.has_subcommands() {
+//!             self.0.push(cmd);
+//!         }
+//!         walk_command(self, cmd);
+//!     }
+//! }
+//!
+//! fn leaves(ir: &AppIR) -> Vec<&CommandOp> {
+//!     let mut visitor = Leaves::default();
+//!     visitor.visit_app(ir);
+//!     visitor.0
+//! }
+//! ```
+
+use crate::{AppIR, CommandOp, Input, Operation, Resource, RouteOp};
+
+/// Visitor over the Application IR.
+///
+/// Commands are visited depth-first, parents before their children. The
+/// `'ir` lifetime lets visitors keep references to the nodes they visit.
+pub trait IrVisitor<'ir> {
+    /// Visit the application. Defaults to [`walk_app`].
+    fn visit_app(&mut self, app: &'ir AppIR) {
+        walk_app(self, app);
+    }
+
+    /// Visit a context resource.
+    fn visit_resource(&mut self, _resource: &'ir Resource) {}
+
+    /// Visit a command. Defaults to [`walk_command`].
+    fn visit_command(&mut self, cmd: &'ir CommandOp) {
+        walk_command(self, cmd);
+    }
+
+    /// Visit a route. Defaults to [`walk_route`].
+    fn visit_route(&mut self, route: &'ir RouteOp) {
+        walk_route(self, route);
+    }
+
+    /// Visit an input of a command or route.
+    fn visit_input(&mut self, _input: &'ir Input) {}
+}
+
+/// Visit the resources, global inputs and operations of an application.
+pub fn walk_app<'ir, V: IrVisitor<'ir> + ?Sized>(visitor: &mut V, app: &'ir AppIR) {
+    for resource in &app.resources {
+        visitor.visit_resource(resource);
+    }
+    for input in &app.global_inputs {
+        visitor.visit_input(input);
+    }
+    for op in &app.operations {
+        match op {
+            Operation::Command(cmd) => visitor.visit_command(cmd),
+            Operation::Route(route) => visitor.visit_route(route),
+        }
+    }
+}
+
+/// Visit the inputs and subcommands of a command.
+pub fn walk_command<'ir, V: IrVisitor<'ir> + ?Sized>(visitor: &mut V, cmd: &'ir CommandOp) {
+    for input in &cmd.inputs {
+        visitor.visit_input(input);
+    }
+    for child in &cmd.children {
+        visitor.visit_command(child);
+    }
+}
+
+/// Visit the inputs of a route.
+pub fn walk_route<'ir, V: IrVisitor<'ir> + ?Sized>(visitor: &mut V, route: &'ir RouteOp) {
+    for input in &route.inputs {
+        visitor.visit_input(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppMeta, HttpClientResource, HttpMethod, InputKind, InputType};
+
+    fn input(name: &str) -> Input {
+        Input {
+            name: name.into(),
+            ty: InputType::String,
+            kind: InputKind::Positional,
+            required: true,
+            default: None,
+            description: None,
+            choices: None,
+            multiple: false,
+            delimiter: None,
+            allow_hyphen_values: false,
+            prompt: None,
+            constraints: Default::default(),
+            requires: vec![],
+            required_if: None,
+            hint: None,
+            long_description: None,
+            deprecated: None,
+            span: None,
+        }
+    }
+
+    fn command(path: &[&str], inputs: Vec<Input>, children: Vec<CommandOp>) -> CommandOp {
+        CommandOp {
+            name: path.last().unwrap().to_string(),
+            path: path.iter().map(|s| s.to_string()).collect(),
+            description: String::new(),
+            inputs,
+            aliases: vec![],
+            deprecated: None,
+            groups: vec![],
+            children,
+            default_command: None,
+            exit_codes: Default::default(),
+            requires: vec![],
+            docs: Default::default(),
+            span: None,
+        }
+    }
+
+    fn app() -> AppIR {
+        AppIR {
+            meta: AppMeta {
+                name: "test".into(),
+                version: "0.1.0".into(),
+                description: None,
+                author: None,
+                docs: Default::default(),
+                build_info: false,
+                docs_command: false,
+                doctor_command: false,
+                external_subcommands: false,
+                default_command: None,
+                header: None,
+            },
+            resources: vec![Resource::HttpClient(HttpClientResource {
+                name: "http".into(),
+                options: Default::default(),
+            })],
+            operations: vec![
+                Operation::Command(command(
+                    &["db"],
+                    vec![],
+                    vec![
+                        command(&["db", "migrate"], vec![input("target")], vec![]),
+                        command(&["db", "seed"], vec![], vec![]),
+                    ],
+                )),
+                Operation::Route(RouteOp {
+                    name: "get_user".into(),
+                    method: HttpMethod::Get,
+                    path: "/users/{id}".into(),
+                    description: None,
+                    inputs: vec![input("id")],
+                    span: None,
+                }),
+            ],
+            global_inputs: vec![Input {
+                kind: InputKind::Flag { short: Some('v') },
+                ..input("verbose")
+            }],
+            locales: vec![],
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl IrVisitor<'_> for Recorder {
+        fn visit_resource(&mut self, resource: &Resource) {
+            if let Resource::HttpClient(http) = resource {
+                self.0.push(format!("resource {}", http.name));
+            }
+        }
+
+        fn visit_command(&mut self, cmd: &CommandOp) {
+            self.0.push(format!("command {}", cmd.handler_path()));
+            walk_command(self, cmd);
+        }
+
+        fn visit_route(&mut self, route: &RouteOp) {
+            self.0.push(format!("route {}", route.name));
+            walk_route(self, route);
+        }
+
+        fn visit_input(&mut self, input: &Input) {
+            self.0.push(format!("input {}", input.name));
+        }
+    }
+
+    #[test]
+    fn test_walk_order() {
+        let mut recorder = Recorder::default();
+        recorder.visit_app(&app());
+        assert_eq!(
+            recorder.0,
+            vec![
+                "resource http",
+                "input verbose",
+                "command db",
+                "command db/migrate",
+                "input target",
+                "command db/seed",
+                "route get_user",
+                "input id",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_override_without_walk_skips_children() {
+        struct TopLevel(usize);
+
+        impl IrVisitor<'_> for TopLevel {
+            fn visit_command(&mut self, _cmd: &CommandOp) {
+                self.0 += 1;
+            }
+        }
+
+        let mut visitor = TopLevel(0);
+        visitor.visit_app(&app());
+        assert_eq!(visitor.0, 1);
+    }
+}
@@ -0,0 +1,353 @@
+//! JSON round-trip tests for the Application IR.
+//!
+//! `bao ir` output is consumed by external tooling, so these tests pin down
+//! both that every IR type survives a round trip and the shape of the JSON.
+
+use std::time::Duration;
+
+use baobao_ir::{
+    AppIR, AppMeta, CommandOp, DatabaseResource, DatabaseType, DefaultValue, Docs, Example,
+    HttpAuth, HttpClientResource, HttpMethod, HttpOptions, IR_VERSION, Input, InputConstraints,
+    InputKind, InputPrompt, InputType, JournalMode, Locale, LogFormat, LogLevel, LoggerResource,
+    Message, Operation, PoolConfig, RequiredIf, Resource, RouteOp, SourceSpan, SqliteOptions,
+    SynchronousMode,
+};
+
+fn input(name: &str, ty: InputType, kind: InputKind) -> Input {
+    Input {
+        name: name.into(),
+        ty,
+        kind,
+        required: false,
+        default: None,
+        description: None,
+        choices: None,
+        multiple: false,
+        delimiter: None,
+        allow_hyphen_values: false,
+        prompt: None,
+        constraints: Default::default(),
+        requires: vec![],
+        required_if: None,
+        hint: None,
+        long_description: None,
+        deprecated: None,
+        span: None,
+    }
+}
+
+fn sample_ir() -> AppIR {
+    let create = CommandOp {
+        name: "create".into(),
+        path: vec!["users".into(), "create".into()],
+        description: "Create a user".into(),
+        inputs: vec![
+            Input {
+                required: true,
+                description: Some("User name".into()),
+                ..input("name", InputType::String, InputKind::Positional)
+            },
+            Input {
+                default: Some(DefaultValue::Int(18)),
+                ..input("age", InputType::Int, InputKind::Flag { short: Some('a') })
+            },
+            Input {
+                default: Some(DefaultValue::Float(1.5)),
+                constraints: InputConstraints {
+                    min: Some(0.0),
+                    max: Some(10.0),
+                    ..Default::default()
+                },
+                ..input("score", InputType::Float, InputKind::Flag { short: None })
+            },
+            Input {
+                prompt: Some(InputPrompt::Confirm),
+                ..input("yes", InputType::Bool, InputKind::Flag { short: Some('y') })
+            },
+            Input {
+                prompt: Some(InputPrompt::Secret),
+                constraints: InputConstraints {
+                    pattern: Some("^\\S+$".into()),
+                    min_length: Some(8),
+                    max_length: Some(64),
+                    ..Default::default()
+                },
+                required_if: Some(RequiredIf {
+                    flag: "role".into(),
+                    value: "admin".into(),
+                }),
+                deprecated: Some("passwords are generated".into()),
+                ..input(
+                    "password",
+                    InputType::String,
+                    InputKind::Flag { short: None },
+                )
+            },
+            Input {
+                default: Some(DefaultValue::String("./out".into())),
+                constraints: InputConstraints {
+                    path_exists: true,
+                    ..Default::default()
+                },
+                ..input("output", InputType::Path, InputKind::Flag { short: None })
+            },
+            Input {
+                choices: Some(vec!["admin".into(), "user".into()]),
+                multiple: true,
+                delimiter: None,
+                allow_hyphen_values: false,
+                default: Some(DefaultValue::List(vec![DefaultValue::String(
+                    "user".into(),
+                )])),
+                ..input("role", InputType::String, InputKind::Flag { short: None })
+            },
+            Input {
+                default: Some(DefaultValue::Table(vec![
+                    ("retries".into(), DefaultValue::Int(3)),
+                    ("verbose".into(), DefaultValue::Bool(false)),
+                ])),
+                ..input(
+                    "options",
+                    InputType::String,
+                    InputKind::Flag { short: None },
+                )
+            },
+        ],
+        aliases: vec!["new".into()],
+        deprecated: Some("use `users create` instead".into()),
+        groups: vec![],
+        children: vec![],
+        default_command: None,
+        exit_codes: [("Conflict".to_string(), 3), ("NotFound".to_string(), 2)]
+            .into_iter()
+            .collect(),
+        requires: vec!["db".into()],
+        docs: Docs {
+            long_description: Some("Create a user account.\n\nThe name must be unique.".into()),
+            examples: vec![
+                Example::new("myapp users create alice"),
+                Example {
+                    description: Some("Create an admin".into()),
+                    ..Example::new("myapp users create bob --role admin")
+                },
+            ],
+            footer: Some("Docs: https://example.com/users".into()),
+            category: Some("Accounts".into()),
+            authors: vec!["Jane Doe".into()],
+        },
+        span: Some(SourceSpan::new(
+            "bao.toml",
+            "[commands.users.commands.create]",
+            25,
+            31,
+        )),
+    };
+
+    AppIR {
+        meta: AppMeta {
+            name: "myapp".into(),
+            version: "1.2.3".into(),
+            description: Some("My CLI".into()),
+            author: None,
+            docs: Default::default(),
+            build_info: true,
+            docs_command: true,
+            doctor_command: true,
+            external_subcommands: true,
+            default_command: None,
+            header: Some("SPDX-License-Identifier: MIT".into()),
+        },
+        resources: vec![
+            Resource::Database(DatabaseResource {
+                name: "db".into(),
+                db_type: DatabaseType::Sqlite,
+                env_var: "DATABASE_URL".into(),
+                pool: PoolConfig {
+                    max_connections: Some(5),
+                    min_connections: None,
+                    acquire_timeout: Some(Duration::from_secs(30)),
+                    idle_timeout: None,
+                    max_lifetime: Some(Duration::from_millis(1500)),
+                },
+                sqlite: Some(SqliteOptions {
+                    path: Some("app.db".into()),
+                    create_if_missing: Some(true),
+                    read_only: None,
+                    journal_mode: Some(JournalMode::Wal),
+                    synchronous: Some(SynchronousMode::Normal),
+                    busy_timeout: Some(Duration::from_millis(250)),
+                    foreign_keys: Some(true),
+                    migrations: Some("migrations".into()),
+                }),
+                duckdb: None,
+            }),
+            Resource::HttpClient(HttpClientResource {
+                name: "github".into(),
+                options: HttpOptions {
+                    base_url: Some("https://api.github.com".into()),
+                    headers: [("Accept".into(), "application/json".into())].into(),
+                    timeout: Some(Duration::from_secs(30)),
+                    user_agent: None,
+                    retries: 3,
+                    backoff: Some(Duration::from_millis(200)),
+                    retry_on: vec![429, 503],
+                    auth: Some(HttpAuth::Bearer {
+                        env: "GITHUB_TOKEN".into(),
+                    }),
+                },
+            }),
+            Resource::Logger(LoggerResource {
+                name: "logger".into(),
+                level: LogLevel::Debug,
+                format: LogFormat::Json,
+                env_filter: Some("debug,sqlx=warn".into()),
+            }),
+        ],
+        operations: vec![
+            Operation::Command(CommandOp {
+                name: "users".into(),
+                path: vec!["users".into()],
+                description: "Manage users".into(),
+                inputs: vec![],
+                aliases: vec![],
+                deprecated: None,
+                groups: vec![],
+                children: vec![create],
+                default_command: None,
+                exit_codes: Default::default(),
+                requires: vec![],
+                docs: Default::default(),
+                span: None,
+            }),
+            Operation::Route(RouteOp {
+                name: "get_user".into(),
+                method: HttpMethod::Get,
+                path: "/users/{id}".into(),
+                description: None,
+                inputs: vec![
+                    Input {
+                        required: true,
+                        ..input("id", InputType::Int, InputKind::Positional)
+                    },
+                    input("fields", InputType::String, InputKind::Flag { short: None }),
+                ],
+                span: None,
+            }),
+        ],
+        global_inputs: vec![Input {
+            description: Some("Output format".into()),
+            choices: Some(vec!["text".into(), "json".into()]),
+            multiple: false,
+            delimiter: None,
+            allow_hyphen_values: false,
+            default: Some(DefaultValue::String("text".into())),
+            ..input("format", InputType::String, InputKind::Flag { short: None })
+        }],
+        locales: vec![Locale {
+            name: "fr".into(),
+            messages: vec![
+                Message {
+                    command: vec![],
+                    input: None,
+                    text: "Ma CLI".into(),
+                },
+                Message {
+                    command: vec!["users".into(), "create".into()],
+                    input: Some("name".into()),
+                    text: "Nom de l'utilisateur".into(),
+                },
+            ],
+        }],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let ir = sample_ir();
+    let json = ir.to_json().unwrap();
+    assert_eq!(AppIR::from_json(&json).unwrap(), ir);
+}
+
+#[test]
+fn test_json_is_stable() {
+    let json = sample_ir().to_json().unwrap();
+    let reserialized = AppIR::from_json(&json).unwrap().to_json().unwrap();
+    assert_eq!(json, reserialized);
+}
+
+#[test]
+fn test_json_shape() {
+    let json: serde_json::Value = serde_json::from_str(&sample_ir().to_json().unwrap()).unwrap();
+
+    let db = &json["resources"][0]["Database"];
+    assert_eq!(db["db_type"], "Sqlite");
+    assert_eq!(db["pool"]["acquire_timeout"], 30000);
+    assert_eq!(db["pool"]["idle_timeout"], serde_json::Value::Null);
+    assert_eq!(db["sqlite"]["busy_timeout"], 250);
+
+    let create = &json["operations"][0]["Command"]["children"][0];
+    assert_eq!(create["path"], serde_json::json!(["users", "create"]));
+    assert_eq!(create["aliases"], serde_json::json!(["new"]));
+    assert_eq!(create["inputs"][0]["kind"], "Positional");
+    assert_eq!(create["inputs"][1]["kind"]["Flag"]["short"], "a");
+    assert_eq!(create["inputs"][1]["default"]["Int"], 18);
+    assert!(create["inputs"][1].get("constraints").is_none());
+    assert_eq!(
+        create["inputs"][2]["constraints"],
+        serde_json::json!({"min": 0.0, "max": 10.0})
+    );
+    assert!(create["inputs"][2].get("requires").is_none());
+    assert_eq!(
+        create["inputs"][4]["required_if"],
+        serde_json::json!({"flag": "role", "value": "admin"})
+    );
+    assert_eq!(json["operations"][1]["Route"]["method"], "Get");
+    assert!(json["meta"].get("docs").is_none());
+    assert_eq!(json["global_inputs"][0]["name"], "format");
+    assert!(json["locales"][0]["messages"][0].get("input").is_none());
+    assert_eq!(create["span"]["column"], 26);
+    assert!(create["inputs"][0].get("span").is_none());
+    assert_eq!(
+        create["docs"]["examples"][0]["command"],
+        "myapp users create alice"
+    );
+    assert!(create["docs"]["examples"][0].get("description").is_none());
+    assert_eq!(
+        create["exit_codes"],
+        serde_json::json!({"Conflict": 3, "NotFound": 2})
+    );
+}
+
+#[test]
+fn test_json_is_versioned() {
+    let json: serde_json::Value = serde_json::from_str(&sample_ir().to_json().unwrap()).unwrap();
+    assert_eq!(json["version"], IR_VERSION);
+}
+
+#[test]
+fn test_from_json_checks_version() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&sample_ir().to_json().unwrap()).unwrap();
+
+    json["version"] = (IR_VERSION + 1).into();
+    let err = AppIR::from_json(&json.to_string()).unwrap_err();
+    assert!(err.to_string().contains("newer than the supported version"));
+
+    json.as_object_mut().unwrap().remove("version");
+    let err = AppIR::from_json(&json.to_string()).unwrap_err();
+    assert!(err.to_string().contains("missing IR version"));
+}
+
+#[test]
+fn test_from_json_ignores_unknown_fields() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&sample_ir().to_json().unwrap()).unwrap();
+    json["added_in_a_later_release"] = true.into();
+    assert_eq!(AppIR::from_json(&json.to_string()).unwrap(), sample_ir());
+}
+
+#[test]
+fn test_from_json_rejects_invalid_ir() {
+    assert!(AppIR::from_json(r#"{"version": 1}"#).is_err());
+    assert!(AppIR::from_json(r#"{"version": 1, "meta": {"name": "x"}}"#).is_err());
+}
@@ -0,0 +1,288 @@
+//! [`Arbitrary`] implementations for fuzzing.
+//!
+//! Generated manifests have the shape of a parsed manifest but skip
+//! validation: names may be empty, reserved words or collide once converted
+//! to another case. Serialize them with [`Manifest::to_toml_string`] and parse
+//! the result to put them through the same checks as a user's bao.toml.
+
+use std::collections::{BTreeMap, HashMap};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use baobao_core::{ARG_TYPES, ArgType, Version};
+use toml::Spanned;
+
+use crate::{
+    Arg, CliConfig, Command, Context, Description, Flag, GenerationConfig, Group, HintKind,
+    HttpMethod, Language, Manifest, Param, PromptKind, RequiredIf, Route, SourceMap,
+    TypeScriptConfig,
+};
+
+/// Deepest level of nested subcommands.
+const MAX_DEPTH: usize = 4;
+
+/// Most entries in one table of commands, args, flags, routes or params.
+const MAX_ENTRIES: usize = 4;
+
+/// Names that are valid-looking identifiers or known trouble: reserved words
+/// of the target languages, names colliding after case conversion, non-ASCII.
+const NAMES: &[&str] = &[
+    "hello",
+    "db",
+    "migrate",
+    "user-name",
+    "user_name",
+    "userName",
+    "fn",
+    "type",
+    "class",
+    "func",
+    "default",
+    "help",
+    "version",
+    "self",
+    "_",
+    "héllo",
+    "2fa",
+];
+
+impl<'a> Arbitrary<'a> for Manifest {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Manifest {
+            schema_version: None,
+            extends: None,
+            cli: CliConfig {
+                name: name(u)?,
+                version: Version::new(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?),
+                description: u.arbitrary()?,
+                author: u.arbitrary()?,
+                footer: u.arbitrary()?,
+                language: Some(*u.choose(&[Language::Rust, Language::TypeScript, Language::Go])?),
+                locale: None,
+                build_info: u.arbitrary()?,
+                docs_command: u.arbitrary()?,
+                doctor_command: u.arbitrary()?,
+                external_subcommands: u.arbitrary()?,
+                default_command: None,
+            },
+            context: Context::default(),
+            generation: GenerationConfig::default(),
+            typescript: TypeScriptConfig::default(),
+            targets: BTreeMap::new(),
+            profile: BTreeMap::new(),
+            flags: entries(u, |u| u.arbitrary())?,
+            commands: commands(u, 0)?,
+            routes: entries(u, |u| u.arbitrary())?,
+            spans: SourceMap::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Command {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        command(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Arg {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Arg {
+            arg_type: arg_type(u)?,
+            required: u.arbitrary()?,
+            description: u.arbitrary()?,
+            long_description: u.arbitrary()?,
+            default: default_value(u)?,
+            choices: u.arbitrary()?,
+            multiple: u.arbitrary()?,
+            allow_hyphen_values: u.arbitrary()?,
+            min: u.arbitrary()?,
+            max: u.arbitrary()?,
+            pattern: u.arbitrary()?,
+            // Bounded so the manifest serializes as a TOML integer
+            min_length: u.arbitrary::<Option<u16>>()?.map(usize::from),
+            max_length: u.arbitrary::<Option<u16>>()?.map(usize::from),
+            path_exists: u.arbitrary()?,
+            prompt: prompt(u)?,
+            hint: hint(u)?,
+            index: u.arbitrary::<Option<u8>>()?.map(usize::from),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Flag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Flag {
+            flag_type: arg_type(u)?,
+            short: u
+                .arbitrary::<Option<char>>()?
+                .map(|c| Spanned::new(0..0, c)),
+            description: u.arbitrary()?,
+            long_description: u.arbitrary()?,
+            default: default_value(u)?,
+            choices: u.arbitrary()?,
+            multiple: u.arbitrary()?,
+            delimiter: u.arbitrary()?,
+            min: u.arbitrary()?,
+            max: u.arbitrary()?,
+            pattern: u.arbitrary()?,
+            // Bounded so the manifest serializes as a TOML integer
+            min_length: u.arbitrary::<Option<u16>>()?.map(usize::from),
+            max_length: u.arbitrary::<Option<u16>>()?.map(usize::from),
+            path_exists: u.arbitrary()?,
+            requires: u.arbitrary()?,
+            required_if: u.arbitrary()?,
+            prompt: prompt(u)?,
+            hint: hint(u)?,
+            deprecated: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RequiredIf {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RequiredIf {
+            flag: u.arbitrary()?,
+            value: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Group {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Group {
+            members: u.arbitrary()?,
+            exclusive: u.arbitrary()?,
+            required: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Route {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let params: BTreeMap<String, Param> = entries(u, |u| u.arbitrary())?;
+        let mut path = String::new();
+        for param in params.keys() {
+            if u.arbitrary()? {
+                path.push_str(&format!("/{{{}}}", param));
+            } else {
+                path.push('/');
+                path.push_str(&name(u)?);
+            }
+        }
+        Ok(Route {
+            method: *u.choose(&[
+                HttpMethod::Get,
+                HttpMethod::Post,
+                HttpMethod::Put,
+                HttpMethod::Patch,
+                HttpMethod::Delete,
+                HttpMethod::Head,
+                HttpMethod::Options,
+            ])?,
+            path,
+            description: u.arbitrary()?,
+            params,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Param {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Param {
+            param_type: arg_type(u)?,
+            required: u.arbitrary()?,
+            description: u.arbitrary()?,
+            default: default_value(u)?,
+            choices: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Description {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Description::from(u.arbitrary::<String>()?))
+    }
+}
+
+fn command(u: &mut Unstructured<'_>, depth: usize) -> Result<Command> {
+    Ok(Command {
+        description: u.arbitrary()?,
+        long_description: u.arbitrary()?,
+        aliases: u.arbitrary()?,
+        deprecated: u.arbitrary()?,
+        examples: u.arbitrary()?,
+        footer: u.arbitrary()?,
+        args: entries(u, |u| u.arbitrary())?,
+        flags: entries(u, |u| u.arbitrary())?,
+        groups: u.arbitrary()?,
+        commands: if depth < MAX_DEPTH {
+            commands(u, depth + 1)?
+        } else {
+            HashMap::new()
+        },
+        // A name from `commands` would have to be picked to validate
+        default_command: None,
+        exit_codes: u.arbitrary()?,
+        // Names from `[context]` would have to be picked to validate
+        requires: Vec::new(),
+    })
+}
+
+fn commands(u: &mut Unstructured<'_>, depth: usize) -> Result<HashMap<String, Command>> {
+    entries(u, |u| command(u, depth))
+}
+
+/// Up to [`MAX_ENTRIES`] named entries.
+fn entries<'a, T, C>(
+    u: &mut Unstructured<'a>,
+    mut value: impl FnMut(&mut Unstructured<'a>) -> Result<T>,
+) -> Result<C>
+where
+    C: FromIterator<(String, T)>,
+{
+    let len = u.int_in_range(0..=MAX_ENTRIES)?;
+    (0..len).map(|_| Ok((name(u)?, value(u)?))).collect()
+}
+
+/// A name from [`NAMES`], or any string.
+fn name(u: &mut Unstructured<'_>) -> Result<String> {
+    if u.ratio(3, 4)? {
+        Ok(u.choose(NAMES)?.to_string())
+    } else {
+        u.arbitrary()
+    }
+}
+
+fn arg_type(u: &mut Unstructured<'_>) -> Result<ArgType> {
+    Ok(u.choose(ARG_TYPES)?.arg_type)
+}
+
+fn prompt(u: &mut Unstructured<'_>) -> Result<Option<PromptKind>> {
+    Ok(match u.int_in_range(0..=3)? {
+        0 => None,
+        1 => Some(PromptKind::Confirm),
+        2 => Some(PromptKind::Secret),
+        _ => Some(PromptKind::Text(String::arbitrary(u)?)),
+    })
+}
+
+fn hint(u: &mut Unstructured<'_>) -> Result<Option<HintKind>> {
+    Ok(match u.int_in_range(0..=5)? {
+        0 => None,
+        1 => Some(HintKind::File),
+        2 => Some(HintKind::Dir),
+        3 => Some(HintKind::Hostname),
+        4 => Some(HintKind::Url),
+        _ => Some(HintKind::Command),
+    })
+}
+
+/// A default value of any TOML scalar type, regardless of the input's type.
+fn default_value(u: &mut Unstructured<'_>) -> Result<Option<toml::Value>> {
+    Ok(match u.int_in_range(0..=4)? {
+        0 => None,
+        1 => Some(toml::Value::String(u.arbitrary()?)),
+        2 => Some(toml::Value::Integer(u.arbitrary()?)),
+        3 => Some(toml::Value::Float(u.arbitrary()?)),
+        _ => Some(toml::Value::Boolean(u.arbitrary()?)),
+    })
+}
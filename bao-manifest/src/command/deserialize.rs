@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{
     Deserialize,
@@ -6,7 +6,8 @@ use serde::{
 };
 use toml::Spanned;
 
-use super::{Arg, ArgType, Flag, default_true};
+use super::{Arg, ArgType, Flag, HintKind, PromptKind, RequiredIf, default_true};
+use crate::Description;
 
 /// Arg with name field for array format deserialization
 #[derive(Debug, Deserialize)]
@@ -16,10 +17,26 @@ pub(super) struct ArgWithName {
     arg_type: ArgType,
     #[serde(default = "default_true")]
     required: bool,
-    description: Option<String>,
+    description: Option<Description>,
+    long_description: Option<String>,
     default: Option<toml::Value>,
     #[serde(default)]
     choices: Option<Vec<String>>,
+    #[serde(default)]
+    multiple: bool,
+    #[serde(default)]
+    allow_hyphen_values: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    pattern: Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    #[serde(default)]
+    path_exists: bool,
+    #[serde(default)]
+    prompt: Option<PromptKind>,
+    hint: Option<HintKind>,
+    index: Option<usize>,
 }
 
 /// Flag with name field for array format deserialization
@@ -29,55 +46,107 @@ pub(super) struct FlagWithName {
     #[serde(rename = "type", default)]
     flag_type: ArgType,
     short: Option<char>,
-    description: Option<String>,
+    description: Option<Description>,
+    long_description: Option<String>,
     default: Option<toml::Value>,
     #[serde(default)]
     choices: Option<Vec<String>>,
+    #[serde(default)]
+    multiple: bool,
+    delimiter: Option<char>,
+    min: Option<f64>,
+    max: Option<f64>,
+    pattern: Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    #[serde(default)]
+    path_exists: bool,
+    #[serde(default)]
+    requires: Vec<String>,
+    required_if: Option<RequiredIf>,
+    #[serde(default)]
+    prompt: Option<PromptKind>,
+    hint: Option<HintKind>,
+    deprecated: Option<String>,
 }
 
-/// Untagged enum to support both array and map formats for args
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum ArgsFormat {
-    Array(Vec<ArgWithName>),
-    Map(HashMap<String, Arg>),
-}
-
-impl From<ArgsFormat> for HashMap<String, Arg> {
-    fn from(format: ArgsFormat) -> Self {
-        match format {
-            ArgsFormat::Array(vec) => vec
-                .into_iter()
-                .map(|a| {
-                    (
-                        a.name.clone(),
-                        Arg {
-                            arg_type: a.arg_type,
-                            required: a.required,
-                            description: a.description,
-                            default: a.default,
-                            choices: a.choices,
-                        },
-                    )
-                })
-                .collect(),
-            ArgsFormat::Map(map) => map,
-        }
-    }
-}
-
+/// Deserialize args from either array or map format
+/// Uses manual Visitor rather than an untagged enum, which would buffer
+/// every command's args before trying each format
 pub(super) fn deserialize_args<'de, D>(
     deserializer: D,
 ) -> std::result::Result<HashMap<String, Arg>, D::Error>
 where
-    D: serde::Deserializer<'de>,
+    D: Deserializer<'de>,
 {
-    ArgsFormat::deserialize(deserializer).map(Into::into)
+    struct ArgsVisitor;
+
+    impl<'de> Visitor<'de> for ArgsVisitor {
+        type Value = HashMap<String, Arg>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a map of args or an array of args with name field")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(item) = seq.next_element::<ArgWithName>()? {
+                items.push(item);
+            }
+            // Explicit indexes take their positions; the other args fill the
+            // remaining ones in declaration order
+            let taken: HashSet<usize> = items.iter().filter_map(|item| item.index).collect();
+            let free: Vec<usize> = if items.len() < 2 {
+                Vec::new()
+            } else {
+                (1..=items.len()).filter(|i| !taken.contains(i)).collect()
+            };
+            let mut free = free.into_iter();
+            let mut map = HashMap::with_capacity(items.len());
+            for item in items {
+                map.insert(
+                    item.name,
+                    Arg {
+                        arg_type: item.arg_type,
+                        required: item.required,
+                        description: item.description,
+                        long_description: item.long_description,
+                        default: item.default,
+                        choices: item.choices,
+                        multiple: item.multiple,
+                        allow_hyphen_values: item.allow_hyphen_values,
+                        min: item.min,
+                        max: item.max,
+                        pattern: item.pattern,
+                        min_length: item.min_length,
+                        max_length: item.max_length,
+                        path_exists: item.path_exists,
+                        prompt: item.prompt,
+                        hint: item.hint,
+                        index: item.index.or_else(|| free.next()),
+                    },
+                );
+            }
+            Ok(map)
+        }
+
+        fn visit_map<M>(self, map: M) -> std::result::Result<Self::Value, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            HashMap::deserialize(de::value::MapAccessDeserializer::new(map))
+        }
+    }
+
+    deserializer.deserialize_any(ArgsVisitor)
 }
 
 /// Deserialize flags from either array or map format
 /// Uses manual Visitor because Flag.short uses Spanned which doesn't work with untagged enums
-pub(super) fn deserialize_flags<'de, D>(
+pub(crate) fn deserialize_flags<'de, D>(
     deserializer: D,
 ) -> std::result::Result<HashMap<String, Flag>, D::Error>
 where
@@ -96,17 +165,31 @@ where
         where
             A: SeqAccess<'de>,
         {
-            let mut map = HashMap::new();
+            let mut map = HashMap::with_capacity(seq.size_hint().unwrap_or(0));
             while let Some(item) = seq.next_element::<FlagWithName>()? {
                 map.insert(
-                    item.name.clone(),
+                    item.name,
                     Flag {
                         flag_type: item.flag_type,
                         // Use empty span for array format (span info not available)
                         short: item.short.map(|c| Spanned::new(0..0, c)),
                         description: item.description,
+                        long_description: item.long_description,
                         default: item.default,
                         choices: item.choices,
+                        multiple: item.multiple,
+                        delimiter: item.delimiter,
+                        min: item.min,
+                        max: item.max,
+                        pattern: item.pattern,
+                        min_length: item.min_length,
+                        max_length: item.max_length,
+                        path_exists: item.path_exists,
+                        requires: item.requires,
+                        required_if: item.required_if,
+                        prompt: item.prompt,
+                        hint: item.hint,
+                        deprecated: item.deprecated,
                     },
                 );
             }
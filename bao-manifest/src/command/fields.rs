@@ -0,0 +1,178 @@
+//! Rejection of unknown keys in command, argument, flag and group tables.
+//!
+//! Serde silently ignores unknown keys, so a misspelled `requird = true`
+//! would be a no-op. This check walks the raw TOML document to report them
+//! with their source location. Keys prefixed with `x-` are reserved for
+//! extensions and always allowed.
+
+use toml::{
+    Spanned,
+    de::{DeTable, DeValue},
+};
+
+use super::{ARG_FIELDS, COMMAND_FIELDS, FLAG_FIELDS, GROUP_FIELDS, REQUIRED_IF_FIELDS};
+use crate::{
+    Result,
+    manifest::{PROFILE_CLI_FIELDS, PROFILE_FIELDS, ParseContext},
+};
+
+/// Prefix of keys that bao ignores, for use by other tools.
+const EXTENSION_PREFIX: &str = "x-";
+
+/// Check every command, argument, flag, global flag and profile of a
+/// manifest's parsed document for unknown keys.
+pub(crate) fn check_fields(ctx: &ParseContext, root: &DeTable<'_>) -> Result<()> {
+    if let Some(flags) = root.get("flags") {
+        check_inputs(ctx, flags.get_ref(), FLAG_FIELDS, "global flag", None)?;
+    }
+    if let Some(DeValue::Table(profiles)) = root.get("profile").map(Spanned::get_ref) {
+        check_profiles(ctx, profiles)?;
+    }
+    match root.get("commands").map(Spanned::get_ref) {
+        Some(DeValue::Table(commands)) => check_commands(ctx, commands, ""),
+        _ => Ok(()),
+    }
+}
+
+fn check_profiles(ctx: &ParseContext, profiles: &DeTable<'_>) -> Result<()> {
+    for (name, profile) in profiles.iter() {
+        let DeValue::Table(profile) = profile.get_ref() else {
+            continue;
+        };
+        let context = format!("profile '{}'", name.get_ref());
+        check_table(ctx, profile, PROFILE_FIELDS, &context)?;
+        if let Some(DeValue::Table(cli)) = profile.get("cli").map(Spanned::get_ref) {
+            check_table(
+                ctx,
+                cli,
+                PROFILE_CLI_FIELDS,
+                &format!("'cli' of {}", context),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn check_commands(ctx: &ParseContext, commands: &DeTable<'_>, parent: &str) -> Result<()> {
+    for (name, command) in commands.iter() {
+        let DeValue::Table(command) = command.get_ref() else {
+            continue;
+        };
+        let path = if parent.is_empty() {
+            name.get_ref().to_string()
+        } else {
+            format!("{}.{}", parent, name.get_ref())
+        };
+        check_table(ctx, command, COMMAND_FIELDS, &format!("command '{}'", path))?;
+
+        for (key, value) in command.iter() {
+            match (key.get_ref().as_ref(), value.get_ref()) {
+                ("args", value) => check_inputs(ctx, value, ARG_FIELDS, "argument", Some(&path))?,
+                ("flags", value) => check_inputs(ctx, value, FLAG_FIELDS, "flag", Some(&path))?,
+                ("groups", value) => check_inputs(ctx, value, GROUP_FIELDS, "group", Some(&path))?,
+                ("commands", DeValue::Table(children)) => check_commands(ctx, children, &path)?,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check args or flags in either map or array format, given the command
+/// declaring them, if any.
+fn check_inputs(
+    ctx: &ParseContext,
+    inputs: &DeValue<'_>,
+    fields: &[&str],
+    kind: &str,
+    command: Option<&str>,
+) -> Result<()> {
+    let context = |name: &str| match command {
+        Some(command) => format!("{} '{}' of '{}'", kind, name, command),
+        None => format!("{} '{}'", kind, name),
+    };
+    match inputs {
+        DeValue::Table(inputs) => {
+            for (name, input) in inputs.iter() {
+                if let DeValue::Table(input) = input.get_ref() {
+                    check_input(ctx, input, fields, &context(name.get_ref()))?;
+                }
+            }
+        }
+        DeValue::Array(inputs) => {
+            let fields: Vec<_> = fields.iter().copied().chain(["name"]).collect();
+            for input in inputs.iter() {
+                if let DeValue::Table(input) = input.get_ref() {
+                    let name = match input.get("name").map(Spanned::get_ref) {
+                        Some(DeValue::String(name)) => name.as_ref(),
+                        _ => "?",
+                    };
+                    check_input(ctx, input, &fields, &context(name))?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Check an input table, and its `required_if` table if any.
+fn check_input(
+    ctx: &ParseContext,
+    input: &DeTable<'_>,
+    fields: &[&str],
+    context: &str,
+) -> Result<()> {
+    check_table(ctx, input, fields, context)?;
+    match input.get("required_if").map(Spanned::get_ref) {
+        Some(DeValue::Table(required_if)) => check_table(
+            ctx,
+            required_if,
+            REQUIRED_IF_FIELDS,
+            &format!("'required_if' of {}", context),
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn check_table(
+    ctx: &ParseContext,
+    table: &DeTable<'_>,
+    fields: &[&str],
+    context: &str,
+) -> Result<()> {
+    for key in table.keys() {
+        let field = key.get_ref();
+        if fields.contains(&field.as_ref()) || field.starts_with(EXTENSION_PREFIX) {
+            continue;
+        }
+        let span = key.span();
+        return Err(ctx.source_context().unknown_field_error(
+            field.as_ref(),
+            context,
+            help(field, fields),
+            (span.start, span.end - span.start),
+        ));
+    }
+    Ok(())
+}
+
+/// Suggest the closest known field, or list them all.
+fn help(field: &str, fields: &[&str]) -> String {
+    let closest = fields
+        .iter()
+        .map(|known| (strsim::levenshtein(field, known), known))
+        .min()
+        .filter(|(distance, _)| *distance <= 2);
+    match closest {
+        Some((_, known)) => format!(
+            "did you mean '{}'? Prefix extension keys with '{}'",
+            known, EXTENSION_PREFIX
+        ),
+        None => format!(
+            "expected one of: {}. Prefix extension keys with '{}'",
+            fields.join(", "),
+            EXTENSION_PREFIX
+        ),
+    }
+}
@@ -1,17 +1,108 @@
 mod deserialize;
+mod fields;
 mod validate;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use deserialize::{deserialize_args, deserialize_flags};
-use serde::{Deserialize, Serialize};
+pub use baobao_core::ArgType;
+use deserialize::deserialize_args;
+pub(crate) use deserialize::deserialize_flags;
+pub(crate) use fields::check_fields;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use toml::Spanned;
+pub(crate) use validate::{validate_default_command, validate_global_flags};
+
+use crate::Description;
+
+/// Keys allowed in a command table.
+pub(crate) const COMMAND_FIELDS: &[&str] = &[
+    "description",
+    "long_description",
+    "aliases",
+    "deprecated",
+    "examples",
+    "footer",
+    "args",
+    "flags",
+    "groups",
+    "commands",
+    "default_command",
+    "exit_codes",
+    "requires",
+];
+
+/// Keys allowed in an argument table.
+pub(crate) const ARG_FIELDS: &[&str] = &[
+    "type",
+    "required",
+    "description",
+    "long_description",
+    "default",
+    "choices",
+    "multiple",
+    "allow_hyphen_values",
+    "min",
+    "max",
+    "pattern",
+    "min_length",
+    "max_length",
+    "path_exists",
+    "prompt",
+    "hint",
+    "index",
+];
+
+/// Keys allowed in a flag table.
+pub(crate) const FLAG_FIELDS: &[&str] = &[
+    "type",
+    "short",
+    "description",
+    "long_description",
+    "default",
+    "choices",
+    "multiple",
+    "delimiter",
+    "min",
+    "max",
+    "pattern",
+    "min_length",
+    "max_length",
+    "path_exists",
+    "requires",
+    "required_if",
+    "prompt",
+    "hint",
+    "deprecated",
+];
+
+/// Keys allowed in a flag's `required_if` table.
+pub(crate) const REQUIRED_IF_FIELDS: &[&str] = &["flag", "value"];
+
+/// Keys allowed in a group table.
+pub(crate) const GROUP_FIELDS: &[&str] = &["members", "exclusive", "required"];
 
 /// A CLI command or subcommand
 #[derive(Debug, Clone, Deserialize)]
 pub struct Command {
     /// Command description for help text
-    pub description: String,
+    pub description: Description,
+
+    /// Extended description shown in `--help` after the short description
+    pub long_description: Option<String>,
+
+    /// Other names the command can be invoked by
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Deprecation message; using the command prints a warning
+    pub deprecated: Option<String>,
+
+    /// Example command lines shown after the command's help
+    #[serde(default)]
+    pub examples: Vec<String>,
+
+    /// Text shown at the end of the command's help, such as links to docs
+    pub footer: Option<String>,
 
     /// Positional arguments
     /// Supports both formats:
@@ -27,9 +118,28 @@ pub struct Command {
     #[serde(default, deserialize_with = "deserialize_flags")]
     pub flags: HashMap<String, Flag>,
 
+    /// Named groups of this command's arguments and flags
+    #[serde(default)]
+    pub groups: BTreeMap<String, Group>,
+
     /// Nested subcommands
     #[serde(default)]
     pub commands: HashMap<String, Command>,
+
+    /// Subcommand run when the command is invoked without one, instead of
+    /// printing help
+    pub default_command: Option<String>,
+
+    /// Process exit codes for errors raised by the handler, keyed by error
+    /// name (e.g. `NotFoundError = 3`). Inherited by subcommands.
+    #[serde(default)]
+    pub exit_codes: BTreeMap<String, u8>,
+
+    /// Context resources the handler uses, by their `[context]` key (e.g.
+    /// `database`). Once any command declares some, resources are set up on
+    /// first use rather than for every invocation. Inherited by subcommands.
+    #[serde(default)]
+    pub requires: Vec<String>,
 }
 
 impl Command {
@@ -37,6 +147,37 @@ impl Command {
     pub fn has_subcommands(&self) -> bool {
         !self.commands.is_empty()
     }
+
+    /// Returns true if any argument of this command or its subcommands asks
+    /// for a missing value with a prompt message
+    pub fn has_prompt_messages(&self) -> bool {
+        self.args.values().any(Arg::has_prompt_message)
+            || self.commands.values().any(Command::has_prompt_messages)
+    }
+
+    /// Positional arguments in the order they are given on the command line.
+    ///
+    /// Arguments with an `index` take that position; the others fill the
+    /// remaining ones in name order.
+    pub fn positional_args(&self) -> Vec<(&String, &Arg)> {
+        let mut named: Vec<_> = self.args.iter().collect();
+        named.sort_by_key(|(name, _)| *name);
+        let mut slots = vec![None; named.len()];
+        let mut rest = Vec::new();
+        for (name, arg) in named {
+            match arg.index.and_then(|i| i.checked_sub(1)) {
+                Some(i) if slots.get(i).is_some_and(Option::is_none) => {
+                    slots[i] = Some((name, arg))
+                }
+                _ => rest.push((name, arg)),
+            }
+        }
+        let mut rest = rest.into_iter();
+        slots
+            .into_iter()
+            .filter_map(|slot| slot.or_else(|| rest.next()))
+            .collect()
+    }
 }
 
 /// A positional argument
@@ -51,7 +192,10 @@ pub struct Arg {
     pub required: bool,
 
     /// Description for help text
-    pub description: Option<String>,
+    pub description: Option<Description>,
+
+    /// Extended description shown in `--help` after the short description
+    pub long_description: Option<String>,
 
     /// Default value (makes argument optional)
     pub default: Option<toml::Value>,
@@ -59,6 +203,61 @@ pub struct Arg {
     /// Allowed choices for this argument (creates enum in generated code)
     #[serde(default)]
     pub choices: Option<Vec<String>>,
+
+    /// Whether the argument takes one or more values
+    #[serde(default)]
+    pub multiple: bool,
+
+    /// Whether values starting with a dash, such as `-5`, are taken as the
+    /// argument rather than as a flag
+    #[serde(default)]
+    pub allow_hyphen_values: bool,
+
+    /// Smallest allowed value, for int and float arguments
+    pub min: Option<f64>,
+
+    /// Largest allowed value, for int and float arguments
+    pub max: Option<f64>,
+
+    /// Regular expression a string argument must match in full
+    pub pattern: Option<String>,
+
+    /// Minimum length of a string argument, in characters
+    pub min_length: Option<usize>,
+
+    /// Maximum length of a string argument, in characters
+    pub max_length: Option<usize>,
+
+    /// Whether a path argument must name an existing file or directory
+    #[serde(default)]
+    pub path_exists: bool,
+
+    /// Prompt interactively when the argument is not provided
+    #[serde(default)]
+    pub prompt: Option<PromptKind>,
+
+    /// Kind of value, used by shells to complete the argument
+    pub hint: Option<HintKind>,
+
+    /// Position among the command's arguments, starting at 1. Arguments
+    /// declared in array format default to their declaration order.
+    pub index: Option<usize>,
+}
+
+/// A named group of a command's arguments and flags
+/// (`[commands.build.groups.output]`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Group {
+    /// Names of the arguments and flags in the group
+    pub members: Vec<String>,
+
+    /// Whether at most one member can be given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive: bool,
+
+    /// Whether at least one member must be given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
 }
 
 pub(crate) fn default_true() -> bool {
@@ -77,7 +276,10 @@ pub struct Flag {
     pub short: Option<Spanned<char>>,
 
     /// Description for help text
-    pub description: Option<String>,
+    pub description: Option<Description>,
+
+    /// Extended description shown in `--help` after the short description
+    pub long_description: Option<String>,
 
     /// Default value
     pub default: Option<toml::Value>,
@@ -85,29 +287,134 @@ pub struct Flag {
     /// Allowed choices for this flag (creates enum in generated code)
     #[serde(default)]
     pub choices: Option<Vec<String>>,
+
+    /// Whether the flag can be repeated to collect several values
+    #[serde(default)]
+    pub multiple: bool,
+
+    /// Character splitting one occurrence into several values (e.g. `--tags a,b,c`)
+    pub delimiter: Option<char>,
+
+    /// Smallest allowed value, for int and float flags
+    pub min: Option<f64>,
+
+    /// Largest allowed value, for int and float flags
+    pub max: Option<f64>,
+
+    /// Regular expression a string flag must match in full
+    pub pattern: Option<String>,
+
+    /// Minimum length of a string flag, in characters
+    pub min_length: Option<usize>,
+
+    /// Maximum length of a string flag, in characters
+    pub max_length: Option<usize>,
+
+    /// Whether a path flag must name an existing file or directory
+    #[serde(default)]
+    pub path_exists: bool,
+
+    /// Arguments and flags that must also be given when this flag is
+    #[serde(default)]
+    pub requires: Vec<String>,
+
+    /// Makes the flag required when another flag has a given value
+    pub required_if: Option<RequiredIf>,
+
+    /// Prompt interactively when the flag is not provided
+    #[serde(default)]
+    pub prompt: Option<PromptKind>,
+
+    /// Kind of value, used by shells to complete the flag
+    pub hint: Option<HintKind>,
+
+    /// Deprecation message; giving the flag prints a warning
+    pub deprecated: Option<String>,
+}
+
+/// Condition making a flag required
+/// (`required_if = { flag = "mode", value = "remote" }`)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RequiredIf {
+    /// Name of the flag to check
+    pub flag: String,
+
+    /// Value of that flag which makes this one required
+    pub value: String,
+}
+
+/// Interactive prompt shown when an input is not provided on the command line
+///
+/// Written as `"confirm"`, `"secret"`, or any other string, which is the
+/// message asking for a missing argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Ask for yes/no confirmation unless the bool flag is set (e.g. `--yes`)
+    Confirm,
+    /// Read a string without echoing it to the terminal
+    Secret,
+    /// Ask for a missing argument with this message, unless `--no-input` is given
+    Text(String),
+}
+
+impl PromptKind {
+    /// Get the string representation
+    pub fn as_str(&self) -> &str {
+        match self {
+            PromptKind::Confirm => "confirm",
+            PromptKind::Secret => "secret",
+            PromptKind::Text(message) => message,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PromptKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match String::deserialize(deserializer)?.as_str() {
+            "confirm" => Self::Confirm,
+            "secret" => Self::Secret,
+            message => Self::Text(message.to_string()),
+        })
+    }
 }
 
-/// Supported argument types
-#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+impl Serialize for PromptKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_str().serialize(serializer)
+    }
+}
+
+/// Kind of value an input takes, which shells use to complete it
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-pub enum ArgType {
-    String,
-    Int,
-    Float,
-    #[default]
-    Bool,
-    Path,
+pub enum HintKind {
+    /// Path to a file
+    File,
+    /// Path to a directory
+    Dir,
+    /// Host name
+    Hostname,
+    /// URL
+    Url,
+    /// Name of a command on the `PATH`
+    Command,
 }
 
-impl ArgType {
+impl HintKind {
     /// Get the string representation
     pub fn as_str(&self) -> &'static str {
         match self {
-            ArgType::String => "string",
-            ArgType::Int => "int",
-            ArgType::Float => "float",
-            ArgType::Bool => "bool",
-            ArgType::Path => "path",
+            HintKind::File => "file",
+            HintKind::Dir => "dir",
+            HintKind::Hostname => "hostname",
+            HintKind::Url => "url",
+            HintKind::Command => "command",
         }
     }
 }
@@ -116,8 +423,10 @@ impl ArgType {
 mod tests {
     use std::str::FromStr;
 
+    use miette::Diagnostic;
+
     use super::*;
-    use crate::Manifest;
+    use crate::{Error, Manifest};
 
     fn parse(content: &str) -> Manifest {
         toml::from_str(content).expect("Failed to parse TOML")
@@ -152,7 +461,7 @@ mod tests {
         let arg = cmd.args.get("target").unwrap();
         assert_eq!(arg.arg_type, ArgType::String);
         assert!(arg.required);
-        assert_eq!(arg.description, Some("Target to greet".to_string()));
+        assert_eq!(arg.description.as_deref(), Some("Target to greet"));
     }
 
     #[test]
@@ -211,7 +520,7 @@ mod tests {
         let flag = cmd.flags.get("verbose").unwrap();
         assert_eq!(flag.flag_type, ArgType::Bool);
         assert_eq!(flag.short_char(), Some('v'));
-        assert_eq!(flag.description, Some("Enable verbose output".to_string()));
+        assert_eq!(flag.description.as_deref(), Some("Enable verbose output"));
     }
 
     #[test]
@@ -287,7 +596,7 @@ mod tests {
         let arg = cmd.args.get("target").unwrap();
         assert_eq!(arg.arg_type, ArgType::String);
         assert!(arg.required);
-        assert_eq!(arg.description, Some("Target to greet".to_string()));
+        assert_eq!(arg.description.as_deref(), Some("Target to greet"));
     }
 
     #[test]
@@ -685,6 +994,47 @@ mod tests {
         assert!(err.to_string().contains("reserved keyword"));
     }
 
+    #[test]
+    fn test_reserved_keywords_follow_targets() {
+        let manifest = |language: &str, name: &str| {
+            Manifest::from_str(&format!(
+                r#"
+                [cli]
+                name = "test"
+                language = "{}"
+
+                [commands.{}]
+                description = "Test"
+                "#,
+                language, name
+            ))
+        };
+
+        assert!(manifest("rust", "class").is_ok());
+        assert!(manifest("typescript", "fn").is_ok());
+        let err = manifest("typescript", "class").unwrap_err();
+        assert!(err.to_string().contains("TypeScript reserved keyword"));
+        let err = manifest("go", "func").unwrap_err();
+        assert!(err.to_string().contains("Go reserved keyword"));
+
+        let targets = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+
+            [targets.node]
+            language = "typescript"
+
+            [commands.delete]
+            description = "Test"
+            "#,
+        );
+        assert!(targets.is_err());
+    }
+
     #[test]
     fn test_valid_identifier_with_dash() {
         // Dashes are now allowed in command names
@@ -810,4 +1160,1431 @@ mod tests {
             );
         }
     }
+
+    // ========================================================================
+    // Prompt tests
+    // ========================================================================
+
+    #[test]
+    fn test_prompt_kinds_parse() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.drop]
+            description = "Drop the database"
+
+            [commands.drop.flags.yes]
+            type = "bool"
+            prompt = "confirm"
+
+            [commands.drop.flags.password]
+            type = "string"
+            prompt = "secret"
+            "#,
+        );
+
+        let cmd = schema.commands.get("drop").unwrap();
+        assert_eq!(cmd.flags["yes"].prompt, Some(PromptKind::Confirm));
+        assert_eq!(cmd.flags["password"].prompt, Some(PromptKind::Secret));
+    }
+
+    #[test]
+    fn test_default_must_be_a_choice() {
+        let result = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.export]
+            description = "Export data"
+
+            [commands.export.flags.format]
+            type = "string"
+            choices = ["json", "text"]
+            default = "yaml"
+            "#,
+        );
+
+        let err = result.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("default 'yaml' of flag 'format' in 'export' is not one of its choices")
+        );
+
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.export]
+            description = "Export data"
+
+            [commands.export.args.level]
+            type = "int"
+            choices = ["1", "2", "3"]
+            default = 2
+            "#,
+        );
+        assert_eq!(
+            manifest.commands["export"].args["level"].choices.as_deref(),
+            Some(&["1".to_string(), "2".to_string(), "3".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn test_count_flags() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.build]
+            description = "Build the project"
+
+            [commands.build.flags.verbose]
+            type = "count"
+            short = "v"
+            "#,
+        );
+        assert_eq!(
+            manifest.commands["build"].flags["verbose"].flag_type,
+            ArgType::Count
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.build]
+            description = "Build the project"
+
+            [commands.build.flags.verbose]
+            type = "count"
+            default = 2
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("count flag 'verbose' in 'build' cannot set 'default'")
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.build]
+            description = "Build the project"
+
+            [commands.build.args.level]
+            type = "count"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "argument 'level' in 'build' cannot have type 'count'; only flags count occurrences"
+        ));
+    }
+
+    #[test]
+    fn test_value_constraints() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.greet]
+            description = "Greet someone"
+
+            [commands.greet.args.name]
+            type = "string"
+            pattern = "[a-z]+"
+            min_length = 2
+            max_length = 16
+
+            [commands.greet.args.config]
+            type = "path"
+            path_exists = true
+
+            [commands.greet.flags.times]
+            type = "int"
+            min = 1
+            max = 5
+            default = 3
+            "#,
+        );
+        let greet = &manifest.commands["greet"];
+        assert_eq!(greet.args["name"].pattern.as_deref(), Some("[a-z]+"));
+        assert_eq!(greet.args["name"].min_length, Some(2));
+        assert_eq!(greet.args["name"].max_length, Some(16));
+        assert!(greet.args["config"].path_exists);
+        assert_eq!(greet.flags["times"].min, Some(1.0));
+        assert_eq!(greet.flags["times"].max, Some(5.0));
+
+        let invalid = [
+            (
+                "type = \"string\"\nmin = 1",
+                "flag 'level' in 'greet' sets 'min' but has type 'string' (expected 'int' or 'float')",
+            ),
+            (
+                "type = \"int\"\npattern = \"[0-9]+\"",
+                "flag 'level' in 'greet' sets 'pattern' but has type 'int' (expected 'string')",
+            ),
+            (
+                "type = \"int\"\nmin = 1.5",
+                "'min' of flag 'level' in 'greet' must be a finite integer",
+            ),
+            (
+                "type = \"float\"\nmin = 2\nmax = 1",
+                "flag 'level' in 'greet' has min 2 greater than max 1",
+            ),
+            (
+                "type = \"string\"\npattern = \"[a-z\"",
+                "pattern of flag 'level' in 'greet' is not a valid regular expression",
+            ),
+            (
+                "type = \"string\"\nmax_length = 0",
+                "'max_length' of flag 'level' in 'greet' must be at least 1",
+            ),
+            (
+                "type = \"string\"\nmin_length = 5\nmax_length = 4",
+                "flag 'level' in 'greet' has min_length 5 greater than max_length 4",
+            ),
+            (
+                "type = \"string\"\nmin_length = 3\ndefault = \"ab\"",
+                "default \"ab\" of flag 'level' in 'greet' does not satisfy its 'min_length'",
+            ),
+            (
+                "type = \"string\"\npath_exists = true",
+                "flag 'level' in 'greet' sets 'path_exists' but has type 'string' (expected 'path')",
+            ),
+            (
+                "type = \"string\"\nchoices = [\"a\"]\nmax_length = 4",
+                "flag 'level' in 'greet' cannot set both 'choices' and 'max_length'",
+            ),
+            (
+                "type = \"int\"\nmax = 5\ndefault = 9",
+                "default 9 of flag 'level' in 'greet' does not satisfy its 'max'",
+            ),
+            (
+                "type = \"string\"\npattern = \"[a-z]+\"\ndefault = \"ABC\"",
+                "default \"ABC\" of flag 'level' in 'greet' does not satisfy its 'pattern'",
+            ),
+        ];
+        for (flag, message) in invalid {
+            let content = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.greet]\ndescription = \"Greet\"\n\n\
+                 [commands.greet.flags.level]\n{}\n",
+                flag
+            );
+            let err = Manifest::from_str(&content).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", message, err);
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.build]
+            description = "Build"
+
+            [commands.build.flags.json]
+            type = "bool"
+
+            [commands.build.flags.yaml]
+            type = "bool"
+
+            [commands.build.groups.output]
+            members = ["json", "yaml"]
+            exclusive = true
+            "#,
+        );
+        let output = &manifest.commands["build"].groups["output"];
+        assert_eq!(output.members, ["json", "yaml"]);
+        assert!(output.exclusive);
+        assert!(!output.required);
+
+        let invalid = [
+            (
+                "members = [\"json\", \"yaml\"]",
+                "group 'output' in 'build' must set 'exclusive' or 'required'",
+            ),
+            (
+                "members = [\"json\"]\nexclusive = true",
+                "group 'output' in 'build' needs at least two members",
+            ),
+            (
+                "members = [\"json\", \"json\"]\nexclusive = true",
+                "group 'output' in 'build' lists member 'json' twice",
+            ),
+            (
+                "members = [\"json\", \"xml\"]\nexclusive = true",
+                "group 'output' in 'build' has unknown member 'xml'",
+            ),
+            (
+                "members = [\"json\", \"level\"]\nrequired = true",
+                "group 'output' in 'build' has member 'level' with a default",
+            ),
+            (
+                "members = [\"json\", \"path\"]\nexclusive = true",
+                "group 'output' in 'build' is exclusive but member 'path' is a required argument",
+            ),
+        ];
+        for (group, message) in invalid {
+            let content = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.build]\ndescription = \"Build\"\n\n\
+                 [commands.build.args.path]\ntype = \"path\"\n\n\
+                 [commands.build.flags.json]\ntype = \"bool\"\n\n\
+                 [commands.build.flags.level]\ntype = \"int\"\ndefault = 1\n\n\
+                 [commands.build.groups.output]\n{}\n",
+                group
+            );
+            let err = Manifest::from_str(&content).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", message, err);
+        }
+    }
+
+    #[test]
+    fn test_aliases() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.list]
+            description = "List"
+            aliases = ["ls", "l"]
+
+            [commands.db]
+            description = "Database"
+
+            [commands.db.commands.migrate]
+            description = "Migrate"
+            aliases = ["m"]
+            "#
+        .parse()
+        .unwrap();
+        assert_eq!(manifest.commands["list"].aliases, ["ls", "l"]);
+        assert!(manifest.has_command("list"));
+        assert!(manifest.has_command("ls"));
+        assert!(manifest.has_command("db/m"));
+        assert!(!manifest.has_command("m"));
+        assert!(!manifest.has_command("db/ls"));
+
+        let invalid = [
+            ("[\"ls\", \"ls\"]", "alias 'ls' of 'list' is listed twice"),
+            (
+                "[\"list\"]",
+                "alias 'list' of 'list' is the command's own name",
+            ),
+            ("[\"l s\"]", "alias 'l s' of 'list' is invalid"),
+        ];
+        for (aliases, message) in invalid {
+            let content = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.list]\ndescription = \"List\"\naliases = {}\n",
+                aliases
+            );
+            let err = Manifest::from_str(&content).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", message, err);
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+            examples = ["test deploy prod --force", "test deploy staging"]
+            "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            manifest.commands["deploy"].examples,
+            ["test deploy prod --force", "test deploy staging"]
+        );
+
+        let err = Manifest::from_str(
+            "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+             [commands.deploy]\ndescription = \"Deploy\"\nexamples = [\"test deploy\", \" \"]\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("example 2 of 'deploy' is empty"), "{}", err);
+    }
+
+    #[test]
+    fn test_positional_order() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.copy]
+            description = "Copy files"
+
+            [commands.copy.args.sources]
+            type = "path"
+            multiple = true
+            index = 3
+
+            [commands.copy.args.target]
+            type = "path"
+            index = 1
+
+            [commands.copy.args.mode]
+            type = "string"
+
+            [commands.rename]
+            description = "Rename a file"
+
+            [[commands.rename.args]]
+            name = "source"
+            type = "path"
+
+            [[commands.rename.args]]
+            name = "dest"
+            type = "path"
+
+            [commands.link]
+            description = "Link a file"
+
+            [[commands.link.args]]
+            name = "source"
+            type = "path"
+
+            [[commands.link.args]]
+            name = "target"
+            type = "path"
+            index = 1
+
+            [[commands.link.args]]
+            name = "dest"
+            type = "path"
+            "#
+        .parse()
+        .unwrap();
+        let order = |command: &str| -> Vec<String> {
+            manifest.commands[command]
+                .positional_args()
+                .into_iter()
+                .map(|(name, _)| name.clone())
+                .collect()
+        };
+        assert_eq!(order("copy"), ["target", "mode", "sources"]);
+        assert_eq!(order("rename"), ["source", "dest"]);
+        assert_eq!(order("link"), ["target", "source", "dest"]);
+
+        let parse = |args: &str| {
+            Manifest::from_str(&format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.copy]\ndescription = \"Copy\"\n{}",
+                args
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+        let err = parse(
+            "[commands.copy.args.a]\ntype = \"path\"\nindex = 3\n\
+             [commands.copy.args.b]\ntype = \"path\"\n",
+        );
+        assert!(
+            err.contains("index 3 of argument 'a' in 'copy' must be between 1 and 2"),
+            "{}",
+            err
+        );
+        let err = parse(
+            "[commands.copy.args.a]\ntype = \"path\"\nindex = 1\n\
+             [commands.copy.args.b]\ntype = \"path\"\nindex = 1\n",
+        );
+        assert!(
+            err.contains("index 1 of argument 'b' in 'copy' is also the index of 'a'"),
+            "{}",
+            err
+        );
+        let err = parse(
+            "[commands.copy.args.a]\ntype = \"path\"\nmultiple = true\nindex = 1\n\
+             [commands.copy.args.b]\ntype = \"path\"\n",
+        );
+        assert!(
+            err.contains(
+                "argument 'a' in 'copy' takes multiple values but is not the last argument"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_hint() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.ssh]
+            description = "Connect to a host"
+
+            [[commands.ssh.args]]
+            name = "host"
+            type = "string"
+            hint = "hostname"
+
+            [commands.ssh.flags.identity]
+            type = "path"
+            hint = "file"
+            "#
+        .parse()
+        .unwrap();
+        let ssh = &manifest.commands["ssh"];
+        assert_eq!(ssh.args["host"].hint, Some(HintKind::Hostname));
+        assert_eq!(ssh.flags["identity"].hint, Some(HintKind::File));
+
+        let err = Manifest::from_str(
+            "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+             [commands.ssh]\ndescription = \"Connect\"\n\
+             [commands.ssh.flags.verbose]\ntype = \"count\"\nhint = \"dir\"\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains(
+                "flag 'verbose' in 'ssh' sets hint = \"dir\" but has type 'count', which takes no value"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_long_description() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+            long_description = """
+Builds the project and uploads it.
+
+Runs migrations first."""
+
+            [[commands.deploy.args]]
+            name = "target"
+            type = "string"
+            long_description = "Environment from deploy.toml"
+
+            [commands.deploy.flags.force]
+            long_description = "Skip the confirmation prompt"
+            "#
+        .parse()
+        .unwrap();
+        let deploy = &manifest.commands["deploy"];
+        assert_eq!(
+            deploy.long_description.as_deref(),
+            Some("Builds the project and uploads it.\n\nRuns migrations first.")
+        );
+        assert_eq!(
+            deploy.args["target"].long_description.as_deref(),
+            Some("Environment from deploy.toml")
+        );
+        assert_eq!(
+            deploy.flags["force"].long_description.as_deref(),
+            Some("Skip the confirmation prompt")
+        );
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.old]
+            description = "Old"
+            deprecated = "use `new` instead"
+
+            [commands.old.flags.legacy]
+            type = "bool"
+            deprecated = "has no effect"
+            "#
+        .parse()
+        .unwrap();
+        let old = &manifest.commands["old"];
+        assert_eq!(old.deprecated.as_deref(), Some("use `new` instead"));
+        assert_eq!(
+            old.flags["legacy"].deprecated.as_deref(),
+            Some("has no effect")
+        );
+
+        let invalid = [
+            ("deprecated = \"\"", "'deprecated' of 'old' cannot be empty"),
+            (
+                "[commands.old.flags.legacy]\ndeprecated = \" \"",
+                "'deprecated' of flag 'legacy' in 'old' cannot be empty",
+            ),
+            (
+                "[commands.old.flags.legacy]\ndefault = \"x\"\ndeprecated = \"gone\"",
+                "'deprecated' of flag 'legacy' in 'old' cannot be used on a flag with a default",
+            ),
+            (
+                "[flags.verbose]\ntype = \"bool\"\ndeprecated = \"gone\"",
+                "global flag 'verbose' cannot use 'deprecated'",
+            ),
+        ];
+        for (snippet, message) in invalid {
+            let content = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.old]\ndescription = \"Old\"\n{}\n",
+                snippet
+            );
+            let err = Manifest::from_str(&content).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", message, err);
+        }
+    }
+
+    #[test]
+    fn test_requirements() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.deploy]
+            description = "Deploy"
+
+            [commands.deploy.flags.mode]
+            type = "string"
+            choices = ["local", "remote"]
+
+            [commands.deploy.flags.host]
+            type = "string"
+            requires = ["mode"]
+            required_if = { flag = "mode", value = "remote" }
+            "#,
+        );
+        let host = &manifest.commands["deploy"].flags["host"];
+        assert_eq!(host.requires, ["mode"]);
+        assert_eq!(
+            host.required_if,
+            Some(RequiredIf {
+                flag: "mode".into(),
+                value: "remote".into(),
+            })
+        );
+
+        let invalid = [
+            (
+                "requires = [\"host\"]",
+                "'requires' of flag 'host' in 'deploy' names the flag itself",
+            ),
+            (
+                "requires = [\"port\", \"port\"]",
+                "'requires' of flag 'host' in 'deploy' lists 'port' twice",
+            ),
+            (
+                "requires = [\"user\"]",
+                "'requires' of flag 'host' in 'deploy' names unknown input 'user'",
+            ),
+            (
+                "requires = [\"port\"]\ndefault = \"localhost\"",
+                "'requires' of flag 'host' in 'deploy' cannot be used on a flag with a default",
+            ),
+            (
+                "requires = [\"mode\"]",
+                "'requires' of flag 'host' in 'deploy' names 'mode', which has a default",
+            ),
+            (
+                "required_if = { flag = \"port\", value = \"80\" }",
+                "'required_if' of flag 'host' in 'deploy' names flag 'port', which is not a single string flag",
+            ),
+            (
+                "required_if = { flag = \"mode\", value = \"cloud\" }",
+                "'required_if' of flag 'host' in 'deploy' tests value 'cloud', which is not one of the choices of flag 'mode'",
+            ),
+            (
+                "required_if = { flag = \"mode\", value = \"local\" }",
+                "'required_if' of flag 'host' in 'deploy' tests value 'local', which is already the default of flag 'mode'",
+            ),
+            (
+                "required_if = { flag = \"mode\", value = \"remote\", when = true }",
+                "unknown field 'when' in 'required_if' of flag 'host' of 'deploy'",
+            ),
+        ];
+        for (host, message) in invalid {
+            let content = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.deploy]\ndescription = \"Deploy\"\n\n\
+                 [commands.deploy.flags.mode]\ntype = \"string\"\n\
+                 choices = [\"local\", \"remote\"]\ndefault = \"local\"\n\n\
+                 [commands.deploy.flags.port]\ntype = \"int\"\n\n\
+                 [commands.deploy.flags.host]\ntype = \"string\"\n{}\n",
+                host
+            );
+            let err = Manifest::from_str(&content).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", message, err);
+        }
+
+        let err = Manifest::from_str(
+            "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+             [flags.verbose]\ntype = \"bool\"\nrequires = [\"quiet\"]\n\n\
+             [flags.quiet]\ntype = \"bool\"\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("global flag 'verbose' cannot use 'requires'")
+        );
+    }
+
+    #[test]
+    fn test_global_flags() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.verbose]
+            type = "bool"
+            short = "v"
+            description = "Verbose output"
+
+            [commands.build]
+            description = "Build the project"
+            "#,
+        );
+        let verbose = &manifest.flags["verbose"];
+        assert_eq!(verbose.flag_type, ArgType::Bool);
+        assert_eq!(verbose.short_char(), Some('v'));
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.verbose]
+            type = "bool"
+
+            [commands.build]
+            description = "Build the project"
+
+            [commands.build.commands.release]
+            description = "Release build"
+
+            [commands.build.commands.release.flags.verbose]
+            type = "bool"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("flag 'verbose' in 'build.release' has the same name as a global flag")
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.verbose]
+            type = "bool"
+            short = "v"
+
+            [commands.build]
+            description = "Build the project"
+
+            [commands.build.flags.version]
+            type = "string"
+            short = "v"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "flag 'version' in 'build' uses short '-v', already taken by global flag 'verbose'"
+        ));
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.token]
+            type = "string"
+            prompt = "secret"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("global flag 'token' cannot use a prompt")
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [flags.verbose]
+            type = "bool"
+            colour = "red"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("global flag 'verbose'"));
+    }
+
+    #[test]
+    fn test_multiple_inputs() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.add]
+            description = "Add files"
+
+            [commands.add.args.paths]
+            type = "path"
+            multiple = true
+
+            [commands.add.flags.tag]
+            type = "string"
+            multiple = true
+            default = ["latest"]
+            "#,
+        );
+        let add = &manifest.commands["add"];
+        assert!(add.args["paths"].multiple);
+        assert!(add.flags["tag"].multiple);
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.add]
+            description = "Add files"
+
+            [commands.add.flags.force]
+            type = "bool"
+            multiple = true
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("flag 'force' in 'add' cannot take multiple values because it is a bool")
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.copy]
+            description = "Copy files"
+
+            [commands.copy.args.sources]
+            type = "path"
+            multiple = true
+
+            [commands.copy.args.target]
+            type = "path"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "argument 'sources' in 'copy' takes multiple values but is not the last argument"
+        ));
+    }
+
+    #[test]
+    fn test_default_command() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            default_command = "db"
+
+            [commands.db]
+            description = "Database"
+            default_command = "status"
+
+            [commands.db.commands.status]
+            description = "Show status"
+
+            [commands.db.commands.reset]
+            description = "Reset"
+
+            [commands.db.commands.reset.args.target]
+            type = "string"
+            "#,
+        );
+        assert_eq!(manifest.cli.default_command.as_deref(), Some("db"));
+        assert_eq!(
+            manifest.commands["db"].default_command.as_deref(),
+            Some("status")
+        );
+
+        let cases = [
+            (
+                "missing",
+                "default_command 'missing' of 'db' is not one of its subcommands",
+            ),
+            (
+                "reset",
+                "default_command 'reset' of 'db' requires argument 'target'",
+            ),
+        ];
+        for (target, message) in cases {
+            let err = Manifest::from_str(&format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.db]\ndescription = \"Database\"\ndefault_command = \"{}\"\n\n\
+                 [commands.db.commands.status]\ndescription = \"Show status\"\n\n\
+                 [commands.db.commands.reset]\ndescription = \"Reset\"\n\n\
+                 [commands.db.commands.reset.args.target]\ntype = \"string\"\n",
+                target
+            ))
+            .unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            default_command = "db"
+
+            [commands.db]
+            description = "Database"
+
+            [commands.db.commands.status]
+            description = "Show status"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("default_command 'db' of the CLI has subcommands of its own")
+        );
+    }
+
+    #[test]
+    fn test_flag_delimiter() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.add]
+            description = "Add files"
+
+            [commands.add.flags.tags]
+            type = "string"
+            multiple = true
+            delimiter = ","
+            "#,
+        );
+        assert_eq!(manifest.commands["add"].flags["tags"].delimiter, Some(','));
+
+        let cases = [
+            (
+                "type = \"string\"",
+                "flag 'tags' in 'add' sets delimiter but does not take multiple values",
+            ),
+            (
+                "type = \"int\"\nmultiple = true",
+                "flag 'tags' in 'add' sets delimiter but has type 'int'",
+            ),
+            (
+                "type = \"string\"\nmultiple = true\nchoices = [\"a\", \"b\"]",
+                "flag 'tags' in 'add' sets delimiter but has choices",
+            ),
+        ];
+        for (fields, message) in cases {
+            let err = Manifest::from_str(&format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.add]\ndescription = \"Add files\"\n\n\
+                 [commands.add.flags.tags]\n{}\ndelimiter = \",\"\n",
+                fields
+            ))
+            .unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_default_type() {
+        let manifest = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.seek]
+            description = "Move the cursor"
+
+            [commands.seek.args.offset]
+            type = "float"
+            default = 5
+
+            [commands.seek.flags.label]
+            type = "string"
+            default = 3
+
+            [commands.seek.flags.marks]
+            type = "int"
+            multiple = true
+            default = [1, 2]
+            "#,
+        );
+        assert!(
+            manifest.commands["seek"].args["offset"]
+                .default_mismatch()
+                .is_none()
+        );
+
+        let cases = [
+            (
+                "args.offset]\ntype = \"int\"\ndefault = \"abc\"",
+                "default \"abc\" of argument 'offset' in 'seek' is not an integer",
+            ),
+            (
+                "flags.verbose]\ntype = \"bool\"\ndefault = 1",
+                "default 1 of flag 'verbose' in 'seek' is not a boolean",
+            ),
+            (
+                "flags.out]\ntype = \"path\"\ndefault = true",
+                "default true of flag 'out' in 'seek' is not a string",
+            ),
+            (
+                "flags.marks]\ntype = \"int\"\ndefault = [1, 2]",
+                "default [1, 2] of flag 'marks' in 'seek' is a list, but 'multiple' is not set",
+            ),
+            (
+                "flags.marks]\ntype = \"int\"\nmultiple = true\ndefault = [1, \"b\"]",
+                "default [1, \"b\"] of flag 'marks' in 'seek' has item \"b\", which is not an integer",
+            ),
+        ];
+        for (table, message) in cases {
+            let src = format!(
+                "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n\
+                 [commands.seek]\ndescription = \"Move the cursor\"\n\n\
+                 [commands.seek.{}\n",
+                table
+            );
+            let err = Manifest::from_str(&src).unwrap_err();
+            assert_eq!(err.to_string(), message);
+            let label = err.labels().unwrap().next().unwrap();
+            assert_eq!(label.offset(), src.find("default = ").unwrap() + 10);
+        }
+    }
+
+    #[test]
+    fn test_confirm_prompt_requires_bool_flag() {
+        let result = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.drop]
+            description = "Drop the database"
+
+            [commands.drop.flags.yes]
+            type = "string"
+            prompt = "confirm"
+            "#,
+        );
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("not a bool flag"));
+    }
+
+    #[test]
+    fn test_secret_prompt_requires_string() {
+        let result = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.login]
+            description = "Log in"
+
+            [commands.login.args.token]
+            type = "int"
+            prompt = "secret"
+            "#,
+        );
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("expected 'string'"));
+    }
+
+    #[test]
+    fn test_secret_type() {
+        let manifest = |extra: &str| {
+            Manifest::from_str(&format!(
+                r#"
+                [cli]
+                name = "test"
+                language = "rust"
+
+                [commands.login]
+                description = "Log in"
+
+                [commands.login.args.password]
+                type = "secret"
+
+                [commands.login.flags.token]
+                type = "secret"
+                {}
+                "#,
+                extra
+            ))
+        };
+        assert!(manifest("").is_ok());
+
+        let err = manifest("default = \"hunter2\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("secret flag 'token' in 'login' cannot set 'default'")
+        );
+
+        let err = manifest("[commands.login.args.user]\ntype = \"string\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "required argument 'user' in 'login' cannot come after secret argument 'password'"
+        ));
+
+        let err = manifest("[flags.key]\ntype = \"secret\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("global flag 'key' cannot have type 'secret'")
+        );
+    }
+
+    #[test]
+    fn test_prompt_message() {
+        let manifest = |extra: &str| {
+            Manifest::from_str(&format!(
+                r#"
+                [cli]
+                name = "test"
+                language = "rust"
+
+                [commands.greet]
+                description = "Greet someone"
+
+                [commands.greet.args.name]
+                type = "string"
+                prompt = "What is your name?"
+                {}
+                "#,
+                extra
+            ))
+        };
+        let schema = manifest("").unwrap();
+        assert_eq!(
+            schema.commands["greet"].args["name"].prompt,
+            Some(PromptKind::Text("What is your name?".to_string()))
+        );
+
+        let err = manifest("default = \"world\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("argument 'name' in 'greet' has a prompt message and a default")
+        );
+
+        let err = manifest("required = false").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("argument 'name' in 'greet' has a prompt message but is not required")
+        );
+
+        let err = manifest("[commands.greet.args.target]\ntype = \"int\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "required argument 'target' in 'greet' cannot come after prompted argument 'name'"
+        ));
+
+        let err = manifest("[commands.greet.flags.no-input]\ntype = \"bool\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "has the same name as the global flag turning off prompts for missing arguments"
+        ));
+
+        let err = manifest("[flags.no-input]\ntype = \"string\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("global flag 'no-input' must be a bool")
+        );
+        assert!(manifest("[flags.no-input]\ntype = \"bool\"").is_ok());
+    }
+
+    // ========================================================================
+    // Exit code tests
+    // ========================================================================
+
+    #[test]
+    fn test_exit_codes_parse() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.deploy]
+            description = "Deploy the app"
+
+            [commands.deploy.exit_codes]
+            NotFoundError = 3
+            AuthError = 4
+            "#,
+        );
+
+        let cmd = schema.commands.get("deploy").unwrap();
+        assert_eq!(cmd.exit_codes.get("NotFoundError"), Some(&3));
+        assert_eq!(cmd.exit_codes.get("AuthError"), Some(&4));
+    }
+
+    #[test]
+    fn test_exit_code_zero_rejected() {
+        let result = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.deploy]
+            description = "Deploy the app"
+
+            [commands.deploy.exit_codes]
+            NotFoundError = 0
+            "#,
+        );
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("between 1 and 255"));
+    }
+
+    // ========================================================================
+    // Context requirement tests
+    // ========================================================================
+
+    #[test]
+    fn test_requires_parse() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "sqlite"
+
+            [context.cache]
+
+            [commands.db]
+            description = "Database commands"
+            requires = ["database"]
+
+            [commands.db.commands.seed]
+            description = "Seed the database"
+            requires = ["cache"]
+            "#,
+        );
+
+        let db = schema.commands.get("db").unwrap();
+        assert_eq!(db.requires, ["database"]);
+        assert_eq!(db.commands["seed"].requires, ["cache"]);
+    }
+
+    #[test]
+    fn test_requires_unknown_resource_rejected() {
+        let result = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "sqlite"
+
+            [commands.db]
+            description = "Database commands"
+
+            [commands.db.commands.seed]
+            description = "Seed the database"
+            requires = ["database", "cache"]
+            "#,
+        );
+
+        let err = result.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires of 'db.seed' names 'cache', which is not in [context]")
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_point_at_source() {
+        let src = r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [commands.deploy]
+            description = "Deploy the app"
+
+            [commands.deploy.flags.kind]
+            type = "string"
+
+            [commands.deploy.exit_codes]
+            NotFoundError = 0
+            "#;
+        let err = Manifest::from_str(src).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(label.offset(), src.find("= 0").unwrap() + 2);
+
+        let err = Manifest::from_str(&src.replace("flags.kind", "flags.delete")).unwrap_err();
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(err.to_string(), "'delete' is a TypeScript reserved keyword");
+        assert_eq!(label.offset(), src.find("kind]").unwrap());
+    }
+
+    // ========================================================================
+    // Unknown field tests
+    // ========================================================================
+
+    #[test]
+    fn test_unknown_arg_field_rejected() {
+        let src = r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.hello]
+            description = "Say hello"
+
+            [commands.hello.args.name]
+            type = "string"
+            requird = true
+            "#;
+        let err = Manifest::from_str(src).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "unknown field 'requird' in argument 'name' of 'hello'"
+        );
+        let Error::UnknownField { span, help, .. } = *err else {
+            panic!("expected unknown field error");
+        };
+        assert_eq!(&src[span.offset()..span.offset() + span.len()], "requird");
+        assert!(help.contains("did you mean 'required'?"));
+    }
+
+    #[test]
+    fn test_unknown_fields_in_nested_and_array_format() {
+        let nested = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.db]
+            description = "Database"
+
+            [commands.db.commands.migrate]
+            description = "Migrate"
+            alias = "m"
+            "#,
+        );
+        let array = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.hello]
+            description = "Say hello"
+
+            [[commands.hello.flags]]
+            name = "loud"
+            shrot = "l"
+            "#,
+        );
+
+        assert!(
+            nested
+                .unwrap_err()
+                .to_string()
+                .contains("'alias' in command 'db.migrate'")
+        );
+        assert!(
+            array
+                .unwrap_err()
+                .to_string()
+                .contains("'shrot' in flag 'loud' of 'hello'")
+        );
+    }
+
+    #[test]
+    fn test_extension_fields_allowed() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [commands.hello]
+            description = "Say hello"
+            x-owner = "platform"
+
+            [commands.hello.args.name]
+            type = "string"
+            x-completion = "users"
+
+            [[commands.hello.flags]]
+            name = "loud"
+            x-hidden = true
+            "#,
+        );
+
+        assert!(schema.commands["hello"].flags.contains_key("loud"));
+    }
 }
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 
-use super::{Command, Flag};
+use baobao_ir::NO_INPUT_FLAG;
+
+use super::{Arg, ArgType, Command, Flag, PromptKind};
 use crate::{
     error::{Error, Result},
-    manifest::ParseContext,
+    manifest::{ParseContext, validate_identifier},
 };
 
 /// Info about a short flag for error reporting
@@ -18,48 +20,201 @@ impl Command {
     /// The context tracks the current path through the command hierarchy,
     /// making error messages more informative.
     pub fn validate(&self, ctx: &ParseContext) -> Result<()> {
-        // Validate argument names
-        for name in self.args.keys() {
-            ctx.validate_name(name, "argument")?;
-        }
+        let table = ctx.command_key();
 
-        // Validate flag names and check for duplicate short flags
-        let mut short_flags: HashMap<char, ShortFlagInfo> = HashMap::new();
+        // Validate argument names
+        for (name, arg) in &self.args {
+            let key = format!("{}.args.{}", table, name);
+            ctx.validate_name_at(name, "argument", &key)?;
+            validate_default_type(
+                ctx,
+                name,
+                "argument",
+                arg.default.as_ref(),
+                arg.default_mismatch(),
+                &key,
+            )?;
+            validate_default_choice(
+                ctx,
+                name,
+                "argument",
+                arg.default.as_ref(),
+                arg.choices.as_deref(),
+                &key,
+            )?;
+            if arg.arg_type == ArgType::Secret {
+                let fields = [
+                    ("choices", arg.choices.is_some()),
+                    ("default", arg.default.is_some()),
+                    ("prompt", arg.prompt.is_some()),
+                    ("multiple", arg.multiple),
+                ];
+                validate_secret_type(ctx, name, "argument", &fields, &key)?;
+            }
+            if arg.arg_type == ArgType::Count {
+                return Err(ctx.validation_error(
+                    format!(
+                        "argument '{}' in '{}' cannot have type 'count'; only flags count occurrences",
+                        name,
+                        ctx.path_string()
+                    ),
+                    ctx.value_span(&format!("{}.type", key)),
+                ));
+            }
+            validate_constraints(
+                ctx,
+                name,
+                "argument",
+                &Constraints::of_arg(arg),
+                arg.choices.is_some(),
+                arg.default.as_ref(),
+                &key,
+            )?;
+            if arg.multiple {
+                validate_multiple(
+                    ctx,
+                    name,
+                    "argument",
+                    &arg.arg_type,
+                    arg.prompt.is_some(),
+                    &key,
+                )?;
+            }
 
-        for (name, flag) in &self.flags {
-            // Validate flag name
-            ctx.validate_name(name, "flag")?;
-
-            if let Some(ref short) = flag.short {
-                let short_char = *short.get_ref();
-                let span = short.span();
-
-                if let Some(existing) = short_flags.get(&short_char) {
-                    return Err(Box::new(Error::DuplicateShortFlag {
-                        src: miette::NamedSource::new(ctx.filename(), ctx.src().to_string()),
-                        first_span: (existing.span.start, existing.span.end - existing.span.start)
-                            .into(),
-                        second_span: (span.start, span.end - span.start).into(),
-                        short: short_char,
-                        first_flag: existing.flag_name.to_string(),
-                        second_flag: name.clone(),
-                    }));
+            match arg.prompt {
+                Some(PromptKind::Confirm) => {
+                    return Err(ctx.validation_error(
+                        format!(
+                            "argument '{}' in '{}' cannot use prompt = \"confirm\"; use a bool flag instead",
+                            name,
+                            ctx.path_string()
+                        ),
+                        ctx.value_span(&format!("{}.prompt", key)),
+                    ));
+                }
+                Some(PromptKind::Secret) => {
+                    validate_prompt_type(ctx, name, "argument", SECRET_PROMPT, &arg.arg_type, &key)?
                 }
+                Some(PromptKind::Text(ref message)) => {
+                    validate_prompt_type(ctx, name, "argument", TEXT_PROMPT, &arg.arg_type, &key)?;
+                    let conflict = if message.trim().is_empty() {
+                        Some("an empty prompt message")
+                    } else if arg.default.is_some() {
+                        Some("a prompt message and a default")
+                    } else if !arg.required {
+                        Some("a prompt message but is not required")
+                    } else {
+                        None
+                    };
+                    if let Some(conflict) = conflict {
+                        return Err(ctx.validation_error(
+                            format!(
+                                "argument '{}' in '{}' has {}",
+                                name,
+                                ctx.path_string(),
+                                conflict
+                            ),
+                            ctx.value_span(&format!("{}.prompt", key)),
+                        ));
+                    }
+                }
+                None => {}
+            }
+        }
 
-                short_flags.insert(
-                    short_char,
-                    ShortFlagInfo {
-                        flag_name: name,
-                        span,
+        // Only the last positional can take several values
+        self.validate_indexes(ctx, &table)?;
+        let positionals = self.positional_args();
+        if let Some((name, _)) = positionals
+            .iter()
+            .rev()
+            .skip(1)
+            .find(|(_, arg)| arg.multiple)
+        {
+            return Err(ctx.validation_error(
+                format!(
+                    "argument '{}' in '{}' takes multiple values but is not the last argument",
+                    name,
+                    ctx.path_string()
+                ),
+                ctx.value_span(&format!("{}.args.{}.multiple", table, name)),
+            ));
+        }
+
+        // Secrets and prompted arguments may be left out on the command
+        // line, so no required argument can follow them
+        if let Some(asks) = positionals
+            .iter()
+            .position(|(_, arg)| arg.asks_when_missing())
+            && let Some((name, _)) = positionals[asks + 1..]
+                .iter()
+                .find(|(_, arg)| arg.required && arg.default.is_none() && !arg.asks_when_missing())
+        {
+            let (other, arg) = positionals[asks];
+            return Err(ctx.validation_error(
+                format!(
+                    "required argument '{}' in '{}' cannot come after {} argument '{}'",
+                    name,
+                    ctx.path_string(),
+                    if arg.arg_type == ArgType::Secret {
+                        "secret"
+                    } else {
+                        "prompted"
                     },
-                );
+                    other
+                ),
+                ctx.key_span(&format!("{}.args.{}", table, name)),
+            ));
+        }
+
+        self.validate_aliases(ctx, &table)?;
+        self.validate_deprecations(ctx, &table)?;
+        if let Some(i) = self.examples.iter().position(|e| e.trim().is_empty()) {
+            return Err(ctx.validation_error(
+                format!("example {} of '{}' is empty", i + 1, ctx.path_string()),
+                ctx.value_span(&format!("{}.examples", table)),
+            ));
+        }
+        validate_flags(ctx, &self.flags, &format!("{}.flags", table))?;
+        self.validate_groups(ctx, &table)?;
+        self.validate_requirements(ctx, &table)?;
+
+        for (error, code) in &self.exit_codes {
+            let key = format!("{}.exit_codes.{}", table, error);
+            if !is_error_name(error) {
+                return Err(ctx.validation_error(
+                    format!(
+                        "exit code key '{}' in '{}' is not a valid error name",
+                        error,
+                        ctx.path_string()
+                    ),
+                    ctx.key_span(&key),
+                ));
+            }
+            if *code == 0 {
+                return Err(ctx.validation_error(
+                    format!(
+                        "exit code for '{}' in '{}' must be between 1 and 255",
+                        error,
+                        ctx.path_string()
+                    ),
+                    ctx.value_span(&key),
+                ));
             }
         }
 
+        validate_default_command(
+            ctx,
+            &format!("'{}'", ctx.path_string()),
+            self.default_command.as_deref(),
+            &self.commands,
+            &format!("{}.default_command", table),
+        )?;
+
         // Validate nested commands
         for (name, cmd) in &self.commands {
             // Validate subcommand name
-            ctx.validate_name(name, "subcommand")?;
+            ctx.validate_name_at(name, "subcommand", &format!("{}.commands.{}", table, name))?;
 
             // Create a nested context with the subcommand path
             let nested_ctx = ctx.push(name);
@@ -70,10 +225,1133 @@ impl Command {
     }
 }
 
+impl Command {
+    /// Check that aliases are valid command names that differ from the
+    /// command's own name and from each other. Clashes with sibling commands
+    /// are reported by the `duplicate-command` lint.
+    fn validate_aliases(&self, ctx: &ParseContext, table: &str) -> Result<()> {
+        let name = ctx.name().unwrap_or_default();
+        for (i, alias) in self.aliases.iter().enumerate() {
+            let message = if let Some(reason) = validate_identifier(alias) {
+                format!("is invalid: {}", reason)
+            } else if alias == name {
+                "is the command's own name".to_string()
+            } else if self.aliases[..i].contains(alias) {
+                "is listed twice".to_string()
+            } else {
+                continue;
+            };
+            return Err(ctx.validation_error(
+                format!("alias '{}' of '{}' {}", alias, ctx.path_string(), message),
+                ctx.value_span(&format!("{}.aliases", table)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that argument indexes are positions between 1 and the number of
+    /// arguments, and that no two arguments share one.
+    fn validate_indexes(&self, ctx: &ParseContext, table: &str) -> Result<()> {
+        let mut names: Vec<_> = self.args.keys().collect();
+        names.sort();
+        let mut taken: HashMap<usize, &String> = HashMap::new();
+        for name in names {
+            let Some(index) = self.args[name].index else {
+                continue;
+            };
+            let message = if index == 0 || index > self.args.len() {
+                format!("must be between 1 and {}", self.args.len())
+            } else if let Some(other) = taken.insert(index, name) {
+                format!("is also the index of '{}'", other)
+            } else {
+                continue;
+            };
+            return Err(ctx.validation_error(
+                format!(
+                    "index {} of argument '{}' in '{}' {}",
+                    index,
+                    name,
+                    ctx.path_string(),
+                    message
+                ),
+                ctx.value_span(&format!("{}.args.{}.index", table, name)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that deprecation messages are not empty and that deprecated
+    /// flags have no default, which would always count as given.
+    fn validate_deprecations(&self, ctx: &ParseContext, table: &str) -> Result<()> {
+        if self
+            .deprecated
+            .as_ref()
+            .is_some_and(|m| m.trim().is_empty())
+        {
+            return Err(ctx.validation_error(
+                format!("'deprecated' of '{}' cannot be empty", ctx.path_string()),
+                ctx.value_span(&format!("{}.deprecated", table)),
+            ));
+        }
+        for (name, flag) in &self.flags {
+            let Some(message) = &flag.deprecated else {
+                continue;
+            };
+            let error = if message.trim().is_empty() {
+                "cannot be empty"
+            } else if flag.default.is_some() {
+                "cannot be used on a flag with a default"
+            } else {
+                continue;
+            };
+            return Err(ctx.validation_error(
+                format!(
+                    "'deprecated' of flag '{}' in '{}' {}",
+                    name,
+                    ctx.path_string(),
+                    error
+                ),
+                ctx.value_span(&format!("{}.flags.{}.deprecated", table, name)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that groups name two or more of this command's own inputs and
+    /// are exclusive or required. Members cannot have defaults, which would
+    /// always count as given, and an exclusive group cannot hold a required
+    /// argument.
+    fn validate_groups(&self, ctx: &ParseContext, table: &str) -> Result<()> {
+        for (name, group) in &self.groups {
+            let key = format!("{}.groups.{}", table, name);
+            ctx.validate_name_at(name, "group", &key)?;
+            // Errors point at the group's name, or at `members` when known
+            let error = |message: String, members: bool| {
+                let span = if members {
+                    ctx.value_span(&format!("{}.members", key))
+                } else {
+                    None
+                };
+                Err(ctx.validation_error(
+                    format!("group '{}' in '{}' {}", name, ctx.path_string(), message),
+                    span.or_else(|| ctx.key_span(&key)),
+                ))
+            };
+            if self.args.contains_key(name) || self.flags.contains_key(name) {
+                return error(
+                    "has the same name as an argument or flag".to_string(),
+                    false,
+                );
+            }
+            if !group.exclusive && !group.required {
+                return error("must set 'exclusive' or 'required'".to_string(), false);
+            }
+            if group.members.len() < 2 {
+                return error("needs at least two members".to_string(), true);
+            }
+            for (i, member) in group.members.iter().enumerate() {
+                if group.members[..i].contains(member) {
+                    return error(format!("lists member '{}' twice", member), true);
+                }
+                let default = match (self.args.get(member), self.flags.get(member)) {
+                    (Some(arg), _) => arg.default.is_some(),
+                    (_, Some(flag)) => flag.default.is_some(),
+                    _ => false,
+                };
+                if default {
+                    return error(
+                        format!(
+                            "has member '{}' with a default, which would always count as given",
+                            member
+                        ),
+                        true,
+                    );
+                }
+                if let Some(arg) = self.args.get(member) {
+                    if group.exclusive && arg.required {
+                        return error(
+                            format!(
+                                "is exclusive but member '{}' is a required argument",
+                                member
+                            ),
+                            true,
+                        );
+                    }
+                } else if !self.flags.contains_key(member) {
+                    return error(
+                        format!(
+                            "has unknown member '{}'; members must be arguments or flags of the command",
+                            member
+                        ),
+                        true,
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Command {
+    /// Check that each flag's `requires` and `required_if` name other inputs
+    /// of this command. Neither the flag nor the inputs it requires can have
+    /// a default, which would always count as given, and `required_if` must
+    /// test a single string flag for a value it can take.
+    fn validate_requirements(&self, ctx: &ParseContext, table: &str) -> Result<()> {
+        for (name, flag) in &self.flags {
+            let key = format!("{}.flags.{}", table, name);
+            let error = |field: &str, message: String| {
+                Err(ctx.validation_error(
+                    format!(
+                        "'{}' of flag '{}' in '{}' {}",
+                        field,
+                        name,
+                        ctx.path_string(),
+                        message
+                    ),
+                    ctx.value_span(&format!("{}.{}", key, field)),
+                ))
+            };
+            let field = match (flag.requires.is_empty(), &flag.required_if) {
+                (true, None) => continue,
+                (false, _) => "requires",
+                (true, Some(_)) => "required_if",
+            };
+            if flag.default.is_some() {
+                return error(field, "cannot be used on a flag with a default".to_string());
+            }
+
+            for (i, other) in flag.requires.iter().enumerate() {
+                if other == name {
+                    return error("requires", "names the flag itself".to_string());
+                }
+                if flag.requires[..i].contains(other) {
+                    return error("requires", format!("lists '{}' twice", other));
+                }
+                let default = match (self.args.get(other), self.flags.get(other)) {
+                    (Some(arg), _) => arg.default.is_some(),
+                    (_, Some(flag)) => flag.default.is_some(),
+                    _ => {
+                        return error(
+                            "requires",
+                            format!(
+                                "names unknown input '{}'; it must be an argument or flag of the command",
+                                other
+                            ),
+                        );
+                    }
+                };
+                if default {
+                    return error(
+                        "requires",
+                        format!(
+                            "names '{}', which has a default and would always count as given",
+                            other
+                        ),
+                    );
+                }
+            }
+
+            let Some(required_if) = &flag.required_if else {
+                continue;
+            };
+            if &required_if.flag == name {
+                return error("required_if", "names the flag itself".to_string());
+            }
+            let Some(other) = self.flags.get(&required_if.flag) else {
+                return error(
+                    "required_if",
+                    format!(
+                        "names unknown flag '{}'; it must be a flag of the command",
+                        required_if.flag
+                    ),
+                );
+            };
+            if other.flag_type != ArgType::String || other.multiple {
+                return error(
+                    "required_if",
+                    format!(
+                        "names flag '{}', which is not a single string flag",
+                        required_if.flag
+                    ),
+                );
+            }
+            if other
+                .choices
+                .as_ref()
+                .is_some_and(|choices| !choices.contains(&required_if.value))
+            {
+                return error(
+                    "required_if",
+                    format!(
+                        "tests value '{}', which is not one of the choices of flag '{}'",
+                        required_if.value, required_if.flag
+                    ),
+                );
+            }
+            if other.default.as_ref().and_then(toml::Value::as_str) == Some(&required_if.value) {
+                return error(
+                    "required_if",
+                    format!(
+                        "tests value '{}', which is already the default of flag '{}'",
+                        required_if.value, required_if.flag
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate flag names, defaults and prompts, and check for duplicate short
+/// flags. `table` is the dotted key of the flags table.
+pub(crate) fn validate_flags(
+    ctx: &ParseContext,
+    flags: &HashMap<String, Flag>,
+    table: &str,
+) -> Result<()> {
+    let mut short_flags: HashMap<char, ShortFlagInfo> = HashMap::new();
+
+    for (name, flag) in flags {
+        // Validate flag name
+        let key = format!("{}.{}", table, name);
+        ctx.validate_name_at(name, "flag", &key)?;
+        validate_default_type(
+            ctx,
+            name,
+            "flag",
+            flag.default.as_ref(),
+            flag.default_mismatch(),
+            &key,
+        )?;
+        validate_default_choice(
+            ctx,
+            name,
+            "flag",
+            flag.default.as_ref(),
+            flag.choices.as_deref(),
+            &key,
+        )?;
+        if flag.flag_type == ArgType::Count {
+            validate_count(ctx, name, flag, &key)?;
+        }
+        if flag.flag_type == ArgType::Secret {
+            let fields = [
+                ("choices", flag.choices.is_some()),
+                ("default", flag.default.is_some()),
+                ("prompt", flag.prompt.is_some()),
+                ("multiple", flag.multiple),
+            ];
+            validate_secret_type(ctx, name, "flag", &fields, &key)?;
+        }
+        validate_constraints(
+            ctx,
+            name,
+            "flag",
+            &Constraints::of_flag(flag),
+            flag.choices.is_some(),
+            flag.default.as_ref(),
+            &key,
+        )?;
+        if flag.multiple {
+            validate_multiple(
+                ctx,
+                name,
+                "flag",
+                &flag.flag_type,
+                flag.prompt.is_some(),
+                &key,
+            )?;
+        }
+        if flag.delimiter.is_some() {
+            validate_delimiter(ctx, name, flag, &key)?;
+        }
+
+        match flag.prompt {
+            Some(PromptKind::Confirm) if flag.flag_type != ArgType::Bool => {
+                return Err(ctx.validation_error(
+                    format!(
+                        "flag '{}' in '{}' uses prompt = \"confirm\" but is not a bool flag",
+                        name,
+                        ctx.path_string()
+                    ),
+                    ctx.value_span(&format!("{}.type", key)),
+                ));
+            }
+            Some(PromptKind::Secret) => {
+                validate_prompt_type(ctx, name, "flag", SECRET_PROMPT, &flag.flag_type, &key)?
+            }
+            Some(PromptKind::Text(_)) => {
+                return Err(ctx.validation_error(
+                    format!(
+                        "flag '{}' in '{}' cannot have a prompt message; only arguments ask for missing values",
+                        name,
+                        ctx.path_string()
+                    ),
+                    ctx.value_span(&format!("{}.prompt", key)),
+                ));
+            }
+            _ => {}
+        }
+
+        // Bool and count flags take no value to complete
+        if let Some(hint) = flag.hint
+            && matches!(flag.flag_type, ArgType::Bool | ArgType::Count)
+        {
+            return Err(ctx.validation_error(
+                format!(
+                    "flag '{}' in '{}' sets hint = \"{}\" but has type '{}', which takes no value",
+                    name,
+                    ctx.path_string(),
+                    hint.as_str(),
+                    flag.flag_type.as_str()
+                ),
+                ctx.value_span(&format!("{}.hint", key)),
+            ));
+        }
+
+        if let Some(ref short) = flag.short {
+            let short_char = *short.get_ref();
+            let span = short.span();
+
+            if let Some(existing) = short_flags.get(&short_char) {
+                return Err(Box::new(Error::DuplicateShortFlag {
+                    src: miette::NamedSource::new(ctx.filename(), ctx.src().to_string()),
+                    first_span: (existing.span.start, existing.span.end - existing.span.start)
+                        .into(),
+                    second_span: (span.start, span.end - span.start).into(),
+                    short: short_char,
+                    first_flag: existing.flag_name.to_string(),
+                    second_flag: name.clone(),
+                }));
+            }
+
+            short_flags.insert(
+                short_char,
+                ShortFlagInfo {
+                    flag_name: name,
+                    span,
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+const SECRET_PROMPT: &str = "prompt = \"secret\"";
+const TEXT_PROMPT: &str = "a prompt message";
+
+/// Secret and message prompts read a string, so the input must be
+/// string-typed.
+fn validate_prompt_type(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    prompt: &str,
+    ty: &ArgType,
+    key: &str,
+) -> Result<()> {
+    if *ty != ArgType::String {
+        // Flags default to bool, so `type` may be missing
+        let span = ctx
+            .value_span(&format!("{}.type", key))
+            .or_else(|| ctx.value_span(&format!("{}.prompt", key)));
+        return Err(ctx.validation_error(
+            format!(
+                "{} '{}' in '{}' uses {} but has type '{}' (expected 'string')",
+                kind,
+                name,
+                ctx.path_string(),
+                prompt,
+                ty.as_str()
+            ),
+            span,
+        ));
+    }
+    Ok(())
+}
+
+/// Count flags take no value and start at zero, so they cannot have
+/// choices, a default, a prompt or multiple values.
+fn validate_count(ctx: &ParseContext, name: &str, flag: &Flag, key: &str) -> Result<()> {
+    let field = if flag.choices.is_some() {
+        "choices"
+    } else if flag.default.is_some() {
+        "default"
+    } else if flag.prompt.is_some() {
+        "prompt"
+    } else if flag.multiple {
+        "multiple"
+    } else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "count flag '{}' in '{}' cannot set '{}'",
+            name,
+            ctx.path_string(),
+            field
+        ),
+        ctx.value_span(&format!("{}.{}", key, field)),
+    ))
+}
+
+/// Secret inputs come from the command line, the environment or a hidden
+/// prompt, so they cannot have choices, a default, another prompt or
+/// multiple values. `fields` pairs each of those fields with whether it is
+/// set.
+fn validate_secret_type(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    fields: &[(&str, bool)],
+    key: &str,
+) -> Result<()> {
+    let Some((field, _)) = fields.iter().find(|(_, set)| *set) else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "secret {} '{}' in '{}' cannot set '{}'",
+            kind,
+            name,
+            ctx.path_string(),
+            field
+        ),
+        ctx.value_span(&format!("{}.{}", key, field)),
+    ))
+}
+
+/// Bool inputs are switches and prompts ask for a single value, so neither
+/// can take several values.
+fn validate_multiple(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    ty: &ArgType,
+    prompt: bool,
+    key: &str,
+) -> Result<()> {
+    let reason = if *ty == ArgType::Bool {
+        "is a bool"
+    } else if prompt {
+        "uses a prompt"
+    } else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "{} '{}' in '{}' cannot take multiple values because it {}",
+            kind,
+            name,
+            ctx.path_string(),
+            reason
+        ),
+        ctx.value_span(&format!("{}.multiple", key)),
+    ))
+}
+
+/// The command run when none is given must be one of `commands`, and able to
+/// run with nothing else given: it has no subcommands of its own and no
+/// argument it would have to be given.
+pub(crate) fn validate_default_command(
+    ctx: &ParseContext,
+    owner: &str,
+    default: Option<&str>,
+    commands: &HashMap<String, Command>,
+    key: &str,
+) -> Result<()> {
+    let Some(name) = default else {
+        return Ok(());
+    };
+    let message = match commands.get(name) {
+        None => "is not one of its subcommands".to_string(),
+        Some(cmd) if cmd.has_subcommands() => "has subcommands of its own".to_string(),
+        Some(cmd) => {
+            let mut required: Vec<_> = cmd
+                .args
+                .iter()
+                .filter(|(_, arg)| {
+                    arg.required
+                        && arg.default.is_none()
+                        && arg.prompt.is_none()
+                        && !arg.asks_when_missing()
+                })
+                .map(|(name, _)| name)
+                .collect();
+            required.sort();
+            match required.first() {
+                Some(arg) => format!("requires argument '{}'", arg),
+                None => return Ok(()),
+            }
+        }
+    };
+    Err(ctx.validation_error(
+        format!("default_command '{}' of {} {}", name, owner, message),
+        ctx.value_span(key),
+    ))
+}
+
+/// A delimiter splits the raw text of a multiple flag, so the flag must take
+/// several values and be split before any number or choice is parsed.
+fn validate_delimiter(ctx: &ParseContext, name: &str, flag: &Flag, key: &str) -> Result<()> {
+    let reason = if !flag.multiple {
+        "does not take multiple values".to_string()
+    } else if matches!(flag.flag_type, ArgType::Int | ArgType::Float) {
+        format!(
+            "has type '{}'; only text values can be split",
+            flag.flag_type.as_str()
+        )
+    } else if flag.choices.is_some() {
+        "has choices; only free-form values can be split".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "flag '{}' in '{}' sets delimiter but {}",
+            name,
+            ctx.path_string(),
+            reason
+        ),
+        ctx.value_span(&format!("{}.delimiter", key)),
+    ))
+}
+
+/// A default must have the input's type, and be a list only when the input
+/// takes multiple values.
+fn validate_default_type(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    default: Option<&toml::Value>,
+    mismatch: Option<String>,
+    key: &str,
+) -> Result<()> {
+    let (Some(default), Some(mismatch)) = (default, mismatch) else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "default {} of {} '{}' in '{}' {}",
+            default,
+            kind,
+            name,
+            ctx.path_string(),
+            mismatch
+        ),
+        ctx.value_span(&format!("{}.default", key)),
+    ))
+}
+
+/// Why `default` does not fit an input of type `ty` (`is not an integer`),
+/// if it does not.
+///
+/// String inputs also take numbers and booleans, which the generators turn
+/// into text.
+fn default_mismatch(ty: ArgType, multiple: bool, default: &toml::Value) -> Option<String> {
+    let expected = match ty {
+        ArgType::Int | ArgType::Count => "an integer",
+        ArgType::Float => "a number",
+        ArgType::Bool => "a boolean",
+        ArgType::String => "a string, number or boolean",
+        ArgType::Path
+        | ArgType::Url
+        | ArgType::Duration
+        | ArgType::DateTime
+        | ArgType::Uuid
+        | ArgType::Secret => "a string",
+    };
+    let fits = |value: &toml::Value| match value {
+        toml::Value::Integer(_) => matches!(
+            ty,
+            ArgType::Int | ArgType::Count | ArgType::Float | ArgType::String
+        ),
+        toml::Value::Float(_) => matches!(ty, ArgType::Float | ArgType::String),
+        toml::Value::Boolean(_) => matches!(ty, ArgType::Bool | ArgType::String),
+        toml::Value::String(_) => !matches!(
+            ty,
+            ArgType::Int | ArgType::Count | ArgType::Float | ArgType::Bool
+        ),
+        toml::Value::Datetime(_) | toml::Value::Array(_) | toml::Value::Table(_) => false,
+    };
+    match default {
+        toml::Value::Array(_) if !multiple => Some("is a list, but 'multiple' is not set".into()),
+        toml::Value::Array(items) => items
+            .iter()
+            .find(|item| !fits(item))
+            .map(|item| format!("has item {}, which is not {}", item, expected)),
+        value if fits(value) => None,
+        _ => Some(format!("is not {}", expected)),
+    }
+}
+
+/// A default must be one of the input's choices, as must every item of a
+/// list default.
+fn validate_default_choice(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    default: Option<&toml::Value>,
+    choices: Option<&[String]>,
+    key: &str,
+) -> Result<()> {
+    let (Some(default), Some(choices)) = (default, choices) else {
+        return Ok(());
+    };
+    let values = match default {
+        toml::Value::Array(items) => items.iter().collect(),
+        value => vec![value],
+    };
+    for value in values {
+        let text = match value {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        if !choices.contains(&text) {
+            return Err(ctx.validation_error(
+                format!(
+                    "default '{}' of {} '{}' in '{}' is not one of its choices ({})",
+                    text,
+                    kind,
+                    name,
+                    ctx.path_string(),
+                    choices.join(", ")
+                ),
+                ctx.value_span(&format!("{}.default", key)),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Value constraints of an argument or flag.
+struct Constraints<'a> {
+    ty: ArgType,
+    min: Option<f64>,
+    max: Option<f64>,
+    pattern: Option<&'a str>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    path_exists: bool,
+}
+
+impl<'a> Constraints<'a> {
+    fn of_arg(arg: &'a Arg) -> Self {
+        Self {
+            ty: arg.arg_type,
+            min: arg.min,
+            max: arg.max,
+            pattern: arg.pattern.as_deref(),
+            min_length: arg.min_length,
+            max_length: arg.max_length,
+            path_exists: arg.path_exists,
+        }
+    }
+
+    fn of_flag(flag: &'a Flag) -> Self {
+        Self {
+            ty: flag.flag_type,
+            min: flag.min,
+            max: flag.max,
+            pattern: flag.pattern.as_deref(),
+            min_length: flag.min_length,
+            max_length: flag.max_length,
+            path_exists: flag.path_exists,
+        }
+    }
+
+    /// Names of the constraints that are set.
+    fn fields(&self) -> Vec<&'static str> {
+        [
+            ("min", self.min.is_some()),
+            ("max", self.max.is_some()),
+            ("pattern", self.pattern.is_some()),
+            ("min_length", self.min_length.is_some()),
+            ("max_length", self.max_length.is_some()),
+            ("path_exists", self.path_exists),
+        ]
+        .into_iter()
+        .filter_map(|(field, set)| set.then_some(field))
+        .collect()
+    }
+}
+
+/// Bounds apply to int and float inputs, patterns and lengths to string
+/// inputs and `path_exists` to path inputs. They cannot be combined with
+/// choices, and a default must satisfy them, except for `path_exists`, which
+/// is only known when the command runs.
+fn validate_constraints(
+    ctx: &ParseContext,
+    name: &str,
+    kind: &str,
+    constraints: &Constraints,
+    has_choices: bool,
+    default: Option<&toml::Value>,
+    key: &str,
+) -> Result<()> {
+    let fields = constraints.fields();
+    let Some(&first) = fields.first() else {
+        return Ok(());
+    };
+    let field_span = |field: &str| ctx.value_span(&format!("{}.{}", key, field));
+    if has_choices {
+        return Err(ctx.validation_error(
+            format!(
+                "{} '{}' in '{}' cannot set both 'choices' and '{}'",
+                kind,
+                name,
+                ctx.path_string(),
+                first
+            ),
+            field_span(first),
+        ));
+    }
+    for field in &fields {
+        let (allowed, expected) = match *field {
+            "min" | "max" => (
+                matches!(constraints.ty, ArgType::Int | ArgType::Float),
+                "'int' or 'float'",
+            ),
+            "path_exists" => (constraints.ty == ArgType::Path, "'path'"),
+            _ => (constraints.ty == ArgType::String, "'string'"),
+        };
+        if !allowed {
+            return Err(ctx.validation_error(
+                format!(
+                    "{} '{}' in '{}' sets '{}' but has type '{}' (expected {})",
+                    kind,
+                    name,
+                    ctx.path_string(),
+                    field,
+                    constraints.ty.as_str(),
+                    expected
+                ),
+                field_span(field),
+            ));
+        }
+    }
+
+    for (field, bound) in [("min", constraints.min), ("max", constraints.max)] {
+        let Some(bound) = bound else { continue };
+        let whole = bound.fract() == 0.0;
+        if !bound.is_finite() || (constraints.ty == ArgType::Int && !whole) {
+            return Err(ctx.validation_error(
+                format!(
+                    "'{}' of {} '{}' in '{}' must be a finite {}",
+                    field,
+                    kind,
+                    name,
+                    ctx.path_string(),
+                    if constraints.ty == ArgType::Int {
+                        "integer"
+                    } else {
+                        "number"
+                    }
+                ),
+                field_span(field),
+            ));
+        }
+    }
+    if let (Some(min), Some(max)) = (constraints.min, constraints.max)
+        && min > max
+    {
+        return Err(ctx.validation_error(
+            format!(
+                "{} '{}' in '{}' has min {} greater than max {}",
+                kind,
+                name,
+                ctx.path_string(),
+                min,
+                max
+            ),
+            field_span("min"),
+        ));
+    }
+    let pattern = match constraints.pattern {
+        Some(pattern) => match regex::Regex::new(&format!("^(?:{})$", pattern)) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                return Err(ctx.validation_error(
+                    format!(
+                        "pattern of {} '{}' in '{}' is not a valid regular expression: {}",
+                        kind,
+                        name,
+                        ctx.path_string(),
+                        err
+                    ),
+                    field_span("pattern"),
+                ));
+            }
+        },
+        None => None,
+    };
+    if constraints.max_length == Some(0) {
+        return Err(ctx.validation_error(
+            format!(
+                "'max_length' of {} '{}' in '{}' must be at least 1",
+                kind,
+                name,
+                ctx.path_string()
+            ),
+            field_span("max_length"),
+        ));
+    }
+    if let (Some(min), Some(max)) = (constraints.min_length, constraints.max_length)
+        && min > max
+    {
+        return Err(ctx.validation_error(
+            format!(
+                "{} '{}' in '{}' has min_length {} greater than max_length {}",
+                kind,
+                name,
+                ctx.path_string(),
+                min,
+                max
+            ),
+            field_span("min_length"),
+        ));
+    }
+
+    let Some(default) = default else {
+        return Ok(());
+    };
+    let values = match default {
+        toml::Value::Array(items) => items.iter().collect(),
+        value => vec![value],
+    };
+    for value in values {
+        let violated = match value {
+            toml::Value::Integer(n) => out_of_range(*n as f64, constraints),
+            toml::Value::Float(n) => out_of_range(*n, constraints),
+            toml::Value::String(s) => {
+                let length = s.chars().count();
+                if constraints.min_length.is_some_and(|min| length < min) {
+                    Some("min_length")
+                } else if constraints.max_length.is_some_and(|max| length > max) {
+                    Some("max_length")
+                } else if pattern.as_ref().is_some_and(|regex| !regex.is_match(s)) {
+                    Some("pattern")
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        if let Some(field) = violated {
+            return Err(ctx.validation_error(
+                format!(
+                    "default {} of {} '{}' in '{}' does not satisfy its '{}'",
+                    value,
+                    kind,
+                    name,
+                    ctx.path_string(),
+                    field
+                ),
+                ctx.value_span(&format!("{}.default", key)),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The bound a number falls outside of, if any.
+fn out_of_range(value: f64, constraints: &Constraints) -> Option<&'static str> {
+    if constraints.min.is_some_and(|min| value < min) {
+        Some("min")
+    } else if constraints.max.is_some_and(|max| value > max) {
+        Some("max")
+    } else {
+        None
+    }
+}
+
+/// Error names are matched against `Error.name`, so they must be identifiers.
+fn is_error_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// Validation extension for flags
 impl Flag {
     /// Get the short flag character, if any
     pub fn short_char(&self) -> Option<char> {
         self.short.as_ref().map(|s| *s.get_ref())
     }
+
+    /// Describe how the default does not fit the flag's type, if it does not
+    pub fn default_mismatch(&self) -> Option<String> {
+        let default = self.default.as_ref()?;
+        default_mismatch(self.flag_type, self.multiple, default)
+    }
+}
+
+/// Validation extension for arguments
+impl Arg {
+    /// Describe how the default does not fit the argument's type, if it does not
+    pub fn default_mismatch(&self) -> Option<String> {
+        let default = self.default.as_ref()?;
+        default_mismatch(self.arg_type, self.multiple, default)
+    }
+
+    /// Whether the argument asks for its value with a message when missing
+    pub fn has_prompt_message(&self) -> bool {
+        matches!(self.prompt, Some(PromptKind::Text(_)))
+    }
+
+    /// Whether the argument is read from the environment or asked for when
+    /// left out, so the command line parser treats it as optional
+    pub fn asks_when_missing(&self) -> bool {
+        self.arg_type == ArgType::Secret || self.has_prompt_message()
+    }
+}
+
+/// Validate the manifest's global flags and check that no command declares
+/// an input or short flag that shadows one of them.
+pub(crate) fn validate_global_flags(
+    ctx: &ParseContext,
+    flags: &HashMap<String, Flag>,
+    commands: &HashMap<String, Command>,
+) -> Result<()> {
+    let flags_ctx = ctx.push("flags");
+    validate_flags(&flags_ctx, flags, "flags")?;
+    for (name, flag) in flags {
+        if flag.prompt.is_some() {
+            return Err(ctx.validation_error(
+                format!("global flag '{}' cannot use a prompt", name),
+                ctx.value_span(&format!("flags.{}.prompt", name)),
+            ));
+        }
+        if flag.flag_type == ArgType::Secret {
+            return Err(ctx.validation_error(
+                format!(
+                    "global flag '{}' cannot have type 'secret', which prompts when not given",
+                    name
+                ),
+                ctx.value_span(&format!("flags.{}.type", name)),
+            ));
+        }
+        let requirement = match (flag.requires.is_empty(), &flag.required_if) {
+            (false, _) => Some("requires"),
+            (true, Some(_)) => Some("required_if"),
+            (true, None) => flag.deprecated.as_ref().map(|_| "deprecated"),
+        };
+        if let Some(field) = requirement {
+            return Err(ctx.validation_error(
+                format!("global flag '{}' cannot use '{}'", name, field),
+                ctx.value_span(&format!("flags.{}.{}", name, field)),
+            ));
+        }
+    }
+    // Prompt messages add a `--no-input` flag unless it is declared here
+    if commands.values().any(Command::has_prompt_messages) {
+        match flags.get(NO_INPUT_FLAG) {
+            Some(flag) if flag.flag_type != ArgType::Bool => {
+                return Err(ctx.validation_error(
+                    format!(
+                        "global flag '{}' must be a bool, since it turns off prompts for missing arguments",
+                        NO_INPUT_FLAG
+                    ),
+                    ctx.value_span(&format!("flags.{}.type", NO_INPUT_FLAG))
+                        .or_else(|| ctx.key_span(&format!("flags.{}", NO_INPUT_FLAG))),
+                ));
+            }
+            Some(_) => {}
+            None => {
+                for (name, command) in commands {
+                    check_no_input_shadowing(&ctx.push(name), command)?;
+                }
+            }
+        }
+    }
+    if flags.is_empty() {
+        return Ok(());
+    }
+    for (name, command) in commands {
+        check_shadowing(&ctx.push(name), flags, command)?;
+    }
+    Ok(())
+}
+
+/// No input can take the name of the `--no-input` flag added for prompts.
+fn check_no_input_shadowing(ctx: &ParseContext, command: &Command) -> Result<()> {
+    let table = ctx.command_key();
+    let shadowing = if command.args.contains_key(NO_INPUT_FLAG) {
+        Some(("argument", "args"))
+    } else if command.flags.contains_key(NO_INPUT_FLAG) {
+        Some(("flag", "flags"))
+    } else {
+        None
+    };
+    if let Some((kind, field)) = shadowing {
+        return Err(ctx.validation_error(
+            format!(
+                "{} '{}' in '{}' has the same name as the global flag turning off prompts for missing arguments",
+                kind,
+                NO_INPUT_FLAG,
+                ctx.path_string()
+            ),
+            ctx.key_span(&format!("{}.{}.{}", table, field, NO_INPUT_FLAG)),
+        ));
+    }
+    for (name, child) in &command.commands {
+        check_no_input_shadowing(&ctx.push(name), child)?;
+    }
+    Ok(())
+}
+
+fn check_shadowing(
+    ctx: &ParseContext,
+    globals: &HashMap<String, Flag>,
+    command: &Command,
+) -> Result<()> {
+    let table = ctx.command_key();
+    let inputs = command
+        .args
+        .keys()
+        .map(|name| ("argument", name, format!("{}.args.{}", table, name)))
+        .chain(
+            command
+                .flags
+                .keys()
+                .map(|name| ("flag", name, format!("{}.flags.{}", table, name))),
+        );
+    for (kind, name, key) in inputs {
+        if globals.contains_key(name) {
+            return Err(ctx.validation_error(
+                format!(
+                    "{} '{}' in '{}' has the same name as a global flag",
+                    kind,
+                    name,
+                    ctx.path_string()
+                ),
+                ctx.key_span(&key),
+            ));
+        }
+    }
+    for (name, flag) in &command.flags {
+        let Some(short) = flag.short.as_ref().map(|s| *s.get_ref()) else {
+            continue;
+        };
+        if let Some((global, _)) = globals
+            .iter()
+            .find(|(_, g)| g.short.as_ref().is_some_and(|s| *s.get_ref() == short))
+        {
+            return Err(ctx.validation_error(
+                format!(
+                    "flag '{}' in '{}' uses short '-{}', already taken by global flag '{}'",
+                    name,
+                    ctx.path_string(),
+                    short,
+                    global
+                ),
+                ctx.value_span(&format!("{}.flags.{}.short", table, name)),
+            ));
+        }
+    }
+    for (name, child) in &command.commands {
+        check_shadowing(&ctx.push(name), globals, child)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the in-memory cache
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct CacheConfig {
+    /// Maximum number of entries (default: 1000)
+    pub max_capacity: Option<u64>,
+
+    /// Seconds an entry lives after it is inserted (default: no expiry)
+    pub ttl: Option<u64>,
+
+    /// Where entries are kept (default: memory)
+    #[serde(default)]
+    pub mode: CacheMode,
+
+    /// Directory of the disk cache (default: the CLI name in the user cache directory)
+    pub path: Option<String>,
+}
+
+impl CacheConfig {
+    /// The maximum number of entries, or the default one.
+    pub fn resolved_max_capacity(&self) -> u64 {
+        self.max_capacity.unwrap_or(1000)
+    }
+}
+
+/// Where cache entries are kept
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// In memory, for a single invocation
+    #[default]
+    Memory,
+    /// In memory, backed by files that outlive the invocation
+    Disk,
+}
+
+impl CacheMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheMode::Memory => "memory",
+            CacheMode::Disk => "disk",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_cache_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.cache]
+            ttl = 300
+            mode = "disk"
+            "#,
+        );
+
+        let cache = schema.context.cache_config().unwrap();
+        assert_eq!(cache.mode, CacheMode::Disk);
+        assert_eq!(cache.ttl, Some(300));
+        assert_eq!(cache.path, None);
+        assert_eq!(cache.resolved_max_capacity(), 1000);
+    }
+
+    #[test]
+    fn test_cache_validation() {
+        assert!(parse_context("cache", "").is_ok());
+        assert!(parse_context("cache", "mode = \"disk\"\npath = \".cache\"").is_ok());
+
+        let err = parse_context("cache", "path = \".cache\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("path in [context.cache] only applies to mode = \"disk\"")
+        );
+
+        let err = parse_context("cache", "max_capacity = 0").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("max_capacity in [context.cache] must be greater than 0")
+        );
+
+        let err = parse_context("cache", "ttl = 0").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("ttl in [context.cache] must be greater than 0")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the application config file
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct AppConfig {
+    /// Path of the config file, relative to the working directory
+    pub path: String,
+
+    /// File format (default: from the file extension)
+    pub format: Option<ConfigFormat>,
+
+    /// Fields of the file, by key
+    #[serde(default)]
+    pub fields: BTreeMap<String, ConfigField>,
+}
+
+impl AppConfig {
+    /// The declared format, or the one implied by the file extension.
+    pub fn resolved_format(&self) -> Option<ConfigFormat> {
+        self.format.or_else(
+            || match self.path.rsplit_once('.').map(|(_, extension)| extension) {
+                Some("toml") => Some(ConfigFormat::Toml),
+                Some("json") => Some(ConfigFormat::Json),
+                Some("yaml" | "yml") => Some(ConfigFormat::Yaml),
+                _ => None,
+            },
+        )
+    }
+}
+
+/// Config file format
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Json => "json",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// A field of the config file
+///
+/// Either a type name (`port = "int"`) or a table
+/// (`port = { type = "int", optional = true }`).
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "RawConfigField")]
+pub struct ConfigField {
+    /// Value type
+    pub field_type: ConfigFieldType,
+    /// Whether the key may be left out of the file
+    pub optional: bool,
+}
+
+/// Value type of a config field
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl ConfigFieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigFieldType::String => "string",
+            ConfigFieldType::Int => "int",
+            ConfigFieldType::Float => "float",
+            ConfigFieldType::Bool => "bool",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawConfigField {
+    Short(ConfigFieldType),
+    Full {
+        #[serde(rename = "type")]
+        field_type: ConfigFieldType,
+        #[serde(default)]
+        optional: bool,
+    },
+}
+
+impl From<RawConfigField> for ConfigField {
+    fn from(raw: RawConfigField) -> Self {
+        match raw {
+            RawConfigField::Short(field_type) => Self {
+                field_type,
+                optional: false,
+            },
+            RawConfigField::Full {
+                field_type,
+                optional,
+            } => Self {
+                field_type,
+                optional,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_app_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.config]
+            path = "settings.yml"
+
+            [context.config.fields]
+            api_url = "string"
+            retries = { type = "int", optional = true }
+            "#,
+        );
+
+        let config = schema.context.app_config().unwrap();
+        assert_eq!(config.path, "settings.yml");
+        assert_eq!(config.format, None);
+        assert_eq!(config.resolved_format(), Some(ConfigFormat::Yaml));
+        assert_eq!(
+            config.fields["api_url"],
+            ConfigField {
+                field_type: ConfigFieldType::String,
+                optional: false,
+            }
+        );
+        assert_eq!(
+            config.fields["retries"],
+            ConfigField {
+                field_type: ConfigFieldType::Int,
+                optional: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_app_config_validation() {
+        assert!(parse_context("config", "path = \"settings\"\nformat = \"json\"").is_ok());
+
+        let err = parse_context("config", "path = \"settings.ini\"").unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "cannot infer the format of 'settings.ini' in [context.config]; set format"
+            )
+        );
+
+        let err = parse_context(
+            "config",
+            "path = \"config.toml\"\nfields = { \"1st\" = \"string\" }",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("1st"));
+    }
+}
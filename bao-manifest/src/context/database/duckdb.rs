@@ -0,0 +1,141 @@
+use serde::Deserialize;
+
+use super::{DatabaseConfig, PoolConfig};
+
+/// Configuration for DuckDB database
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DuckdbConfig {
+    /// Direct file path to the DuckDB database (e.g., "analytics.duckdb", or ":memory:")
+    pub path: Option<String>,
+
+    /// Environment variable for database path (ignored if `path` is set)
+    pub env: Option<String>,
+
+    /// Pool configuration
+    #[serde(flatten)]
+    pub pool: PoolConfig,
+
+    /// Open the database in read-only mode (default: false)
+    pub read_only: Option<bool>,
+
+    /// Number of threads queries run on (default: one per core)
+    pub threads: Option<u32>,
+}
+
+impl DuckdbConfig {
+    /// Returns true if any DuckDB-specific option is configured
+    pub fn has_duckdb_options(&self) -> bool {
+        self.read_only.is_some() || self.threads.is_some()
+    }
+}
+
+impl DatabaseConfig for DuckdbConfig {
+    fn env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+
+    fn pool(&self) -> &PoolConfig {
+        &self.pool
+    }
+
+    fn sqlx_feature(&self) -> &'static str {
+        "duckdb"
+    }
+
+    // sqlx has no DuckDB driver, so pools come from the duckdb crate's r2d2 manager
+    fn dependencies(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "duckdb",
+                r#"{ version = "1", features = ["bundled", "r2d2"] }"#,
+            ),
+            ("r2d2", r#""0.8""#),
+            (
+                "tokio",
+                r#"{ version = "1", features = ["rt-multi-thread", "macros"] }"#,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{ContextField, Manifest};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_duckdb_basic() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "duckdb"
+            env = "DUCKDB_PATH"
+            "#,
+        );
+
+        let database = schema.context.database.as_ref().unwrap();
+        assert!(matches!(database, ContextField::Duckdb(_)));
+        assert_eq!(database.type_name(), "duckdb");
+        assert_eq!(database.env(), Some("DUCKDB_PATH"));
+        assert!(database.is_database());
+    }
+
+    #[test]
+    fn test_duckdb_full_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "duckdb"
+            path = "analytics.duckdb"
+            read_only = true
+            threads = 4
+            max_connections = 2
+            "#,
+        );
+
+        let database = schema.context.database.as_ref().unwrap();
+        let duckdb = database.duckdb_config().unwrap();
+        assert!(duckdb.has_duckdb_options());
+        assert_eq!(duckdb.path, Some("analytics.duckdb".to_string()));
+        assert_eq!(duckdb.read_only, Some(true));
+        assert_eq!(duckdb.threads, Some(4));
+        assert_eq!(duckdb.pool.max_connections, Some(2));
+        assert!(
+            database
+                .dependencies()
+                .iter()
+                .any(|(name, _)| *name == "duckdb")
+        );
+        assert!(
+            !database
+                .dependencies()
+                .iter()
+                .any(|(name, _)| *name == "sqlx")
+        );
+    }
+
+    #[test]
+    fn test_duckdb_zero_threads() {
+        let err = Manifest::from_str(
+            "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n[context.database]\ntype = \"duckdb\"\nthreads = 0",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("threads in [context.database] must be greater than 0")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod duckdb;
 pub mod mysql;
 pub mod postgres;
 pub mod sqlite;
@@ -7,7 +8,7 @@ use serde::Deserialize;
 /// Trait for database configuration types.
 ///
 /// This trait provides a common interface for accessing shared properties
-/// across PostgreSQL, MySQL, and SQLite configurations, reducing code
+/// across PostgreSQL, MySQL, SQLite and DuckDB configurations, reducing code
 /// duplication in `ContextField` methods.
 pub trait DatabaseConfig {
     /// Get the environment variable name for the connection string.
@@ -58,6 +59,21 @@ pub struct BasicDbConfig {
     /// Pool configuration
     #[serde(flatten)]
     pub pool: PoolConfig,
+
+    /// Read-only replica, as `[context.database.replica]`
+    pub replica: Option<ReplicaConfig>,
+}
+
+/// A read-only replica of a PostgreSQL or MySQL database, pooled separately
+/// from the primary.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ReplicaConfig {
+    /// Environment variable for the replica's connection string (default: DATABASE_REPLICA_URL)
+    pub env: Option<String>,
+
+    /// Pool configuration of the replica
+    #[serde(flatten)]
+    pub pool: PoolConfig,
 }
 
 impl DatabaseConfig for sqlite::SqliteConfig {
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use super::{BasicDbConfig, DatabaseConfig, PoolConfig};
+use super::{BasicDbConfig, DatabaseConfig, PoolConfig, ReplicaConfig};
 
 /// Configuration for MySQL database.
 ///
@@ -20,6 +20,11 @@ impl MySqlConfig {
     pub fn pool(&self) -> &PoolConfig {
         &self.0.pool
     }
+
+    /// Get the read-only replica configuration.
+    pub fn replica(&self) -> Option<&ReplicaConfig> {
+        self.0.replica.as_ref()
+    }
 }
 
 impl DatabaseConfig for MySqlConfig {
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use super::{BasicDbConfig, DatabaseConfig, PoolConfig};
+use super::{BasicDbConfig, DatabaseConfig, PoolConfig, ReplicaConfig};
 
 /// Configuration for PostgreSQL database.
 ///
@@ -20,6 +20,11 @@ impl PostgresConfig {
     pub fn pool(&self) -> &PoolConfig {
         &self.0.pool
     }
+
+    /// Get the read-only replica configuration.
+    pub fn replica(&self) -> Option<&ReplicaConfig> {
+        self.0.replica.as_ref()
+    }
 }
 
 impl DatabaseConfig for PostgresConfig {
@@ -38,7 +43,7 @@ impl DatabaseConfig for PostgresConfig {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ContextField, Manifest};
+    use crate::{ContextField, Manifest, context::parse_context};
 
     fn parse(content: &str) -> Manifest {
         toml::from_str(content).expect("Failed to parse TOML")
@@ -168,4 +173,63 @@ mod tests {
         assert_eq!(pool.max_connections, Some(15));
         assert_eq!(pool.min_connections, Some(2));
     }
+
+    #[test]
+    fn test_replica_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.database]
+            type = "postgres"
+            max_connections = 5
+
+            [context.database.replica]
+            env = "READER_URL"
+            max_connections = 20
+            "#,
+        );
+
+        let database = schema.context.database.as_ref().unwrap();
+        assert_eq!(database.pool_config().unwrap().max_connections, Some(5));
+        let replica = database.replica_config().unwrap();
+        assert_eq!(replica.env.as_deref(), Some("READER_URL"));
+        assert_eq!(replica.pool.max_connections, Some(20));
+    }
+
+    #[test]
+    fn test_replica_validation() {
+        assert!(
+            parse_context("database", "type = \"mysql\"\n\n[context.database.replica]").is_ok()
+        );
+
+        let err = parse_context(
+            "database",
+            "type = \"sqlite\"\n\n[context.database.replica]",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "replica in [context.database] is only supported for postgres and mysql, not sqlite"
+        ));
+
+        let err = parse_context(
+            "database",
+            "type = \"postgres\"\nenv = \"PG_URL\"\n\n[context.database.replica]\nenv = \"PG_URL\"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "[context.database.replica] reads PG_URL, the connection string of the primary"
+        ));
+
+        let err = parse_context(
+            "database",
+            "type = \"postgres\"\n\n[context.database.replica]\n\n[context.http.db_replica]\nbase_url = \"https://example.com\"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "HTTP client 'db_replica' conflicts with the built-in context field of the same name"
+        ));
+    }
 }
@@ -32,6 +32,9 @@ pub struct SqliteConfig {
 
     /// Enable foreign key constraints (default: true)
     pub foreign_keys: Option<bool>,
+
+    /// Directory of `<version>_<name>.up.sql` / `.down.sql` migration files
+    pub migrations: Option<String>,
 }
 
 impl SqliteConfig {
@@ -275,4 +278,25 @@ mod tests {
         assert_eq!(sqlite.foreign_keys, Some(true));
         assert_eq!(sqlite.pool.max_connections, Some(3));
     }
+
+    #[test]
+    fn test_sqlite_migrations() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [context.database]
+            type = "sqlite"
+            path = "app.db"
+            migrations = "db/migrations"
+            "#,
+        );
+
+        let database = schema.context.database.as_ref().unwrap();
+        let sqlite = database.sqlite_config().unwrap();
+        assert_eq!(sqlite.migrations, Some("db/migrations".to_string()));
+        assert!(!sqlite.has_sqlite_options());
+    }
 }
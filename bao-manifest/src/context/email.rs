@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for an SMTP transport
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct EmailConfig {
+    /// SMTP server host name
+    pub host: String,
+
+    /// SMTP server port (default: 587 for starttls, 465 for tls, 25 for none)
+    pub port: Option<u16>,
+
+    /// How the connection is encrypted (default: starttls)
+    #[serde(default)]
+    pub tls: SmtpTls,
+
+    /// Environment variable holding the SMTP username
+    pub username_env: Option<String>,
+
+    /// Environment variable holding the SMTP password
+    pub password_env: Option<String>,
+}
+
+/// SMTP connection encryption
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS, and fail if unsupported
+    #[default]
+    Starttls,
+    /// Implicit TLS from the first byte
+    Tls,
+    /// Unencrypted, for local development servers
+    None,
+}
+
+impl SmtpTls {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SmtpTls::Starttls => "starttls",
+            SmtpTls::Tls => "tls",
+            SmtpTls::None => "none",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_email_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.email]
+            host = "smtp.example.com"
+            port = 2525
+            username_env = "SMTP_USERNAME"
+            password_env = "SMTP_PASSWORD"
+            "#,
+        );
+
+        let email = schema.context.email_config().unwrap();
+        assert_eq!(email.host, "smtp.example.com");
+        assert_eq!(email.port, Some(2525));
+        assert_eq!(email.tls, SmtpTls::Starttls);
+        assert_eq!(email.username_env.as_deref(), Some("SMTP_USERNAME"));
+        assert_eq!(email.password_env.as_deref(), Some("SMTP_PASSWORD"));
+    }
+
+    #[test]
+    fn test_email_validation() {
+        assert!(
+            parse_context("email", "host = \"localhost\"\nport = 1025\ntls = \"none\"").is_ok()
+        );
+
+        let err = parse_context("email", "host = \"\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("host in [context.email] cannot be empty")
+        );
+
+        let err = parse_context(
+            "email",
+            "host = \"smtp.example.com\"\nusername_env = \"SMTP_USERNAME\"",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "[context.email] needs both username_env and password_env to authenticate"
+            )
+        );
+    }
+}
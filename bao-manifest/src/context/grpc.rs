@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+/// Configuration for a gRPC client channel
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct GrpcConfig {
+    /// Server endpoint, such as `http://localhost:50051`
+    pub endpoint: String,
+
+    /// `.proto` files that clients are generated from
+    #[serde(default)]
+    pub protos: Vec<String>,
+
+    /// Directories searched for imported `.proto` files
+    /// (default: the directories of `protos`)
+    #[serde(default)]
+    pub includes: Vec<String>,
+
+    /// Request timeout in seconds
+    pub timeout: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_grpc_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.grpc]
+            endpoint = "http://localhost:50051"
+            protos = ["proto/greeter.proto"]
+            timeout = 10
+            "#,
+        );
+
+        let grpc = schema.context.grpc_config().unwrap();
+        assert_eq!(grpc.endpoint, "http://localhost:50051");
+        assert_eq!(grpc.protos, ["proto/greeter.proto"]);
+        assert!(grpc.includes.is_empty());
+        assert_eq!(grpc.timeout, Some(10));
+    }
+
+    #[test]
+    fn test_grpc_validation() {
+        assert!(parse_context("grpc", "endpoint = \"https://api.example.com\"").is_ok());
+
+        let err = parse_context("grpc", "endpoint = \"localhost:50051\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "endpoint 'localhost:50051' in [context.grpc] must start with http:// or https://"
+        ));
+
+        let err = parse_context(
+            "grpc",
+            "endpoint = \"http://localhost:50051\"\nprotos = [\"greeter.txt\"]",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'greeter.txt' in [context.grpc] protos is not a .proto file")
+        );
+    }
+
+    #[test]
+    fn test_grpc_requires_endpoint() {
+        let result: Result<Manifest, _> = toml::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.grpc]
+            protos = ["proto/greeter.proto"]
+            "#,
+        );
+        assert!(result.is_err());
+    }
+}
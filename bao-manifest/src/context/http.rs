@@ -1,18 +1,143 @@
-use serde::Deserialize;
+use std::{collections::BTreeMap, fmt};
+
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, Visitor},
+};
 
 /// Configuration for HTTP client
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
 pub struct HttpConfig {
+    /// Base URL that request paths are resolved against
+    pub base_url: Option<String>,
+
+    /// Headers sent with every request
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
     /// Request timeout in seconds
     pub timeout: Option<u64>,
 
     /// User agent string
     pub user_agent: Option<String>,
+
+    /// Times a failed request is retried (default: 0)
+    pub retries: Option<u32>,
+
+    /// Delay before the first retry in milliseconds, doubled for each retry
+    pub backoff_ms: Option<u64>,
+
+    /// Response statuses that are retried (default: 408, 429 and 5xx)
+    #[serde(default)]
+    pub retry_on: Vec<u16>,
+
+    /// Credentials attached to every request
+    pub auth: Option<HttpAuth>,
+}
+
+/// Authentication for an HTTP client, with the secret read from an
+/// environment variable.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HttpAuth {
+    /// `Authorization: Bearer <token>`
+    Bearer {
+        /// Environment variable holding the token
+        env: String,
+    },
+    /// `Authorization: Basic <base64(username:password)>`
+    Basic {
+        /// User name
+        username: String,
+        /// Environment variable holding the password
+        env: String,
+    },
+    /// A custom header such as `X-Api-Key`
+    Header {
+        /// Header name
+        name: String,
+        /// Environment variable holding the header value
+        env: String,
+    },
+}
+
+impl HttpAuth {
+    /// The environment variable holding the secret.
+    pub fn env(&self) -> &str {
+        match self {
+            Self::Bearer { env } | Self::Basic { env, .. } | Self::Header { env, .. } => env,
+        }
+    }
+}
+
+/// The `[context.http]` table: either a single client configured in place,
+/// or named clients such as `[context.http.github]`.
+#[derive(Debug, Clone)]
+pub(crate) enum HttpContext {
+    Single(HttpConfig),
+    Named(BTreeMap<String, HttpConfig>),
+}
+
+impl<'de> Deserialize<'de> for HttpContext {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct HttpContextVisitor;
+
+        impl<'de> Visitor<'de> for HttpContextVisitor {
+            type Value = HttpContext;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("HTTP client options or named HTTP clients")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut single = HttpConfig::default();
+                let mut has_options = false;
+                let mut named = BTreeMap::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "type" => {
+                            map.next_value::<de::IgnoredAny>()?;
+                            continue;
+                        }
+                        "base_url" => single.base_url = Some(map.next_value()?),
+                        "headers" => single.headers = map.next_value()?,
+                        "timeout" => single.timeout = Some(map.next_value()?),
+                        "user_agent" => single.user_agent = Some(map.next_value()?),
+                        "retries" => single.retries = Some(map.next_value()?),
+                        "backoff_ms" => single.backoff_ms = Some(map.next_value()?),
+                        "retry_on" => single.retry_on = map.next_value()?,
+                        "auth" => single.auth = Some(map.next_value()?),
+                        _ => {
+                            named.insert(key, map.next_value::<HttpConfig>()?);
+                            continue;
+                        }
+                    }
+                    has_options = true;
+                }
+                match (has_options, named.is_empty()) {
+                    (true, false) => Err(de::Error::custom(
+                        "[context.http] cannot mix client options with named clients; move the options into each [context.http.<name>] table",
+                    )),
+                    (_, true) => Ok(HttpContext::Single(single)),
+                    (false, false) => Ok(HttpContext::Named(named)),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(HttpContextVisitor)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Manifest;
+    use super::HttpAuth;
+    use crate::{Manifest, context::parse_context};
 
     fn parse(content: &str) -> Manifest {
         toml::from_str(content).expect("Failed to parse TOML")
@@ -53,4 +178,200 @@ mod tests {
         assert_eq!(http.timeout, Some(30));
         assert_eq!(http.user_agent, Some("my-cli/1.0".to_string()));
     }
+
+    #[test]
+    fn test_http_retries() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http]
+            retries = 3
+            backoff_ms = 200
+            retry_on = [429, 503]
+            "#,
+        );
+
+        let http = schema.context.http_config().unwrap();
+        assert_eq!(http.retries, Some(3));
+        assert_eq!(http.backoff_ms, Some(200));
+        assert_eq!(http.retry_on, [429, 503]);
+    }
+
+    #[test]
+    fn test_http_auth() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http.github]
+            auth = { type = "bearer", env = "GITHUB_TOKEN" }
+
+            [context.http.registry]
+            auth = { type = "basic", username = "ci", env = "REGISTRY_PASSWORD" }
+
+            [context.http.search]
+            auth = { type = "header", name = "X-Api-Key", env = "SEARCH_API_KEY" }
+            "#,
+        );
+
+        let auth = |name: &str| {
+            schema.context.http_clients[name]
+                .http_config()
+                .and_then(|http| http.auth.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            auth("github"),
+            HttpAuth::Bearer {
+                env: "GITHUB_TOKEN".into()
+            }
+        );
+        assert_eq!(
+            auth("registry"),
+            HttpAuth::Basic {
+                username: "ci".into(),
+                env: "REGISTRY_PASSWORD".into()
+            }
+        );
+        assert_eq!(auth("search").env(), "SEARCH_API_KEY");
+    }
+
+    #[test]
+    fn test_named_http_clients() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http.github]
+            base_url = "https://api.github.com"
+            timeout = 30
+            headers = { Accept = "application/vnd.github+json" }
+
+            [context.http.internal]
+            base_url = "http://localhost:8080"
+            "#,
+        );
+
+        assert!(schema.context.http.is_none());
+        assert_eq!(schema.context.len(), 2);
+        assert!(schema.context.has_field("github"));
+
+        let clients = schema.context.http_configs();
+        let names: Vec<_> = clients.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["github", "internal"]);
+
+        let github = clients[0].1;
+        assert_eq!(github.base_url.as_deref(), Some("https://api.github.com"));
+        assert_eq!(github.timeout, Some(30));
+        assert_eq!(github.headers["Accept"], "application/vnd.github+json");
+    }
+
+    #[test]
+    fn test_http_headers_on_single_client() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http]
+            base_url = "https://example.com"
+            headers = { Accept = "application/json" }
+            "#,
+        );
+
+        assert!(schema.context.http_clients.is_empty());
+        let http = schema.context.http_config().unwrap();
+        assert_eq!(http.headers["Accept"], "application/json");
+    }
+
+    #[test]
+    fn test_http_cannot_mix_options_and_named_clients() {
+        let result: Result<Manifest, _> = toml::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.http]
+            timeout = 30
+
+            [context.http.github]
+            base_url = "https://api.github.com"
+            "#,
+        );
+        let err = result.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("[context.http] cannot mix client options with named clients")
+        );
+    }
+
+    #[test]
+    fn test_http_validation() {
+        assert!(parse_context("http.github", "base_url = \"https://api.github.com\"").is_ok());
+
+        let err = parse_context("http.github", "base_url = \"api.github.com\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "base_url 'api.github.com' in [context.http.github] must start with http:// or https://"
+        ));
+
+        let err = parse_context("http.storage", "base_url = \"https://example.com\"").unwrap_err();
+        assert!(err.to_string().contains(
+            "HTTP client 'storage' conflicts with the built-in context field of the same name"
+        ));
+
+        let err = parse_context("http.match", "").unwrap_err();
+        assert!(err.to_string().contains("'match'"));
+
+        let err = parse_context("http", "headers = { \"X Token\" = \"abc\" }").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'X Token' in [context.http] is not a valid header name")
+        );
+
+        let err = parse_context("http", "retry_on = [503]").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("retry_on in [context.http] has no effect without retries")
+        );
+
+        let err = parse_context("http", "retries = 2\nretry_on = [42]").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("retry_on in [context.http] contains 42, which is not an HTTP status")
+        );
+
+        let err = parse_context("http", "headers = { Accept = \"caf\u{e9}\" }").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("value of header 'Accept' in [context.http] must be printable ASCII")
+        );
+
+        let err = parse_context(
+            "http",
+            "auth = { type = \"header\", name = \"X Key\", env = \"API_KEY\" }",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'X Key' in [context.http.auth] is not a valid header name")
+        );
+
+        let err = parse_context(
+            "http",
+            "headers = { authorization = \"x\" }\nauth = { type = \"bearer\", env = \"API_TOKEN\" }",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "header 'authorization' in [context.http] is already set by auth; remove it from headers"
+        ));
+    }
 }
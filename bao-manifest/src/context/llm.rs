@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for an LLM API client
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct LlmConfig {
+    /// API provider
+    pub provider: LlmProvider,
+
+    /// Model requests are sent to, e.g. "gpt-4o" or "claude-sonnet-4-5"
+    pub model: String,
+
+    /// Environment variable holding the API key (default: OPENAI_API_KEY or ANTHROPIC_API_KEY)
+    pub api_key_env: Option<String>,
+
+    /// Base URL of the API, for proxies and compatible servers (default: the provider's)
+    pub base_url: Option<String>,
+
+    /// Maximum number of tokens in a reply (default: 1024 for anthropic, unset for openai)
+    pub max_tokens: Option<u32>,
+}
+
+impl LlmConfig {
+    /// The API key variable, or the provider's default one.
+    pub fn resolved_api_key_env(&self) -> &str {
+        self.api_key_env
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_api_key_env())
+    }
+
+    /// The base URL, or the provider's default one.
+    pub fn resolved_base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_base_url())
+    }
+}
+
+/// LLM API provider
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    /// OpenAI Chat Completions API
+    Openai,
+    /// Anthropic Messages API
+    Anthropic,
+}
+
+impl LlmProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LlmProvider::Openai => "openai",
+            LlmProvider::Anthropic => "anthropic",
+        }
+    }
+
+    /// The variable the provider's SDKs read the API key from.
+    pub fn default_api_key_env(&self) -> &'static str {
+        match self {
+            LlmProvider::Openai => "OPENAI_API_KEY",
+            LlmProvider::Anthropic => "ANTHROPIC_API_KEY",
+        }
+    }
+
+    /// The base URL the provider's SDKs default to.
+    pub fn default_base_url(&self) -> &'static str {
+        match self {
+            LlmProvider::Openai => "https://api.openai.com/v1",
+            LlmProvider::Anthropic => "https://api.anthropic.com",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_llm_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.llm]
+            provider = "anthropic"
+            model = "claude-sonnet-4-5"
+            max_tokens = 4096
+            "#,
+        );
+
+        let llm = schema.context.llm_config().unwrap();
+        assert_eq!(llm.provider, LlmProvider::Anthropic);
+        assert_eq!(llm.model, "claude-sonnet-4-5");
+        assert_eq!(llm.max_tokens, Some(4096));
+        assert_eq!(llm.resolved_api_key_env(), "ANTHROPIC_API_KEY");
+        assert_eq!(llm.resolved_base_url(), "https://api.anthropic.com");
+    }
+
+    #[test]
+    fn test_llm_validation() {
+        assert!(parse_context("llm", "provider = \"openai\"\nmodel = \"gpt-4o\"\nbase_url = \"http://localhost:11434/v1\"").is_ok());
+
+        let err = parse_context("llm", "provider = \"openai\"\nmodel = \"\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("model in [context.llm] cannot be empty")
+        );
+
+        let err = parse_context(
+            "llm",
+            "provider = \"openai\"\nmodel = \"gpt-4o\"\nbase_url = \"localhost\"",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "base_url 'localhost' in [context.llm] must start with http:// or https://"
+            )
+        );
+
+        assert!(
+            parse_context(
+                "llm",
+                "provider = \"openai\"\nmodel = \"gpt-4o\"\nmax_tokens = 0"
+            )
+            .is_err()
+        );
+    }
+}
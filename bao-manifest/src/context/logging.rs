@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the application logger
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct LoggingConfig {
+    /// Minimum level that is emitted (default: info)
+    #[serde(default)]
+    pub level: LogLevel,
+
+    /// Output format (default: pretty)
+    #[serde(default)]
+    pub format: LogFormat,
+
+    /// Default filter in `RUST_LOG` syntax, e.g. "info,myapp::db=debug"
+    /// (default: the level)
+    pub env_filter: Option<String>,
+}
+
+impl LoggingConfig {
+    /// The first `env_filter` directive that is neither a level nor
+    /// `target=level`.
+    pub fn invalid_directive(&self) -> Option<&str> {
+        self.env_filter
+            .as_deref()?
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .find(|directive| {
+                let (target, level) = match directive.split_once('=') {
+                    Some((target, level)) => (Some(target.trim()), level.trim()),
+                    None => (None, *directive),
+                };
+                target.is_some_and(str::is_empty)
+                    || !["trace", "debug", "info", "warn", "error"]
+                        .contains(&level.to_ascii_lowercase().as_str())
+            })
+    }
+}
+
+/// Log level
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+/// Log output format
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::Manifest;
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_logging_defaults() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [context.logging]
+            "#,
+        );
+
+        let logging = schema.context.logging_config().unwrap();
+        assert_eq!(logging.level, LogLevel::Info);
+        assert_eq!(logging.format, LogFormat::Pretty);
+    }
+
+    #[test]
+    fn test_logging_with_options() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "typescript"
+
+            [context.logging]
+            level = "debug"
+            format = "json"
+            "#,
+        );
+
+        let logging = schema.context.logging_config().unwrap();
+        assert_eq!(logging.level, LogLevel::Debug);
+        assert_eq!(logging.format, LogFormat::Json);
+        assert_eq!(logging.env_filter, None);
+    }
+
+    #[test]
+    fn test_logging_env_filter() {
+        let logging = |env_filter: &str| LoggingConfig {
+            env_filter: Some(env_filter.to_string()),
+            ..LoggingConfig::default()
+        };
+        assert_eq!(logging("info,myapp::db=debug").invalid_directive(), None);
+        assert_eq!(logging("WARN, sqlx=error,").invalid_directive(), None);
+        assert_eq!(
+            logging("info,sqlx=loud").invalid_directive(),
+            Some("sqlx=loud")
+        );
+        assert_eq!(logging("=debug").invalid_directive(), Some("=debug"));
+        assert_eq!(logging("verbose").invalid_directive(), Some("verbose"));
+
+        let err = Manifest::from_str(
+            "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n[context.logging]\nenv_filter = \"info,sqlx=loud\"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "invalid directive 'sqlx=loud' in env_filter of [context.logging]; expected a level or target=level"
+        ));
+    }
+}
@@ -1,14 +1,40 @@
+mod cache;
+mod config;
 mod database;
+mod email;
+mod grpc;
 mod http;
+mod llm;
+mod logging;
+mod queue;
+mod secrets;
+mod storage;
+mod workdir;
 
+use std::collections::BTreeMap;
+
+pub use cache::{CacheConfig, CacheMode};
+pub use config::{AppConfig, ConfigField, ConfigFieldType, ConfigFormat};
 pub use database::{
-    DatabaseConfig, PoolConfig,
+    DatabaseConfig, PoolConfig, ReplicaConfig,
+    duckdb::DuckdbConfig,
     mysql::MySqlConfig,
     postgres::PostgresConfig,
     sqlite::{JournalMode, SqliteConfig, SynchronousMode},
 };
-pub use http::HttpConfig;
+pub use email::{EmailConfig, SmtpTls};
+pub use grpc::GrpcConfig;
+use http::HttpContext;
+pub use http::{HttpAuth, HttpConfig};
+pub use llm::{LlmConfig, LlmProvider};
+pub use logging::{LogFormat, LogLevel, LoggingConfig};
+use queue::QueueContextField;
+pub use queue::{KafkaConfig, NatsConfig};
+pub use secrets::{SecretsBackend, SecretsConfig};
 use serde::Deserialize;
+pub use storage::S3Config;
+use storage::StorageContextField;
+pub use workdir::WorkdirConfig;
 
 /// A context field declaration
 #[derive(Debug, Clone)]
@@ -19,8 +45,32 @@ pub enum ContextField {
     Mysql(MySqlConfig),
     /// SQLite database pool
     Sqlite(SqliteConfig),
-    /// HTTP client (only via [context.http])
+    /// DuckDB database pool
+    Duckdb(DuckdbConfig),
+    /// HTTP client (only via [context.http] or [context.http.<name>])
     Http(HttpConfig),
+    /// Application logger (only via [context.logging])
+    Logging(LoggingConfig),
+    /// S3-compatible object storage (only via [context.storage])
+    S3(S3Config),
+    /// gRPC client channel (only via [context.grpc])
+    Grpc(GrpcConfig),
+    /// NATS connection (only via [context.queue])
+    Nats(NatsConfig),
+    /// Kafka producer (only via [context.queue])
+    Kafka(KafkaConfig),
+    /// SMTP transport (only via [context.email])
+    Email(EmailConfig),
+    /// Typed config file (only via [context.config])
+    Config(AppConfig),
+    /// Secrets accessor (only via [context.secrets])
+    Secrets(SecretsConfig),
+    /// LLM API client (only via [context.llm])
+    Llm(LlmConfig),
+    /// In-memory cache (only via [context.cache])
+    Cache(CacheConfig),
+    /// Data and scratch directories (only via [context.workdir])
+    Workdir(WorkdirConfig),
 }
 
 /// Database context types (used for tagged deserialization)
@@ -30,6 +80,7 @@ pub(crate) enum DatabaseContextField {
     Postgres(PostgresConfig),
     Mysql(MySqlConfig),
     Sqlite(SqliteConfig),
+    Duckdb(DuckdbConfig),
 }
 
 impl From<DatabaseContextField> for ContextField {
@@ -38,6 +89,24 @@ impl From<DatabaseContextField> for ContextField {
             DatabaseContextField::Postgres(c) => ContextField::Postgres(c),
             DatabaseContextField::Mysql(c) => ContextField::Mysql(c),
             DatabaseContextField::Sqlite(c) => ContextField::Sqlite(c),
+            DatabaseContextField::Duckdb(c) => ContextField::Duckdb(c),
+        }
+    }
+}
+
+impl From<StorageContextField> for ContextField {
+    fn from(storage: StorageContextField) -> Self {
+        match storage {
+            StorageContextField::S3(c) => ContextField::S3(c),
+        }
+    }
+}
+
+impl From<QueueContextField> for ContextField {
+    fn from(queue: QueueContextField) -> Self {
+        match queue {
+            QueueContextField::Nats(c) => ContextField::Nats(c),
+            QueueContextField::Kafka(c) => ContextField::Kafka(c),
         }
     }
 }
@@ -45,53 +114,154 @@ impl From<DatabaseContextField> for ContextField {
 impl ContextField {
     /// Get the database configuration if this is a database type.
     ///
-    /// Returns `Some(&dyn DatabaseConfig)` for Postgres, MySQL, and SQLite,
-    /// or `None` for HTTP.
+    /// Returns `Some(&dyn DatabaseConfig)` for Postgres, MySQL, SQLite and DuckDB,
+    /// or `None` for HTTP, logging, storage, gRPC, queues, email, config, secrets, LLM clients,
+    /// caches and working directories.
     pub fn as_database(&self) -> Option<&dyn DatabaseConfig> {
         match self {
             ContextField::Postgres(c) => Some(c),
             ContextField::Mysql(c) => Some(c),
             ContextField::Sqlite(c) => Some(c),
-            ContextField::Http(_) => None,
+            ContextField::Duckdb(c) => Some(c),
+            ContextField::Http(_)
+            | ContextField::Logging(_)
+            | ContextField::S3(_)
+            | ContextField::Grpc(_)
+            | ContextField::Nats(_)
+            | ContextField::Kafka(_)
+            | ContextField::Email(_)
+            | ContextField::Config(_)
+            | ContextField::Secrets(_)
+            | ContextField::Llm(_)
+            | ContextField::Cache(_)
+            | ContextField::Workdir(_) => None,
         }
     }
 
     /// Get a human-readable type name for display purposes.
     ///
-    /// Returns names like "postgres", "mysql", "sqlite", "http".
+    /// Returns names like "postgres", "mysql", "sqlite", "duckdb", "http", "logging", "s3", "grpc",
+    /// "nats", "kafka", "email", "config", "secrets", "llm", "cache", "workdir".
     pub fn type_name(&self) -> &'static str {
         match self {
             ContextField::Postgres(_) => "postgres",
             ContextField::Mysql(_) => "mysql",
             ContextField::Sqlite(_) => "sqlite",
+            ContextField::Duckdb(_) => "duckdb",
             ContextField::Http(_) => "http",
+            ContextField::Logging(_) => "logging",
+            ContextField::S3(_) => "s3",
+            ContextField::Grpc(_) => "grpc",
+            ContextField::Nats(_) => "nats",
+            ContextField::Kafka(_) => "kafka",
+            ContextField::Email(_) => "email",
+            ContextField::Config(_) => "config",
+            ContextField::Secrets(_) => "secrets",
+            ContextField::Llm(_) => "llm",
+            ContextField::Cache(_) => "cache",
+            ContextField::Workdir(_) => "workdir",
         }
     }
 
     /// Get the environment variable for this field
     pub fn env(&self) -> Option<&str> {
-        self.as_database().and_then(|db| db.env())
+        match self {
+            ContextField::Nats(c) => c.env.as_deref(),
+            ContextField::Kafka(c) => c.env.as_deref(),
+            _ => self.as_database().and_then(|db| db.env()),
+        }
     }
 
     /// Get the default environment variable name
     pub fn default_env(&self) -> &'static str {
-        match self.as_database() {
-            Some(db) => db.default_env(),
-            None => "",
+        match self {
+            ContextField::Nats(_) => "NATS_URL",
+            ContextField::Kafka(_) => "KAFKA_BROKERS",
+            _ => match self.as_database() {
+                Some(db) => db.default_env(),
+                None => "",
+            },
         }
     }
 
     /// Get the cargo dependencies needed for this type
     pub fn dependencies(&self) -> Vec<(&'static str, &'static str)> {
-        match self.as_database() {
-            Some(db) => db.dependencies(),
-            None => vec![("reqwest", r#"{ version = "0.12", features = ["json"] }"#)],
+        match self {
+            ContextField::Http(_) => {
+                vec![("reqwest", r#"{ version = "0.12", features = ["json"] }"#)]
+            }
+            ContextField::Logging(_) => vec![
+                ("tracing", r#""0.1""#),
+                (
+                    "tracing-subscriber",
+                    r#"{ version = "0.3", features = ["env-filter", "json"] }"#,
+                ),
+            ],
+            ContextField::S3(_) => {
+                vec![(
+                    "object_store",
+                    r#"{ version = "0.12", features = ["aws"] }"#,
+                )]
+            }
+            ContextField::Grpc(_) => vec![("tonic", r#""0.12""#), ("prost", r#""0.13""#)],
+            ContextField::Nats(_) => vec![("async-nats", r#""0.42""#)],
+            ContextField::Kafka(_) => vec![("rdkafka", r#""0.36""#)],
+            ContextField::Email(_) => vec![(
+                "lettre",
+                r#"{ version = "0.11", features = ["tokio1", "tokio1-native-tls"] }"#,
+            )],
+            ContextField::Config(c) => vec![
+                ("serde", r#"{ version = "1", features = ["derive"] }"#),
+                (
+                    "figment",
+                    match c.resolved_format() {
+                        Some(ConfigFormat::Json) => r#"{ version = "0.10", features = ["json"] }"#,
+                        Some(ConfigFormat::Yaml) => r#"{ version = "0.10", features = ["yaml"] }"#,
+                        _ => r#"{ version = "0.10", features = ["toml"] }"#,
+                    },
+                ),
+            ],
+            ContextField::Secrets(c) if c.backend == SecretsBackend::Keyring => vec![(
+                "keyring",
+                r#"{ version = "3", features = ["apple-native", "windows-native", "sync-secret-service", "crypto-rust", "vendored"] }"#,
+            )],
+            ContextField::Secrets(_) => vec![],
+            ContextField::Llm(_) => vec![("reqwest", r#""0.12""#), ("serde_json", r#""1""#)],
+            ContextField::Cache(c) if c.mode == CacheMode::Disk && c.path.is_none() => vec![
+                ("moka", r#"{ version = "0.12", features = ["future"] }"#),
+                ("dirs", r#""6""#),
+            ],
+            ContextField::Cache(_) => {
+                vec![("moka", r#"{ version = "0.12", features = ["future"] }"#)]
+            }
+            ContextField::Workdir(c) => {
+                let mut deps = Vec::new();
+                if c.has_data() && c.path.is_none() {
+                    deps.push(("dirs", r#""6""#));
+                }
+                if c.temp {
+                    deps.push(("tempfile", r#""3""#));
+                }
+                deps
+            }
+            _ => self
+                .as_database()
+                .map(|db| db.dependencies())
+                .unwrap_or_default(),
         }
     }
 
     /// Returns true if this type requires async initialization
     pub fn is_async(&self) -> bool {
         self.as_database().is_some()
+            || matches!(
+                self,
+                ContextField::Nats(_)
+                    | ContextField::Kafka(_)
+                    | ContextField::Email(_)
+                    | ContextField::Llm(_)
+                    | ContextField::Cache(_)
+            )
     }
 
     /// Returns true if this is a database type
@@ -104,6 +274,15 @@ impl ContextField {
         self.as_database().map(|db| db.pool())
     }
 
+    /// Get the read-only replica of a PostgreSQL or MySQL database
+    pub fn replica_config(&self) -> Option<&ReplicaConfig> {
+        match self {
+            ContextField::Postgres(c) => c.replica(),
+            ContextField::Mysql(c) => c.replica(),
+            _ => None,
+        }
+    }
+
     /// Get SQLite-specific configuration
     pub fn sqlite_config(&self) -> Option<&SqliteConfig> {
         match self {
@@ -112,6 +291,14 @@ impl ContextField {
         }
     }
 
+    /// Get DuckDB-specific configuration
+    pub fn duckdb_config(&self) -> Option<&DuckdbConfig> {
+        match self {
+            ContextField::Duckdb(c) => Some(c),
+            _ => None,
+        }
+    }
+
     /// Get HTTP-specific configuration
     pub fn http_config(&self) -> Option<&HttpConfig> {
         match self {
@@ -119,22 +306,130 @@ impl ContextField {
             _ => None,
         }
     }
+
+    /// Get logging-specific configuration
+    pub fn logging_config(&self) -> Option<&LoggingConfig> {
+        match self {
+            ContextField::Logging(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get S3-specific configuration
+    pub fn s3_config(&self) -> Option<&S3Config> {
+        match self {
+            ContextField::S3(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get gRPC-specific configuration
+    pub fn grpc_config(&self) -> Option<&GrpcConfig> {
+        match self {
+            ContextField::Grpc(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get SMTP-specific configuration
+    pub fn email_config(&self) -> Option<&EmailConfig> {
+        match self {
+            ContextField::Email(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get config file-specific configuration
+    pub fn app_config(&self) -> Option<&AppConfig> {
+        match self {
+            ContextField::Config(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get secrets-specific configuration
+    pub fn secrets_config(&self) -> Option<&SecretsConfig> {
+        match self {
+            ContextField::Secrets(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get LLM client-specific configuration
+    pub fn llm_config(&self) -> Option<&LlmConfig> {
+        match self {
+            ContextField::Llm(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get cache-specific configuration
+    pub fn cache_config(&self) -> Option<&CacheConfig> {
+        match self {
+            ContextField::Cache(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get working directory-specific configuration
+    pub fn workdir_config(&self) -> Option<&WorkdirConfig> {
+        match self {
+            ContextField::Workdir(c) => Some(c),
+            _ => None,
+        }
+    }
 }
 
 /// Application context configuration
-/// Only allows [context.database] and [context.http]
+/// Only allows [context.database], [context.http], [context.logging], [context.storage],
+/// [context.grpc], [context.queue], [context.email], [context.config], [context.secrets],
+/// [context.llm], [context.cache] and [context.workdir]
 #[derive(Debug, Clone, Default)]
 pub struct Context {
-    /// Database connection pool (postgres, mysql, or sqlite)
+    /// Database connection pool (postgres, mysql, sqlite or duckdb)
     pub database: Option<ContextField>,
     /// HTTP client (stored as ContextField for uniform iteration)
     pub http: Option<ContextField>,
+    /// Named HTTP clients from [context.http.<name>], keyed by field name
+    pub http_clients: BTreeMap<String, ContextField>,
+    /// Application logger (stored as ContextField for uniform iteration)
+    pub logging: Option<ContextField>,
+    /// Object storage bucket (s3)
+    pub storage: Option<ContextField>,
+    /// gRPC client channel
+    pub grpc: Option<ContextField>,
+    /// Message queue (nats or kafka)
+    pub queue: Option<ContextField>,
+    /// SMTP transport
+    pub email: Option<ContextField>,
+    /// Typed config file
+    pub config: Option<ContextField>,
+    /// Secrets accessor
+    pub secrets: Option<ContextField>,
+    /// LLM API client
+    pub llm: Option<ContextField>,
+    /// In-memory cache
+    pub cache: Option<ContextField>,
+    /// Data and scratch directories
+    pub workdir: Option<ContextField>,
 }
 
 impl Context {
     /// Returns true if no context is configured
     pub fn is_empty(&self) -> bool {
-        self.database.is_none() && self.http.is_none()
+        self.database.is_none()
+            && self.http.is_none()
+            && self.http_clients.is_empty()
+            && self.logging.is_none()
+            && self.storage.is_none()
+            && self.grpc.is_none()
+            && self.queue.is_none()
+            && self.email.is_none()
+            && self.config.is_none()
+            && self.secrets.is_none()
+            && self.llm.is_none()
+            && self.cache.is_none()
+            && self.workdir.is_none()
     }
 
     /// Returns the number of configured context fields
@@ -146,12 +441,48 @@ impl Context {
         if self.http.is_some() {
             count += 1;
         }
+        count += self.http_clients.len();
+        if self.logging.is_some() {
+            count += 1;
+        }
+        if self.storage.is_some() {
+            count += 1;
+        }
+        if self.grpc.is_some() {
+            count += 1;
+        }
+        if self.queue.is_some() {
+            count += 1;
+        }
+        if self.email.is_some() {
+            count += 1;
+        }
+        if self.config.is_some() {
+            count += 1;
+        }
+        if self.secrets.is_some() {
+            count += 1;
+        }
+        if self.llm.is_some() {
+            count += 1;
+        }
+        if self.cache.is_some() {
+            count += 1;
+        }
+        if self.workdir.is_some() {
+            count += 1;
+        }
         count
     }
 
-    /// Returns true if any async context is configured (database)
+    /// Returns true if any async context is configured (database, queue, email, LLM client or
+    /// cache)
     pub fn has_async(&self) -> bool {
         self.database.is_some()
+            || self.queue.is_some()
+            || self.email.is_some()
+            || self.llm.is_some()
+            || self.cache.is_some()
     }
 
     /// Check if a context field exists by name
@@ -159,12 +490,22 @@ impl Context {
         match name {
             "database" => self.database.is_some(),
             "http" => self.http.is_some(),
-            _ => false,
+            "logging" => self.logging.is_some(),
+            "storage" => self.storage.is_some(),
+            "grpc" => self.grpc.is_some(),
+            "queue" => self.queue.is_some(),
+            "email" => self.email.is_some(),
+            "config" => self.config.is_some(),
+            "secrets" => self.secrets.is_some(),
+            "llm" => self.llm.is_some(),
+            "cache" => self.cache.is_some(),
+            "workdir" => self.workdir.is_some(),
+            name => self.http_clients.contains_key(name),
         }
     }
 
     /// Get all context fields as a vector of (name, field) pairs
-    pub fn fields(&self) -> Vec<(&'static str, &ContextField)> {
+    pub fn fields(&self) -> Vec<(&str, &ContextField)> {
         let mut fields = Vec::new();
         if let Some(db) = &self.database {
             fields.push(("database", db));
@@ -172,6 +513,39 @@ impl Context {
         if let Some(http) = &self.http {
             fields.push(("http", http));
         }
+        for (name, client) in &self.http_clients {
+            fields.push((name.as_str(), client));
+        }
+        if let Some(logging) = &self.logging {
+            fields.push(("logging", logging));
+        }
+        if let Some(storage) = &self.storage {
+            fields.push(("storage", storage));
+        }
+        if let Some(grpc) = &self.grpc {
+            fields.push(("grpc", grpc));
+        }
+        if let Some(queue) = &self.queue {
+            fields.push(("queue", queue));
+        }
+        if let Some(email) = &self.email {
+            fields.push(("email", email));
+        }
+        if let Some(config) = &self.config {
+            fields.push(("config", config));
+        }
+        if let Some(secrets) = &self.secrets {
+            fields.push(("secrets", secrets));
+        }
+        if let Some(llm) = &self.llm {
+            fields.push(("llm", llm));
+        }
+        if let Some(cache) = &self.cache {
+            fields.push(("cache", cache));
+        }
+        if let Some(workdir) = &self.workdir {
+            fields.push(("workdir", workdir));
+        }
         fields
     }
 
@@ -179,39 +553,126 @@ impl Context {
     pub fn http_config(&self) -> Option<&HttpConfig> {
         self.http.as_ref().and_then(|f| f.http_config())
     }
+
+    /// Get every HTTP client as (field name, config), the unnamed one as "http"
+    pub fn http_configs(&self) -> Vec<(&str, &HttpConfig)> {
+        let unnamed = self.http_config().map(|config| ("http", config));
+        let named = self
+            .http_clients
+            .iter()
+            .filter_map(|(name, field)| field.http_config().map(|config| (name.as_str(), config)));
+        unnamed.into_iter().chain(named).collect()
+    }
+
+    /// Get the logging configuration if present
+    pub fn logging_config(&self) -> Option<&LoggingConfig> {
+        self.logging.as_ref().and_then(|f| f.logging_config())
+    }
+
+    /// Get the S3 storage configuration if present
+    pub fn s3_config(&self) -> Option<&S3Config> {
+        self.storage.as_ref().and_then(|f| f.s3_config())
+    }
+
+    /// Get the gRPC configuration if present
+    pub fn grpc_config(&self) -> Option<&GrpcConfig> {
+        self.grpc.as_ref().and_then(|f| f.grpc_config())
+    }
+
+    /// Get the SMTP configuration if present
+    pub fn email_config(&self) -> Option<&EmailConfig> {
+        self.email.as_ref().and_then(|f| f.email_config())
+    }
+
+    /// Get the config file configuration if present
+    pub fn app_config(&self) -> Option<&AppConfig> {
+        self.config.as_ref().and_then(|f| f.app_config())
+    }
+
+    /// Get the secrets configuration if present
+    pub fn secrets_config(&self) -> Option<&SecretsConfig> {
+        self.secrets.as_ref().and_then(|f| f.secrets_config())
+    }
+
+    /// Get the LLM client configuration if present
+    pub fn llm_config(&self) -> Option<&LlmConfig> {
+        self.llm.as_ref().and_then(|f| f.llm_config())
+    }
+
+    /// Get the cache configuration if present
+    pub fn cache_config(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref().and_then(|f| f.cache_config())
+    }
+
+    /// Get the working directory configuration if present
+    pub fn workdir_config(&self) -> Option<&WorkdirConfig> {
+        self.workdir.as_ref().and_then(|f| f.workdir_config())
+    }
 }
 
-/// Custom deserializer for Context that handles database and http fields
+/// Custom deserializer for Context that handles database, http, logging, storage, grpc,
+/// queue, email, config, secrets, llm, cache and workdir fields
+///
+/// Each field is deserialized straight into its config type, so errors keep
+/// the location of the offending value.
 pub(crate) fn deserialize<'de, D>(deserializer: D) -> std::result::Result<Context, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    use serde::de::Error;
-
     #[derive(Deserialize)]
     struct RawContext {
-        database: Option<toml::Value>,
-        http: Option<toml::Value>,
+        database: Option<DatabaseContextField>,
+        http: Option<HttpContext>,
+        logging: Option<LoggingConfig>,
+        storage: Option<StorageContextField>,
+        grpc: Option<GrpcConfig>,
+        queue: Option<QueueContextField>,
+        email: Option<EmailConfig>,
+        config: Option<AppConfig>,
+        secrets: Option<SecretsConfig>,
+        llm: Option<LlmConfig>,
+        cache: Option<CacheConfig>,
+        workdir: Option<WorkdirConfig>,
     }
 
-    let raw: RawContext = RawContext::deserialize(deserializer)?;
-    let mut ctx = Context::default();
-
-    if let Some(db_value) = raw.database {
-        let db: DatabaseContextField = db_value
-            .try_into()
-            .map_err(|e: toml::de::Error| D::Error::custom(e.message()))?;
-        ctx.database = Some(db.into());
-    }
+    let raw = RawContext::deserialize(deserializer)?;
+    let (http, http_clients) = match raw.http {
+        Some(HttpContext::Single(config)) => (Some(ContextField::Http(config)), BTreeMap::new()),
+        Some(HttpContext::Named(clients)) => (
+            None,
+            clients
+                .into_iter()
+                .map(|(name, config)| (name, ContextField::Http(config)))
+                .collect(),
+        ),
+        None => (None, BTreeMap::new()),
+    };
+    Ok(Context {
+        database: raw.database.map(Into::into),
+        http,
+        http_clients,
+        logging: raw.logging.map(ContextField::Logging),
+        storage: raw.storage.map(Into::into),
+        grpc: raw.grpc.map(ContextField::Grpc),
+        queue: raw.queue.map(Into::into),
+        email: raw.email.map(ContextField::Email),
+        config: raw.config.map(ContextField::Config),
+        secrets: raw.secrets.map(ContextField::Secrets),
+        llm: raw.llm.map(ContextField::Llm),
+        cache: raw.cache.map(ContextField::Cache),
+        workdir: raw.workdir.map(ContextField::Workdir),
+    })
+}
 
-    if let Some(http_value) = raw.http {
-        let http: HttpConfig = http_value
-            .try_into()
-            .map_err(|e: toml::de::Error| D::Error::custom(e.message()))?;
-        ctx.http = Some(ContextField::Http(http));
-    }
+/// Parse and validate a test manifest whose `[context.<section>]` table has `body`.
+#[cfg(test)]
+pub(crate) fn parse_context(section: &str, body: &str) -> crate::Result<crate::Manifest> {
+    use std::str::FromStr;
 
-    Ok(ctx)
+    crate::Manifest::from_str(&format!(
+        "[cli]\nname = \"test\"\nlanguage = \"rust\"\n\n[context.{}]\n{}",
+        section, body
+    ))
 }
 
 #[cfg(test)]
@@ -0,0 +1,88 @@
+use serde::Deserialize;
+
+/// Configuration for a NATS connection
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct NatsConfig {
+    /// Environment variable holding the server URL (default: NATS_URL)
+    pub env: Option<String>,
+}
+
+/// Configuration for a Kafka producer
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct KafkaConfig {
+    /// Environment variable holding the comma-separated broker list
+    /// (default: KAFKA_BROKERS)
+    pub env: Option<String>,
+}
+
+/// Message queue context types (used for tagged deserialization)
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum QueueContextField {
+    Nats(NatsConfig),
+    Kafka(KafkaConfig),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ContextField, Manifest};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_nats_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.queue]
+            type = "nats"
+            "#,
+        );
+
+        let queue = schema.context.queue.as_ref().unwrap();
+        assert!(matches!(queue, ContextField::Nats(_)));
+        assert_eq!(queue.type_name(), "nats");
+        assert_eq!(queue.env(), None);
+        assert_eq!(queue.default_env(), "NATS_URL");
+    }
+
+    #[test]
+    fn test_kafka_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.queue]
+            type = "kafka"
+            env = "BROKERS"
+            "#,
+        );
+
+        let queue = schema.context.queue.as_ref().unwrap();
+        assert!(matches!(queue, ContextField::Kafka(_)));
+        assert_eq!(queue.env(), Some("BROKERS"));
+        assert_eq!(queue.default_env(), "KAFKA_BROKERS");
+    }
+
+    #[test]
+    fn test_queue_requires_type() {
+        let result: Result<Manifest, _> = toml::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.queue]
+            env = "NATS_URL"
+            "#,
+        );
+        assert!(result.is_err());
+    }
+}
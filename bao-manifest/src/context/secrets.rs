@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the secrets accessor
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct SecretsConfig {
+    /// Where secrets are read from (default: keyring)
+    #[serde(default)]
+    pub backend: SecretsBackend,
+
+    /// Keyring service name (default: the CLI name)
+    pub service: Option<String>,
+
+    /// Path of the secrets file, relative to the working directory (default: ".secrets")
+    pub path: Option<String>,
+
+    /// Prefix of the environment variables, e.g. "MYAPP_" (default: none)
+    pub prefix: Option<String>,
+}
+
+impl SecretsConfig {
+    /// The secrets file path, or the default one.
+    pub fn resolved_path(&self) -> &str {
+        self.path.as_deref().unwrap_or(".secrets")
+    }
+}
+
+/// Secrets backend
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretsBackend {
+    /// The OS keyring, falling back to environment variables
+    #[default]
+    Keyring,
+    /// Environment variables
+    Env,
+    /// A file of `NAME=value` lines
+    File,
+}
+
+impl SecretsBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretsBackend::Keyring => "keyring",
+            SecretsBackend::Env => "env",
+            SecretsBackend::File => "file",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_secrets_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.secrets]
+            backend = "keyring"
+            service = "acme"
+            prefix = "ACME_"
+            "#,
+        );
+
+        let secrets = schema.context.secrets_config().unwrap();
+        assert_eq!(secrets.backend, SecretsBackend::Keyring);
+        assert_eq!(secrets.service.as_deref(), Some("acme"));
+        assert_eq!(secrets.prefix.as_deref(), Some("ACME_"));
+        assert_eq!(secrets.resolved_path(), ".secrets");
+    }
+
+    #[test]
+    fn test_secrets_validation() {
+        assert!(parse_context("secrets", "backend = \"file\"\npath = \"creds.env\"").is_ok());
+
+        let err = parse_context("secrets", "backend = \"env\"\npath = \"creds.env\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("path in [context.secrets] only applies to backend = \"file\"")
+        );
+
+        let err = parse_context("secrets", "backend = \"file\"\nservice = \"acme\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("service in [context.secrets] only applies to backend = \"keyring\"")
+        );
+
+        assert_eq!(
+            parse_context("secrets", "")
+                .unwrap()
+                .context
+                .secrets_config()
+                .unwrap()
+                .backend,
+            SecretsBackend::Keyring
+        );
+        assert!(parse_context("secrets", "backend = \"vault\"").is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::Deserialize;
+
+/// Configuration for an S3-compatible object storage bucket
+///
+/// Credentials are not part of the manifest; generated code reads them from
+/// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct S3Config {
+    /// Bucket name
+    pub bucket: String,
+
+    /// Region (default: `AWS_REGION`)
+    pub region: Option<String>,
+
+    /// Endpoint URL of an S3-compatible service such as MinIO or R2
+    pub endpoint: Option<String>,
+}
+
+/// Object storage context types (used for tagged deserialization)
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum StorageContextField {
+    S3(S3Config),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_s3_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.storage]
+            type = "s3"
+            bucket = "uploads"
+            "#,
+        );
+
+        let s3 = schema.context.s3_config().unwrap();
+        assert_eq!(s3.bucket, "uploads");
+        assert_eq!(s3.region, None);
+        assert_eq!(s3.endpoint, None);
+    }
+
+    #[test]
+    fn test_s3_with_options() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.storage]
+            type = "s3"
+            bucket = "uploads"
+            region = "eu-west-1"
+            endpoint = "http://localhost:9000"
+            "#,
+        );
+
+        let s3 = schema.context.s3_config().unwrap();
+        assert_eq!(s3.region.as_deref(), Some("eu-west-1"));
+        assert_eq!(s3.endpoint.as_deref(), Some("http://localhost:9000"));
+    }
+
+    #[test]
+    fn test_s3_validation() {
+        assert!(parse_context("storage", "type = \"s3\"\nbucket = \"uploads\"").is_ok());
+
+        let err = parse_context("storage", "type = \"s3\"\nbucket = \"\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("bucket in [context.storage] cannot be empty")
+        );
+
+        let err = parse_context(
+            "storage",
+            "type = \"s3\"\nbucket = \"uploads\"\nendpoint = \"localhost:9000\"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(
+            "endpoint 'localhost:9000' in [context.storage] must start with http:// or https://"
+        ));
+    }
+
+    #[test]
+    fn test_s3_requires_bucket() {
+        let result: Result<Manifest, _> = toml::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.storage]
+            type = "s3"
+            "#,
+        );
+        assert!(result.is_err());
+    }
+}
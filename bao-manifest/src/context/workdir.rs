@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+/// Configuration for the app's working directories
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct WorkdirConfig {
+    /// Keep a data directory that outlives the invocation (default: true)
+    pub data: Option<bool>,
+
+    /// Create a scratch directory, removed when the invocation ends (default: false)
+    #[serde(default)]
+    pub temp: bool,
+
+    /// Data directory (default: the CLI name in the user data directory)
+    pub path: Option<String>,
+}
+
+impl WorkdirConfig {
+    /// Whether the data directory is kept, which it is unless turned off.
+    pub fn has_data(&self) -> bool {
+        self.data.unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Manifest, context::parse_context};
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_workdir_config() {
+        let schema = parse(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [context.workdir]
+            temp = true
+            "#,
+        );
+
+        let workdir = schema.context.workdir_config().unwrap();
+        assert!(workdir.has_data());
+        assert!(workdir.temp);
+        assert_eq!(workdir.path, None);
+    }
+
+    #[test]
+    fn test_workdir_validation() {
+        assert!(parse_context("workdir", "").is_ok());
+        assert!(parse_context("workdir", "data = false\ntemp = true").is_ok());
+        assert!(parse_context("workdir", "path = \".data\"").is_ok());
+
+        let err = parse_context("workdir", "data = false").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("[context.workdir] turns off both data and temp")
+        );
+
+        let err =
+            parse_context("workdir", "data = false\ntemp = true\npath = \".data\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("path in [context.workdir] only applies when data is kept")
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Help text that may be translated into several locales.
+//!
+//! A description is either a plain string or a table keyed by locale:
+//!
+//! ```toml
+//! [commands.hello]
+//! description = { en = "Say hello", fr = "Dire bonjour" }
+//! ```
+//!
+//! The text in the manifest's default locale (`cli.locale`, `en` unless
+//! set) is used wherever a single string is needed; generated CLIs pick a
+//! translation at runtime and fall back to it.
+
+use std::{collections::BTreeMap, fmt, ops::Deref};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Locale used when `cli.locale` is not set.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// A description, with its translations if it was given per locale.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Description {
+    /// Text in the default locale
+    text: String,
+    /// Text per locale, including the default one; empty for a plain string
+    translations: BTreeMap<String, String>,
+}
+
+impl Description {
+    /// A description with translations, keyed by locale.
+    ///
+    /// The text stays empty until [`Description::resolve`] selects the
+    /// default locale.
+    pub fn localized(translations: BTreeMap<String, String>) -> Self {
+        Self {
+            text: String::new(),
+            translations,
+        }
+    }
+
+    /// Text in the default locale.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Text per locale, including the default one; empty unless the
+    /// description was given as a table.
+    pub fn translations(&self) -> &BTreeMap<String, String> {
+        &self.translations
+    }
+
+    /// Whether the description was given per locale.
+    pub fn is_localized(&self) -> bool {
+        !self.translations.is_empty()
+    }
+
+    /// Translations other than the default locale's.
+    pub fn translations_except<'a>(
+        &'a self,
+        locale: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.translations
+            .iter()
+            .filter(move |(name, _)| *name != locale)
+            .map(|(name, text)| (name.as_str(), text.as_str()))
+    }
+
+    /// Select the text in `locale` from the translations.
+    ///
+    /// Returns `false` if the description is localized but has no text in
+    /// that locale.
+    pub(crate) fn resolve(&mut self, locale: &str) -> bool {
+        if !self.is_localized() {
+            return true;
+        }
+        match self.translations.get(locale) {
+            Some(text) => {
+                self.text = text.clone();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Deref for Description {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<String> for Description {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            translations: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<&str> for Description {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+impl From<Description> for String {
+    fn from(description: Description) -> Self {
+        description.text
+    }
+}
+
+impl<'de> Deserialize<'de> for Description {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Localized(BTreeMap<String, String>),
+        }
+
+        match Raw::deserialize(deserializer).map_err(|_| {
+            serde::de::Error::custom("expected a string or a table of strings keyed by locale")
+        })? {
+            Raw::Text(text) => Ok(text.into()),
+            Raw::Localized(translations) if translations.is_empty() => Err(
+                serde::de::Error::custom("expected at least one locale in the description table"),
+            ),
+            Raw::Localized(translations) => Ok(Self::localized(translations)),
+        }
+    }
+}
+
+impl Serialize for Description {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.is_localized() {
+            self.translations.serialize(serializer)
+        } else {
+            self.text.serialize(serializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Diagnostic;
+
+    use super::*;
+    use crate::Manifest;
+
+    #[derive(Deserialize)]
+    struct Doc {
+        description: Description,
+    }
+
+    fn parse(src: &str) -> Result<Description, toml::de::Error> {
+        toml::from_str::<Doc>(src).map(|doc| doc.description)
+    }
+
+    #[test]
+    fn test_plain_description() {
+        let description = parse(r#"description = "Say hello""#).unwrap();
+        assert_eq!(&*description, "Say hello");
+        assert!(!description.is_localized());
+    }
+
+    #[test]
+    fn test_localized_description() {
+        let mut description =
+            parse(r#"description = { en = "Say hello", fr = "Dire bonjour" }"#).unwrap();
+        assert!(description.is_localized());
+        assert!(description.resolve("en"));
+        assert_eq!(&*description, "Say hello");
+        let others: Vec<_> = description.translations_except("en").collect();
+        assert_eq!(others, vec![("fr", "Dire bonjour")]);
+
+        assert!(!description.resolve("de"));
+    }
+
+    #[test]
+    fn test_manifest_default_locale() {
+        let manifest: Manifest = r#"
+            [cli]
+            name = "app"
+            language = "rust"
+            locale = "fr"
+            description = { en = "My app", fr = "Mon app" }
+
+            [commands.hello]
+            description = "Dire bonjour"
+
+            [commands.hello.flags.loud]
+            description = { en = "Shout", fr = "Crier" }
+            "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(manifest.cli.description.as_deref(), Some("Mon app"));
+        assert_eq!(&*manifest.commands["hello"].description, "Dire bonjour");
+        let loud = manifest.commands["hello"].flags["loud"]
+            .description
+            .as_ref();
+        assert_eq!(loud.map(|d| d.as_str()), Some("Crier"));
+    }
+
+    #[test]
+    fn test_manifest_missing_default_locale() {
+        let err = r#"
+            [cli]
+            name = "app"
+            language = "rust"
+
+            [commands.hello]
+            description = { fr = "Dire bonjour" }
+            "#
+        .parse::<Manifest>()
+        .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("no text in the default locale 'en'")
+        );
+        assert!(err.labels().is_some());
+    }
+
+    #[test]
+    fn test_invalid_description() {
+        assert!(parse("description = {}").is_err());
+        assert!(parse("description = 3").is_err());
+    }
+}
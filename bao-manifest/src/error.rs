@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use thiserror::Error;
 
+use crate::Language;
+
 /// Result type for bao-schema operations (boxed to reduce size on stack)
 pub type Result<T> = std::result::Result<T, Box<Error>>;
 
@@ -16,7 +18,7 @@ pub type Result<T> = std::result::Result<T, Box<Error>>;
 /// ```ignore
 /// let ctx = SourceContext::new(content, "bao.toml");
 /// ctx.validation_error("missing required field", None);
-/// ctx.reserved_keyword_error("fn", "command", span);
+/// ctx.reserved_keyword_error("fn", "command", Language::Rust, span);
 /// ```
 #[derive(Debug, Clone)]
 pub struct SourceContext {
@@ -80,11 +82,29 @@ impl SourceContext {
         })
     }
 
+    /// Create an unknown field error.
+    pub fn unknown_field_error(
+        &self,
+        field: impl Into<String>,
+        context: impl Into<String>,
+        help: impl Into<String>,
+        span: impl Into<SourceSpan>,
+    ) -> Box<Error> {
+        Box::new(Error::UnknownField {
+            src: self.named_source(),
+            span: span.into(),
+            field: field.into(),
+            context: context.into(),
+            help: help.into(),
+        })
+    }
+
     /// Create a reserved keyword error.
     pub fn reserved_keyword_error(
         &self,
         name: impl Into<String>,
         context: impl Into<String>,
+        language: Language,
         span: Option<SourceSpan>,
     ) -> Box<Error> {
         Box::new(Error::ReservedKeyword {
@@ -92,6 +112,7 @@ impl SourceContext {
             span,
             name: name.into(),
             context: context.into(),
+            language,
         })
     }
 
@@ -178,7 +199,20 @@ pub enum Error {
         message: String,
     },
 
-    #[error("'{name}' is a Rust reserved keyword")]
+    #[error("unknown field '{field}' in {context}")]
+    #[diagnostic(code(bao::unknown_field))]
+    UnknownField {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("unknown field")]
+        span: SourceSpan,
+        field: String,
+        context: String,
+        #[help]
+        help: String,
+    },
+
+    #[error("'{name}' is a {} reserved keyword", language.display_name())]
     #[diagnostic(help("rename '{name}' to something else, e.g. '{name}_cmd' or '{name}_arg'"))]
     ReservedKeyword {
         #[source_code]
@@ -187,6 +221,7 @@ pub enum Error {
         span: Option<SourceSpan>,
         name: String,
         context: String,
+        language: Language,
     },
 
     #[error("invalid {context} name '{name}'")]
@@ -242,6 +277,7 @@ impl Error {
     pub fn reserved_keyword(
         name: impl Into<String>,
         context: impl Into<String>,
+        language: Language,
         src: &str,
         filename: &str,
         span: Option<SourceSpan>,
@@ -251,6 +287,7 @@ impl Error {
             span,
             name: name.into(),
             context: context.into(),
+            language,
         })
     }
 
@@ -26,36 +26,57 @@
 //! - **CLI metadata** - Name, version, description
 //! - **Commands** - With arguments, flags, and subcommands
 //! - **Context** - Shared state like database pools and HTTP clients
+//! - **Routes** - HTTP endpoints with path and query parameters
+//!
+//! # Features
+//!
+//! - `fs` (default) - [`Manifest::from_file`], [`BaoToml`], `extends` and
+//!   `[generation] header_file`. Without it the crate builds for
+//!   `wasm32-unknown-unknown`, and manifests are parsed with
+//!   [`str::parse`](std::str::FromStr); `extends` and `header_file` are
+//!   rejected.
+//! - `remote` - `extends` pointing at an http(s) URL. Implies `fs`.
+//! - `arbitrary` - [`arbitrary::Arbitrary`] for [`Manifest`] and its
+//!   commands and routes, for fuzzing.
 
 // Miette's derive macro generates code that triggers these warnings
 #![allow(unused_assignments)]
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod command;
 mod context;
+mod description;
 mod error;
 mod manifest;
+mod route;
+mod schema;
 mod serialize;
 
 // Command
-pub use command::{Arg, ArgType, Command, Flag};
+pub use command::{Arg, ArgType, Command, Flag, Group, HintKind, PromptKind, RequiredIf};
 // Context
 pub use context::{
-    Context, ContextField, DatabaseConfig, HttpConfig, JournalMode, MySqlConfig, PoolConfig,
-    PostgresConfig, SqliteConfig, SynchronousMode,
+    AppConfig, CacheConfig, CacheMode, ConfigField, ConfigFieldType, ConfigFormat, Context,
+    ContextField, DatabaseConfig, DuckdbConfig, EmailConfig, GrpcConfig, HttpAuth, HttpConfig,
+    JournalMode, KafkaConfig, LlmConfig, LlmProvider, LogFormat, LogLevel, LoggingConfig,
+    MySqlConfig, NatsConfig, PoolConfig, PostgresConfig, ReplicaConfig, S3Config, SecretsBackend,
+    SecretsConfig, SmtpTls, SqliteConfig, SynchronousMode, WorkdirConfig,
 };
+// Description
+pub use description::{DEFAULT_LOCALE, Description};
 // Error
 pub use error::{Error, Result, SourceContext};
 // Manifest
+#[cfg(feature = "fs")]
+pub use manifest::{BaoToml, find_manifest};
 pub use manifest::{
-    BaoToml,
-    CliConfig,
-    Language,
-    Manifest,
-    ParseContext,
-    // TOML editing utilities
-    append_section,
-    command_section_header,
+    CliConfig, FlagCase, GenerationConfig, ImportExtension, Language, MANIFEST_FILE_NAMES,
+    Manifest, ManifestEditor, ManifestFormat, ModuleFormat, ParseContext, Profile, SCHEMA_VERSION,
+    SourceMap, Target, TargetConfig, TypeScriptConfig, command_section_header,
     context_section_header,
-    remove_toml_section,
-    rename_command_section,
 };
+// Route
+pub use route::{HttpMethod, Param, Route};
+// Schema
+pub use schema::json_schema;
@@ -2,6 +2,7 @@ use baobao_core::Version;
 use serde::Deserialize;
 
 use super::Language;
+use crate::{Description, description::DEFAULT_LOCALE};
 
 /// CLI metadata configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -14,13 +15,51 @@ pub struct CliConfig {
     pub version: Version,
 
     /// CLI description for help text
-    pub description: Option<String>,
+    pub description: Option<Description>,
 
     /// Author information
     pub author: Option<String>,
 
-    /// Target language for code generation
-    pub language: Language,
+    /// Text shown at the end of the CLI's help, such as links to docs or
+    /// support contacts
+    pub footer: Option<String>,
+
+    /// Target language for code generation (required unless `[targets]` is set)
+    pub language: Option<Language>,
+
+    /// Locale of the text used when a description has no translation for the
+    /// user's locale; `en` unless set
+    pub locale: Option<String>,
+
+    /// Show the git commit and build time in the generated `--version` output
+    #[serde(default)]
+    pub build_info: bool,
+
+    /// Add a hidden `docs` subcommand writing Markdown or man pages for the
+    /// generated CLI's own commands
+    #[serde(default)]
+    pub docs_command: bool,
+
+    /// Add a `doctor` subcommand checking that the environment variables,
+    /// databases, endpoints and files the context needs are in place
+    #[serde(default)]
+    pub doctor_command: bool,
+
+    /// Run `<name>-<command>` from PATH for commands the CLI does not define,
+    /// like git does with its plugins
+    #[serde(default)]
+    pub external_subcommands: bool,
+
+    /// Command run when the CLI is invoked without one, instead of printing
+    /// help
+    pub default_command: Option<String>,
+}
+
+impl CliConfig {
+    /// Default locale of descriptions.
+    pub fn locale(&self) -> &str {
+        self.locale.as_deref().unwrap_or(DEFAULT_LOCALE)
+    }
 }
 
 fn default_version() -> Version {
@@ -1,7 +1,15 @@
-//! TOML section manipulation utilities.
+//! Comment-preserving editing of bao.toml files.
 //!
-//! This module provides utilities for editing bao.toml files at the
-//! section level, preserving formatting and comments where possible.
+//! [`ManifestEditor`] applies typed operations (adding, removing and renaming
+//! commands, setting descriptions, ...) to a parsed TOML document. Everything
+//! it does not touch, including comments, key order and whitespace, is
+//! written back unchanged.
+
+use std::fmt;
+
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use crate::{Result, error::SourceContext};
 
 /// Build a TOML section header for a command path.
 ///
@@ -46,142 +54,331 @@ pub fn context_section_header(name: &str) -> String {
     format!("[context.{}]", name)
 }
 
-/// Remove a TOML section and its content from a string.
-///
-/// This removes the section header and all content until the next section
-/// (indicated by a line starting with `[`). Also cleans up extra blank lines.
+/// A TOML document edited in place, preserving its formatting.
 ///
-/// # Arguments
-///
-/// * `content` - The full TOML content
-/// * `section_header` - The exact section header to remove (e.g., "[commands.hello]")
+/// Command paths use `/` to separate nesting levels (`users/create`). The
+/// editor only checks that the tables it touches exist; re-parse the result
+/// (for example with [`BaoToml::set_content`](crate::BaoToml::set_content))
+/// to validate it as a manifest.
 ///
 /// # Examples
 ///
 /// ```
-/// use baobao_manifest::remove_toml_section;
+/// use baobao_manifest::ManifestEditor;
 ///
-/// let content = r#"[cli]
-/// name = "myapp"
+/// let mut editor = ManifestEditor::parse(r#"[cli]
+/// name = "myapp" # the binary name
 ///
-/// [commands.hello]
-/// description = "Say hello"
+/// [commands.users]
+/// description = "User management"
+/// "#).unwrap();
 ///
-/// [commands.world]
-/// description = "Say world"
-/// "#;
+/// editor.add_command("users/create", "Create a user").unwrap();
+/// editor.rename_command("users", "accounts").unwrap();
 ///
-/// let result = remove_toml_section(content, "[commands.hello]");
-/// assert!(!result.contains("[commands.hello]"));
-/// assert!(result.contains("[commands.world]"));
+/// let content = editor.to_string();
+/// assert!(content.contains("name = \"myapp\" # the binary name"));
+/// assert!(content.contains("[commands.accounts.commands.create]"));
 /// ```
-pub fn remove_toml_section(content: &str, section_header: &str) -> String {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = Vec::new();
-    let mut skip = false;
-    let mut skip_blank_after = false;
-
-    for line in lines {
-        if line.trim() == section_header {
-            skip = true;
-            skip_blank_after = true;
-            continue;
+#[derive(Debug, Clone)]
+pub struct ManifestEditor {
+    pub(super) doc: DocumentMut,
+    source: SourceContext,
+}
+
+impl ManifestEditor {
+    /// Parse TOML content for editing.
+    pub fn parse(content: &str) -> Result<Self> {
+        Self::parse_with_filename(content, "bao.toml")
+    }
+
+    /// Parse TOML content with a custom filename for error reporting.
+    pub fn parse_with_filename(content: &str, filename: &str) -> Result<Self> {
+        let source = SourceContext::new(content, filename);
+        let doc = content.parse::<DocumentMut>().map_err(|e| match e.span() {
+            Some(span) => source.validation_error_at(e.message().to_string(), span),
+            None => source.validation_error(e.message().to_string()),
+        })?;
+        Ok(Self { doc, source })
+    }
+
+    /// Add a command with a description.
+    ///
+    /// The parent of a nested command must already exist.
+    pub fn add_command(&mut self, path: &str, description: &str) -> Result<()> {
+        let (parent, name) = split_path(path);
+        if command(&self.doc, path).is_some() {
+            return Err(self.error(format!("command '{}' already exists", path)));
         }
 
-        if skip {
-            // Stop skipping when we hit another section
-            if line.starts_with('[') {
-                skip = false;
-                skip_blank_after = false;
-            } else {
-                continue;
-            }
+        let parent_table = match parent {
+            Some(parent) => command_mut(&mut self.doc, parent)
+                .ok_or_else(|| missing_command(&self.source, parent))?,
+            None => self.doc.as_table_mut(),
+        };
+        let Some(commands) = implicit_table(parent_table, "commands") else {
+            return Err(self.error(format!("'commands' of '{}' is not a table", path)));
+        };
+        let mut table = Table::new();
+        table.insert("description", toml_edit::value(description));
+        commands.insert(name, Item::Table(table));
+        Ok(())
+    }
+
+    /// Remove a command and its subcommands.
+    pub fn remove_command(&mut self, path: &str) -> Result<()> {
+        let (parent, name) = split_path(path);
+        commands_of_mut(&mut self.doc, parent)
+            .and_then(|commands| commands.remove(name))
+            .map(|_| ())
+            .ok_or_else(|| missing_command(&self.source, path))
+    }
+
+    /// Rename a command, keeping its subcommands, comments and position.
+    ///
+    /// Both paths must have the same parent.
+    pub fn rename_command(&mut self, old_path: &str, new_path: &str) -> Result<()> {
+        let (old_parent, old_name) = split_path(old_path);
+        let (new_parent, new_name) = split_path(new_path);
+        if old_parent != new_parent {
+            return Err(self.error(format!(
+                "cannot move '{}' to '{}': commands can only be renamed within the same parent",
+                old_path, new_path
+            )));
+        }
+        if command(&self.doc, new_path).is_some() {
+            return Err(self.error(format!("command '{}' already exists", new_path)));
         }
 
-        // Skip blank lines immediately after removed section
-        if skip_blank_after && line.trim().is_empty() {
-            skip_blank_after = false;
-            continue;
+        let commands = commands_of_mut(&mut self.doc, old_parent)
+            .filter(|commands| commands.contains_key(old_name))
+            .ok_or_else(|| missing_command(&self.source, old_path))?;
+        if let Some(item) = commands.remove(old_name) {
+            commands.insert(new_name, item);
         }
+        Ok(())
+    }
 
-        result.push(line);
+    /// Set the description of a command, keeping any trailing comment.
+    pub fn set_description(&mut self, path: &str, description: &str) -> Result<()> {
+        let command =
+            command_mut(&mut self.doc, path).ok_or_else(|| missing_command(&self.source, path))?;
+        set_preserving_decor(command, "description", description.into());
+        Ok(())
     }
 
-    // Clean up trailing blank lines
-    while result.last().is_some_and(|l| l.trim().is_empty()) {
-        result.pop();
+    /// Remove a positional argument from a command.
+    pub fn remove_arg(&mut self, command: &str, name: &str) -> Result<()> {
+        self.remove_input(command, "args", name)
     }
 
-    if result.is_empty() {
-        String::new()
-    } else {
-        format!("{}\n", result.join("\n"))
+    /// Remove a flag from a command.
+    pub fn remove_flag(&mut self, command: &str, name: &str) -> Result<()> {
+        self.remove_input(command, "flags", name)
+    }
+
+    /// Add a `[context.<name>]` section with the given keys.
+    pub fn add_context(&mut self, name: &str, fields: Vec<(&str, Value)>) -> Result<()> {
+        let Some(context) = implicit_table(self.doc.as_table_mut(), "context") else {
+            return Err(self.error("'context' is not a table"));
+        };
+        if context.contains_key(name) {
+            return Err(self.error(format!("context field '{}' already exists", name)));
+        }
+
+        let mut table = Table::new();
+        for (key, value) in fields {
+            table.insert(key, Item::Value(value));
+        }
+        context.insert(name, Item::Table(table));
+        Ok(())
+    }
+
+    /// Remove a `[context.<name>]` section, or a named `[context.http.<name>]`
+    /// client, dropping `[context.http]` once its last client is gone.
+    pub fn remove_context(&mut self, name: &str) -> Result<()> {
+        let removed = self
+            .doc
+            .get_mut("context")
+            .and_then(Item::as_table_like_mut)
+            .and_then(|context| {
+                if let Some(item) = context.remove(name) {
+                    return Some(item);
+                }
+                let http = context.get_mut("http").and_then(Item::as_table_like_mut)?;
+                let item = http.remove(name)?;
+                if http.is_empty() {
+                    context.remove("http");
+                }
+                Some(item)
+            });
+        removed
+            .map(|_| ())
+            .ok_or_else(|| self.error(format!("context field '{}' does not exist", name)))
+    }
+
+    /// Set `key` in the table at `table` (e.g. `&["cli"]`), keeping any
+    /// trailing comment on the existing value.
+    pub fn set_value(&mut self, table: &[&str], key: &str, value: impl Into<Value>) -> Result<()> {
+        let mut current: &mut dyn TableLike = self.doc.as_table_mut();
+        for segment in table {
+            current = current
+                .get_mut(segment)
+                .and_then(Item::as_table_like_mut)
+                .ok_or_else(|| {
+                    self.source
+                        .validation_error(format!("table [{}] does not exist", table.join(".")))
+                })?;
+        }
+        set_preserving_decor(current, key, value.into());
+        Ok(())
+    }
+
+    fn remove_input(&mut self, command: &str, kind: &str, name: &str) -> Result<()> {
+        let table = command_mut(&mut self.doc, command)
+            .ok_or_else(|| missing_command(&self.source, command))?;
+        let removed = match table.get_mut(kind) {
+            // [commands.x.flags.name] or flags = { name = { ... } }
+            Some(item) if item.is_table_like() => item
+                .as_table_like_mut()
+                .and_then(|inputs| inputs.remove(name))
+                .is_some(),
+            // [[commands.x.flags]] with name = "..."
+            Some(Item::ArrayOfTables(inputs)) => {
+                let before = inputs.len();
+                inputs.retain(|input| input.get("name").and_then(Item::as_str) != Some(name));
+                inputs.len() < before
+            }
+            _ => false,
+        };
+
+        if removed {
+            Ok(())
+        } else {
+            let singular = kind.trim_end_matches('s');
+            Err(self.error(format!(
+                "{} '{}' of command '{}' does not exist",
+                singular, name, command
+            )))
+        }
+    }
+
+    pub(super) fn error(&self, message: impl Into<String>) -> Box<crate::Error> {
+        self.source.validation_error(message)
     }
 }
 
-/// Rename a command in TOML content by replacing section headers.
-///
-/// This replaces the section header for the old command with the new one,
-/// and also updates any nested subcommand section prefixes.
-///
-/// # Arguments
-///
-/// * `content` - The full TOML content
-/// * `old_name` - The old command path (e.g., "users" or "users/create")
-/// * `new_name` - The new command path (must be at same level)
-///
-/// # Examples
-///
-/// ```
-/// use baobao_manifest::rename_command_section;
-///
-/// let content = r#"[commands.users]
-/// description = "User management"
-///
-/// [commands.users.commands.create]
-/// description = "Create user"
-/// "#;
-///
-/// let result = rename_command_section(content, "users", "accounts");
-/// assert!(result.contains("[commands.accounts]"));
-/// assert!(result.contains("[commands.accounts.commands.create]"));
-/// ```
-pub fn rename_command_section(content: &str, old_name: &str, new_name: &str) -> String {
-    let old_header = command_section_header(old_name);
-    let new_header = command_section_header(new_name);
+impl fmt::Display for ManifestEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.doc.fmt(f)
+    }
+}
 
-    // Replace the section header
-    let mut result = content.replace(&old_header, &new_header);
+fn command<'a>(doc: &'a DocumentMut, path: &str) -> Option<&'a dyn TableLike> {
+    let mut current: &dyn TableLike = doc.as_table();
+    for segment in path.split('/') {
+        current = current
+            .get("commands")?
+            .as_table_like()?
+            .get(segment)?
+            .as_table_like()?;
+    }
+    Some(current)
+}
 
-    // For top-level commands, also replace nested section prefixes
-    // e.g., renaming "users" -> "accounts" also updates [commands.users.commands.X]
-    if !old_name.contains('/') {
-        let old_prefix = format!("[commands.{}.", old_name);
-        let new_prefix = format!("[commands.{}.", new_name);
-        result = result.replace(&old_prefix, &new_prefix);
+fn command_mut<'a>(doc: &'a mut DocumentMut, path: &str) -> Option<&'a mut dyn TableLike> {
+    let mut current: &mut dyn TableLike = doc.as_table_mut();
+    for segment in path.split('/') {
+        current = current
+            .get_mut("commands")?
+            .as_table_like_mut()?
+            .get_mut(segment)?
+            .as_table_like_mut()?;
     }
+    Some(current)
+}
 
-    result
+/// The `commands` table of a command, or the top-level one.
+fn commands_of_mut<'a>(
+    doc: &'a mut DocumentMut,
+    parent: Option<&str>,
+) -> Option<&'a mut dyn TableLike> {
+    let table = match parent {
+        Some(parent) => command_mut(doc, parent)?,
+        None => doc.as_table_mut(),
+    };
+    table.get_mut("commands")?.as_table_like_mut()
 }
 
-/// Append a section to TOML content with proper spacing.
-///
-/// # Arguments
-///
-/// * `content` - The current TOML content
-/// * `section` - The section to append (including header and fields)
-///
-/// # Returns
-///
-/// The new content with the section appended.
-pub fn append_section(content: &str, section: &str) -> String {
-    format!("{}\n\n{}", content.trim_end(), section.trim())
+fn missing_command(source: &SourceContext, path: &str) -> Box<crate::Error> {
+    source.validation_error(format!("command '{}' does not exist", path))
+}
+
+/// `users/create` -> `(Some("users"), "create")`.
+fn split_path(path: &str) -> (Option<&str>, &str) {
+    match path.rsplit_once('/') {
+        Some((parent, name)) => (Some(parent), name),
+        None => (None, path),
+    }
+}
+
+/// Get or create the sub-table `key`, created implicit so that only its
+/// children get a header.
+fn implicit_table<'a>(table: &'a mut dyn TableLike, key: &str) -> Option<&'a mut dyn TableLike> {
+    table
+        .entry(key)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_like_mut()
+}
+
+fn set_preserving_decor(table: &mut dyn TableLike, key: &str, mut value: Value) {
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        Some(existing) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const CONTENT: &str = r#"# My CLI
+[cli]
+name = "myapp"
+version = "1.0.0" # release
+
+# User management
+[commands.users]
+description = "User management"
+
+[commands.users.commands.create]
+description = "Create user"
+
+[[commands.users.commands.create.flags]]
+name = "admin"
+type = "bool"
+
+[commands.hello]
+description = "Say hello" # greeting
+args = { name = { type = "string" } }
+
+[context.database]
+type = "sqlite"
+"#;
+
+    fn editor() -> ManifestEditor {
+        ManifestEditor::parse(CONTENT).unwrap()
+    }
+
     #[test]
     fn test_command_section_header_simple() {
         assert_eq!(command_section_header("hello"), "[commands.hello]");
@@ -210,72 +407,168 @@ mod tests {
     }
 
     #[test]
-    fn test_remove_toml_section_basic() {
-        let content = r#"[cli]
-name = "myapp"
+    fn test_unchanged_round_trip() {
+        assert_eq!(editor().to_string(), CONTENT);
+    }
 
-[commands.hello]
-description = "Say hello"
+    #[test]
+    fn test_add_command() {
+        let mut editor = editor();
+        editor.add_command("world", "Say world").unwrap();
+        editor.add_command("users/delete", "Delete user").unwrap();
+
+        // New sections follow their last sibling
+        let result = editor.to_string();
+        let position = |text: &str| result.find(text).unwrap();
+        assert!(result.contains("\n\n[commands.world]\ndescription = \"Say world\"\n"));
+        assert!(position("[commands.hello]") < position("[commands.world]"));
+        assert!(position("[commands.world]") < position("[context.database]"));
+        assert!(position("admin") < position("[commands.users.commands.delete]"));
+        assert!(position("[commands.users.commands.delete]") < position("[commands.hello]"));
+
+        assert!(editor.add_command("hello", "Again").is_err());
+        assert!(editor.add_command("missing/child", "Orphan").is_err());
+    }
 
-[commands.world]
-description = "Say world"
-"#;
+    #[test]
+    fn test_add_first_command() {
+        let mut editor = ManifestEditor::parse("[cli]\nname = \"myapp\"\n").unwrap();
+        editor.add_command("hello", "Say hello").unwrap();
+        assert_eq!(
+            editor.to_string(),
+            "[cli]\nname = \"myapp\"\n\n[commands.hello]\ndescription = \"Say hello\"\n"
+        );
+    }
 
-        let result = remove_toml_section(content, "[commands.hello]");
-        assert!(!result.contains("[commands.hello]"));
-        assert!(!result.contains("Say hello"));
-        assert!(result.contains("[commands.world]"));
-        assert!(result.contains("Say world"));
+    #[test]
+    fn test_remove_command() {
+        let mut editor = editor();
+        editor.remove_command("users").unwrap();
+
+        let result = editor.to_string();
+        assert!(!result.contains("users"));
+        assert!(!result.contains("# User management"));
+        assert!(result.contains("# My CLI"));
+        assert!(result.contains("[commands.hello]"));
+        assert!(editor.remove_command("users").is_err());
     }
 
     #[test]
-    fn test_remove_toml_section_last() {
-        let content = r#"[cli]
-name = "myapp"
+    fn test_remove_nested_command() {
+        let mut editor = editor();
+        editor.remove_command("users/create").unwrap();
+
+        let result = editor.to_string();
+        assert!(result.contains("[commands.users]"));
+        assert!(!result.contains("create"));
+        assert!(!result.contains("admin"));
+    }
 
-[commands.hello]
-description = "Say hello"
-"#;
+    #[test]
+    fn test_rename_command() {
+        let mut editor = editor();
+        editor.rename_command("users", "accounts").unwrap();
 
-        let result = remove_toml_section(content, "[commands.hello]");
-        assert!(!result.contains("[commands.hello]"));
-        assert!(result.contains("[cli]"));
+        let result = editor.to_string();
+        assert!(result.contains("# User management\n[commands.accounts]\n"));
+        assert!(result.contains("[commands.accounts.commands.create]"));
+        assert!(result.contains("[[commands.accounts.commands.create.flags]]"));
+        assert!(!result.contains("[commands.users"));
+        // Position in the file is kept
+        assert!(result.find("accounts").unwrap() < result.find("[commands.hello]").unwrap());
     }
 
     #[test]
-    fn test_rename_command_section() {
-        let content = r#"[commands.users]
-description = "User management"
-"#;
+    fn test_rename_nested_command() {
+        let mut editor = editor();
+        editor.rename_command("users/create", "users/add").unwrap();
+        assert!(editor.to_string().contains("[commands.users.commands.add]"));
+
+        assert!(editor.rename_command("users/add", "hello/add").is_err());
+        assert!(editor.rename_command("users/add", "hello").is_err());
+        assert!(editor.rename_command("missing", "other").is_err());
+    }
 
-        let result = rename_command_section(content, "users", "accounts");
-        assert!(result.contains("[commands.accounts]"));
-        assert!(!result.contains("[commands.users]"));
+    #[test]
+    fn test_set_description() {
+        let mut editor = editor();
+        editor.set_description("hello", "Greet someone").unwrap();
+        editor
+            .set_description("users/create", "Add a user")
+            .unwrap();
+
+        let result = editor.to_string();
+        assert!(result.contains("description = \"Greet someone\" # greeting\n"));
+        assert!(result.contains("description = \"Add a user\"\n"));
+        assert!(editor.set_description("missing", "None").is_err());
     }
 
     #[test]
-    fn test_rename_command_section_with_nested() {
-        let content = r#"[commands.users]
-description = "User management"
+    fn test_remove_inputs() {
+        let mut editor = editor();
+        editor.remove_flag("users/create", "admin").unwrap();
+        editor.remove_arg("hello", "name").unwrap();
 
-[commands.users.commands.create]
-description = "Create user"
-"#;
+        let result = editor.to_string();
+        assert!(!result.contains("admin"));
+        assert!(result.contains("args = {}"));
 
-        let result = rename_command_section(content, "users", "accounts");
-        assert!(result.contains("[commands.accounts]"));
-        assert!(result.contains("[commands.accounts.commands.create]"));
-        assert!(!result.contains("[commands.users]"));
+        let err = editor.remove_flag("hello", "verbose").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "flag 'verbose' of command 'hello' does not exist"
+        );
     }
 
     #[test]
-    fn test_append_section() {
-        let content = "[cli]\nname = \"myapp\"";
-        let section = "[commands.hello]\ndescription = \"Hello\"";
+    fn test_context() {
+        let mut editor = editor();
+        editor
+            .add_context("http", vec![("timeout", 30.into())])
+            .unwrap();
+        assert!(
+            editor
+                .to_string()
+                .ends_with("[context.http]\ntimeout = 30\n")
+        );
+        assert!(editor.add_context("database", vec![]).is_err());
 
-        let result = append_section(content, section);
-        assert!(result.contains("[cli]"));
-        assert!(result.contains("[commands.hello]"));
-        assert!(result.contains("\n\n")); // proper spacing
+        editor.remove_context("database").unwrap();
+        assert!(!editor.to_string().contains("sqlite"));
+        assert!(editor.remove_context("database").is_err());
+    }
+
+    #[test]
+    fn test_remove_named_http_client() {
+        let mut editor = ManifestEditor::parse(
+            "[cli]\nname = \"app\"\n\n[context.http.github]\nbase_url = \"https://api.github.com\"\n\n[context.http.internal]\ntimeout = 5\n",
+        )
+        .unwrap();
+
+        editor.remove_context("github").unwrap();
+        let result = editor.to_string();
+        assert!(!result.contains("github"));
+        assert!(result.contains("[context.http.internal]"));
+
+        editor.remove_context("internal").unwrap();
+        assert!(!editor.to_string().contains("context.http"));
+        assert!(editor.remove_context("internal").is_err());
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut editor = editor();
+        editor.set_value(&["cli"], "version", "1.1.0").unwrap();
+        editor.set_value(&["cli"], "author", "Jane").unwrap();
+
+        let result = editor.to_string();
+        assert!(result.contains("version = \"1.1.0\" # release\n"));
+        assert!(result.contains("author = \"Jane\"\n"));
+        assert!(editor.set_value(&["package"], "version", "1.1.0").is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(ManifestEditor::parse("[cli\n").is_err());
     }
 }
@@ -0,0 +1,502 @@
+//! Manifest inheritance through `extends`.
+//!
+//! A manifest can set `extends = "../base.bao.toml"` to inherit from a base
+//! manifest, such as one holding an organization's shared context and
+//! commands. The base is resolved relative to the extending file and may
+//! itself extend another manifest. With the `remote` feature it can also be
+//! an http(s) URL, and relative `extends` in a downloaded manifest are
+//! resolved against its URL.
+//!
+//! Merging is key by key: tables are merged recursively and any other value
+//! in the extending manifest (strings, numbers, arrays) replaces the base
+//! value. Array-format args and flags are therefore replaced as a whole.
+
+use std::path::Path;
+
+use miette::SourceSpan;
+#[cfg(feature = "fs")]
+use toml::Spanned;
+use toml::{Table, Value, de::DeTable};
+
+#[cfg(feature = "fs")]
+use super::ManifestFormat;
+use super::ParseContext;
+use crate::Result;
+#[cfg(feature = "fs")]
+use crate::{Error, command::check_fields};
+
+/// Key naming the base manifest.
+const EXTENDS: &str = "extends";
+
+/// Resolve the `extends` chain of a manifest, given its parsed document.
+///
+/// Returns the merged document, or `None` if the manifest does not extend
+/// another one.
+pub(crate) fn resolve(ctx: &ParseContext, root: Option<&DeTable<'_>>) -> Result<Option<Table>> {
+    if !root.is_some_and(|root| root.contains_key(EXTENDS)) {
+        return Ok(None);
+    }
+    let table = parse_table(ctx)?;
+
+    let mut chain = Vec::new();
+    if let Ok(path) = Path::new(ctx.filename()).canonicalize() {
+        chain.push(path.display().to_string());
+    }
+    resolve_table(ctx, table, &mut chain).map(Some)
+}
+
+fn resolve_table(ctx: &ParseContext, mut table: Table, chain: &mut Vec<String>) -> Result<Table> {
+    let Some(extends) = table.get(EXTENDS) else {
+        return Ok(table);
+    };
+    let span = ctx.value_span(EXTENDS);
+    let Value::String(extends) = extends else {
+        return Err(ctx.validation_error("'extends' must be a path to a manifest", span));
+    };
+    if extends.contains("://") && !extends.starts_with("file://") && !is_remote(extends) {
+        return Err(ctx.validation_error(
+            format!(
+                "cannot extend '{}': only paths and http(s) URLs are supported",
+                extends
+            ),
+            span,
+        ));
+    }
+
+    let base = if is_remote(extends) || is_remote(ctx.filename()) {
+        let Some(url) = join_url(ctx.filename(), extends) else {
+            return Err(ctx.validation_error(
+                format!(
+                    "cannot extend '{}': a downloaded manifest can only extend relative paths and URLs",
+                    extends
+                ),
+                span,
+            ));
+        };
+        fetch_base(ctx, &url, span, chain)?
+    } else {
+        load_base(ctx, extends, span, chain)?
+    };
+
+    // The extending manifest's own `extends` is kept to record where it came from
+    let extends = table.remove(EXTENDS);
+    let mut merged = merge(base, table);
+    if let Some(extends) = extends {
+        merged.insert(EXTENDS.to_string(), extends);
+    }
+    Ok(merged)
+}
+
+/// Read and resolve the base manifest `extends` points to.
+#[cfg(feature = "fs")]
+fn load_base(
+    ctx: &ParseContext,
+    extends: &str,
+    span: Option<SourceSpan>,
+    chain: &mut Vec<String>,
+) -> Result<Table> {
+    let dir = Path::new(ctx.filename()).parent().unwrap_or(Path::new(""));
+    let base_path = dir.join(extends.trim_start_matches("file://"));
+    let canonical = base_path.canonicalize().map_err(|e| {
+        Box::new(Error::Io {
+            path: base_path.clone(),
+            source: e,
+        })
+    })?;
+    enter(ctx, canonical.display().to_string(), span, chain)?;
+
+    let content = std::fs::read_to_string(&base_path).map_err(|e| {
+        Box::new(Error::Io {
+            path: base_path.clone(),
+            source: e,
+        })
+    })?;
+    load_document(&content, &base_path.display().to_string(), chain)
+}
+
+/// Download and resolve the base manifest at `url`.
+#[cfg(feature = "remote")]
+fn fetch_base(
+    ctx: &ParseContext,
+    url: &str,
+    span: Option<SourceSpan>,
+    chain: &mut Vec<String>,
+) -> Result<Table> {
+    enter(ctx, url.to_string(), span, chain)?;
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(std::time::Duration::from_secs(30)))
+        .build()
+        .into();
+    let content = agent
+        .get(url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| ctx.validation_error(format!("cannot fetch '{}': {}", url, e), span))?;
+    load_document(&content, url, chain)
+}
+
+/// Downloading base manifests needs the `remote` feature.
+#[cfg(not(feature = "remote"))]
+fn fetch_base(
+    ctx: &ParseContext,
+    url: &str,
+    span: Option<SourceSpan>,
+    _chain: &mut Vec<String>,
+) -> Result<Table> {
+    Err(ctx.validation_error(
+        format!(
+            "cannot extend '{}': this build of bao cannot download manifests",
+            url
+        ),
+        span,
+    ))
+}
+
+/// Record `base` in the `extends` chain, rejecting cycles.
+#[cfg(feature = "fs")]
+fn enter(
+    ctx: &ParseContext,
+    base: String,
+    span: Option<SourceSpan>,
+    chain: &mut Vec<String>,
+) -> Result<()> {
+    if let Some(start) = chain.iter().position(|seen| *seen == base) {
+        let mut cycle = chain[start..].to_vec();
+        cycle.push(base);
+        return Err(ctx.validation_error(
+            format!("manifest extends itself: {}", cycle.join(" -> ")),
+            span,
+        ));
+    }
+    chain.push(base);
+    Ok(())
+}
+
+/// Parse a base manifest read from `filename` and resolve its own `extends`.
+#[cfg(feature = "fs")]
+fn load_document(content: &str, filename: &str, chain: &mut Vec<String>) -> Result<Table> {
+    let content = ManifestFormat::from_path(filename).to_toml(content, filename)?;
+    let document = DeTable::parse(&content).ok();
+    let root = document.as_ref().map(Spanned::get_ref);
+    let base_ctx = ParseContext::from_document(&content, filename, root);
+    if let Some(root) = root {
+        check_fields(&base_ctx, root)?;
+    }
+    resolve_table(&base_ctx, parse_table(&base_ctx)?, chain)
+}
+
+/// Base manifests are files, so `extends` needs filesystem access.
+#[cfg(not(feature = "fs"))]
+fn load_base(
+    ctx: &ParseContext,
+    extends: &str,
+    span: Option<SourceSpan>,
+    _chain: &mut Vec<String>,
+) -> Result<Table> {
+    Err(ctx.validation_error(
+        format!(
+            "cannot extend '{}': this build of bao cannot read files",
+            extends
+        ),
+        span,
+    ))
+}
+
+fn is_remote(extends: &str) -> bool {
+    extends.starts_with("http://") || extends.starts_with("https://")
+}
+
+/// Resolve `extends` against `base`, the file or URL of the extending manifest.
+///
+/// Returns `None` when a downloaded manifest extends a local file.
+fn join_url(base: &str, extends: &str) -> Option<String> {
+    if is_remote(extends) {
+        return Some(extends.to_string());
+    }
+    if !is_remote(base) || extends.contains("://") || extends.starts_with('/') {
+        return None;
+    }
+
+    let base = base.split(['?', '#']).next().unwrap_or(base);
+    let authority = base.find("://")? + 3;
+    let (origin, path) = base.split_at(
+        base[authority..]
+            .find('/')
+            .map_or(base.len(), |i| authority + i),
+    );
+    let mut segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if !path.ends_with('/') {
+        segments.pop();
+    }
+    for segment in extends.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(format!("{}/{}", origin, segments.join("/")))
+}
+
+fn parse_table(ctx: &ParseContext) -> Result<Table> {
+    ctx.src()
+        .parse()
+        .map_err(|e| ctx.source_context().parse_error(e))
+}
+
+/// Merge `overlay` into `base`, recursing into tables present in both.
+pub(super) fn merge(mut base: Table, overlay: Table) -> Table {
+    for (key, value) in overlay {
+        let value = match (base.remove(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => Value::Table(merge(base, overlay)),
+            (_, value) => value,
+        };
+        base.insert(key, value);
+    }
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "fs")]
+    use std::fs;
+
+    use super::*;
+    use crate::Manifest;
+
+    fn table(src: &str) -> Table {
+        src.parse().unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = table(
+            r#"
+            [cli]
+            name = "base"
+            author = "Platform Team"
+
+            [commands.version]
+            description = "Print the version"
+
+            [[commands.version.flags]]
+            name = "json"
+            "#,
+        );
+        let overlay = table(
+            r#"
+            [cli]
+            name = "app"
+
+            [[commands.version.flags]]
+            name = "short"
+            "#,
+        );
+        let merged = merge(base, overlay);
+
+        assert_eq!(merged["cli"]["name"].as_str(), Some("app"));
+        assert_eq!(merged["cli"]["author"].as_str(), Some("Platform Team"));
+        let version = &merged["commands"]["version"];
+        assert_eq!(version["description"].as_str(), Some("Print the version"));
+        let flags = version["flags"].as_array().unwrap();
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags[0]["name"].as_str(), Some("short"));
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extends_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("org.bao.toml"),
+            r#"
+            [cli]
+            name = "org"
+            language = "rust"
+            author = "Platform Team"
+
+            [context.database]
+            type = "postgres"
+            env = "ORG_DATABASE_URL"
+
+            [commands.version]
+            description = "Print the version"
+            "#,
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("team")).unwrap();
+        fs::write(
+            dir.path().join("team/base.bao.toml"),
+            r#"
+            extends = "../org.bao.toml"
+
+            [context.database]
+            env = "DATABASE_URL"
+            "#,
+        )
+        .unwrap();
+        let path = dir.path().join("bao.toml");
+        fs::write(
+            &path,
+            r#"
+            extends = "team/base.bao.toml"
+
+            [cli]
+            name = "app"
+
+            [commands.hello]
+            description = "Say hello"
+            "#,
+        )
+        .unwrap();
+
+        let manifest = Manifest::from_file(&path).unwrap();
+
+        assert_eq!(manifest.extends.as_deref(), Some("team/base.bao.toml"));
+        assert_eq!(manifest.cli.name, "app");
+        assert_eq!(manifest.cli.author.as_deref(), Some("Platform Team"));
+        assert_eq!(
+            manifest.context.database.as_ref().unwrap().env(),
+            Some("DATABASE_URL")
+        );
+        assert!(manifest.commands.contains_key("version"));
+        assert!(manifest.commands.contains_key("hello"));
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extends_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.toml"), "extends = \"b.toml\"\n").unwrap();
+        fs::write(dir.path().join("b.toml"), "extends = \"a.toml\"\n").unwrap();
+
+        let err = Manifest::from_file(dir.path().join("a.toml")).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("manifest extends itself"));
+        assert!(message.contains("a.toml -> "));
+    }
+
+    #[test]
+    fn test_join_url() {
+        let base = "https://example.com/org/team/base.bao.toml";
+        assert_eq!(
+            join_url(base, "../org.bao.toml").as_deref(),
+            Some("https://example.com/org/org.bao.toml")
+        );
+        assert_eq!(
+            join_url(base, "./shared.yaml").as_deref(),
+            Some("https://example.com/org/team/shared.yaml")
+        );
+        assert_eq!(
+            join_url("bao.toml", "http://example.com/base.toml").as_deref(),
+            Some("http://example.com/base.toml")
+        );
+        assert_eq!(join_url(base, "/etc/base.toml"), None);
+        assert_eq!(join_url(base, "file:///etc/base.toml"), None);
+    }
+
+    /// Serve `files` over HTTP on localhost, returning the server URL.
+    #[cfg(feature = "remote")]
+    fn serve(files: &'static [(&'static str, &'static str)]) -> String {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match files.iter().find(|(file, _)| *file == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_extends_remote() {
+        let url = serve(&[
+            (
+                "/org.bao.toml",
+                "[cli]\nname = \"org\"\nlanguage = \"rust\"\nauthor = \"Platform Team\"\n",
+            ),
+            (
+                "/team/base.yaml",
+                "extends: ../org.bao.toml\ncommands:\n  version:\n    description: Print the version\n",
+            ),
+        ]);
+        let manifest = Manifest::from_str_with_filename(
+            &format!(
+                "extends = \"{}/team/base.yaml\"\n\n[cli]\nname = \"app\"\n",
+                url
+            ),
+            "bao.toml",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.cli.name, "app");
+        assert_eq!(manifest.cli.author.as_deref(), Some("Platform Team"));
+        assert!(manifest.commands.contains_key("version"));
+
+        let missing = Manifest::from_str_with_filename(
+            &format!("extends = \"{}/missing.toml\"\n", url),
+            "bao.toml",
+        )
+        .unwrap_err();
+        assert!(missing.to_string().contains("cannot fetch"));
+    }
+
+    #[test]
+    fn test_extends_errors() {
+        let scheme = Manifest::from_str_with_filename(
+            "extends = \"ftp://example.com/base.bao.toml\"\n",
+            "bao.toml",
+        )
+        .unwrap_err();
+        assert!(scheme.to_string().contains("only paths and http(s) URLs"));
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_extends_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bao.toml");
+        fs::write(&path, "extends = \"missing.toml\"\n").unwrap();
+        let missing = Manifest::from_file(&path).unwrap_err();
+        assert!(matches!(*missing, Error::Io { .. }));
+    }
+
+    #[test]
+    #[cfg(not(feature = "fs"))]
+    fn test_extends_without_fs() {
+        let err = Manifest::from_str_with_filename(
+            "extends = \"base.bao.toml\"\n\n[cli]\nname = \"app\"\n",
+            "bao.toml",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot extend 'base.bao.toml': this build of bao cannot read files")
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use std::path::{Path, PathBuf};
 
-use super::Manifest;
+use super::{Manifest, ManifestEditor, ManifestFormat};
 use crate::Result;
 
 /// Represents a bao.toml file with both raw content and parsed manifest.
+///
+/// `bao.yaml` and `bao.json` manifests can be opened too, but only TOML
+/// manifests can be edited.
 pub struct BaoToml {
     path: PathBuf,
+    format: ManifestFormat,
     content: String,
     manifest: Manifest,
 }
@@ -21,10 +25,12 @@ impl BaoToml {
             })
         })?;
         let filename = path.display().to_string();
-        let manifest = Manifest::from_str_with_filename(&content, &filename)?;
+        let format = ManifestFormat::from_path(&path);
+        let manifest = Manifest::from_str_with_format(&content, &filename, format)?;
 
         Ok(Self {
             path,
+            format,
             content,
             manifest,
         })
@@ -35,6 +41,11 @@ impl BaoToml {
         &self.path
     }
 
+    /// Get the format of the file.
+    pub fn format(&self) -> ManifestFormat {
+        self.format
+    }
+
     /// Get the raw content.
     pub fn content(&self) -> &str {
         &self.content
@@ -48,12 +59,31 @@ impl BaoToml {
     /// Update content and re-parse the manifest.
     pub fn set_content(&mut self, content: String) -> Result<()> {
         let filename = self.path.display().to_string();
-        let manifest = Manifest::from_str_with_filename(&content, &filename)?;
+        let manifest = Manifest::from_str_with_format(&content, &filename, self.format)?;
         self.content = content;
         self.manifest = manifest;
         Ok(())
     }
 
+    /// Edit the content in place, preserving comments and formatting, and
+    /// re-parse the manifest.
+    ///
+    /// The content is left unchanged if `edit` fails or the result is not a
+    /// valid manifest. YAML and JSON manifests cannot be edited.
+    pub fn edit(&mut self, edit: impl FnOnce(&mut ManifestEditor) -> Result<()>) -> Result<()> {
+        let filename = self.path.display().to_string();
+        if self.format != ManifestFormat::Toml {
+            return Err(crate::SourceContext::new(self.content.clone(), filename)
+                .validation_error(format!(
+                    "cannot edit a {} manifest, only bao.toml can be edited in place",
+                    self.format.as_str()
+                )));
+        }
+        let mut editor = ManifestEditor::parse_with_filename(&self.content, &filename)?;
+        edit(&mut editor)?;
+        self.set_content(editor.to_string())
+    }
+
     /// Save the current content to disk.
     pub fn save(&self) -> Result<()> {
         std::fs::write(&self.path, &self.content).map_err(|e| {
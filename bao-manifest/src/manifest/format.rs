@@ -0,0 +1,206 @@
+//! Manifest file formats.
+//!
+//! TOML is the native format; `bao.yaml` and `bao.json` hold the same
+//! document. They are converted to TOML and parsed like bao.toml, so every
+//! format shares the same validation. Syntax errors point into the original
+//! file; later errors are shown against the converted TOML.
+
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
+use std::{borrow::Cow, path::Path};
+
+use miette::SourceSpan;
+use toml::Table;
+
+use crate::{Result, error::SourceContext};
+
+/// Manifest file names, in the order they are looked for.
+pub const MANIFEST_FILE_NAMES: &[&str] = &["bao.toml", "bao.yaml", "bao.yml", "bao.json"];
+
+/// Format of a manifest file, chosen by its extension.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    #[default]
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ManifestFormat {
+    /// The format of the file at `path`: YAML for `.yaml` and `.yml`, JSON
+    /// for `.json` and TOML otherwise.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+
+    /// Returns the format name as a static string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toml => "TOML",
+            Self::Yaml => "YAML",
+            Self::Json => "JSON",
+        }
+    }
+
+    /// The manifest in `content` as TOML.
+    pub(crate) fn to_toml<'a>(self, content: &'a str, filename: &str) -> Result<Cow<'a, str>> {
+        let source = SourceContext::new(content, filename);
+        let table: Table = match self {
+            Self::Toml => return Ok(Cow::Borrowed(content)),
+            Self::Yaml => serde_yaml_ng::from_str(content).map_err(|e| {
+                let span = e.location().map(|l| SourceSpan::from((l.index(), 0)));
+                syntax_error(&source, self, &e, span)
+            })?,
+            Self::Json => serde_json::from_str(content).map_err(|e| {
+                let span = offset(content, e.line(), e.column()).map(|i| SourceSpan::from((i, 0)));
+                syntax_error(&source, self, &e, span)
+            })?,
+        };
+        toml::to_string(&table)
+            .map(Cow::Owned)
+            .map_err(|e| source.validation_error(format!("cannot convert to TOML: {}", e)))
+    }
+}
+
+/// The manifest in `dir`: the first of [`MANIFEST_FILE_NAMES`] that exists,
+/// or `bao.toml` if there is none.
+#[cfg(feature = "fs")]
+pub fn find_manifest(dir: impl AsRef<Path>) -> PathBuf {
+    let dir = dir.as_ref();
+    MANIFEST_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(MANIFEST_FILE_NAMES[0]))
+}
+
+fn syntax_error(
+    source: &SourceContext,
+    format: ManifestFormat,
+    error: &dyn std::fmt::Display,
+    span: Option<SourceSpan>,
+) -> Box<crate::Error> {
+    let message = format!("invalid {}: {}", format.as_str(), error);
+    match span {
+        Some(span) => source.validation_error_at(message, span),
+        None => source.validation_error(message),
+    }
+}
+
+/// Byte offset of a 1-based line and column, as reported by serde_json.
+fn offset(content: &str, line: usize, column: usize) -> Option<usize> {
+    let start: usize = content
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum();
+    Some((start + column.saturating_sub(1)).min(content.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::Manifest;
+
+    const TOML: &str = r#"
+        [cli]
+        name = "app"
+        language = "rust"
+
+        [commands.hello]
+        description = "Say hello"
+        args = { name = { type = "string" } }
+        "#;
+
+    const YAML: &str = "cli:
+  name: app
+  language: rust
+commands:
+  hello:
+    description: Say hello
+    args:
+      name:
+        type: string
+";
+
+    const JSON: &str = r#"{
+  "cli": { "name": "app", "language": "rust" },
+  "commands": {
+    "hello": {
+      "description": "Say hello",
+      "args": { "name": { "type": "string" } }
+    }
+  }
+}"#;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(ManifestFormat::from_path("bao.toml"), ManifestFormat::Toml);
+        assert_eq!(
+            ManifestFormat::from_path("dir/bao.yml"),
+            ManifestFormat::Yaml
+        );
+        assert_eq!(ManifestFormat::from_path("bao.yaml"), ManifestFormat::Yaml);
+        assert_eq!(ManifestFormat::from_path("bao.json"), ManifestFormat::Json);
+        assert_eq!(ManifestFormat::from_path("bao"), ManifestFormat::Toml);
+    }
+
+    #[test]
+    fn test_formats_agree() {
+        let expected = Manifest::from_str(TOML).unwrap();
+        for (content, format) in [(YAML, ManifestFormat::Yaml), (JSON, ManifestFormat::Json)] {
+            let manifest = Manifest::from_str_with_format(content, "bao", format).unwrap();
+            assert_eq!(manifest.cli.name, expected.cli.name);
+            assert_eq!(manifest.language(), expected.language());
+            let hello = &manifest.commands["hello"];
+            assert_eq!(hello.description.as_str(), "Say hello");
+            assert!(hello.args.contains_key("name"));
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let err = Manifest::from_str_with_format("cli: [\n", "bao.yaml", ManifestFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("invalid YAML: "), "{}", err);
+
+        let content = "{\n  \"cli\": { \"name\": \"app\", }\n}";
+        let err =
+            Manifest::from_str_with_format(content, "bao.json", ManifestFormat::Json).unwrap_err();
+        assert!(err.to_string().starts_with("invalid JSON: "), "{}", err);
+        let crate::Error::Validation { span, .. } = *err else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(span.map(|s| s.offset()), Some(28));
+    }
+
+    #[test]
+    fn test_validation_is_shared() {
+        let yaml = "cli:\n  name: app\n  language: rust\ncommands:\n  hello:\n    description: Hi\n    alias: hi\n";
+        let err = Manifest::from_str_with_format(yaml, "bao.yaml", ManifestFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field 'alias'"), "{}", err);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_find_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(find_manifest(dir.path()), dir.path().join("bao.toml"));
+        std::fs::write(dir.path().join("bao.json"), JSON).unwrap();
+        assert_eq!(find_manifest(dir.path()), dir.path().join("bao.json"));
+        std::fs::write(dir.path().join("bao.yml"), YAML).unwrap();
+        assert_eq!(find_manifest(dir.path()), dir.path().join("bao.yml"));
+
+        let manifest = Manifest::from_file(dir.path().join("bao.yml")).unwrap();
+        assert_eq!(manifest.cli.name, "app");
+    }
+}
@@ -0,0 +1,101 @@
+//! Language-independent generation options.
+
+use serde::Deserialize;
+
+/// Options from the `[generation]` section, applying to every target.
+///
+/// ```toml
+/// [generation]
+/// header_file = "HEADER.txt"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct GenerationConfig {
+    /// File whose contents are prepended to every generated source file,
+    /// as comments, relative to the manifest
+    pub header_file: Option<String>,
+
+    /// Contents of `header_file`, read when the manifest is parsed from a file
+    #[serde(skip)]
+    pub header: Option<String>,
+}
+
+impl GenerationConfig {
+    /// Returns true if no options are set.
+    pub fn is_default(&self) -> bool {
+        self.header_file.is_none()
+    }
+
+    /// Text of the license header, without trailing newlines.
+    pub fn header(&self) -> Option<&str> {
+        self.header
+            .as_deref()
+            .map(|header| header.trim_end())
+            .filter(|header| !header.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "fs")]
+    use std::fs;
+
+    use crate::Manifest;
+
+    const MANIFEST: &str = r#"
+        [cli]
+        name = "app"
+        language = "rust"
+
+        [generation]
+        header_file = "HEADER.txt"
+        "#;
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_header_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("HEADER.txt"),
+            "Copyright 2026 Acme Corp.\nSPDX-License-Identifier: MIT\n\n",
+        )
+        .unwrap();
+        let path = dir.path().join("bao.toml");
+        fs::write(&path, MANIFEST).unwrap();
+
+        let manifest = Manifest::from_file(&path).unwrap();
+
+        assert_eq!(
+            manifest.generation.header(),
+            Some("Copyright 2026 Acme Corp.\nSPDX-License-Identifier: MIT")
+        );
+        assert!(
+            manifest
+                .to_toml_string()
+                .contains("header_file = \"HEADER.txt\"")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_missing_header_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bao.toml");
+        fs::write(&path, MANIFEST).unwrap();
+
+        let err = Manifest::from_file(&path).unwrap_err();
+
+        assert!(err.to_string().contains("cannot read header file"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "fs"))]
+    fn test_header_file_without_fs() {
+        let err = Manifest::from_str_with_filename(MANIFEST, "bao.toml").unwrap_err();
+
+        assert!(
+            err.to_string().contains(
+                "cannot read header file 'HEADER.txt': this build of bao cannot read files"
+            )
+        );
+    }
+}
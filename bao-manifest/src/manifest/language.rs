@@ -12,6 +12,8 @@ pub enum Language {
     Rust,
     /// TypeScript (Bun runtime)
     TypeScript,
+    /// Go (cobra + viper)
+    Go,
 }
 
 impl Language {
@@ -20,6 +22,16 @@ impl Language {
         match self {
             Language::Rust => "rust",
             Language::TypeScript => "typescript",
+            Language::Go => "go",
+        }
+    }
+
+    /// Returns the language name as written in prose (e.g. `TypeScript`).
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::TypeScript => "TypeScript",
+            Language::Go => "Go",
         }
     }
 }
@@ -37,8 +49,9 @@ impl FromStr for Language {
         match s.to_lowercase().as_str() {
             "rust" | "rs" => Ok(Language::Rust),
             "typescript" | "ts" => Ok(Language::TypeScript),
+            "go" | "golang" => Ok(Language::Go),
             _ => Err(format!(
-                "unknown language '{}', expected 'rust' or 'typescript'",
+                "unknown language '{}', expected 'rust', 'typescript' or 'go'",
                 s
             )),
         }
@@ -63,6 +76,8 @@ mod tests {
             Language::from_str("TypeScript").unwrap(),
             Language::TypeScript
         );
+        assert_eq!(Language::from_str("go").unwrap(), Language::Go);
+        assert_eq!(Language::from_str("golang").unwrap(), Language::Go);
         assert!(Language::from_str("python").is_err());
     }
 
@@ -70,6 +85,7 @@ mod tests {
     fn test_display() {
         assert_eq!(Language::Rust.to_string(), "rust");
         assert_eq!(Language::TypeScript.to_string(), "typescript");
+        assert_eq!(Language::Go.to_string(), "go");
     }
 
     #[test]
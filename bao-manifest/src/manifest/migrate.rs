@@ -0,0 +1,163 @@
+//! Upgrading manifests written for older schema versions.
+//!
+//! `schema_version` at the top of bao.toml records the layout a manifest
+//! was written for; manifests without it predate versioning and count as
+//! version 1. Each migration rewrites one version into the next with
+//! [`ManifestEditor`], so comments and formatting survive the upgrade.
+
+use super::{Manifest, ManifestEditor};
+use crate::Result;
+
+/// Latest manifest schema version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Schema version of manifests written before `schema_version` existed.
+const UNVERSIONED: u32 = 1;
+
+/// Top-level key holding the schema version.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Upgrade of a manifest from one schema version to the next.
+struct Migration {
+    /// Version the migration upgrades from
+    from: u32,
+    /// Rewrite the document, returning a description of each change
+    apply: fn(&mut ManifestEditor) -> Vec<String>,
+}
+
+/// Migrations in order; empty while [`SCHEMA_VERSION`] is the first version.
+const MIGRATIONS: &[Migration] = &[];
+
+impl Manifest {
+    /// The schema version the manifest was written for.
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version.unwrap_or(UNVERSIONED)
+    }
+}
+
+impl ManifestEditor {
+    /// The `schema_version` of the document, or 1 if it has none.
+    pub fn schema_version(&self) -> Result<u32> {
+        match self.doc.get(SCHEMA_VERSION_KEY) {
+            None => Ok(UNVERSIONED),
+            Some(item) => item
+                .as_integer()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| self.error("'schema_version' must be a non-negative integer")),
+        }
+    }
+
+    /// Upgrade the document to [`SCHEMA_VERSION`], returning a description
+    /// of each change. Nothing is changed if it is already up to date.
+    pub fn migrate(&mut self) -> Result<Vec<String>> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            return Err(self.error(format!(
+                "schema_version {} is newer than the latest supported version {}",
+                version, SCHEMA_VERSION
+            )));
+        }
+        if version == SCHEMA_VERSION {
+            return Ok(Vec::new());
+        }
+
+        let mut changes = Vec::new();
+        for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+            changes.extend((migration.apply)(self));
+        }
+        self.set_schema_version();
+        changes.push(format!(
+            "set schema_version = {} (was {})",
+            SCHEMA_VERSION, version
+        ));
+        Ok(changes)
+    }
+
+    /// Set `schema_version`, leaving a blank line between a newly added key
+    /// and the first table.
+    fn set_schema_version(&mut self) {
+        let root = self.doc.as_table_mut();
+        if !root.contains_key(SCHEMA_VERSION_KEY) {
+            let first = root
+                .iter_mut()
+                .filter_map(|(_, item)| item.as_table_mut())
+                .filter(|table| !table.is_implicit())
+                .min_by_key(|table| table.position());
+            if let Some(table) = first {
+                let prefix = table
+                    .decor()
+                    .prefix()
+                    .and_then(|p| p.as_str())
+                    .unwrap_or("");
+                let prefix = format!("\n{}", prefix);
+                table.decor_mut().set_prefix(prefix);
+            }
+        }
+        root.insert(
+            SCHEMA_VERSION_KEY,
+            toml_edit::value(i64::from(SCHEMA_VERSION)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::Manifest;
+
+    #[test]
+    fn test_migrate() {
+        // Manifests without schema_version are already at the first version
+        let content = r#"# My CLI
+[cli]
+name = "myapp"
+language = "rust"
+"#;
+        let mut editor = ManifestEditor::parse(content).unwrap();
+        assert_eq!(editor.schema_version().unwrap(), 1);
+        assert!(editor.migrate().unwrap().is_empty());
+        assert_eq!(editor.to_string(), content);
+        assert_eq!(Manifest::from_str(content).unwrap().schema_version(), 1);
+
+        let mut editor =
+            ManifestEditor::parse(&format!("schema_version = 0\n{}", content)).unwrap();
+        let changes = editor.migrate().unwrap();
+        assert_eq!(changes, ["set schema_version = 1 (was 0)"]);
+
+        let migrated = editor.to_string();
+        assert!(migrated.starts_with("schema_version = 1\n# My CLI\n[cli]\n"));
+        let manifest = Manifest::from_str(&migrated).unwrap();
+        assert_eq!(manifest.schema_version, Some(SCHEMA_VERSION));
+
+        // Up to date manifests are left alone
+        assert!(editor.migrate().unwrap().is_empty());
+        assert_eq!(editor.to_string(), migrated);
+    }
+
+    #[test]
+    fn test_migrate_invalid_version() {
+        let mut editor = ManifestEditor::parse("schema_version = \"1\"\n").unwrap();
+        assert_eq!(
+            editor.migrate().unwrap_err().to_string(),
+            "'schema_version' must be a non-negative integer"
+        );
+
+        let mut editor = ManifestEditor::parse("schema_version = 9\n").unwrap();
+        assert_eq!(
+            editor.migrate().unwrap_err().to_string(),
+            "schema_version 9 is newer than the latest supported version 1"
+        );
+
+        let err =
+            Manifest::from_str("schema_version = 9\n[cli]\nname = \"app\"\nlanguage = \"go\"\n")
+                .unwrap_err()
+                .to_string();
+        assert!(
+            err.contains("newer than the latest supported version 1"),
+            "{}",
+            err
+        );
+    }
+}
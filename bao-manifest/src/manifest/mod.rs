@@ -2,28 +2,56 @@
 
 mod cli;
 mod edit;
+mod extends;
+#[cfg(feature = "fs")]
 mod file;
+mod format;
+mod generation;
 mod language;
+mod migrate;
 mod parse;
+mod profile;
+mod spans;
+mod targets;
+mod typescript;
 mod validate;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub use cli::CliConfig;
-pub use edit::{
-    append_section, command_section_header, context_section_header, remove_toml_section,
-    rename_command_section,
-};
+pub use edit::{ManifestEditor, command_section_header, context_section_header};
+#[cfg(feature = "fs")]
 pub use file::BaoToml;
+#[cfg(feature = "fs")]
+pub use format::find_manifest;
+pub use format::{MANIFEST_FILE_NAMES, ManifestFormat};
+pub use generation::GenerationConfig;
 pub use language::Language;
+pub use migrate::SCHEMA_VERSION;
+pub use profile::Profile;
+pub(crate) use profile::{PROFILE_CLI_FIELDS, PROFILE_FIELDS};
 use serde::Deserialize;
+pub use spans::SourceMap;
+pub use targets::{Target, TargetConfig};
+pub use typescript::{FlagCase, ImportExtension, ModuleFormat, TypeScriptConfig};
 pub use validate::ParseContext;
+pub(crate) use validate::validate_identifier;
 
-use crate::{Command, Context};
+use crate::{Command, Context, Flag, Route};
 
 /// Root manifest for bao.toml
 #[derive(Debug, Clone, Deserialize)]
 pub struct Manifest {
+    /// Schema version the manifest was written for; `bao migrate` upgrades
+    /// older manifests
+    #[serde(default)]
+    pub schema_version: Option<u32>,
+
+    /// Path of the base manifest this one inherits from, relative to this
+    /// file, or its http(s) URL
+    #[serde(default)]
+    pub extends: Option<String>,
+
     /// CLI metadata
     pub cli: CliConfig,
 
@@ -32,28 +60,100 @@ pub struct Manifest {
     #[serde(default, deserialize_with = "crate::context::deserialize")]
     pub context: Context,
 
+    /// Options applying to every generation target
+    #[serde(default)]
+    pub generation: GenerationConfig,
+
+    /// TypeScript-specific generation options
+    #[serde(default)]
+    pub typescript: TypeScriptConfig,
+
+    /// Named generation targets, replacing `cli.language`
+    #[serde(default)]
+    pub targets: BTreeMap<String, TargetConfig>,
+
+    /// Named profiles overriding CLI metadata and context settings
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+
+    /// Global flags, accepted by every command
+    #[serde(default, deserialize_with = "crate::command::deserialize_flags")]
+    pub flags: HashMap<String, Flag>,
+
     /// Top-level commands
     #[serde(default)]
     pub commands: HashMap<String, Command>,
+
+    /// HTTP routes
+    #[serde(default)]
+    pub routes: BTreeMap<String, Route>,
+
+    /// Source locations of commands, inputs and routes; empty unless the
+    /// manifest was parsed with [`Manifest::from_str`] or [`Manifest::from_file`]
+    #[serde(skip)]
+    pub spans: SourceMap,
 }
 
 impl Manifest {
+    /// Generation targets: the `[targets]` entries sorted by name, or a
+    /// single implicit target for `cli.language`.
+    pub fn targets(&self) -> Vec<Target> {
+        if self.targets.is_empty() {
+            return vec![Target::implicit(self.language(), self.typescript.clone())];
+        }
+        self.targets
+            .iter()
+            .map(|(name, config)| Target {
+                name: name.clone(),
+                language: config.language,
+                output: config.output.clone().unwrap_or_else(|| name.into()),
+                typescript: config
+                    .typescript
+                    .clone()
+                    .unwrap_or_else(|| self.typescript.clone()),
+            })
+            .collect()
+    }
+
+    /// Primary target language: `cli.language`, or the first `[targets]` entry.
+    ///
+    /// Validation guarantees one of them is set; Rust is assumed otherwise.
+    pub fn language(&self) -> Language {
+        self.cli
+            .language
+            .or_else(|| self.targets.values().next().map(|t| t.language))
+            .unwrap_or(Language::Rust)
+    }
+
+    /// The manifest as seen by a single target, with `cli.language` and
+    /// `[typescript]` taken from it.
+    pub fn for_target(&self, target: &Target) -> Manifest {
+        let mut manifest = self.clone();
+        manifest.cli.language = Some(target.language);
+        manifest.typescript = target.typescript.clone();
+        manifest
+    }
+
     /// Check if a command exists (supports nested paths like "users/create")
+    ///
+    /// Aliases are reserved like names, so a path whose last segment is an
+    /// alias of a command under the same parent also matches.
     pub fn has_command(&self, name: &str) -> bool {
-        let parts: Vec<&str> = name.split('/').collect();
-
-        if parts.len() == 1 {
-            return self.commands.contains_key(name);
-        }
+        let (parents, leaf) = match name.rsplit_once('/') {
+            Some((parents, leaf)) => (Some(parents), leaf),
+            None => (None, name),
+        };
 
         let mut current = &self.commands;
-        for (i, part) in parts.iter().enumerate() {
-            match current.get(*part) {
-                Some(cmd) if i == parts.len() - 1 => return true,
+        for part in parents.into_iter().flat_map(|parents| parents.split('/')) {
+            match current.get(part) {
                 Some(cmd) => current = &cmd.commands,
                 None => return false,
             }
         }
-        false
+        current.contains_key(leaf)
+            || current
+                .values()
+                .any(|cmd| cmd.aliases.iter().any(|alias| alias == leaf))
     }
 }
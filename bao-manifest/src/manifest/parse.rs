@@ -1,9 +1,19 @@
 //! Manifest parsing from files and strings.
 
-use std::{path::Path, str::FromStr};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use super::{Manifest, validate::ParseContext};
-use crate::{Error, Result, error::SourceContext};
+use baobao_core::path_key;
+use toml::{Spanned, Value, de::DeTable};
+
+use super::{Language, Manifest, ManifestFormat, SCHEMA_VERSION, extends, validate::ParseContext};
+use crate::{
+    CacheMode, Command, Description, Error, HttpAuth, Result, SecretsBackend,
+    command::{check_fields, validate_default_command, validate_global_flags},
+};
 
 impl FromStr for Manifest {
     type Err = Box<Error>;
@@ -15,6 +25,7 @@ impl FromStr for Manifest {
 
 impl Manifest {
     /// Parse a bao.toml file from the given path.
+    #[cfg(feature = "fs")]
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path).map_err(|e| {
@@ -23,33 +34,748 @@ impl Manifest {
                 source: e,
             })
         })?;
-        parse_manifest(&content, &path.display().to_string())
+        let format = ManifestFormat::from_path(path);
+        Self::from_str_with_format(&content, &path.display().to_string(), format)
     }
 
     /// Parse a bao.toml from a string with a custom filename for error reporting.
     pub fn from_str_with_filename(content: &str, filename: &str) -> Result<Self> {
         parse_manifest(content, filename)
     }
+
+    /// Parse a manifest in the given format, with a custom filename for
+    /// error reporting.
+    pub fn from_str_with_format(
+        content: &str,
+        filename: &str,
+        format: ManifestFormat,
+    ) -> Result<Self> {
+        parse_manifest(&format.to_toml(content, filename)?, filename)
+    }
 }
 
 /// Parse a manifest from content with the given filename for error reporting.
 pub fn parse_manifest(content: &str, filename: &str) -> Result<Manifest> {
-    let source_ctx = SourceContext::new(content, filename);
-    let manifest: Manifest = toml::from_str(content).map_err(|e| source_ctx.parse_error(e))?;
-    validate_manifest(&manifest, content, filename)?;
+    // Parsed once for source locations, unknown keys and `extends`; invalid
+    // TOML is reported by deserialization below
+    let document = DeTable::parse(content).ok();
+    let root = document.as_ref().map(Spanned::get_ref);
+    let ctx = ParseContext::from_document(content, filename, root);
+    let parsed = match extends::resolve(&ctx, root)? {
+        Some(merged) => Value::Table(merged).try_into(),
+        None => toml::from_str(content),
+    };
+    let mut manifest: Manifest = parsed.map_err(|e| ctx.source_context().parse_error(e))?;
+    if let Some(root) = root {
+        check_fields(&ctx, root)?;
+    }
+    validate_manifest(&manifest, &ctx)?;
+    resolve_descriptions(&mut manifest, &ctx)?;
+    load_header(&mut manifest, &ctx)?;
+    manifest.spans = ctx.into_spans();
     Ok(manifest)
 }
 
 /// Validate the manifest after parsing.
-fn validate_manifest(manifest: &Manifest, src: &str, filename: &str) -> Result<()> {
-    let ctx = ParseContext::new(src, filename);
+fn validate_manifest(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    validate_schema_version(manifest, ctx)?;
+    validate_targets(manifest, ctx)?;
+    validate_profiles(manifest, ctx)?;
+    validate_context(manifest, ctx)?;
+    validate_requires(manifest, ctx, &manifest.commands, "")?;
+
+    // Names become identifiers in every target language
+    let ctx = &ctx
+        .clone()
+        .with_languages(manifest.targets().into_iter().map(|t| t.language));
+
+    validate_global_flags(ctx, &manifest.flags, &manifest.commands)?;
+    validate_default_command(
+        ctx,
+        "the CLI",
+        manifest.cli.default_command.as_deref(),
+        &manifest.commands,
+        "cli.default_command",
+    )?;
 
     for (name, command) in &manifest.commands {
-        ctx.validate_name(name, "command")?;
+        ctx.validate_name_at(name, "command", &format!("commands.{}", name))?;
 
         // Create a context with the command name for nested validation
         let cmd_ctx = ctx.push(name);
         command.validate(&cmd_ctx)?;
     }
+
+    for (name, route) in &manifest.routes {
+        ctx.validate_name_at(name, "route", &format!("routes.{}", name))?;
+        route.validate(&ctx.push(name))?;
+    }
+    Ok(())
+}
+
+/// Select the default locale's text of every localized description.
+fn resolve_descriptions(manifest: &mut Manifest, ctx: &ParseContext) -> Result<()> {
+    let locale = manifest.cli.locale().to_string();
+    if let Some(description) = &mut manifest.cli.description {
+        resolve_description(description, &locale, "cli.description", "cli", ctx)?;
+    }
+    for (name, flag) in &mut manifest.flags {
+        if let Some(description) = &mut flag.description {
+            let key = format!("flags.{}.description", name);
+            resolve_description(description, &locale, &key, "flags", ctx)?;
+        }
+    }
+    for (name, command) in &mut manifest.commands {
+        resolve_command(command, &locale, &format!("commands.{}", name), ctx)?;
+    }
+    Ok(())
+}
+
+fn resolve_command(
+    command: &mut Command,
+    locale: &str,
+    key: &str,
+    ctx: &ParseContext,
+) -> Result<()> {
+    let description_key = format!("{}.description", key);
+    resolve_description(&mut command.description, locale, &description_key, key, ctx)?;
+    let inputs = command
+        .args
+        .iter_mut()
+        .map(|(name, arg)| (format!("{}.args.{}", key, name), &mut arg.description))
+        .chain(
+            command
+                .flags
+                .iter_mut()
+                .map(|(name, flag)| (format!("{}.flags.{}", key, name), &mut flag.description)),
+        );
+    for (input_key, description) in inputs {
+        if let Some(description) = description {
+            let description_key = format!("{}.description", input_key);
+            resolve_description(description, locale, &description_key, key, ctx)?;
+        }
+    }
+    for (name, child) in &mut command.commands {
+        resolve_command(child, locale, &format!("{}.commands.{}", key, name), ctx)?;
+    }
+    Ok(())
+}
+
+/// Resolve one description, labelling errors at `key` or, for array-format
+/// inputs that are not indexed by name, at `fallback`.
+fn resolve_description(
+    description: &mut Description,
+    locale: &str,
+    key: &str,
+    fallback: &str,
+    ctx: &ParseContext,
+) -> Result<()> {
+    if description.resolve(locale) {
+        return Ok(());
+    }
+    let span = ctx.value_span(key).or_else(|| ctx.key_span(fallback));
+    Err(ctx.validation_error(
+        format!(
+            "'{}' has no text in the default locale '{}'; add it or set 'locale' in [cli]",
+            key, locale
+        ),
+        span,
+    ))
+}
+
+/// Read `generation.header_file`, relative to the manifest.
+#[cfg(feature = "fs")]
+fn load_header(manifest: &mut Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(header_file) = &manifest.generation.header_file else {
+        return Ok(());
+    };
+    let dir = Path::new(ctx.filename()).parent().unwrap_or(Path::new(""));
+    let path = dir.join(header_file);
+    let header = std::fs::read_to_string(&path).map_err(|e| {
+        ctx.validation_error(
+            format!("cannot read header file '{}': {}", path.display(), e),
+            ctx.value_span("generation.header_file"),
+        )
+    })?;
+    manifest.generation.header = Some(header);
+    Ok(())
+}
+
+/// Without filesystem access `generation.header_file` cannot be read.
+#[cfg(not(feature = "fs"))]
+fn load_header(manifest: &mut Manifest, ctx: &ParseContext) -> Result<()> {
+    match &manifest.generation.header_file {
+        Some(header_file) => Err(ctx.validation_error(
+            format!(
+                "cannot read header file '{}': this build of bao cannot read files",
+                header_file
+            ),
+            ctx.value_span("generation.header_file"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Reject manifests whose `schema_version` is newer than [`SCHEMA_VERSION`].
+fn validate_schema_version(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    match manifest.schema_version {
+        Some(version) if version > SCHEMA_VERSION => Err(ctx.validation_error(
+            format!(
+                "schema_version {} is newer than the latest supported version {}; upgrade bao",
+                version, SCHEMA_VERSION
+            ),
+            ctx.value_span("schema_version"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Check that every profile's overrides apply to the manifest.
+fn validate_profiles(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    for (name, profile) in &manifest.profile {
+        if let Err(message) = manifest.apply_profile(profile) {
+            return Err(ctx.validation_error(
+                format!("profile '{}': {}", name, message),
+                ctx.key_span(&format!("profile.{}", name)),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check the `[context.http]` clients, `[context.storage]` bucket and
+/// `[context.grpc]` channel.
+fn validate_context(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    validate_duckdb(manifest, ctx)?;
+    validate_replica(manifest, ctx)?;
+    validate_http_clients(manifest, ctx)?;
+    validate_logging(manifest, ctx)?;
+    validate_storage(manifest, ctx)?;
+    validate_grpc(manifest, ctx)?;
+    validate_email(manifest, ctx)?;
+    validate_app_config(manifest, ctx)?;
+    validate_secrets(manifest, ctx)?;
+    validate_llm(manifest, ctx)?;
+    validate_cache(manifest, ctx)?;
+    validate_workdir(manifest, ctx)
+}
+
+/// Check that the `requires` of every command name `[context]` resources.
+fn validate_requires(
+    manifest: &Manifest,
+    ctx: &ParseContext,
+    commands: &HashMap<String, Command>,
+    parent: &str,
+) -> Result<()> {
+    for (name, command) in commands {
+        let path = if parent.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", parent, name)
+        };
+        for resource in &command.requires {
+            if !manifest.context.has_field(resource) {
+                let key = format!("commands.{}.requires", path.replace('.', ".commands."));
+                return Err(ctx.validation_error(
+                    format!(
+                        "requires of '{}' names '{}', which is not in [context]",
+                        path, resource
+                    ),
+                    ctx.value_span(&key),
+                ));
+            }
+        }
+        validate_requires(manifest, ctx, &command.commands, &path)?;
+    }
+    Ok(())
+}
+
+/// Check the `[context.logging]` filter directives.
+fn validate_logging(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(directive) = manifest
+        .context
+        .logging_config()
+        .and_then(|logging| logging.invalid_directive())
+    else {
+        return Ok(());
+    };
+    Err(ctx.validation_error(
+        format!(
+            "invalid directive '{}' in env_filter of [context.logging]; expected a level or target=level",
+            directive
+        ),
+        ctx.value_span("context.logging.env_filter"),
+    ))
+}
+
+/// Check the `[context.storage]` bucket and endpoint.
+fn validate_storage(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(s3) = manifest.context.s3_config() else {
+        return Ok(());
+    };
+    if s3.bucket.is_empty() {
+        return Err(ctx.validation_error(
+            "bucket in [context.storage] cannot be empty",
+            ctx.value_span("context.storage.bucket"),
+        ));
+    }
+    if let Some(endpoint) = &s3.endpoint
+        && !endpoint.starts_with("http://")
+        && !endpoint.starts_with("https://")
+    {
+        return Err(ctx.validation_error(
+            format!(
+                "endpoint '{}' in [context.storage] must start with http:// or https://",
+                endpoint
+            ),
+            ctx.value_span("context.storage.endpoint"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the `[context.grpc]` endpoint and proto files.
+fn validate_grpc(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(grpc) = manifest.context.grpc_config() else {
+        return Ok(());
+    };
+    if !grpc.endpoint.starts_with("http://") && !grpc.endpoint.starts_with("https://") {
+        return Err(ctx.validation_error(
+            format!(
+                "endpoint '{}' in [context.grpc] must start with http:// or https://",
+                grpc.endpoint
+            ),
+            ctx.value_span("context.grpc.endpoint"),
+        ));
+    }
+    if let Some(proto) = grpc.protos.iter().find(|proto| !proto.ends_with(".proto")) {
+        return Err(ctx.validation_error(
+            format!("'{}' in [context.grpc] protos is not a .proto file", proto),
+            ctx.value_span("context.grpc.protos"),
+        ));
+    }
     Ok(())
 }
+
+/// Check the `[context.email]` host and credentials.
+fn validate_email(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(email) = manifest.context.email_config() else {
+        return Ok(());
+    };
+    if email.host.is_empty() {
+        return Err(ctx.validation_error(
+            "host in [context.email] cannot be empty",
+            ctx.value_span("context.email.host"),
+        ));
+    }
+    let key = match (&email.username_env, &email.password_env) {
+        (Some(_), None) => "username_env",
+        (None, Some(_)) => "password_env",
+        _ => return Ok(()),
+    };
+    Err(ctx.validation_error(
+        "[context.email] needs both username_env and password_env to authenticate",
+        ctx.value_span(&format!("context.email.{}", key)),
+    ))
+}
+
+/// Check the `[context.config]` format and field names.
+fn validate_app_config(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(config) = manifest.context.app_config() else {
+        return Ok(());
+    };
+    if config.resolved_format().is_none() {
+        return Err(ctx.validation_error(
+            format!(
+                "cannot infer the format of '{}' in [context.config]; set format to toml, json or yaml",
+                config.path
+            ),
+            ctx.value_span("context.config.path"),
+        ));
+    }
+    for name in config.fields.keys() {
+        ctx.validate_name_at(
+            name,
+            "config field",
+            &format!("context.config.fields.{}", name),
+        )?;
+    }
+    Ok(())
+}
+
+/// Check that `[context.secrets]` only sets the options of its backend.
+fn validate_secrets(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(secrets) = manifest.context.secrets_config() else {
+        return Ok(());
+    };
+    let (key, backend) = match secrets.backend {
+        SecretsBackend::File if secrets.service.is_some() => ("service", SecretsBackend::Keyring),
+        SecretsBackend::Keyring | SecretsBackend::Env if secrets.path.is_some() => {
+            ("path", SecretsBackend::File)
+        }
+        SecretsBackend::Env if secrets.service.is_some() => ("service", SecretsBackend::Keyring),
+        _ => return Ok(()),
+    };
+    Err(ctx.validation_error(
+        format!(
+            "{} in [context.secrets] only applies to backend = \"{}\"",
+            key,
+            backend.as_str()
+        ),
+        ctx.value_span(&format!("context.secrets.{}", key)),
+    ))
+}
+
+/// Check the `[context.llm]` model, base URL and token limit.
+fn validate_llm(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(llm) = manifest.context.llm_config() else {
+        return Ok(());
+    };
+    if llm.model.is_empty() {
+        return Err(ctx.validation_error(
+            "model in [context.llm] cannot be empty",
+            ctx.value_span("context.llm.model"),
+        ));
+    }
+    if let Some(base_url) = &llm.base_url
+        && !base_url.starts_with("http://")
+        && !base_url.starts_with("https://")
+    {
+        return Err(ctx.validation_error(
+            format!(
+                "base_url '{}' in [context.llm] must start with http:// or https://",
+                base_url
+            ),
+            ctx.value_span("context.llm.base_url"),
+        ));
+    }
+    if llm.max_tokens == Some(0) {
+        return Err(ctx.validation_error(
+            "max_tokens in [context.llm] must be greater than 0",
+            ctx.value_span("context.llm.max_tokens"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the DuckDB thread count of `[context.database]`.
+fn validate_duckdb(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(duckdb) = manifest
+        .context
+        .database
+        .as_ref()
+        .and_then(|db| db.duckdb_config())
+    else {
+        return Ok(());
+    };
+    if duckdb.threads == Some(0) {
+        return Err(ctx.validation_error(
+            "threads in [context.database] must be greater than 0",
+            ctx.value_span("context.database.threads"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `[context.database.replica]` belongs to a server database and
+/// reads a different connection string than the primary.
+fn validate_replica(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(db) = manifest.context.database.as_ref() else {
+        return Ok(());
+    };
+    let Some(replica) = db.replica_config() else {
+        // SQLite and DuckDB configs don't read the table, so look for its key
+        if let Some(span) = ctx.key_span("context.database.replica") {
+            return Err(ctx.validation_error(
+                format!(
+                    "replica in [context.database] is only supported for postgres and mysql, not {}",
+                    db.type_name()
+                ),
+                Some(span),
+            ));
+        }
+        return Ok(());
+    };
+    let primary = db.env().unwrap_or("DATABASE_URL");
+    let env = replica.env.as_deref().unwrap_or("DATABASE_REPLICA_URL");
+    if env == primary {
+        return Err(ctx.validation_error(
+            format!(
+                "[context.database.replica] reads {}, the connection string of the primary",
+                env
+            ),
+            ctx.value_span("context.database.replica.env"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the `[context.cache]` limits and that `path` is only set for disk caches.
+fn validate_cache(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(cache) = manifest.context.cache_config() else {
+        return Ok(());
+    };
+    for (key, value) in [("max_capacity", cache.max_capacity), ("ttl", cache.ttl)] {
+        if value == Some(0) {
+            return Err(ctx.validation_error(
+                format!("{} in [context.cache] must be greater than 0", key),
+                ctx.value_span(&format!("context.cache.{}", key)),
+            ));
+        }
+    }
+    if cache.path.is_some() && cache.mode != CacheMode::Disk {
+        return Err(ctx.validation_error(
+            "path in [context.cache] only applies to mode = \"disk\"",
+            ctx.value_span("context.cache.path"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `[context.workdir]` keeps at least one directory and that
+/// `path` is only set for the data directory.
+fn validate_workdir(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    let Some(workdir) = manifest.context.workdir_config() else {
+        return Ok(());
+    };
+    if workdir.has_data() {
+        return Ok(());
+    }
+    if workdir.path.is_some() {
+        return Err(ctx.validation_error(
+            "path in [context.workdir] only applies when data is kept",
+            ctx.value_span("context.workdir.path"),
+        ));
+    }
+    if !workdir.temp {
+        return Err(ctx.validation_error(
+            "[context.workdir] turns off both data and temp",
+            ctx.value_span("context.workdir.data"),
+        ));
+    }
+    Ok(())
+}
+
+/// Context fields generated for other resources, which named HTTP clients
+/// cannot reuse.
+const RESERVED_CONTEXT_FIELDS: &[&str] = &[
+    "db",
+    "db_replica",
+    "http",
+    "logger",
+    "storage",
+    "grpc",
+    "queue",
+    "email",
+    "config",
+    "secrets",
+    "llm",
+    "cache",
+    "workdir",
+    "globals",
+];
+
+/// Check HTTP client names, base URLs, retry settings, default headers and
+/// auth headers.
+fn validate_http_clients(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    for name in manifest.context.http_clients.keys() {
+        let key = format!("context.http.{}", name);
+        if RESERVED_CONTEXT_FIELDS.contains(&name.as_str()) {
+            return Err(ctx.validation_error(
+                format!(
+                    "HTTP client '{}' conflicts with the built-in context field of the same name",
+                    name
+                ),
+                ctx.key_span(&key),
+            ));
+        }
+        ctx.validate_name_at(name, "HTTP client", &key)?;
+    }
+    for (name, config) in manifest.context.http_configs() {
+        let key = if manifest.context.http_clients.contains_key(name) {
+            format!("context.http.{}", name)
+        } else {
+            "context.http".to_string()
+        };
+        if let Some(base_url) = &config.base_url
+            && !base_url.starts_with("http://")
+            && !base_url.starts_with("https://")
+        {
+            return Err(ctx.validation_error(
+                format!(
+                    "base_url '{}' in [{}] must start with http:// or https://",
+                    base_url, key
+                ),
+                ctx.value_span(&format!("{}.base_url", key)),
+            ));
+        }
+        if config.retries.is_none() {
+            let setting = if config.backoff_ms.is_some() {
+                Some("backoff_ms")
+            } else if !config.retry_on.is_empty() {
+                Some("retry_on")
+            } else {
+                None
+            };
+            if let Some(setting) = setting {
+                return Err(ctx.validation_error(
+                    format!("{} in [{}] has no effect without retries", setting, key),
+                    ctx.key_span(&format!("{}.{}", key, setting)),
+                ));
+            }
+        }
+        if let Some(status) = config
+            .retry_on
+            .iter()
+            .find(|status| !(100..=599).contains(*status))
+        {
+            return Err(ctx.validation_error(
+                format!(
+                    "retry_on in [{}] contains {}, which is not an HTTP status",
+                    key, status
+                ),
+                ctx.value_span(&format!("{}.retry_on", key)),
+            ));
+        }
+        for (header, value) in &config.headers {
+            let header_key = format!("{}.headers.{}", key, header);
+            if header.is_empty() || !header.bytes().all(is_header_name_byte) {
+                return Err(ctx.validation_error(
+                    format!("'{}' in [{}] is not a valid header name", header, key),
+                    ctx.key_span(&header_key),
+                ));
+            }
+            if !value
+                .bytes()
+                .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+            {
+                return Err(ctx.validation_error(
+                    format!(
+                        "value of header '{}' in [{}] must be printable ASCII",
+                        header, key
+                    ),
+                    ctx.value_span(&header_key),
+                ));
+            }
+        }
+        if let Some(auth) = &config.auth {
+            let auth_key = format!("{}.auth", key);
+            let header = match auth {
+                HttpAuth::Bearer { .. } | HttpAuth::Basic { .. } => "Authorization",
+                HttpAuth::Header { name, .. } => name,
+            };
+            if header.is_empty() || !header.bytes().all(is_header_name_byte) {
+                return Err(ctx.validation_error(
+                    format!("'{}' in [{}] is not a valid header name", header, auth_key),
+                    ctx.value_span(&format!("{}.name", auth_key)),
+                ));
+            }
+            if let Some(name) = config
+                .headers
+                .keys()
+                .find(|name| name.eq_ignore_ascii_case(header))
+            {
+                return Err(ctx.validation_error(
+                    format!(
+                        "header '{}' in [{}] is already set by auth; remove it from headers",
+                        name, key
+                    ),
+                    ctx.key_span(&format!("{}.headers.{}", key, name)),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `b` can appear in a header name (an RFC 9110 token).
+fn is_header_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Validate `cli.language` and the `[targets]` section.
+fn validate_targets(manifest: &Manifest, ctx: &ParseContext) -> Result<()> {
+    match (manifest.cli.language, manifest.targets.is_empty()) {
+        (None, true) => {
+            return Err(ctx.validation_error(
+                "missing target language: set 'language' in [cli] or add a [targets] section",
+                ctx.key_span("cli"),
+            ));
+        }
+        (Some(_), false) => {
+            return Err(ctx.validation_error(
+                "'language' in [cli] cannot be combined with [targets]; set the language on each target instead",
+                ctx.key_span("cli.language"),
+            ));
+        }
+        _ => {}
+    }
+
+    let mut outputs: Vec<(Vec<String>, &str, PathBuf)> = Vec::new();
+    for (name, target) in &manifest.targets {
+        if target.typescript.is_some() && target.language != Language::TypeScript {
+            return Err(ctx.validation_error(
+                format!(
+                    "[targets.{}.typescript] only applies to typescript targets",
+                    name
+                ),
+                ctx.key_span(&format!("targets.{}.typescript", name)),
+            ));
+        }
+        let output = target.output.clone().unwrap_or_else(|| name.into());
+        let key = output_key(&output);
+        for (other_key, other, other_output) in &outputs {
+            let message = if key == *other_key {
+                format!(
+                    "targets '{}' and '{}' both write to '{}'",
+                    other,
+                    name,
+                    output.display()
+                )
+            } else if key.starts_with(other_key) {
+                format!(
+                    "target '{}' writes to '{}', inside the output of target '{}' ('{}')",
+                    name,
+                    output.display(),
+                    other,
+                    other_output.display()
+                )
+            } else if other_key.starts_with(&key) {
+                format!(
+                    "target '{}' writes to '{}', which contains the output of target '{}' ('{}')",
+                    name,
+                    output.display(),
+                    other,
+                    other_output.display()
+                )
+            } else {
+                continue;
+            };
+            let span = ctx
+                .value_span(&format!("targets.{}.output", name))
+                .or_else(|| ctx.key_span(&format!("targets.{}", name)));
+            return Err(ctx.validation_error(message, span));
+        }
+        outputs.push((key, name, output));
+    }
+    Ok(())
+}
+
+/// Lexically normalized path segments of a target output, so `./out`,
+/// `out/` and `x/../out` all compare equal.
+fn output_key(output: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    if output.has_root() {
+        segments.push("/".to_string());
+    }
+    let normalized = path_key(&output.to_string_lossy());
+    for segment in normalized.split('/').filter(|s| !s.is_empty()) {
+        match segment {
+            ".." if segments.last().is_some_and(|s| s != ".." && s != "/") => {
+                segments.pop();
+            }
+            _ => segments.push(segment.to_string()),
+        }
+    }
+    segments
+}
@@ -0,0 +1,151 @@
+//! Profiles selecting alternative settings at bake time.
+//!
+//! A `[profile.<name>]` table overrides `[cli]` metadata and `[context]`
+//! settings, such as a smaller pool or a local SQLite file for development:
+//!
+//! ```toml
+//! [profile.dev.context.database]
+//! path = "dev.db"
+//! ```
+//!
+//! Overrides are merged key by key like `extends`, so a profile only lists
+//! the settings it changes. `bao bake --profile dev` applies one.
+
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use super::{Manifest, extends::merge};
+use crate::{CliConfig, Result, error::SourceContext, serialize::SerializableCliConfig};
+
+/// Keys allowed in a profile table.
+pub(crate) const PROFILE_FIELDS: &[&str] = &["cli", "context"];
+
+/// `[cli]` keys a profile can override.
+pub(crate) const PROFILE_CLI_FIELDS: &[&str] = &["name", "version", "description", "author"];
+
+/// Overrides applied when a profile is selected (`[profile.dev]`)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Profile {
+    /// Overrides of `[cli]` metadata
+    #[serde(default, skip_serializing_if = "Table::is_empty")]
+    pub cli: Table,
+
+    /// Overrides of `[context]` settings
+    #[serde(default, skip_serializing_if = "Table::is_empty")]
+    pub context: Table,
+}
+
+impl Manifest {
+    /// The manifest with the overrides of the named profile applied.
+    pub fn with_profile(&self, name: &str) -> Result<Manifest> {
+        let source = SourceContext::new(String::new(), "bao.toml");
+        let Some(profile) = self.profile.get(name) else {
+            let message = if self.profile.is_empty() {
+                format!("unknown profile '{}': the manifest has no profiles", name)
+            } else {
+                let names: Vec<_> = self.profile.keys().map(String::as_str).collect();
+                format!(
+                    "unknown profile '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                )
+            };
+            return Err(source.validation_error(message));
+        };
+        self.apply_profile(profile)
+            .map_err(|e| source.validation_error(format!("profile '{}': {}", name, e)))
+    }
+
+    /// Merge a profile's overrides into a copy of the manifest.
+    pub(crate) fn apply_profile(&self, profile: &Profile) -> std::result::Result<Manifest, String> {
+        let mut manifest = self.clone();
+        if !profile.cli.is_empty() {
+            let cli = Table::try_from(SerializableCliConfig::from(&self.cli))
+                .map_err(|e| e.to_string())?;
+            manifest.cli = CliConfig::deserialize(Value::Table(merge(cli, profile.cli.clone())))
+                .map_err(|e| format!("invalid [cli] override: {}", e.message()))?;
+        }
+        if !profile.context.is_empty() {
+            let context = Table::try_from(&self.context).map_err(|e| e.to_string())?;
+            manifest.context =
+                crate::context::deserialize(Value::Table(merge(context, profile.context.clone())))
+                    .map_err(|e| format!("invalid [context] override: {}", e.message()))?;
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{ContextField, Manifest};
+
+    const MANIFEST: &str = r#"
+        [cli]
+        name = "app"
+        version = "1.2.0"
+        description = "My app"
+        language = "rust"
+
+        [context.database]
+        type = "sqlite"
+        path = "app.db"
+        max_connections = 10
+
+        [profile.dev]
+        cli = { name = "app-dev" }
+
+        [profile.dev.context.database]
+        path = "dev.db"
+        max_connections = 1
+
+        [profile.release]
+        "#;
+
+    #[test]
+    fn test_with_profile() {
+        let manifest = Manifest::from_str(MANIFEST).unwrap();
+        let dev = manifest.with_profile("dev").unwrap();
+        assert_eq!(dev.cli.name, "app-dev");
+        assert_eq!(dev.cli.version.to_string(), "1.2.0");
+        let Some(ContextField::Sqlite(sqlite)) = &dev.context.database else {
+            panic!("expected a sqlite database");
+        };
+        assert_eq!(sqlite.path.as_deref(), Some("dev.db"));
+        assert_eq!(sqlite.pool.max_connections, Some(1));
+
+        let release = manifest.with_profile("release").unwrap();
+        assert_eq!(release.cli.name, "app");
+
+        let err = manifest.with_profile("staging").unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "unknown profile 'staging', expected one of: dev, release"
+        );
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        let parse = |profile: &str| {
+            Manifest::from_str(&format!(
+                "[cli]\nname = \"app\"\nlanguage = \"rust\"\n\n{}",
+                profile
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+        let err = parse("[profile.dev.cli]\nlanguage = \"go\"\n");
+        assert!(
+            err.contains("unknown field 'language' in 'cli' of profile 'dev'"),
+            "{}",
+            err
+        );
+        let err = parse("[profile.dev]\ncontext = { http = { timeout = \"soon\" } }\n");
+        assert!(
+            err.contains("profile 'dev': invalid [context] override"),
+            "{}",
+            err
+        );
+    }
+}
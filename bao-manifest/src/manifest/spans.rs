@@ -0,0 +1,405 @@
+//! Source locations of manifest entries, and the lints allowed by
+//! `# bao: allow(...)` comments.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use baobao_ir::SourceSpan;
+use toml::{
+    Spanned,
+    de::{DeTable, DeValue},
+};
+
+/// Byte ranges of the keys and values of a parsed manifest.
+///
+/// Entries are keyed by their dotted TOML path, such as
+/// `commands.db.commands.migrate` or `commands.db.flags.dir`. Args and flags
+/// declared in array format (`[[commands.db.args]]`) are keyed by their
+/// `name` and point at it.
+///
+/// A `# bao: allow(lint, ...)` comment above a table header, or after it on
+/// the same line, allows those lints for everything up to the next header.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    file: String,
+    src: String,
+    /// Byte offset of the start of every line, to locate spans without
+    /// rescanning the source
+    line_starts: Vec<usize>,
+    entries: HashMap<String, Entry>,
+    /// Values spanning several lines, whose lines are neither comments nor
+    /// headers
+    multiline_values: Vec<Range<usize>>,
+    allows: Vec<Allow>,
+}
+
+/// Lints allowed in one table, from the header to the next one.
+#[derive(Debug, Clone)]
+struct Allow {
+    table: Range<usize>,
+    /// Lint names with their position in the comment
+    lints: Vec<(String, Range<usize>)>,
+}
+
+/// Byte ranges of one key and, unless it is an array-format `name`, its value.
+#[derive(Debug, Clone)]
+struct Entry {
+    key: Range<usize>,
+    value: Option<Range<usize>>,
+}
+
+impl SourceMap {
+    /// Index the keys of a TOML document. Invalid TOML yields an empty map.
+    pub fn parse(src: &str, file: &str) -> Self {
+        let document = DeTable::parse(src).ok();
+        Self::from_document(src, file, document.as_ref().map(Spanned::get_ref))
+    }
+
+    /// Index the keys of a document already parsed from `src`.
+    pub(crate) fn from_document(src: &str, file: &str, root: Option<&DeTable<'_>>) -> Self {
+        let mut map = Self {
+            file: file.to_string(),
+            src: src.to_string(),
+            line_starts: std::iter::once(0)
+                .chain(src.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+            entries: HashMap::new(),
+            multiline_values: Vec::new(),
+            allows: Vec::new(),
+        };
+        if let Some(root) = root {
+            map.index_table("", root);
+            map.index_allows();
+        }
+        map
+    }
+
+    fn index_table(&mut self, prefix: &str, table: &DeTable<'_>) {
+        for (key, value) in table.iter() {
+            let path = join(prefix, key.get_ref());
+            let span = value.span();
+            let is_tables = match value.get_ref() {
+                DeValue::Table(_) => true,
+                DeValue::Array(items) => items
+                    .iter()
+                    .any(|item| matches!(item.get_ref(), DeValue::Table(_))),
+                _ => false,
+            };
+            if !is_tables && self.src.get(span.clone()).is_some_and(|v| v.contains('\n')) {
+                self.multiline_values.push(span);
+            }
+            self.index_value(&path, value);
+            self.entries.entry(path).or_insert(Entry {
+                key: key.span(),
+                value: Some(value.span()),
+            });
+        }
+    }
+
+    fn index_value(&mut self, path: &str, value: &Spanned<DeValue<'_>>) {
+        match value.get_ref() {
+            DeValue::Table(table) => self.index_table(path, table),
+            DeValue::Array(items) => {
+                for item in items.iter() {
+                    let DeValue::Table(table) = item.get_ref() else {
+                        continue;
+                    };
+                    let name = table.iter().find_map(|(key, value)| match value.get_ref() {
+                        DeValue::String(name) if key.get_ref() == "name" => {
+                            Some((name, value.span()))
+                        }
+                        _ => None,
+                    });
+                    if let Some((name, span)) = name {
+                        let entry = join(path, name);
+                        // Point inside the quotes
+                        let span = span.start + 1..span.end.saturating_sub(1).max(span.start + 1);
+                        self.index_table(&entry, table);
+                        self.entries.entry(entry).or_insert(Entry {
+                            key: span,
+                            value: None,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Find the tables each `# bao: allow(...)` comment applies to.
+    fn index_allows(&mut self) {
+        let key_starts: HashSet<usize> = self.entries.values().map(|e| e.key.start).collect();
+        let mut pending = Vec::new();
+        let mut current: Option<Allow> = None;
+
+        for (i, &start) in self.line_starts.iter().enumerate() {
+            let end = self
+                .line_starts
+                .get(i + 1)
+                .map_or(self.src.len(), |next| next - 1);
+            if self
+                .multiline_values
+                .iter()
+                .any(|value| value.start < start && start < value.end)
+            {
+                continue;
+            }
+            let line = &self.src[start..end];
+            let trimmed = line.trim_start();
+
+            if trimmed.starts_with('#') {
+                pending.extend(parse_allow(line, start));
+            } else if trimmed.starts_with('[') && (start..end).any(|o| key_starts.contains(&o)) {
+                if let Some(mut allow) = current.take() {
+                    allow.table.end = start;
+                    self.allows.push(allow);
+                }
+                // A comment may follow the header on the same line
+                if let Some(close) = line.rfind(']') {
+                    pending.extend(parse_allow(&line[close + 1..], start + close + 1));
+                }
+                if !pending.is_empty() {
+                    current = Some(Allow {
+                        table: start..self.src.len(),
+                        lints: std::mem::take(&mut pending),
+                    });
+                }
+            }
+        }
+        self.allows.extend(current);
+    }
+
+    /// Whether `lint` is allowed in the table containing a byte offset.
+    pub fn is_allowed(&self, lint: &str, offset: usize) -> bool {
+        self.allows.iter().any(|allow| {
+            allow.table.contains(&offset) && allow.lints.iter().any(|(name, _)| name == lint)
+        })
+    }
+
+    /// Lint names in `# bao: allow(...)` comments that apply to a table, with
+    /// their spans.
+    pub fn allowed_lints(&self) -> Vec<(&str, SourceSpan)> {
+        self.allows
+            .iter()
+            .flat_map(|allow| &allow.lints)
+            .map(|(name, range)| (name.as_str(), self.span(range)))
+            .collect()
+    }
+
+    /// Span of a dotted TOML path.
+    pub fn get(&self, path: &str) -> Option<SourceSpan> {
+        Some(self.span(&self.entries.get(path)?.key))
+    }
+
+    fn span(&self, range: &Range<usize>) -> SourceSpan {
+        let start = range.start.min(self.src.len());
+        let line = self.line_starts.partition_point(|&s| s <= start).max(1);
+        let line_start = self.line_starts.get(line - 1).copied().unwrap_or(0);
+        SourceSpan {
+            file: self.file.clone(),
+            start: range.start,
+            end: range.end,
+            line,
+            column: self.src[line_start..start].chars().count() + 1,
+        }
+    }
+
+    /// Byte range of the key at a dotted TOML path.
+    pub fn key_range(&self, path: &str) -> Option<Range<usize>> {
+        self.entries.get(path).map(|entry| entry.key.clone())
+    }
+
+    /// Byte range of the value at a dotted TOML path.
+    pub fn value_range(&self, path: &str) -> Option<Range<usize>> {
+        self.entries.get(path)?.value.clone()
+    }
+
+    /// Span of a command's name, given its path from the root.
+    pub fn command(&self, path: &[String]) -> Option<SourceSpan> {
+        self.get(&command_key(path))
+    }
+
+    /// Span of a positional argument of a command.
+    pub fn arg(&self, command: &[String], name: &str) -> Option<SourceSpan> {
+        self.get(&format!("{}.args.{}", command_key(command), name))
+    }
+
+    /// Span of a flag of a command.
+    pub fn flag(&self, command: &[String], name: &str) -> Option<SourceSpan> {
+        self.get(&format!("{}.flags.{}", command_key(command), name))
+    }
+
+    /// Span of a global flag.
+    pub fn global_flag(&self, name: &str) -> Option<SourceSpan> {
+        self.get(&format!("flags.{}", name))
+    }
+
+    /// Span of a route's name.
+    pub fn route(&self, name: &str) -> Option<SourceSpan> {
+        self.get(&format!("routes.{}", name))
+    }
+
+    /// Span of a route parameter.
+    pub fn param(&self, route: &str, name: &str) -> Option<SourceSpan> {
+        self.get(&format!("routes.{}.params.{}", route, name))
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Lint names in a `# bao: allow(a, b)` comment found in `text`, which starts
+/// at byte `offset` of the source.
+fn parse_allow(text: &str, offset: usize) -> Vec<(String, Range<usize>)> {
+    let Some(hash) = text.find('#') else {
+        return Vec::new();
+    };
+    let comment = &text[hash + 1..];
+    let Some(args) = comment
+        .trim_start()
+        .strip_prefix("bao:")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix("allow("))
+    else {
+        return Vec::new();
+    };
+    let Some(close) = args.find(')') else {
+        return Vec::new();
+    };
+    let mut start = offset + hash + 1 + (comment.len() - args.len());
+    let mut lints = Vec::new();
+    for name in args[..close].split(',') {
+        let trimmed = name.trim();
+        if !trimmed.is_empty() {
+            let leading = name.len() - name.trim_start().len();
+            lints.push((
+                trimmed.to_string(),
+                start + leading..start + leading + trimmed.len(),
+            ));
+        }
+        start += name.len() + 1;
+    }
+    lints
+}
+
+/// `["db", "migrate"]` -> `commands.db.commands.migrate`.
+pub(crate) fn command_key(path: &[impl AsRef<str>]) -> String {
+    path.iter()
+        .map(|name| format!("commands.{}", name.as_ref()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = r#"[cli]
+name = "app"
+
+[commands.db]
+description = "Database"
+
+[commands.db.commands.migrate]
+description = "Migrate"
+flags = { dry_run = { type = "bool" } }
+
+[[commands.db.commands.migrate.args]]
+name = "steps"
+type = "int"
+
+[routes.get_user]
+method = "GET"
+path = "/users/{id}"
+params = { id = { type = "int" } }
+"#;
+
+    fn text(span: &SourceSpan) -> &str {
+        &SRC[span.start..span.end]
+    }
+
+    #[test]
+    fn test_commands_and_inputs() {
+        let map = SourceMap::parse(SRC, "bao.toml");
+        let path = ["db".to_string(), "migrate".to_string()];
+
+        let migrate = map.command(&path).unwrap();
+        assert_eq!(text(&migrate), "migrate");
+        assert_eq!((migrate.line, migrate.column), (7, 23));
+
+        assert_eq!(text(&map.flag(&path, "dry_run").unwrap()), "dry_run");
+        let steps = map.arg(&path, "steps").unwrap();
+        assert_eq!(text(&steps), "steps");
+        assert_eq!(steps.line, 12);
+        assert!(map.arg(&path, "missing").is_none());
+    }
+
+    #[test]
+    fn test_values() {
+        let map = SourceMap::parse(SRC, "bao.toml");
+        let range = map
+            .value_range("commands.db.commands.migrate.description")
+            .unwrap();
+        assert_eq!(&SRC[range], "\"Migrate\"");
+        let range = map.key_range("routes.get_user.method").unwrap();
+        assert_eq!(&SRC[range], "method");
+    }
+
+    #[test]
+    fn test_routes() {
+        let map = SourceMap::parse(SRC, "bao.toml");
+        assert_eq!(text(&map.route("get_user").unwrap()), "get_user");
+        assert_eq!(text(&map.param("get_user", "id").unwrap()), "id");
+    }
+
+    #[test]
+    fn test_allow_comments() {
+        let src = r#"# bao: allow(command-naming)
+[commands.db_tools]
+description = ""
+
+# bao: allow(empty-description, command-naming)
+[commands.deploy]
+description = """
+[commands.fake]
+# bao: allow(not-a-header)
+"""
+
+[commands.build] # bao: allow( empty-description )
+description = ""
+
+[commands.test]
+description = ""
+"#;
+        let map = SourceMap::parse(src, "bao.toml");
+        let at = |path: &str| map.key_range(path).unwrap().start;
+
+        assert!(map.is_allowed("command-naming", at("commands.db_tools")));
+        assert!(!map.is_allowed("empty-description", at("commands.db_tools")));
+        assert!(map.is_allowed("empty-description", at("commands.deploy")));
+        assert!(map.is_allowed("command-naming", at("commands.deploy.description")));
+        assert!(map.is_allowed("empty-description", at("commands.build")));
+        assert!(!map.is_allowed("empty-description", at("commands.test")));
+
+        let names: Vec<&str> = map.allowed_lints().iter().map(|(n, _)| *n).collect();
+        assert_eq!(
+            names,
+            [
+                "command-naming",
+                "empty-description",
+                "command-naming",
+                "empty-description"
+            ]
+        );
+        let (_, span) = &map.allowed_lints()[3];
+        assert_eq!(&src[span.start..span.end], "empty-description");
+        assert_eq!(span.line, 12);
+    }
+}
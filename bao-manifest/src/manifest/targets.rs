@@ -0,0 +1,270 @@
+//! Generation targets from the `[targets]` section.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{Language, TypeScriptConfig};
+
+/// A named generation target from the `[targets]` section.
+///
+/// Each target bakes the same commands into its own output directory, so one
+/// manifest can produce CLIs in several languages. `[targets]` replaces
+/// `cli.language`:
+///
+/// ```toml
+/// [targets.native]
+/// language = "rust"
+///
+/// [targets.node]
+/// language = "typescript"
+/// output = "packages/cli"
+///
+/// [targets.node.typescript]
+/// module = "cjs"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TargetConfig {
+    /// Language generated for this target
+    pub language: Language,
+
+    /// Output directory relative to the bake output (default: the target name)
+    pub output: Option<PathBuf>,
+
+    /// TypeScript options, replacing the top-level `[typescript]` section
+    pub typescript: Option<TypeScriptConfig>,
+}
+
+/// A resolved generation target: a `[targets]` entry, or the implicit
+/// target built from `cli.language`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    /// Target name (the language name for the implicit target)
+    pub name: String,
+    /// Language generated for this target
+    pub language: Language,
+    /// Output directory relative to the bake output (empty for the implicit target)
+    pub output: PathBuf,
+    /// TypeScript options in effect for this target
+    pub typescript: TypeScriptConfig,
+}
+
+impl Target {
+    /// The single target used when no `[targets]` section is present.
+    pub fn implicit(language: Language, typescript: TypeScriptConfig) -> Self {
+        Self {
+            name: language.as_str().to_string(),
+            language,
+            output: PathBuf::new(),
+            typescript,
+        }
+    }
+
+    /// Resolve the output directory of this target under `base`.
+    pub fn output_dir(&self, base: &Path) -> PathBuf {
+        if self.output.as_os_str().is_empty() {
+            base.to_path_buf()
+        } else {
+            base.join(&self.output)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{Manifest, ModuleFormat};
+
+    #[test]
+    fn test_implicit_target() {
+        let manifest = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            "#,
+        )
+        .unwrap();
+
+        let targets = manifest.targets();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].language, Language::Rust);
+        assert_eq!(targets[0].output_dir(Path::new("out")), Path::new("out"));
+    }
+
+    #[test]
+    fn test_explicit_targets() {
+        let manifest = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+
+            [targets.node]
+            language = "typescript"
+            output = "packages/cli"
+
+            [targets.node.typescript]
+            module = "cjs"
+            "#,
+        )
+        .unwrap();
+
+        let targets = manifest.targets();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].name, "native");
+        assert_eq!(
+            targets[0].output_dir(Path::new("out")),
+            Path::new("out/native")
+        );
+        assert_eq!(targets[1].name, "node");
+        assert_eq!(
+            targets[1].output_dir(Path::new("out")),
+            Path::new("out/packages/cli")
+        );
+
+        let node = manifest.for_target(&targets[1]);
+        assert_eq!(node.cli.language, Some(Language::TypeScript));
+        assert_eq!(node.typescript.module, ModuleFormat::Cjs);
+    }
+
+    #[test]
+    fn test_missing_language() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("missing target language"));
+    }
+
+    #[test]
+    fn test_language_with_targets() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+
+            [targets.node]
+            language = "typescript"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot be combined with [targets]")
+        );
+    }
+
+    #[test]
+    fn test_duplicate_target_output() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+            output = "cli"
+
+            [targets.node]
+            language = "typescript"
+            output = "cli"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("both write to 'cli'"));
+    }
+
+    #[test]
+    fn test_duplicate_target_output_normalized() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+            output = "./cli"
+
+            [targets.node]
+            language = "typescript"
+            output = "cli/"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("both write to 'cli/'"));
+    }
+
+    #[test]
+    fn test_nested_target_output() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+            output = "cli"
+
+            [targets.node]
+            language = "typescript"
+            output = "cli/node"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "target 'node' writes to 'cli/node', inside the output of target 'native'"
+            )
+        );
+
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+            output = "."
+
+            [targets.node]
+            language = "typescript"
+            output = "node"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("inside the output of target 'native'")
+        );
+    }
+
+    #[test]
+    fn test_typescript_options_on_rust_target() {
+        let err = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+
+            [targets.native]
+            language = "rust"
+
+            [targets.native.typescript]
+            module = "cjs"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("[targets.native.typescript] only applies to typescript targets")
+        );
+    }
+}
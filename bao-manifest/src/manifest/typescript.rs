@@ -0,0 +1,146 @@
+//! TypeScript-specific generation options.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Options for the TypeScript backend from the `[typescript]` section.
+///
+/// Compiler options are rendered into `tsconfig.json` when it is first
+/// created; unset options keep the generator defaults.
+///
+/// ```toml
+/// [typescript]
+/// progress = true
+/// docker = true
+/// import_extension = "js"
+/// module = "cjs"
+/// flag_case = "preserve"
+/// target = "ES2022"
+/// module_resolution = "node"
+/// no_unused_locals = false
+///
+/// [typescript.paths]
+/// "@/*" = ["./src/*"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct TypeScriptConfig {
+    /// Generate a `src/progress.ts` spinner module for long-running handlers
+    #[serde(default)]
+    pub progress: bool,
+
+    /// Generate a `Dockerfile` that compiles the CLI into a standalone binary
+    #[serde(default)]
+    pub docker: bool,
+
+    /// Extension used for relative imports in generated code
+    #[serde(default)]
+    pub import_extension: ImportExtension,
+
+    /// Module format of the generated package (default: "esm")
+    #[serde(default)]
+    pub module: ModuleFormat,
+
+    /// Naming of option keys and command-line flags (default: "camel")
+    #[serde(default)]
+    pub flag_case: FlagCase,
+
+    /// `compilerOptions.target` (default: "ESNext")
+    pub target: Option<String>,
+
+    /// `compilerOptions.moduleResolution` (default: "bundler")
+    pub module_resolution: Option<String>,
+
+    /// `compilerOptions.strict` (default: true)
+    pub strict: Option<bool>,
+
+    /// `compilerOptions.noUnusedLocals` (default: true)
+    pub no_unused_locals: Option<bool>,
+
+    /// `compilerOptions.noUnusedParameters` (default: true)
+    pub no_unused_parameters: Option<bool>,
+
+    /// `compilerOptions.noFallthroughCasesInSwitch` (default: true)
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+
+    /// `compilerOptions.noPropertyAccessFromIndexSignature` (default: true)
+    pub no_property_access_from_index_signature: Option<bool>,
+
+    /// `compilerOptions.noUncheckedIndexedAccess` (default: false)
+    pub no_unchecked_indexed_access: Option<bool>,
+
+    /// `compilerOptions.paths` aliases
+    #[serde(default)]
+    pub paths: BTreeMap<String, Vec<String>>,
+}
+
+impl TypeScriptConfig {
+    /// Returns true if no options differ from their defaults.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Extension style for relative import specifiers in generated code.
+///
+/// `.ts` works with Bun and `allowImportingTsExtensions`; `.js` and
+/// extensionless specifiers are needed when compiling with `tsc` for Node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportExtension {
+    #[default]
+    Ts,
+    Js,
+    None,
+}
+
+impl ImportExtension {
+    /// The extension including the leading dot, or an empty string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportExtension::Ts => ".ts",
+            ImportExtension::Js => ".js",
+            ImportExtension::None => "",
+        }
+    }
+}
+
+/// How manifest flag names map to option keys and command-line flags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlagCase {
+    /// camelCase option keys, parsed as kebab-case flags (`dry_run` → `options.dryRun`, `--dry-run`)
+    #[default]
+    Camel,
+    /// The manifest name unchanged for both (`dry_run` → `options.dry_run`, `--dry_run`)
+    Preserve,
+}
+
+/// Module format of the generated TypeScript package.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleFormat {
+    /// ES modules (`"type": "module"`)
+    #[default]
+    Esm,
+    /// CommonJS (`"type": "commonjs"`)
+    Cjs,
+}
+
+impl ModuleFormat {
+    /// Value of the `"type"` field in package.json.
+    pub fn package_type(&self) -> &'static str {
+        match self {
+            ModuleFormat::Esm => "module",
+            ModuleFormat::Cjs => "commonjs",
+        }
+    }
+
+    /// Value of `compilerOptions.module` in tsconfig.json.
+    pub fn compiler_module(&self) -> &'static str {
+        match self {
+            ModuleFormat::Esm => "ESNext",
+            ModuleFormat::Cjs => "CommonJS",
+        }
+    }
+}
@@ -3,29 +3,36 @@
 use std::sync::Arc;
 
 use miette::SourceSpan;
+use toml::{Spanned, de::DeTable};
 
-use crate::{Result, error::SourceContext};
+use super::{Language, SourceMap, spans::command_key};
+use crate::{Error, Result, error::SourceContext};
 
 /// Parsing and validation context that carries source information.
 ///
 /// This struct encapsulates the source content, filename, and current path
 /// through the manifest hierarchy, making it easier to pass validation
-/// context through recursive operations.
+/// context through recursive operations. It also indexes the source so
+/// errors can point at the key or value they are about.
 ///
 /// # Example
 ///
 /// ```ignore
 /// let ctx = ParseContext::new(src, "bao.toml");
-/// ctx.validate_name("hello", "command")?;
+/// ctx.validate_name_at("hello", "command", "commands.hello")?;
 ///
 /// // For nested validation
 /// let nested = ctx.push("commands").push("db");
-/// nested.validate_name("migrate", "subcommand")?;
+/// nested.validate_name_at("migrate", "subcommand", "commands.db.commands.migrate")?;
 /// ```
 #[derive(Debug, Clone)]
 pub struct ParseContext<'a> {
     /// Source context for error reporting (shared across nested contexts)
     source: Arc<SourceContext>,
+    /// Source locations of keys and values (shared across nested contexts)
+    spans: Arc<SourceMap>,
+    /// Target languages whose reserved words names must avoid
+    languages: Arc<[Language]>,
     /// Path segments for nested validation (e.g., ["commands", "db", "migrate"])
     path: Vec<&'a str>,
 }
@@ -33,12 +40,27 @@ pub struct ParseContext<'a> {
 impl<'a> ParseContext<'a> {
     /// Create a new parse context with the given source and filename.
     pub fn new(src: &str, filename: &str) -> Self {
+        let document = DeTable::parse(src).ok();
+        Self::from_document(src, filename, document.as_ref().map(Spanned::get_ref))
+    }
+
+    /// Create a parse context for a document already parsed from `src`.
+    pub(crate) fn from_document(src: &str, filename: &str, root: Option<&DeTable<'_>>) -> Self {
         Self {
             source: Arc::new(SourceContext::new(src, filename)),
+            spans: Arc::new(SourceMap::from_document(src, filename, root)),
+            languages: Arc::new([Language::Rust]),
             path: Vec::new(),
         }
     }
 
+    /// Check names against the reserved words of `languages` instead of
+    /// Rust's.
+    pub fn with_languages(mut self, languages: impl IntoIterator<Item = Language>) -> Self {
+        self.languages = languages.into_iter().collect();
+        self
+    }
+
     /// Get the source content.
     pub fn src(&self) -> &str {
         self.source.src()
@@ -49,6 +71,17 @@ impl<'a> ParseContext<'a> {
         self.source.filename()
     }
 
+    /// Get the source locations of the manifest's keys and values.
+    pub fn spans(&self) -> &SourceMap {
+        &self.spans
+    }
+
+    /// Take the source locations, cloning them only if a nested context
+    /// still shares them.
+    pub(crate) fn into_spans(self) -> SourceMap {
+        Arc::try_unwrap(self.spans).unwrap_or_else(|spans| (*spans).clone())
+    }
+
     /// Get the source context for error creation.
     pub fn source_context(&self) -> &SourceContext {
         &self.source
@@ -62,10 +95,17 @@ impl<'a> ParseContext<'a> {
         new_path.push(segment);
         Self {
             source: Arc::clone(&self.source),
+            spans: Arc::clone(&self.spans),
+            languages: Arc::clone(&self.languages),
             path: new_path,
         }
     }
 
+    /// Get the last path segment, the name of the command being validated.
+    pub(crate) fn name(&self) -> Option<&'a str> {
+        self.path.last().copied()
+    }
+
     /// Get the current path as a dot-separated string.
     ///
     /// Returns the segment if only one element, or joins with dots otherwise.
@@ -85,6 +125,33 @@ impl<'a> ParseContext<'a> {
         }
     }
 
+    /// Dotted TOML path of the current command, e.g. `commands.db.commands.migrate`.
+    pub fn command_key(&self) -> String {
+        command_key(&self.path)
+    }
+
+    /// Span of the key at a dotted TOML path.
+    pub fn key_span(&self, path: &str) -> Option<SourceSpan> {
+        self.spans.key_range(path).map(SourceSpan::from)
+    }
+
+    /// Span of the value at a dotted TOML path.
+    pub fn value_span(&self, path: &str) -> Option<SourceSpan> {
+        self.spans.value_range(path).map(SourceSpan::from)
+    }
+
+    /// Create a validation error, labelled at `span` if known.
+    pub fn validation_error(
+        &self,
+        message: impl Into<String>,
+        span: Option<SourceSpan>,
+    ) -> Box<Error> {
+        match span {
+            Some(span) => self.source.validation_error_at(message, span),
+            None => self.source.validation_error(message),
+        }
+    }
+
     /// Find the span of a name in the source.
     pub fn find_span(&self, name: &str) -> Option<SourceSpan> {
         find_name_span(self.source.src(), name)
@@ -92,13 +159,26 @@ impl<'a> ParseContext<'a> {
 
     /// Validate that a name is a valid identifier.
     ///
-    /// Checks for reserved keywords and valid identifier format.
+    /// Checks for reserved keywords of the target languages and valid
+    /// identifier format.
     pub fn validate_name(&self, name: &str, kind: &str) -> Result<()> {
-        if is_rust_keyword(name) {
+        self.check_name(name, kind, self.find_span(name))
+    }
+
+    /// Validate that a name is a valid identifier, pointing errors at the
+    /// key at the dotted TOML path `key`.
+    pub fn validate_name_at(&self, name: &str, kind: &str, key: &str) -> Result<()> {
+        let span = self.key_span(key).or_else(|| self.find_span(name));
+        self.check_name(name, kind, span)
+    }
+
+    fn check_name(&self, name: &str, kind: &str, span: Option<SourceSpan>) -> Result<()> {
+        if let Some(language) = reserved_by(name, &self.languages) {
             return Err(self.source.reserved_keyword_error(
                 name,
                 self.context_for(kind),
-                self.find_span(name),
+                language,
+                span,
             ));
         }
 
@@ -107,7 +187,7 @@ impl<'a> ParseContext<'a> {
                 name,
                 self.context_for(kind),
                 reason,
-                self.find_span(name),
+                span,
             ));
         }
 
@@ -130,9 +210,105 @@ pub(crate) const RUST_KEYWORDS: &[&str] = &[
     "union", "dyn",
 ];
 
-/// Check if a name is a Rust reserved keyword
-pub(crate) fn is_rust_keyword(name: &str) -> bool {
-    RUST_KEYWORDS.contains(&name)
+/// JavaScript reserved words, including those reserved in strict mode,
+/// which TypeScript modules always use
+pub(crate) const TYPESCRIPT_KEYWORDS: &[&str] = &[
+    // Reserved words
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    // Reserved in strict mode and modules
+    "await",
+    "implements",
+    "interface",
+    "let",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "static",
+    "yield",
+];
+
+/// Go keywords
+/// Source: https://go.dev/ref/spec#Keywords
+pub(crate) const GO_KEYWORDS: &[&str] = &[
+    // Keywords
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// Reserved words of a target language.
+pub(crate) fn keywords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => RUST_KEYWORDS,
+        Language::TypeScript => TYPESCRIPT_KEYWORDS,
+        Language::Go => GO_KEYWORDS,
+    }
+}
+
+/// The first of `languages` that reserves `name`, if any.
+pub(crate) fn reserved_by(name: &str, languages: &[Language]) -> Option<Language> {
+    languages
+        .iter()
+        .copied()
+        .find(|language| keywords(*language).contains(&name))
 }
 
 /// Find the span of a name in the TOML source
@@ -194,9 +370,12 @@ pub(crate) fn find_name_span(src: &str, name: &str) -> Option<SourceSpan> {
     None
 }
 
-/// Validate that a name is a valid Rust identifier (or dashed identifier for commands)
+/// Validate that a name is a valid identifier (or dashed identifier for commands)
 /// Returns None if valid, Some(reason) if invalid
 ///
+/// Reserved words depend on the target language and are checked separately
+/// with [`reserved_by`].
+///
 /// Allows dashes in names (e.g., "my-command") which will be converted to
 /// snake_case for Rust identifiers during code generation.
 pub(crate) fn validate_identifier(name: &str) -> Option<&'static str> {
@@ -204,17 +383,6 @@ pub(crate) fn validate_identifier(name: &str) -> Option<&'static str> {
         return Some("name cannot be empty");
     }
 
-    // Check if it's a reserved keyword (exact match)
-    if is_rust_keyword(name) {
-        return Some("name is a Rust reserved keyword");
-    }
-
-    // Also check if the snake_case version would be a reserved keyword
-    let snake_case = name.replace('-', "_");
-    if is_rust_keyword(&snake_case) {
-        return Some("name converts to a Rust reserved keyword");
-    }
-
     let mut chars = name.chars().peekable();
 
     // First character must be a letter or underscore
@@ -271,24 +439,24 @@ mod tests {
 
     #[test]
     fn test_reserved_keywords() {
-        assert!(validate_identifier("fn").is_some());
-        assert!(validate_identifier("struct").is_some());
-        assert!(validate_identifier("impl").is_some());
-        assert!(validate_identifier("let").is_some());
-        assert!(validate_identifier("mut").is_some());
-        assert!(validate_identifier("async").is_some());
-        assert!(validate_identifier("await").is_some());
-        assert!(validate_identifier("self").is_some());
-        assert!(validate_identifier("Self").is_some());
-        assert!(validate_identifier("type").is_some());
-        assert!(validate_identifier("trait").is_some());
-        assert!(validate_identifier("enum").is_some());
-        assert!(validate_identifier("match").is_some());
-        assert!(validate_identifier("mod").is_some());
-        assert!(validate_identifier("use").is_some());
-        assert!(validate_identifier("pub").is_some());
-        assert!(validate_identifier("crate").is_some());
-        assert!(validate_identifier("super").is_some());
+        assert!(reserved_by("fn", &[Language::Rust]).is_some());
+        assert!(reserved_by("struct", &[Language::Rust]).is_some());
+        assert!(reserved_by("impl", &[Language::Rust]).is_some());
+        assert!(reserved_by("let", &[Language::Rust]).is_some());
+        assert!(reserved_by("mut", &[Language::Rust]).is_some());
+        assert!(reserved_by("async", &[Language::Rust]).is_some());
+        assert!(reserved_by("await", &[Language::Rust]).is_some());
+        assert!(reserved_by("self", &[Language::Rust]).is_some());
+        assert!(reserved_by("Self", &[Language::Rust]).is_some());
+        assert!(reserved_by("type", &[Language::Rust]).is_some());
+        assert!(reserved_by("trait", &[Language::Rust]).is_some());
+        assert!(reserved_by("enum", &[Language::Rust]).is_some());
+        assert!(reserved_by("match", &[Language::Rust]).is_some());
+        assert!(reserved_by("mod", &[Language::Rust]).is_some());
+        assert!(reserved_by("use", &[Language::Rust]).is_some());
+        assert!(reserved_by("pub", &[Language::Rust]).is_some());
+        assert!(reserved_by("crate", &[Language::Rust]).is_some());
+        assert!(reserved_by("super", &[Language::Rust]).is_some());
     }
 
     #[test]
@@ -321,7 +489,22 @@ mod tests {
         // "fn_test" is not a keyword, so "fn-test" is allowed
         assert!(validate_identifier("fn-test").is_none());
         // But exact keywords are still rejected
-        assert!(validate_identifier("fn").is_some());
+        assert!(reserved_by("fn", &[Language::Rust]).is_some());
+    }
+
+    #[test]
+    fn test_reserved_by_language() {
+        assert_eq!(
+            reserved_by("delete", &[Language::TypeScript]),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(reserved_by("delete", &[Language::Rust]), None);
+        assert_eq!(reserved_by("func", &[Language::Go]), Some(Language::Go));
+        assert_eq!(
+            reserved_by("type", &[Language::TypeScript, Language::Go]),
+            Some(Language::Go)
+        );
+        assert_eq!(reserved_by("class", &[]), None);
     }
 
     #[test]
@@ -331,10 +514,11 @@ mod tests {
 
     #[test]
     fn test_is_rust_keyword() {
-        assert!(is_rust_keyword("fn"));
-        assert!(is_rust_keyword("struct"));
-        assert!(!is_rust_keyword("hello"));
-        assert!(!is_rust_keyword("my_function"));
+        let rust = [Language::Rust];
+        assert!(reserved_by("fn", &rust).is_some());
+        assert!(reserved_by("struct", &rust).is_some());
+        assert!(reserved_by("hello", &rust).is_none());
+        assert!(reserved_by("my_function", &rust).is_none());
     }
 
     #[test]
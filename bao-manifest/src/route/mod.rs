@@ -0,0 +1,174 @@
+mod validate;
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ArgType;
+
+/// An HTTP route served by the application
+///
+/// Path placeholders (`/users/{id}`) name path parameters; every other entry
+/// in `params` is read from the query string.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Route {
+    /// HTTP method
+    pub method: HttpMethod,
+
+    /// URL path, with `{name}` placeholders for path parameters
+    pub path: String,
+
+    /// Route description for generated docs
+    pub description: Option<String>,
+
+    /// Path and query parameters
+    #[serde(default)]
+    pub params: BTreeMap<String, Param>,
+}
+
+impl Route {
+    /// Names of the `{name}` placeholders in the path, in order.
+    pub fn path_params(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+            .collect()
+    }
+
+    /// Returns true if `name` is a path parameter.
+    pub fn is_path_param(&self, name: &str) -> bool {
+        self.path_params().contains(&name)
+    }
+}
+
+/// A route parameter
+#[derive(Debug, Clone, Deserialize)]
+pub struct Param {
+    /// Parameter type (defaults to string)
+    #[serde(rename = "type", default = "default_param_type")]
+    pub param_type: ArgType,
+
+    /// Whether a query parameter is required (path parameters always are)
+    #[serde(default)]
+    pub required: bool,
+
+    /// Description for generated docs
+    pub description: Option<String>,
+
+    /// Default value for a query parameter
+    pub default: Option<toml::Value>,
+
+    /// Allowed values
+    #[serde(default)]
+    pub choices: Option<Vec<String>>,
+}
+
+fn default_param_type() -> ArgType {
+    ArgType::String
+}
+
+/// HTTP request method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Head,
+    Options,
+}
+
+impl HttpMethod {
+    /// Get the uppercase string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Options => "OPTIONS",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use miette::Diagnostic;
+
+    use super::*;
+    use crate::Manifest;
+
+    const ROUTES: &str = r#"
+        [cli]
+        name = "test"
+        language = "rust"
+
+        [routes.get_user]
+        method = "GET"
+        path = "/users/{id}"
+        description = "Fetch a user"
+
+        [routes.get_user.params]
+        id = { type = "int" }
+        fields = { default = "name" }
+    "#;
+
+    #[test]
+    fn test_parse_routes() {
+        let manifest = Manifest::from_str(ROUTES).unwrap();
+        let route = &manifest.routes["get_user"];
+
+        assert_eq!(route.method, HttpMethod::Get);
+        assert_eq!(route.path_params(), vec!["id"]);
+        assert!(route.is_path_param("id"));
+        assert!(!route.is_path_param("fields"));
+        assert_eq!(route.params["id"].param_type, ArgType::Int);
+        assert_eq!(route.params["fields"].param_type, ArgType::String);
+    }
+
+    #[test]
+    fn test_routes_default_to_empty() {
+        let manifest = Manifest::from_str(
+            r#"
+            [cli]
+            name = "test"
+            language = "rust"
+            "#,
+        )
+        .unwrap();
+        assert!(manifest.routes.is_empty());
+    }
+
+    #[test]
+    fn test_undeclared_path_param_rejected() {
+        let content = ROUTES.replace("id = { type = \"int\" }", "");
+        let err = Manifest::from_str(&content).unwrap_err();
+        assert!(err.to_string().contains("path parameter 'id'"));
+    }
+
+    #[test]
+    fn test_path_param_default_rejected() {
+        let content = ROUTES.replace(
+            "id = { type = \"int\" }",
+            "id = { type = \"int\", default = 1 }",
+        );
+        let err = Manifest::from_str(&content).unwrap_err();
+        assert!(err.to_string().contains("cannot have a default"));
+
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&content[label.offset()..label.offset() + label.len()], "1");
+    }
+
+    #[test]
+    fn test_relative_path_rejected() {
+        let content = ROUTES.replace("\"/users/{id}\"", "\"users/{id}\"");
+        let err = Manifest::from_str(&content).unwrap_err();
+        assert!(err.to_string().contains("must start with '/'"));
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use super::Route;
+use crate::{ArgType, error::Result, manifest::ParseContext};
+
+impl Route {
+    /// Validate the route definition using the given parse context.
+    pub fn validate(&self, ctx: &ParseContext) -> Result<()> {
+        let table = format!("routes.{}", ctx.path_string());
+        let path_span = ctx.value_span(&format!("{}.path", table));
+
+        if !self.path.starts_with('/') {
+            return Err(ctx.validation_error(
+                format!(
+                    "path '{}' of route '{}' must start with '/'",
+                    self.path,
+                    ctx.path_string()
+                ),
+                path_span,
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for name in self.path_params() {
+            if !seen.insert(name) {
+                return Err(ctx.validation_error(
+                    format!(
+                        "path parameter '{}' appears more than once in route '{}'",
+                        name,
+                        ctx.path_string()
+                    ),
+                    path_span,
+                ));
+            }
+            match self.params.get(name) {
+                None => {
+                    return Err(ctx.validation_error(
+                        format!(
+                            "path parameter '{}' of route '{}' is not declared in params",
+                            name,
+                            ctx.path_string()
+                        ),
+                        path_span,
+                    ));
+                }
+                Some(param) if param.default.is_some() => {
+                    return Err(ctx.validation_error(
+                        format!(
+                            "path parameter '{}' of route '{}' cannot have a default",
+                            name,
+                            ctx.path_string()
+                        ),
+                        ctx.value_span(&format!("{}.params.{}.default", table, name)),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (name, param) in &self.params {
+            let key = format!("{}.params.{}", table, name);
+            ctx.validate_name_at(name, "parameter", &key)?;
+            if param.param_type == ArgType::Count {
+                return Err(ctx.validation_error(
+                    format!(
+                        "parameter '{}' of route '{}' cannot have type 'count'; only flags count occurrences",
+                        name,
+                        ctx.path_string()
+                    ),
+                    ctx.value_span(&format!("{}.type", key)),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
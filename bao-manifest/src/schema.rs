@@ -0,0 +1,949 @@
+//! JSON Schema describing the bao.toml format.
+//!
+//! Editors validate and complete TOML against a JSON Schema (taplo and Even
+//! Better TOML read it from a `#:schema` comment or their settings). The
+//! schema covers the layout and value types; rules spanning several keys,
+//! such as names colliding with keywords, are only checked by the parser.
+
+use baobao_core::ARG_TYPES;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    SCHEMA_VERSION,
+    command::{ARG_FIELDS, COMMAND_FIELDS, FLAG_FIELDS, GROUP_FIELDS, REQUIRED_IF_FIELDS},
+    manifest::{PROFILE_CLI_FIELDS, PROFILE_FIELDS},
+};
+
+/// The JSON Schema (draft 7) of bao.toml.
+///
+/// # Examples
+///
+/// ```
+/// let schema = baobao_manifest::json_schema();
+/// assert_eq!(schema["required"][0], "cli");
+/// assert!(schema["definitions"]["command"].is_object());
+/// ```
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "bao.toml",
+        "description": "Manifest of a CLI generated by bao",
+        "type": "object",
+        "required": ["cli"],
+        "additionalProperties": false,
+        "properties": {
+            "schema_version": {
+                "description": "Schema version the manifest was written for; `bao migrate` upgrades older manifests",
+                "type": "integer",
+                "minimum": 0,
+                "maximum": SCHEMA_VERSION,
+            },
+            "extends": {
+                "description": "Path of a base manifest this one inherits from, relative to this file, or its http(s) URL",
+                "type": "string",
+            },
+            "cli": { "$ref": "#/definitions/cli" },
+            "context": { "$ref": "#/definitions/context" },
+            "generation": { "$ref": "#/definitions/generation" },
+            "typescript": { "$ref": "#/definitions/typescript" },
+            "targets": {
+                "description": "Named generation targets, replacing `cli.language`",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/target" },
+            },
+            "profile": {
+                "description": "Named profiles overriding CLI metadata and context settings, selected with `bao bake --profile`",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/profile" },
+            },
+            "flags": {
+                "description": "Global flags, accepted by every command",
+                "$ref": "#/definitions/flags",
+            },
+            "commands": {
+                "description": "Top-level commands",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/command" },
+            },
+            "routes": {
+                "description": "HTTP routes",
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/route" },
+            },
+        },
+        "definitions": definitions(),
+    })
+}
+
+fn definitions() -> Value {
+    json!({
+        "description": {
+            "description": "Text, or translations keyed by locale",
+            "oneOf": [
+                { "type": "string" },
+                { "type": "object", "additionalProperties": { "type": "string" } },
+            ],
+        },
+        "language": {
+            "description": "Target language",
+            "enum": ["rust", "typescript", "go"],
+        },
+        "argType": {
+            "description": "Value type",
+            "enum": ARG_TYPES.iter().map(|spec| spec.name).collect::<Vec<_>>(),
+        },
+        "cli": {
+            "description": "CLI metadata",
+            "type": "object",
+            "required": ["name"],
+            "additionalProperties": false,
+            "properties": cli_properties(),
+        },
+        "command": {
+            "description": "A command and its subcommands",
+            "type": "object",
+            "required": ["description"],
+            "additionalProperties": false,
+            "properties": object(COMMAND_FIELDS, |field| match field {
+                "description" => json!({ "$ref": "#/definitions/description" }),
+                "long_description" => json!({
+                    "description": "Extended description shown in `--help`",
+                    "type": "string",
+                }),
+                "aliases" => json!({
+                    "description": "Other names the command can be invoked by",
+                    "type": "array",
+                    "items": { "type": "string" },
+                }),
+                "deprecated" => json!({
+                    "description": "Deprecation message; using the command prints a warning",
+                    "type": "string",
+                }),
+                "examples" => json!({
+                    "description": "Example command lines shown after the command's help",
+                    "type": "array",
+                    "items": { "type": "string" },
+                }),
+                "footer" => json!({
+                    "description": "Text shown at the end of the command's help",
+                    "type": "string",
+                }),
+                "args" => json!({ "$ref": "#/definitions/args" }),
+                "flags" => json!({ "$ref": "#/definitions/flags" }),
+                "groups" => json!({
+                    "description": "Named groups of the command's arguments and flags",
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/group" },
+                }),
+                "commands" => json!({
+                    "description": "Nested subcommands",
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/command" },
+                }),
+                "default_command" => json!({
+                    "description": "Subcommand run when none is given, instead of printing help",
+                    "type": "string",
+                }),
+                "exit_codes" => json!({
+                    "description": "Process exit codes keyed by handler error name, inherited by subcommands",
+                    "type": "object",
+                    "additionalProperties": { "type": "integer", "minimum": 0, "maximum": 255 },
+                }),
+                "requires" => json!({
+                    "description": "Context resources the handler uses, by their [context] key, inherited by subcommands",
+                    "type": "array",
+                    "items": { "type": "string" },
+                }),
+                _ => unreachable!("command field '{}' has no schema", field),
+            }),
+        },
+        "args": {
+            "description": "Positional arguments, keyed by name or as an array of tables with a `name`",
+            "oneOf": [
+                { "type": "object", "additionalProperties": { "$ref": "#/definitions/arg" } },
+                { "type": "array", "items": { "$ref": "#/definitions/namedArg" } },
+            ],
+        },
+        "arg": input(ARG_FIELDS, &["type"], false),
+        "namedArg": input(ARG_FIELDS, &["type"], true),
+        "flags": {
+            "description": "Flags, keyed by name or as an array of tables with a `name`",
+            "oneOf": [
+                { "type": "object", "additionalProperties": { "$ref": "#/definitions/flag" } },
+                { "type": "array", "items": { "$ref": "#/definitions/namedFlag" } },
+            ],
+        },
+        "flag": input(FLAG_FIELDS, &[], false),
+        "namedFlag": input(FLAG_FIELDS, &[], true),
+        "requiredIf": {
+            "description": "Makes the flag required when another flag has a given value",
+            "type": "object",
+            "required": REQUIRED_IF_FIELDS,
+            "additionalProperties": false,
+            "properties": {
+                "flag": { "description": "Name of the flag to check", "type": "string" },
+                "value": { "description": "Value of that flag which makes this one required", "type": "string" },
+            },
+        },
+        "group": {
+            "description": "A named group of a command's arguments and flags",
+            "type": "object",
+            "required": ["members"],
+            "additionalProperties": false,
+            "properties": object(GROUP_FIELDS, |field| match field {
+                "members" => json!({
+                    "description": "Names of the arguments and flags in the group",
+                    "type": "array",
+                    "items": { "type": "string" },
+                }),
+                "exclusive" => json!({
+                    "description": "Whether at most one member can be given",
+                    "type": "boolean",
+                }),
+                "required" => json!({
+                    "description": "Whether at least one member must be given",
+                    "type": "boolean",
+                }),
+                _ => unreachable!("group field '{}' has no schema", field),
+            }),
+        },
+        "httpClient": {
+            "description": "HTTP client",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "type": { "const": "http" },
+                "base_url": { "description": "Base URL that request paths are resolved against", "type": "string" },
+                "headers": {
+                    "description": "Headers sent with every request",
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                },
+                "timeout": { "description": "Request timeout in seconds", "type": "integer", "minimum": 0 },
+                "user_agent": { "description": "User agent string", "type": "string" },
+                "retries": { "description": "Times a failed request is retried", "type": "integer", "minimum": 0 },
+                "backoff_ms": {
+                    "description": "Delay before the first retry in milliseconds, doubled for each retry",
+                    "type": "integer",
+                    "minimum": 0,
+                },
+                "retry_on": {
+                    "description": "Response statuses that are retried (default: 408, 429 and 5xx)",
+                    "type": "array",
+                    "items": { "type": "integer", "minimum": 100, "maximum": 599 },
+                },
+                "auth": {
+                    "description": "Credentials attached to every request",
+                    "oneOf": [
+                        http_auth("bearer", "token", json!({})),
+                        http_auth("basic", "password", json!({
+                            "username": { "description": "User name", "type": "string" },
+                        })),
+                        http_auth("header", "header value", json!({
+                            "name": { "description": "Header name", "type": "string" },
+                        })),
+                    ],
+                },
+            },
+        },
+        "context": {
+            "description": "Shared resources available to every handler",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "database": {
+                    "description": "Database connection pool",
+                    "oneOf": [
+                        database("sqlite", sqlite_properties()),
+                        database("postgres", replica_properties()),
+                        database("mysql", replica_properties()),
+                        database("duckdb", duckdb_properties()),
+                    ],
+                },
+                "http": {
+                    "description": "HTTP client, or named clients as [context.http.<name>]",
+                    "anyOf": [
+                        { "$ref": "#/definitions/httpClient" },
+                        {
+                            "type": "object",
+                            "additionalProperties": { "$ref": "#/definitions/httpClient" },
+                        },
+                    ],
+                },
+                "logging": {
+                    "description": "Application logger",
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "level": {
+                            "description": "Minimum level that is emitted (default: info)",
+                            "enum": ["trace", "debug", "info", "warn", "error"],
+                        },
+                        "format": {
+                            "description": "Output format (default: pretty)",
+                            "enum": ["pretty", "json"],
+                        },
+                        "env_filter": {
+                            "description": "Default filter in RUST_LOG syntax, e.g. \"info,myapp::db=debug\" (default: the level)",
+                            "type": "string",
+                        },
+                    },
+                },
+                "storage": {
+                    "description": "Object storage bucket",
+                    "type": "object",
+                    "required": ["type", "bucket"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "type": { "const": "s3" },
+                        "bucket": { "description": "Bucket name", "type": "string" },
+                        "region": { "description": "Region (default: AWS_REGION)", "type": "string" },
+                        "endpoint": {
+                            "description": "Endpoint URL of an S3-compatible service such as MinIO or R2",
+                            "type": "string",
+                        },
+                    },
+                },
+                "grpc": grpc(),
+                "email": email(),
+                "config": app_config(),
+                "secrets": secrets(),
+                "llm": llm(),
+                "cache": cache(),
+                "workdir": workdir(),
+                "queue": {
+                    "description": "Message queue connection",
+                    "type": "object",
+                    "required": ["type"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "type": { "enum": ["nats", "kafka"] },
+                        "env": {
+                            "description": "Environment variable holding the NATS server URL or comma-separated Kafka brokers (default: NATS_URL or KAFKA_BROKERS)",
+                            "type": "string",
+                        },
+                    },
+                },
+            },
+        },
+        "generation": {
+            "description": "Options applying to every generation target",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "header_file": {
+                    "description": "File prepended to every generated source file as comments, relative to the manifest",
+                    "type": "string",
+                },
+            },
+        },
+        "typescript": {
+            "description": "TypeScript generation options",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": typescript_properties(),
+        },
+        "target": {
+            "description": "A generation target",
+            "type": "object",
+            "required": ["language"],
+            "additionalProperties": false,
+            "properties": {
+                "language": { "$ref": "#/definitions/language" },
+                "output": {
+                    "description": "Output directory relative to the bake output (default: the target name)",
+                    "type": "string",
+                },
+                "typescript": { "$ref": "#/definitions/typescript" },
+            },
+        },
+        "profile": {
+            "description": "Overrides applied when the profile is selected",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": object(PROFILE_FIELDS, |field| match field {
+                "cli" => {
+                    let cli = cli_properties();
+                    json!({
+                        "description": "Overrides of [cli] metadata",
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": object(PROFILE_CLI_FIELDS, |key| cli[key].clone()),
+                    })
+                }
+                "context" => json!({
+                    "description": "Overrides of [context] settings, merged key by key",
+                    "type": "object",
+                }),
+                _ => unreachable!("profile field '{}' has no schema", field),
+            }),
+        },
+        "route": {
+            "description": "An HTTP route served by the application",
+            "type": "object",
+            "required": ["method", "path"],
+            "additionalProperties": false,
+            "properties": {
+                "method": {
+                    "description": "HTTP method",
+                    "enum": ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"],
+                },
+                "path": {
+                    "description": "URL path, with `{name}` placeholders for path parameters",
+                    "type": "string",
+                },
+                "description": { "description": "Route description for generated docs", "type": "string" },
+                "params": {
+                    "description": "Path and query parameters",
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/param" },
+                },
+            },
+        },
+        "param": {
+            "description": "A route parameter",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "type": { "$ref": "#/definitions/argType" },
+                "required": {
+                    "description": "Whether a query parameter is required (path parameters always are)",
+                    "type": "boolean",
+                },
+                "description": { "description": "Description for generated docs", "type": "string" },
+                "default": { "description": "Default value for a query parameter" },
+                "choices": {
+                    "description": "Allowed values",
+                    "type": "array",
+                    "items": { "type": "string" },
+                },
+            },
+        },
+    })
+}
+
+fn cli_properties() -> Map<String, Value> {
+    let properties = json!({
+        "name": { "description": "Name of the CLI binary", "type": "string" },
+        "version": { "description": "Semantic version (default: 0.1.0)", "type": "string" },
+        "description": { "$ref": "#/definitions/description" },
+        "author": { "description": "Author information", "type": "string" },
+        "footer": {
+            "description": "Text shown at the end of the CLI's help, such as links to docs",
+            "type": "string",
+        },
+        "language": { "$ref": "#/definitions/language" },
+        "locale": {
+            "description": "Locale of the text used when a description has no translation (default: en)",
+            "type": "string",
+        },
+        "build_info": {
+            "description": "Show the git commit and build time in `--version`",
+            "type": "boolean",
+        },
+        "docs_command": {
+            "description": "Add a hidden `docs` subcommand writing Markdown or man pages",
+            "type": "boolean",
+        },
+        "doctor_command": {
+            "description": "Add a `doctor` subcommand checking the environment the context needs",
+            "type": "boolean",
+        },
+        "external_subcommands": {
+            "description": "Run `<name>-<command>` from PATH for commands the CLI does not define",
+            "type": "boolean",
+        },
+        "default_command": {
+            "description": "Command run when none is given, instead of printing help",
+            "type": "string",
+        },
+    });
+    into_map(properties)
+}
+
+/// Schema of an argument or flag table made of `fields`; `named` adds the
+/// `name` key of the array format.
+fn input(fields: &[&str], required: &[&str], named: bool) -> Value {
+    let mut properties = object(fields, |field| match field {
+        "type" => json!({ "$ref": "#/definitions/argType" }),
+        "required" => json!({
+            "description": "Whether the argument must be given (default: true)",
+            "type": "boolean",
+        }),
+        "short" => json!({
+            "description": "Short flag character (e.g. \"v\" for -v)",
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 1,
+        }),
+        "description" => json!({ "$ref": "#/definitions/description" }),
+        "long_description" => json!({
+            "description": "Extended description shown in `--help`",
+            "type": "string",
+        }),
+        "default" => json!({ "description": "Default value" }),
+        "choices" => json!({
+            "description": "Allowed values",
+            "type": "array",
+            "items": { "type": "string" },
+        }),
+        "multiple" => json!({
+            "description": "Whether several values can be given",
+            "type": "boolean",
+        }),
+        "allow_hyphen_values" => json!({
+            "description": "Whether values starting with a dash, such as -5, are taken as the argument",
+            "type": "boolean",
+        }),
+        "delimiter" => json!({
+            "description": "Character splitting one value into several, for multiple flags",
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 1,
+        }),
+        "min" => {
+            json!({ "description": "Smallest allowed value, for int and float types", "type": "number" })
+        }
+        "max" => {
+            json!({ "description": "Largest allowed value, for int and float types", "type": "number" })
+        }
+        "pattern" => json!({
+            "description": "Regular expression a string value must match in full",
+            "type": "string",
+        }),
+        "min_length" => json!({
+            "description": "Minimum length of a string value, in characters",
+            "type": "integer",
+            "minimum": 0,
+        }),
+        "max_length" => json!({
+            "description": "Maximum length of a string value, in characters",
+            "type": "integer",
+            "minimum": 0,
+        }),
+        "path_exists" => json!({
+            "description": "Whether a path value must name an existing file or directory",
+            "type": "boolean",
+        }),
+        "requires" => json!({
+            "description": "Arguments and flags that must also be given when this flag is",
+            "type": "array",
+            "items": { "type": "string" },
+        }),
+        "required_if" => json!({ "$ref": "#/definitions/requiredIf" }),
+        "prompt" => json!({
+            "description": "Prompt interactively when the value is not given: \"confirm\", \"secret\", or the message asking for a missing argument",
+            "type": "string",
+            "examples": ["confirm", "secret"],
+        }),
+        "hint" => json!({
+            "description": "Kind of value, used by shells to complete it",
+            "enum": ["file", "dir", "hostname", "url", "command"],
+        }),
+        "index" => json!({
+            "description": "1-based position among the command's arguments",
+            "type": "integer",
+            "minimum": 1,
+        }),
+        "deprecated" => json!({
+            "description": "Deprecation message; giving the flag prints a warning",
+            "type": "string",
+        }),
+        _ => unreachable!("input field '{}' has no schema", field),
+    });
+    let mut required: Vec<&str> = required.to_vec();
+    if named {
+        properties.insert(
+            "name".into(),
+            json!({ "description": "Name", "type": "string" }),
+        );
+        required.insert(0, "name");
+    }
+    json!({
+        "type": "object",
+        "required": required,
+        "additionalProperties": false,
+        "properties": properties,
+    })
+}
+
+/// Schema of a `[context.database]` table with `type = "<kind>"`.
+fn database(kind: &str, extra: Map<String, Value>) -> Value {
+    let mut properties = into_map(json!({
+        "type": { "const": kind },
+        "env": {
+            "description": "Environment variable holding the connection string",
+            "type": "string",
+        },
+        "max_connections": { "description": "Maximum connections in the pool (default: 10)", "type": "integer", "minimum": 0 },
+        "min_connections": { "description": "Minimum connections kept open (default: 0)", "type": "integer", "minimum": 0 },
+        "acquire_timeout": { "description": "Seconds to wait for a connection (default: 30)", "type": "integer", "minimum": 0 },
+        "idle_timeout": { "description": "Seconds before an idle connection is closed (default: 600)", "type": "integer", "minimum": 0 },
+        "max_lifetime": { "description": "Maximum lifetime of a connection in seconds (default: 1800)", "type": "integer", "minimum": 0 },
+    }));
+    properties.extend(extra);
+    json!({
+        "type": "object",
+        "required": ["type"],
+        "additionalProperties": false,
+        "properties": properties,
+    })
+}
+
+fn http_auth(kind: &str, secret: &str, extra: Value) -> Value {
+    let mut properties = into_map(json!({
+        "type": { "const": kind },
+        "env": {
+            "description": format!("Environment variable holding the {}", secret),
+            "type": "string",
+        },
+    }));
+    let extra = into_map(extra);
+    let mut required = vec!["type".to_string(), "env".to_string()];
+    required.extend(extra.keys().cloned());
+    properties.extend(extra);
+    json!({
+        "type": "object",
+        "required": required,
+        "additionalProperties": false,
+        "properties": properties,
+    })
+}
+
+fn grpc() -> Value {
+    json!({
+        "description": "gRPC client channel",
+        "type": "object",
+        "required": ["endpoint"],
+        "additionalProperties": false,
+        "properties": {
+            "endpoint": {
+                "description": "Server endpoint, such as http://localhost:50051",
+                "type": "string",
+            },
+            "protos": {
+                "description": ".proto files that clients are generated from",
+                "type": "array",
+                "items": { "type": "string" },
+            },
+            "includes": {
+                "description": "Directories searched for imported .proto files (default: the directories of protos)",
+                "type": "array",
+                "items": { "type": "string" },
+            },
+            "timeout": { "description": "Request timeout in seconds", "type": "integer", "minimum": 0 },
+        },
+    })
+}
+
+fn email() -> Value {
+    json!({
+        "description": "SMTP transport for sending email",
+        "type": "object",
+        "required": ["host"],
+        "additionalProperties": false,
+        "properties": {
+            "host": { "description": "SMTP server host name", "type": "string" },
+            "port": {
+                "description": "SMTP server port (default: 587 for starttls, 465 for tls, 25 for none)",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 65535,
+            },
+            "tls": {
+                "description": "Connection encryption (default: starttls)",
+                "enum": ["starttls", "tls", "none"],
+            },
+            "username_env": {
+                "description": "Environment variable holding the SMTP username",
+                "type": "string",
+            },
+            "password_env": {
+                "description": "Environment variable holding the SMTP password",
+                "type": "string",
+            },
+        },
+    })
+}
+
+fn cache() -> Value {
+    json!({
+        "description": "In-memory cache",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "max_capacity": { "description": "Maximum number of entries (default: 1000)", "type": "integer", "minimum": 1 },
+            "ttl": { "description": "Seconds an entry lives after it is inserted (default: no expiry)", "type": "integer", "minimum": 1 },
+            "mode": { "description": "Where entries are kept: in memory for a single invocation, or also in files that outlive it (default: memory)", "enum": ["memory", "disk"] },
+            "path": { "description": "Directory of the disk cache (default: the CLI name in the user cache directory)", "type": "string" },
+        },
+    })
+}
+
+fn workdir() -> Value {
+    json!({
+        "description": "Data and scratch directories",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "data": { "description": "Keep a data directory that outlives the invocation (default: true)", "type": "boolean" },
+            "temp": { "description": "Create a scratch directory, removed when the invocation ends (default: false)", "type": "boolean" },
+            "path": { "description": "Data directory (default: the CLI name in the user data directory)", "type": "string" },
+        },
+    })
+}
+
+fn llm() -> Value {
+    json!({
+        "description": "LLM API client",
+        "type": "object",
+        "required": ["provider", "model"],
+        "additionalProperties": false,
+        "properties": {
+            "provider": { "description": "API provider", "enum": ["openai", "anthropic"] },
+            "model": { "description": "Model requests are sent to, e.g. \"gpt-4o\" or \"claude-sonnet-4-5\"", "type": "string" },
+            "api_key_env": { "description": "Environment variable holding the API key (default: OPENAI_API_KEY or ANTHROPIC_API_KEY)", "type": "string" },
+            "base_url": { "description": "Base URL of the API, for proxies and compatible servers (default: the provider's)", "type": "string" },
+            "max_tokens": { "description": "Maximum number of tokens in a reply (default: 1024 for anthropic, unset for openai)", "type": "integer", "minimum": 1 },
+        },
+    })
+}
+
+fn secrets() -> Value {
+    json!({
+        "description": "Secrets accessor for credentials",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "backend": {
+                "description": "Where secrets are read from: the OS keyring (falling back to environment variables), environment variables, or a file of NAME=value lines (default: keyring)",
+                "enum": ["keyring", "env", "file"],
+            },
+            "service": { "description": "Keyring service name (default: the CLI name)", "type": "string" },
+            "path": { "description": "Path of the secrets file, relative to the working directory (default: \".secrets\")", "type": "string" },
+            "prefix": { "description": "Prefix of the environment variables, e.g. \"MYAPP_\" (default: none)", "type": "string" },
+        },
+    })
+}
+
+fn app_config() -> Value {
+    let field_type = json!({
+        "description": "Value type",
+        "enum": ["string", "int", "float", "bool"],
+    });
+    json!({
+        "description": "Typed config file loaded at startup",
+        "type": "object",
+        "required": ["path"],
+        "additionalProperties": false,
+        "properties": {
+            "path": { "description": "Path of the config file, relative to the working directory", "type": "string" },
+            "format": {
+                "description": "File format (default: from the file extension)",
+                "enum": ["toml", "json", "yaml"],
+            },
+            "fields": {
+                "description": "Fields of the file, by key",
+                "type": "object",
+                "additionalProperties": {
+                    "oneOf": [
+                        field_type,
+                        {
+                            "type": "object",
+                            "required": ["type"],
+                            "additionalProperties": false,
+                            "properties": {
+                                "type": field_type,
+                                "optional": {
+                                    "description": "Whether the key may be left out of the file (default: false)",
+                                    "type": "boolean",
+                                },
+                            },
+                        },
+                    ],
+                },
+            },
+        },
+    })
+}
+
+fn sqlite_properties() -> Map<String, Value> {
+    into_map(json!({
+        "path": { "description": "Path of the database file", "type": "string" },
+        "create_if_missing": { "description": "Create the database file if it does not exist (default: true)", "type": "boolean" },
+        "read_only": { "description": "Open the database read-only (default: false)", "type": "boolean" },
+        "journal_mode": {
+            "description": "Journal mode (default: wal)",
+            "enum": ["wal", "delete", "truncate", "persist", "memory", "off"],
+        },
+        "synchronous": { "description": "Synchronous mode (default: full)", "enum": ["full", "normal", "off"] },
+        "busy_timeout": { "description": "Busy timeout in milliseconds (default: 5000)", "type": "integer", "minimum": 0 },
+        "foreign_keys": { "description": "Enable foreign key constraints (default: true)", "type": "boolean" },
+        "migrations": {
+            "description": "Directory of `<version>_<name>.up.sql` / `.down.sql` migration files",
+            "type": "string",
+        },
+    }))
+}
+
+fn replica_properties() -> Map<String, Value> {
+    into_map(json!({
+        "replica": {
+            "description": "Read-only replica, pooled separately from the primary",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "env": {
+                    "description": "Environment variable holding the replica's connection string (default: DATABASE_REPLICA_URL)",
+                    "type": "string",
+                },
+                "max_connections": { "description": "Maximum connections in the pool (default: 10)", "type": "integer", "minimum": 0 },
+                "min_connections": { "description": "Minimum connections kept open (default: 0)", "type": "integer", "minimum": 0 },
+                "acquire_timeout": { "description": "Seconds to wait for a connection (default: 30)", "type": "integer", "minimum": 0 },
+                "idle_timeout": { "description": "Seconds before an idle connection is closed (default: 600)", "type": "integer", "minimum": 0 },
+                "max_lifetime": { "description": "Maximum lifetime of a connection in seconds (default: 1800)", "type": "integer", "minimum": 0 },
+            },
+        },
+    }))
+}
+
+fn duckdb_properties() -> Map<String, Value> {
+    into_map(json!({
+        "path": { "description": "Path of the database file, or :memory:", "type": "string" },
+        "read_only": { "description": "Open the database read-only (default: false)", "type": "boolean" },
+        "threads": { "description": "Number of threads queries run on (default: one per core)", "type": "integer", "minimum": 1 },
+    }))
+}
+
+fn typescript_properties() -> Map<String, Value> {
+    let compiler_option = |option: &str, default: bool| {
+        json!({
+            "description": format!("`compilerOptions.{}` (default: {})", option, default),
+            "type": "boolean",
+        })
+    };
+    into_map(json!({
+        "progress": {
+            "description": "Generate a `src/progress.ts` spinner module",
+            "type": "boolean",
+        },
+        "docker": {
+            "description": "Generate a Dockerfile compiling the CLI into a standalone binary",
+            "type": "boolean",
+        },
+        "import_extension": {
+            "description": "Extension of relative imports in generated code (default: ts)",
+            "enum": ["ts", "js", "none"],
+        },
+        "module": { "description": "Module format of the package (default: esm)", "enum": ["esm", "cjs"] },
+        "flag_case": {
+            "description": "Naming of option keys and command-line flags (default: camel)",
+            "enum": ["camel", "preserve"],
+        },
+        "target": { "description": "`compilerOptions.target` (default: ESNext)", "type": "string" },
+        "module_resolution": {
+            "description": "`compilerOptions.moduleResolution` (default: bundler)",
+            "type": "string",
+        },
+        "strict": compiler_option("strict", true),
+        "no_unused_locals": compiler_option("noUnusedLocals", true),
+        "no_unused_parameters": compiler_option("noUnusedParameters", true),
+        "no_fallthrough_cases_in_switch": compiler_option("noFallthroughCasesInSwitch", true),
+        "no_property_access_from_index_signature": compiler_option("noPropertyAccessFromIndexSignature", true),
+        "no_unchecked_indexed_access": compiler_option("noUncheckedIndexedAccess", false),
+        "paths": {
+            "description": "`compilerOptions.paths` aliases",
+            "type": "object",
+            "additionalProperties": { "type": "array", "items": { "type": "string" } },
+        },
+    }))
+}
+
+/// Properties for `fields`, in order, each described by `schema`.
+fn object(fields: &[&str], schema: impl Fn(&str) -> Value) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|field| (field.to_string(), schema(field)))
+        .collect()
+}
+
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!("expected a JSON object"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(value: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn sorted<'a>(fields: &[&'a str]) -> Vec<&'a str> {
+        let mut fields = fields.to_vec();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_fields_match_parser() {
+        let schema = json_schema();
+        let definitions = &schema["definitions"];
+        assert_eq!(
+            keys(&definitions["command"]["properties"]),
+            sorted(COMMAND_FIELDS)
+        );
+        assert_eq!(keys(&definitions["arg"]["properties"]), sorted(ARG_FIELDS));
+        assert_eq!(
+            keys(&definitions["flag"]["properties"]),
+            sorted(FLAG_FIELDS)
+        );
+        assert_eq!(
+            keys(&definitions["group"]["properties"]),
+            sorted(GROUP_FIELDS)
+        );
+        assert_eq!(
+            keys(&definitions["profile"]["properties"]["cli"]["properties"]),
+            sorted(PROFILE_CLI_FIELDS)
+        );
+        assert_eq!(definitions["namedFlag"]["required"], json!(["name"]));
+        assert_eq!(definitions["namedArg"]["required"], json!(["name", "type"]));
+    }
+
+    #[test]
+    fn test_arg_types() {
+        let schema = json_schema();
+        let types = schema["definitions"]["argType"]["enum"].as_array().unwrap();
+        assert_eq!(types.len(), ARG_TYPES.len());
+        assert!(types.contains(&json!("uuid")));
+    }
+
+    #[test]
+    fn test_references_resolve() {
+        fn check(value: &Value, definitions: &Value) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        let name = reference.strip_prefix("#/definitions/").unwrap();
+                        assert!(definitions.get(name).is_some(), "unresolved {}", reference);
+                    }
+                    map.values().for_each(|v| check(v, definitions));
+                }
+                Value::Array(items) => items.iter().for_each(|v| check(v, definitions)),
+                _ => {}
+            }
+        }
+        let schema = json_schema();
+        check(&schema, &schema["definitions"]);
+    }
+}
@@ -1,61 +1,127 @@
 //! Serialization support for formatting bao.toml files.
 //!
 //! This module provides serializable versions of the manifest types that
-//! output canonical, sorted TOML.
+//! output canonical, sorted TOML. [`Manifest`], [`Command`], [`Arg`],
+//! [`Flag`] and [`Context`] implement [`Serialize`] through them, so
+//! manifests built in code serialize to the same canonical form as
+//! `bao fmt` writes.
 
 use std::collections::BTreeMap;
 
 use baobao_core::Version;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
 use crate::{
-    ArgType, CliConfig, Command, Context, ContextField, HttpConfig, JournalMode, Language,
-    Manifest, SynchronousMode,
+    AppConfig, Arg, ArgType, CacheConfig, CacheMode, CliConfig, Command, ConfigFieldType,
+    ConfigFormat, Context, ContextField, Description, EmailConfig, Flag, FlagCase,
+    GenerationConfig, Group, GrpcConfig, HintKind, HttpAuth, HttpConfig, HttpMethod,
+    ImportExtension, JournalMode, Language, LlmConfig, LlmProvider, LogFormat, LogLevel,
+    LoggingConfig, Manifest, ModuleFormat, Param, Profile, PromptKind, ReplicaConfig, RequiredIf,
+    Route, S3Config, SecretsBackend, SecretsConfig, SmtpTls, SynchronousMode, TargetConfig,
+    TypeScriptConfig, WorkdirConfig,
 };
 
 /// Serializable manifest for canonical TOML output.
 ///
-/// Fields are ordered: cli, context, commands
+/// Fields are ordered: schema_version, cli, context, generation, typescript, targets,
+/// profile, flags, commands, routes
 #[derive(Debug, Serialize)]
 pub struct SerializableManifest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
     pub cli: SerializableCliConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<SerializableContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation: Option<SerializableGenerationConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typescript: Option<SerializableTypeScriptConfig>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, SerializableTargetConfig>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile: BTreeMap<String, Profile>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub flags: BTreeMap<String, SerializableFlag>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub commands: BTreeMap<String, SerializableCommand>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub routes: BTreeMap<String, SerializableRoute>,
 }
 
 impl From<&Manifest> for SerializableManifest {
     fn from(m: &Manifest) -> Self {
         Self {
+            schema_version: m.schema_version,
             cli: SerializableCliConfig::from(&m.cli),
             context: if m.context.is_empty() {
                 None
             } else {
                 Some(SerializableContext::from(&m.context))
             },
+            generation: if m.generation.is_default() {
+                None
+            } else {
+                Some(SerializableGenerationConfig::from(&m.generation))
+            },
+            typescript: if m.typescript.is_default() {
+                None
+            } else {
+                Some(SerializableTypeScriptConfig::from(&m.typescript))
+            },
+            targets: m
+                .targets
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableTargetConfig::from(v)))
+                .collect(),
+            profile: m.profile.clone(),
+            flags: m
+                .flags
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableFlag::from(v)))
+                .collect(),
             commands: m
                 .commands
                 .iter()
                 .map(|(k, v)| (k.clone(), SerializableCommand::from(v)))
                 .collect(),
+            routes: m
+                .routes
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableRoute::from(v)))
+                .collect(),
         }
     }
 }
 
 /// Serializable CLI configuration.
 ///
-/// Fields ordered: name, language, version, author, description
+/// Fields ordered: name, language, version, author, description, footer,
+/// locale
 #[derive(Debug, Serialize)]
 pub struct SerializableCliConfig {
     pub name: String,
-    pub language: Language,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
     #[serde(skip_serializing_if = "is_default_version")]
     pub version: Version,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    pub description: Option<Description>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub build_info: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub docs_command: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub doctor_command: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub external_subcommands: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_command: Option<String>,
 }
 
 fn is_default_version(v: &Version) -> bool {
@@ -70,30 +136,183 @@ impl From<&CliConfig> for SerializableCliConfig {
             version: c.version.clone(),
             author: c.author.clone(),
             description: c.description.clone(),
+            footer: c.footer.clone(),
+            locale: c.locale.clone(),
+            build_info: c.build_info,
+            docs_command: c.docs_command,
+            doctor_command: c.doctor_command,
+            external_subcommands: c.external_subcommands,
+            default_command: c.default_command.clone(),
+        }
+    }
+}
+
+/// Serializable generation target.
+///
+/// Fields ordered: language, output, typescript
+#[derive(Debug, Serialize)]
+pub struct SerializableTargetConfig {
+    pub language: Language,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typescript: Option<SerializableTypeScriptConfig>,
+}
+
+impl From<&TargetConfig> for SerializableTargetConfig {
+    fn from(t: &TargetConfig) -> Self {
+        Self {
+            language: t.language,
+            output: t.output.as_ref().map(|p| p.display().to_string()),
+            typescript: t
+                .typescript
+                .as_ref()
+                .map(SerializableTypeScriptConfig::from),
+        }
+    }
+}
+
+/// Serializable `[generation]` section.
+#[derive(Debug, Serialize)]
+pub struct SerializableGenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_file: Option<String>,
+}
+
+impl From<&GenerationConfig> for SerializableGenerationConfig {
+    fn from(c: &GenerationConfig) -> Self {
+        Self {
+            header_file: c.header_file.clone(),
+        }
+    }
+}
+
+/// Serializable TypeScript options.
+///
+/// Fields ordered: progress, docker, import_extension, module, flag_case, compiler options (alphabetical), paths
+#[derive(Debug, Serialize)]
+pub struct SerializableTypeScriptConfig {
+    #[serde(skip_serializing_if = "is_false")]
+    pub progress: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub docker: bool,
+    #[serde(skip_serializing_if = "is_default_import_extension")]
+    pub import_extension: ImportExtension,
+    #[serde(skip_serializing_if = "is_default_module_format")]
+    pub module: ModuleFormat,
+    #[serde(skip_serializing_if = "is_default_flag_case")]
+    pub flag_case: FlagCase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_resolution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_property_access_from_index_signature: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unchecked_indexed_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unused_locals: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unused_parameters: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<String, Vec<String>>,
+}
+
+fn is_false(v: &bool) -> bool {
+    !*v
+}
+
+fn is_default_import_extension(v: &ImportExtension) -> bool {
+    *v == ImportExtension::default()
+}
+
+fn is_default_flag_case(v: &FlagCase) -> bool {
+    *v == FlagCase::default()
+}
+
+fn is_default_module_format(v: &ModuleFormat) -> bool {
+    *v == ModuleFormat::default()
+}
+
+impl From<&TypeScriptConfig> for SerializableTypeScriptConfig {
+    fn from(c: &TypeScriptConfig) -> Self {
+        Self {
+            progress: c.progress,
+            docker: c.docker,
+            import_extension: c.import_extension,
+            module: c.module,
+            flag_case: c.flag_case,
+            module_resolution: c.module_resolution.clone(),
+            no_fallthrough_cases_in_switch: c.no_fallthrough_cases_in_switch,
+            no_property_access_from_index_signature: c.no_property_access_from_index_signature,
+            no_unchecked_indexed_access: c.no_unchecked_indexed_access,
+            no_unused_locals: c.no_unused_locals,
+            no_unused_parameters: c.no_unused_parameters,
+            strict: c.strict,
+            target: c.target.clone(),
+            paths: c.paths.clone(),
         }
     }
 }
 
 /// Serializable context configuration.
 ///
-/// Fields ordered: database, http
+/// Fields ordered: database, http, logging, storage, grpc, queue, email, config, secrets, llm,
+/// cache, workdir
 #[derive(Debug, Serialize)]
 pub struct SerializableContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub database: Option<SerializableDatabaseConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub http: Option<SerializableHttpConfig>,
+    pub http: Option<SerializableHttp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<SerializableLoggingConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<SerializableStorageConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<SerializableGrpcConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue: Option<SerializableQueueConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<SerializableEmailConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<SerializableAppConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<SerializableSecretsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm: Option<SerializableLlmConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<SerializableCacheConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workdir: Option<SerializableWorkdirConfig>,
 }
 
 impl From<&Context> for SerializableContext {
     fn from(c: &Context) -> Self {
         Self {
             database: c.database.as_ref().map(SerializableDatabaseConfig::from),
-            http: c
-                .http
+            http: SerializableHttp::from_context(c),
+            logging: c
+                .logging
                 .as_ref()
-                .and_then(|f| f.http_config())
-                .map(SerializableHttpConfig::from),
+                .and_then(|f| f.logging_config())
+                .map(SerializableLoggingConfig::from),
+            storage: c.s3_config().map(SerializableStorageConfig::from),
+            grpc: c.grpc_config().map(SerializableGrpcConfig::from),
+            queue: c
+                .queue
+                .as_ref()
+                .and_then(SerializableQueueConfig::from_field),
+            email: c.email_config().map(SerializableEmailConfig::from),
+            config: c.app_config().map(SerializableAppConfig::from),
+            secrets: c.secrets_config().map(SerializableSecretsConfig::from),
+            llm: c.llm_config().map(SerializableLlmConfig::from),
+            cache: c.cache_config().map(SerializableCacheConfig::from),
+            workdir: c.workdir_config().map(SerializableWorkdirConfig::from),
         }
     }
 }
@@ -128,9 +347,47 @@ pub struct SerializableDatabaseConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub journal_mode: Option<JournalMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub migrations: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub read_only: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub synchronous: Option<SynchronousMode>,
+    // DuckDB-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+    // Tables come after values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replica: Option<SerializableReplicaConfig>,
+}
+
+/// Serializable `[context.database.replica]` table.
+#[derive(Debug, Serialize)]
+pub struct SerializableReplicaConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acquire_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_connections: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lifetime: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_connections: Option<u32>,
+}
+
+impl From<&ReplicaConfig> for SerializableReplicaConfig {
+    fn from(c: &ReplicaConfig) -> Self {
+        Self {
+            env: c.env.clone(),
+            acquire_timeout: c.pool.acquire_timeout,
+            idle_timeout: c.pool.idle_timeout,
+            max_connections: c.pool.max_connections,
+            max_lifetime: c.pool.max_lifetime,
+            min_connections: c.pool.min_connections,
+        }
+    }
 }
 
 impl From<&ContextField> for SerializableDatabaseConfig {
@@ -149,8 +406,11 @@ impl From<&ContextField> for SerializableDatabaseConfig {
                 create_if_missing: None,
                 foreign_keys: None,
                 journal_mode: None,
+                migrations: None,
                 read_only: None,
                 synchronous: None,
+                threads: None,
+                replica: c.replica().map(SerializableReplicaConfig::from),
             },
             ContextField::Mysql(c) => Self {
                 db_type: "mysql".to_string(),
@@ -165,8 +425,11 @@ impl From<&ContextField> for SerializableDatabaseConfig {
                 create_if_missing: None,
                 foreign_keys: None,
                 journal_mode: None,
+                migrations: None,
                 read_only: None,
                 synchronous: None,
+                threads: None,
+                replica: c.replica().map(SerializableReplicaConfig::from),
             },
             ContextField::Sqlite(c) => Self {
                 db_type: "sqlite".to_string(),
@@ -181,149 +444,724 @@ impl From<&ContextField> for SerializableDatabaseConfig {
                 create_if_missing: c.create_if_missing,
                 foreign_keys: c.foreign_keys,
                 journal_mode: c.journal_mode.clone(),
+                migrations: c.migrations.clone(),
                 read_only: c.read_only,
                 synchronous: c.synchronous.clone(),
+                threads: None,
+                replica: None,
+            },
+            ContextField::Duckdb(c) => Self {
+                db_type: "duckdb".to_string(),
+                env: c.env.clone(),
+                path: c.path.clone(),
+                acquire_timeout: c.pool.acquire_timeout,
+                idle_timeout: c.pool.idle_timeout,
+                max_connections: c.pool.max_connections,
+                max_lifetime: c.pool.max_lifetime,
+                min_connections: c.pool.min_connections,
+                busy_timeout: None,
+                create_if_missing: None,
+                foreign_keys: None,
+                journal_mode: None,
+                migrations: None,
+                read_only: c.read_only,
+                synchronous: None,
+                threads: c.threads,
+                replica: None,
             },
             ContextField::Http(_) => panic!("HTTP is not a database config"),
+            ContextField::Logging(_) => panic!("logging is not a database config"),
+            ContextField::S3(_) => panic!("S3 is not a database config"),
+            ContextField::Grpc(_) => panic!("gRPC is not a database config"),
+            ContextField::Nats(_) | ContextField::Kafka(_) => {
+                panic!("queue is not a database config")
+            }
+            ContextField::Email(_) => panic!("email is not a database config"),
+            ContextField::Config(_) => panic!("config is not a database config"),
+            ContextField::Secrets(_) => panic!("secrets is not a database config"),
+            ContextField::Llm(_) => panic!("llm is not a database config"),
+            ContextField::Cache(_) => panic!("cache is not a database config"),
+            ContextField::Workdir(_) => panic!("workdir is not a database config"),
+        }
+    }
+}
+
+/// Serializable `[context.http]` table: one client, or named clients.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum SerializableHttp {
+    Single(SerializableHttpConfig),
+    Named(BTreeMap<String, SerializableHttpConfig>),
+}
+
+impl SerializableHttp {
+    fn from_context(c: &Context) -> Option<Self> {
+        if let Some(config) = c.http_config() {
+            return Some(Self::Single(config.into()));
+        }
+        if c.http_clients.is_empty() {
+            return None;
         }
+        let clients = c
+            .http_clients
+            .iter()
+            .filter_map(|(name, field)| Some((name.clone(), field.http_config()?.into())))
+            .collect();
+        Some(Self::Named(clients))
     }
 }
 
 /// Serializable HTTP configuration.
+///
+/// Fields ordered: base_url, timeout, user_agent, retries, backoff_ms, retry_on, headers, auth
 #[derive(Debug, Serialize)]
 pub struct SerializableHttpConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub retry_on: Vec<u16>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<HttpAuth>,
 }
 
 impl From<&HttpConfig> for SerializableHttpConfig {
     fn from(c: &HttpConfig) -> Self {
         Self {
+            base_url: c.base_url.clone(),
             timeout: c.timeout,
             user_agent: c.user_agent.clone(),
+            retries: c.retries,
+            backoff_ms: c.backoff_ms,
+            retry_on: c.retry_on.clone(),
+            headers: c.headers.clone(),
+            auth: c.auth.clone(),
         }
     }
 }
 
-/// Serializable command.
+/// Serializable object storage configuration with type tag.
 ///
-/// Fields ordered: description, args, commands, flags
+/// Fields ordered: type, bucket, endpoint, region
 #[derive(Debug, Serialize)]
-pub struct SerializableCommand {
-    pub description: String,
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub args: BTreeMap<String, SerializableArg>,
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub commands: BTreeMap<String, SerializableCommand>,
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub flags: BTreeMap<String, SerializableFlag>,
+pub struct SerializableStorageConfig {
+    #[serde(rename = "type")]
+    pub storage_type: String,
+    pub bucket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
 }
 
-impl From<&Command> for SerializableCommand {
-    fn from(c: &Command) -> Self {
+impl From<&S3Config> for SerializableStorageConfig {
+    fn from(c: &S3Config) -> Self {
         Self {
-            description: c.description.clone(),
-            args: c
-                .args
-                .iter()
-                .map(|(k, v)| (k.clone(), SerializableArg::from(v)))
-                .collect(),
-            commands: c
-                .commands
-                .iter()
-                .map(|(k, v)| (k.clone(), SerializableCommand::from(v)))
-                .collect(),
-            flags: c
-                .flags
-                .iter()
-                .map(|(k, v)| (k.clone(), SerializableFlag::from(v)))
-                .collect(),
+            storage_type: "s3".to_string(),
+            bucket: c.bucket.clone(),
+            endpoint: c.endpoint.clone(),
+            region: c.region.clone(),
         }
     }
 }
 
-/// Serializable argument.
+/// Serializable gRPC configuration.
 ///
-/// Fields ordered: type, default, description, required
+/// Fields ordered: endpoint, timeout, protos, includes
 #[derive(Debug, Serialize)]
-pub struct SerializableArg {
+pub struct SerializableGrpcConfig {
+    pub endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub protos: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+}
+
+impl From<&GrpcConfig> for SerializableGrpcConfig {
+    fn from(c: &GrpcConfig) -> Self {
+        Self {
+            endpoint: c.endpoint.clone(),
+            timeout: c.timeout,
+            protos: c.protos.clone(),
+            includes: c.includes.clone(),
+        }
+    }
+}
+
+/// Serializable message queue configuration with type tag.
+///
+/// Fields ordered: type, env
+#[derive(Debug, Serialize)]
+pub struct SerializableQueueConfig {
     #[serde(rename = "type")]
-    pub arg_type: ArgType,
+    pub queue_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<toml::Value>,
+    pub env: Option<String>,
+}
+
+impl SerializableQueueConfig {
+    fn from_field(field: &ContextField) -> Option<Self> {
+        match field {
+            ContextField::Nats(_) | ContextField::Kafka(_) => Some(Self {
+                queue_type: field.type_name().to_string(),
+                env: field.env().map(String::from),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Serializable SMTP configuration.
+///
+/// Fields ordered: host, port, tls, username_env, password_env
+#[derive(Debug, Serialize)]
+pub struct SerializableEmailConfig {
+    pub host: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    #[serde(skip_serializing_if = "is_true")]
-    pub required: bool,
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "is_default_smtp_tls")]
+    pub tls: SmtpTls,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
 }
 
-fn is_true(v: &bool) -> bool {
-    *v
+fn is_default_smtp_tls(tls: &SmtpTls) -> bool {
+    *tls == SmtpTls::default()
 }
 
-impl From<&crate::Arg> for SerializableArg {
-    fn from(a: &crate::Arg) -> Self {
+impl From<&EmailConfig> for SerializableEmailConfig {
+    fn from(c: &EmailConfig) -> Self {
         Self {
-            arg_type: a.arg_type.clone(),
-            default: a.default.clone(),
-            description: a.description.clone(),
-            required: a.required,
+            host: c.host.clone(),
+            port: c.port,
+            tls: c.tls,
+            username_env: c.username_env.clone(),
+            password_env: c.password_env.clone(),
         }
     }
 }
 
-/// Serializable flag.
+/// Serializable secrets configuration.
 ///
-/// Fields ordered: type, default, description, short
+/// Fields ordered: backend, service, path, prefix
 #[derive(Debug, Serialize)]
-pub struct SerializableFlag {
-    #[serde(rename = "type", skip_serializing_if = "is_default_flag_type")]
-    pub flag_type: ArgType,
+pub struct SerializableSecretsConfig {
+    pub backend: SecretsBackend,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<toml::Value>,
+    pub service: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub short: Option<char>,
+    pub prefix: Option<String>,
 }
 
-fn is_default_flag_type(t: &ArgType) -> bool {
-    *t == ArgType::Bool
+impl From<&SecretsConfig> for SerializableSecretsConfig {
+    fn from(c: &SecretsConfig) -> Self {
+        Self {
+            backend: c.backend,
+            service: c.service.clone(),
+            path: c.path.clone(),
+            prefix: c.prefix.clone(),
+        }
+    }
 }
 
-impl From<&crate::Flag> for SerializableFlag {
-    fn from(f: &crate::Flag) -> Self {
+/// Serializable LLM client configuration.
+///
+/// Fields ordered: provider, model, api_key_env, base_url, max_tokens
+#[derive(Debug, Serialize)]
+pub struct SerializableLlmConfig {
+    pub provider: LlmProvider,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+impl From<&LlmConfig> for SerializableLlmConfig {
+    fn from(c: &LlmConfig) -> Self {
         Self {
-            flag_type: f.flag_type.clone(),
-            default: f.default.clone(),
-            description: f.description.clone(),
-            short: f.short.as_ref().map(|s| *s.get_ref()),
+            provider: c.provider,
+            model: c.model.clone(),
+            api_key_env: c.api_key_env.clone(),
+            base_url: c.base_url.clone(),
+            max_tokens: c.max_tokens,
         }
     }
 }
 
-/// Convert a manifest to a formatted TOML string.
-pub fn to_formatted_string(manifest: &Manifest) -> String {
-    let serializable = SerializableManifest::from(manifest);
-    toml::to_string_pretty(&serializable).expect("serialization cannot fail for valid manifest")
+/// Serializable cache configuration.
+///
+/// Fields ordered: max_capacity, ttl, mode, path
+#[derive(Debug, Serialize)]
+pub struct SerializableCacheConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_capacity: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+    #[serde(skip_serializing_if = "is_default_cache_mode")]
+    pub mode: CacheMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn is_default_cache_mode(mode: &CacheMode) -> bool {
+    *mode == CacheMode::default()
+}
 
-    fn parse(content: &str) -> Manifest {
-        toml::from_str(content).expect("Failed to parse TOML")
+impl From<&CacheConfig> for SerializableCacheConfig {
+    fn from(c: &CacheConfig) -> Self {
+        Self {
+            max_capacity: c.max_capacity,
+            ttl: c.ttl,
+            mode: c.mode,
+            path: c.path.clone(),
+        }
     }
+}
 
-    #[test]
-    fn test_round_trip_basic() {
-        let input = r#"
-[cli]
-name = "test"
-language = "rust"
+/// Serializable working directory configuration.
+///
+/// Fields ordered: data, temp, path
+#[derive(Debug, Serialize)]
+pub struct SerializableWorkdirConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<bool>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub temp: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl From<&WorkdirConfig> for SerializableWorkdirConfig {
+    fn from(c: &WorkdirConfig) -> Self {
+        Self {
+            data: c.data,
+            temp: c.temp,
+            path: c.path.clone(),
+        }
+    }
+}
+
+/// Serializable config file configuration.
+///
+/// Fields ordered: path, format, fields
+#[derive(Debug, Serialize)]
+pub struct SerializableAppConfig {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ConfigFormat>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, SerializableConfigField>,
+}
+
+/// A config field, written as its type name unless it is optional.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum SerializableConfigField {
+    Short(ConfigFieldType),
+    Full {
+        #[serde(rename = "type")]
+        field_type: ConfigFieldType,
+        optional: bool,
+    },
+}
+
+impl From<&AppConfig> for SerializableAppConfig {
+    fn from(c: &AppConfig) -> Self {
+        Self {
+            path: c.path.clone(),
+            format: c.format,
+            fields: c
+                .fields
+                .iter()
+                .map(|(name, field)| {
+                    let field = if field.optional {
+                        SerializableConfigField::Full {
+                            field_type: field.field_type,
+                            optional: true,
+                        }
+                    } else {
+                        SerializableConfigField::Short(field.field_type)
+                    };
+                    (name.clone(), field)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Serializable logging configuration.
+///
+/// Fields ordered: format, level, env_filter
+#[derive(Debug, Serialize)]
+pub struct SerializableLoggingConfig {
+    #[serde(skip_serializing_if = "is_default_log_format")]
+    pub format: LogFormat,
+    #[serde(skip_serializing_if = "is_default_log_level")]
+    pub level: LogLevel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_filter: Option<String>,
+}
+
+fn is_default_log_format(v: &LogFormat) -> bool {
+    *v == LogFormat::default()
+}
+
+fn is_default_log_level(v: &LogLevel) -> bool {
+    *v == LogLevel::default()
+}
+
+impl From<&LoggingConfig> for SerializableLoggingConfig {
+    fn from(c: &LoggingConfig) -> Self {
+        Self {
+            format: c.format,
+            level: c.level,
+            env_filter: c.env_filter.clone(),
+        }
+    }
+}
+
+/// Serializable command.
+///
+/// Fields ordered: description, aliases, args, commands, default_command,
+/// deprecated, examples, exit_codes, flags, footer, groups, long_description,
+/// requires
+#[derive(Debug, Serialize)]
+pub struct SerializableCommand {
+    pub description: Description,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, SerializableArg>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, SerializableCommand>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub exit_codes: BTreeMap<String, u8>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub flags: BTreeMap<String, SerializableFlag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Group>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+}
+
+impl From<&Command> for SerializableCommand {
+    fn from(c: &Command) -> Self {
+        Self {
+            description: c.description.clone(),
+            aliases: c.aliases.clone(),
+            args: c
+                .args
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableArg::from(v)))
+                .collect(),
+            commands: c
+                .commands
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableCommand::from(v)))
+                .collect(),
+            default_command: c.default_command.clone(),
+            deprecated: c.deprecated.clone(),
+            examples: c.examples.clone(),
+            exit_codes: c.exit_codes.clone(),
+            flags: c
+                .flags
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableFlag::from(v)))
+                .collect(),
+            footer: c.footer.clone(),
+            groups: c.groups.clone(),
+            long_description: c.long_description.clone(),
+            requires: c.requires.clone(),
+        }
+    }
+}
+
+/// Serializable argument.
+///
+/// Fields ordered: type, allow_hyphen_values, choices, default, description,
+/// hint, index, long_description, max, max_length, min, min_length, multiple,
+/// path_exists, pattern, prompt, required
+#[derive(Debug, Serialize)]
+pub struct SerializableArg {
+    #[serde(rename = "type")]
+    pub arg_type: ArgType,
+    #[serde(skip_serializing_if = "is_false")]
+    pub allow_hyphen_values: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<HintKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub multiple: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub path_exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<PromptKind>,
+    #[serde(skip_serializing_if = "is_true")]
+    pub required: bool,
+}
+
+fn is_true(v: &bool) -> bool {
+    *v
+}
+
+impl From<&Arg> for SerializableArg {
+    fn from(a: &Arg) -> Self {
+        Self {
+            arg_type: a.arg_type,
+            allow_hyphen_values: a.allow_hyphen_values,
+            choices: a.choices.clone(),
+            default: a.default.clone(),
+            description: a.description.clone(),
+            hint: a.hint,
+            index: a.index,
+            long_description: a.long_description.clone(),
+            max: a.max,
+            max_length: a.max_length,
+            min: a.min,
+            min_length: a.min_length,
+            multiple: a.multiple,
+            path_exists: a.path_exists,
+            pattern: a.pattern.clone(),
+            prompt: a.prompt.clone(),
+            required: a.required,
+        }
+    }
+}
+
+/// Serializable flag.
+///
+/// Fields ordered: type, choices, default, delimiter, deprecated, description,
+/// hint, long_description, max, max_length, min, min_length, multiple,
+/// path_exists, pattern, prompt, required_if, requires, short
+#[derive(Debug, Serialize)]
+pub struct SerializableFlag {
+    #[serde(rename = "type", skip_serializing_if = "is_default_flag_type")]
+    pub flag_type: ArgType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<HintKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub multiple: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub path_exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<PromptKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_if: Option<RequiredIf>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short: Option<char>,
+}
+
+fn is_default_flag_type(t: &ArgType) -> bool {
+    *t == ArgType::Bool
+}
+
+impl From<&Flag> for SerializableFlag {
+    fn from(f: &Flag) -> Self {
+        Self {
+            flag_type: f.flag_type,
+            choices: f.choices.clone(),
+            default: f.default.clone(),
+            delimiter: f.delimiter,
+            deprecated: f.deprecated.clone(),
+            description: f.description.clone(),
+            hint: f.hint,
+            long_description: f.long_description.clone(),
+            max: f.max,
+            max_length: f.max_length,
+            min: f.min,
+            min_length: f.min_length,
+            multiple: f.multiple,
+            path_exists: f.path_exists,
+            pattern: f.pattern.clone(),
+            prompt: f.prompt.clone(),
+            required_if: f.required_if.clone(),
+            requires: f.requires.clone(),
+            short: f.short_char(),
+        }
+    }
+}
+
+/// Serializable route.
+///
+/// Fields ordered: description, method, path, params
+#[derive(Debug, Serialize)]
+pub struct SerializableRoute {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub method: HttpMethod,
+    pub path: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, SerializableParam>,
+}
+
+impl From<&Route> for SerializableRoute {
+    fn from(r: &Route) -> Self {
+        Self {
+            description: r.description.clone(),
+            method: r.method,
+            path: r.path.clone(),
+            params: r
+                .params
+                .iter()
+                .map(|(k, v)| (k.clone(), SerializableParam::from(v)))
+                .collect(),
+        }
+    }
+}
+
+/// Serializable route parameter.
+///
+/// Fields ordered: type, choices, default, description, required
+#[derive(Debug, Serialize)]
+pub struct SerializableParam {
+    #[serde(rename = "type", skip_serializing_if = "is_default_param_type")]
+    pub param_type: ArgType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub required: bool,
+}
+
+fn is_default_param_type(t: &ArgType) -> bool {
+    *t == ArgType::String
+}
+
+impl From<&Param> for SerializableParam {
+    fn from(p: &Param) -> Self {
+        Self {
+            param_type: p.param_type,
+            choices: p.choices.clone(),
+            default: p.default.clone(),
+            description: p.description.clone(),
+            required: p.required,
+        }
+    }
+}
+
+/// Implement [`Serialize`] for a manifest type by converting it to its
+/// canonical serializable form.
+macro_rules! serialize_via {
+    ($($ty:ty => $serializable:ty),* $(,)?) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    <$serializable>::from(self).serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+serialize_via! {
+    Manifest => SerializableManifest,
+    Command => SerializableCommand,
+    Arg => SerializableArg,
+    Flag => SerializableFlag,
+    Context => SerializableContext,
+}
+
+impl Manifest {
+    /// Serialize the manifest to canonical bao.toml content.
+    pub fn to_toml_string(&self) -> String {
+        to_formatted_string(self)
+    }
+}
+
+/// Convert a manifest to a formatted TOML string.
+pub fn to_formatted_string(manifest: &Manifest) -> String {
+    let serializable = SerializableManifest::from(manifest);
+    toml::to_string_pretty(&serializable).expect("serialization cannot fail for valid manifest")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Manifest {
+        toml::from_str(content).expect("Failed to parse TOML")
+    }
+
+    #[test]
+    fn test_round_trip_basic() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
 
 [commands.hello]
 description = "Say hello"
@@ -389,6 +1227,376 @@ language = "rust"
         assert!(context_pos < commands_pos);
     }
 
+    #[test]
+    fn test_typescript_section_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "typescript"
+
+[typescript]
+progress = true
+docker = true
+import_extension = "none"
+module = "cjs"
+flag_case = "preserve"
+target = "ES2022"
+strict = false
+
+[typescript.paths]
+"@/*" = ["./src/*"]
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[typescript]"));
+        assert!(output.contains("import_extension = \"none\""));
+        assert_eq!(manifest.typescript, reparsed.typescript);
+    }
+
+    #[test]
+    fn test_logging_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "typescript"
+
+[context.logging]
+level = "debug"
+format = "json"
+env_filter = "debug,sqlx=warn"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.logging]"));
+        assert_eq!(
+            manifest.context.logging_config(),
+            reparsed.context.logging_config()
+        );
+    }
+
+    #[test]
+    fn test_storage_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.storage]
+type = "s3"
+bucket = "uploads"
+endpoint = "http://localhost:9000"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.storage]\ntype = \"s3\"\nbucket = \"uploads\""));
+        assert_eq!(manifest.context.s3_config(), reparsed.context.s3_config());
+    }
+
+    #[test]
+    fn test_grpc_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.grpc]
+includes = ["proto", "vendor/proto"]
+protos = ["proto/greeter.proto"]
+endpoint = "http://localhost:50051"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains(
+            "[context.grpc]\nendpoint = \"http://localhost:50051\"\nprotos = [\"proto/greeter.proto\"]"
+        ));
+        assert_eq!(
+            manifest.context.grpc_config(),
+            reparsed.context.grpc_config()
+        );
+    }
+
+    #[test]
+    fn test_email_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.email]
+tls = "tls"
+host = "smtp.example.com"
+username_env = "SMTP_USERNAME"
+password_env = "SMTP_PASSWORD"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.email]\nhost = \"smtp.example.com\"\ntls = \"tls\""));
+        assert_eq!(
+            manifest.context.email_config(),
+            reparsed.context.email_config()
+        );
+    }
+
+    #[test]
+    fn test_app_config_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.config]
+path = "settings.conf"
+format = "yaml"
+
+[context.config.fields]
+api_url = "string"
+retries = { type = "int", optional = true }
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.config]\npath = \"settings.conf\"\nformat = \"yaml\""));
+        assert_eq!(manifest.context.app_config(), reparsed.context.app_config());
+    }
+
+    #[test]
+    fn test_secrets_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.secrets]
+prefix = "ACME_"
+backend = "env"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.secrets]\nbackend = \"env\"\nprefix = \"ACME_\""));
+        assert_eq!(
+            manifest.context.secrets_config(),
+            reparsed.context.secrets_config()
+        );
+    }
+
+    #[test]
+    fn test_llm_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.llm]
+model = "gpt-4o"
+provider = "openai"
+api_key_env = "LLM_KEY"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains(
+            "[context.llm]\nprovider = \"openai\"\nmodel = \"gpt-4o\"\napi_key_env = \"LLM_KEY\""
+        ));
+        assert_eq!(manifest.context.llm_config(), reparsed.context.llm_config());
+    }
+
+    #[test]
+    fn test_cache_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.cache]
+mode = "disk"
+ttl = 600
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.cache]\nttl = 600\nmode = \"disk\""));
+        assert_eq!(
+            manifest.context.cache_config(),
+            reparsed.context.cache_config()
+        );
+    }
+
+    #[test]
+    fn test_workdir_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.workdir]
+temp = true
+path = ".data"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.workdir]\ntemp = true\npath = \".data\""));
+        assert_eq!(
+            manifest.context.workdir_config(),
+            reparsed.context.workdir_config()
+        );
+    }
+
+    #[test]
+    fn test_duckdb_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.database]
+type = "duckdb"
+path = "analytics.duckdb"
+read_only = true
+threads = 4
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("type = \"duckdb\""));
+        assert!(output.contains("threads = 4"));
+        let duckdb = reparsed
+            .context
+            .database
+            .as_ref()
+            .unwrap()
+            .duckdb_config()
+            .unwrap();
+        assert_eq!(duckdb.path.as_deref(), Some("analytics.duckdb"));
+        assert_eq!(duckdb.read_only, Some(true));
+        assert_eq!(duckdb.threads, Some(4));
+    }
+
+    #[test]
+    fn test_replica_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.database]
+type = "postgres"
+max_connections = 10
+
+[context.database.replica]
+env = "READER_URL"
+max_connections = 20
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.database.replica]\nenv = \"READER_URL\""));
+        let replica = reparsed
+            .context
+            .database
+            .as_ref()
+            .unwrap()
+            .replica_config()
+            .unwrap();
+        assert_eq!(replica.env.as_deref(), Some("READER_URL"));
+        assert_eq!(replica.pool.max_connections, Some(20));
+    }
+
+    #[test]
+    fn test_queue_context_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.queue]
+env = "BROKERS"
+type = "kafka"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("[context.queue]\ntype = \"kafka\"\nenv = \"BROKERS\""));
+        let reparsed = reparsed.context.queue.as_ref().unwrap();
+        assert_eq!(reparsed.type_name(), "kafka");
+        assert_eq!(reparsed.env(), Some("BROKERS"));
+    }
+
+    #[test]
+    fn test_named_http_clients_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[context.http.internal]
+base_url = "http://localhost:8080"
+auth = { type = "basic", username = "ci", env = "INTERNAL_PASSWORD" }
+
+[context.http.github]
+base_url = "https://api.github.com"
+headers = { Accept = "application/vnd.github+json" }
+retry_on = [429, 503]
+retries = 3
+timeout = 30
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains(
+            "[context.http.github]\nbase_url = \"https://api.github.com\"\ntimeout = 30\nretries = 3\n"
+        ));
+        assert!(output.find("[context.http.github]") < output.find("[context.http.internal]"));
+        assert_eq!(
+            manifest.context.http_configs(),
+            reparsed.context.http_configs()
+        );
+    }
+
+    #[test]
+    fn test_routes_round_trip() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[routes.get_user]
+method = "GET"
+path = "/users/{id}"
+
+[routes.get_user.params.id]
+type = "int"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(output.contains("method = \"GET\""));
+        let route = &reparsed.routes["get_user"];
+        assert_eq!(route.path, "/users/{id}");
+        assert_eq!(route.params["id"].param_type, ArgType::Int);
+    }
+
     #[test]
     fn test_empty_context_omitted() {
         let input = r#"
@@ -494,4 +1702,205 @@ description = "A subcommand"
 
         assert!(alpha_pos < zebra_pos);
     }
+
+    #[test]
+    fn test_round_trip_targets() {
+        let input = r#"
+[cli]
+name = "test"
+
+[targets.node]
+language = "typescript"
+output = "packages/cli"
+
+[targets.node.typescript]
+module = "cjs"
+
+[targets.native]
+language = "rust"
+"#;
+        let manifest = parse(input);
+        let output = to_formatted_string(&manifest);
+        let reparsed: Manifest = toml::from_str(&output).expect("Failed to reparse");
+
+        assert!(reparsed.cli.language.is_none());
+        assert!(output.find("[targets.native]").unwrap() < output.find("[targets.node]").unwrap());
+        assert_eq!(manifest.targets, reparsed.targets);
+    }
+
+    #[test]
+    fn test_round_trip_choices() {
+        let input = r#"
+[cli]
+name = "test"
+language = "rust"
+
+[commands.deploy]
+description = "Deploy"
+
+[commands.deploy.args.env]
+type = "string"
+choices = ["dev", "prod"]
+
+[commands.deploy.flags.region]
+type = "string"
+choices = ["eu", "us"]
+"#;
+        let manifest = parse(input);
+        let output = manifest.to_toml_string();
+        let reparsed = parse(&output);
+        let deploy = &reparsed.commands["deploy"];
+
+        assert_eq!(
+            deploy.args["env"].choices.as_deref(),
+            Some(&["dev".to_string(), "prod".to_string()][..])
+        );
+        assert_eq!(
+            deploy.flags["region"].choices.as_deref(),
+            Some(&["eu".to_string(), "us".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn test_programmatic_manifest() {
+        let arg = Arg {
+            arg_type: ArgType::String,
+            required: true,
+            description: Some("Who to greet".into()),
+            long_description: None,
+            default: None,
+            choices: None,
+            multiple: false,
+            allow_hyphen_values: false,
+            min: None,
+            max: None,
+            pattern: None,
+            min_length: None,
+            max_length: None,
+            path_exists: false,
+            prompt: None,
+            hint: None,
+            index: None,
+        };
+        let flag = Flag {
+            flag_type: ArgType::Bool,
+            short: Some(toml::Spanned::new(0..0, 'l')),
+            description: Some("Shout the greeting".into()),
+            long_description: None,
+            default: None,
+            choices: None,
+            multiple: false,
+            delimiter: None,
+            min: None,
+            max: None,
+            pattern: None,
+            min_length: None,
+            max_length: None,
+            path_exists: false,
+            requires: vec![],
+            required_if: None,
+            prompt: None,
+            hint: None,
+            deprecated: None,
+        };
+        let hello = Command {
+            description: "Say hello".into(),
+            long_description: None,
+            aliases: vec![],
+            deprecated: None,
+            examples: vec![],
+            footer: None,
+            args: [("name".to_string(), arg)].into_iter().collect(),
+            flags: [("loud".to_string(), flag)].into_iter().collect(),
+            commands: Default::default(),
+            default_command: None,
+            groups: Default::default(),
+            exit_codes: Default::default(),
+            requires: vec![],
+        };
+        let manifest = Manifest {
+            schema_version: None,
+            extends: None,
+            cli: CliConfig {
+                name: "greeter".into(),
+                version: Version::new(1, 0, 0),
+                description: None,
+                author: None,
+                footer: None,
+                language: Some(Language::Rust),
+                locale: None,
+                build_info: false,
+                docs_command: false,
+                doctor_command: false,
+                external_subcommands: false,
+                default_command: None,
+            },
+            context: Context::default(),
+            generation: Default::default(),
+            typescript: TypeScriptConfig::default(),
+            targets: Default::default(),
+            profile: Default::default(),
+            flags: Default::default(),
+            commands: [("hello".to_string(), hello)].into_iter().collect(),
+            routes: Default::default(),
+            spans: Default::default(),
+        };
+
+        let output = manifest.to_toml_string();
+        let reparsed: Manifest = output.parse().expect("Failed to reparse");
+
+        assert!(output.contains("[commands.hello.flags.loud]"));
+        assert_eq!(reparsed.cli.version, Version::new(1, 0, 0));
+        assert_eq!(
+            reparsed.commands["hello"].flags["loud"].short_char(),
+            Some('l')
+        );
+        assert_eq!(reparsed.to_toml_string(), output);
+    }
+
+    #[test]
+    fn test_round_trip_localized_descriptions() {
+        let manifest = parse(
+            r#"
+[cli]
+name = "test"
+language = "rust"
+locale = "fr"
+
+[commands.hello]
+description = { en = "Say hello", fr = "Dire bonjour" }
+"#,
+        );
+        let output = manifest.to_toml_string();
+        let reparsed: Manifest = output.parse().unwrap();
+
+        assert!(output.contains("locale = \"fr\""));
+        assert_eq!(&*reparsed.commands["hello"].description, "Dire bonjour");
+        assert_eq!(reparsed.to_toml_string(), output);
+    }
+
+    #[test]
+    fn test_serialize_command() {
+        let manifest = parse(
+            r#"
+[cli]
+name = "test"
+language = "rust"
+
+[commands.hello]
+description = "Say hello"
+
+[commands.hello.flags.verbose]
+short = "v"
+"#,
+        );
+        let output = toml::to_string(&manifest.commands["hello"]).unwrap();
+
+        assert!(output.contains("description = \"Say hello\""));
+        assert!(output.contains("short = \"v\""));
+        assert_eq!(
+            toml::to_string_pretty(&manifest).unwrap(),
+            manifest.to_toml_string()
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use baobao_manifest::{BaoToml, append_section, command_section_header, context_section_header};
+use baobao_manifest::BaoToml;
 use clap::{Args, Subcommand};
 use eyre::{Result, bail};
 
@@ -29,13 +29,13 @@ struct AddCommandArgs {
     description: String,
 
     /// Path to bao.toml
-    #[arg(short, long, default_value = "bao.toml")]
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     config: PathBuf,
 }
 
 #[derive(Args)]
 struct AddContextArgs {
-    /// Context type: sqlite, postgres, mysql, or http
+    /// Context type: sqlite, postgres, mysql, duckdb, or http
     #[arg(name = "type")]
     context_type: String,
 
@@ -44,7 +44,7 @@ struct AddContextArgs {
     name: Option<String>,
 
     /// Path to bao.toml
-    #[arg(short, long, default_value = "bao.toml")]
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     config: PathBuf,
 }
 
@@ -63,14 +63,7 @@ impl AddCommand {
             bail!("Command '{}' already exists", args.name);
         }
 
-        let section = format!(
-            "{}\ndescription = \"{}\"",
-            command_section_header(&args.name),
-            args.description
-        );
-        let new_content = append_section(bao_toml.content(), &section);
-
-        bao_toml.set_content(new_content)?;
+        bao_toml.edit(|editor| editor.add_command(&args.name, &args.description))?;
         bao_toml.save()?;
         println!("Added command '{}'", args.name);
 
@@ -78,7 +71,7 @@ impl AddCommand {
     }
 
     fn add_context(args: &AddContextArgs) -> Result<()> {
-        let valid_types = ["sqlite", "postgres", "mysql", "http"];
+        let valid_types = ["sqlite", "postgres", "mysql", "duckdb", "http"];
         if !valid_types.contains(&args.context_type.as_str()) {
             bail!(
                 "Invalid context type '{}'. Valid types: {}",
@@ -106,22 +99,23 @@ impl AddCommand {
             bail!("Context field '{}' already exists", field_name);
         }
 
-        let section = match args.context_type.as_str() {
-            "sqlite" => format!(
-                "{}\ntype = \"sqlite\"\nenv = \"DATABASE_URL\"\ncreate_if_missing = true\njournal_mode = \"wal\"\nforeign_keys = true",
-                context_section_header(&field_name)
-            ),
-            "postgres" | "mysql" => format!(
-                "{}\ntype = \"{}\"\nenv = \"DATABASE_URL\"",
-                context_section_header(&field_name),
-                args.context_type
-            ),
-            "http" => context_section_header("http"),
+        let fields = match args.context_type.as_str() {
+            "sqlite" => vec![
+                ("type", "sqlite".into()),
+                ("env", "DATABASE_URL".into()),
+                ("create_if_missing", true.into()),
+                ("journal_mode", "wal".into()),
+                ("foreign_keys", true.into()),
+            ],
+            "postgres" | "mysql" | "duckdb" => vec![
+                ("type", args.context_type.as_str().into()),
+                ("env", "DATABASE_URL".into()),
+            ],
+            "http" => vec![],
             _ => unreachable!(),
         };
 
-        let new_content = append_section(bao_toml.content(), &section);
-        bao_toml.set_content(new_content)?;
+        bao_toml.edit(|editor| editor.add_context(&field_name, fields))?;
         bao_toml.save()?;
         println!(
             "Added context '{}' (type: {})",
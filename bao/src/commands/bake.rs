@@ -1,20 +1,26 @@
-use std::path::PathBuf;
-
-use baobao_manifest::{BaoToml, Language};
-use clap::Args;
-use eyre::Result;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
-use super::UnwrapOrExit;
-use crate::{
+use baobao::{
     language::LanguageSupport,
     ops,
-    reports::{Report, TerminalOutput},
+    reports::{Output, Report, TerminalOutput},
 };
+use baobao_core::OverwritePrompt;
+use baobao_manifest::{BaoToml, Language, Manifest, Target};
+use clap::Args;
+use eyre::{Context, Result};
+
+use super::UnwrapOrExit;
+use crate::prompt::InteractivePrompt;
 
 #[derive(Args)]
 pub struct BakeCommand {
-    /// Path to bao.toml (defaults to ./bao.toml)
-    #[arg(short, long, default_value = "bao.toml")]
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     pub config: PathBuf,
 
     /// Output directory (defaults to current directory)
@@ -25,32 +31,93 @@ pub struct BakeCommand {
     #[arg(long)]
     pub dry_run: bool,
 
-    /// Target language (overrides bao.toml setting)
+    /// Ask before overwriting existing files that changed
+    #[arg(short, long, conflicts_with = "dry_run")]
+    pub interactive: bool,
+
+    /// Target language (overrides bao.toml language and targets)
     #[arg(short, long)]
     pub language: Option<Language>,
 
     /// Output intermediate representations for debugging
     #[arg(long)]
     pub visualize: bool,
+
+    /// Apply a [profile.<NAME>] of bao.toml, such as dev or release
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Write the generated project to a .tar.gz archive ("-" for stdout)
+    /// instead of the output directory
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["dry_run", "interactive", "visualize"])]
+    pub archive: Option<PathBuf>,
 }
 
 impl BakeCommand {
     pub fn run(&self) -> Result<()> {
         let bao_toml = BaoToml::open(&self.config).unwrap_or_exit();
         let manifest = bao_toml.schema();
-        let lang = LanguageSupport::get(self.language.unwrap_or(manifest.cli.language));
-
-        let report = ops::bake(
-            manifest,
-            lang,
-            ops::bake::BakeOptions {
-                output_dir: &self.output,
-                dry_run: self.dry_run,
-                visualize: self.visualize,
-            },
-        )?;
+        let targets = match self.language {
+            Some(language) => vec![Target::implicit(language, manifest.typescript.clone())],
+            None => manifest.targets(),
+        };
+
+        if let Some(archive) = &self.archive {
+            return self.archive_targets(manifest, &targets, archive);
+        }
+
+        self.bake_targets(manifest, &targets)
+    }
+
+    /// Generate every target into one archive, each under its output directory.
+    fn archive_targets(&self, manifest: &Manifest, targets: &[Target], path: &Path) -> Result<()> {
+        let writer: Box<dyn Write> = if path == Path::new("-") {
+            Box::new(io::stdout().lock())
+        } else {
+            let file = fs::File::create(path)
+                .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+            Box::new(io::BufWriter::new(file))
+        };
 
+        let mut archive = ops::Archive::new(writer).with_profile(self.profile.clone());
+        for target in targets {
+            archive.add(
+                &manifest.for_target(target),
+                LanguageSupport::get(target.language),
+                &target.output_dir(Path::new("")),
+            )?;
+        }
+        let mut report = archive.finish()?;
+        if path != Path::new("-") {
+            report.destination = Some(path.display().to_string());
+        }
         report.render(&mut TerminalOutput::new());
         Ok(())
     }
+
+    fn bake_targets(&self, manifest: &Manifest, targets: &[Target]) -> Result<()> {
+        let mut out = TerminalOutput::new();
+        // Shared across targets so "all" and "none" answers carry over
+        let mut prompt = self.interactive.then(InteractivePrompt::new);
+        for target in targets {
+            let output_dir = target.output_dir(&self.output);
+            let report = ops::bake(
+                &manifest.for_target(target),
+                LanguageSupport::get(target.language),
+                ops::bake::BakeOptions {
+                    output_dir: &output_dir,
+                    dry_run: self.dry_run,
+                    visualize: self.visualize,
+                    profile: self.profile.as_deref(),
+                    prompt: prompt.as_mut().map(|p| p as &mut dyn OverwritePrompt),
+                },
+            )?;
+
+            if targets.len() > 1 {
+                out.section(&format!("{} ({})", target.name, output_dir.display()));
+            }
+            report.render(&mut out);
+        }
+        Ok(())
+    }
 }
@@ -1,19 +1,19 @@
 use std::path::PathBuf;
 
+use baobao::{
+    ops,
+    reports::{Report, TerminalOutput},
+};
 use baobao_manifest::BaoToml;
 use clap::Args;
 use eyre::Result;
 
 use super::UnwrapOrExit;
-use crate::{
-    ops,
-    reports::{Report, TerminalOutput},
-};
 
 #[derive(Args)]
 pub struct CheckCommand {
-    /// Path to bao.toml (defaults to ./bao.toml)
-    #[arg(short, long, default_value = "bao.toml")]
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     pub config: PathBuf,
 }
 
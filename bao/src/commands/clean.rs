@@ -1,20 +1,20 @@
 use std::path::PathBuf;
 
+use baobao::{
+    language::LanguageSupport,
+    ops,
+    reports::{Output, Report, TerminalOutput},
+};
 use baobao_manifest::BaoToml;
 use clap::Args;
 use eyre::Result;
 
 use super::UnwrapOrExit;
-use crate::{
-    language::LanguageSupport,
-    ops,
-    reports::{Report, TerminalOutput},
-};
 
 #[derive(Args)]
 pub struct CleanCommand {
-    /// Path to bao.toml (defaults to ./bao.toml)
-    #[arg(short, long, default_value = "bao.toml")]
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     pub config: PathBuf,
 
     /// Output directory (defaults to current directory)
@@ -30,18 +30,25 @@ impl CleanCommand {
     pub fn run(&self) -> Result<()> {
         let bao_toml = BaoToml::open(&self.config).unwrap_or_exit();
         let manifest = bao_toml.schema();
-        let lang = LanguageSupport::get(manifest.cli.language);
-
-        let report = ops::clean(
-            manifest,
-            lang,
-            ops::clean::CleanOptions {
-                output_dir: &self.output,
-                dry_run: self.dry_run,
-            },
-        )?;
-
-        report.render(&mut TerminalOutput::new());
+        let targets = manifest.targets();
+
+        let mut out = TerminalOutput::new();
+        for target in &targets {
+            let output_dir = target.output_dir(&self.output);
+            let report = ops::clean(
+                &manifest.for_target(target),
+                LanguageSupport::get(target.language),
+                ops::clean::CleanOptions {
+                    output_dir: &output_dir,
+                    dry_run: self.dry_run,
+                },
+            )?;
+
+            if targets.len() > 1 {
+                out.section(&format!("{} ({})", target.name, output_dir.display()));
+            }
+            report.render(&mut out);
+        }
         Ok(())
     }
 }
@@ -1,15 +1,15 @@
 use std::path::PathBuf;
 
-use baobao_manifest::BaoToml;
+use baobao_manifest::{BaoToml, ManifestFormat};
 use clap::Args;
-use eyre::Result;
+use eyre::{Result, bail};
 
 use super::UnwrapOrExit;
 
 #[derive(Args)]
 pub struct FmtCommand {
-    /// Path to bao.toml (defaults to ./bao.toml)
-    #[arg(short, long, default_value = "bao.toml")]
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     pub config: PathBuf,
 
     /// Check if file is formatted without making changes (exit 1 if not)
@@ -20,6 +20,20 @@ pub struct FmtCommand {
 impl FmtCommand {
     pub fn run(&self) -> Result<()> {
         let bao_toml = BaoToml::open(&self.config).unwrap_or_exit();
+        if bao_toml.format() != ManifestFormat::Toml {
+            bail!(
+                "cannot format {}: only TOML manifests can be formatted",
+                self.config.display()
+            );
+        }
+        if let Some(base) = &bao_toml.schema().extends {
+            // Formatting serializes the merged manifest, which would inline the base
+            bail!(
+                "cannot format {}: it extends '{}'",
+                self.config.display(),
+                base
+            );
+        }
         let formatted = bao_toml.to_formatted_string();
 
         if self.check {
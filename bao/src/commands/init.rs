@@ -1,6 +1,10 @@
 use std::path::{Path, PathBuf};
 
 use baobao_codegen::{generation::BaoToml, language::LanguageCodegen, pipeline::Pipeline};
+use baobao_codegen_go::{
+    Generator as GoGenerator,
+    files::{GitIgnore as GoGitIgnore, GoMod, MainGo},
+};
 use baobao_codegen_rust::{
     Generator as RustGenerator,
     files::{CargoToml, GitIgnore as RustGitIgnore, MainRs},
@@ -42,11 +46,12 @@ impl InitCommand {
         match language {
             Language::Rust => Self::create_rust_project(&project_name, &output_dir),
             Language::TypeScript => Self::create_typescript_project(&project_name, &output_dir),
+            Language::Go => Self::create_go_project(&project_name, &output_dir),
         }
     }
 
     fn prompt_language() -> Result<Language> {
-        let languages = ["Rust", "TypeScript"];
+        let languages = ["Rust", "TypeScript", "Go"];
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Select a language")
             .items(&languages)
@@ -56,7 +61,8 @@ impl InitCommand {
 
         Ok(match selection {
             0 => Language::Rust,
-            _ => Language::TypeScript,
+            1 => Language::TypeScript,
+            _ => Language::Go,
         })
     }
 
@@ -155,13 +161,13 @@ pub fn run(_ctx: &Context, args: HelloArgs) -> eyre::Result<()> {
         PackageJson::new(name).write(output_dir)?;
 
         // Create tsconfig.json
-        TsConfig.write(output_dir)?;
+        TsConfig::new().write(output_dir)?;
 
         // Create .gitignore
         TsGitIgnore.write(output_dir)?;
 
         // Create index.ts
-        IndexTs.write(output_dir)?;
+        IndexTs::new().write(output_dir)?;
 
         // Create handlers/hello.ts with a working example
         std::fs::create_dir_all(output_dir.join("src").join("handlers"))?;
@@ -215,4 +221,80 @@ export async function run(ctx: Context, args: HelloArgs): Promise<void> {
 
         Ok(())
     }
+
+    fn create_go_project(name: &str, output_dir: &Path) -> Result<()> {
+        // Create bao.toml
+        BaoToml::new(name, Language::Go).write(output_dir)?;
+
+        // Create go.mod
+        GoMod::new(name).write(output_dir)?;
+
+        // Create .gitignore
+        GoGitIgnore::new(name).write(output_dir)?;
+
+        // Create main.go
+        MainGo::new(name).write(output_dir)?;
+
+        // Create handlers/hello.go with a working example
+        File::new(
+            output_dir.join("handlers").join("hello.go"),
+            format!(
+                r#"package handlers
+
+import (
+	"fmt"
+	"strings"
+
+	"{name}/internal/app"
+	"{name}/internal/generated"
+)
+
+// Hello handles `{name} hello`.
+func Hello(ctx *app.Context, args generated.HelloArgs) error {{
+	name := args.Name
+	if name == "" {{
+		name = "World"
+	}}
+	greeting := fmt.Sprintf("Hello, %s!", name)
+
+	if args.Uppercase {{
+		greeting = strings.ToUpper(greeting)
+	}}
+	fmt.Println(greeting)
+
+	return nil
+}}
+"#
+            ),
+        )
+        .write()?;
+
+        // Generate code from bao.toml
+        let bao_toml_path = output_dir.join("bao.toml");
+        let schema = match Manifest::from_file(&bao_toml_path) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("{:?}", Report::new(*e));
+                std::process::exit(1);
+            }
+        };
+
+        let pipeline = Pipeline::new();
+        let ctx = pipeline.run(schema).wrap_err("Pipeline failed")?;
+        let generator = GoGenerator::from_context(ctx);
+        let _ = generator
+            .generate(output_dir)
+            .wrap_err("Failed to generate code")?;
+
+        println!("Created new Go CLI project in {}", output_dir.display());
+        println!();
+        println!("Next steps:");
+        if output_dir != Path::new(".") {
+            println!("  cd {}", output_dir.display());
+        }
+        println!("  go mod tidy");
+        println!("  go run . hello --help");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use baobao_codegen::pipeline::Pipeline;
+use baobao_core::write_file;
+use baobao_manifest::BaoToml;
+use clap::Args;
+use eyre::{Context, Result};
+
+use super::UnwrapOrExit;
+
+#[derive(Args)]
+pub struct IrCommand {
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
+    pub config: PathBuf,
+
+    /// Write the IR to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl IrCommand {
+    pub fn run(&self) -> Result<()> {
+        let bao_toml = BaoToml::open(&self.config).unwrap_or_exit();
+        let manifest = bao_toml.schema();
+
+        let ctx = Pipeline::new()
+            .run(manifest.clone())
+            .wrap_err("Pipeline failed")?;
+        let ir = ctx.ir.as_ref().expect("AppIR should be set");
+        let json = ir.to_json().wrap_err("Failed to serialize IR")?;
+
+        match &self.output {
+            Some(path) => {
+                write_file(path, &format!("{}\n", json))?;
+                println!("Wrote IR to {}", path.display());
+            }
+            None => println!("{}", json),
+        }
+
+        Ok(())
+    }
+}
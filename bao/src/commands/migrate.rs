@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use baobao_manifest::{Manifest, ManifestEditor, ManifestFormat, SCHEMA_VERSION};
+use clap::Args;
+use eyre::{Context, Result, bail};
+
+use super::UnwrapOrExit;
+
+#[derive(Args)]
+pub struct MigrateCommand {
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
+    pub config: PathBuf,
+
+    /// Show what would change without writing bao.toml
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl MigrateCommand {
+    pub fn run(&self) -> Result<()> {
+        if ManifestFormat::from_path(&self.config) != ManifestFormat::Toml {
+            bail!(
+                "cannot migrate {}: only TOML manifests can be migrated",
+                self.config.display()
+            );
+        }
+
+        // Older manifests may not parse as the current schema, so the raw
+        // document is migrated and only the result is validated
+        let content = std::fs::read_to_string(&self.config)
+            .wrap_err_with(|| format!("Failed to read {}", self.config.display()))?;
+        let filename = self.config.display().to_string();
+        let mut editor = ManifestEditor::parse_with_filename(&content, &filename).unwrap_or_exit();
+        let changes = editor.migrate().unwrap_or_exit();
+
+        if changes.is_empty() {
+            println!(
+                "{} is already at schema version {}",
+                self.config.display(),
+                SCHEMA_VERSION
+            );
+            return Ok(());
+        }
+
+        let migrated = editor.to_string();
+        Manifest::from_str_with_filename(&migrated, &filename).unwrap_or_exit();
+        if self.dry_run {
+            println!("Would migrate {}:", self.config.display());
+        } else {
+            std::fs::write(&self.config, &migrated)?;
+            println!("Migrated {}:", self.config.display());
+        }
+        for change in &changes {
+            println!("  - {}", change);
+        }
+
+        Ok(())
+    }
+}
@@ -7,10 +7,16 @@ mod explain;
 mod fmt;
 mod info;
 mod init;
+mod ir;
 mod list;
+mod migrate;
 mod remove;
 mod rename;
 mod run;
+mod schema;
+mod version;
+
+use std::path::PathBuf;
 
 use add::AddCommand;
 use bake::BakeCommand;
@@ -23,10 +29,20 @@ use eyre::Result;
 use fmt::FmtCommand;
 use info::InfoCommand;
 use init::InitCommand;
+use ir::IrCommand;
 use list::ListCommand;
+use migrate::MigrateCommand;
 use remove::RemoveCommand;
 use rename::RenameCommand;
 use run::RunCommand;
+use schema::SchemaCommand;
+use version::VersionCommand;
+
+/// The manifest in the current directory: bao.toml, bao.yaml, bao.yml or
+/// bao.json, whichever exists first.
+fn default_manifest() -> PathBuf {
+    baobao_manifest::find_manifest("")
+}
 
 /// Extension trait for exiting on manifest errors with pretty formatting
 pub(crate) trait UnwrapOrExit<T> {
@@ -63,13 +79,17 @@ impl Cli {
             Commands::Clean(cmd) => cmd.run(),
             Commands::Explain(cmd) => cmd.run(),
             Commands::Fmt(cmd) => cmd.run(),
+            Commands::Migrate(cmd) => cmd.run(),
             Commands::Info(cmd) => cmd.run(),
+            Commands::Ir(cmd) => cmd.run(),
+            Commands::Schema(cmd) => cmd.run(),
             Commands::Add(cmd) => cmd.run(),
             Commands::Remove(cmd) => cmd.run(),
             Commands::Rename(cmd) => cmd.run(),
             Commands::List(cmd) => cmd.run(),
             Commands::Completions(cmd) => cmd.run(),
             Commands::Run(cmd) => cmd.run(),
+            Commands::Version(cmd) => cmd.run(),
         }
     }
 }
@@ -94,9 +114,18 @@ enum Commands {
     /// Format bao.toml
     Fmt(FmtCommand),
 
+    /// Upgrade bao.toml to the latest schema version
+    Migrate(MigrateCommand),
+
     /// Show project information
     Info(InfoCommand),
 
+    /// Print the lowered application IR as JSON
+    Ir(IrCommand),
+
+    /// Print the JSON Schema of bao.toml, for editor validation
+    Schema(SchemaCommand),
+
     /// Add a command or context to bao.toml
     Add(AddCommand),
 
@@ -114,4 +143,7 @@ enum Commands {
 
     /// Run the generated CLI (shortcut for cargo run --)
     Run(RunCommand),
+
+    /// Manage the CLI version
+    Version(VersionCommand),
 }
@@ -1,8 +1,6 @@
 use std::path::PathBuf;
 
-use baobao_manifest::{
-    BaoToml, command_section_header, context_section_header, remove_toml_section,
-};
+use baobao_manifest::BaoToml;
 use clap::{Args, Subcommand};
 use eyre::{Result, bail};
 
@@ -27,7 +25,7 @@ struct RemoveCommandArgs {
     name: String,
 
     /// Path to bao.toml
-    #[arg(short, long, default_value = "bao.toml")]
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     config: PathBuf,
 }
 
@@ -37,7 +35,7 @@ struct RemoveContextArgs {
     name: String,
 
     /// Path to bao.toml
-    #[arg(short, long, default_value = "bao.toml")]
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     config: PathBuf,
 }
 
@@ -56,9 +54,7 @@ impl RemoveCommand {
             bail!("Command '{}' does not exist", args.name);
         }
 
-        let new_content =
-            remove_toml_section(bao_toml.content(), &command_section_header(&args.name));
-        bao_toml.set_content(new_content)?;
+        bao_toml.edit(|editor| editor.remove_command(&args.name))?;
         bao_toml.save()?;
         println!("Removed command '{}'", args.name);
 
@@ -72,9 +68,7 @@ impl RemoveCommand {
             bail!("Context field '{}' does not exist", args.name);
         }
 
-        let new_content =
-            remove_toml_section(bao_toml.content(), &context_section_header(&args.name));
-        bao_toml.set_content(new_content)?;
+        bao_toml.edit(|editor| editor.remove_context(&args.name))?;
         bao_toml.save()?;
         println!("Removed context '{}'", args.name);
 
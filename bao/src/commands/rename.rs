@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
-use baobao_manifest::{BaoToml, rename_command_section};
+use baobao_core::join_relative;
+use baobao_manifest::BaoToml;
 use clap::{Args, Subcommand};
 use eyre::{Context, Result};
 
@@ -27,7 +28,7 @@ struct RenameCommandArgs {
     new_name: String,
 
     /// Path to bao.toml
-    #[arg(short, long, default_value = "bao.toml")]
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
     config: PathBuf,
 
     /// Output directory containing src/handlers
@@ -82,9 +83,7 @@ impl RenameCommand {
         }
 
         // Update bao.toml
-        let new_content =
-            rename_command_section(bao_toml.content(), &args.old_name, &args.new_name);
-        bao_toml.set_content(new_content)?;
+        bao_toml.edit(|editor| editor.rename_command(&args.old_name, &args.new_name))?;
         bao_toml.save()?;
 
         // Rename handler file/directory
@@ -113,24 +112,8 @@ fn validate_command_name(name: &str) -> Option<&'static str> {
         return Some("name cannot be empty");
     }
 
-    // Check reserved keywords
-    const RUST_KEYWORDS: &[&str] = &[
-        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
-        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
-        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
-        "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
-        "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
-    ];
-
-    if RUST_KEYWORDS.contains(&name) {
-        return Some("name is a Rust reserved keyword");
-    }
-
-    // Also check snake_case version
-    let snake = name.replace('-', "_");
-    if RUST_KEYWORDS.contains(&snake.as_str()) {
-        return Some("name converts to a Rust reserved keyword");
-    }
+    // Reserved words depend on the target languages and are checked when
+    // the edited manifest is re-parsed
 
     // Check first character
     let mut chars = name.chars().peekable();
@@ -180,19 +163,19 @@ fn rename_handler(
     old_name: &str,
     new_name: &str,
 ) -> Result<Option<(PathBuf, PathBuf)>> {
-    let handlers_dir = output.join("src/handlers");
+    let handlers_dir = join_relative(output, "src/handlers");
 
     // Convert command paths to file paths
     let old_segments: Vec<String> = old_name.split('/').map(to_snake_case).collect();
     let new_segments: Vec<String> = new_name.split('/').map(to_snake_case).collect();
 
     // Build paths
-    let old_file = handlers_dir.join(format!("{}.rs", old_segments.join("/")));
-    let new_file = handlers_dir.join(format!("{}.rs", new_segments.join("/")));
+    let old_file = join_relative(&handlers_dir, &format!("{}.rs", old_segments.join("/")));
+    let new_file = join_relative(&handlers_dir, &format!("{}.rs", new_segments.join("/")));
 
     // Check for directory (parent command with subcommands)
-    let old_dir = handlers_dir.join(old_segments.join("/"));
-    let new_dir = handlers_dir.join(new_segments.join("/"));
+    let old_dir = join_relative(&handlers_dir, &old_segments.join("/"));
+    let new_dir = join_relative(&handlers_dir, &new_segments.join("/"));
 
     // Try to rename file first
     if old_file.exists() {
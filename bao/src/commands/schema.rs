@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use baobao_core::write_file;
+use clap::Args;
+use eyre::{Context, Result};
+
+#[derive(Args)]
+pub struct SchemaCommand {
+    /// Write the schema to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl SchemaCommand {
+    pub fn run(&self) -> Result<()> {
+        let schema = baobao_manifest::json_schema();
+        let json = serde_json::to_string_pretty(&schema).wrap_err("Failed to serialize schema")?;
+
+        match &self.output {
+            Some(path) => {
+                write_file(path, &format!("{}\n", json))?;
+                println!("Wrote schema to {}", path.display());
+            }
+            None => println!("{}", json),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::PathBuf;
+
+use baobao::{
+    ops,
+    reports::{Report, TerminalOutput},
+};
+use baobao_manifest::BaoToml;
+use clap::{Args, Subcommand, ValueEnum};
+use eyre::{Result, eyre};
+
+use super::UnwrapOrExit;
+
+#[derive(Args)]
+pub struct VersionCommand {
+    #[command(subcommand)]
+    command: VersionSubcommand,
+}
+
+#[derive(Subcommand)]
+enum VersionSubcommand {
+    /// Bump the CLI version in bao.toml and the generated project manifests
+    Bump(BumpArgs),
+}
+
+#[derive(Args)]
+struct BumpArgs {
+    /// Version component to increment
+    level: BumpLevel,
+
+    /// Pre-release identifiers for the new version (e.g., "rc.1")
+    #[arg(long)]
+    pre: Option<String>,
+
+    /// Path to the manifest (defaults to bao.toml, bao.yaml or bao.json)
+    #[arg(short, long, default_value_os_t = super::default_manifest())]
+    config: PathBuf,
+
+    /// Output directory of the generated project (defaults to current directory)
+    #[arg(short, long, default_value = ".")]
+    output: PathBuf,
+
+    /// Show what would change without writing files
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BumpLevel {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl VersionCommand {
+    pub fn run(&self) -> Result<()> {
+        match &self.command {
+            VersionSubcommand::Bump(args) => Self::bump(args),
+        }
+    }
+
+    fn bump(args: &BumpArgs) -> Result<()> {
+        let mut bao_toml = BaoToml::open(&args.config).unwrap_or_exit();
+
+        let current = &bao_toml.schema().cli.version;
+        let mut version = match args.level {
+            BumpLevel::Major => current.bump_major(),
+            BumpLevel::Minor => current.bump_minor(),
+            BumpLevel::Patch => current.bump_patch(),
+        };
+        if let Some(pre) = &args.pre {
+            version = version.with_pre(pre).map_err(|e| eyre!(e))?;
+        }
+
+        let report = ops::version::bump(
+            &mut bao_toml,
+            version,
+            ops::version::BumpOptions {
+                output_dir: &args.output,
+                dry_run: args.dry_run,
+            },
+        )?;
+
+        report.render(&mut TerminalOutput::new());
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Programmatic generation, the library counterpart of `bao bake`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use baobao_codegen::pipeline::Pipeline;
+use baobao_core::{Overwrite, WriteResult, checksum};
+use baobao_manifest::{BaoToml, Language, Manifest, Target};
+use eyre::{Context, Result};
+
+use crate::{
+    language::LanguageSupport,
+    ops::{self, bake::BakeOptions},
+    reports::GenerationResult,
+};
+
+/// Options for [`generate`].
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Directory the targets are generated under. Defaults to the directory
+    /// containing the manifest.
+    pub output_dir: Option<PathBuf>,
+    /// Generate only this language, ignoring the manifest's `[[targets]]`.
+    pub language: Option<Language>,
+    /// Apply this `[profile.<name>]` of the manifest.
+    pub profile: Option<String>,
+}
+
+/// Outcome of [`generate`], one report per target.
+#[derive(Debug)]
+pub struct GenerationReport {
+    pub targets: Vec<TargetReport>,
+}
+
+/// Files written for one target.
+#[derive(Debug)]
+pub struct TargetReport {
+    /// Target name (the language name for manifests without `[[targets]]`).
+    pub name: String,
+    /// Target language.
+    pub language: Language,
+    /// Directory the target was generated into.
+    pub output_dir: PathBuf,
+    /// Warnings from the pipeline.
+    pub warnings: Vec<String>,
+    /// Generated files, relative to `output_dir`, with their write results.
+    pub files: Vec<(String, WriteResult)>,
+    /// Generated files that had been edited by hand and were overwritten.
+    pub overwritten_edits: Vec<String>,
+    /// Handler stubs created for new commands, relative to the handlers directory.
+    pub created_handlers: Vec<String>,
+    /// Handlers no longer used by any command, relative to the handlers directory.
+    pub orphan_handlers: Vec<String>,
+}
+
+/// Generate code for every target of the manifest at `manifest_path`.
+///
+/// Behaves like `bao bake`: generated files are overwritten, handler stubs
+/// are only created, and `bao.lock` is updated. Manifest errors are returned
+/// rather than printed.
+pub fn generate(
+    manifest_path: impl AsRef<Path>,
+    options: &GenerateOptions,
+) -> Result<GenerationReport> {
+    let manifest_path = manifest_path.as_ref();
+    let bao_toml = BaoToml::open(manifest_path)?;
+    let manifest = bao_toml.schema();
+
+    let mut reports = Vec::new();
+    for (target, output_dir) in targets(manifest_path, manifest, options) {
+        let report = ops::bake(
+            &manifest.for_target(&target),
+            LanguageSupport::get(target.language),
+            BakeOptions {
+                output_dir: &output_dir,
+                dry_run: false,
+                visualize: false,
+                profile: options.profile.as_deref(),
+                prompt: None,
+            },
+        )?;
+        let GenerationResult::Written(written) = report.result else {
+            unreachable!("bake without dry_run writes files");
+        };
+
+        reports.push(TargetReport {
+            name: target.name,
+            language: target.language,
+            output_dir,
+            warnings: report.warnings,
+            files: written.files,
+            overwritten_edits: written.overwritten_edits,
+            created_handlers: written.handlers.created,
+            orphan_handlers: written.handlers.orphans,
+        });
+    }
+
+    Ok(GenerationReport { targets: reports })
+}
+
+/// Whether generating the manifest at `manifest_path` would leave every
+/// target unchanged.
+///
+/// Generated files must match what the manifest produces, and handler stubs
+/// must exist. Nothing is written.
+pub fn is_up_to_date(manifest_path: impl AsRef<Path>, options: &GenerateOptions) -> Result<bool> {
+    let manifest_path = manifest_path.as_ref();
+    let bao_toml = BaoToml::open(manifest_path)?;
+    let manifest = bao_toml.schema();
+
+    for (target, output_dir) in targets(manifest_path, manifest, options) {
+        let mut pipeline = Pipeline::new();
+        if let Some(profile) = &options.profile {
+            pipeline = pipeline.profile(profile);
+        }
+        let ctx = pipeline
+            .run(manifest.for_target(&target))
+            .wrap_err("Pipeline failed")?;
+        let files = LanguageSupport::get(target.language)
+            .generator(ctx)
+            .files()
+            .wrap_err("Failed to generate code")?;
+        for file in files {
+            let up_to_date = match fs::read(file.full_path(&output_dir)) {
+                Ok(_) if file.overwrite() == Overwrite::IfMissing => true,
+                Ok(content) => checksum(&content) == file.checksum(),
+                Err(_) => false,
+            };
+            if !up_to_date {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Targets to generate, with their output directories.
+fn targets(
+    manifest_path: &Path,
+    manifest: &Manifest,
+    options: &GenerateOptions,
+) -> Vec<(Target, PathBuf)> {
+    let base = match &options.output_dir {
+        Some(dir) => dir.clone(),
+        None => match manifest_path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        },
+    };
+    let targets = match options.language {
+        Some(language) => vec![Target::implicit(language, manifest.typescript.clone())],
+        None => manifest.targets(),
+    };
+    targets
+        .into_iter()
+        .map(|target| {
+            let output_dir = target.output_dir(&base);
+            (target, output_dir)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_next_to_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("bao.toml");
+        fs::write(
+            &manifest,
+            r#"
+            [cli]
+            name = "app"
+            language = "go"
+
+            [commands.hello]
+            description = "Say hello"
+            "#,
+        )
+        .unwrap();
+
+        let report = generate(&manifest, &GenerateOptions::default()).unwrap();
+
+        let target = &report.targets[0];
+        assert_eq!(target.language, Language::Go);
+        assert_eq!(target.created_handlers, ["hello.go"]);
+        assert!(
+            target
+                .files
+                .iter()
+                .any(|(path, result)| path == "cmd/root.go" && result.is_created())
+        );
+        assert!(dir.path().join("handlers/hello.go").exists());
+        assert!(dir.path().join("bao.lock").exists());
+    }
+
+    #[test]
+    fn test_is_up_to_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("bao.toml");
+        fs::write(
+            &manifest,
+            r#"
+            [cli]
+            name = "app"
+            language = "go"
+
+            [commands.hello]
+            description = "Say hello"
+            "#,
+        )
+        .unwrap();
+        let options = GenerateOptions::default();
+
+        assert!(!is_up_to_date(&manifest, &options).unwrap());
+        generate(&manifest, &options).unwrap();
+        assert!(is_up_to_date(&manifest, &options).unwrap());
+
+        // Handlers are the user's to edit
+        fs::write(dir.path().join("handlers/hello.go"), "package handlers\n").unwrap();
+        assert!(is_up_to_date(&manifest, &options).unwrap());
+
+        fs::remove_file(dir.path().join("cmd/root.go")).unwrap();
+        assert!(!is_up_to_date(&manifest, &options).unwrap());
+    }
+}
@@ -3,6 +3,7 @@
 //! Centralizes language-specific generator creation and metadata.
 
 use baobao_codegen::{language::LanguageCodegen, pipeline::CompilationContext};
+use baobao_codegen_go::Generator as GoGenerator;
 use baobao_codegen_rust::Generator as RustGenerator;
 use baobao_codegen_typescript::Generator as TypeScriptGenerator;
 use baobao_manifest::Language;
@@ -16,6 +17,8 @@ pub struct LanguageSupport {
     pub gen_subdir: &'static str,
     /// File extension with dot (e.g., ".rs").
     pub extension: &'static str,
+    /// Directory holding handler files (e.g., "src/handlers/").
+    pub handlers_dir: &'static str,
 }
 
 impl LanguageSupport {
@@ -26,11 +29,19 @@ impl LanguageSupport {
                 language,
                 gen_subdir: "src/generated/",
                 extension: ".rs",
+                handlers_dir: "src/handlers/",
             },
             Language::TypeScript => Self {
                 language,
                 gen_subdir: "src/",
                 extension: ".ts",
+                handlers_dir: "src/handlers/",
+            },
+            Language::Go => Self {
+                language,
+                gen_subdir: "cmd/",
+                extension: ".go",
+                handlers_dir: "handlers/",
             },
         }
     }
@@ -40,6 +51,7 @@ impl LanguageSupport {
         match self.language {
             Language::Rust => Box::new(RustGenerator::from_context(ctx)),
             Language::TypeScript => Box::new(TypeScriptGenerator::from_context(ctx)),
+            Language::Go => Box::new(GoGenerator::from_context(ctx)),
         }
     }
 }
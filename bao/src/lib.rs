@@ -0,0 +1,30 @@
+//! Generate type-safe CLI applications from a `bao.toml` manifest.
+//!
+//! Besides the `bao` binary, this crate exposes a library API so build
+//! tools, editors and tests can drive generation without shelling out:
+//!
+//! ```no_run
+//! use baobao::{GenerateOptions, generate};
+//!
+//! let report = generate("bao.toml", &GenerateOptions::default())?;
+//! for target in &report.targets {
+//!     println!("{}: {} files", target.name, target.files.len());
+//! }
+//! # Ok::<(), eyre::Report>(())
+//! ```
+//!
+//! Only the items exported from the crate root are part of the stable API.
+
+mod generate;
+
+// Shared with the bao binary; not part of the stable API.
+#[doc(hidden)]
+pub mod language;
+#[doc(hidden)]
+pub mod ops;
+#[doc(hidden)]
+pub mod reports;
+
+pub use baobao_core::{LineChanges, WriteResult};
+pub use baobao_manifest::Language;
+pub use generate::{GenerateOptions, GenerationReport, TargetReport, generate, is_up_to_date};
@@ -1,7 +1,5 @@
 mod commands;
-mod language;
-mod ops;
-mod reports;
+mod prompt;
 
 use clap::Parser;
 use eyre::Result;
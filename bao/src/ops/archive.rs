@@ -0,0 +1,109 @@
+//! Archive operation - code generation into a .tar.gz instead of the filesystem.
+
+use std::{io::Write, path::Path};
+
+use baobao_codegen::{
+    generation::FileCategory,
+    pipeline::{Pipeline, Severity},
+};
+use baobao_core::{LOCK_FILE, Lockfile, Overwrite, join_relative, to_slash_path};
+use baobao_manifest::Manifest;
+use eyre::{Context, Result};
+use flate2::{Compression, write::GzEncoder};
+
+use crate::{language::LanguageSupport, reports::ArchiveReport};
+
+/// Writes generated files into a gzip-compressed tarball.
+///
+/// Entries have a fixed modification time so that baking the same manifest
+/// twice produces identical archives.
+pub struct Archive<W: Write> {
+    builder: tar::Builder<GzEncoder<W>>,
+    report: ArchiveReport,
+    profile: Option<String>,
+}
+
+impl<W: Write> Archive<W> {
+    /// Start an archive written to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            builder: tar::Builder::new(GzEncoder::new(writer, Compression::default())),
+            report: ArchiveReport::default(),
+            profile: None,
+        }
+    }
+
+    /// Apply a manifest profile to every target.
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Generate code for one target and add every file under `prefix`.
+    ///
+    /// Handler stubs are included, since the archive starts from an empty tree.
+    pub fn add(&mut self, manifest: &Manifest, lang: LanguageSupport, prefix: &Path) -> Result<()> {
+        let mut pipeline = Pipeline::new();
+        if let Some(profile) = &self.profile {
+            pipeline = pipeline.profile(profile);
+        }
+        let ctx = pipeline.run(manifest.clone()).wrap_err("Pipeline failed")?;
+        self.report.warnings.extend(
+            ctx.diagnostics
+                .iter()
+                .filter(|d| matches!(d.severity, Severity::Warning))
+                .map(|d| d.message.clone()),
+        );
+
+        let files = lang
+            .generator(ctx)
+            .files()
+            .wrap_err("Failed to generate code")?;
+        for file in &files {
+            self.append(
+                &file.full_path(prefix),
+                file.content.as_bytes(),
+                file.mode.unwrap_or(0o644),
+            )?;
+        }
+
+        // Record the generated files, as a bake to disk would
+        let lock = Lockfile::new(
+            files
+                .iter()
+                .filter(|file| {
+                    file.category != FileCategory::Handler && file.overwrite() == Overwrite::Always
+                })
+                .map(|file| (file.path.clone(), file.checksum())),
+        );
+        self.append(
+            &join_relative(prefix, LOCK_FILE),
+            lock.render()?.as_bytes(),
+            0o644,
+        )
+    }
+
+    fn append(&mut self, path: &Path, content: &[u8], mode: u32) -> Result<()> {
+        let path = to_slash_path(path);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(mode);
+        header.set_mtime(0);
+        self.builder
+            .append_data(&mut header, &path, content)
+            .wrap_err_with(|| format!("Failed to archive {}", path))?;
+        self.report.files.push(path);
+        Ok(())
+    }
+
+    /// Finish the archive and flush the underlying writer.
+    pub fn finish(self) -> Result<ArchiveReport> {
+        let mut writer = self
+            .builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .wrap_err("Failed to write archive")?;
+        writer.flush()?;
+        Ok(self.report)
+    }
+}
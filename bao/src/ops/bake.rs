@@ -6,6 +6,7 @@ use baobao_codegen::{
     pipeline::{Pipeline, Severity, SnapshotPlugin},
     schema::{CommandTree, DisplayStyle},
 };
+use baobao_core::{Lockfile, OverwritePrompt, WriteResult, join_relative};
 use baobao_manifest::Manifest;
 use eyre::{Context, Result};
 
@@ -24,6 +25,10 @@ pub struct BakeOptions<'a> {
     pub dry_run: bool,
     /// Whether to output debug snapshots.
     pub visualize: bool,
+    /// Manifest profile to apply.
+    pub profile: Option<&'a str>,
+    /// Asks before replacing generated files that differ on disk.
+    pub prompt: Option<&'a mut dyn OverwritePrompt>,
 }
 
 /// Execute the bake operation.
@@ -31,7 +36,7 @@ pub struct BakeOptions<'a> {
 /// Runs the pipeline on the manifest and generates code for the target language.
 pub fn bake(manifest: &Manifest, lang: LanguageSupport, opts: BakeOptions) -> Result<BakeReport> {
     // Set up the pipeline with optional visualization
-    let debug_dir = opts.output_dir.join(".bao/debug");
+    let debug_dir = join_relative(opts.output_dir, ".bao/debug");
     let snapshot_plugin = if opts.visualize {
         Some(SnapshotPlugin::with_output_dir(&debug_dir))
     } else {
@@ -43,6 +48,9 @@ pub fn bake(manifest: &Manifest, lang: LanguageSupport, opts: BakeOptions) -> Re
     if let Some(plugin) = snapshot_plugin {
         pipeline = pipeline.plugin(plugin);
     }
+    if let Some(profile) = opts.profile {
+        pipeline = pipeline.profile(profile);
+    }
     let ctx = pipeline.run(manifest.clone()).wrap_err("Pipeline failed")?;
 
     // Collect warnings
@@ -74,17 +82,46 @@ pub fn bake(manifest: &Manifest, lang: LanguageSupport, opts: BakeOptions) -> Re
             .collect();
         GenerationResult::Preview(PreviewResult { files })
     } else {
+        // Files edited by hand since the last bake, detected from bao.lock
+        let lock = Lockfile::load(opts.output_dir)?;
+        let edited = lock.modified_files(opts.output_dir);
+
         let gen_result = generator
-            .generate(opts.output_dir)
+            .generate_with_prompt(opts.output_dir, opts.prompt)
             .wrap_err("Failed to generate code")?;
 
+        // Keep the previous checksum of files the user chose not to overwrite
+        let mut checksums = gen_result.checksums;
+        for (path, result) in &gen_result.files {
+            if *result == WriteResult::Skipped
+                && let Some(recorded) = lock.get(path)
+            {
+                checksums.insert(path.clone(), recorded.to_string());
+            }
+        }
+        Lockfile::new(checksums)
+            .save(opts.output_dir)
+            .wrap_err("Failed to write bao.lock")?;
+
+        let overwritten_edits = gen_result
+            .files
+            .iter()
+            .filter(|(path, result)| {
+                matches!(result, WriteResult::Updated(_)) && edited.contains(path)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+
         GenerationResult::Written(WrittenResult {
             output_dir: opts.output_dir.to_path_buf(),
             gen_subdir: lang.gen_subdir.to_string(),
+            files: gen_result.files,
+            overwritten_edits,
             handlers: HandlerChanges {
                 created: gen_result.created_handlers,
                 orphans: gen_result.orphan_handlers,
                 extension: lang.extension.to_string(),
+                dir: lang.handlers_dir.to_string(),
             },
             debug_dir: if opts.visualize {
                 Some(debug_dir)
@@ -97,7 +134,7 @@ pub fn bake(manifest: &Manifest, lang: LanguageSupport, opts: BakeOptions) -> Re
     Ok(BakeReport {
         cli_name: manifest.cli.name.clone(),
         cli_version: manifest.cli.version.to_string(),
-        cli_description: manifest.cli.description.clone(),
+        cli_description: manifest.cli.description.clone().map(Into::into),
         warnings,
         command_count,
         command_tree,
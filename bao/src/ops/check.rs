@@ -1,16 +1,22 @@
 //! Check operation - manifest validation.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use baobao_codegen::pipeline::{Pipeline, Severity};
-use baobao_manifest::Manifest;
+use baobao_codegen::{
+    AppIR,
+    pipeline::{Pipeline, Severity},
+};
+use baobao_core::{to_kebab_case, to_snake_case};
+use baobao_manifest::{Language, Manifest, ManifestFormat, SCHEMA_VERSION};
 use eyre::{Context, Result};
 
-use crate::reports::CheckReport;
+use crate::{language::LanguageSupport, reports::CheckReport};
 
 /// Execute the check operation.
 ///
-/// Runs the pipeline to validate the manifest and returns diagnostics.
+/// Runs the pipeline to validate the manifest and returns diagnostics, with
+/// a warning when the manifest was written for an older schema version, and
+/// one for each handler using a context field its command doesn't require.
 pub fn check(manifest: &Manifest, config_path: &Path) -> Result<CheckReport> {
     let pipeline = Pipeline::new();
     let ctx = pipeline
@@ -22,8 +28,8 @@ pub fn check(manifest: &Manifest, config_path: &Path) -> Result<CheckReport> {
     let mut infos = Vec::new();
 
     for diag in &ctx.diagnostics {
-        let msg = if let Some(loc) = &diag.location {
-            format!("{}\n  --> {}", diag.message, loc)
+        let msg = if let Some(position) = diag.position() {
+            format!("{}\n  --> {}", diag.message, position)
         } else {
             diag.message.clone()
         };
@@ -35,6 +41,38 @@ pub fn check(manifest: &Manifest, config_path: &Path) -> Result<CheckReport> {
         }
     }
 
+    if let Some(ir) = ctx.ir.as_ref().filter(|ir| ir.has_requirements()) {
+        let base = match config_path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        for target in manifest.targets() {
+            warnings.extend(undeclared_requirements(
+                ir,
+                target.language,
+                &target.output_dir(&base),
+            ));
+        }
+    }
+
+    let schema_version = manifest.schema_version();
+    if schema_version < SCHEMA_VERSION {
+        let upgrade = match ManifestFormat::from_path(config_path) {
+            ManifestFormat::Toml => "run `bao migrate` to upgrade it".to_string(),
+            _ => format!(
+                "set schema_version to {} once it is up to date",
+                SCHEMA_VERSION
+            ),
+        };
+        warnings.push(format!(
+            "{} uses schema version {}, the latest is {}; {}",
+            config_path.display(),
+            schema_version,
+            SCHEMA_VERSION,
+            upgrade
+        ));
+    }
+
     Ok(CheckReport {
         config_path: config_path.to_path_buf(),
         errors,
@@ -42,3 +80,121 @@ pub fn check(manifest: &Manifest, config_path: &Path) -> Result<CheckReport> {
         infos,
     })
 }
+
+/// Warn about handlers that use `ctx.<field>` without their command requiring it.
+///
+/// Go contexts are never lazy, so only Rust and TypeScript handlers are read.
+/// Handlers that don't exist yet are skipped.
+fn undeclared_requirements(ir: &AppIR, language: Language, output_dir: &Path) -> Vec<String> {
+    let naming: fn(&str) -> String = match language {
+        Language::Rust => to_snake_case,
+        Language::TypeScript => to_kebab_case,
+        Language::Go => return Vec::new(),
+    };
+    let lang = LanguageSupport::get(language);
+    let fields: Vec<String> = ir.context_fields().into_iter().map(|f| f.name).collect();
+
+    let mut leaves = Vec::new();
+    let mut stack: Vec<_> = ir.commands().collect();
+    while let Some(cmd) = stack.pop() {
+        if cmd.has_subcommands() {
+            stack.extend(&cmd.children);
+        } else {
+            leaves.push(cmd);
+        }
+    }
+    leaves.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut warnings = Vec::new();
+    for cmd in leaves {
+        let file = format!(
+            "{}{}{}",
+            lang.handlers_dir,
+            cmd.path
+                .iter()
+                .map(|s| naming(s))
+                .collect::<Vec<_>>()
+                .join("/"),
+            lang.extension
+        );
+        let Ok(source) = std::fs::read_to_string(output_dir.join(&file)) else {
+            continue;
+        };
+        for field in &fields {
+            if !cmd.requires.contains(field) && uses_field(&source, field) {
+                warnings.push(format!(
+                    "{} uses ctx.{}, which is not in the requires of '{}'",
+                    output_dir.join(&file).display(),
+                    field,
+                    cmd.path.join(" ")
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Whether `source` reads `ctx.<field>`, and not just a field it prefixes.
+fn uses_field(source: &str, field: &str) -> bool {
+    let access = format!("ctx.{}", field);
+    source.match_indices(&access).any(|(i, _)| {
+        let before = source[..i].chars().next_back();
+        let after = source[i + access.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, str::FromStr};
+
+    use super::*;
+
+    #[test]
+    fn test_check_undeclared_requirements() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("bao.toml");
+        let manifest = Manifest::from_str(
+            r#"
+            schema_version = 1
+
+            [cli]
+            name = "app"
+            language = "rust"
+
+            [context.database]
+            type = "sqlite"
+
+            [context.cache]
+
+            [commands.db]
+            description = "Database commands"
+            requires = ["database"]
+
+            [commands.db.commands.seed]
+            description = "Seed the database"
+
+            [commands.fetch]
+            description = "Fetch a page"
+            "#,
+        )
+        .unwrap();
+        let handlers = dir.path().join("src/handlers");
+        fs::create_dir_all(handlers.join("db")).unwrap();
+        fs::write(
+            handlers.join("db/seed.rs"),
+            "let pool = ctx.db()?;\nlet cache = ctx.cache().await?;",
+        )
+        .unwrap();
+        fs::write(handlers.join("fetch.rs"), "let dbx = ctx.dbx;").unwrap();
+
+        let report = check(&manifest, &config_path).unwrap();
+
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(
+            report.warnings[0]
+                .ends_with("uses ctx.cache, which is not in the requires of 'db seed'")
+        );
+    }
+}
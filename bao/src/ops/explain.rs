@@ -3,7 +3,7 @@
 use std::path::Path;
 
 use baobao_codegen::pipeline::{Pipeline, phases::ValidatePhase};
-use baobao_core::{ContextFieldType, DatabaseType};
+use baobao_core::{ContextFieldType, DatabaseType, QueueType};
 use baobao_manifest::{Language, Manifest};
 use eyre::{Context, Result};
 
@@ -53,7 +53,10 @@ pub fn explain(manifest: &Manifest, config_path: &Path) -> Result<ExplainReport>
         .iter()
         .map(|f| ContextFieldInfo {
             name: f.name.clone(),
-            type_name: field_type_name(&f.field_type).to_string(),
+            type_name: match &f.custom {
+                Some(custom) => custom.kind.clone(),
+                None => field_type_name(&f.field_type).to_string(),
+            },
             env_var: f.env_var.clone(),
         })
         .collect();
@@ -63,7 +66,12 @@ pub fn explain(manifest: &Manifest, config_path: &Path) -> Result<ExplainReport>
         manifest: ManifestInfo {
             name: manifest.cli.name.clone(),
             version: manifest.cli.version.to_string(),
-            language: language_name(manifest.cli.language).to_string(),
+            language: manifest
+                .targets()
+                .iter()
+                .map(|t| language_name(t.language))
+                .collect::<Vec<_>>()
+                .join(", "),
         },
         phases,
         lints,
@@ -83,6 +91,7 @@ fn language_name(lang: Language) -> &'static str {
     match lang {
         Language::Rust => "Rust",
         Language::TypeScript => "TypeScript",
+        Language::Go => "Go",
     }
 }
 
@@ -92,7 +101,20 @@ fn field_type_name(field_type: &ContextFieldType) -> &'static str {
             DatabaseType::Postgres => "PostgreSQL",
             DatabaseType::Mysql => "MySQL",
             DatabaseType::Sqlite => "SQLite",
+            DatabaseType::Duckdb => "DuckDB",
         },
         ContextFieldType::Http => "HTTP client",
+        ContextFieldType::Logger => "Logger",
+        ContextFieldType::Storage => "S3 storage",
+        ContextFieldType::Grpc => "gRPC client",
+        ContextFieldType::Email => "SMTP transport",
+        ContextFieldType::Config => "Config file",
+        ContextFieldType::Secrets => "Secrets",
+        ContextFieldType::Llm => "LLM client",
+        ContextFieldType::Cache => "Cache",
+        ContextFieldType::Workdir => "Working directories",
+        ContextFieldType::Queue(QueueType::Nats) => "NATS",
+        ContextFieldType::Queue(QueueType::Kafka) => "Kafka producer",
+        ContextFieldType::Custom => "Custom",
     }
 }
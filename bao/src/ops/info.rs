@@ -26,7 +26,7 @@ pub fn info(manifest: &Manifest, config_path: &Path) -> InfoReport {
 
     InfoReport {
         name: manifest.cli.name.clone(),
-        description: manifest.cli.description.clone(),
+        description: manifest.cli.description.clone().map(Into::into),
         version: manifest.cli.version.to_string(),
         author: manifest.cli.author.clone(),
         config_path: std::fs::canonicalize(config_path)
@@ -77,6 +77,7 @@ fn collect_context(manifest: &Manifest) -> Option<ContextInfo> {
                 ("MySQL", c.env().map(String::from), c.pool().max_connections)
             }
             ContextField::Sqlite(c) => ("SQLite", c.env.clone(), c.pool.max_connections),
+            ContextField::Duckdb(c) => ("DuckDB", c.env.clone(), c.pool.max_connections),
             _ => {
                 return crate::reports::DatabaseInfo {
                     db_type: "Unknown".to_string(),
@@ -96,6 +97,23 @@ fn collect_context(manifest: &Manifest) -> Option<ContextInfo> {
                 extra.push(format!("journal: {}", mode.as_str().to_lowercase()));
             }
         }
+        if let ContextField::Duckdb(c) = db {
+            if let Some(path) = &c.path {
+                extra.push(format!("path: {}", path));
+            }
+            if c.read_only == Some(true) {
+                extra.push("read-only".to_string());
+            }
+            if let Some(threads) = c.threads {
+                extra.push(format!("threads: {}", threads));
+            }
+        }
+        if let Some(replica) = db.replica_config() {
+            extra.push(format!(
+                "replica: {}",
+                replica.env.as_deref().unwrap_or("DATABASE_REPLICA_URL")
+            ));
+        }
 
         crate::reports::DatabaseInfo {
             db_type: db_type.to_string(),
@@ -105,12 +123,17 @@ fn collect_context(manifest: &Manifest) -> Option<ContextInfo> {
         }
     });
 
-    let http = manifest.context.http.as_ref().and_then(|h| {
-        h.http_config().map(|config| crate::reports::HttpInfo {
+    let http = manifest
+        .context
+        .http_configs()
+        .into_iter()
+        .map(|(name, config)| crate::reports::HttpInfo {
+            name: name.to_string(),
+            base_url: config.base_url.clone(),
             timeout: config.timeout,
             user_agent: config.user_agent.clone(),
         })
-    });
+        .collect();
 
     Some(ContextInfo { database, http })
 }
@@ -3,12 +3,15 @@
 //! This module contains the business logic for bao commands,
 //! separated from CLI argument parsing and output rendering.
 
+pub mod archive;
 pub mod bake;
 pub mod check;
 pub mod clean;
 pub mod explain;
 pub mod info;
+pub mod version;
 
+pub use archive::Archive;
 pub use bake::bake;
 pub use check::check;
 pub use clean::clean;
@@ -0,0 +1,116 @@
+//! Version operation - bump the CLI version across project files.
+
+use std::path::Path;
+
+use baobao_core::{Version, json_value_span, write_file};
+use baobao_manifest::{BaoToml, Language, ManifestEditor};
+use eyre::Result;
+
+use crate::reports::VersionReport;
+
+/// Options for the version bump operation.
+pub struct BumpOptions<'a> {
+    /// Output directory of the generated project(s).
+    pub output_dir: &'a Path,
+    /// Whether to report changes without writing files.
+    pub dry_run: bool,
+}
+
+/// Set the CLI version in bao.toml and in each target's project manifest.
+///
+/// Rust targets get `[package] version` in Cargo.toml and TypeScript targets
+/// the `"version"` field of package.json. Go modules carry no version; it is
+/// compiled into `cmd/root.go` on the next bake.
+pub fn bump(bao_toml: &mut BaoToml, version: Version, opts: BumpOptions) -> Result<VersionReport> {
+    let old = bao_toml.schema().cli.version.clone();
+    let version_str = version.to_string();
+
+    bao_toml.edit(|editor| editor.set_value(&["cli"], "version", version_str.as_str()))?;
+    if !opts.dry_run {
+        bao_toml.save()?;
+    }
+
+    let mut updated_files = vec![bao_toml.path().display().to_string()];
+    for target in bao_toml.schema().targets() {
+        let dir = target.output_dir(opts.output_dir);
+        let (path, update): (_, fn(&str, &str) -> String) = match target.language {
+            Language::Rust => (dir.join("Cargo.toml"), set_cargo_version),
+            Language::TypeScript => (dir.join("package.json"), set_json_version),
+            Language::Go => continue,
+        };
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let updated = update(&content, &version_str);
+        if updated != content {
+            if !opts.dry_run {
+                write_file(&path, &updated)?;
+            }
+            updated_files.push(path.display().to_string());
+        }
+    }
+
+    Ok(VersionReport {
+        old: old.to_string(),
+        new: version.to_string(),
+        dry_run: opts.dry_run,
+        updated_files,
+    })
+}
+
+/// Set `[package] version` in a Cargo.toml, keeping the rest of the file as
+/// written. Files without a `[package]` table are returned unchanged.
+fn set_cargo_version(content: &str, version: &str) -> String {
+    let Ok(mut editor) = ManifestEditor::parse_with_filename(content, "Cargo.toml") else {
+        return content.to_string();
+    };
+    match editor.set_value(&["package"], "version", version) {
+        Ok(()) => editor.to_string(),
+        Err(_) => content.to_string(),
+    }
+}
+
+/// Replace the value of the top-level `"version"` key in a package.json,
+/// keeping the rest of the file as written. Files that are not a JSON object
+/// with a `"version"` are returned unchanged.
+fn set_json_version(content: &str, version: &str) -> String {
+    let has_version = serde_json::from_str::<serde_json::Value>(content)
+        .is_ok_and(|json| json.get("version").is_some());
+    match json_value_span(content, "version").filter(|_| has_version) {
+        Some(span) => format!(
+            "{}\"{}\"{}",
+            &content[..span.start],
+            version,
+            &content[span.end..]
+        ),
+        None => content.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_json_version() {
+        let content = r#"{
+  "name": "myapp",
+  "engines": {
+    "version": ">=1.0.0"
+  },
+  "version": "0.1.0",
+  "private": true
+}
+"#;
+
+        assert_eq!(
+            set_json_version(content, "0.2.0"),
+            content.replace("\"0.1.0\"", "\"0.2.0\"")
+        );
+        // Only a nested version: nothing to set
+        let nested = r#"{ "engines": { "version": ">=1.0.0" } }"#;
+        assert_eq!(set_json_version(nested, "0.2.0"), nested);
+        assert_eq!(set_json_version("not json", "0.2.0"), "not json");
+    }
+}
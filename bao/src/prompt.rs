@@ -0,0 +1,64 @@
+//! Interactive overwrite prompt for `bao bake --interactive`.
+
+use std::path::Path;
+
+use baobao_core::{LineChanges, OverwritePrompt};
+use dialoguer::{Select, theme::ColorfulTheme};
+use eyre::{Context, Result};
+use similar::TextDiff;
+
+/// Asks before overwriting each existing file whose content changed.
+///
+/// Answering "all" or "none" applies to every remaining file.
+#[derive(Debug, Default)]
+pub struct InteractivePrompt {
+    remaining: Option<bool>,
+}
+
+impl InteractivePrompt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OverwritePrompt for InteractivePrompt {
+    fn confirm(&mut self, path: &Path, existing: &str, new: &str) -> Result<bool> {
+        if let Some(answer) = self.remaining {
+            return Ok(answer);
+        }
+
+        let LineChanges { added, removed } = LineChanges::between(existing, new);
+
+        let choices = ["yes", "no", "all", "none", "show diff"];
+        loop {
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "Overwrite {} (+{} -{})?",
+                    path.display(),
+                    added,
+                    removed
+                ))
+                .items(&choices)
+                .default(0)
+                .interact()
+                .wrap_err("Failed to get overwrite confirmation")?;
+
+            match selection {
+                0 => return Ok(true),
+                1 => return Ok(false),
+                2 | 3 => {
+                    let answer = selection == 2;
+                    self.remaining = Some(answer);
+                    return Ok(answer);
+                }
+                _ => println!(
+                    "{}",
+                    TextDiff::from_lines(existing, new)
+                        .unified_diff()
+                        .context_radius(3)
+                        .header(&path.display().to_string(), "generated")
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Archive report data structures.
+
+use super::output::{Output, Report};
+
+/// Report data from generating into an archive.
+#[derive(Debug, Default)]
+pub struct ArchiveReport {
+    /// Archive path, or `None` when the archive went to stdout.
+    pub destination: Option<String>,
+    /// Warning messages from pipeline.
+    pub warnings: Vec<String>,
+    /// Archived file paths.
+    pub files: Vec<String>,
+}
+
+impl Report for ArchiveReport {
+    fn render(&self, out: &mut dyn Output) {
+        for warning in &self.warnings {
+            out.warning(warning);
+        }
+
+        // Stdout holds the archive itself
+        let Some(destination) = &self.destination else {
+            return;
+        };
+        out.key_value("Archived", destination);
+        out.key_value("Files", &self.files.len().to_string());
+    }
+}
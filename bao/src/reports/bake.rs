@@ -2,6 +2,8 @@
 
 use std::path::PathBuf;
 
+use baobao_core::{WriteResult, to_slash_path};
+
 use super::output::{Output, Report};
 
 /// Report data from code generation.
@@ -45,6 +47,10 @@ pub struct WrittenResult {
     pub output_dir: PathBuf,
     /// Generated code subdirectory (e.g., "src/generated/").
     pub gen_subdir: String,
+    /// Generated files with their write results.
+    pub files: Vec<(String, WriteResult)>,
+    /// Generated files that had been edited by hand and were overwritten.
+    pub overwritten_edits: Vec<String>,
     /// Handler file changes.
     pub handlers: HandlerChanges,
     /// Path to debug snapshots, if visualization was enabled.
@@ -60,6 +66,8 @@ pub struct HandlerChanges {
     pub orphans: Vec<String>,
     /// File extension for handlers.
     pub extension: String,
+    /// Directory holding handlers (e.g., "src/handlers/").
+    pub dir: String,
 }
 
 /// Result of a dry-run preview.
@@ -102,6 +110,12 @@ impl BakeReport {
         for warning in &self.warnings {
             out.warning(warning);
         }
+        for path in &written.overwritten_edits {
+            out.warning(&format!(
+                "{} was edited since the last bake; manual changes were overwritten",
+                path
+            ));
+        }
 
         // Print header
         out.preformatted(&format!("{} v{}", self.cli_name, self.cli_version));
@@ -118,19 +132,63 @@ impl BakeReport {
         // Print generation summary
         out.key_value(
             "Generated",
-            &format!("{}/{}", written.output_dir.display(), written.gen_subdir),
+            &format!(
+                "{}/{}",
+                to_slash_path(&written.output_dir),
+                written.gen_subdir
+            ),
         );
 
+        // Print per-file changes
+        self.render_file_changes(out, &written.files);
+
         // Print handler changes
         self.render_handler_changes(out, &written.handlers);
     }
 
+    fn render_file_changes(&self, out: &mut dyn Output, files: &[(String, WriteResult)]) {
+        let (mut created, mut updated, mut unchanged) = (0, 0, 0);
+        for (_, result) in files {
+            match result {
+                WriteResult::Created(_) => created += 1,
+                WriteResult::Updated(_) => updated += 1,
+                WriteResult::Unchanged => unchanged += 1,
+                WriteResult::Skipped => {}
+            }
+        }
+
+        out.key_value(
+            "Files",
+            &format!(
+                "{} created, {} updated, {} unchanged",
+                created, updated, unchanged
+            ),
+        );
+
+        if created + updated > 0 {
+            out.newline();
+            out.section("Changed files");
+            for (path, result) in files {
+                match result {
+                    WriteResult::Created(changes) => {
+                        out.added_item(&format!("{} (+{})", path, changes.added))
+                    }
+                    WriteResult::Updated(changes) => out.modified_item(&format!(
+                        "{} (+{} -{})",
+                        path, changes.added, changes.removed
+                    )),
+                    WriteResult::Unchanged | WriteResult::Skipped => {}
+                }
+            }
+        }
+    }
+
     fn render_handler_changes(&self, out: &mut dyn Output, handlers: &HandlerChanges) {
         if !handlers.created.is_empty() {
             out.newline();
             out.section("New handlers");
             for handler in &handlers.created {
-                out.added_item(&format!("src/handlers/{}", handler));
+                out.added_item(&format!("{}{}", handlers.dir, handler));
             }
         }
 
@@ -138,7 +196,7 @@ impl BakeReport {
             out.newline();
             out.section("Unused handlers");
             for orphan in &handlers.orphans {
-                out.removed_item(&format!("src/handlers/{}{}", orphan, handlers.extension));
+                out.removed_item(&format!("{}{}{}", handlers.dir, orphan, handlers.extension));
             }
         }
     }
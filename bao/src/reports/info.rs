@@ -43,8 +43,8 @@ pub struct Stats {
 pub struct ContextInfo {
     /// Database configuration.
     pub database: Option<DatabaseInfo>,
-    /// HTTP client configuration.
-    pub http: Option<HttpInfo>,
+    /// HTTP client configurations.
+    pub http: Vec<HttpInfo>,
 }
 
 /// Database context info.
@@ -63,6 +63,10 @@ pub struct DatabaseInfo {
 /// HTTP client context info.
 #[derive(Debug)]
 pub struct HttpInfo {
+    /// Context field name.
+    pub name: String,
+    /// Base URL.
+    pub base_url: Option<String>,
     /// Timeout in seconds.
     pub timeout: Option<u64>,
     /// User agent.
@@ -122,12 +126,15 @@ impl Report for InfoReport {
                 }
             }
 
-            if let Some(http) = &context.http {
+            for http in &context.http {
                 let timeout = http
                     .timeout
                     .map(|t| format!(" ({}s timeout)", t))
                     .unwrap_or_default();
-                out.preformatted(&format!("  http        reqwest::Client{}", timeout));
+                out.preformatted(&format!("  {:<12}reqwest::Client{}", http.name, timeout));
+                if let Some(base_url) = &http.base_url {
+                    out.preformatted(&format!("              └─ base url: {}", base_url));
+                }
                 if let Some(ua) = &http.user_agent {
                     out.preformatted(&format!("              └─ user-agent: {}", ua));
                 }
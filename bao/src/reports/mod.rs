@@ -3,13 +3,16 @@
 //! This module provides data structures that separate data collection from rendering.
 //! Commands build reports, then render them to an Output target.
 
+mod archive;
 mod bake;
 mod check;
 mod clean;
 mod explain;
 mod info;
 mod output;
+mod version;
 
+pub use archive::ArchiveReport;
 pub use bake::{
     BakeReport, GenerationResult, HandlerChanges, PreviewFile, PreviewResult, WrittenResult,
 };
@@ -19,4 +22,5 @@ pub use explain::{
     AnalysisResult, ContextFieldInfo, ExplainReport, LintInfo, ManifestInfo, PhaseInfo,
 };
 pub use info::{ContextInfo, DatabaseInfo, HttpInfo, InfoReport, Stats};
-pub use output::{Report, TerminalOutput};
+pub use output::{Output, Report, TerminalOutput};
+pub use version::VersionReport;
@@ -29,6 +29,9 @@ pub trait Output {
     /// Render a removed item (e.g., deleted file).
     fn removed_item(&mut self, text: &str);
 
+    /// Render a modified item (e.g., updated file).
+    fn modified_item(&mut self, text: &str);
+
     /// Render a warning message.
     fn warning(&mut self, msg: &str);
 
@@ -97,6 +100,10 @@ impl Output for TerminalOutput {
         println!("  - {}", text);
     }
 
+    fn modified_item(&mut self, text: &str) {
+        println!("  ~ {}", text);
+    }
+
     fn warning(&mut self, msg: &str) {
         eprintln!("warning: {}", msg);
     }
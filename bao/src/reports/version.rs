@@ -0,0 +1,37 @@
+//! Version command report data structures.
+
+use super::output::{Output, Report};
+
+/// Report data from bumping the CLI version.
+#[derive(Debug)]
+pub struct VersionReport {
+    /// Version before the bump.
+    pub old: String,
+    /// Version after the bump.
+    pub new: String,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+    /// Files whose version was updated.
+    pub updated_files: Vec<String>,
+}
+
+impl Report for VersionReport {
+    fn render(&self, out: &mut dyn Output) {
+        out.key_value("Version", &format!("{} -> {}", self.old, self.new));
+        out.newline();
+
+        if self.dry_run {
+            out.section("Would update");
+        } else {
+            out.section("Updated");
+        }
+        for path in &self.updated_files {
+            out.modified_item(path);
+        }
+
+        if !self.dry_run {
+            out.newline();
+            out.preformatted("Run `bao bake` to regenerate code with the new version.");
+        }
+    }
+}
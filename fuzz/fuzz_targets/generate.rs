@@ -0,0 +1,26 @@
+//! Every generator must handle any manifest that passes validation.
+
+#![no_main]
+
+use baobao_codegen::{
+    language::LanguageCodegen, pipeline::CompilationContext, testing::compile_roundtrip,
+};
+use baobao_manifest::Manifest;
+use libfuzzer_sys::fuzz_target;
+
+type Generator = fn(CompilationContext) -> Box<dyn LanguageCodegen>;
+
+const GENERATORS: [Generator; 3] = [
+    |ctx| Box::new(baobao_codegen_rust::Generator::from_context(ctx)),
+    |ctx| Box::new(baobao_codegen_typescript::Generator::from_context(ctx)),
+    |ctx| Box::new(baobao_codegen_go::Generator::from_context(ctx)),
+];
+
+fuzz_target!(|manifest: Manifest| {
+    for generator in GENERATORS {
+        let Some(ctx) = compile_roundtrip(&manifest) else {
+            return;
+        };
+        let _ = generator(ctx).files();
+    }
+});
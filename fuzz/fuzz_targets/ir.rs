@@ -0,0 +1,20 @@
+//! The IR's JSON form must read back as the same IR.
+
+#![no_main]
+
+use baobao_ir::AppIR;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|ir: AppIR| {
+    // JSON has no NaN or infinity: non-finite floats are written as null and
+    // read back as missing or rejected, so compare from the first read onwards
+    let Some(ir) = ir
+        .to_json()
+        .ok()
+        .and_then(|json| AppIR::from_json(&json).ok())
+    else {
+        return;
+    };
+    let json = ir.to_json().unwrap();
+    assert_eq!(AppIR::from_json(&json).unwrap(), ir, "{}", json);
+});
@@ -0,0 +1,10 @@
+//! Parsing and validating arbitrary text as bao.toml must not panic.
+
+#![no_main]
+
+use baobao_manifest::Manifest;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|src: &str| {
+    let _ = src.parse::<Manifest>();
+});